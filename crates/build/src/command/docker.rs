@@ -5,7 +5,7 @@ use cargo_metadata::camino::Utf8PathBuf;
 
 use crate::BuildArgs;
 
-use super::utils::{get_program_build_args, get_rust_compiler_flags};
+use super::utils::{get_memory_layout_env, get_program_build_args, get_rust_compiler_flags};
 
 /// Uses SP1_DOCKER_IMAGE environment variable if set, otherwise constructs the image to use based
 /// on the provided tag.
@@ -66,6 +66,7 @@ pub(crate) fn create_docker_command(
     // conflicts with the parent process. Source: https://github.com/rust-lang/cargo/issues/6412
     // 2. Set the rustup toolchain to succinct.
     // 3. Set the encoded rust flags.
+    // 4. Set the memory layout of the program.
     // Note: In Docker, you can't use the .env command to set environment variables, you have to use
     // the -e flag.
     let mut docker_args = vec![
@@ -82,12 +83,12 @@ pub(crate) fn create_docker_command(
         "-e".to_string(),
         "RUSTUP_TOOLCHAIN=succinct".to_string(),
         "-e".to_string(),
-        format!("CARGO_ENCODED_RUSTFLAGS={}", get_rust_compiler_flags()),
-        "--entrypoint".to_string(),
-        "".to_string(),
-        image,
-        "cargo".to_string(),
+        format!("CARGO_ENCODED_RUSTFLAGS={}", get_rust_compiler_flags(args)),
     ];
+    for (key, value) in get_memory_layout_env(args) {
+        docker_args.extend(["-e".to_string(), format!("{key}={value}")]);
+    }
+    docker_args.extend(["--entrypoint".to_string(), "".to_string(), image, "cargo".to_string()]);
 
     // Add the SP1 program build arguments.
     docker_args.extend_from_slice(&get_program_build_args(args));
@@ -4,7 +4,7 @@ use crate::{BuildArgs, HELPER_TARGET_SUBDIR};
 use cargo_metadata::camino::Utf8PathBuf;
 use dirs::home_dir;
 
-use super::utils::{get_program_build_args, get_rust_compiler_flags};
+use super::utils::{get_memory_layout_env, get_program_build_args, get_rust_compiler_flags};
 
 /// Get the command to build the program locally.
 pub(crate) fn create_local_command(
@@ -33,13 +33,15 @@ pub(crate) fn create_local_command(
     // conflicts with the parent process. Source: https://github.com/rust-lang/cargo/issues/6412
     // 2. Set the rustup toolchain to succinct.
     // 3. Set the encoded rust flags.
-    // 4. Remove the rustc configuration, otherwise in a build script it will attempt to compile the
+    // 4. Set the memory layout of the program.
+    // 5. Remove the rustc configuration, otherwise in a build script it will attempt to compile the
     //    program with the toolchain of the normal build process, rather than the Succinct
     //    toolchain.
     command
         .current_dir(canonicalized_program_dir)
         .env("RUSTUP_TOOLCHAIN", "succinct")
-        .env("CARGO_ENCODED_RUSTFLAGS", get_rust_compiler_flags())
+        .env("CARGO_ENCODED_RUSTFLAGS", get_rust_compiler_flags(args))
+        .envs(get_memory_layout_env(args))
         .env_remove("RUSTC")
         .env("CARGO_TARGET_DIR", program_metadata.target_directory.join(HELPER_TARGET_SUBDIR))
         .args(&get_program_build_args(args));
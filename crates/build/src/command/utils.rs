@@ -41,16 +41,65 @@ pub(crate) fn get_program_build_args(args: &BuildArgs) -> Vec<String> {
     build_args
 }
 
+/// The end of the memory of a program, as addresses must be lower than the BabyBear prime.
+const MAX_MEMORY: u32 = 0x7800_0000;
+
+/// The default top of the stack of a program.
+const DEFAULT_STACK_TOP: u32 = 0x0020_0400;
+
+/// The gap between the top of the stack and the text of a program.
+const STACK_TEXT_GAP: u32 = 0x400;
+
+/// The top of the stack of the program, which grows down from just below its text.
+fn get_stack_top(args: &BuildArgs) -> u32 {
+    args.stack_size.map_or(DEFAULT_STACK_TOP, |size| size.next_multiple_of(16))
+}
+
+/// Get the environment variables read by `sp1-zkvm` to lay out the memory of the program, see
+/// `sp1_zkvm::layout`.
+///
+/// # Panics
+///
+/// Panics if the layout doesn't fit in the memory of a program.
+pub(crate) fn get_memory_layout_env(args: &BuildArgs) -> Vec<(&'static str, String)> {
+    let max_memory = args.max_memory.unwrap_or(MAX_MEMORY);
+    assert!(max_memory <= MAX_MEMORY, "--max-memory exceeds 0x{MAX_MEMORY:08x}");
+    assert!(
+        get_stack_top(args) + STACK_TEXT_GAP < max_memory,
+        "--stack-size doesn't fit in --max-memory"
+    );
+    if let Some(heap_start) = args.heap_start {
+        assert!(heap_start < max_memory, "--heap-start is past --max-memory");
+    }
+
+    let mut env = Vec::new();
+    if let Some(max_memory) = args.max_memory {
+        env.push(("SP1_MAX_MEMORY", max_memory.to_string()));
+    }
+    if args.stack_size.is_some() {
+        env.push(("SP1_STACK_TOP", get_stack_top(args).to_string()));
+    }
+    if let Some(heap_start) = args.heap_start {
+        env.push(("SP1_HEAP_START", heap_start.to_string()));
+    }
+    env
+}
+
 /// Rust flags for compilation of C libraries.
-pub(crate) fn get_rust_compiler_flags() -> String {
-    let rust_flags = [
+pub(crate) fn get_rust_compiler_flags(args: &BuildArgs) -> String {
+    let text_start = get_stack_top(args) + STACK_TEXT_GAP;
+    let mut rust_flags = vec![
         "-C".to_string(),
         "passes=loweratomic".to_string(),
         "-C".to_string(),
-        "link-arg=-Ttext=0x00200800".to_string(),
+        format!("link-arg=-Ttext=0x{text_start:08x}"),
         "-C".to_string(),
         "panic=abort".to_string(),
     ];
+    if args.bitmanip {
+        rust_flags.push("-C".to_string());
+        rust_flags.push("target-feature=+zba,+zbb,+zbs".to_string());
+    }
     rust_flags.join("\x1f")
 }
 
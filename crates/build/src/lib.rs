@@ -61,6 +61,21 @@ pub struct BuildArgs {
         default_value = DEFAULT_OUTPUT_DIR
     )]
     pub output_directory: String,
+    #[clap(
+        long,
+        action,
+        help = "Compile the program with the Zba, Zbb and Zbs bit manipulation extensions"
+    )]
+    pub bitmanip: bool,
+    #[clap(
+        long,
+        help = "End of the memory of the program in bytes, at most 2013265920 (0x78000000)"
+    )]
+    pub max_memory: Option<u32>,
+    #[clap(long, help = "Size of the stack of the program in bytes")]
+    pub stack_size: Option<u32>,
+    #[clap(long, help = "Lowest address of the heap of the program")]
+    pub heap_start: Option<u32>,
 }
 
 // Implement default args to match clap defaults.
@@ -76,6 +91,10 @@ impl Default for BuildArgs {
             output_directory: DEFAULT_OUTPUT_DIR.to_string(),
             locked: false,
             no_default_features: false,
+            bitmanip: false,
+            max_memory: None,
+            stack_size: None,
+            heap_start: None,
         }
     }
 }
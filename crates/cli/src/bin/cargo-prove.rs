@@ -2,9 +2,9 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 use sp1_cli::{
     commands::{
-        build::BuildCmd, build_toolchain::BuildToolchainCmd,
-        install_toolchain::InstallToolchainCmd, new::NewCmd, prove::ProveCmd, trace::TraceCmd,
-        vkey::VkeyCmd,
+        build::BuildCmd, build_toolchain::BuildToolchainCmd, install_circuits::InstallCircuitsCmd,
+        install_toolchain::InstallToolchainCmd, new::NewCmd, prove::ProveCmd, prune::PruneCmd,
+        trace::TraceCmd, vkey::VkeyCmd,
     },
     SP1_VERSION_MESSAGE,
 };
@@ -32,8 +32,10 @@ pub enum ProveCliCommands {
     Prove(ProveCmd),
     BuildToolchain(BuildToolchainCmd),
     InstallToolchain(InstallToolchainCmd),
+    InstallCircuits(InstallCircuitsCmd),
     Trace(TraceCmd),
     Vkey(VkeyCmd),
+    Prune(PruneCmd),
 }
 
 fn main() -> Result<()> {
@@ -45,7 +47,9 @@ fn main() -> Result<()> {
         ProveCliCommands::Prove(cmd) => cmd.run(),
         ProveCliCommands::BuildToolchain(cmd) => cmd.run(),
         ProveCliCommands::InstallToolchain(cmd) => cmd.run(),
+        ProveCliCommands::InstallCircuits(cmd) => cmd.run(),
         ProveCliCommands::Trace(cmd) => cmd.run(),
         ProveCliCommands::Vkey(cmd) => cmd.run(),
+        ProveCliCommands::Prune(cmd) => cmd.run(),
     }
 }
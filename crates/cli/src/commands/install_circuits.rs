@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use sp1_sdk::install::{
+    install_circuit_artifacts_from_file_with, install_circuit_artifacts_with, repair_installation,
+    ArtifactsType, InstallConfig,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "install-circuits",
+    about = "Install the circuit artifacts used for Plonk and Groth16 proofs."
+)]
+pub struct InstallCircuitsCmd {
+    /// The artifacts to download: `plonk`, `groth16` or `all`.
+    #[arg(long, default_value = "all")]
+    kind: ArtifactsType,
+
+    /// Install from a local artifacts tarball instead of downloading it.
+    #[arg(long)]
+    from_file: Option<PathBuf>,
+
+    /// The root directory to install the artifacts under. Defaults to `SP1_CIRCUITS_DIR` or
+    /// `~/.sp1/circuits`.
+    #[arg(long)]
+    circuits_dir: Option<PathBuf>,
+
+    /// The proxy to download the artifacts through. Defaults to `HTTPS_PROXY` or `HTTP_PROXY`.
+    #[arg(long)]
+    proxy: Option<String>,
+
+    /// Install the artifacts even if they come without a checksum manifest to check them against.
+    #[arg(long)]
+    allow_unverified: bool,
+
+    /// Don't print anything while installing.
+    #[arg(long)]
+    quiet: bool,
+
+    /// Check the installed artifacts against their manifest, and download them again if any file
+    /// is missing or corrupt.
+    #[arg(long, conflicts_with = "from_file")]
+    verify: bool,
+}
+
+impl InstallCircuitsCmd {
+    pub fn run(&self) -> Result<()> {
+        let mut config = InstallConfig::from_env();
+        if let Some(circuits_dir) = &self.circuits_dir {
+            config.circuits_dir.clone_from(circuits_dir);
+        }
+        config.proxy.clone_from(&self.proxy);
+        if self.quiet {
+            config = config.quiet();
+        }
+        config.allow_unverified = self.allow_unverified;
+
+        if self.verify {
+            let report = repair_installation(&config, self.kind)?;
+            if report.is_ok() {
+                println!(
+                    "[sp1] {} artifacts in {} are intact",
+                    self.kind,
+                    config.build_dir().display()
+                );
+            } else {
+                for file in &report.missing {
+                    println!("[sp1] repaired missing file {}", file);
+                }
+                for file in &report.corrupt {
+                    println!("[sp1] repaired corrupt file {}", file);
+                }
+            }
+            return Ok(());
+        }
+
+        match &self.from_file {
+            Some(path) => {
+                install_circuit_artifacts_from_file_with(&config, path)?;
+            }
+            None => {
+                install_circuit_artifacts_with(&config, self.kind)?;
+            }
+        }
+
+        Ok(())
+    }
+}
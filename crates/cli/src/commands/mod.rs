@@ -1,7 +1,9 @@
 pub mod build;
 pub mod build_toolchain;
+pub mod install_circuits;
 pub mod install_toolchain;
 pub mod new;
 pub mod prove;
+pub mod prune;
 pub mod trace;
 pub mod vkey;
@@ -0,0 +1,58 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::Parser;
+use indicatif::HumanBytes;
+use sp1_sdk::{
+    install::{circuits_dir, installed_artifacts, prune_old_artifacts_in},
+    SP1_CIRCUIT_VERSION,
+};
+
+#[derive(Parser)]
+#[command(name = "prune", about = "Delete the circuit artifacts of old SP1 versions.")]
+pub struct PruneCmd {
+    /// Versions to keep in addition to the current one.
+    #[arg(long, value_delimiter = ',')]
+    keep: Vec<String>,
+
+    /// Only list the installed versions without deleting anything.
+    #[arg(long)]
+    dry_run: bool,
+
+    /// The root directory the artifacts are installed under. Defaults to `SP1_CIRCUITS_DIR` or
+    /// `~/.sp1/circuits`.
+    #[arg(long)]
+    circuits_dir: Option<PathBuf>,
+}
+
+impl PruneCmd {
+    pub fn run(&self) -> Result<()> {
+        let circuits_dir = self.circuits_dir.clone().unwrap_or_else(circuits_dir);
+        let installed = installed_artifacts(&circuits_dir)?;
+        if installed.is_empty() {
+            println!("No circuit artifacts installed at {}", circuits_dir.display());
+            return Ok(());
+        }
+
+        let keep = self.keep.iter().map(String::as_str).collect::<Vec<_>>();
+        println!("Circuit artifacts installed at {}:", circuits_dir.display());
+        for artifacts in &installed {
+            let kept = artifacts.version == SP1_CIRCUIT_VERSION
+                || keep.contains(&artifacts.version.as_str());
+            println!(
+                "  {:<16} {:>12}  {}",
+                artifacts.version,
+                HumanBytes(artifacts.size).to_string(),
+                if kept { "keep" } else { "delete" }
+            );
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let pruned = prune_old_artifacts_in(&circuits_dir, &keep)?;
+        let freed: u64 = pruned.iter().map(|artifacts| artifacts.size).sum();
+        println!("Deleted {} version(s), freed {}", pruned.len(), HumanBytes(freed));
+        Ok(())
+    }
+}
@@ -1,13 +1,26 @@
 use core::mem::take;
-use std::sync::Arc;
+use std::{
+    collections::BTreeMap,
+    io::Write,
+    path::PathBuf,
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
 
 use hashbrown::HashMap;
 
 use crate::{
-    hook::{hookify, BoxedHook, HookEnv, HookRegistry},
+    hook::{
+        hook_fd, hookify, BoxedHook, FallibleHook, HookEnv, HookError, HookRegistrationError,
+        HookRegistry,
+    },
     subproof::SubproofVerifier,
+    CancellationToken, ProgressCallback, ProofEvent, ReplayLog, TraceExportOpts,
 };
 
+/// A writer the standard output or error of a program is sent to.
+pub type OutputWriter<'a> = Arc<Mutex<dyn Write + Send + 'a>>;
+
 /// Context to run a program inside SP1.
 #[derive(Clone, Default)]
 pub struct SP1Context<'a> {
@@ -21,15 +34,92 @@ pub struct SP1Context<'a> {
 
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
+
+    /// The size of the guest memory, bounding the addresses of loads and stores.
+    ///
+    /// Note: `None` denotes the largest memory, up to the end of
+    /// [`VALID_MEMORY_ADDRESSES`](crate::VALID_MEMORY_ADDRESSES).
+    pub max_memory: Option<u32>,
+
+    /// The wall-clock time the execution may run for.
+    pub max_duration: Option<Duration>,
+
+    /// Whether loads of words that were never initialized fail, rather than reading zero.
+    pub strict_memory: bool,
+
+    /// The token to cancel the execution with.
+    pub cancellation_token: Option<CancellationToken>,
+
+    /// The callback receiving the progress of proof generation.
+    pub progress: Option<ProgressCallback<'a>>,
+
+    /// The directory the shard proofs are persisted to, so that an interrupted proof resumes
+    /// without proving its shards again.
+    pub shard_proofs_dir: Option<PathBuf>,
+
+    /// The directory the checkpoints of the execution are streamed to, rather than to temporary
+    /// files.
+    pub checkpoints_dir: Option<PathBuf>,
+
+    /// The writer the standard output of the program is sent to.
+    ///
+    /// Note: `None` denotes printing it to the standard output of the host.
+    pub stdout: Option<OutputWriter<'a>>,
+
+    /// The writer the standard error of the program is sent to.
+    ///
+    /// Note: `None` denotes printing it to the standard output of the host.
+    pub stderr: Option<OutputWriter<'a>>,
+
+    /// The writer the instruction-level trace of the execution is written to, with its options.
+    pub trace_export: Option<(OutputWriter<'a>, TraceExportOpts)>,
+
+    /// Whether to attribute the cycles of the execution to the functions of the program, in
+    /// [`ExecutionReport::function_cycles`](crate::ExecutionReport::function_cycles).
+    pub function_profile: bool,
+
+    /// Whether to count the runs of the instruction at each program counter, in
+    /// [`ExecutionReport::pc_counts`](crate::ExecutionReport::pc_counts).
+    pub pc_histogram: bool,
+
+    /// The writer the [`ReplayLog`] of the execution is written to.
+    pub record_replay: Option<OutputWriter<'a>>,
+
+    /// The log the execution is replayed from, in place of its inputs and of its hooks.
+    pub replay: Option<ReplayLog>,
+
+    /// The arguments of the program, returned by `std::env::args` inside SP1.
+    pub args: Vec<String>,
+
+    /// The environment variables of the program, returned by `std::env::var` inside SP1.
+    pub env: BTreeMap<String, String>,
 }
 
 /// A builder for [`SP1Context`].
 #[derive(Clone, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct SP1ContextBuilder<'a> {
     no_default_hooks: bool,
     hook_registry_entries: Vec<(u32, BoxedHook<'a>)>,
+    hook_names: HashMap<u32, String>,
     subproof_verifier: Option<Arc<dyn SubproofVerifier + 'a>>,
     max_cycles: Option<u64>,
+    max_memory: Option<u32>,
+    max_duration: Option<Duration>,
+    strict_memory: bool,
+    cancellation_token: Option<CancellationToken>,
+    progress: Option<ProgressCallback<'a>>,
+    shard_proofs_dir: Option<PathBuf>,
+    checkpoints_dir: Option<PathBuf>,
+    stdout: Option<OutputWriter<'a>>,
+    stderr: Option<OutputWriter<'a>>,
+    trace_export: Option<(OutputWriter<'a>, TraceExportOpts)>,
+    function_profile: bool,
+    pc_histogram: bool,
+    record_replay: Option<OutputWriter<'a>>,
+    replay: Option<ReplayLog>,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
 }
 
 impl<'a> SP1Context<'a> {
@@ -66,9 +156,46 @@ impl<'a> SP1ContextBuilder<'a> {
                 table.extend(take(&mut self.hook_registry_entries));
                 HookRegistry { table }
             });
+        self.hook_names.clear();
         let subproof_verifier = take(&mut self.subproof_verifier);
         let cycle_limit = take(&mut self.max_cycles);
-        SP1Context { hook_registry, subproof_verifier, max_cycles: cycle_limit }
+        let max_memory = take(&mut self.max_memory);
+        let max_duration = take(&mut self.max_duration);
+        let strict_memory = take(&mut self.strict_memory);
+        let cancellation_token = take(&mut self.cancellation_token);
+        let progress = take(&mut self.progress);
+        let shard_proofs_dir = take(&mut self.shard_proofs_dir);
+        let checkpoints_dir = take(&mut self.checkpoints_dir);
+        let stdout = take(&mut self.stdout);
+        let stderr = take(&mut self.stderr);
+        let trace_export = take(&mut self.trace_export);
+        let function_profile = take(&mut self.function_profile);
+        let pc_histogram = take(&mut self.pc_histogram);
+        let record_replay = take(&mut self.record_replay);
+        let replay = take(&mut self.replay);
+        let args = take(&mut self.args);
+        let env = take(&mut self.env);
+        SP1Context {
+            hook_registry,
+            subproof_verifier,
+            max_cycles: cycle_limit,
+            max_memory,
+            max_duration,
+            strict_memory,
+            cancellation_token,
+            progress,
+            shard_proofs_dir,
+            checkpoints_dir,
+            stdout,
+            stderr,
+            trace_export,
+            function_profile,
+            pc_histogram,
+            record_replay,
+            replay,
+            args,
+            env,
+        }
     }
 
     /// Add a runtime [Hook](super::Hook) into the context.
@@ -85,6 +212,63 @@ impl<'a> SP1ContextBuilder<'a> {
         self
     }
 
+    /// Add a runtime hook into the context under `name`, accessed through the file descriptor
+    /// [`hook_fd`]`(name)`.
+    ///
+    /// Programs invoke the hook by writing to `sp1_zkvm::io::hook_fd(name)`, and read the
+    /// returned data with successive calls to [`sp1_zkvm::io::read`]. If the hook returns an
+    /// error, the execution fails with [`crate::ExecutionError::HookFailed`], which is retryable
+    /// if the error is a [`HookError::retryable`].
+    ///
+    /// Namespacing the name, as in `"my-app/fetch-block"`, avoids clashing with the hooks of
+    /// other libraries.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a hook is already registered under `name`, or under another name mapping to the
+    /// same file descriptor.
+    pub fn named_hook<E: Into<HookError>>(
+        &mut self,
+        name: &str,
+        f: impl FnMut(HookEnv, &[u8]) -> Result<Vec<Vec<u8>>, E> + Send + Sync + 'a,
+    ) -> Result<&mut Self, HookRegistrationError> {
+        let fd = hook_fd(name);
+        if let Some(existing) = self.hook_names.get(&fd) {
+            return Err(if existing == name {
+                HookRegistrationError::DuplicateName(name.to_string())
+            } else {
+                HookRegistrationError::Collision {
+                    name: name.to_string(),
+                    existing: existing.clone(),
+                    fd,
+                }
+            });
+        }
+        self.hook_names.insert(fd, name.to_string());
+        self.hook_registry_entries.push((fd, Arc::new(RwLock::new(FallibleHook(f)))));
+        Ok(self)
+    }
+
+    /// Add a handler of the requests programs send with `sp1_zkvm::io::request(name, request)`,
+    /// returning the response they receive.
+    ///
+    /// The handler is a [`Self::named_hook`] with a single response, invoked when the request is
+    /// sent, so that it can compute data depending on the execution so far. The responses are
+    /// recorded in the checkpoints of the execution, so that re-executing from them when proving
+    /// doesn't invoke the handler again, and with [`Self::record_replay`].
+    ///
+    /// # Errors
+    ///
+    /// Fails if a hook is already registered under `name`, or under another name mapping to the
+    /// same file descriptor.
+    pub fn request_handler<E: Into<HookError>>(
+        &mut self,
+        name: &str,
+        mut f: impl FnMut(&[u8]) -> Result<Vec<u8>, E> + Send + Sync + 'a,
+    ) -> Result<&mut Self, HookRegistrationError> {
+        self.named_hook(name, move |_, request| f(request).map(|response| vec![response]))
+    }
+
     /// Avoid registering the default hooks in the runtime.
     ///
     /// It is not necessary to call this to override hooks --- instead, simply
@@ -106,25 +290,234 @@ impl<'a> SP1ContextBuilder<'a> {
     }
 
     /// Set the maximum number of cpu cycles to use for execution.
+    ///
+    /// The execution halts with [`crate::ExecutionError::CycleLimitExceeded`] once it runs that
+    /// many cycles without the program ending.
     pub fn max_cycles(&mut self, max_cycles: u64) -> &mut Self {
         self.max_cycles = Some(max_cycles);
         self
     }
+
+    /// Set the size of the guest memory, for programs built with a matching `max_memory` in their
+    /// build options.
+    ///
+    /// Loads and stores past it fail with [`crate::ExecutionError::InvalidMemoryAccess`]. It must
+    /// fit the program and be at most the end of
+    /// [`VALID_MEMORY_ADDRESSES`](crate::VALID_MEMORY_ADDRESSES), which is the default.
+    pub fn max_memory(&mut self, max_memory: u32) -> &mut Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Set the wall-clock time the execution may run for, counted from the creation of the
+    /// executor.
+    ///
+    /// The execution halts with [`crate::ExecutionError::TimedOut`] once it runs that long,
+    /// independently of the cycle limit and of shard boundaries, so that programs looping without
+    /// syscalls are interrupted too. The time is checked every few thousand cycles, and not during
+    /// syscalls or hooks.
+    pub fn max_duration(&mut self, max_duration: Duration) -> &mut Self {
+        self.max_duration = Some(max_duration);
+        self
+    }
+
+    /// Fail the loads of words that were never initialized, rather than reading zero.
+    ///
+    /// The words of the program image, those written by the program or by syscalls, and those
+    /// hints are read into count as initialized. A load of any other word fails with
+    /// [`crate::ExecutionError::InvalidMemoryAccess`] of kind
+    /// [`crate::MemoryFaultKind::Uninitialized`], reporting its pc and address. Initialization is
+    /// tracked per word, so a byte load next to a byte written in the same word succeeds.
+    pub fn strict_memory(&mut self) -> &mut Self {
+        self.strict_memory = true;
+        self
+    }
+
+    /// Set the token to cancel the execution with.
+    ///
+    /// The execution stops with [`crate::ExecutionError::Cancelled`] within a few thousand cycles
+    /// once the token is cancelled.
+    pub fn cancellation_token(&mut self, token: CancellationToken) -> &mut Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Set the callback receiving the progress of proof generation.
+    ///
+    /// The callback is invoked as the proof moves through its stages and as shards and
+    /// recursive proofs are generated, possibly from several threads at once.
+    pub fn progress(&mut self, f: impl Fn(&ProofEvent) + Send + Sync + 'a) -> &mut Self {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Set the directory the shard proofs are persisted to.
+    ///
+    /// Every shard proof is written to the directory as soon as it is generated, and shards
+    /// whose proof is already in the directory aren't proven again. The directory must only be
+    /// used to prove one program on one input: the proofs in it aren't checked against the
+    /// execution.
+    pub fn shard_proofs_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.shard_proofs_dir = Some(dir.into());
+        self
+    }
+
+    /// Set the directory the checkpoints of the execution are streamed to.
+    ///
+    /// Every checkpoint is written to a file in the directory, along with an index of them, as
+    /// soon as the execution reaches it. This keeps the memory used by very long executions
+    /// bounded even where temporary files are kept in memory, and lets other processes read
+    /// the checkpoints as they are written, see `sp1_core_machine::utils::CheckpointStore`.
+    pub fn checkpoints_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.checkpoints_dir = Some(dir.into());
+        self
+    }
+
+    /// Send the standard output of the program, file descriptor 1, to `writer`.
+    ///
+    /// The bytes are written as the program writes them, without the `stdout: ` prefix and line
+    /// buffering used when printing to the host. Cycle tracker commands aren't written.
+    pub fn stdout(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.stdout = Some(Arc::new(Mutex::new(writer)));
+        self
+    }
+
+    /// Send the standard error of the program, file descriptor 2, to `writer`.
+    ///
+    /// The bytes are written as the program writes them, without the `stderr: ` prefix and line
+    /// buffering used when printing to the host.
+    pub fn stderr(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.stderr = Some(Arc::new(Mutex::new(writer)));
+        self
+    }
+
+    /// Write the instruction-level trace of the execution to `writer`, with the format, columns
+    /// and sampling of `opts`.
+    ///
+    /// Every instruction executed outside of unconstrained blocks is written, unless sampled
+    /// out. Tracing slows the execution down considerably, as every instruction is serialized.
+    pub fn trace_export(
+        &mut self,
+        writer: impl Write + Send + 'a,
+        opts: TraceExportOpts,
+    ) -> &mut Self {
+        self.trace_export = Some((Arc::new(Mutex::new(writer)), opts));
+        self
+    }
+
+    /// Attribute the cycles of the execution to the functions of the program, using the symbol
+    /// table of its ELF.
+    ///
+    /// The cycles of each function are reported in
+    /// [`ExecutionReport::function_cycles`](crate::ExecutionReport::function_cycles), ranked by
+    /// [`ExecutionReport::function_profile`](crate::ExecutionReport::function_profile), without
+    /// instrumenting the program. Every instruction is attributed, which slows down the execution.
+    pub fn function_profile(&mut self) -> &mut Self {
+        self.function_profile = true;
+        self
+    }
+
+    /// Count the runs of the instruction at each program counter, to find where the cycles of the
+    /// execution go without tracing it.
+    ///
+    /// The histogram is reported in
+    /// [`ExecutionReport::pc_counts`](crate::ExecutionReport::pc_counts), and grouped into the
+    /// hottest basic blocks of the program by
+    /// [`ExecutionReport::hot_blocks`](crate::ExecutionReport::hot_blocks), which are also logged
+    /// at the end of the execution.
+    pub fn pc_histogram(&mut self) -> &mut Self {
+        self.pc_histogram = true;
+        self
+    }
+
+    /// Record the inputs of the execution and the responses of its hooks to `writer`, to replay
+    /// it deterministically with [`Self::replay`].
+    ///
+    /// The log is written as the program runs, so that it can be read with [`ReplayLog::read`]
+    /// even if the execution fails.
+    pub fn record_replay(&mut self, writer: impl Write + Send + 'a) -> &mut Self {
+        self.record_replay = Some(Arc::new(Mutex::new(writer)));
+        self
+    }
+
+    /// Execute the program again as recorded in `log` by [`Self::record_replay`].
+    ///
+    /// The stdin, arguments and environment variables of the log replace those given to the
+    /// execution, and the hooks return their recorded responses, in order, without being invoked.
+    /// The hooks must still be registered as when recording, so that their file descriptors are
+    /// known. If the program invokes other hooks than recorded, the execution fails with
+    /// [`crate::ExecutionError::HookFailed`].
+    pub fn replay(&mut self, log: ReplayLog) -> &mut Self {
+        self.replay = Some(log);
+        self
+    }
+
+    /// Set the arguments of the program, returned by `std::env::args` inside SP1. By convention,
+    /// the first one is the name of the program.
+    ///
+    /// Like the standard input, the arguments aren't part of the proof, so a program must commit
+    /// those its public values depend on.
+    pub fn args<S: Into<String>>(&mut self, args: impl IntoIterator<Item = S>) -> &mut Self {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the environment variable `key` of the program to `value`, returned by `std::env::var`
+    /// inside SP1. Programs can't list the environment variables, only read them by name.
+    pub fn env(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.env.insert(key.into(), value.into());
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
 
-    use crate::{subproof::DefaultSubproofVerifier, SP1Context};
+    use crate::{hook_fd, subproof::DefaultSubproofVerifier, HookRegistrationError, SP1Context};
 
     #[test]
     fn defaults() {
-        let SP1Context { hook_registry, subproof_verifier, max_cycles: cycle_limit } =
-            SP1Context::builder().build();
+        let SP1Context {
+            hook_registry,
+            subproof_verifier,
+            max_cycles: cycle_limit,
+            max_memory,
+            max_duration,
+            strict_memory,
+            cancellation_token,
+            progress,
+            shard_proofs_dir,
+            checkpoints_dir,
+            stdout,
+            stderr,
+            trace_export,
+            function_profile,
+            pc_histogram,
+            record_replay,
+            replay,
+            args,
+            env,
+        } = SP1Context::builder().build();
         assert!(hook_registry.is_none());
         assert!(subproof_verifier.is_none());
         assert!(cycle_limit.is_none());
+        assert!(max_memory.is_none());
+        assert!(max_duration.is_none());
+        assert!(!strict_memory);
+        assert!(cancellation_token.is_none());
+        assert!(progress.is_none());
+        assert!(shard_proofs_dir.is_none());
+        assert!(checkpoints_dir.is_none());
+        assert!(stdout.is_none());
+        assert!(stderr.is_none());
+        assert!(trace_export.is_none());
+        assert!(!function_profile);
+        assert!(!pc_histogram);
+        assert!(record_replay.is_none());
+        assert!(replay.is_none());
+        assert!(args.is_empty());
+        assert!(env.is_empty());
     }
 
     #[test]
@@ -141,6 +534,18 @@ mod tests {
         assert!(hook_registry.unwrap().table.contains_key(&30));
     }
 
+    #[test]
+    fn with_named_hook() {
+        let mut builder = SP1Context::builder();
+        builder.named_hook("test/a", |_, _| Ok::<_, String>(vec![])).unwrap();
+        assert_eq!(
+            builder.named_hook("test/a", |_, _| Ok::<_, String>(vec![])).err(),
+            Some(HookRegistrationError::DuplicateName("test/a".to_string()))
+        );
+        let SP1Context { hook_registry, .. } = builder.build();
+        assert!(hook_registry.unwrap().table.contains_key(&hook_fd("test/a")));
+    }
+
     #[test]
     fn without_default_hooks_with_custom_hook() {
         let SP1Context { hook_registry, .. } =
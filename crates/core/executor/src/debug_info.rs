@@ -0,0 +1,107 @@
+use std::{
+    fmt::{self, Debug, Display, Formatter},
+    sync::Arc,
+};
+
+use addr2line::{object, Context};
+use elf::{endian::LittleEndian, ElfBytes};
+use serde::{Deserialize, Serialize};
+
+/// The DWARF debug information of a program, used to symbolize its program counters.
+///
+/// It is only kept when the ELF of the program has a `.debug_info` section, e.g. when the program
+/// was built with `debug = true` in its profile, and is parsed on demand, so that it costs nothing
+/// until a fault is reported.
+#[derive(Clone)]
+pub struct DebugInfo {
+    elf: Arc<[u8]>,
+}
+
+/// A frame of a backtrace, innermost first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+    /// The program counter of the frame: the faulting instruction for the innermost frame, and
+    /// the call instruction for the others.
+    pub pc: u32,
+    /// The demangled name of the function, if known.
+    pub function: Option<String>,
+    /// The source file of the instruction, if known.
+    pub file: Option<String>,
+    /// The source line of the instruction, if known.
+    pub line: Option<u32>,
+}
+
+impl DebugInfo {
+    /// The debug information of `elf`, or `None` if it has none.
+    #[must_use]
+    pub fn new(elf: &[u8]) -> Option<Self> {
+        let file = ElfBytes::<LittleEndian>::minimal_parse(elf).ok()?;
+        file.section_header_by_name(".debug_info").ok()??;
+        Some(Self { elf: elf.into() })
+    }
+
+    /// Symbolizes `pcs`, with one frame per pc and per function inlined at it.
+    ///
+    /// The pcs are returned unsymbolized if the debug information can't be parsed.
+    #[must_use]
+    pub fn symbolize(&self, pcs: &[u32]) -> Vec<Frame> {
+        self.try_symbolize(pcs).unwrap_or_else(|| pcs.iter().copied().map(Frame::new).collect())
+    }
+
+    fn try_symbolize(&self, pcs: &[u32]) -> Option<Vec<Frame>> {
+        let file = object::File::parse(&*self.elf).ok()?;
+        let context = Context::new(&file).ok()?;
+        let mut frames = Vec::new();
+        for &pc in pcs {
+            let start = frames.len();
+            let mut iter = context.find_frames(u64::from(pc)).skip_all_loads().ok()?;
+            while let Some(frame) = iter.next().ok()? {
+                let location = frame.location.as_ref();
+                frames.push(Frame {
+                    pc,
+                    function: frame
+                        .function
+                        .as_ref()
+                        .and_then(|function| function.demangle().ok())
+                        .map(std::borrow::Cow::into_owned),
+                    file: location.and_then(|location| location.file).map(str::to_string),
+                    line: location.and_then(|location| location.line),
+                });
+            }
+            if frames.len() == start {
+                frames.push(Frame::new(pc));
+            }
+        }
+        Some(frames)
+    }
+}
+
+impl Debug for DebugInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DebugInfo").field("elf_len", &self.elf.len()).finish()
+    }
+}
+
+impl Frame {
+    /// An unsymbolized frame at `pc`.
+    #[must_use]
+    pub const fn new(pc: u32) -> Self {
+        Self { pc, function: None, file: None, line: None }
+    }
+}
+
+impl Display for Frame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{:08x}", self.pc)?;
+        if let Some(function) = &self.function {
+            write!(f, " in {function}")?;
+        }
+        if let Some(file) = &self.file {
+            write!(f, " at {file}")?;
+            if let Some(line) = self.line {
+                write!(f, ":{line}")?;
+            }
+        }
+        Ok(())
+    }
+}
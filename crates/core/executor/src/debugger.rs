@@ -0,0 +1,270 @@
+use std::ops::Range;
+
+use crate::{ExecutionError, Executor, ExecutorMode, Instruction, Register};
+
+/// The identifier of a breakpoint of a [`Debugger`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BreakpointId(u32);
+
+/// A condition the execution of a [`Debugger`] stops on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Breakpoint {
+    /// Stops before executing an instruction whose program counter is in the range.
+    Pc(Range<u32>),
+    /// Stops after an instruction read or wrote the word at the address, which is rounded down
+    /// to a multiple of 4. The addresses below 32 are the registers.
+    Memory(u32),
+}
+
+impl Breakpoint {
+    /// A breakpoint on the instruction at `pc`.
+    #[must_use]
+    pub fn pc(pc: u32) -> Self {
+        Self::Pc(pc..pc.saturating_add(1))
+    }
+
+    /// The breakpoint with its address rounded down to a multiple of 4.
+    fn aligned(self) -> Self {
+        match self {
+            Self::Memory(addr) => Self::Memory(addr & !3),
+            breakpoint @ Self::Pc(_) => breakpoint,
+        }
+    }
+}
+
+/// Why the execution of a [`Debugger`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// A single instruction was executed.
+    Step,
+    /// The next instruction is at a [`Breakpoint::Pc`].
+    Breakpoint(BreakpointId),
+    /// The last instruction accessed the address of a [`Breakpoint::Memory`].
+    MemoryAccess {
+        /// The breakpoint on the address.
+        breakpoint: BreakpointId,
+        /// The address of the word accessed.
+        addr: u32,
+        /// Whether the word was written.
+        write: bool,
+    },
+    /// The program finished.
+    Finished,
+}
+
+/// Executes a program one instruction at a time, stopping on breakpoints, so that its registers
+/// and memory can be inspected in between. It is meant as a base for debugger frontends.
+///
+/// The execution isn't traced, as with [`Executor::run_fast`], and its errors are returned from
+/// [`Self::step`] and [`Self::resume`].
+///
+/// ### Examples
+/// ```ignore
+/// let mut debugger = Debugger::new(Executor::new(program, SP1CoreOpts::default()));
+/// debugger.add_breakpoint(Breakpoint::pc(0x2010_0000));
+/// while debugger.resume()? != StopReason::Finished {
+///     println!("pc 0x{:08x}: {:?}", debugger.pc(), debugger.registers());
+/// }
+/// ```
+pub struct Debugger<'a> {
+    executor: Executor<'a>,
+    breakpoints: Vec<(BreakpointId, Breakpoint)>,
+    next_id: u32,
+    finished: bool,
+}
+
+impl<'a> Debugger<'a> {
+    /// Creates a debugger stopped before the next instruction of `executor`.
+    #[must_use]
+    pub fn new(mut executor: Executor<'a>) -> Self {
+        executor.executor_mode = ExecutorMode::Simple;
+        if executor.state.global_clk == 0 {
+            executor.initialize();
+        }
+        Self { executor, breakpoints: Vec::new(), next_id: 0, finished: false }
+    }
+
+    /// Adds `breakpoint`, returning its identifier.
+    pub fn add_breakpoint(&mut self, breakpoint: Breakpoint) -> BreakpointId {
+        let id = BreakpointId(self.next_id);
+        self.next_id += 1;
+        self.breakpoints.push((id, breakpoint.aligned()));
+        self.watch_memory();
+        id
+    }
+
+    /// Removes the breakpoint `id`, returning it if it existed.
+    pub fn remove_breakpoint(&mut self, id: BreakpointId) -> Option<Breakpoint> {
+        let index = self.breakpoints.iter().position(|(other, _)| *other == id)?;
+        let (_, breakpoint) = self.breakpoints.remove(index);
+        self.watch_memory();
+        Some(breakpoint)
+    }
+
+    /// The breakpoints, in the order they were added.
+    pub fn breakpoints(&self) -> impl Iterator<Item = (BreakpointId, &Breakpoint)> {
+        self.breakpoints.iter().map(|(id, breakpoint)| (*id, breakpoint))
+    }
+
+    /// Executes the next instruction.
+    ///
+    /// Returns [`StopReason::Step`] unless the instruction hit a breakpoint or finished the
+    /// program. Once it finished, nothing is executed anymore.
+    pub fn step(&mut self) -> Result<StopReason, ExecutionError> {
+        if self.finished {
+            return Ok(StopReason::Finished);
+        }
+
+        self.executor.watch_hit = None;
+        let done = self.executor.execute_cycle()?;
+        // The records of the shards are empty when not tracing.
+        self.executor.records.clear();
+        if done {
+            self.executor.postprocess();
+            self.finished = true;
+            return Ok(StopReason::Finished);
+        }
+
+        if let Some((addr, write)) = self.executor.watch_hit.take() {
+            let breakpoint = self.breakpoints.iter().find_map(|(id, breakpoint)| {
+                (*breakpoint == Breakpoint::Memory(addr)).then_some(*id)
+            });
+            if let Some(breakpoint) = breakpoint {
+                return Ok(StopReason::MemoryAccess { breakpoint, addr, write });
+            }
+        }
+
+        let pc = self.executor.state.pc;
+        let breakpoint = self.breakpoints.iter().find_map(|(id, breakpoint)| match breakpoint {
+            Breakpoint::Pc(range) if range.contains(&pc) => Some(*id),
+            _ => None,
+        });
+        Ok(breakpoint.map_or(StopReason::Step, StopReason::Breakpoint))
+    }
+
+    /// Executes instructions until one hits a breakpoint or the program finishes. At least one
+    /// instruction is executed, so that resuming from a breakpoint moves past it.
+    pub fn resume(&mut self) -> Result<StopReason, ExecutionError> {
+        loop {
+            match self.step()? {
+                StopReason::Step => {}
+                reason => return Ok(reason),
+            }
+        }
+    }
+
+    /// Whether the program finished.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// The program counter of the next instruction.
+    #[must_use]
+    pub fn pc(&self) -> u32 {
+        self.executor.state.pc
+    }
+
+    /// The number of instructions executed.
+    #[must_use]
+    pub fn global_clk(&self) -> u64 {
+        self.executor.state.global_clk
+    }
+
+    /// The next instruction, or `None` if the program finished.
+    #[must_use]
+    pub fn instruction(&self) -> Option<Instruction> {
+        (!self.finished).then(|| self.executor.fetch())
+    }
+
+    /// The values of the registers.
+    #[must_use]
+    pub fn registers(&mut self) -> [u32; 32] {
+        self.executor.registers()
+    }
+
+    /// The value of `register`.
+    #[must_use]
+    pub fn register(&mut self, register: Register) -> u32 {
+        self.executor.register(register)
+    }
+
+    /// The word at `addr`, rounded down to a multiple of 4.
+    #[must_use]
+    pub fn word(&mut self, addr: u32) -> u32 {
+        self.executor.word(addr & !3)
+    }
+
+    /// The byte at `addr`.
+    #[must_use]
+    pub fn byte(&mut self, addr: u32) -> u8 {
+        self.executor.byte(addr)
+    }
+
+    /// The executor, e.g. to read its public values or report.
+    #[must_use]
+    pub fn executor(&self) -> &Executor<'a> {
+        &self.executor
+    }
+
+    /// Consumes the debugger, returning its executor.
+    #[must_use]
+    pub fn into_executor(self) -> Executor<'a> {
+        self.executor
+    }
+
+    /// Updates the addresses watched by the executor to those of the memory breakpoints.
+    fn watch_memory(&mut self) {
+        self.executor.watched_memory = self
+            .breakpoints
+            .iter()
+            .filter_map(|(_, breakpoint)| match breakpoint {
+                Breakpoint::Memory(addr) => Some(*addr),
+                Breakpoint::Pc(_) => None,
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::*;
+    use crate::{
+        programs::tests::{simple_memory_program, simple_program},
+        Opcode,
+    };
+
+    #[test]
+    fn test_debugger() {
+        let mut debugger = Debugger::new(Executor::new(simple_program(), SP1CoreOpts::default()));
+        let breakpoint = debugger.add_breakpoint(Breakpoint::pc(8));
+        assert_eq!(debugger.instruction().map(|instruction| instruction.opcode), Some(Opcode::ADD));
+
+        assert_eq!(debugger.step().unwrap(), StopReason::Step);
+        assert_eq!(debugger.register(Register::X29), 5);
+        assert_eq!(debugger.resume().unwrap(), StopReason::Breakpoint(breakpoint));
+        assert_eq!(debugger.pc(), 8);
+        assert_eq!(debugger.register(Register::X31), 0);
+        assert_eq!(debugger.resume().unwrap(), StopReason::Finished);
+        assert_eq!(debugger.register(Register::X31), 42);
+        assert!(debugger.is_finished());
+
+        let mut debugger =
+            Debugger::new(Executor::new(simple_memory_program(), SP1CoreOpts::default()));
+        let breakpoint = debugger.add_breakpoint(Breakpoint::Memory(0x27654322));
+        assert_eq!(
+            debugger.resume().unwrap(),
+            StopReason::MemoryAccess { breakpoint, addr: 0x27654320, write: true }
+        );
+        assert_eq!(debugger.word(0x27654320), 0x12348765);
+        assert_eq!(
+            debugger.resume().unwrap(),
+            StopReason::MemoryAccess { breakpoint, addr: 0x27654320, write: false }
+        );
+        assert_eq!(debugger.global_clk(), 3);
+        assert_eq!(debugger.remove_breakpoint(breakpoint), Some(Breakpoint::Memory(0x27654320)));
+        assert_eq!(debugger.resume().unwrap(), StopReason::Finished);
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::{self, Display, Formatter};
+
+use thiserror::Error;
+
+use crate::{Debugger, ExecutionError, Executor, Instruction, Opcode, Register, StopReason};
+
+/// The architectural state compared by a [`DifferentialExecutor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchState {
+    /// The program counter of the next instruction.
+    pub pc: u32,
+    /// The values of the registers.
+    pub registers: [u32; 32],
+}
+
+/// An emulator running the same program as a [`DifferentialExecutor`], e.g. an adapter driving
+/// Spike or another RISC-V emulator through its debug interface.
+pub trait ReferenceEmulator {
+    /// Executes the next `n` instructions.
+    fn step(&mut self, n: u64) -> eyre::Result<()>;
+
+    /// The current state of the emulator.
+    fn state(&mut self) -> eyre::Result<ArchState>;
+
+    /// Overwrites the state of the emulator.
+    ///
+    /// The syscalls of SP1 aren't those of the reference, so `ECALL` instructions are only
+    /// executed by the executor, after which the reference is given its state.
+    fn set_state(&mut self, state: &ArchState) -> eyre::Result<()>;
+}
+
+/// The first divergence between the executor and the reference found by a
+/// [`DifferentialExecutor`].
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// The number of instructions executed when the divergence was found.
+    pub global_clk: u64,
+    /// The state of the reference.
+    pub expected: ArchState,
+    /// The state of the executor.
+    pub actual: ArchState,
+    /// The instructions executed since the states last matched, with their program counter, the
+    /// first divergent one among them.
+    pub instructions: Vec<(u32, Instruction)>,
+}
+
+impl Divergence {
+    /// The registers whose values differ, with their expected and actual values.
+    pub fn registers(&self) -> impl Iterator<Item = (Register, u32, u32)> + '_ {
+        (0..32).filter(|&i| self.expected.registers[i] != self.actual.registers[i]).map(|i| {
+            (Register::from_u32(i as u32), self.expected.registers[i], self.actual.registers[i])
+        })
+    }
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "diverged from the reference after {} instructions", self.global_clk)?;
+        if self.expected.pc != self.actual.pc {
+            writeln!(f, "  pc: expected 0x{:08x}, got 0x{:08x}", self.expected.pc, self.actual.pc)?;
+        }
+        for (register, expected, actual) in self.registers() {
+            writeln!(f, "  {register:?}: expected 0x{expected:08x}, got 0x{actual:08x}")?;
+        }
+        writeln!(f, "instructions since the last match:")?;
+        for (pc, instruction) in &self.instructions {
+            writeln!(f, "  0x{pc:08x}: {instruction:?}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors of a [`DifferentialExecutor`].
+#[derive(Error, Debug)]
+pub enum DifferentialError {
+    /// The state of the executor diverged from the reference.
+    #[error("{0}")]
+    Diverged(Box<Divergence>),
+
+    /// The executor failed.
+    #[error(transparent)]
+    Execution(#[from] ExecutionError),
+
+    /// The reference emulator failed.
+    #[error("reference emulator failed: {0:#}")]
+    Reference(eyre::Report),
+}
+
+/// Runs a program on the executor and on a [`ReferenceEmulator`] side by side, comparing their
+/// [`ArchState`] every [`Self::with_interval`] instructions and after every `ECALL`.
+///
+/// The divergence reported is the first one seen, along with the instructions executed since
+/// the last comparison, so an interval of 1, the default, pinpoints the instruction at fault.
+/// Memory isn't compared, so the writes of precompiles are only noticed once they're loaded.
+pub struct DifferentialExecutor<'a, R> {
+    debugger: Debugger<'a>,
+    reference: R,
+    interval: u64,
+}
+
+impl<'a, R: ReferenceEmulator> DifferentialExecutor<'a, R> {
+    /// Creates a differential executor of `executor` against `reference`, both about to run the
+    /// same program from the same state.
+    #[must_use]
+    pub fn new(executor: Executor<'a>, reference: R) -> Self {
+        Self { debugger: Debugger::new(executor), reference, interval: 1 }
+    }
+
+    /// Sets the number of instructions executed between comparisons.
+    #[must_use]
+    pub fn with_interval(mut self, interval: u64) -> Self {
+        self.interval = interval.max(1);
+        self
+    }
+
+    /// Runs the program to its end, stopping at the first divergence.
+    pub fn run(&mut self) -> Result<(), DifferentialError> {
+        let mut instructions = Vec::new();
+        while let Some(instruction) = self.debugger.instruction() {
+            let pc = self.debugger.pc();
+            let finished = self.debugger.step()? == StopReason::Finished;
+            if instruction.opcode == Opcode::ECALL {
+                self.compare(&mut instructions)?;
+                let state = self.state();
+                self.reference.set_state(&state).map_err(DifferentialError::Reference)?;
+            } else {
+                instructions.push((pc, instruction));
+                if finished || instructions.len() as u64 >= self.interval {
+                    self.compare(&mut instructions)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The executor.
+    #[must_use]
+    pub fn executor(&self) -> &Executor<'a> {
+        self.debugger.executor()
+    }
+
+    /// The reference emulator.
+    #[must_use]
+    pub fn reference(&self) -> &R {
+        &self.reference
+    }
+
+    /// Steps the reference over `instructions` and compares it with the executor.
+    fn compare(
+        &mut self,
+        instructions: &mut Vec<(u32, Instruction)>,
+    ) -> Result<(), DifferentialError> {
+        if instructions.is_empty() {
+            return Ok(());
+        }
+        self.reference.step(instructions.len() as u64).map_err(DifferentialError::Reference)?;
+        let expected = self.reference.state().map_err(DifferentialError::Reference)?;
+        let actual = self.state();
+        if expected != actual {
+            return Err(DifferentialError::Diverged(Box::new(Divergence {
+                global_clk: self.debugger.global_clk(),
+                expected,
+                actual,
+                instructions: std::mem::take(instructions),
+            })));
+        }
+        instructions.clear();
+        Ok(())
+    }
+
+    /// The current state of the executor.
+    fn state(&mut self) -> ArchState {
+        ArchState { pc: self.debugger.pc(), registers: self.debugger.registers() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_stark::SP1CoreOpts;
+
+    use super::*;
+    use crate::programs::tests::simple_program;
+
+    /// Another executor standing in for a reference emulator.
+    struct ExecutorReference(Debugger<'static>);
+
+    impl ReferenceEmulator for ExecutorReference {
+        fn step(&mut self, n: u64) -> eyre::Result<()> {
+            for _ in 0..n {
+                self.0.step()?;
+            }
+            Ok(())
+        }
+
+        fn state(&mut self) -> eyre::Result<ArchState> {
+            Ok(ArchState { pc: self.0.pc(), registers: self.0.registers() })
+        }
+
+        fn set_state(&mut self, _: &ArchState) -> eyre::Result<()> {
+            unreachable!("the program has no syscalls")
+        }
+    }
+
+    fn reference() -> ExecutorReference {
+        ExecutorReference(Debugger::new(Executor::new(simple_program(), SP1CoreOpts::default())))
+    }
+
+    #[test]
+    fn test_differential() {
+        let executor = Executor::new(simple_program(), SP1CoreOpts::default());
+        DifferentialExecutor::new(executor, reference()).run().unwrap();
+
+        let mut program = simple_program();
+        program.instructions[1] = Instruction::new(Opcode::ADD, 30, 0, 38, false, true);
+        let executor = Executor::new(program, SP1CoreOpts::default());
+        let Err(DifferentialError::Diverged(divergence)) =
+            DifferentialExecutor::new(executor, reference()).run()
+        else {
+            panic!("expected a divergence");
+        };
+        assert_eq!(divergence.global_clk, 2);
+        assert_eq!(divergence.registers().collect::<Vec<_>>(), vec![(Register::X30, 37, 38)]);
+        let [(pc, executed)] = divergence.instructions[..] else {
+            panic!("expected one instruction since the last match");
+        };
+        assert_eq!((pc, executed.op_c), (4, 38));
+    }
+}
@@ -381,18 +381,111 @@ impl InstructionProcessor for InstructionTranspiler {
     }
 }
 
-/// Transpile the [`Instruction`]s from the 32-bit encoded instructions.
+/// Decode an instruction of the Zba, Zbb or Zbs bit manipulation extensions, which the
+/// instruction processor doesn't know about.
 ///
-/// # Panics
+/// The immediate forms become the register forms with an immediate operand, and the unary
+/// instructions take an immediate `0` as their second operand.
+fn transpile_bitmanip(instruction: u32) -> Option<Instruction> {
+    const OP: u32 = 0b011_0011;
+    const OP_IMM: u32 = 0b001_0011;
+
+    let r_type = RType::new(instruction);
+    let r = |opcode| Some(Instruction::from_r_type(opcode, &r_type));
+    let funct7 = instruction >> 25;
+    match (instruction & 0x7f, r_type.funct3, funct7) {
+        (OP, 0b111, 0b010_0000) => r(Opcode::ANDN),
+        (OP, 0b110, 0b010_0000) => r(Opcode::ORN),
+        (OP, 0b100, 0b010_0000) => r(Opcode::XNOR),
+        (OP, 0b100, 0b000_0101) => r(Opcode::MIN),
+        (OP, 0b101, 0b000_0101) => r(Opcode::MINU),
+        (OP, 0b110, 0b000_0101) => r(Opcode::MAX),
+        (OP, 0b111, 0b000_0101) => r(Opcode::MAXU),
+        (OP, 0b001, 0b011_0000) => r(Opcode::ROL),
+        (OP, 0b101, 0b011_0000) => r(Opcode::ROR),
+        (OP, 0b010, 0b001_0000) => r(Opcode::SH1ADD),
+        (OP, 0b100, 0b001_0000) => r(Opcode::SH2ADD),
+        (OP, 0b110, 0b001_0000) => r(Opcode::SH3ADD),
+        (OP, 0b001, 0b010_0100) => r(Opcode::BCLR),
+        (OP, 0b101, 0b010_0100) => r(Opcode::BEXT),
+        (OP, 0b001, 0b011_0100) => r(Opcode::BINV),
+        (OP, 0b001, 0b001_0100) => r(Opcode::BSET),
+        (OP, 0b100, 0b000_0100) if r_type.rs2 == 0 => Some(Instruction::new(
+            Opcode::ZEXTH,
+            r_type.rd as u32,
+            r_type.rs1 as u32,
+            0,
+            false,
+            true,
+        )),
+        (OP_IMM, funct3, _) => {
+            let i_type = ITypeShamt::new(instruction);
+            let unary = |opcode| {
+                Some(Instruction::new(opcode, i_type.rd as u32, i_type.rs1 as u32, 0, false, true))
+            };
+            match (funct3, instruction >> 20) {
+                (0b001, 0x600) => unary(Opcode::CLZ),
+                (0b001, 0x601) => unary(Opcode::CTZ),
+                (0b001, 0x602) => unary(Opcode::CPOP),
+                (0b001, 0x604) => unary(Opcode::SEXTB),
+                (0b001, 0x605) => unary(Opcode::SEXTH),
+                (0b101, 0x287) => unary(Opcode::ORCB),
+                (0b101, 0x698) => unary(Opcode::REV8),
+                (0b101, _) if funct7 == 0b011_0000 => {
+                    Some(Instruction::from_i_type_shamt(Opcode::ROR, &i_type))
+                }
+                (0b001, _) if funct7 == 0b010_0100 => {
+                    Some(Instruction::from_i_type_shamt(Opcode::BCLR, &i_type))
+                }
+                (0b101, _) if funct7 == 0b010_0100 => {
+                    Some(Instruction::from_i_type_shamt(Opcode::BEXT, &i_type))
+                }
+                (0b001, _) if funct7 == 0b011_0100 => {
+                    Some(Instruction::from_i_type_shamt(Opcode::BINV, &i_type))
+                }
+                (0b001, _) if funct7 == 0b001_0100 => {
+                    Some(Instruction::from_i_type_shamt(Opcode::BSET, &i_type))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Transpile the [`Instruction`]s from the 32-bit encoded instructions, starting at `pc_base`.
+///
+/// # Errors
 ///
-/// This function will return an error if the [`Instruction`] cannot be processed.
-#[must_use]
-pub(crate) fn transpile(instructions_u32: &[u32]) -> Vec<Instruction> {
+/// This function will return an error if an [`Instruction`] cannot be processed, e.g. if it is a
+/// floating point instruction.
+pub(crate) fn transpile(instructions_u32: &[u32], pc_base: u32) -> eyre::Result<Vec<Instruction>> {
     let mut instructions = Vec::new();
     let mut transpiler = InstructionTranspiler;
-    for instruction_u32 in instructions_u32 {
-        let instruction = process_instruction(&mut transpiler, *instruction_u32).unwrap();
+    for (i, instruction_u32) in instructions_u32.iter().enumerate() {
+        let instruction = transpile_bitmanip(*instruction_u32)
+            .or_else(|| process_instruction(&mut transpiler, *instruction_u32));
+        let Some(instruction) = instruction else {
+            let pc = pc_base.wrapping_add(4 * i as u32);
+            if is_float_instruction(*instruction_u32) {
+                eyre::bail!(
+                    "floating point instruction 0x{instruction_u32:08x} at pc 0x{pc:08x}: the F \
+                     and D extensions aren't supported, build for the \
+                     riscv32im-succinct-zkvm-elf target, which implements floats in software"
+                );
+            }
+            eyre::bail!("invalid instruction 0x{instruction_u32:08x} at pc 0x{pc:08x}");
+        };
         instructions.push(instruction);
     }
-    instructions
+    Ok(instructions)
+}
+
+/// Whether `instruction` is of the F, D or Q floating point extensions, by its major opcode.
+const fn is_float_instruction(instruction: u32) -> bool {
+    matches!(
+        instruction & 0x7f,
+        // LOAD-FP, STORE-FP, MADD, MSUB, NMSUB, NMADD and OP-FP.
+        0b000_0111 | 0b010_0111 | 0b100_0011 | 0b100_0111 | 0b100_1011 | 0b100_1111 | 0b101_0011
+    )
 }
@@ -230,7 +230,7 @@ impl From<Opcode> for ByteOpcode {
 }
 
 impl ByteOpcode {
-    /// Get all the byte opcodes.
+    /// Get all the opcodes of the byte table.
     #[must_use]
     pub fn all() -> Vec<Self> {
         let opcodes = vec![
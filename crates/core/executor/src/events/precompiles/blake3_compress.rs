@@ -0,0 +1,37 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::memory::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of words in the state and in the message block of the BLAKE3 compression function.
+pub const BLAKE3_STATE_SIZE: usize = 16;
+
+/// BLAKE3 Compress Event.
+///
+/// This event is emitted when a BLAKE3 compression operation is performed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blake3CompressEvent {
+    /// The lookup identifer.
+    pub lookup_id: u128,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The address of the state.
+    pub state_ptr: u32,
+    /// The address of the message block.
+    pub msg_ptr: u32,
+    /// The initial state: the chaining value, the first four words of the IV, the counter, the
+    /// block length and the flags.
+    pub pre_state: [u32; BLAKE3_STATE_SIZE],
+    /// The output of the compression function.
+    pub post_state: [u32; BLAKE3_STATE_SIZE],
+    /// The message block.
+    pub msg: [u32; BLAKE3_STATE_SIZE],
+    /// The memory records for the state, read as the previous values and written with the
+    /// post-state.
+    pub state_write_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the message block.
+    pub msg_read_records: Vec<MemoryReadRecord>,
+}
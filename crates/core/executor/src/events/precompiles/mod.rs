@@ -1,15 +1,29 @@
+mod aes_encrypt;
+mod blake3_compress;
 mod ec;
 mod edwards;
 mod fptower;
 mod keccak256_permute;
+mod poseidon2_permute;
+mod ripemd160_compress;
 mod sha256_compress;
 mod sha256_extend;
+mod sha512_compress;
+mod sha512_extend;
 mod uint256;
+mod uint_mod;
 
+pub use aes_encrypt::*;
+pub use blake3_compress::*;
 pub use ec::*;
 pub use edwards::*;
 pub use fptower::*;
 pub use keccak256_permute::*;
+pub use poseidon2_permute::*;
+pub use ripemd160_compress::*;
 pub use sha256_compress::*;
 pub use sha256_extend::*;
+pub use sha512_compress::*;
+pub use sha512_extend::*;
 pub use uint256::*;
+pub use uint_mod::*;
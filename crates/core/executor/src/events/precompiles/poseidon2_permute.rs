@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::memory::MemoryWriteRecord;
+
+/// The number of `BabyBear` elements in the state of the Poseidon2 permutation.
+pub const POSEIDON2_WIDTH: usize = 16;
+
+/// Poseidon2 Permutation Event.
+///
+/// This event is emitted when a Poseidon2 permutation over `BabyBear` is performed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Poseidon2PermuteEvent {
+    /// The lookup identifer.
+    pub lookup_id: u128,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The address of the state.
+    pub state_ptr: u32,
+    /// The pre-state as the words in memory, read as `BabyBear` elements modulo the field order.
+    pub pre_state: [u32; POSEIDON2_WIDTH],
+    /// The post-state as canonical `BabyBear` elements.
+    pub post_state: [u32; POSEIDON2_WIDTH],
+    /// The memory records for the state, read as the previous values and written with the
+    /// post-state.
+    pub state_write_records: Vec<MemoryWriteRecord>,
+}
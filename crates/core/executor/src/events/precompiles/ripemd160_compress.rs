@@ -0,0 +1,98 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::memory::{MemoryReadRecord, MemoryWriteRecord};
+
+/// The number of words in the state of the RIPEMD-160 compression function.
+pub const RIPEMD160_STATE_SIZE: usize = 5;
+
+/// The number of words in a RIPEMD-160 message block.
+pub const RIPEMD160_BLOCK_SIZE: usize = 16;
+
+/// The number of steps of each of the two lines of the compression.
+pub const RIPEMD160_NUM_STEPS: usize = 80;
+
+/// The indices of the message words used by each step of the left line.
+pub const RIPEMD160_R_LEFT: [usize; RIPEMD160_NUM_STEPS] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, //
+    7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8, //
+    3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, //
+    1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2, //
+    4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+
+/// The indices of the message words used by each step of the right line.
+pub const RIPEMD160_R_RIGHT: [usize; RIPEMD160_NUM_STEPS] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, //
+    6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2, //
+    15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, //
+    8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14, //
+    12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+
+/// The left rotations of each step of the left line.
+pub const RIPEMD160_S_LEFT: [u32; RIPEMD160_NUM_STEPS] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, //
+    7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12, //
+    11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, //
+    11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, //
+    9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+
+/// The left rotations of each step of the right line.
+pub const RIPEMD160_S_RIGHT: [u32; RIPEMD160_NUM_STEPS] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, //
+    9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11, //
+    9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, //
+    15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, //
+    8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+
+/// The constants of each round of sixteen steps of the left line.
+pub const RIPEMD160_K_LEFT: [u32; 5] = [0x00000000, 0x5a827999, 0x6ed9eba1, 0x8f1bbcdc, 0xa953fd4e];
+
+/// The constants of each round of sixteen steps of the right line.
+pub const RIPEMD160_K_RIGHT: [u32; 5] =
+    [0x50a28be6, 0x5c4dd124, 0x6d703ef3, 0x7a6d76e9, 0x00000000];
+
+/// The boolean function of the `i`-th round, the left line using them in order and the right line
+/// in the reverse order.
+#[must_use]
+pub const fn ripemd160_f(i: usize, x: u32, y: u32, z: u32) -> u32 {
+    match i {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+/// RIPEMD-160 Compress Event.
+///
+/// This event is emitted when a RIPEMD-160 compression operation is performed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ripemd160CompressEvent {
+    /// The lookup identifer.
+    pub lookup_id: u128,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The address of the state.
+    pub state_ptr: u32,
+    /// The address of the message block.
+    pub block_ptr: u32,
+    /// The state before the compression.
+    pub pre_state: [u32; RIPEMD160_STATE_SIZE],
+    /// The state after the compression.
+    pub post_state: [u32; RIPEMD160_STATE_SIZE],
+    /// The message block.
+    pub block: [u32; RIPEMD160_BLOCK_SIZE],
+    /// The memory records for the state, read as the previous values and written with the
+    /// post-state.
+    pub state_write_records: Vec<MemoryWriteRecord>,
+    /// The memory records for the message block.
+    pub block_read_records: Vec<MemoryReadRecord>,
+}
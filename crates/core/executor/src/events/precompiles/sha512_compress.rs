@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::memory::{MemoryReadRecord, MemoryWriteRecord};
+
+/// SHA-512 Compress Event.
+///
+/// This event is emitted when a SHA-512 compress operation is performed. Each double word is
+/// accessed as two words, the low one first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sha512CompressEvent {
+    /// The lookup identifer.
+    pub lookup_id: u128,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the message schedule.
+    pub w_ptr: u32,
+    /// The pointer to the hash state.
+    pub h_ptr: u32,
+    /// The message schedule.
+    pub w: Vec<u64>,
+    /// The hash state before the compression.
+    pub h: [u64; 8],
+    /// The memory records for the message schedule.
+    pub w_i_read_records: Vec<[MemoryReadRecord; 2]>,
+    /// The memory records for the hash state, read as the previous values and written with the
+    /// compressed state.
+    pub h_write_records: [MemoryWriteRecord; 16],
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+
+use crate::events::memory::{MemoryReadRecord, MemoryWriteRecord};
+
+/// SHA-512 Extend Event.
+///
+/// This event is emitted when a SHA-512 extend operation is performed. Each double word of the
+/// message schedule is accessed as two words, the low one first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sha512ExtendEvent {
+    /// The lookup identifer.
+    pub lookup_id: u128,
+    /// The shard number.
+    pub shard: u32,
+    /// The channel number.
+    pub channel: u8,
+    /// The clock cycle.
+    pub clk: u32,
+    /// The pointer to the message schedule.
+    pub w_ptr: u32,
+    /// The memory reads of w[i-15].
+    pub w_i_minus_15_reads: Vec<[MemoryReadRecord; 2]>,
+    /// The memory reads of w[i-2].
+    pub w_i_minus_2_reads: Vec<[MemoryReadRecord; 2]>,
+    /// The memory reads of w[i-16].
+    pub w_i_minus_16_reads: Vec<[MemoryReadRecord; 2]>,
+    /// The memory reads of w[i-7].
+    pub w_i_minus_7_reads: Vec<[MemoryReadRecord; 2]>,
+    /// The memory writes of w[i].
+    pub w_i_writes: Vec<[MemoryWriteRecord; 2]>,
+}
@@ -2,33 +2,53 @@ use std::{
     fs::File,
     io::{BufWriter, Write},
     sync::Arc,
+    time::Instant,
 };
 
-use hashbrown::{hash_map::Entry, HashMap};
+use hashbrown::{HashMap, HashSet};
 use nohash_hasher::BuildNoHashHasher;
 use serde::{Deserialize, Serialize};
+use sp1_primitives::consts::WORD_SIZE;
 use sp1_stark::SP1CoreOpts;
 use thiserror::Error;
 
 use crate::{
-    context::SP1Context,
+    cancel::CancellationToken,
+    context::{OutputWriter, SP1Context},
+    debug_info::Frame,
     events::{
         create_alu_lookup_id, create_alu_lookups, AluEvent, CpuEvent, MemoryAccessPosition,
         MemoryInitializeFinalizeEvent, MemoryReadRecord, MemoryRecord, MemoryWriteRecord,
     },
-    hook::{HookEnv, HookRegistry},
+    fast::BlockCache,
+    fault::{MemoryFault, MemoryFaultKind, VALID_MEMORY_ADDRESSES},
+    hook::{HookEnv, HookError, HookRegistry},
+    memory::Entry,
     record::{ExecutionRecord, MemoryAccessRecord},
-    report::ExecutionReport,
-    state::{ExecutionState, ForkState},
+    replay::{HookResponse, ReplayRecorder, Replayer},
+    report::{ExecutionReport, MEMORY_PAGE_SIZE, UNKNOWN_FUNCTION},
+    state::{ExecutionSnapshot, ExecutionState, ForkState},
     subproof::{DefaultSubproofVerifier, SubproofVerifier},
     syscalls::{default_syscall_map, Syscall, SyscallCode, SyscallContext},
-    Instruction, Opcode, Program, Register,
+    trace::{TraceExporter, TraceMemoryAccess, TraceRow},
+    Instruction, Opcode, OpcodeClass, Program, Register,
 };
 
+/// The number of cycles between two checks of the cancellation token and of the deadline of the
+/// execution.
+pub(crate) const WATCHDOG_INTERVAL: u64 = 1 << 12;
+
+/// The number of cycles between two logs of the progress of the execution.
+pub(crate) const LOG_INTERVAL: u64 = 10_000_000;
+
+/// The number of hottest basic blocks logged at the end of an execution with a pc histogram.
+const HOT_BLOCKS_LOGGED: usize = 10;
+
 /// An executor for the SP1 RISC-V zkVM.
 ///
 /// The exeuctor is responsible for executing a user program and tracing important events which
 /// occur during execution (i.e., memory reads, alu operations, etc).
+#[allow(clippy::struct_excessive_bools)]
 pub struct Executor<'a> {
     /// The program.
     pub program: Arc<Program>,
@@ -57,6 +77,25 @@ pub struct Executor<'a> {
     /// A buffer for stdout and stderr IO.
     pub io_buf: HashMap<u32, String>,
 
+    /// The writer the stdout of the program is sent to, instead of being printed.
+    pub stdout: Option<OutputWriter<'a>>,
+
+    /// The writer the stderr of the program is sent to, instead of being printed.
+    pub stderr: Option<OutputWriter<'a>>,
+
+    /// The exporter of the instruction-level trace, if it is exported.
+    pub(crate) trace_exporter: Option<TraceExporter<'a>>,
+
+    /// The recorder of the replay log of the execution, if recording it.
+    pub(crate) replay_recorder: Option<ReplayRecorder<'a>>,
+
+    /// The source of the hook responses, if replaying the execution.
+    pub(crate) replayer: Option<Replayer>,
+
+    /// The responses of the hooks invoked since the last checkpoint, see
+    /// [`ExecutionState::hook_responses`].
+    pub(crate) checkpoint_hook_responses: Vec<HookResponse>,
+
     /// A buffer for writing trace events to a file.
     pub trace_buf: Option<BufWriter<File>>,
 
@@ -85,21 +124,59 @@ pub struct Executor<'a> {
     /// Whether we should write to the report.
     pub print_report: bool,
 
+    /// Whether to attribute the cycles in the report to the functions of the program.
+    pub function_profile: bool,
+
+    /// Whether to count the runs of the instruction at each program counter in the report.
+    pub pc_histogram: bool,
+
     /// Verifier used to sanity check `verify_sp1_proof` during runtime.
     pub subproof_verifier: Arc<dyn SubproofVerifier + 'a>,
 
     /// Registry of hooks, to be invoked by writing to certain file descriptors.
     pub hook_registry: HookRegistry<'a>,
 
+    /// The file descriptor and error of the hook that failed during the last syscall, if any.
+    pub hook_failure: Option<(u32, HookError)>,
+
+    /// The message and location of the panic reported by the program, if any.
+    pub guest_panic: Option<(String, Option<String>)>,
+
     /// The options for the runtime.
     pub opts: SP1CoreOpts,
 
     /// The maximum number of cpu cycles to use for execution.
     pub max_cycles: Option<u64>,
 
+    /// The end of the addresses loads and stores may access.
+    pub max_memory: u32,
+
+    /// Whether loads of words that were never initialized fail.
+    pub strict_memory: bool,
+
+    /// The instant the execution times out at, set by [`SP1Context::max_duration`].
+    pub deadline: Option<Instant>,
+
+    /// The token checked periodically to cancel the execution.
+    pub cancellation_token: Option<CancellationToken>,
+
     /// Memory addresses that were touched in this batch of shards. Used to minimize the size of
     /// checkpoints.
     pub memory_checkpoint: HashMap<u32, Option<MemoryRecord>, BuildNoHashHasher<u32>>,
+
+    /// The memory addresses watched by the breakpoints of a [`Debugger`](crate::Debugger).
+    pub(crate) watched_memory: HashSet<u32>,
+
+    /// The access to a watched address since it was last taken, with whether it was a write.
+    /// Writes take precedence over reads.
+    pub(crate) watch_hit: Option<(u32, bool)>,
+
+    /// The program counters of the calls currently executing, outermost first, for the
+    /// backtraces of faults.
+    pub(crate) call_stack: Vec<u32>,
+
+    /// The blocks of the program decoded for the fast path of the simple mode.
+    pub(crate) block_cache: BlockCache,
 }
 
 /// The different modes the executor can run in.
@@ -120,9 +197,21 @@ pub enum ExecutionError {
     #[error("execution failed with exit code {0}")]
     HaltWithNonZeroExitCode(u32),
 
-    /// The execution failed with an invalid memory access.
-    #[error("invalid memory access for opcode {0} and address {1}")]
-    InvalidMemoryAccess(Opcode, u32),
+    /// The program panicked, with the message and location reported by its panic hook.
+    #[error(
+        "guest panicked at {}: {message}",
+        .location.as_deref().unwrap_or("an unknown location")
+    )]
+    GuestPanicked {
+        /// The panic message.
+        message: String,
+        /// The source location of the panic, as `file:line:column`, if known.
+        location: Option<String>,
+    },
+
+    /// The execution failed with a misaligned or out-of-bounds load or store.
+    #[error("{0}")]
+    InvalidMemoryAccess(Box<MemoryFault>),
 
     /// The execution failed with an unimplemented syscall.
     #[error("unimplemented syscall {0}")]
@@ -132,9 +221,14 @@ pub enum ExecutionError {
     #[error("breakpoint encountered")]
     Breakpoint(),
 
-    /// The execution failed with an exceeded cycle limit.
-    #[error("exceeded cycle limit of {0}")]
-    ExceededCycleLimit(u64),
+    /// The execution was halted at the cycle limit set by [`SP1Context::max_cycles`].
+    #[error("exceeded the cycle limit after {cycles} cycles at pc 0x{pc:08x}")]
+    CycleLimitExceeded {
+        /// The number of cycles executed.
+        cycles: u64,
+        /// The program counter of the next instruction.
+        pc: u32,
+    },
 
     /// The execution failed because the syscall was called in unconstrained mode.
     #[error("syscall called in unconstrained mode")]
@@ -143,6 +237,37 @@ pub enum ExecutionError {
     /// The execution failed with an unimplemented feature.
     #[error("got unimplemented as opcode")]
     Unimplemented(),
+
+    /// The execution was cancelled through its [`CancellationToken`].
+    #[error("execution was cancelled")]
+    Cancelled,
+
+    /// The execution was halted at the time limit set by [`SP1Context::max_duration`].
+    #[error("exceeded the time limit after {cycles} cycles at pc 0x{pc:08x}")]
+    TimedOut {
+        /// The number of cycles executed.
+        cycles: u64,
+        /// The program counter of the next instruction.
+        pc: u32,
+    },
+
+    /// The execution failed because a hook returned an error.
+    #[error("hook at file descriptor {fd} failed: {error}")]
+    HookFailed {
+        /// The file descriptor of the hook.
+        fd: u32,
+        /// The error returned by the hook.
+        error: HookError,
+    },
+}
+
+impl ExecutionError {
+    /// Whether executing the program again may succeed, as the failure is transient, e.g. a
+    /// [`HookError::retryable`] returned by a hook.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::HookFailed { error: HookError { retryable: true, .. }, .. })
+    }
 }
 
 macro_rules! assert_valid_memory_access {
@@ -163,11 +288,35 @@ impl<'a> Executor<'a> {
     ///
     /// # Panics
     ///
-    /// This function may panic if it fails to create the trace file if `TRACE_FILE` is set.
+    /// This function may panic if it fails to create the trace file if `TRACE_FILE` is set, or if
+    /// the [`SP1Context::max_memory`] of the context exceeds [`VALID_MEMORY_ADDRESSES`] or doesn't
+    /// fit the program.
     #[must_use]
     pub fn with_context(program: Program, opts: SP1CoreOpts, context: SP1Context<'a>) -> Self {
-        // Create a shared reference to the program.
-        let program = Arc::new(program);
+        let max_memory = context.max_memory.unwrap_or(VALID_MEMORY_ADDRESSES.end);
+        assert!(
+            max_memory <= VALID_MEMORY_ADDRESSES.end,
+            "the maximum memory 0x{max_memory:08x} exceeds 0x{:08x}",
+            VALID_MEMORY_ADDRESSES.end
+        );
+        let program_end = program
+            .memory_image
+            .keys()
+            .map(|addr| addr.saturating_add(4))
+            .max()
+            .unwrap_or(0)
+            .max(program.pc_base + 4 * program.instructions.len() as u32);
+        assert!(
+            program_end <= max_memory,
+            "the program ends at 0x{program_end:08x}, past the maximum memory 0x{max_memory:08x}"
+        );
+
+        Self::with_program(Arc::new(program), opts, context)
+    }
+
+    /// Create a new runtime from a shared program, options, and a context.
+    fn with_program(program: Arc<Program>, opts: SP1CoreOpts, context: SP1Context<'a>) -> Self {
+        let max_memory = context.max_memory.unwrap_or(VALID_MEMORY_ADDRESSES.end);
 
         // Create a default record with the program.
         let record = ExecutionRecord { program: program.clone(), ..Default::default() };
@@ -189,16 +338,35 @@ impl<'a> Executor<'a> {
             context.subproof_verifier.unwrap_or_else(|| Arc::new(DefaultSubproofVerifier::new()));
         let hook_registry = context.hook_registry.unwrap_or_default();
 
+        let mut state = ExecutionState::new(program.pc_start);
+        state.args = context.args;
+        state.env = context.env;
+
+        // When replaying, the recorded inputs replace those of the context.
+        let replayer = context.replay.map(|log| {
+            state.args = log.args;
+            state.env = log.env;
+            Replayer::new(Some(log.stdin), log.hook_responses)
+        });
+
         Self {
             record,
             records: vec![],
-            state: ExecutionState::new(program.pc_start),
+            state,
             program,
             memory_accesses: MemoryAccessRecord::default(),
             shard_size: (opts.shard_size as u32) * 4,
             shard_batch_size: opts.shard_batch_size as u32,
             cycle_tracker: HashMap::new(),
             io_buf: HashMap::new(),
+            stdout: context.stdout,
+            stderr: context.stderr,
+            trace_exporter: context
+                .trace_export
+                .map(|(writer, opts)| TraceExporter::new(writer, opts)),
+            replay_recorder: context.record_replay.map(ReplayRecorder::new),
+            replayer,
+            checkpoint_hook_responses: Vec::new(),
             trace_buf,
             unconstrained: false,
             unconstrained_state: ForkState::default(),
@@ -207,11 +375,23 @@ impl<'a> Executor<'a> {
             max_syscall_cycles,
             report: ExecutionReport::default(),
             print_report: false,
+            function_profile: context.function_profile,
+            pc_histogram: context.pc_histogram,
             subproof_verifier,
             hook_registry,
+            hook_failure: None,
+            guest_panic: None,
             opts,
             max_cycles: context.max_cycles,
+            max_memory,
+            strict_memory: context.strict_memory,
+            deadline: context.max_duration.map(|max_duration| Instant::now() + max_duration),
+            cancellation_token: context.cancellation_token,
             memory_checkpoint: HashMap::default(),
+            watched_memory: HashSet::new(),
+            watch_hit: None,
+            call_stack: Vec::new(),
+            block_cache: BlockCache::default(),
         }
     }
 
@@ -222,11 +402,11 @@ impl<'a> Executor<'a> {
     /// If the file descriptor is not found in the [``HookRegistry``], this function will return an
     /// error.
     pub fn hook(&self, fd: u32, buf: &[u8]) -> eyre::Result<Vec<Vec<u8>>> {
-        Ok(self
-            .hook_registry
+        self.hook_registry
             .get(fd)
             .ok_or(eyre::eyre!("no hook found for file descriptor {}", fd))?
-            .invoke_hook(self.hook_env(), buf))
+            .invoke_hook(self.hook_env(), buf)
+            .map_err(|err| eyre::eyre!("hook at file descriptor {} failed: {}", fd, err))
     }
 
     /// Prepare a `HookEnv` for use by hooks.
@@ -235,14 +415,114 @@ impl<'a> Executor<'a> {
         HookEnv { runtime: self }
     }
 
+    /// Takes a snapshot of the execution, to continue it later from this point with
+    /// [`Self::restore`] or [`Self::fork`], e.g. with different hints.
+    ///
+    /// Taking it is cheap, as the memory is shared copy-on-write with the execution.
+    ///
+    /// # Panics
+    ///
+    /// Panics in an unconstrained block.
+    #[must_use]
+    pub fn snapshot(&self) -> ExecutionSnapshot {
+        assert!(!self.unconstrained, "can't take a snapshot in an unconstrained block");
+        ExecutionSnapshot {
+            state: self.state.clone(),
+            report: self.report.clone(),
+            call_stack: self.call_stack.clone(),
+        }
+    }
+
+    /// Rewinds the execution to `snapshot`, taken from this executor or another one of the same
+    /// program.
+    ///
+    /// The events recorded since the last shard boundary are dropped, so snapshots are meant for
+    /// the simple mode of [`Self::run_fast`] and the [`Debugger`](crate::Debugger).
+    pub fn restore(&mut self, snapshot: ExecutionSnapshot) {
+        self.state = snapshot.state;
+        self.report = snapshot.report;
+        self.call_stack = snapshot.call_stack;
+        self.record = ExecutionRecord::new(self.program.clone());
+        self.memory_checkpoint.clear();
+        self.io_buf.clear();
+        self.hook_failure = None;
+        self.guest_panic = None;
+        self.watch_hit = None;
+    }
+
+    /// Forks the execution into a new executor continuing from this point, with the hooks,
+    /// outputs and options of this one.
+    ///
+    /// The memory of the two executors is shared copy-on-write, so that they diverge cheaply,
+    /// e.g. after changing the input stream of the fork.
+    ///
+    /// # Panics
+    ///
+    /// Panics in an unconstrained block.
+    #[must_use]
+    pub fn fork(&self) -> Self {
+        let context = SP1Context {
+            hook_registry: Some(self.hook_registry.clone()),
+            subproof_verifier: Some(self.subproof_verifier.clone()),
+            max_cycles: self.max_cycles,
+            max_memory: Some(self.max_memory),
+            cancellation_token: self.cancellation_token.clone(),
+            stdout: self.stdout.clone(),
+            stderr: self.stderr.clone(),
+            ..Default::default()
+        };
+        let mut fork = Self::with_program(self.program.clone(), self.opts, context);
+        fork.deadline = self.deadline;
+        fork.strict_memory = self.strict_memory;
+        fork.executor_mode = self.executor_mode;
+        fork.print_report = self.print_report;
+        fork.function_profile = self.function_profile;
+        fork.pc_histogram = self.pc_histogram;
+        fork.restore(self.snapshot());
+        fork
+    }
+
     /// Recover runtime state from a program and existing execution state.
     #[must_use]
-    pub fn recover(program: Program, state: ExecutionState, opts: SP1CoreOpts) -> Self {
+    ///
+    /// The hooks invoked after the checkpoint `state` return their responses recorded in it,
+    /// without being invoked again.
+    pub fn recover(program: Program, mut state: ExecutionState, opts: SP1CoreOpts) -> Self {
         let mut runtime = Self::new(program, opts);
+        let hook_responses = std::mem::take(&mut state.hook_responses);
         runtime.state = state;
+        if !hook_responses.is_empty() {
+            runtime.replayer = Some(Replayer::new(None, hook_responses));
+        }
         runtime
     }
 
+    /// Invokes the hook at `fd` with `request`, or returns its recorded response when replaying,
+    /// returning `None` if there is no hook at `fd`.
+    pub(crate) fn invoke_hook(
+        &mut self,
+        fd: u32,
+        request: &[u8],
+    ) -> Option<Result<Vec<Vec<u8>>, HookError>> {
+        let result = match &mut self.replayer {
+            Some(replayer) => match replayer.next(fd) {
+                Some(result) => result,
+                None if self.hook_registry.get(fd).is_some() => Err(HookError::new(
+                    "the replay diverged, the hook wasn't invoked at this point when recording",
+                )),
+                None => return None,
+            },
+            None => self.hook_registry.get(fd)?.invoke_hook(self.hook_env(), request),
+        };
+        if let Some(recorder) = &mut self.replay_recorder {
+            recorder.record(fd, &result);
+        }
+        if self.executor_mode == ExecutorMode::Checkpoint {
+            self.checkpoint_hook_responses.push(HookResponse { fd, result: result.clone() });
+        }
+        Some(result)
+    }
+
     /// Get the current values of the registers.
     #[allow(clippy::single_match_else)]
     #[must_use]
@@ -250,7 +530,7 @@ impl<'a> Executor<'a> {
         let mut registers = [0; 32];
         for i in 0..32 {
             let addr = Register::from_u32(i as u32) as u32;
-            let record = self.state.memory.get(&addr);
+            let record = self.state.memory.get(addr);
 
             if self.executor_mode != ExecutorMode::Simple {
                 match record {
@@ -275,7 +555,7 @@ impl<'a> Executor<'a> {
     #[must_use]
     pub fn register(&mut self, register: Register) -> u32 {
         let addr = register as u32;
-        let record = self.state.memory.get(&addr);
+        let record = self.state.memory.get(addr);
 
         if self.executor_mode != ExecutorMode::Simple {
             match record {
@@ -298,7 +578,7 @@ impl<'a> Executor<'a> {
     #[must_use]
     pub fn word(&mut self, addr: u32) -> u32 {
         #[allow(clippy::single_match_else)]
-        let record = self.state.memory.get(&addr);
+        let record = self.state.memory.get(addr);
 
         if self.executor_mode != ExecutorMode::Simple {
             match record {
@@ -346,6 +626,10 @@ impl<'a> Executor<'a> {
 
     /// Read a word from memory and create an access record.
     pub fn mr(&mut self, addr: u32, shard: u32, timestamp: u32) -> MemoryReadRecord {
+        if self.watch_hit.is_none() && self.watched_memory.contains(&addr) {
+            self.watch_hit = Some((addr, false));
+        }
+
         // Get the memory record entry.
         let entry = self.state.memory.entry(addr);
         if self.executor_mode != ExecutorMode::Simple {
@@ -375,7 +659,7 @@ impl<'a> Executor<'a> {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
                 // If addr has a specific value to be initialized with, use that, otherwise 0.
-                let value = self.state.uninitialized_memory.get(&addr).unwrap_or(&0);
+                let value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
                 entry.insert(MemoryRecord { value: *value, shard: 0, timestamp: 0 })
             }
         };
@@ -391,6 +675,10 @@ impl<'a> Executor<'a> {
 
     /// Write a word to memory and create an access record.
     pub fn mw(&mut self, addr: u32, value: u32, shard: u32, timestamp: u32) -> MemoryWriteRecord {
+        if self.watched_memory.contains(&addr) {
+            self.watch_hit = Some((addr, true));
+        }
+
         // Get the memory record entry.
         let entry = self.state.memory.entry(addr);
         if self.executor_mode != ExecutorMode::Simple {
@@ -420,7 +708,7 @@ impl<'a> Executor<'a> {
             Entry::Occupied(entry) => entry.into_mut(),
             Entry::Vacant(entry) => {
                 // If addr has a specific value to be initialized with, use that, otherwise 0.
-                let value = self.state.uninitialized_memory.get(&addr).unwrap_or(&0);
+                let value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
 
                 entry.insert(MemoryRecord { value: *value, shard: 0, timestamp: 0 })
             }
@@ -597,6 +885,9 @@ impl<'a> Executor<'a> {
             Opcode::DIVU | Opcode::REMU | Opcode::DIV | Opcode::REM => {
                 self.record.divrem_events.push(event);
             }
+            _ if opcode.is_bitmanip() => {
+                self.record.bitmanip_events.push(event);
+            }
             _ => {}
         }
     }
@@ -637,23 +928,75 @@ impl<'a> Executor<'a> {
     }
 
     /// Fetch the input operand values for a load instruction.
-    fn load_rr(&mut self, instruction: &Instruction) -> (Register, u32, u32, u32, u32) {
+    fn load_rr(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(Register, u32, u32, u32, u32), ExecutionError> {
         let (rd, rs1, imm) = instruction.i_type();
         let (b, c) = (self.rr(rs1, MemoryAccessPosition::B), imm);
         let addr = b.wrapping_add(c);
+        self.check_memory_access(instruction.opcode, addr)?;
         let memory_value = self.mr_cpu(align(addr), MemoryAccessPosition::Memory);
-        (rd, b, c, addr, memory_value)
+        Ok((rd, b, c, addr, memory_value))
     }
 
     /// Fetch the input operand values for a store instruction.
-    fn store_rr(&mut self, instruction: &Instruction) -> (u32, u32, u32, u32, u32) {
+    fn store_rr(
+        &mut self,
+        instruction: &Instruction,
+    ) -> Result<(u32, u32, u32, u32, u32), ExecutionError> {
         let (rs1, rs2, imm) = instruction.s_type();
         let c = imm;
         let b = self.rr(rs2, MemoryAccessPosition::B);
         let a = self.rr(rs1, MemoryAccessPosition::A);
         let addr = b.wrapping_add(c);
+        self.check_memory_access(instruction.opcode, addr)?;
         let memory_value = self.word(align(addr));
-        (a, b, c, addr, memory_value)
+        Ok((a, b, c, addr, memory_value))
+    }
+
+    /// Checks that the load or store `opcode` at `addr` is aligned and in bounds, and in strict
+    /// memory mode that a load reads an initialized word.
+    fn check_memory_access(&self, opcode: Opcode, addr: u32) -> Result<(), ExecutionError> {
+        let Some(kind) = self.memory_fault(opcode, addr) else {
+            return Ok(());
+        };
+
+        let width = access_width(opcode);
+        let pc = self.state.pc;
+        let pcs =
+            std::iter::once(pc).chain(self.call_stack.iter().rev().copied()).collect::<Vec<_>>();
+        let backtrace = match &self.program.debug_info {
+            Some(debug_info) => debug_info.symbolize(&pcs),
+            None => pcs.into_iter().map(Frame::new).collect(),
+        };
+        Err(ExecutionError::InvalidMemoryAccess(Box::new(MemoryFault {
+            kind,
+            opcode,
+            pc,
+            addr,
+            width,
+            backtrace,
+        })))
+    }
+
+    /// The kind of fault of the load or store `opcode` at `addr`, if it faults.
+    pub(crate) fn memory_fault(&self, opcode: Opcode, addr: u32) -> Option<MemoryFaultKind> {
+        let is_load = !matches!(opcode, Opcode::SB | Opcode::SH | Opcode::SW);
+        if addr % access_width(opcode) != 0 {
+            Some(MemoryFaultKind::Misaligned)
+        } else if !(VALID_MEMORY_ADDRESSES.start..self.max_memory).contains(&addr) {
+            Some(MemoryFaultKind::OutOfBounds)
+        } else if self.strict_memory && is_load && !self.is_initialized(align(addr)) {
+            Some(MemoryFaultKind::Uninitialized)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the word at `addr` was initialized, by the program image, a write or a hint.
+    fn is_initialized(&self, addr: u32) -> bool {
+        self.state.memory.get(addr).is_some() || self.state.uninitialized_memory.get(addr).is_some()
     }
 
     /// Fetch the input operand values for a branch instruction.
@@ -666,7 +1009,7 @@ impl<'a> Executor<'a> {
     }
 
     /// Fetch the instruction at the current program counter.
-    fn fetch(&self) -> Instruction {
+    pub(crate) fn fetch(&self) -> Instruction {
         let idx = ((self.state.pc - self.program.pc_base) / 4) as usize;
         self.program.instructions[idx]
     }
@@ -699,184 +1042,83 @@ impl<'a> Executor<'a> {
                 .entry(instruction.opcode)
                 .and_modify(|c| *c += 1)
                 .or_insert(1);
+            if self.function_profile {
+                self.profile_instruction();
+            }
+            if self.pc_histogram {
+                *self.report.pc_counts.entry(self.state.pc).or_default() += 1;
+            }
         }
 
         match instruction.opcode {
-            // Arithmetic instructions.
-            Opcode::ADD => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b.wrapping_add(c);
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::SUB => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b.wrapping_sub(c);
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::XOR => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b ^ c;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::OR => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b | c;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::AND => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b & c;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::SLL => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b.wrapping_shl(c);
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::SRL => {
+            // Arithmetic, multiply and bit manipulation instructions.
+            Opcode::ADD
+            | Opcode::SUB
+            | Opcode::XOR
+            | Opcode::OR
+            | Opcode::AND
+            | Opcode::SLL
+            | Opcode::SRL
+            | Opcode::SRA
+            | Opcode::SLT
+            | Opcode::SLTU
+            | Opcode::MUL
+            | Opcode::MULH
+            | Opcode::MULHU
+            | Opcode::MULHSU
+            | Opcode::DIV
+            | Opcode::DIVU
+            | Opcode::REM
+            | Opcode::REMU
+            | Opcode::ANDN
+            | Opcode::ORN
+            | Opcode::XNOR
+            | Opcode::CLZ
+            | Opcode::CTZ
+            | Opcode::CPOP
+            | Opcode::MAX
+            | Opcode::MAXU
+            | Opcode::MIN
+            | Opcode::MINU
+            | Opcode::SEXTB
+            | Opcode::SEXTH
+            | Opcode::ZEXTH
+            | Opcode::ROL
+            | Opcode::ROR
+            | Opcode::ORCB
+            | Opcode::REV8
+            | Opcode::SH1ADD
+            | Opcode::SH2ADD
+            | Opcode::SH3ADD
+            | Opcode::BCLR
+            | Opcode::BEXT
+            | Opcode::BINV
+            | Opcode::BSET => {
                 (rd, b, c) = self.alu_rr(instruction);
-                a = b.wrapping_shr(c);
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::SRA => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = (b as i32).wrapping_shr(c) as u32;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::SLT => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = if (b as i32) < (c as i32) { 1 } else { 0 };
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::SLTU => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = if b < c { 1 } else { 0 };
+                a = alu(instruction.opcode, b, c);
                 self.alu_rw(instruction, rd, a, b, c, lookup_id);
             }
 
             // Load instructions.
-            Opcode::LB => {
-                (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
-                let value = (memory_read_value).to_le_bytes()[(addr % 4) as usize];
-                a = ((value as i8) as i32) as u32;
-                memory_store_value = Some(memory_read_value);
-                self.rw(rd, a);
-            }
-            Opcode::LH => {
-                (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
-                if addr % 2 != 0 {
-                    return Err(ExecutionError::InvalidMemoryAccess(Opcode::LH, addr));
-                }
-                let value = match (addr >> 1) % 2 {
-                    0 => memory_read_value & 0x0000_FFFF,
-                    1 => (memory_read_value & 0xFFFF_0000) >> 16,
-                    _ => unreachable!(),
-                };
-                a = ((value as i16) as i32) as u32;
-                memory_store_value = Some(memory_read_value);
-                self.rw(rd, a);
-            }
-            Opcode::LW => {
-                (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
-                if addr % 4 != 0 {
-                    return Err(ExecutionError::InvalidMemoryAccess(Opcode::LW, addr));
-                }
-                a = memory_read_value;
-                memory_store_value = Some(memory_read_value);
-                self.rw(rd, a);
-            }
-            Opcode::LBU => {
-                (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
-                let value = (memory_read_value).to_le_bytes()[(addr % 4) as usize];
-                a = value as u32;
-                memory_store_value = Some(memory_read_value);
-                self.rw(rd, a);
-            }
-            Opcode::LHU => {
-                (rd, b, c, addr, memory_read_value) = self.load_rr(instruction);
-                if addr % 2 != 0 {
-                    return Err(ExecutionError::InvalidMemoryAccess(Opcode::LHU, addr));
-                }
-                let value = match (addr >> 1) % 2 {
-                    0 => memory_read_value & 0x0000_FFFF,
-                    1 => (memory_read_value & 0xFFFF_0000) >> 16,
-                    _ => unreachable!(),
-                };
-                a = (value as u16) as u32;
+            Opcode::LB | Opcode::LH | Opcode::LW | Opcode::LBU | Opcode::LHU => {
+                (rd, b, c, addr, memory_read_value) = self.load_rr(instruction)?;
+                a = load_value(instruction.opcode, addr, memory_read_value);
                 memory_store_value = Some(memory_read_value);
                 self.rw(rd, a);
             }
 
             // Store instructions.
-            Opcode::SB => {
-                (a, b, c, addr, memory_read_value) = self.store_rr(instruction);
-                let value = match addr % 4 {
-                    0 => (a & 0x0000_00FF) + (memory_read_value & 0xFFFF_FF00),
-                    1 => ((a & 0x0000_00FF) << 8) + (memory_read_value & 0xFFFF_00FF),
-                    2 => ((a & 0x0000_00FF) << 16) + (memory_read_value & 0xFF00_FFFF),
-                    3 => ((a & 0x0000_00FF) << 24) + (memory_read_value & 0x00FF_FFFF),
-                    _ => unreachable!(),
-                };
-                memory_store_value = Some(value);
-                self.mw_cpu(align(addr), value, MemoryAccessPosition::Memory);
-            }
-            Opcode::SH => {
-                (a, b, c, addr, memory_read_value) = self.store_rr(instruction);
-                if addr % 2 != 0 {
-                    return Err(ExecutionError::InvalidMemoryAccess(Opcode::SH, addr));
-                }
-                let value = match (addr >> 1) % 2 {
-                    0 => (a & 0x0000_FFFF) + (memory_read_value & 0xFFFF_0000),
-                    1 => ((a & 0x0000_FFFF) << 16) + (memory_read_value & 0x0000_FFFF),
-                    _ => unreachable!(),
-                };
-                memory_store_value = Some(value);
-                self.mw_cpu(align(addr), value, MemoryAccessPosition::Memory);
-            }
-            Opcode::SW => {
-                (a, b, c, addr, _) = self.store_rr(instruction);
-                if addr % 4 != 0 {
-                    return Err(ExecutionError::InvalidMemoryAccess(Opcode::SW, addr));
-                }
-                let value = a;
+            Opcode::SB | Opcode::SH | Opcode::SW => {
+                (a, b, c, addr, memory_read_value) = self.store_rr(instruction)?;
+                let value = store_value(instruction.opcode, addr, a, memory_read_value);
                 memory_store_value = Some(value);
                 self.mw_cpu(align(addr), value, MemoryAccessPosition::Memory);
             }
 
             // B-type instructions.
-            Opcode::BEQ => {
+            Opcode::BEQ | Opcode::BNE | Opcode::BLT | Opcode::BGE | Opcode::BLTU | Opcode::BGEU => {
                 (a, b, c) = self.branch_rr(instruction);
-                if a == b {
-                    next_pc = self.state.pc.wrapping_add(c);
-                }
-            }
-            Opcode::BNE => {
-                (a, b, c) = self.branch_rr(instruction);
-                if a != b {
-                    next_pc = self.state.pc.wrapping_add(c);
-                }
-            }
-            Opcode::BLT => {
-                (a, b, c) = self.branch_rr(instruction);
-                if (a as i32) < (b as i32) {
-                    next_pc = self.state.pc.wrapping_add(c);
-                }
-            }
-            Opcode::BGE => {
-                (a, b, c) = self.branch_rr(instruction);
-                if (a as i32) >= (b as i32) {
-                    next_pc = self.state.pc.wrapping_add(c);
-                }
-            }
-            Opcode::BLTU => {
-                (a, b, c) = self.branch_rr(instruction);
-                if a < b {
-                    next_pc = self.state.pc.wrapping_add(c);
-                }
-            }
-            Opcode::BGEU => {
-                (a, b, c) = self.branch_rr(instruction);
-                if a >= b {
+                if branch_taken(instruction.opcode, a, b) {
                     next_pc = self.state.pc.wrapping_add(c);
                 }
             }
@@ -887,6 +1129,9 @@ impl<'a> Executor<'a> {
                 (b, c) = (imm, 0);
                 a = self.state.pc + 4;
                 self.rw(rd, a);
+                if rd == Register::X1 {
+                    self.call_stack.push(self.state.pc);
+                }
                 next_pc = self.state.pc.wrapping_add(imm);
             }
             Opcode::JALR => {
@@ -894,6 +1139,11 @@ impl<'a> Executor<'a> {
                 (b, c) = (self.rr(rs1, MemoryAccessPosition::B), imm);
                 a = self.state.pc + 4;
                 self.rw(rd, a);
+                if rd == Register::X1 {
+                    self.call_stack.push(self.state.pc);
+                } else if rd == Register::X0 && rs1 == Register::X1 {
+                    self.call_stack.pop();
+                }
                 next_pc = b.wrapping_add(c);
             }
 
@@ -915,7 +1165,9 @@ impl<'a> Executor<'a> {
                 b = self.rr(Register::X10, MemoryAccessPosition::B);
                 let syscall = SyscallCode::from_u32(syscall_id);
 
-                if self.print_report && !self.unconstrained {
+                // The syscall may leave unconstrained mode, so check it before executing it.
+                let report_syscall = self.print_report && !self.unconstrained;
+                if report_syscall {
                     self.report.syscall_counts.entry(syscall).and_modify(|c| *c += 1).or_insert(1);
                 }
 
@@ -946,8 +1198,17 @@ impl<'a> Executor<'a> {
                             a = syscall_id;
                         }
 
+                        // If a hook invoked by the syscall failed, fail the execution with it.
+                        if let Some((fd, error)) = precompile_rt.rt.hook_failure.take() {
+                            return Err(ExecutionError::HookFailed { fd, error });
+                        }
+
                         // If the syscall is `HALT` and the exit code is non-zero, return an error.
+                        // If the program reported a panic, return it instead.
                         if syscall == SyscallCode::HALT && precompile_rt.exit_code != 0 {
+                            if let Some((message, location)) = precompile_rt.rt.guest_panic.take() {
+                                return Err(ExecutionError::GuestPanicked { message, location });
+                            }
                             return Err(ExecutionError::HaltWithNonZeroExitCode(
                                 precompile_rt.exit_code,
                             ));
@@ -971,6 +1232,11 @@ impl<'a> Executor<'a> {
                 self.state.clk += precompile_cycles;
                 exit_code = returned_exit_code;
 
+                if report_syscall {
+                    *self.report.syscall_cycles.entry(syscall).or_default() +=
+                        1 + u64::from(precompile_cycles);
+                }
+
                 // Update the syscall counts.
                 let syscall_for_count = syscall.count_map();
                 let syscall_count = self.state.syscall_counts.entry(syscall_for_count).or_insert(0);
@@ -978,6 +1244,11 @@ impl<'a> Executor<'a> {
                     SyscallCode::KECCAK_PERMUTE => (self.opts.split_opts.keccak, 24),
                     SyscallCode::SHA_EXTEND => (self.opts.split_opts.sha_extend, 48),
                     SyscallCode::SHA_COMPRESS => (self.opts.split_opts.sha_compress, 80),
+                    SyscallCode::SHA512_EXTEND => (self.opts.split_opts.sha512_extend, 64),
+                    SyscallCode::SHA512_COMPRESS => (self.opts.split_opts.sha512_compress, 80),
+                    SyscallCode::RIPEMD160_COMPRESS => {
+                        (self.opts.split_opts.ripemd160_compress, 80)
+                    }
                     _ => (self.opts.split_opts.deferred, 1),
                 };
                 let nonce = (((*syscall_count as usize) % threshold) * multiplier) as u32;
@@ -988,64 +1259,6 @@ impl<'a> Executor<'a> {
                 return Err(ExecutionError::Breakpoint());
             }
 
-            // Multiply instructions.
-            Opcode::MUL => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = b.wrapping_mul(c);
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::MULH => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = (((b as i32) as i64).wrapping_mul((c as i32) as i64) >> 32) as u32;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::MULHU => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = ((b as u64).wrapping_mul(c as u64) >> 32) as u32;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::MULHSU => {
-                (rd, b, c) = self.alu_rr(instruction);
-                a = (((b as i32) as i64).wrapping_mul(c as i64) >> 32) as u32;
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::DIV => {
-                (rd, b, c) = self.alu_rr(instruction);
-                if c == 0 {
-                    a = u32::MAX;
-                } else {
-                    a = (b as i32).wrapping_div(c as i32) as u32;
-                }
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::DIVU => {
-                (rd, b, c) = self.alu_rr(instruction);
-                if c == 0 {
-                    a = u32::MAX;
-                } else {
-                    a = b.wrapping_div(c);
-                }
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::REM => {
-                (rd, b, c) = self.alu_rr(instruction);
-                if c == 0 {
-                    a = b;
-                } else {
-                    a = (b as i32).wrapping_rem(c as i32) as u32;
-                }
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-            Opcode::REMU => {
-                (rd, b, c) = self.alu_rr(instruction);
-                if c == 0 {
-                    a = b;
-                } else {
-                    a = b.wrapping_rem(c);
-                }
-                self.alu_rw(instruction, rd, a, b, c, lookup_id);
-            }
-
             // See https://github.com/riscv-non-isa/riscv-asm-manual/blob/master/riscv-asm.md#instruction-aliases
             Opcode::UNIMP => {
                 return Err(ExecutionError::Unimplemented());
@@ -1065,6 +1278,34 @@ impl<'a> Executor<'a> {
             self.state.channel = (self.state.channel + 1) % NUM_BYTE_LOOKUP_CHANNELS;
         }
 
+        // Export the instruction to the trace.
+        if !self.unconstrained
+            && self.trace_exporter.as_ref().is_some_and(|e| e.samples(self.state.global_clk))
+        {
+            let memory = match instruction.opcode.class() {
+                OpcodeClass::Load | OpcodeClass::Store => Some(TraceMemoryAccess {
+                    addr: align(b.wrapping_add(c)),
+                    value: memory_store_value.unwrap_or_default(),
+                    write: instruction.opcode.class() == OpcodeClass::Store,
+                }),
+                _ => None,
+            };
+            let registers = std::array::from_fn(|i| {
+                self.state.memory.get(i as u32).map_or(0, |record| record.value)
+            });
+            let row = TraceRow {
+                clk: self.state.global_clk,
+                pc,
+                opcode: instruction.opcode,
+                operands: [a, b, c],
+                registers,
+                memory,
+            };
+            if let Some(exporter) = &mut self.trace_exporter {
+                exporter.export(&row);
+            }
+        }
+
         // Emit the CPU event for this cycle.
         if self.executor_mode == ExecutorMode::Trace {
             self.emit_cpu(
@@ -1087,9 +1328,21 @@ impl<'a> Executor<'a> {
         Ok(())
     }
 
+    /// Attributes the cycle of the instruction at the current program counter to its function.
+    fn profile_instruction(&mut self) {
+        let function = self.program.symbols.as_ref().and_then(|symbols| {
+            symbols.function_at(self.state.pc).map(|function| function.name.as_str())
+        });
+        let name = function.unwrap_or(UNKNOWN_FUNCTION);
+        match self.report.function_cycles.get_mut(name) {
+            Some(cycles) => *cycles += 1,
+            None => drop(self.report.function_cycles.insert(name.to_string(), 1)),
+        }
+    }
+
     /// Executes one cycle of the program, returning whether the program has finished.
     #[inline]
-    fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
+    pub(crate) fn execute_cycle(&mut self) -> Result<bool, ExecutionError> {
         // Fetch the instruction at the current program counter.
         let instruction = self.fetch();
 
@@ -1102,6 +1355,20 @@ impl<'a> Executor<'a> {
         // Increment the clock.
         self.state.global_clk += 1;
 
+        // Check the watchdog every few thousand cycles, even in unconstrained blocks and without
+        // reaching a shard boundary.
+        if self.state.global_clk % WATCHDOG_INTERVAL == 0 {
+            if self.is_cancelled() {
+                return Err(ExecutionError::Cancelled);
+            }
+            if self.deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                return Err(ExecutionError::TimedOut {
+                    cycles: self.state.global_clk,
+                    pc: self.state.pc,
+                });
+            }
+        }
+
         // If there's not enough cycles left for another instruction, move to the next shard.
         // We multiply by 4 because clk is incremented by 4 for each normal instruction.
         if !self.unconstrained && self.max_syscall_cycles + self.state.clk >= self.shard_size {
@@ -1110,17 +1377,37 @@ impl<'a> Executor<'a> {
             self.state.channel = 0;
 
             self.bump_record();
+
+            if self.is_cancelled() {
+                return Err(ExecutionError::Cancelled);
+            }
         }
 
-        // If the cycle limit is exceeded, return an error.
+        let done = self.is_done();
+
+        // If the cycle limit is reached before the program ends, halt with an error.
         if let Some(max_cycles) = self.max_cycles {
-            if self.state.global_clk >= max_cycles {
-                return Err(ExecutionError::ExceededCycleLimit(max_cycles));
+            if !done && self.state.global_clk >= max_cycles {
+                return Err(ExecutionError::CycleLimitExceeded {
+                    cycles: self.state.global_clk,
+                    pc: self.state.pc,
+                });
             }
         }
 
-        Ok(self.state.pc.wrapping_sub(self.program.pc_base)
-            >= (self.program.instructions.len() * 4) as u32)
+        Ok(done)
+    }
+
+    /// Whether the program counter left the program, ending it.
+    fn is_done(&self) -> bool {
+        self.state.pc.wrapping_sub(self.program.pc_base)
+            >= (self.program.instructions.len() * 4) as u32
+    }
+
+    /// Whether the execution was cancelled through its [`CancellationToken`].
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled)
     }
 
     /// Bump the record.
@@ -1153,6 +1440,7 @@ impl<'a> Executor<'a> {
     /// This function will return an error if the program execution fails.
     pub fn execute_state(&mut self) -> Result<(ExecutionState, bool), ExecutionError> {
         self.memory_checkpoint.clear();
+        self.checkpoint_hook_responses.clear();
         self.executor_mode = ExecutorMode::Checkpoint;
         self.print_report = true;
 
@@ -1162,6 +1450,7 @@ impl<'a> Executor<'a> {
         self.state.memory = memory;
 
         let done = tracing::info_span!("execute").in_scope(|| self.execute())?;
+        checkpoint.hook_responses = std::mem::take(&mut self.checkpoint_hook_responses);
         // Create a checkpoint using `memory_checkpoint`. Just include all memory if `done` since we
         // need it all for MemoryFinalize.
         tracing::info_span!("create memory checkpoint").in_scope(|| {
@@ -1174,7 +1463,7 @@ impl<'a> Executor<'a> {
                     if let Some(record) = record {
                         checkpoint.memory.insert(addr, record);
                     } else {
-                        checkpoint.memory.remove(&addr);
+                        checkpoint.memory.remove(addr);
                     }
                 });
             } else {
@@ -1187,7 +1476,7 @@ impl<'a> Executor<'a> {
         Ok((checkpoint, done))
     }
 
-    fn initialize(&mut self) {
+    pub(crate) fn initialize(&mut self) {
         self.state.clk = 0;
         self.state.channel = 0;
 
@@ -1195,6 +1484,13 @@ impl<'a> Executor<'a> {
         for (addr, value) in &self.program.memory_image {
             self.state.memory.insert(*addr, MemoryRecord { value: *value, shard: 0, timestamp: 0 });
         }
+
+        if let Some(stdin) = self.replayer.as_mut().and_then(Replayer::take_stdin) {
+            self.state.input_stream = stdin;
+        }
+        if let Some(recorder) = &mut self.replay_recorder {
+            recorder.start(&self.state.input_stream, &self.state.args, &self.state.env);
+        }
     }
 
     /// Executes the program without tracing and without emitting events.
@@ -1241,6 +1537,16 @@ impl<'a> Executor<'a> {
         let mut current_shard = self.state.current_shard;
         let mut num_shards_executed = 0;
         loop {
+            // Execute the blocks of instructions not needing the checks of a cycle on the fast
+            // path, when it may be used.
+            if self.can_execute_fast() {
+                self.execute_fast();
+                if self.is_done() {
+                    done = true;
+                    break;
+                }
+            }
+
             if self.execute_cycle()? {
                 done = true;
                 break;
@@ -1295,7 +1601,7 @@ impl<'a> Executor<'a> {
         Ok(done)
     }
 
-    fn postprocess(&mut self) {
+    pub(crate) fn postprocess(&mut self) {
         // Flush remaining stdout/stderr
         for (fd, buf) in &self.io_buf {
             if !buf.is_empty() {
@@ -1311,6 +1617,17 @@ impl<'a> Executor<'a> {
             }
         }
 
+        // Flush the writers of stdout/stderr
+        for writer in self.stdout.iter().chain(&self.stderr) {
+            if let Err(err) = writer.lock().unwrap().flush() {
+                tracing::warn!("failed to flush the output of the program: {err}");
+            }
+        }
+
+        if let Some(exporter) = &mut self.trace_exporter {
+            exporter.flush();
+        }
+
         // Flush trace buf
         if let Some(ref mut buf) = self.trace_buf {
             buf.flush().unwrap();
@@ -1326,12 +1643,18 @@ impl<'a> Executor<'a> {
             tracing::warn!("Not all input bytes were read.");
         }
 
+        if self.pc_histogram {
+            for block in self.report.hot_blocks(&self.program).iter().take(HOT_BLOCKS_LOGGED) {
+                tracing::info!("hot block {block}");
+            }
+        }
+
         // SECTION: Set up all MemoryInitializeFinalizeEvents needed for memory argument.
         let memory_finalize_events = &mut self.record.memory_finalize_events;
 
         // We handle the addr = 0 case separately, as we constrain it to be 0 in the first row
         // of the memory finalize table so it must be first in the array of events.
-        let addr_0_record = self.state.memory.get(&0u32);
+        let addr_0_record = self.state.memory.get(0);
 
         let addr_0_final_record = match addr_0_record {
             Some(record) => record,
@@ -1346,18 +1669,32 @@ impl<'a> Executor<'a> {
         memory_initialize_events.push(addr_0_initialize_event);
 
         self.report.touched_memory_addresses = self.state.memory.len() as u64;
+        self.report.touched_memory_pages = self
+            .state
+            .memory
+            .keys()
+            .map(|addr| addr / MEMORY_PAGE_SIZE)
+            .collect::<HashSet<_>>()
+            .len() as u64;
+        // The heap starts right after the program image, and grows upwards.
+        if let Some(image_end) = self.program.memory_image.keys().next_back() {
+            let heap_start = image_end + WORD_SIZE as u32;
+            let heap_end = self.state.memory.keys().filter(|addr| *addr >= heap_start).max();
+            self.report.peak_heap_bytes =
+                heap_end.map_or(0, |end| u64::from(end + WORD_SIZE as u32 - heap_start));
+        }
         for addr in self.state.memory.keys() {
-            if addr == &0 {
+            if addr == 0 {
                 // Handled above.
                 continue;
             }
 
             // Program memory is initialized in the MemoryProgram chip and doesn't require any
             // events, so we only send init events for other memory addresses.
-            if !self.record.program.memory_image.contains_key(addr) {
+            if !self.record.program.memory_image.contains_key(&addr) {
                 let initial_value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
                 memory_initialize_events.push(MemoryInitializeFinalizeEvent::initialize(
-                    *addr,
+                    addr,
                     *initial_value,
                     true,
                 ));
@@ -1365,7 +1702,7 @@ impl<'a> Executor<'a> {
 
             let record = *self.state.memory.get(addr).unwrap();
             memory_finalize_events
-                .push(MemoryInitializeFinalizeEvent::finalize_from_record(*addr, &record));
+                .push(MemoryInitializeFinalizeEvent::finalize_from_record(addr, &record));
         }
     }
 
@@ -1382,7 +1719,7 @@ impl<'a> Executor<'a> {
             }
         }
 
-        if !self.unconstrained && self.state.global_clk % 10_000_000 == 0 {
+        if !self.unconstrained && self.state.global_clk % LOG_INTERVAL == 0 {
             log::info!("clk = {} pc = 0x{:x?}", self.state.global_clk, self.state.pc);
         }
     }
@@ -1394,6 +1731,150 @@ impl Default for ExecutorMode {
     }
 }
 
+/// Computes the result of the ALU instruction `opcode` on the operands `b` and `c`.
+pub(crate) fn alu(opcode: Opcode, b: u32, c: u32) -> u32 {
+    match opcode {
+        Opcode::ADD => b.wrapping_add(c),
+        Opcode::SUB => b.wrapping_sub(c),
+        Opcode::XOR => b ^ c,
+        Opcode::OR => b | c,
+        Opcode::AND => b & c,
+        Opcode::SLL => b.wrapping_shl(c),
+        Opcode::SRL => b.wrapping_shr(c),
+        Opcode::SRA => (b as i32).wrapping_shr(c) as u32,
+        Opcode::SLT => u32::from((b as i32) < (c as i32)),
+        Opcode::SLTU => u32::from(b < c),
+        Opcode::MUL => b.wrapping_mul(c),
+        Opcode::MULH => (((b as i32) as i64).wrapping_mul((c as i32) as i64) >> 32) as u32,
+        Opcode::MULHU => ((b as u64).wrapping_mul(c as u64) >> 32) as u32,
+        Opcode::MULHSU => (((b as i32) as i64).wrapping_mul(c as i64) >> 32) as u32,
+        Opcode::DIV => {
+            if c == 0 {
+                u32::MAX
+            } else {
+                (b as i32).wrapping_div(c as i32) as u32
+            }
+        }
+        Opcode::DIVU => {
+            if c == 0 {
+                u32::MAX
+            } else {
+                b.wrapping_div(c)
+            }
+        }
+        Opcode::REM => {
+            if c == 0 {
+                b
+            } else {
+                (b as i32).wrapping_rem(c as i32) as u32
+            }
+        }
+        Opcode::REMU => {
+            if c == 0 {
+                b
+            } else {
+                b.wrapping_rem(c)
+            }
+        }
+        _ => bitmanip(opcode, b, c),
+    }
+}
+
+/// Whether the branch instruction `opcode` comparing the operands `a` and `b` is taken.
+pub(crate) fn branch_taken(opcode: Opcode, a: u32, b: u32) -> bool {
+    match opcode {
+        Opcode::BEQ => a == b,
+        Opcode::BNE => a != b,
+        Opcode::BLT => (a as i32) < (b as i32),
+        Opcode::BGE => (a as i32) >= (b as i32),
+        Opcode::BLTU => a < b,
+        Opcode::BGEU => a >= b,
+        _ => panic!("not a branch opcode: {opcode}"),
+    }
+}
+
+/// The value loaded by the load instruction `opcode` at `addr`, from the word containing it.
+pub(crate) fn load_value(opcode: Opcode, addr: u32, word: u32) -> u32 {
+    let byte = word.to_le_bytes()[(addr % 4) as usize];
+    let half = if (addr >> 1) % 2 == 0 { word & 0x0000_FFFF } else { (word & 0xFFFF_0000) >> 16 };
+    match opcode {
+        Opcode::LB => ((byte as i8) as i32) as u32,
+        Opcode::LH => ((half as i16) as i32) as u32,
+        Opcode::LW => word,
+        Opcode::LBU => byte as u32,
+        Opcode::LHU => (half as u16) as u32,
+        _ => panic!("not a load opcode: {opcode}"),
+    }
+}
+
+/// The word stored by the store instruction `opcode` of `a` at `addr`, into the word containing
+/// it.
+pub(crate) fn store_value(opcode: Opcode, addr: u32, a: u32, word: u32) -> u32 {
+    match opcode {
+        Opcode::SB => match addr % 4 {
+            0 => (a & 0x0000_00FF) + (word & 0xFFFF_FF00),
+            1 => ((a & 0x0000_00FF) << 8) + (word & 0xFFFF_00FF),
+            2 => ((a & 0x0000_00FF) << 16) + (word & 0xFF00_FFFF),
+            3 => ((a & 0x0000_00FF) << 24) + (word & 0x00FF_FFFF),
+            _ => unreachable!(),
+        },
+        Opcode::SH => match (addr >> 1) % 2 {
+            0 => (a & 0x0000_FFFF) + (word & 0xFFFF_0000),
+            1 => ((a & 0x0000_FFFF) << 16) + (word & 0x0000_FFFF),
+            _ => unreachable!(),
+        },
+        Opcode::SW => a,
+        _ => panic!("not a store opcode: {opcode}"),
+    }
+}
+
+/// Computes the result of the bit manipulation instruction `opcode` on the operands `b` and `c`.
+///
+/// The unary instructions ignore `c`, and the rotations, shifts and single-bit instructions only
+/// use its low 5 bits.
+#[must_use]
+pub fn bitmanip(opcode: Opcode, b: u32, c: u32) -> u32 {
+    let index = c & 31;
+    match opcode {
+        Opcode::ANDN => b & !c,
+        Opcode::ORN => b | !c,
+        Opcode::XNOR => !(b ^ c),
+        Opcode::CLZ => b.leading_zeros(),
+        Opcode::CTZ => b.trailing_zeros(),
+        Opcode::CPOP => b.count_ones(),
+        Opcode::MAX => (b as i32).max(c as i32) as u32,
+        Opcode::MAXU => b.max(c),
+        Opcode::MIN => (b as i32).min(c as i32) as u32,
+        Opcode::MINU => b.min(c),
+        Opcode::SEXTB => b as i8 as i32 as u32,
+        Opcode::SEXTH => b as i16 as i32 as u32,
+        Opcode::ZEXTH => b & 0xffff,
+        Opcode::ROL => b.rotate_left(index),
+        Opcode::ROR => b.rotate_right(index),
+        Opcode::ORCB => {
+            u32::from_le_bytes(b.to_le_bytes().map(|byte| if byte == 0 { 0 } else { 0xff }))
+        }
+        Opcode::REV8 => b.swap_bytes(),
+        Opcode::SH1ADD => (b << 1).wrapping_add(c),
+        Opcode::SH2ADD => (b << 2).wrapping_add(c),
+        Opcode::SH3ADD => (b << 3).wrapping_add(c),
+        Opcode::BCLR => b & !(1 << index),
+        Opcode::BEXT => (b >> index) & 1,
+        Opcode::BINV => b ^ (1 << index),
+        Opcode::BSET => b | (1 << index),
+        _ => panic!("not a bit manipulation opcode: {opcode}"),
+    }
+}
+
+/// The number of bytes accessed by the load or store instruction `opcode`.
+const fn access_width(opcode: Opcode) -> u32 {
+    match opcode {
+        Opcode::LH | Opcode::LHU | Opcode::SH => 2,
+        Opcode::LW | Opcode::SW => 4,
+        _ => 1,
+    }
+}
+
 // TODO: FIX
 /// Aligns an address to the nearest word below or equal to it.
 #[must_use]
@@ -1408,6 +1889,8 @@ pub const NUM_BYTE_LOOKUP_CHANNELS: u8 = 16;
 #[cfg(test)]
 mod tests {
 
+    use std::time::Duration;
+
     use sp1_stark::SP1CoreOpts;
 
     use crate::programs::tests::{
@@ -1415,9 +1898,16 @@ mod tests {
         ssz_withdrawals_program,
     };
 
-    use crate::Register;
+    use crate::{
+        hook_fd,
+        syscalls::{SyscallCode, FD_ARGS, FD_CYCLE_COUNT, FD_GETENV, FD_PANIC},
+        Frame, FunctionSymbol, HookError, HookResponse, MemoryFaultKind, OpcodeClass, Register,
+        ReplayLog, TraceExportOpts,
+    };
 
-    use super::{Executor, Instruction, Opcode, Program};
+    use super::{
+        CancellationToken, ExecutionError, Executor, Instruction, Opcode, Program, SP1Context,
+    };
 
     fn _assert_send<T: Send>() {}
 
@@ -1426,6 +1916,136 @@ mod tests {
         _assert_send::<Executor>();
     }
 
+    #[test]
+    fn test_cancelled_run() {
+        let program = fibonacci_program();
+        let mut opts = SP1CoreOpts::default();
+        opts.shard_size = 1 << 10;
+        let token = CancellationToken::new();
+        let context = SP1Context::builder().cancellation_token(token.clone()).build();
+        let mut runtime = Executor::with_context(program, opts, context);
+        token.cancel();
+        assert!(matches!(runtime.run(), Err(ExecutionError::Cancelled)));
+    }
+
+    #[test]
+    fn test_cycle_limit() {
+        let context = SP1Context::builder().max_cycles(2).build();
+        let mut runtime = Executor::with_context(simple_program(), SP1CoreOpts::default(), context);
+        assert!(matches!(
+            runtime.run(),
+            Err(ExecutionError::CycleLimitExceeded { cycles: 2, pc: 8 })
+        ));
+
+        // A program ending right at the limit doesn't exceed it.
+        let context = SP1Context::builder().max_cycles(3).build();
+        let mut runtime = Executor::with_context(simple_program(), SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+    }
+
+    #[test]
+    fn test_max_duration() {
+        // A loop jumping to itself without any syscall.
+        let program = Program::new(vec![Instruction::new(Opcode::JAL, 0, 0, 0, true, true)], 0, 0);
+        let context = SP1Context::builder().max_duration(Duration::ZERO).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        let Err(ExecutionError::TimedOut { cycles, pc: 0 }) = runtime.run_fast() else {
+            panic!("the execution didn't time out");
+        };
+        assert!(cycles > 0);
+    }
+
+    #[test]
+    fn test_function_profile() {
+        let mut program = simple_program();
+        program.symbols = Some(
+            [
+                FunctionSymbol { name: "main".to_string(), start: 0, size: 4 },
+                FunctionSymbol { name: "alias".to_string(), start: 0, size: 4 },
+                FunctionSymbol { name: "add".to_string(), start: 4, size: 4 },
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let context = SP1Context::builder().function_profile().build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+        assert_eq!(runtime.report.function_profile(), [("<unknown>", 1), ("add", 1), ("main", 1)]);
+    }
+
+    #[test]
+    fn test_soft_float_cycles() {
+        let mut program = simple_program();
+        program.symbols = Some(
+            [
+                FunctionSymbol { name: "main".to_string(), start: 0, size: 4 },
+                FunctionSymbol { name: "__adddf3".to_string(), start: 4, size: 8 },
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let context = SP1Context::builder().function_profile().build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+        assert_eq!(runtime.report.soft_float_cycles(), 2);
+    }
+
+    #[test]
+    fn test_hot_blocks() {
+        //     addi x29, x0, 3
+        // loop:
+        //     addi x29, x29, -1
+        //     bne x29, x0, loop
+        //     addi x30, x0, 1
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 3, false, true),
+            Instruction::new(Opcode::ADD, 29, 29, -1i32 as u32, false, true),
+            Instruction::new(Opcode::BNE, 29, 0, -4i32 as u32, false, true),
+            Instruction::new(Opcode::ADD, 30, 0, 1, false, true),
+        ];
+        let program = Program::new(instructions, 0, 0);
+        let context = SP1Context::builder().pc_histogram().build();
+        let mut runtime = Executor::with_context(program.clone(), SP1CoreOpts::default(), context);
+        runtime.run_fast().unwrap();
+        assert_eq!(runtime.report.pc_counts[&8], 3);
+
+        let blocks = runtime.report.hot_blocks(&program);
+        let blocks = blocks
+            .iter()
+            .map(|block| (block.start, block.end, block.executions, block.cycles))
+            .collect::<Vec<_>>();
+        assert_eq!(blocks, [(4, 12, 3, 6), (0, 4, 1, 1), (12, 16, 1, 1)]);
+    }
+
+    #[test]
+    fn test_fast_path() {
+        // Counting the pc histogram executes every instruction on the slow path.
+        let run = |max_cycles: Option<u64>, slow: bool| {
+            let context = SP1Context { max_cycles, ..Default::default() };
+            let program = ssz_withdrawals_program();
+            let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+            runtime.pc_histogram = slow;
+            let result = runtime.run_fast().map_err(|err| err.to_string());
+            let memory = runtime
+                .state
+                .memory
+                .iter()
+                .map(|(addr, record)| (addr, (record.value, record.shard, record.timestamp)))
+                .collect::<std::collections::BTreeMap<_, _>>();
+            let state = &runtime.state;
+            let report = &runtime.report;
+            let counts = (report.opcode_counts.clone(), report.syscall_counts.clone());
+            (result, state.global_clk, state.current_shard, state.clk, state.pc, memory, counts)
+        };
+        let (fast, slow) = (run(None, false), run(None, true));
+        assert!(fast.0.is_ok());
+        assert_eq!(fast, slow);
+
+        let (fast, slow) = (run(Some(10_000), false), run(Some(10_000), true));
+        assert!(fast.0.is_err());
+        assert_eq!(fast, slow);
+    }
+
     #[test]
     fn test_simple_program_run() {
         let program = simple_program();
@@ -1434,6 +2054,373 @@ mod tests {
         assert_eq!(runtime.register(Register::X31), 42);
     }
 
+    #[test]
+    fn test_output_writers() {
+        // main:
+        //     addi x5, x0, WRITE
+        //     addi x10, x0, fd
+        //     addi x11, x0, 0x1000
+        //     addi x12, x0, len
+        //     ecall
+        let write = |fd, len| {
+            vec![
+                Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+                Instruction::new(Opcode::ADD, 10, 0, fd, false, true),
+                Instruction::new(Opcode::ADD, 11, 0, 0x1000, false, true),
+                Instruction::new(Opcode::ADD, 12, 0, len, false, true),
+                Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            ]
+        };
+        let mut program = Program::new([write(1, 3), write(2, 2)].concat(), 0, 0);
+        program.memory_image.insert(0x1000, u32::from_le_bytes(*b"hi\n\0"));
+
+        let (mut stdout, mut stderr) = (Vec::new(), Vec::new());
+        let context = SP1Context::builder().stdout(&mut stdout).stderr(&mut stderr).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        drop(runtime);
+        assert_eq!(stdout, b"hi\n");
+        assert_eq!(stderr, b"hi");
+    }
+
+    #[test]
+    fn test_trace_export() {
+        let mut trace = Vec::new();
+        let opts = TraceExportOpts { sample_interval: 2, ..Default::default() };
+        let context = SP1Context::builder().trace_export(&mut trace, opts).build();
+        let mut runtime = Executor::with_context(simple_program(), SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        drop(runtime);
+
+        let rows = String::from_utf8(trace)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1]["clk"], 2);
+        assert_eq!(rows[1]["opcode"], "add");
+        assert_eq!(rows[1]["a"], 42);
+        assert!(rows[1].get("registers").is_none());
+    }
+
+    #[test]
+    fn test_cycle_breakdown() {
+        let program = simple_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let breakdown = runtime.report.cycle_breakdown();
+        assert_eq!(breakdown.total_cycles, 3);
+        assert_eq!(
+            breakdown.opcode_classes.into_iter().collect::<Vec<_>>(),
+            [(OpcodeClass::Arithmetic, 3)]
+        );
+        assert!(breakdown.syscalls.is_empty());
+    }
+
+    #[test]
+    fn test_syscall_stats() {
+        //     addi t0, x0, HINT_LEN
+        //     ecall
+        //     addi t0, x0, HINT_LEN
+        //     ecall
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut runtime = Executor::new(Program::new(instructions, 0, 0), SP1CoreOpts::default());
+        runtime.write_stdin_slice(&[1, 2, 3]);
+        runtime.run().unwrap();
+        let stats = runtime.report.syscall_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!((stats[0].code, stats[0].count, stats[0].cycles), (SyscallCode::HINT_LEN, 2, 2));
+        assert!(!stats[0].code.is_precompile());
+        assert!(runtime.report.to_string().contains("\n      2      2 hint_len\n"));
+    }
+
+    #[test]
+    fn test_is_precompile() {
+        assert!(SyscallCode::SECP256K1_DOUBLE.is_precompile());
+        assert!(SyscallCode::SECP256K1_DECOMPRESS.is_precompile());
+        assert!(SyscallCode::SHA_EXTEND.is_precompile());
+        assert!(!SyscallCode::HINT_LEN.is_precompile());
+        assert!(!SyscallCode::COMMIT.is_precompile());
+    }
+
+    #[test]
+    fn test_args_and_env() {
+        //     addi t0, x0, WRITE
+        //     addi a0, x0, FD_ARGS
+        //     addi a1, x0, 0x1000
+        //     ecall
+        //     addi a0, x0, FD_GETENV
+        //     ecall
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, FD_ARGS, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 10, 0, FD_GETENV, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let context = SP1Context::builder()
+            .args(["prog", "-v"])
+            .env("USER", "root")
+            .env("HOME", "/root")
+            .build();
+        let program = Program::new(instructions, 0, 0);
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        // Nothing reads the replies, so each one is added in front of the previous ones.
+        assert_eq!(
+            runtime.state.input_stream,
+            vec![
+                vec![2, 0, 0, 0],
+                b"HOME".to_vec(),
+                b"/root".to_vec(),
+                b"USER".to_vec(),
+                b"root".to_vec(),
+                vec![2, 0, 0, 0],
+                b"prog".to_vec(),
+                b"-v".to_vec()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_guest_panicked() {
+        //     addi t0, x0, WRITE
+        //     addi a0, x0, FD_PANIC
+        //     addi a1, x0, 0x1000
+        //     addi a2, x0, len
+        //     ecall
+        //     addi t0, x0, HALT
+        //     addi a0, x0, 1
+        //     ecall
+        let report = b"src/main.rs:3:5\nboom";
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, FD_PANIC, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0x1000, false, true),
+            Instruction::new(Opcode::ADD, 12, 0, report.len() as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HALT as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, 1, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        for (i, word) in report.chunks(4).enumerate() {
+            let mut bytes = [0; 4];
+            bytes[..word.len()].copy_from_slice(word);
+            program.memory_image.insert(0x1000 + 4 * i as u32, u32::from_le_bytes(bytes));
+        }
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        let err = runtime.run().unwrap_err();
+        assert_eq!(err.to_string(), "guest panicked at src/main.rs:3:5: boom");
+        let ExecutionError::GuestPanicked { message, location } = err else {
+            panic!("expected a guest panic")
+        };
+        assert_eq!((message.as_str(), location.as_deref()), ("boom", Some("src/main.rs:3:5")));
+    }
+
+    #[test]
+    fn test_cycle_count() {
+        //     addi t0, x0, WRITE
+        //     addi a0, x0, FD_CYCLE_COUNT
+        //     ecall
+        //     ecall
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, FD_CYCLE_COUNT, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut runtime = Executor::new(Program::new(instructions, 0, 0), SP1CoreOpts::default());
+        runtime.run().unwrap();
+        // Nothing reads the counts, so the second one is added in front of the first.
+        assert_eq!(runtime.state.input_stream, vec![3u32.to_le_bytes(), 2u32.to_le_bytes()]);
+    }
+
+    #[test]
+    fn test_hook_failed() {
+        //     addi t0, x0, WRITE
+        //     addi a0, x0, hook_fd("test/fetch")
+        //     ecall
+        let fd = hook_fd("test/fetch");
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, fd, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ];
+        let mut builder = SP1Context::builder();
+        builder
+            .named_hook("test/fetch", |_, _| Err(HookError::retryable("connection reset")))
+            .unwrap();
+        let mut runtime = Executor::with_context(
+            Program::new(instructions, 0, 0),
+            SP1CoreOpts::default(),
+            builder.build(),
+        );
+        let err = runtime.run().unwrap_err();
+        assert!(err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            format!("hook at file descriptor {fd} failed: connection reset")
+        );
+        assert!(!ExecutionError::HookFailed { fd, error: "bad input".into() }.is_retryable());
+    }
+
+    #[test]
+    fn test_request_handler() {
+        //     addi t0, x0, WRITE
+        //     addi a0, x0, hook_fd("test/request")
+        //     ecall
+        //     addi t0, x0, HINT_LEN
+        //     ecall
+        //     add x31, x0, t0
+        let fd = hook_fd("test/request");
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, fd, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 31, 0, 5, false, false),
+        ];
+        let program = Program::new(instructions, 0, 0);
+
+        let mut builder = SP1Context::builder();
+        builder
+            .request_handler("test/request", |request| {
+                assert!(request.is_empty());
+                Ok::<_, HookError>(vec![7; 5])
+            })
+            .unwrap();
+        let mut runtime =
+            Executor::with_context(program.clone(), SP1CoreOpts::default(), builder.build());
+        let (checkpoint, done) = runtime.execute_state().unwrap();
+        assert!(done);
+        assert_eq!(runtime.register(Register::X31), 5);
+        assert_eq!(checkpoint.hook_responses, [HookResponse { fd, result: Ok(vec![vec![7; 5]]) }]);
+
+        // Re-executing from the checkpoint replays the response, without the handler.
+        let mut runtime = Executor::recover(program, checkpoint, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X31), 5);
+    }
+
+    #[test]
+    fn test_record_and_replay() {
+        //     addi t0, x0, WRITE
+        //     addi a0, x0, hook_fd("test/random")
+        //     ecall
+        //     addi t0, x0, HINT_LEN
+        //     ecall
+        //     add x31, x0, t0
+        let fd = hook_fd("test/random");
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::WRITE as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, fd, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 31, 0, 5, false, false),
+        ];
+        let program = Program::new(instructions, 0, 0);
+
+        let mut log = Vec::new();
+        {
+            let mut builder = SP1Context::builder();
+            builder.named_hook("test/random", |_, _| Ok::<_, HookError>(vec![vec![4; 3]])).unwrap();
+            let context = builder.env("SEED", "4").record_replay(&mut log).build();
+            let mut runtime =
+                Executor::with_context(program.clone(), SP1CoreOpts::default(), context);
+            runtime.write_stdin_slice(&[9]);
+            runtime.run().unwrap();
+            assert_eq!(runtime.register(Register::X31), 3);
+        }
+
+        let log = ReplayLog::read(&log[..]).unwrap();
+        assert_eq!(log.stdin, [vec![9]]);
+        assert_eq!(log.env["SEED"], "4");
+        assert_eq!(log.hook_responses, [HookResponse { fd, result: Ok(vec![vec![4; 3]]) }]);
+
+        // The replay neither invokes the hook nor needs the stdin.
+        let mut builder = SP1Context::builder();
+        builder
+            .named_hook("test/random", |_, _| {
+                Err::<Vec<Vec<u8>>, _>(HookError::new("invoked during the replay"))
+            })
+            .unwrap();
+        let context = builder.replay(log.clone()).build();
+        let mut runtime = Executor::with_context(program.clone(), SP1CoreOpts::default(), context);
+        runtime.run().unwrap();
+        assert_eq!(runtime.register(Register::X31), 3);
+        assert_eq!(runtime.state.input_stream, [vec![4; 3], vec![9]]);
+
+        // Replaying a log without the hook response fails.
+        let mut builder = SP1Context::builder();
+        builder.named_hook("test/random", |_, _| Ok::<_, HookError>(vec![])).unwrap();
+        let context = builder.replay(ReplayLog { hook_responses: vec![], ..log }).build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        assert!(matches!(runtime.run(), Err(ExecutionError::HookFailed { .. })));
+    }
+
+    #[test]
+    fn test_snapshot_and_fork() {
+        //     addi t0, x0, HINT_LEN
+        //     ecall
+        //     add x31, x0, t0
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::HINT_LEN as u32, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 31, 0, 5, false, false),
+        ];
+        let context = SP1Context::builder().max_cycles(1).build();
+        let mut runtime = Executor::with_context(
+            Program::new(instructions, 0, 0),
+            SP1CoreOpts::default(),
+            context,
+        );
+        assert!(matches!(runtime.run_fast(), Err(ExecutionError::CycleLimitExceeded { .. })));
+        runtime.max_cycles = None;
+        let snapshot = runtime.snapshot();
+
+        // The fork continues with a different hint.
+        let mut fork = runtime.fork();
+        fork.state.input_stream.push(vec![1, 2, 3]);
+        runtime.state.input_stream.push(vec![1]);
+        fork.run_fast().unwrap();
+        runtime.run_fast().unwrap();
+        assert_eq!((runtime.register(Register::X31), fork.register(Register::X31)), (1, 3));
+
+        // Restoring the snapshot rewinds to before the hint was given.
+        runtime.restore(snapshot);
+        runtime.state.input_stream.push(vec![1, 2]);
+        runtime.run_fast().unwrap();
+        assert_eq!(runtime.register(Register::X31), 2);
+    }
+
+    #[test]
+    fn test_memory_report() {
+        // main:
+        //     addi x29, x0, 5
+        //     sw x29, 0x2000(x0)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 5, false, true),
+            Instruction::new(Opcode::SW, 29, 0, 0x2000, false, true),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image.insert(0x1000, 0);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        // The registers, the program image and the stored word are each on their own page.
+        assert_eq!(runtime.report.touched_memory_pages, 3);
+        assert_eq!(runtime.report.peak_heap_bytes, 0x1000);
+    }
+
     #[test]
     fn test_fibonacci_program_run() {
         let program = fibonacci_program();
@@ -1799,6 +2786,105 @@ mod tests {
         assert_eq!(runtime.state.pc, 108);
     }
 
+    #[test]
+    fn test_memory_faults() {
+        //     jal ra, 8
+        //     unimp
+        //     addi x29, x0, 0x10
+        //     lw x30, 0(x29)
+        let instructions = vec![
+            Instruction::new(Opcode::JAL, 1, 8, 0, true, true),
+            Instruction::new(Opcode::UNIMP, 0, 0, 0, true, true),
+            Instruction::new(Opcode::ADD, 29, 0, 0x10, false, true),
+            Instruction::new(Opcode::LW, 30, 29, 0, false, true),
+        ];
+        let mut runtime = Executor::new(Program::new(instructions, 0, 0), SP1CoreOpts::default());
+        let Err(ExecutionError::InvalidMemoryAccess(fault)) = runtime.run() else {
+            panic!("expected an invalid memory access");
+        };
+        assert_eq!(fault.kind, MemoryFaultKind::OutOfBounds);
+        assert_eq!((fault.pc, fault.addr, fault.width), (12, 0x10, 4));
+        assert_eq!(fault.backtrace, vec![Frame::new(12), Frame::new(0)]);
+
+        //     addi x29, x0, 0x1001
+        //     sh x30, 0(x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x1001, false, true),
+            Instruction::new(Opcode::SH, 30, 29, 0, false, true),
+        ];
+        let mut runtime = Executor::new(Program::new(instructions, 0, 0), SP1CoreOpts::default());
+        let Err(ExecutionError::InvalidMemoryAccess(fault)) = runtime.run() else {
+            panic!("expected an invalid memory access");
+        };
+        assert_eq!(fault.kind, MemoryFaultKind::Misaligned);
+        assert!(fault.is_write());
+        assert_eq!(
+            fault.to_string(),
+            "misaligned 2-byte store to 0x00001001 by sh at pc 0x00000004\n  0: 0x00000004"
+        );
+    }
+
+    #[test]
+    fn test_max_memory() {
+        //     addi x29, x0, 0x1000
+        //     sw x29, 0(x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x1000, false, true),
+            Instruction::new(Opcode::SW, 29, 29, 0, false, true),
+        ];
+        let context = SP1Context::builder().max_memory(0x1000).build();
+        let mut runtime = Executor::with_context(
+            Program::new(instructions, 0, 0),
+            SP1CoreOpts::default(),
+            context,
+        );
+        let Err(ExecutionError::InvalidMemoryAccess(fault)) = runtime.run() else {
+            panic!("expected an invalid memory access");
+        };
+        assert_eq!((fault.kind, fault.addr), (MemoryFaultKind::OutOfBounds, 0x1000));
+    }
+
+    #[test]
+    fn test_strict_memory() {
+        //     addi x29, x0, 0x1000
+        //     sb x29, 2(x29)
+        //     lb x30, 3(x29)
+        //     lw x30, 0x1000(x29)
+        //     lw x31, 4(x29)
+        let instructions = vec![
+            Instruction::new(Opcode::ADD, 29, 0, 0x1000, false, true),
+            Instruction::new(Opcode::SB, 29, 29, 2, false, true),
+            Instruction::new(Opcode::LB, 30, 29, 3, false, true),
+            Instruction::new(Opcode::LW, 30, 29, 0x1000, false, true),
+            Instruction::new(Opcode::LW, 31, 29, 4, false, true),
+        ];
+        let mut program = Program::new(instructions, 0, 0);
+        program.memory_image.insert(0x2000, 7);
+        let mut runtime = Executor::new(program.clone(), SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let context = SP1Context::builder().strict_memory().build();
+        let mut runtime = Executor::with_context(program, SP1CoreOpts::default(), context);
+        let Err(ExecutionError::InvalidMemoryAccess(fault)) = runtime.run() else {
+            panic!("expected an invalid memory access");
+        };
+        assert_eq!(fault.kind, MemoryFaultKind::Uninitialized);
+        assert_eq!((fault.pc, fault.addr), (16, 0x1004));
+        assert_eq!(
+            fault.to_string(),
+            "uninitialized 4-byte load from 0x00001004 by lw at pc 0x00000010\n  0: 0x00000010"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "past the maximum memory")]
+    fn test_max_memory_too_small() {
+        let mut program = Program::new(vec![], 0, 0);
+        program.memory_image.insert(0x1000, 0);
+        let context = SP1Context::builder().max_memory(0x1000).build();
+        let _ = Executor::with_context(program, SP1CoreOpts::default(), context);
+    }
+
     fn simple_op_code_test(opcode: Opcode, expected: u32, a: u32, b: u32) {
         let instructions = vec![
             Instruction::new(Opcode::ADD, 10, 0, a, false, true),
@@ -1980,6 +3066,95 @@ mod tests {
         simple_op_code_test(Opcode::SRA, 0xffffffff, 0x81818181, 31);
     }
 
+    #[test]
+    #[allow(clippy::unreadable_literal)]
+    fn bitmanip_tests() {
+        simple_op_code_test(Opcode::ANDN, 0x0000f0f0, 0x0000ffff, 0xff000f0f);
+        simple_op_code_test(Opcode::ORN, 0xff0fffff, 0x000f0000, 0x00f00000);
+        simple_op_code_test(Opcode::XNOR, 0xfff0f0ff, 0x0000ff00, 0x000ff000);
+
+        simple_op_code_test(Opcode::CLZ, 32, 0, 0);
+        simple_op_code_test(Opcode::CLZ, 0, 0x80000000, 0);
+        simple_op_code_test(Opcode::CLZ, 15, 0x00010000, 0);
+        simple_op_code_test(Opcode::CTZ, 32, 0, 0);
+        simple_op_code_test(Opcode::CTZ, 4, 0x12345670, 0);
+        simple_op_code_test(Opcode::CPOP, 32, 0xffffffff, 0);
+        simple_op_code_test(Opcode::CPOP, 13, 0x12345678, 0);
+
+        simple_op_code_test(Opcode::MAX, 1, neg(1), 1);
+        simple_op_code_test(Opcode::MAXU, neg(1), neg(1), 1);
+        simple_op_code_test(Opcode::MIN, neg(1), neg(1), 1);
+        simple_op_code_test(Opcode::MINU, 1, neg(1), 1);
+
+        simple_op_code_test(Opcode::SEXTB, 0xffffff80, 0x12345680, 0);
+        simple_op_code_test(Opcode::SEXTB, 0x0000007f, 0x1234567f, 0);
+        simple_op_code_test(Opcode::SEXTH, 0xffff8765, 0x12348765, 0);
+        simple_op_code_test(Opcode::ZEXTH, 0x00008765, 0x12348765, 0);
+
+        simple_op_code_test(Opcode::ROL, 0x23456781, 0x12345678, 4);
+        simple_op_code_test(Opcode::ROL, 0x12345678, 0x12345678, 32);
+        simple_op_code_test(Opcode::ROR, 0x81234567, 0x12345678, 4);
+        simple_op_code_test(Opcode::ORCB, 0xff00ffff, 0x01000280, 0);
+        simple_op_code_test(Opcode::REV8, 0x78563412, 0x12345678, 0);
+
+        simple_op_code_test(Opcode::SH1ADD, 7, 3, 1);
+        simple_op_code_test(Opcode::SH2ADD, 13, 3, 1);
+        simple_op_code_test(Opcode::SH3ADD, 0x00000007, 0xffffffff, 15);
+
+        simple_op_code_test(Opcode::BCLR, 0xfffffffe, 0xffffffff, 32);
+        simple_op_code_test(Opcode::BEXT, 1, 0x80000000, 31);
+        simple_op_code_test(Opcode::BINV, 0x00000010, 0x00000000, 4);
+        simple_op_code_test(Opcode::BSET, 0x80000001, 0x00000001, 31);
+    }
+
+    #[test]
+    #[allow(clippy::unreadable_literal)]
+    fn bitmanip_transpile_tests() {
+        let instructions = crate::disassembler::transpile(
+            &[
+                0x4020f1b3, // andn x3, x1, x2
+                0x60009193, // clz x3, x1
+                0x6070d193, // rori x3, x1, 7
+                0x29f09193, // bseti x3, x1, 31
+                0x6980d193, // rev8 x3, x1
+            ],
+            0,
+        )
+        .unwrap();
+        let decoded = instructions
+            .iter()
+            .map(|instruction| {
+                (instruction.opcode, instruction.op_a, instruction.op_b, instruction.op_c)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decoded,
+            vec![
+                (Opcode::ANDN, 3, 1, 2),
+                (Opcode::CLZ, 3, 1, 0),
+                (Opcode::ROR, 3, 1, 7),
+                (Opcode::BSET, 3, 1, 31),
+                (Opcode::REV8, 3, 1, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn float_transpile_error() {
+        let err = crate::disassembler::transpile(
+            &[
+                0x00000013, // nop
+                0x003170d3, // fadd.s f1, f2, f3
+            ],
+            0x1000,
+        )
+        .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "floating point instruction 0x003170d3 at pc 0x00001004: the F and D extensions \
+             aren't supported"
+        ));
+    }
+
     #[test]
     #[allow(clippy::unreadable_literal)]
     fn test_simple_memory_program_run() {
@@ -0,0 +1,300 @@
+use crate::{
+    events::{MemoryAccessPosition, MemoryRecord},
+    executor::{
+        align, alu, branch_taken, load_value, store_value, ExecutorMode, LOG_INTERVAL,
+        NUM_BYTE_LOOKUP_CHANNELS, WATCHDOG_INTERVAL,
+    },
+    memory::Entry,
+    report::ExecutionReport,
+    Executor, Instruction, Opcode, OpcodeClass, Register,
+};
+
+/// The basic blocks of a program decoded for the fast path of the simple mode, see
+/// [`Executor::execute_fast`].
+///
+/// A block is the straight-line run of instructions starting at an instruction, up to the first
+/// branch or jump, included, or the first syscall, excluded, which the fast path leaves to
+/// [`Executor::execute_cycle`].
+#[derive(Debug, Clone, Default)]
+pub(crate) struct BlockCache {
+    /// The block starting at each instruction of the program, decoded the first time it runs.
+    blocks: Vec<Option<Block>>,
+    /// The opcodes of the decoded blocks, with the number of their instructions run since they
+    /// were last added to the report.
+    opcode_counts: Vec<(Opcode, u64)>,
+}
+
+/// A block of a [`BlockCache`].
+#[derive(Debug, Clone)]
+struct Block {
+    /// The number of instructions of the block.
+    len: usize,
+    /// The indices of the opcodes of the block in [`BlockCache::opcode_counts`], with their
+    /// number of instructions in the block.
+    opcodes: Box<[(usize, u64)]>,
+}
+
+impl BlockCache {
+    /// The number of instructions of the block starting at the instruction at `index`, or `None`
+    /// if it is past the end of the program.
+    fn block_len(&mut self, index: usize, instructions: &[Instruction]) -> Option<usize> {
+        if index >= instructions.len() {
+            return None;
+        }
+        if self.blocks.is_empty() {
+            self.blocks.resize_with(instructions.len(), || None);
+        }
+        if self.blocks[index].is_none() {
+            self.blocks[index] = Some(self.decode(&instructions[index..]));
+        }
+        self.blocks[index].as_ref().map(|block| block.len)
+    }
+
+    /// Decodes the block at the start of `instructions`.
+    fn decode(&mut self, instructions: &[Instruction]) -> Block {
+        let mut len = 0;
+        let mut opcodes = Vec::<(usize, u64)>::new();
+        for instruction in instructions {
+            if matches!(instruction.opcode, Opcode::ECALL | Opcode::EBREAK | Opcode::UNIMP) {
+                break;
+            }
+            len += 1;
+
+            let position = self.opcode_counts.iter().position(|&(o, _)| o == instruction.opcode);
+            let index = position.unwrap_or_else(|| {
+                self.opcode_counts.push((instruction.opcode, 0));
+                self.opcode_counts.len() - 1
+            });
+            match opcodes.iter_mut().find(|(i, _)| *i == index) {
+                Some((_, count)) => *count += 1,
+                None => opcodes.push((index, 1)),
+            }
+
+            if instruction.is_branch_instruction() || instruction.is_jump_instruction() {
+                break;
+            }
+        }
+        Block { len, opcodes: opcodes.into() }
+    }
+
+    /// Counts a run of the whole block starting at the instruction at `index`.
+    fn count(&mut self, index: usize) {
+        if let Some(block) = &self.blocks[index] {
+            for &(opcode, count) in block.opcodes.iter() {
+                self.opcode_counts[opcode].1 += count;
+            }
+        }
+    }
+
+    /// Adds the counted instructions to the opcode counts of `report`.
+    fn flush(&mut self, report: &mut ExecutionReport) {
+        for (opcode, count) in &mut self.opcode_counts {
+            if *count > 0 {
+                *report.opcode_counts.entry(*opcode).or_default() += std::mem::take(count);
+            }
+        }
+    }
+}
+
+impl Executor<'_> {
+    /// Whether the next instructions may run on the fast path: in the simple mode, outside of
+    /// unconstrained blocks, and without the diagnostics observing every instruction.
+    pub(crate) fn can_execute_fast(&self) -> bool {
+        self.executor_mode == ExecutorMode::Simple
+            && !self.unconstrained
+            && self.trace_exporter.is_none()
+            && self.trace_buf.is_none()
+            && !self.function_profile
+            && !self.pc_histogram
+            && self.watched_memory.is_empty()
+    }
+
+    /// Executes the instructions of the program on the fast path, a block at a time, until the
+    /// next one must run on [`Self::execute_cycle`]: a syscall, an instruction that faults, or
+    /// the cycle of a shard boundary, of a watchdog check or of the cycle limit.
+    ///
+    /// The resulting state and report are those of executing the instructions one at a time,
+    /// without recording the events and memory accesses the simple mode doesn't keep.
+    pub(crate) fn execute_fast(&mut self) {
+        let budget = self.fast_budget();
+        let program = self.program.clone();
+        let mut cache = std::mem::take(&mut self.block_cache);
+        let mut executed = 0;
+        while executed < budget {
+            let index = (self.state.pc.wrapping_sub(program.pc_base) / 4) as usize;
+            let Some(block_len) = cache.block_len(index, &program.instructions) else {
+                break;
+            };
+            let len = block_len.min((budget - executed) as usize);
+            let block = &program.instructions[index..index + len];
+            let ran = block
+                .iter()
+                .take_while(|instruction| self.execute_fast_instruction(instruction))
+                .count();
+            executed += ran as u64;
+
+            if self.print_report {
+                if ran == block_len {
+                    cache.count(index);
+                } else {
+                    for instruction in &block[..ran] {
+                        *self.report.opcode_counts.entry(instruction.opcode).or_default() += 1;
+                    }
+                }
+            }
+            if ran < block_len || block_len == 0 {
+                break;
+            }
+        }
+        cache.flush(&mut self.report);
+        self.block_cache = cache;
+
+        self.state.global_clk += executed;
+        self.state.channel = ((u64::from(self.state.channel) + executed)
+            % u64::from(NUM_BYTE_LOOKUP_CHANNELS)) as u8;
+    }
+
+    /// The number of instructions the fast path may execute before the next one must run on
+    /// [`Self::execute_cycle`] for its checks at the end of a cycle.
+    fn fast_budget(&self) -> u64 {
+        let clk = self.state.global_clk;
+        // The shard ends after the instruction leaving less than `max_syscall_cycles` cycles.
+        let shard =
+            self.shard_size.saturating_sub(self.max_syscall_cycles + self.state.clk + 1) / 4;
+        let watchdog = WATCHDOG_INTERVAL - 1 - clk % WATCHDOG_INTERVAL;
+        let log = (LOG_INTERVAL - clk % LOG_INTERVAL) % LOG_INTERVAL;
+        let cycles =
+            self.max_cycles.map_or(u64::MAX, |max_cycles| max_cycles.saturating_sub(clk + 1));
+        u64::from(shard).min(watchdog).min(log).min(cycles)
+    }
+
+    /// Executes `instruction` like [`Self::execute_instruction`] in the simple mode, or returns
+    /// `false` before changing the state if it must run on [`Self::execute_cycle`].
+    #[inline]
+    fn execute_fast_instruction(&mut self, instruction: &Instruction) -> bool {
+        let opcode = instruction.opcode;
+        let mut next_pc = self.state.pc.wrapping_add(4);
+        match opcode.class() {
+            OpcodeClass::Load => {
+                let (rd, rs1, imm) = instruction.i_type();
+                let addr = self.fast_rr_peek(rs1).wrapping_add(imm);
+                if self.memory_fault(opcode, addr).is_some() {
+                    return false;
+                }
+                self.fast_rr(rs1, MemoryAccessPosition::B);
+                let word = self.fast_mr(align(addr), MemoryAccessPosition::Memory);
+                self.fast_rw(rd, load_value(opcode, addr, word));
+            }
+            OpcodeClass::Store => {
+                let (rs1, rs2, imm) = instruction.s_type();
+                let addr = self.fast_rr_peek(rs2).wrapping_add(imm);
+                if self.memory_fault(opcode, addr).is_some() {
+                    return false;
+                }
+                self.fast_rr(rs2, MemoryAccessPosition::B);
+                let a = self.fast_rr(rs1, MemoryAccessPosition::A);
+                let word = self.word(align(addr));
+                let value = store_value(opcode, addr, a, word);
+                self.fast_mw(align(addr), value, MemoryAccessPosition::Memory);
+            }
+            OpcodeClass::Branch => {
+                let (rs1, rs2, imm) = instruction.b_type();
+                let b = self.fast_rr(rs2, MemoryAccessPosition::B);
+                let a = self.fast_rr(rs1, MemoryAccessPosition::A);
+                if branch_taken(opcode, a, b) {
+                    next_pc = self.state.pc.wrapping_add(imm);
+                }
+            }
+            OpcodeClass::Jump if opcode == Opcode::JAL => {
+                let (rd, imm) = instruction.j_type();
+                self.fast_rw(rd, self.state.pc + 4);
+                if rd == Register::X1 {
+                    self.call_stack.push(self.state.pc);
+                }
+                next_pc = self.state.pc.wrapping_add(imm);
+            }
+            OpcodeClass::Jump => {
+                let (rd, rs1, imm) = instruction.i_type();
+                let b = self.fast_rr(rs1, MemoryAccessPosition::B);
+                self.fast_rw(rd, self.state.pc + 4);
+                if rd == Register::X1 {
+                    self.call_stack.push(self.state.pc);
+                } else if rd == Register::X0 && rs1 == Register::X1 {
+                    self.call_stack.pop();
+                }
+                next_pc = b.wrapping_add(imm);
+            }
+            OpcodeClass::UpperImmediate => {
+                let (rd, imm) = instruction.u_type();
+                self.fast_rw(rd, self.state.pc.wrapping_add(imm));
+            }
+            OpcodeClass::System => return false,
+            _ => {
+                let (rd, b, c) = if !instruction.imm_c {
+                    let (rd, rs1, rs2) = instruction.r_type();
+                    let c = self.fast_rr(rs2, MemoryAccessPosition::C);
+                    (rd, self.fast_rr(rs1, MemoryAccessPosition::B), c)
+                } else if !instruction.imm_b {
+                    let (rd, rs1, imm) = instruction.i_type();
+                    (rd, self.fast_rr(rs1, MemoryAccessPosition::B), imm)
+                } else {
+                    (Register::from_u32(instruction.op_a), instruction.op_b, instruction.op_c)
+                };
+                self.fast_rw(rd, alu(opcode, b, c));
+            }
+        }
+        self.state.pc = next_pc;
+        self.state.clk += 4;
+        true
+    }
+
+    /// The value of `register`, without accessing it.
+    #[inline]
+    fn fast_rr_peek(&self, register: Register) -> u32 {
+        self.state.memory.get(register as u32).map_or(0, |record| record.value)
+    }
+
+    /// Reads `register` like [`Self::rr`] in the simple mode.
+    #[inline]
+    fn fast_rr(&mut self, register: Register, position: MemoryAccessPosition) -> u32 {
+        self.fast_mr(register as u32, position)
+    }
+
+    /// Writes `register` like [`Self::rw`] in the simple mode.
+    #[inline]
+    fn fast_rw(&mut self, register: Register, value: u32) {
+        let value = if register == Register::X0 { 0 } else { value };
+        self.fast_mw(register as u32, value, MemoryAccessPosition::A);
+    }
+
+    /// Reads the word at `addr` like [`Self::mr`], without the bookkeeping of the other modes,
+    /// of unconstrained blocks and of watched addresses.
+    #[inline]
+    fn fast_mr(&mut self, addr: u32, position: MemoryAccessPosition) -> u32 {
+        let (shard, timestamp) = (self.shard(), self.timestamp(&position));
+        let record = match self.state.memory.entry(addr) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = self.state.uninitialized_memory.get(addr).unwrap_or(&0);
+                entry.insert(MemoryRecord { value: *value, shard: 0, timestamp: 0 })
+            }
+        };
+        record.shard = shard;
+        record.timestamp = timestamp;
+        record.value
+    }
+
+    /// Writes the word at `addr` like [`Self::mw`], without the bookkeeping of the other modes,
+    /// of unconstrained blocks and of watched addresses.
+    #[inline]
+    fn fast_mw(&mut self, addr: u32, value: u32, position: MemoryAccessPosition) {
+        let (shard, timestamp) = (self.shard(), self.timestamp(&position));
+        let record = MemoryRecord { shard, timestamp, value };
+        match self.state.memory.entry(addr) {
+            Entry::Occupied(mut entry) => *entry.get_mut() = record,
+            Entry::Vacant(entry) => {
+                entry.insert(record);
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{debug_info::Frame, Opcode};
+
+/// The addresses the loads and stores of a program may access.
+///
+/// The words below `0x20` are the registers, and the addresses from `0x7800_0000` on are past the
+/// end of the heap and don't fit in a field element of the proof.
+pub const VALID_MEMORY_ADDRESSES: Range<u32> = 0x20..0x7800_0000;
+
+/// Why a memory access faulted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MemoryFaultKind {
+    /// The address isn't a multiple of the width of the access.
+    Misaligned,
+    /// The address is out of [`VALID_MEMORY_ADDRESSES`], or past the
+    /// [`SP1Context::max_memory`](crate::SP1Context::max_memory) of the execution.
+    OutOfBounds,
+    /// The load reads a word that was never written, nor loaded from the program image or from a
+    /// hint, reported when [`SP1Context::strict_memory`](crate::SP1Context::strict_memory) is set.
+    Uninitialized,
+}
+
+/// A faulting load or store, reported by [`ExecutionError::InvalidMemoryAccess`].
+///
+/// [`ExecutionError::InvalidMemoryAccess`]: crate::ExecutionError::InvalidMemoryAccess
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryFault {
+    /// Why the access faulted.
+    pub kind: MemoryFaultKind,
+    /// The opcode of the load or store.
+    pub opcode: Opcode,
+    /// The program counter of the load or store.
+    pub pc: u32,
+    /// The address accessed.
+    pub addr: u32,
+    /// The number of bytes accessed.
+    pub width: u32,
+    /// The backtrace of the access, innermost first, symbolized if the program has
+    /// [`DebugInfo`](crate::DebugInfo).
+    ///
+    /// The calls are those seen by the executor, so they are missing when resuming from a
+    /// checkpoint, and calls that don't link through `ra` aren't in it.
+    pub backtrace: Vec<Frame>,
+}
+
+impl MemoryFault {
+    /// Whether the access is a store.
+    #[must_use]
+    pub const fn is_write(&self) -> bool {
+        matches!(self.opcode, Opcode::SB | Opcode::SH | Opcode::SW)
+    }
+}
+
+impl Display for MemoryFault {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let kind = match self.kind {
+            MemoryFaultKind::Misaligned => "misaligned",
+            MemoryFaultKind::OutOfBounds => "out-of-bounds",
+            MemoryFaultKind::Uninitialized => "uninitialized",
+        };
+        let (access, preposition) =
+            if self.is_write() { ("store", "to") } else { ("load", "from") };
+        write!(
+            f,
+            "{kind} {}-byte {access} {preposition} 0x{:08x} by {} at pc 0x{:08x}",
+            self.width,
+            self.addr,
+            self.opcode.mnemonic(),
+            self.pc
+        )?;
+        for (i, frame) in self.backtrace.iter().enumerate() {
+            write!(f, "\n  {i}: {frame}")?;
+        }
+        Ok(())
+    }
+}
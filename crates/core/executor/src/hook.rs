@@ -1,9 +1,16 @@
-use core::fmt::Debug;
+use core::fmt::{Debug, Display};
 
 use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
 use hashbrown::HashMap;
-use sp1_curves::k256::{Invert, RecoveryId, Signature, VerifyingKey};
+use num::{BigUint, One};
+use serde::{Deserialize, Serialize};
+use sp1_curves::{
+    k256::{Invert, RecoveryId, Signature, VerifyingKey},
+    params::FieldParameters,
+    weierstrass::secp256k1::Secp256k1BaseField,
+};
+use thiserror::Error;
 
 use crate::Executor;
 
@@ -13,18 +20,117 @@ pub type BoxedHook<'a> = Arc<RwLock<dyn Hook + Send + Sync + 'a>>;
 /// The file descriptor through which to access `hook_ecrecover`.
 pub const FD_ECRECOVER_HOOK: u32 = 5;
 
+/// The file descriptor through which to access `hook_secp256k1_lift_x`.
+///
+/// Comes after the file descriptors handled by the `write` syscall itself, such as
+/// [`crate::syscalls::FD_PANIC`].
+pub const FD_SECP256K1_LIFT_X_HOOK: u32 = 9;
+
+/// The bit set in the file descriptors of named hooks, keeping them apart from the fixed file
+/// descriptors such as [`FD_ECRECOVER_HOOK`].
+pub const NAMED_HOOK_FD_BIT: u32 = 1 << 31;
+
+/// The file descriptor through which to access the hook registered under `name`.
+///
+/// The name is hashed with 32-bit FNV-1a, with [`NAMED_HOOK_FD_BIT`] set. Must be kept in sync
+/// with `sp1_zkvm::io::hook_fd`, which programs use to invoke the hook.
+#[must_use]
+pub const fn hook_fd(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut hash: u32 = 0x811c_9dc5;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        i += 1;
+    }
+    hash | NAMED_HOOK_FD_BIT
+}
+
+/// An error registering a named hook.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HookRegistrationError {
+    /// A hook was already registered under the same name.
+    #[error("a hook named `{0}` is already registered")]
+    DuplicateName(String),
+
+    /// The name hashes to the file descriptor of a hook registered under another name.
+    #[error("the hook names `{name}` and `{existing}` both map to file descriptor {fd}")]
+    Collision {
+        /// The name being registered.
+        name: String,
+        /// The name of the hook already registered at `fd`.
+        existing: String,
+        /// The file descriptor both names map to.
+        fd: u32,
+    },
+}
+
+/// The error of a hook, failing the execution with [`crate::ExecutionError::HookFailed`].
+#[derive(Error, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[error("{message}")]
+pub struct HookError {
+    /// The description of the failure.
+    pub message: String,
+    /// Whether the failure is transient, e.g. a network request that failed, so that executing
+    /// the program again may succeed.
+    pub retryable: bool,
+}
+
+impl HookError {
+    /// A failure that executing the program again won't fix, e.g. invalid input from the program.
+    #[must_use]
+    pub fn new(message: impl Display) -> Self {
+        Self { message: message.to_string(), retryable: false }
+    }
+
+    /// A transient failure, after which executing the program again may succeed.
+    #[must_use]
+    pub fn retryable(message: impl Display) -> Self {
+        Self { message: message.to_string(), retryable: true }
+    }
+}
+
+impl From<String> for HookError {
+    fn from(message: String) -> Self {
+        Self::new(message)
+    }
+}
+
+impl From<&str> for HookError {
+    fn from(message: &str) -> Self {
+        Self::new(message)
+    }
+}
+
 /// A runtime hook. May be called during execution by writing to a specified file descriptor,
 /// accepting and returning arbitrary data.
 pub trait Hook {
     /// Invoke the runtime hook with a standard environment and arbitrary data.
-    /// Returns the computed data.
-    fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>>;
+    ///
+    /// Returns the computed data, or the error failing the execution with
+    /// [`crate::ExecutionError::HookFailed`].
+    fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Result<Vec<Vec<u8>>, HookError>;
 }
 
 impl<F: FnMut(HookEnv, &[u8]) -> Vec<Vec<u8>>> Hook for F {
-    /// Invokes the function `self` as a hook.
-    fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
-        self(env, buf)
+    /// Invokes the function `self` as a hook that can't fail.
+    fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Result<Vec<Vec<u8>>, HookError> {
+        Ok(self(env, buf))
+    }
+}
+
+/// A runtime hook that may fail, wrapping a function returning a [`Result`].
+pub struct FallibleHook<F>(pub F);
+
+impl<F, E> Hook for FallibleHook<F>
+where
+    F: FnMut(HookEnv, &[u8]) -> Result<Vec<Vec<u8>>, E>,
+    E: Into<HookError>,
+{
+    /// Invokes the function as a hook.
+    fn invoke_hook(&mut self, env: HookEnv, buf: &[u8]) -> Result<Vec<Vec<u8>>, HookError> {
+        (self.0)(env, buf).map_err(Into::into)
     }
 }
 
@@ -76,6 +182,7 @@ impl<'a> Default for HookRegistry<'a> {
             // Note: To ensure any `fd` value is synced with `zkvm/precompiles/src/io.rs`,
             // add an assertion to the test `hook_fds_match` below.
             (FD_ECRECOVER_HOOK, hookify(hook_ecrecover)),
+            (FD_SECP256K1_LIFT_X_HOOK, hookify(hook_secp256k1_lift_x)),
         ]);
 
         Self { table }
@@ -141,6 +248,37 @@ pub fn hook_ecrecover(_: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
     vec![bytes.to_vec(), s_inverse.to_bytes().to_vec()]
 }
 
+/// Tells whether the big-endian `x` is the x coordinate of a point of the Secp256k1 curve, for the
+/// `lift_x` of BIP-340 Schnorr signatures.
+///
+/// The result is a single byte set to 1 if `x^3 + 7` is a square, which the program then
+/// decompresses with the decompress precompile. Otherwise, the byte is 0 and is followed by the
+/// 32 big-endian bytes of the square root of `-(x^3 + 7)`, which proves that `x^3 + 7` isn't a
+/// square as -1 isn't one.
+///
+/// WARNING: This function is used to check the x coordinate outside of the zkVM context. These
+/// values must be constrained by the zkVM for correctness.
+#[must_use]
+pub fn hook_secp256k1_lift_x(_: HookEnv, buf: &[u8]) -> Vec<Vec<u8>> {
+    assert_eq!(buf.len(), 32, "lift_x input should have length 32");
+    let modulus = Secp256k1BaseField::modulus();
+    let x = BigUint::from_bytes_be(buf);
+    assert!(x < modulus, "the x coordinate should be reduced");
+
+    let y_2 = (&x * &x * &x + BigUint::from(7u32)) % &modulus;
+    let legendre = y_2.modpow(&((&modulus - BigUint::one()) >> 1), &modulus);
+    if legendre <= BigUint::one() {
+        return vec![vec![1]];
+    }
+
+    // The modulus is 3 mod 4, so the root is `n^((p + 1) / 4)`.
+    let root = (&modulus - y_2).modpow(&((&modulus + BigUint::one()) >> 2), &modulus);
+    let mut result = vec![0; 33];
+    let root_bytes = root.to_bytes_be();
+    result[33 - root_bytes.len()..].copy_from_slice(&root_bytes);
+    vec![result]
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
@@ -149,6 +287,41 @@ pub mod tests {
     pub fn hook_fds_match() {
         use sp1_zkvm::lib::io;
         assert_eq!(FD_ECRECOVER_HOOK, io::FD_ECRECOVER_HOOK);
+        assert_eq!(FD_SECP256K1_LIFT_X_HOOK, io::FD_SECP256K1_LIFT_X_HOOK);
+        for name in ["", "ecrecover", "my-app/fetch-block"] {
+            assert_eq!(hook_fd(name), io::hook_fd(name));
+        }
+    }
+
+    #[test]
+    pub fn named_hook_fds_are_apart() {
+        assert_ne!(hook_fd("a"), hook_fd("b"));
+        assert!(hook_fd("a") & NAMED_HOOK_FD_BIT != 0);
+        assert_eq!(FD_ECRECOVER_HOOK & NAMED_HOOK_FD_BIT, 0);
+    }
+
+    #[test]
+    pub fn hook_secp256k1_lift_x_proves_non_squares() {
+        let modulus = Secp256k1BaseField::modulus();
+        let runtime =
+            Executor::new(crate::Program::new(vec![], 0, 0), sp1_stark::SP1CoreOpts::default());
+        for x in 0u32..10 {
+            let mut buf = [0; 32];
+            buf[28..].copy_from_slice(&x.to_be_bytes());
+            let result = hook_secp256k1_lift_x(runtime.hook_env(), &buf);
+            let y_2 = BigUint::from(x * x * x + 7);
+            match result[0].split_first() {
+                Some((1, [])) => {
+                    let exponent = (&modulus - BigUint::one()) >> 1;
+                    assert_eq!(y_2.modpow(&exponent, &modulus), BigUint::one());
+                }
+                Some((0, root)) => {
+                    let root = BigUint::from_bytes_be(root);
+                    assert_eq!((&root * &root + y_2) % &modulus, BigUint::from(0u32));
+                }
+                _ => panic!("invalid lift_x result {result:?}"),
+            }
+        }
     }
 
     #[test]
@@ -0,0 +1,97 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{self, Display, Formatter},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ExecutionReport, Opcode, Program};
+
+/// A basic block of a program and the cycles spent in it, see [`ExecutionReport::hot_blocks`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotBlock {
+    /// The address of the first instruction of the block.
+    pub start: u32,
+    /// The address past the last instruction of the block.
+    pub end: u32,
+    /// The number of times the block was entered, i.e. the cycles of its first instruction.
+    pub executions: u64,
+    /// The cycles spent in the instructions of the block.
+    pub cycles: u64,
+    /// The name of the function the block is in, if the program has symbols.
+    pub function: Option<String>,
+}
+
+impl ExecutionReport {
+    /// The basic blocks of `program` run during the execution, the most cycles first, from the
+    /// histogram of [`ExecutionReport::pc_counts`].
+    ///
+    /// The blocks are found statically: they start at the entrypoint, at the functions of the
+    /// program, at the targets of branches and direct jumps, and after every branch, jump and
+    /// syscall. Blocks entered through indirect jumps elsewhere are merged with the block before.
+    #[must_use]
+    pub fn hot_blocks(&self, program: &Program) -> Vec<HotBlock> {
+        let leaders = basic_block_leaders(program);
+        let program_end = program.pc_base + 4 * program.instructions.len() as u32;
+
+        let mut blocks = BTreeMap::<u32, HotBlock>::new();
+        for (&pc, &count) in &self.pc_counts {
+            let Some(&start) = leaders.range(..=pc).next_back() else {
+                continue;
+            };
+            let block = blocks.entry(start).or_insert_with(|| HotBlock {
+                start,
+                end: leaders.range(start + 1..).next().map_or(program_end, |&end| end),
+                executions: 0,
+                cycles: 0,
+                function: program
+                    .symbols
+                    .as_ref()
+                    .and_then(|symbols| symbols.function_at(start))
+                    .map(|function| function.name.clone()),
+            });
+            block.cycles += count;
+            if pc == start {
+                block.executions = count;
+            }
+        }
+
+        let mut blocks = blocks.into_values().collect::<Vec<_>>();
+        blocks.sort_by(|a, b| b.cycles.cmp(&a.cycles));
+        blocks
+    }
+}
+
+/// The addresses of the first instructions of the basic blocks of `program`.
+fn basic_block_leaders(program: &Program) -> BTreeSet<u32> {
+    let mut leaders = BTreeSet::from([program.pc_base, program.pc_start]);
+    if let Some(symbols) = &program.symbols {
+        leaders.extend(symbols.functions().iter().map(|function| function.start));
+    }
+    for (i, instruction) in program.instructions.iter().enumerate() {
+        let pc = program.pc_base + 4 * i as u32;
+        if instruction.is_branch_instruction() {
+            leaders.insert(pc.wrapping_add(instruction.op_c));
+        } else if instruction.opcode == Opcode::JAL {
+            leaders.insert(pc.wrapping_add(instruction.op_b));
+        } else if !matches!(instruction.opcode, Opcode::JALR | Opcode::ECALL | Opcode::EBREAK) {
+            continue;
+        }
+        leaders.insert(pc + 4);
+    }
+    leaders
+}
+
+impl Display for HotBlock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "0x{:08x}..0x{:08x}: {} cycles in {} executions",
+            self.start, self.end, self.cycles, self.executions
+        )?;
+        if let Some(function) = &self.function {
+            write!(f, " in {function}")?;
+        }
+        Ok(())
+    }
+}
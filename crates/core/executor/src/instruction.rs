@@ -63,7 +63,7 @@ impl Instruction {
                 | Opcode::DIVU
                 | Opcode::REM
                 | Opcode::REMU
-        )
+        ) || self.opcode.is_bitmanip()
     }
 
     /// Returns if the instruction is a ecall instruction.
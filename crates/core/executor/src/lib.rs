@@ -19,31 +19,53 @@
 #![allow(clippy::explicit_iter_loop)]
 #![warn(missing_docs)]
 
+mod cancel;
 mod context;
+mod debug_info;
+mod debugger;
+mod differential;
 mod disassembler;
 pub mod events;
 mod executor;
+mod fast;
+mod fault;
 mod hook;
+mod hotspot;
 mod instruction;
 mod io;
+pub mod memory;
 mod opcode;
 mod program;
 #[cfg(any(test, feature = "programs"))]
 pub mod programs;
+mod progress;
 mod record;
 mod register;
+mod replay;
 mod report;
 mod state;
 pub mod subproof;
+mod symbols;
 pub mod syscalls;
+mod trace;
 
+pub use cancel::*;
 pub use context::*;
+pub use debug_info::*;
+pub use debugger::*;
+pub use differential::*;
 pub use executor::*;
+pub use fault::*;
 pub use hook::*;
+pub use hotspot::*;
 pub use instruction::*;
 pub use opcode::*;
 pub use program::*;
+pub use progress::*;
 pub use record::*;
 pub use register::*;
+pub use replay::*;
 pub use report::*;
 pub use state::*;
+pub use symbols::*;
+pub use trace::*;
@@ -0,0 +1,410 @@
+//! A sparse model of the guest memory.
+
+use std::{fmt, marker::PhantomData, sync::Arc};
+
+use hashbrown::HashMap;
+use nohash_hasher::BuildNoHashHasher;
+use serde::{
+    de::{MapAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The number of words in a page of a [`PagedMemory`], 4 KiB of guest memory.
+pub const PAGE_WORDS: usize = 1 << 10;
+
+/// The number of registers, whose addresses are the first 32 and aren't word aligned.
+const NUM_REGISTERS: usize = 32;
+
+/// A sparse map from the addresses of the guest memory to values, which only allocates the pages
+/// of [`PAGE_WORDS`] words that were touched.
+///
+/// The memory addresses must be word aligned, except those of the registers, below `0x20`, which
+/// are kept apart. The iteration isn't in the order of the addresses.
+///
+/// Cloning is cheap, as the pages are shared copy-on-write: a page is only copied when one of the
+/// clones sharing it writes to it.
+#[derive(Debug, Clone)]
+pub struct PagedMemory<V> {
+    registers: [Option<V>; NUM_REGISTERS],
+    pages: HashMap<u32, Arc<Page<V>>, BuildNoHashHasher<u32>>,
+    len: usize,
+}
+
+/// A page of a [`PagedMemory`].
+#[derive(Debug, Clone)]
+struct Page<V> {
+    values: Box<[Option<V>]>,
+    len: usize,
+}
+
+/// An entry of a [`PagedMemory`], for in-place manipulation.
+pub enum Entry<'a, V> {
+    /// The address has a value.
+    Occupied(OccupiedEntry<'a, V>),
+    /// The address has no value.
+    Vacant(VacantEntry<'a, V>),
+}
+
+/// An entry of a [`PagedMemory`] with a value.
+pub struct OccupiedEntry<'a, V> {
+    value: &'a mut V,
+}
+
+/// An entry of a [`PagedMemory`] without a value.
+pub struct VacantEntry<'a, V> {
+    memory: &'a mut PagedMemory<V>,
+    addr: u32,
+}
+
+impl<V> PagedMemory<V> {
+    /// Creates an empty memory, which doesn't allocate until a page is touched.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { registers: Default::default(), pages: HashMap::default(), len: 0 }
+    }
+
+    /// The number of addresses with a value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether no address has a value.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of pages allocated.
+    #[must_use]
+    pub fn num_pages(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// The value at `addr`.
+    #[must_use]
+    pub fn get(&self, addr: u32) -> Option<&V> {
+        match split(addr) {
+            Location::Register(index) => self.registers[index].as_ref(),
+            Location::Page(page, offset) => self.pages.get(&page)?.values[offset].as_ref(),
+        }
+    }
+
+    /// Whether `addr` has a value.
+    #[must_use]
+    pub fn contains_key(&self, addr: u32) -> bool {
+        self.get(addr).is_some()
+    }
+
+    /// Removes every value, freeing the pages.
+    pub fn clear(&mut self) {
+        self.registers = Default::default();
+        self.pages.clear();
+        self.len = 0;
+    }
+
+    /// The addresses with a value and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &V)> {
+        let registers = self
+            .registers
+            .iter()
+            .enumerate()
+            .filter_map(|(index, value)| Some((index as u32, value.as_ref()?)));
+        let pages = self.pages.iter().flat_map(|(page, entry)| {
+            entry
+                .values
+                .iter()
+                .enumerate()
+                .filter_map(move |(offset, value)| Some((join(*page, offset), value.as_ref()?)))
+        });
+        registers.chain(pages)
+    }
+
+    /// The addresses with a value.
+    pub fn keys(&self) -> impl Iterator<Item = u32> + '_ {
+        self.iter().map(|(addr, _)| addr)
+    }
+
+    /// The values.
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<V: Clone> PagedMemory<V> {
+    /// The value at `addr`, mutably, copying its page if it is shared with a clone.
+    pub fn get_mut(&mut self, addr: u32) -> Option<&mut V> {
+        match split(addr) {
+            Location::Register(index) => self.registers[index].as_mut(),
+            Location::Page(page, offset) => {
+                Arc::make_mut(self.pages.get_mut(&page)?).values[offset].as_mut()
+            }
+        }
+    }
+
+    /// Sets the value at `addr`, returning the previous one.
+    pub fn insert(&mut self, addr: u32, value: V) -> Option<V> {
+        match self.entry(addr) {
+            Entry::Occupied(entry) => Some(std::mem::replace(entry.into_mut(), value)),
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+                None
+            }
+        }
+    }
+
+    /// Sets the values of the consecutive words from `addr`, looking up each page only once, so
+    /// that large buffers are mapped a page at a time.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first address that already has a value, after setting the values before it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `addr` is a register and `values` isn't empty.
+    pub fn try_insert_words(
+        &mut self,
+        addr: u32,
+        values: impl IntoIterator<Item = V>,
+    ) -> Result<(), u32> {
+        let mut values = values.into_iter().peekable();
+        if values.peek().is_none() {
+            return Ok(());
+        }
+        let Location::Page(mut page, mut offset) = split(addr) else {
+            panic!("can't insert words at register {addr}");
+        };
+        while values.peek().is_some() {
+            let entry = Arc::make_mut(self.pages.entry(page).or_default());
+            for (i, value) in (offset..PAGE_WORDS).zip(&mut values) {
+                let slot = &mut entry.values[i];
+                if slot.is_some() {
+                    return Err(join(page, i));
+                }
+                *slot = Some(value);
+                entry.len += 1;
+                self.len += 1;
+            }
+            page += 1;
+            offset = 0;
+        }
+        Ok(())
+    }
+
+    /// Removes the value at `addr`, returning it. The page of `addr` is freed once it is empty.
+    pub fn remove(&mut self, addr: u32) -> Option<V> {
+        let value = match split(addr) {
+            Location::Register(index) => self.registers[index].take()?,
+            Location::Page(page, offset) => {
+                let entry = self.pages.get_mut(&page)?;
+                entry.values[offset].as_ref()?;
+                let entry = Arc::make_mut(entry);
+                let value = entry.values[offset].take()?;
+                entry.len -= 1;
+                if entry.len == 0 {
+                    self.pages.remove(&page);
+                }
+                value
+            }
+        };
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// The entry of `addr`. The page is copied if it is shared with a clone only when the entry is
+    /// occupied, and allocated only when a vacant entry is inserted into.
+    pub fn entry(&mut self, addr: u32) -> Entry<'_, V> {
+        if !self.contains_key(addr) {
+            return Entry::Vacant(VacantEntry { memory: self, addr });
+        }
+        let value = match split(addr) {
+            Location::Register(index) => self.registers[index].as_mut(),
+            Location::Page(page, offset) => self
+                .pages
+                .get_mut(&page)
+                .and_then(|page| Arc::make_mut(page).values[offset].as_mut()),
+        };
+        Entry::Occupied(OccupiedEntry { value: value.unwrap() })
+    }
+}
+
+impl<'a, V> OccupiedEntry<'a, V> {
+    /// The value of the entry.
+    #[must_use]
+    pub fn get(&self) -> &V {
+        self.value
+    }
+
+    /// The value of the entry, mutably.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.value
+    }
+
+    /// The value of the entry, with the lifetime of the memory.
+    #[must_use]
+    pub fn into_mut(self) -> &'a mut V {
+        self.value
+    }
+}
+
+impl<'a, V: Clone> VacantEntry<'a, V> {
+    /// Sets the value of the entry, allocating its page if needed.
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.memory.len += 1;
+        let slot = match split(self.addr) {
+            Location::Register(index) => &mut self.memory.registers[index],
+            Location::Page(page, offset) => {
+                let page = Arc::make_mut(self.memory.pages.entry(page).or_default());
+                page.len += 1;
+                &mut page.values[offset]
+            }
+        };
+        slot.insert(value)
+    }
+}
+
+impl<V> Default for PagedMemory<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone> FromIterator<(u32, V)> for PagedMemory<V> {
+    fn from_iter<I: IntoIterator<Item = (u32, V)>>(iter: I) -> Self {
+        let mut memory = Self::new();
+        memory.extend(iter);
+        memory
+    }
+}
+
+impl<V: Clone> Extend<(u32, V)> for PagedMemory<V> {
+    fn extend<I: IntoIterator<Item = (u32, V)>>(&mut self, iter: I) {
+        for (addr, value) in iter {
+            self.insert(addr, value);
+        }
+    }
+}
+
+impl<V: Serialize> Serialize for PagedMemory<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.len))?;
+        for (addr, value) in self.iter() {
+            map.serialize_entry(&addr, value)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de, V: Clone + Deserialize<'de>> Deserialize<'de> for PagedMemory<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PagedMemoryVisitor<V>(PhantomData<V>);
+
+        impl<'de, V: Clone + Deserialize<'de>> Visitor<'de> for PagedMemoryVisitor<V> {
+            type Value = PagedMemory<V>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map from memory addresses to values")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+                let mut memory = PagedMemory::new();
+                while let Some((addr, value)) = access.next_entry()? {
+                    memory.insert(addr, value);
+                }
+                Ok(memory)
+            }
+        }
+
+        deserializer.deserialize_map(PagedMemoryVisitor(PhantomData))
+    }
+}
+
+impl<V> Default for Page<V> {
+    fn default() -> Self {
+        Self { values: std::iter::repeat_with(|| None).take(PAGE_WORDS).collect(), len: 0 }
+    }
+}
+
+/// Where the value of an address is kept.
+enum Location {
+    Register(usize),
+    Page(u32, usize),
+}
+
+/// The location of `addr`.
+#[inline]
+fn split(addr: u32) -> Location {
+    if (addr as usize) < NUM_REGISTERS {
+        return Location::Register(addr as usize);
+    }
+    assert!(addr % 4 == 0, "memory address 0x{addr:08x} isn't word aligned");
+    let word = addr >> 2;
+    Location::Page(word / PAGE_WORDS as u32, (word % PAGE_WORDS as u32) as usize)
+}
+
+/// The address at `offset` in `page`.
+#[inline]
+fn join(page: u32, offset: usize) -> u32 {
+    (page * PAGE_WORDS as u32 + offset as u32) << 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paged_memory() {
+        let mut memory = PagedMemory::new();
+        assert_eq!(memory.insert(5, 1), None);
+        assert_eq!(memory.insert(0x2000_0000, 2), None);
+        assert_eq!(memory.insert(0x2000_0004, 3), None);
+        assert_eq!(memory.insert(0x2000_0004, 4), Some(3));
+        assert_eq!((memory.len(), memory.num_pages()), (3, 1));
+        assert_eq!(memory.get(0x2000_0004), Some(&4));
+        assert_eq!(memory.get(0x2000_0008), None);
+
+        // A vacant entry allocates its page only when inserted into.
+        assert!(matches!(memory.entry(0x6000_0000), Entry::Vacant(_)));
+        assert_eq!(memory.num_pages(), 1);
+        let Entry::Vacant(entry) = memory.entry(0x7000_0000) else { panic!("expected vacant") };
+        *entry.insert(5) += 1;
+        assert_eq!(memory.get(0x7000_0000), Some(&6));
+
+        let bytes = bincode::serialize(&memory).unwrap();
+        let mut memory: PagedMemory<u32> = bincode::deserialize(&bytes).unwrap();
+        let mut entries = memory.iter().map(|(addr, value)| (addr, *value)).collect::<Vec<_>>();
+        entries.sort_unstable();
+        assert_eq!(entries, vec![(5, 1), (0x2000_0000, 2), (0x2000_0004, 4), (0x7000_0000, 6)]);
+
+        assert_eq!(memory.remove(0x7000_0000), Some(6));
+        assert_eq!(memory.remove(0x7000_0000), None);
+        assert_eq!((memory.len(), memory.num_pages()), (3, 1));
+
+        // The words cross into the next page and stop at the first one already set.
+        let addr = 0x2000_0000 - 8;
+        assert_eq!(memory.try_insert_words(addr, [7, 8]), Ok(()));
+        assert_eq!(memory.try_insert_words(addr - 4, [9, 10]), Err(addr));
+        assert_eq!(memory.get(addr - 4), Some(&9));
+        assert_eq!(memory.try_insert_words(0x3000_0000 - 4, 0..PAGE_WORDS as u32), Ok(()));
+        assert_eq!(
+            memory.get(0x3000_0000 + 4 * (PAGE_WORDS as u32 - 2)),
+            Some(&(PAGE_WORDS as u32 - 1))
+        );
+        assert_eq!((memory.len(), memory.num_pages()), (6 + PAGE_WORDS, 4));
+
+        // A clone shares the pages until it writes to them.
+        let mut clone = memory.clone();
+        *clone.get_mut(0x2000_0004).unwrap() = 11;
+        clone.insert(0x4000_0000, 12);
+        assert_eq!((memory.get(0x2000_0004), clone.get(0x2000_0004)), (Some(&4), Some(&11)));
+        assert!(!memory.contains_key(0x4000_0000));
+    }
+
+    #[test]
+    #[should_panic(expected = "isn't word aligned")]
+    fn test_paged_memory_unaligned() {
+        PagedMemory::new().insert(0x2000_0002, 1);
+    }
+}
@@ -98,6 +98,113 @@ pub enum Opcode {
     REMU = 37,
     /// Unimplemented instruction.
     UNIMP = 39,
+    /// rd ← rs1 & ~rs2, pc ← pc + 4
+    ANDN = 40,
+    /// rd ← rs1 | ~rs2, pc ← pc + 4
+    ORN = 41,
+    /// rd ← ~(rs1 ^ rs2), pc ← pc + 4
+    XNOR = 42,
+    /// rd ← number of leading zero bits of rs1, pc ← pc + 4
+    CLZ = 43,
+    /// rd ← number of trailing zero bits of rs1, pc ← pc + 4
+    CTZ = 44,
+    /// rd ← number of set bits of rs1, pc ← pc + 4
+    CPOP = 45,
+    /// rd ← max(rs1, rs2) (signed), pc ← pc + 4
+    MAX = 46,
+    /// rd ← max(rs1, rs2) (unsigned), pc ← pc + 4
+    MAXU = 47,
+    /// rd ← min(rs1, rs2) (signed), pc ← pc + 4
+    MIN = 48,
+    /// rd ← min(rs1, rs2) (unsigned), pc ← pc + 4
+    MINU = 49,
+    /// rd ← sx(rs1[7:0]), pc ← pc + 4
+    SEXTB = 50,
+    /// rd ← sx(rs1[15:0]), pc ← pc + 4
+    SEXTH = 51,
+    /// rd ← zx(rs1[15:0]), pc ← pc + 4
+    ZEXTH = 52,
+    /// rd ← rs1 rotated left by rs2[4:0], pc ← pc + 4
+    ROL = 53,
+    /// rd ← rs1 rotated right by rs2[4:0], pc ← pc + 4
+    ROR = 54,
+    /// rd ← each byte of rs1 set to 0xff if it is non-zero, pc ← pc + 4
+    ORCB = 55,
+    /// rd ← rs1 with its bytes reversed, pc ← pc + 4
+    REV8 = 56,
+    /// rd ← (rs1 << 1) + rs2, pc ← pc + 4
+    SH1ADD = 57,
+    /// rd ← (rs1 << 2) + rs2, pc ← pc + 4
+    SH2ADD = 58,
+    /// rd ← (rs1 << 3) + rs2, pc ← pc + 4
+    SH3ADD = 59,
+    /// rd ← rs1 & ~(1 << rs2[4:0]), pc ← pc + 4
+    BCLR = 60,
+    /// rd ← (rs1 >> rs2[4:0]) & 1, pc ← pc + 4
+    BEXT = 61,
+    /// rd ← rs1 ^ (1 << rs2[4:0]), pc ← pc + 4
+    BINV = 62,
+    /// rd ← rs1 | (1 << rs2[4:0]), pc ← pc + 4
+    BSET = 63,
+}
+
+/// The class of an [Opcode], grouping the opcodes that perform the same kind of operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
+pub enum OpcodeClass {
+    /// Addition and subtraction.
+    Arithmetic,
+    /// Bitwise operations.
+    Bitwise,
+    /// Shifts.
+    Shift,
+    /// Comparisons.
+    Comparison,
+    /// Multiplications.
+    Multiplication,
+    /// Divisions and remainders.
+    Division,
+    /// Memory loads.
+    Load,
+    /// Memory stores.
+    Store,
+    /// Conditional branches.
+    Branch,
+    /// Unconditional jumps.
+    Jump,
+    /// Upper immediate instructions.
+    UpperImmediate,
+    /// System instructions, including the syscalls.
+    System,
+    /// Instructions of the Zba, Zbb and Zbs bit manipulation extensions.
+    BitManipulation,
+}
+
+impl OpcodeClass {
+    /// Get the name of the opcode class.
+    #[must_use]
+    pub const fn name(&self) -> &str {
+        match self {
+            OpcodeClass::Arithmetic => "arithmetic",
+            OpcodeClass::Bitwise => "bitwise",
+            OpcodeClass::Shift => "shift",
+            OpcodeClass::Comparison => "comparison",
+            OpcodeClass::Multiplication => "multiplication",
+            OpcodeClass::Division => "division",
+            OpcodeClass::Load => "load",
+            OpcodeClass::Store => "store",
+            OpcodeClass::Branch => "branch",
+            OpcodeClass::Jump => "jump",
+            OpcodeClass::UpperImmediate => "upper immediate",
+            OpcodeClass::System => "system",
+            OpcodeClass::BitManipulation => "bit manipulation",
+        }
+    }
+}
+
+impl Display for OpcodeClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
 }
 
 /// Byte Opcode.
@@ -126,6 +233,11 @@ pub enum ByteOpcode {
     MSB = 7,
     /// Unsigned 16-bit Range Check.
     U16Range = 8,
+    /// The AES S-box, with the product of its output by `x` in the field of AES.
+    ///
+    /// This lookup is served by its own table rather than the byte table, so it isn't part of
+    /// [`ByteOpcode::all`].
+    AesSbox = 9,
 }
 
 impl Opcode {
@@ -171,9 +283,86 @@ impl Opcode {
             Opcode::REM => "rem",
             Opcode::REMU => "remu",
             Opcode::UNIMP => "unimp",
+            Opcode::ANDN => "andn",
+            Opcode::ORN => "orn",
+            Opcode::XNOR => "xnor",
+            Opcode::CLZ => "clz",
+            Opcode::CTZ => "ctz",
+            Opcode::CPOP => "cpop",
+            Opcode::MAX => "max",
+            Opcode::MAXU => "maxu",
+            Opcode::MIN => "min",
+            Opcode::MINU => "minu",
+            Opcode::SEXTB => "sext.b",
+            Opcode::SEXTH => "sext.h",
+            Opcode::ZEXTH => "zext.h",
+            Opcode::ROL => "rol",
+            Opcode::ROR => "ror",
+            Opcode::ORCB => "orc.b",
+            Opcode::REV8 => "rev8",
+            Opcode::SH1ADD => "sh1add",
+            Opcode::SH2ADD => "sh2add",
+            Opcode::SH3ADD => "sh3add",
+            Opcode::BCLR => "bclr",
+            Opcode::BEXT => "bext",
+            Opcode::BINV => "binv",
+            Opcode::BSET => "bset",
+        }
+    }
+
+    /// Get the class of the opcode.
+    #[must_use]
+    pub const fn class(&self) -> OpcodeClass {
+        match self {
+            Opcode::ADD | Opcode::SUB => OpcodeClass::Arithmetic,
+            Opcode::XOR | Opcode::OR | Opcode::AND => OpcodeClass::Bitwise,
+            Opcode::SLL | Opcode::SRL | Opcode::SRA => OpcodeClass::Shift,
+            Opcode::SLT | Opcode::SLTU => OpcodeClass::Comparison,
+            Opcode::MUL | Opcode::MULH | Opcode::MULHU | Opcode::MULHSU => {
+                OpcodeClass::Multiplication
+            }
+            Opcode::DIV | Opcode::DIVU | Opcode::REM | Opcode::REMU => OpcodeClass::Division,
+            Opcode::LB | Opcode::LH | Opcode::LW | Opcode::LBU | Opcode::LHU => OpcodeClass::Load,
+            Opcode::SB | Opcode::SH | Opcode::SW => OpcodeClass::Store,
+            Opcode::BEQ | Opcode::BNE | Opcode::BLT | Opcode::BGE | Opcode::BLTU | Opcode::BGEU => {
+                OpcodeClass::Branch
+            }
+            Opcode::JAL | Opcode::JALR => OpcodeClass::Jump,
+            Opcode::AUIPC => OpcodeClass::UpperImmediate,
+            Opcode::ECALL | Opcode::EBREAK | Opcode::UNIMP => OpcodeClass::System,
+            Opcode::ANDN
+            | Opcode::ORN
+            | Opcode::XNOR
+            | Opcode::CLZ
+            | Opcode::CTZ
+            | Opcode::CPOP
+            | Opcode::MAX
+            | Opcode::MAXU
+            | Opcode::MIN
+            | Opcode::MINU
+            | Opcode::SEXTB
+            | Opcode::SEXTH
+            | Opcode::ZEXTH
+            | Opcode::ROL
+            | Opcode::ROR
+            | Opcode::ORCB
+            | Opcode::REV8
+            | Opcode::SH1ADD
+            | Opcode::SH2ADD
+            | Opcode::SH3ADD
+            | Opcode::BCLR
+            | Opcode::BEXT
+            | Opcode::BINV
+            | Opcode::BSET => OpcodeClass::BitManipulation,
         }
     }
 
+    /// Whether the opcode is of the Zba, Zbb or Zbs bit manipulation extensions.
+    #[must_use]
+    pub const fn is_bitmanip(&self) -> bool {
+        matches!(self.class(), OpcodeClass::BitManipulation)
+    }
+
     /// Convert the opcode to a field element.
     #[must_use]
     pub fn as_field<F: Field>(self) -> F {
@@ -9,6 +9,7 @@ use sp1_stark::air::MachineProgram;
 use crate::{
     disassembler::{transpile, Elf},
     instruction::Instruction,
+    DebugInfo, SymbolTable,
 };
 
 /// A program that can be executed by the SP1 zkVM.
@@ -25,13 +26,26 @@ pub struct Program {
     pub pc_base: u32,
     /// The initial memory image, useful for global constants.
     pub memory_image: BTreeMap<u32, u32>,
+    /// The debug information of the ELF, used to symbolize the backtraces of faults.
+    #[serde(skip)]
+    pub debug_info: Option<DebugInfo>,
+    /// The function symbols of the ELF, used to profile the cycles of executions by function.
+    #[serde(skip)]
+    pub symbols: Option<SymbolTable>,
 }
 
 impl Program {
     /// Create a new [Program].
     #[must_use]
     pub const fn new(instructions: Vec<Instruction>, pc_start: u32, pc_base: u32) -> Self {
-        Self { instructions, pc_start, pc_base, memory_image: BTreeMap::new() }
+        Self {
+            instructions,
+            pc_start,
+            pc_base,
+            memory_image: BTreeMap::new(),
+            debug_info: None,
+            symbols: None,
+        }
     }
 
     /// Disassemble a RV32IM ELF to a program that be executed by the VM.
@@ -44,7 +58,7 @@ impl Program {
         let elf = Elf::decode(input)?;
 
         // Transpile the RV32IM instructions.
-        let instructions = transpile(&elf.instructions);
+        let instructions = transpile(&elf.instructions, elf.pc_base)?;
 
         // Return the program.
         Ok(Program {
@@ -52,6 +66,8 @@ impl Program {
             pc_start: elf.pc_start,
             pc_base: elf.pc_base,
             memory_image: elf.memory_image,
+            debug_info: DebugInfo::new(input),
+            symbols: SymbolTable::new(input),
         })
     }
 
@@ -0,0 +1,82 @@
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A stage of proof generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProofStage {
+    /// Executing the program and committing to the shards of the execution.
+    Execute,
+    /// Proving the shards of the execution.
+    Core,
+    /// Recursively compressing the shard proofs into a single proof.
+    Compress,
+    /// Shrinking the compressed proof.
+    Shrink,
+    /// Wrapping the shrunk proof into a proof over a SNARK-friendly field.
+    Wrap,
+    /// Generating the final Plonk proof.
+    Plonk,
+    /// Generating the final Groth16 proof.
+    Groth16,
+}
+
+/// An event reported to a [`ProgressCallback`] while generating a proof.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofEvent {
+    /// A stage of proof generation started.
+    StageStarted(ProofStage),
+    /// A stage of proof generation finished after `elapsed`.
+    StageFinished {
+        /// The stage that finished.
+        stage: ProofStage,
+        /// The time the stage took.
+        elapsed: Duration,
+    },
+    /// One more shard of the execution was proven.
+    ShardProved {
+        /// The number of shards proven so far.
+        proved: usize,
+        /// The total number of shards.
+        total: usize,
+    },
+    /// One more proof of the recursion tree was generated.
+    RecursionProved {
+        /// The number of recursive proofs generated so far.
+        proved: usize,
+        /// The total number of recursive proofs in the tree.
+        total: usize,
+        /// The depth of the proof in the tree, counting from the leaves.
+        height: usize,
+        /// The height of the root of the tree.
+        max_height: usize,
+    },
+}
+
+/// A callback receiving the [`ProofEvent`]s of a proof, e.g. to render a progress bar.
+///
+/// The callback may be invoked from several threads at once.
+pub type ProgressCallback<'a> = Arc<dyn Fn(&ProofEvent) + Send + Sync + 'a>;
+
+/// Report `event` to `progress`, if there is one.
+pub fn report_progress(progress: Option<&ProgressCallback>, event: ProofEvent) {
+    if let Some(progress) = progress {
+        progress(&event);
+    }
+}
+
+/// Run `f` as the proof stage `stage`, reporting to `progress` when it starts and finishes.
+pub fn report_stage<T>(
+    progress: Option<&ProgressCallback>,
+    stage: ProofStage,
+    f: impl FnOnce() -> T,
+) -> T {
+    report_progress(progress, ProofEvent::StageStarted(stage));
+    let start = Instant::now();
+    let result = f();
+    report_progress(progress, ProofEvent::StageFinished { stage, elapsed: start.elapsed() });
+    result
+}
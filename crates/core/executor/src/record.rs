@@ -8,11 +8,12 @@ use serde::{Deserialize, Serialize};
 
 use super::{program::Program, Opcode};
 use crate::events::{
-    add_sharded_byte_lookup_events, AluEvent, ByteLookupEvent, ByteRecord, CpuEvent,
-    EdDecompressEvent, EllipticCurveAddEvent, EllipticCurveDecompressEvent,
-    EllipticCurveDoubleEvent, Fp2AddSubEvent, Fp2MulEvent, FpOpEvent, KeccakPermuteEvent,
-    MemoryInitializeFinalizeEvent, MemoryRecordEnum, ShaCompressEvent, ShaExtendEvent,
-    Uint256MulEvent,
+    add_sharded_byte_lookup_events, AesEncryptEvent, AluEvent, Blake3CompressEvent,
+    ByteLookupEvent, ByteRecord, CpuEvent, EdDecompressEvent, EllipticCurveAddEvent,
+    EllipticCurveDecompressEvent, EllipticCurveDoubleEvent, Fp2AddSubEvent, Fp2MulEvent, FpOpEvent,
+    KeccakPermuteEvent, MemoryInitializeFinalizeEvent, MemoryRecordEnum, Poseidon2PermuteEvent,
+    Ripemd160CompressEvent, Sha512CompressEvent, Sha512ExtendEvent, ShaCompressEvent,
+    ShaExtendEvent, Uint256MulEvent, UintModEvent,
 };
 
 /// A record of the execution of a program.
@@ -40,12 +41,18 @@ pub struct ExecutionRecord {
     pub divrem_events: Vec<AluEvent>,
     /// A trace of the SLT, SLTI, SLTU, and SLTIU events.
     pub lt_events: Vec<AluEvent>,
+    /// A trace of the events of the Zba, Zbb, and Zbs bit manipulation instructions.
+    pub bitmanip_events: Vec<AluEvent>,
     /// A trace of the byte lookups that are needed.
     pub byte_lookups: HashMap<u32, HashMap<ByteLookupEvent, usize>>,
     /// A trace of the sha256 extend events.
     pub sha_extend_events: Vec<ShaExtendEvent>,
     /// A trace of the sha256 compress events.
     pub sha_compress_events: Vec<ShaCompressEvent>,
+    /// A trace of the sha512 extend events.
+    pub sha512_extend_events: Vec<Sha512ExtendEvent>,
+    /// A trace of the sha512 compress events.
+    pub sha512_compress_events: Vec<Sha512CompressEvent>,
     /// A trace of the keccak256 permute events.
     pub keccak_permute_events: Vec<KeccakPermuteEvent>,
     /// A trace of the edwards add events.
@@ -56,18 +63,36 @@ pub struct ExecutionRecord {
     pub secp256k1_add_events: Vec<EllipticCurveAddEvent>,
     /// A trace of the secp256k1 double events.
     pub secp256k1_double_events: Vec<EllipticCurveDoubleEvent>,
+    /// A trace of the secp256r1 add events.
+    pub secp256r1_add_events: Vec<EllipticCurveAddEvent>,
+    /// A trace of the secp256r1 double events.
+    pub secp256r1_double_events: Vec<EllipticCurveDoubleEvent>,
     /// A trace of the bn254 add events.
     pub bn254_add_events: Vec<EllipticCurveAddEvent>,
     /// A trace of the bn254 double events.
     pub bn254_double_events: Vec<EllipticCurveDoubleEvent>,
     /// A trace of the k256 decompress events.
     pub k256_decompress_events: Vec<EllipticCurveDecompressEvent>,
+    /// A trace of the p256 decompress events.
+    pub p256_decompress_events: Vec<EllipticCurveDecompressEvent>,
     /// A trace of the bls12381 add events.
     pub bls12381_add_events: Vec<EllipticCurveAddEvent>,
     /// A trace of the bls12381 double events.
     pub bls12381_double_events: Vec<EllipticCurveDoubleEvent>,
+    /// A trace of the poseidon2 permute events.
+    pub poseidon2_permute_events: Vec<Poseidon2PermuteEvent>,
+    /// A trace of the blake3 compress events.
+    pub blake3_compress_events: Vec<Blake3CompressEvent>,
+    /// A trace of the aes encrypt events.
+    pub aes_encrypt_events: Vec<AesEncryptEvent>,
+    /// A trace of the ripemd160 compress events.
+    pub ripemd160_compress_events: Vec<Ripemd160CompressEvent>,
     /// A trace of the uint256 mul events.
     pub uint256_mul_events: Vec<Uint256MulEvent>,
+    /// A trace of the uint320 add and mul events.
+    pub uint320_mod_events: Vec<UintModEvent>,
+    /// A trace of the uint384 add and mul events.
+    pub uint384_mod_events: Vec<UintModEvent>,
     /// A trace of the memory initialize events.
     pub memory_initialize_events: Vec<MemoryInitializeFinalizeEvent>,
     /// A trace of the memory finalize events.
@@ -134,6 +159,9 @@ impl ExecutionRecord {
                 Opcode::SLT | Opcode::SLTU => {
                     self.lt_events.append(value);
                 }
+                _ if opcode.is_bitmanip() => {
+                    self.bitmanip_events.append(value);
+                }
                 _ => {
                     panic!("Invalid opcode: {opcode:?}");
                 }
@@ -151,6 +179,8 @@ impl ExecutionRecord {
             keccak_permute_events: std::mem::take(&mut self.keccak_permute_events),
             secp256k1_add_events: std::mem::take(&mut self.secp256k1_add_events),
             secp256k1_double_events: std::mem::take(&mut self.secp256k1_double_events),
+            secp256r1_add_events: std::mem::take(&mut self.secp256r1_add_events),
+            secp256r1_double_events: std::mem::take(&mut self.secp256r1_double_events),
             bn254_fp_events: std::mem::take(&mut self.bn254_fp_events),
             bn254_fp2_addsub_events: std::mem::take(&mut self.bn254_fp2_addsub_events),
             bn254_fp2_mul_events: std::mem::take(&mut self.bn254_fp2_mul_events),
@@ -160,10 +190,19 @@ impl ExecutionRecord {
             bls12381_double_events: std::mem::take(&mut self.bls12381_double_events),
             sha_extend_events: std::mem::take(&mut self.sha_extend_events),
             sha_compress_events: std::mem::take(&mut self.sha_compress_events),
+            sha512_extend_events: std::mem::take(&mut self.sha512_extend_events),
+            sha512_compress_events: std::mem::take(&mut self.sha512_compress_events),
             ed_add_events: std::mem::take(&mut self.ed_add_events),
             ed_decompress_events: std::mem::take(&mut self.ed_decompress_events),
             k256_decompress_events: std::mem::take(&mut self.k256_decompress_events),
+            p256_decompress_events: std::mem::take(&mut self.p256_decompress_events),
             uint256_mul_events: std::mem::take(&mut self.uint256_mul_events),
+            uint320_mod_events: std::mem::take(&mut self.uint320_mod_events),
+            uint384_mod_events: std::mem::take(&mut self.uint384_mod_events),
+            poseidon2_permute_events: std::mem::take(&mut self.poseidon2_permute_events),
+            blake3_compress_events: std::mem::take(&mut self.blake3_compress_events),
+            aes_encrypt_events: std::mem::take(&mut self.aes_encrypt_events),
+            ripemd160_compress_events: std::mem::take(&mut self.ripemd160_compress_events),
             bls12381_fp_events: std::mem::take(&mut self.bls12381_fp_events),
             bls12381_fp2_addsub_events: std::mem::take(&mut self.bls12381_fp2_addsub_events),
             bls12381_fp2_mul_events: std::mem::take(&mut self.bls12381_fp2_mul_events),
@@ -209,16 +248,27 @@ impl ExecutionRecord {
         split_events!(self, keccak_permute_events, shards, opts.keccak, last);
         split_events!(self, secp256k1_add_events, shards, opts.deferred, last);
         split_events!(self, secp256k1_double_events, shards, opts.deferred, last);
+        split_events!(self, secp256r1_add_events, shards, opts.deferred, last);
+        split_events!(self, secp256r1_double_events, shards, opts.deferred, last);
         split_events!(self, bn254_add_events, shards, opts.deferred, last);
         split_events!(self, bn254_double_events, shards, opts.deferred, last);
         split_events!(self, bls12381_add_events, shards, opts.deferred, last);
         split_events!(self, bls12381_double_events, shards, opts.deferred, last);
         split_events!(self, sha_extend_events, shards, opts.sha_extend, last);
         split_events!(self, sha_compress_events, shards, opts.sha_compress, last);
+        split_events!(self, sha512_extend_events, shards, opts.sha512_extend, last);
+        split_events!(self, sha512_compress_events, shards, opts.sha512_compress, last);
+        split_events!(self, ripemd160_compress_events, shards, opts.ripemd160_compress, last);
         split_events!(self, ed_add_events, shards, opts.deferred, last);
         split_events!(self, ed_decompress_events, shards, opts.deferred, last);
         split_events!(self, k256_decompress_events, shards, opts.deferred, last);
+        split_events!(self, p256_decompress_events, shards, opts.deferred, last);
         split_events!(self, uint256_mul_events, shards, opts.deferred, last);
+        split_events!(self, uint320_mod_events, shards, opts.deferred, last);
+        split_events!(self, uint384_mod_events, shards, opts.deferred, last);
+        split_events!(self, poseidon2_permute_events, shards, opts.deferred, last);
+        split_events!(self, blake3_compress_events, shards, opts.deferred, last);
+        split_events!(self, aes_encrypt_events, shards, opts.deferred, last);
         split_events!(self, bls12381_decompress_events, shards, opts.deferred, last);
         split_events!(self, bls12381_fp_events, shards, opts.deferred, last);
         split_events!(self, bls12381_fp2_addsub_events, shards, opts.deferred, last);
@@ -302,19 +352,31 @@ impl MachineRecord for ExecutionRecord {
         stats.insert("shift_right_events".to_string(), self.shift_right_events.len());
         stats.insert("divrem_events".to_string(), self.divrem_events.len());
         stats.insert("lt_events".to_string(), self.lt_events.len());
+        stats.insert("bitmanip_events".to_string(), self.bitmanip_events.len());
         stats.insert("sha_extend_events".to_string(), self.sha_extend_events.len());
         stats.insert("sha_compress_events".to_string(), self.sha_compress_events.len());
+        stats.insert("sha512_extend_events".to_string(), self.sha512_extend_events.len());
+        stats.insert("sha512_compress_events".to_string(), self.sha512_compress_events.len());
         stats.insert("keccak_permute_events".to_string(), self.keccak_permute_events.len());
         stats.insert("ed_add_events".to_string(), self.ed_add_events.len());
         stats.insert("ed_decompress_events".to_string(), self.ed_decompress_events.len());
         stats.insert("secp256k1_add_events".to_string(), self.secp256k1_add_events.len());
         stats.insert("secp256k1_double_events".to_string(), self.secp256k1_double_events.len());
+        stats.insert("secp256r1_add_events".to_string(), self.secp256r1_add_events.len());
+        stats.insert("secp256r1_double_events".to_string(), self.secp256r1_double_events.len());
         stats.insert("bn254_add_events".to_string(), self.bn254_add_events.len());
         stats.insert("bn254_double_events".to_string(), self.bn254_double_events.len());
         stats.insert("k256_decompress_events".to_string(), self.k256_decompress_events.len());
+        stats.insert("p256_decompress_events".to_string(), self.p256_decompress_events.len());
         stats.insert("bls12381_add_events".to_string(), self.bls12381_add_events.len());
         stats.insert("bls12381_double_events".to_string(), self.bls12381_double_events.len());
         stats.insert("uint256_mul_events".to_string(), self.uint256_mul_events.len());
+        stats.insert("uint320_mod_events".to_string(), self.uint320_mod_events.len());
+        stats.insert("uint384_mod_events".to_string(), self.uint384_mod_events.len());
+        stats.insert("poseidon2_permute_events".to_string(), self.poseidon2_permute_events.len());
+        stats.insert("blake3_compress_events".to_string(), self.blake3_compress_events.len());
+        stats.insert("aes_encrypt_events".to_string(), self.aes_encrypt_events.len());
+        stats.insert("ripemd160_compress_events".to_string(), self.ripemd160_compress_events.len());
         stats.insert("bls12381_fp_event".to_string(), self.bls12381_fp_events.len());
         stats.insert(
             "bls12381_fp2_addsub_events".to_string(),
@@ -352,19 +414,31 @@ impl MachineRecord for ExecutionRecord {
         self.shift_right_events.append(&mut other.shift_right_events);
         self.divrem_events.append(&mut other.divrem_events);
         self.lt_events.append(&mut other.lt_events);
+        self.bitmanip_events.append(&mut other.bitmanip_events);
         self.sha_extend_events.append(&mut other.sha_extend_events);
         self.sha_compress_events.append(&mut other.sha_compress_events);
+        self.sha512_extend_events.append(&mut other.sha512_extend_events);
+        self.sha512_compress_events.append(&mut other.sha512_compress_events);
         self.keccak_permute_events.append(&mut other.keccak_permute_events);
         self.ed_add_events.append(&mut other.ed_add_events);
         self.ed_decompress_events.append(&mut other.ed_decompress_events);
         self.secp256k1_add_events.append(&mut other.secp256k1_add_events);
         self.secp256k1_double_events.append(&mut other.secp256k1_double_events);
+        self.secp256r1_add_events.append(&mut other.secp256r1_add_events);
+        self.secp256r1_double_events.append(&mut other.secp256r1_double_events);
         self.bn254_add_events.append(&mut other.bn254_add_events);
         self.bn254_double_events.append(&mut other.bn254_double_events);
         self.k256_decompress_events.append(&mut other.k256_decompress_events);
+        self.p256_decompress_events.append(&mut other.p256_decompress_events);
         self.bls12381_add_events.append(&mut other.bls12381_add_events);
         self.bls12381_double_events.append(&mut other.bls12381_double_events);
         self.uint256_mul_events.append(&mut other.uint256_mul_events);
+        self.uint320_mod_events.append(&mut other.uint320_mod_events);
+        self.uint384_mod_events.append(&mut other.uint384_mod_events);
+        self.poseidon2_permute_events.append(&mut other.poseidon2_permute_events);
+        self.blake3_compress_events.append(&mut other.blake3_compress_events);
+        self.aes_encrypt_events.append(&mut other.aes_encrypt_events);
+        self.ripemd160_compress_events.append(&mut other.ripemd160_compress_events);
         self.bls12381_fp_events.append(&mut other.bls12381_fp_events);
         self.bls12381_fp2_addsub_events.append(&mut other.bls12381_fp2_addsub_events);
         self.bls12381_fp2_mul_events.append(&mut other.bls12381_fp2_mul_events);
@@ -417,6 +491,10 @@ impl MachineRecord for ExecutionRecord {
         self.lt_events.iter().enumerate().for_each(|(i, event)| {
             self.nonce_lookup.insert(event.lookup_id, i as u32);
         });
+
+        self.bitmanip_events.iter().enumerate().for_each(|(i, event)| {
+            self.nonce_lookup.insert(event.lookup_id, i as u32);
+        });
     }
 
     /// Retrieves the public values.  This method is needed for the `MachineRecord` trait, since
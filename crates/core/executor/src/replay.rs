@@ -0,0 +1,138 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufReader, ErrorKind, Read},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{context::OutputWriter, hook::HookError};
+
+/// The inputs of an execution, recorded with
+/// [`SP1ContextBuilder::record_replay`](crate::SP1ContextBuilder::record_replay) to execute it
+/// again deterministically with [`SP1ContextBuilder::replay`](crate::SP1ContextBuilder::replay).
+///
+/// The proofs for `verify_sp1_proof` aren't recorded, and must be given again to the replay.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayLog {
+    /// The input stream of the program when it started, i.e. its stdin.
+    pub stdin: Vec<Vec<u8>>,
+    /// The arguments of the program.
+    pub args: Vec<String>,
+    /// The environment variables of the program.
+    pub env: BTreeMap<String, String>,
+    /// The responses of the hooks, in the order they were invoked.
+    pub hook_responses: Vec<HookResponse>,
+}
+
+/// The response of a hook recorded in a [`ReplayLog`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HookResponse {
+    /// The file descriptor of the hook.
+    pub fd: u32,
+    /// What the hook returned.
+    pub result: Result<Vec<Vec<u8>>, HookError>,
+}
+
+/// The start of a replay file, followed by the [`HookResponse`]s as they are invoked.
+#[derive(Serialize, Deserialize)]
+struct ReplayHeader {
+    stdin: Vec<Vec<u8>>,
+    args: Vec<String>,
+    env: BTreeMap<String, String>,
+}
+
+impl ReplayLog {
+    /// Reads the log written by
+    /// [`SP1ContextBuilder::record_replay`](crate::SP1ContextBuilder::record_replay).
+    ///
+    /// The responses are written as the hooks are invoked, so that the log of an execution that
+    /// failed or was interrupted can be read too, up to its last complete response.
+    pub fn read(reader: impl Read) -> bincode::Result<Self> {
+        let mut reader = BufReader::new(reader);
+        let ReplayHeader { stdin, args, env } = bincode::deserialize_from(&mut reader)?;
+        let mut hook_responses = Vec::new();
+        loop {
+            match bincode::deserialize_from(&mut reader) {
+                Ok(response) => hook_responses.push(response),
+                Err(err) if is_end_of_log(&err) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(Self { stdin, args, env, hook_responses })
+    }
+}
+
+/// Whether reading a response failed because the log ends, possibly in the middle of a response
+/// that was being written.
+fn is_end_of_log(err: &bincode::Error) -> bool {
+    matches!(&**err, bincode::ErrorKind::Io(err) if err.kind() == ErrorKind::UnexpectedEof)
+}
+
+/// Writes the [`ReplayLog`] of an execution as it runs.
+pub(crate) struct ReplayRecorder<'a> {
+    writer: OutputWriter<'a>,
+    /// Whether writing the log failed, after which it isn't written anymore.
+    failed: bool,
+}
+
+impl<'a> ReplayRecorder<'a> {
+    pub(crate) fn new(writer: OutputWriter<'a>) -> Self {
+        Self { writer, failed: false }
+    }
+
+    /// Writes the inputs of the program, when it starts.
+    pub(crate) fn start(
+        &mut self,
+        stdin: &[Vec<u8>],
+        args: &[String],
+        env: &BTreeMap<String, String>,
+    ) {
+        let header = ReplayHeader { stdin: stdin.to_vec(), args: args.to_vec(), env: env.clone() };
+        self.write(&header);
+    }
+
+    /// Writes the response of the hook at `fd`.
+    pub(crate) fn record(&mut self, fd: u32, result: &Result<Vec<Vec<u8>>, HookError>) {
+        self.write(&HookResponse { fd, result: result.clone() });
+    }
+
+    fn write(&mut self, value: &impl Serialize) {
+        if self.failed {
+            return;
+        }
+        let mut writer = self.writer.lock().unwrap();
+        let result = bincode::serialize_into(&mut *writer, value)
+            .map_err(std::io::Error::other)
+            .and_then(|()| writer.flush());
+        if let Err(err) = result {
+            tracing::warn!("failed to write the replay log, no longer writing it: {err}");
+            self.failed = true;
+        }
+    }
+}
+
+/// Serves the hook responses of a [`ReplayLog`] in order, in place of the hooks.
+pub(crate) struct Replayer {
+    /// The recorded stdin, until it replaces the input stream when the program starts.
+    stdin: Option<Vec<Vec<u8>>>,
+    responses: std::vec::IntoIter<HookResponse>,
+}
+
+impl Replayer {
+    pub(crate) fn new(stdin: Option<Vec<Vec<u8>>>, hook_responses: Vec<HookResponse>) -> Self {
+        Self { stdin, responses: hook_responses.into_iter() }
+    }
+
+    /// The recorded stdin, taken when the program starts.
+    pub(crate) fn take_stdin(&mut self) -> Option<Vec<Vec<u8>>> {
+        self.stdin.take()
+    }
+
+    /// The recorded response of the hook at `fd`, if it is the next hook recorded.
+    pub(crate) fn next(&mut self, fd: u32) -> Option<Result<Vec<Vec<u8>>, HookError>> {
+        if self.responses.as_slice().first()?.fd != fd {
+            return None;
+        }
+        self.responses.next().map(|response| response.result)
+    }
+}
@@ -1,11 +1,16 @@
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, BTreeMap, HashMap},
     fmt::{Display, Formatter, Result as FmtResult},
     hash::Hash,
     ops::{Add, AddAssign},
 };
 
-use crate::{events::sorted_table_lines, syscalls::SyscallCode, Opcode};
+use serde::{Deserialize, Serialize};
+
+use crate::{events::sorted_table_lines, syscalls::SyscallCode, Opcode, OpcodeClass};
+
+/// The size in bytes of the pages [`ExecutionReport::touched_memory_pages`] counts.
+pub const MEMORY_PAGE_SIZE: u32 = 4096;
 
 /// An execution report.
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
@@ -14,12 +19,38 @@ pub struct ExecutionReport {
     pub opcode_counts: HashMap<Opcode, u64>,
     /// The syscall counts.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+    /// The cycles spent in each syscall: one for the `ECALL` of every invocation, plus the extra
+    /// cycles the syscall takes to execute.
+    pub syscall_cycles: HashMap<SyscallCode, u64>,
     /// The cycle tracker counts.
     pub cycle_tracker: HashMap<String, u64>,
     /// The unique memory address counts.
     pub touched_memory_addresses: u64,
+    /// The number of [`MEMORY_PAGE_SIZE`] pages of memory touched, including the pages of the
+    /// program image and of the registers.
+    pub touched_memory_pages: u64,
+    /// The number of bytes touched past the end of the program image, i.e. the peak heap usage
+    /// of the default allocator of the zkVM, which never frees memory.
+    pub peak_heap_bytes: u64,
+    /// The cycles spent in each function of the program, by demangled name, when profiled with
+    /// [`SP1ContextBuilder::function_profile`](crate::SP1ContextBuilder::function_profile).
+    ///
+    /// The cycles are those of the instructions of each function itself, not of the functions it
+    /// calls, and the instructions outside the symbols of the program are counted in
+    /// [`UNKNOWN_FUNCTION`].
+    pub function_cycles: HashMap<String, u64>,
+    /// The number of times the instruction at each program counter ran, when collected with
+    /// [`SP1ContextBuilder::pc_histogram`](crate::SP1ContextBuilder::pc_histogram).
+    pub pc_counts: HashMap<u32, u64>,
 }
 
+/// The name the cycles of instructions outside any function symbol are counted under in
+/// [`ExecutionReport::function_cycles`].
+pub const UNKNOWN_FUNCTION: &str = "<unknown>";
+
+/// The number of functions the [Display] implementation of [`ExecutionReport`] lists.
+const DISPLAYED_FUNCTIONS: usize = 20;
+
 impl ExecutionReport {
     /// Compute the total number of instructions run during the execution.
     #[must_use]
@@ -32,6 +63,106 @@ impl ExecutionReport {
     pub fn total_syscall_count(&self) -> u64 {
         self.syscall_counts.values().sum()
     }
+
+    /// Compute the cycles spent in each opcode class, one per instruction run.
+    #[must_use]
+    pub fn opcode_class_cycles(&self) -> HashMap<OpcodeClass, u64> {
+        let mut cycles = HashMap::new();
+        for (opcode, count) in &self.opcode_counts {
+            *cycles.entry(opcode.class()).or_default() += count;
+        }
+        cycles
+    }
+
+    /// The number of invocations and the cycles of every syscall invoked, the most cycles first.
+    #[must_use]
+    pub fn syscall_stats(&self) -> Vec<SyscallStats> {
+        let mut stats = self
+            .syscall_counts
+            .iter()
+            .map(|(&code, &count)| SyscallStats {
+                code,
+                count,
+                cycles: self.syscall_cycles.get(&code).copied().unwrap_or_default(),
+            })
+            .collect::<Vec<_>>();
+        stats.sort_unstable_by(|a, b| {
+            (b.cycles, b.count).cmp(&(a.cycles, a.count)).then_with(|| a.code.cmp(&b.code))
+        });
+        stats
+    }
+
+    /// The functions of the program and their cycles, the most cycles first, see
+    /// [`ExecutionReport::function_cycles`].
+    #[must_use]
+    pub fn function_profile(&self) -> Vec<(&str, u64)> {
+        let mut profile = self
+            .function_cycles
+            .iter()
+            .map(|(name, &cycles)| (name.as_str(), cycles))
+            .collect::<Vec<_>>();
+        profile.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        profile
+    }
+
+    /// The cycles spent in the soft-float routines of `compiler_builtins`, e.g. `__adddf3`, which
+    /// implement the floating point operations of programs, see
+    /// [`ExecutionReport::function_cycles`].
+    #[must_use]
+    pub fn soft_float_cycles(&self) -> u64 {
+        self.function_cycles
+            .iter()
+            .filter(|(name, _)| is_soft_float_function(name))
+            .map(|(_, cycles)| cycles)
+            .sum()
+    }
+
+    /// The breakdown of the cycles of the execution by opcode class and by syscall, in a form
+    /// suited for serialization.
+    #[must_use]
+    pub fn cycle_breakdown(&self) -> CycleBreakdown {
+        CycleBreakdown {
+            total_cycles: self.total_instruction_count(),
+            opcode_classes: self.opcode_class_cycles().into_iter().collect(),
+            syscalls: self.syscall_cycles.iter().map(|(k, v)| (*k, *v)).collect(),
+        }
+    }
+}
+
+/// The usage of a syscall during an execution, see [`ExecutionReport::syscall_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyscallStats {
+    /// The syscall.
+    pub code: SyscallCode,
+    /// The number of invocations of the syscall.
+    pub count: u64,
+    /// The cycles spent in the syscall, see [`ExecutionReport::syscall_cycles`].
+    pub cycles: u64,
+}
+
+/// The cycles of an execution by opcode class and by syscall, see
+/// [`ExecutionReport::cycle_breakdown`].
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CycleBreakdown {
+    /// The total number of cycles, one per instruction run.
+    pub total_cycles: u64,
+    /// The cycles spent in each opcode class.
+    pub opcode_classes: BTreeMap<OpcodeClass, u64>,
+    /// The cycles spent in each syscall, see [`ExecutionReport::syscall_cycles`].
+    pub syscalls: BTreeMap<SyscallCode, u64>,
+}
+
+/// Whether `name` is a soft-float routine of `compiler_builtins`, such as `__mulsf3`,
+/// `__ltdf2`, `__fixdfsi` or `__extendsfdf2`.
+fn is_soft_float_function(name: &str) -> bool {
+    const OPERATIONS: [&str; 18] = [
+        "add", "sub", "mul", "div", "neg", "pow", "cmp", "eq", "ne", "lt", "le", "gt", "ge",
+        "unord", "fix", "float", "extend", "trunc",
+    ];
+    name.strip_prefix("__").is_some_and(|name| {
+        OPERATIONS.iter().any(|operation| name.starts_with(operation))
+            && (name.contains("sf") || name.contains("df"))
+    })
 }
 
 /// Combines two `HashMap`s together. If a key is in both maps, the values are added together.
@@ -53,7 +184,12 @@ impl AddAssign for ExecutionReport {
     fn add_assign(&mut self, rhs: Self) {
         hashmap_add_assign(&mut self.opcode_counts, rhs.opcode_counts);
         hashmap_add_assign(&mut self.syscall_counts, rhs.syscall_counts);
+        hashmap_add_assign(&mut self.syscall_cycles, rhs.syscall_cycles);
         self.touched_memory_addresses += rhs.touched_memory_addresses;
+        self.touched_memory_pages += rhs.touched_memory_pages;
+        self.peak_heap_bytes = self.peak_heap_bytes.max(rhs.peak_heap_bytes);
+        hashmap_add_assign(&mut self.function_cycles, rhs.function_cycles);
+        hashmap_add_assign(&mut self.pc_counts, rhs.pc_counts);
     }
 }
 
@@ -73,11 +209,62 @@ impl Display for ExecutionReport {
             writeln!(f, "  {line}")?;
         }
 
-        writeln!(f, "syscall counts ({} total syscall instructions):", self.total_syscall_count())?;
-        for line in sorted_table_lines(&self.syscall_counts) {
+        writeln!(f, "cycles by opcode class:")?;
+        for line in sorted_table_lines(self.opcode_class_cycles()) {
             writeln!(f, "  {line}")?;
         }
 
+        writeln!(f, "syscalls ({} total syscall instructions):", self.total_syscall_count())?;
+        let stats = self.syscall_stats();
+        let count_width =
+            stats.iter().map(|stats| stats.count.to_string().len()).max().unwrap_or(0).max(5);
+        let cycles_width =
+            stats.iter().map(|stats| stats.cycles.to_string().len()).max().unwrap_or(0).max(6);
+        if !stats.is_empty() {
+            writeln!(f, "  {:>count_width$} {:>cycles_width$} syscall", "count", "cycles")?;
+        }
+        for SyscallStats { code, count, cycles } in stats {
+            let kind = if code.is_precompile() { " (precompile)" } else { "" };
+            writeln!(
+                f,
+                "  {count:>count_width$} {cycles:>cycles_width$} {}{kind}",
+                code.to_string().to_lowercase()
+            )?;
+        }
+
+        writeln!(
+            f,
+            "memory: {} touched addresses in {} pages, {} bytes of peak heap usage",
+            self.touched_memory_addresses, self.touched_memory_pages, self.peak_heap_bytes
+        )?;
+
+        let profile = self.function_profile();
+        if !profile.is_empty() {
+            let total = self.total_instruction_count().max(1);
+            writeln!(f, "cycles by function ({} functions):", profile.len())?;
+            let soft_float = self.soft_float_cycles();
+            if soft_float > 0 {
+                let permille = soft_float * 1000 / total;
+                writeln!(
+                    f,
+                    "  {soft_float} cycles ({}.{}%) in soft-float routines",
+                    permille / 10,
+                    permille % 10
+                )?;
+            }
+            let cycles_width =
+                profile.iter().map(|(_, cycles)| cycles.to_string().len()).max().unwrap_or(0);
+            for (name, cycles) in profile.into_iter().take(DISPLAYED_FUNCTIONS) {
+                let permille = cycles * 1000 / total;
+                writeln!(
+                    f,
+                    "  {cycles:>cycles_width$} {:>3}.{}% {name}",
+                    permille / 10,
+                    permille % 10
+                )?;
+            }
+        }
+
         Ok(())
     }
 }
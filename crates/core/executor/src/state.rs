@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     fs::File,
     io::{Seek, Write},
 };
@@ -11,9 +12,10 @@ use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, ShardProof, StarkVerifyi
 
 use crate::{
     events::MemoryRecord,
+    memory::PagedMemory,
     record::{ExecutionRecord, MemoryAccessRecord},
     syscalls::SyscallCode,
-    ExecutorMode,
+    ExecutionReport, ExecutorMode, HookResponse,
 };
 
 /// Holds data describing the current state of a program's execution.
@@ -38,12 +40,8 @@ pub struct ExecutionState {
     pub pc: u32,
 
     /// The memory which instructions operate over. Values contain the memory value and last shard
-    /// + timestamp that each memory address was accessed.
-    // #[serde(
-    //     serialize_with = "serialize_hashmap_as_vec",
-    //     deserialize_with = "deserialize_hashmap_as_vec"
-    // )]
-    pub memory: HashMap<u32, MemoryRecord, BuildNoHashHasher<u32>>,
+    /// + timestamp that each memory address was accessed. Only the pages touched are allocated.
+    pub memory: PagedMemory<MemoryRecord>,
 
     /// Uninitialized memory addresses that have a specific value they should be initialized with.
     /// SyscallHintRead uses this to write hint data into uninitialized memory.
@@ -51,7 +49,7 @@ pub struct ExecutionState {
     //     serialize_with = "serialize_hashmap_as_vec",
     //     deserialize_with = "deserialize_hashmap_as_vec"
     // )]
-    pub uninitialized_memory: HashMap<u32, u32, BuildNoHashHasher<u32>>,
+    pub uninitialized_memory: PagedMemory<u32>,
 
     /// A stream of input values (global to the entire program).
     pub input_stream: Vec<Vec<u8>>,
@@ -74,6 +72,17 @@ pub struct ExecutionState {
 
     /// Keeps track of how many times a certain syscall has been called.
     pub syscall_counts: HashMap<SyscallCode, u64>,
+
+    /// The arguments of the program, see [`SP1Context::args`](crate::SP1Context::args).
+    pub args: Vec<String>,
+
+    /// The environment variables of the program, see
+    /// [`SP1Context::env`](crate::SP1Context::env).
+    pub env: BTreeMap<String, String>,
+
+    /// The responses of the hooks invoked from this checkpoint until the next one, replayed
+    /// rather than invoking the hooks again when re-executing from it.
+    pub hook_responses: Vec<HookResponse>,
 }
 
 impl ExecutionState {
@@ -87,8 +96,8 @@ impl ExecutionState {
             clk: 0,
             channel: 0,
             pc: pc_start,
-            memory: HashMap::default(),
-            uninitialized_memory: HashMap::default(),
+            memory: PagedMemory::new(),
+            uninitialized_memory: PagedMemory::new(),
             input_stream: Vec::new(),
             input_stream_ptr: 0,
             public_values_stream: Vec::new(),
@@ -96,10 +105,29 @@ impl ExecutionState {
             proof_stream: Vec::new(),
             proof_stream_ptr: 0,
             syscall_counts: HashMap::new(),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            hook_responses: Vec::new(),
         }
     }
 }
 
+/// A snapshot of an execution, taken with [`Executor::snapshot`] and continued with
+/// [`Executor::restore`] or [`Executor::fork`].
+///
+/// [`Executor::snapshot`]: crate::Executor::snapshot
+/// [`Executor::restore`]: crate::Executor::restore
+/// [`Executor::fork`]: crate::Executor::fork
+#[derive(Debug, Clone)]
+pub struct ExecutionSnapshot {
+    /// The state of the execution.
+    pub state: ExecutionState,
+    /// The report of the execution so far.
+    pub report: ExecutionReport,
+    /// The call stack of the execution, for the backtraces of faults.
+    pub(crate) call_stack: Vec<u32>,
+}
+
 /// Holds data to track changes made to the runtime since a fork point.
 #[derive(Debug, Clone, Default)]
 #[allow(dead_code)]
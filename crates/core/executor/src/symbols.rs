@@ -0,0 +1,76 @@
+use std::{borrow::Cow, sync::Arc};
+
+use elf::{abi::STT_FUNC, endian::LittleEndian, ElfBytes};
+
+/// The functions of a program, from the symbol table of its ELF, used to attribute the cycles of
+/// an execution to functions.
+///
+/// It is only kept when the ELF of the program isn't stripped of its symbols.
+#[derive(Debug, Clone)]
+pub struct SymbolTable {
+    /// The functions, sorted by start address and not overlapping.
+    functions: Arc<[FunctionSymbol]>,
+}
+
+/// A function of a [`SymbolTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionSymbol {
+    /// The demangled name of the function.
+    pub name: String,
+    /// The address of the first instruction of the function.
+    pub start: u32,
+    /// The size in bytes of the function.
+    pub size: u32,
+}
+
+impl SymbolTable {
+    /// The functions of `elf`, or `None` if it has no function symbols.
+    #[must_use]
+    pub fn new(elf: &[u8]) -> Option<Self> {
+        let file = ElfBytes::<LittleEndian>::minimal_parse(elf).ok()?;
+        let (symbols, strings) = file.symbol_table().ok()??;
+        let functions = symbols
+            .iter()
+            .filter(|symbol| symbol.st_symtype() == STT_FUNC && symbol.st_size > 0)
+            .filter_map(|symbol| {
+                let name = strings.get(symbol.st_name as usize).ok()?;
+                Some(FunctionSymbol {
+                    name: addr2line::demangle_auto(Cow::Borrowed(name), None).into_owned(),
+                    start: symbol.st_value.try_into().ok()?,
+                    size: symbol.st_size.try_into().ok()?,
+                })
+            });
+        let table = functions.collect::<Self>();
+        (!table.functions.is_empty()).then_some(table)
+    }
+
+    /// The function containing the instruction at `pc`, if any.
+    #[must_use]
+    pub fn function_at(&self, pc: u32) -> Option<&FunctionSymbol> {
+        let index = self.functions.partition_point(|function| function.start <= pc);
+        let function = self.functions.get(index.checked_sub(1)?)?;
+        (pc - function.start < function.size).then_some(function)
+    }
+
+    /// The functions, sorted by start address.
+    #[must_use]
+    pub fn functions(&self) -> &[FunctionSymbol] {
+        &self.functions
+    }
+}
+
+impl FromIterator<FunctionSymbol> for SymbolTable {
+    /// Collects the functions into a table, keeping the first of the functions starting at the
+    /// same address, e.g. aliases, and truncating the functions overlapping the next one.
+    fn from_iter<T: IntoIterator<Item = FunctionSymbol>>(iter: T) -> Self {
+        let mut functions = iter.into_iter().collect::<Vec<_>>();
+        functions.sort_by_key(|function| function.start);
+        functions.dedup_by_key(|function| function.start);
+        for i in 1..functions.len() {
+            let next_start = functions[i].start;
+            let function = &mut functions[i - 1];
+            function.size = function.size.min(next_start - function.start);
+        }
+        Self { functions: functions.into() }
+    }
+}
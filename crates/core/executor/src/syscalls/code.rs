@@ -22,6 +22,7 @@ use strum_macros::EnumIter;
 )]
 #[allow(non_camel_case_types)]
 #[allow(clippy::upper_case_acronyms)]
+#[allow(clippy::mistyped_literal_suffixes)]
 pub enum SyscallCode {
     /// Halts the program.
     HALT = 0x00_00_00_00,
@@ -127,11 +128,54 @@ pub enum SyscallCode {
 
     /// Executes the `BN254_FP2_MUL` precompile.
     BN254_FP2_MUL = 0x00_01_01_2B,
+
+    /// Executes the `SECP256R1_ADD` precompile.
+    SECP256R1_ADD = 0x00_01_01_2C,
+
+    /// Executes the `SECP256R1_DOUBLE` precompile.
+    SECP256R1_DOUBLE = 0x00_00_01_2D,
+
+    /// Executes the `SECP256R1_DECOMPRESS` precompile.
+    SECP256R1_DECOMPRESS = 0x00_00_01_2E,
+
+    /// Executes the `POSEIDON2_PERMUTE` precompile.
+    POSEIDON2_PERMUTE = 0x00_01_01_2F,
+
+    /// Executes the `BLAKE3_COMPRESS` precompile.
+    BLAKE3_COMPRESS = 0x00_01_01_30,
+
+    /// Executes the `SHA512_EXTEND` precompile.
+    SHA512_EXTEND = 0x00_40_01_31,
+
+    /// Executes the `SHA512_COMPRESS` precompile.
+    SHA512_COMPRESS = 0x00_01_01_32,
+
+    /// Executes the `AES128_ENCRYPT` precompile.
+    AES128_ENCRYPT = 0x00_01_01_33,
+
+    /// Executes the `AES256_ENCRYPT` precompile.
+    AES256_ENCRYPT = 0x00_01_01_34,
+
+    /// Executes the `RIPEMD160_COMPRESS` precompile.
+    RIPEMD160_COMPRESS = 0x00_01_01_35,
+
+    /// Executes the `UINT320_ADD` precompile.
+    UINT320_ADD = 0x00_01_01_36,
+
+    /// Executes the `UINT320_MUL` precompile.
+    UINT320_MUL = 0x00_01_01_37,
+
+    /// Executes the `UINT384_ADD` precompile.
+    UINT384_ADD = 0x00_01_01_38,
+
+    /// Executes the `UINT384_MUL` precompile.
+    UINT384_MUL = 0x00_01_01_39,
 }
 
 impl SyscallCode {
     /// Create a [`SyscallCode`] from a u32.
     #[must_use]
+    #[allow(clippy::mistyped_literal_suffixes)]
     pub fn from_u32(value: u32) -> Self {
         match value {
             0x00_00_00_00 => SyscallCode::HALT,
@@ -169,6 +213,20 @@ impl SyscallCode {
             0x00_01_01_2A => SyscallCode::BN254_FP2_SUB,
             0x00_01_01_2B => SyscallCode::BN254_FP2_MUL,
             0x00_00_01_1C => SyscallCode::BLS12381_DECOMPRESS,
+            0x00_01_01_2C => SyscallCode::SECP256R1_ADD,
+            0x00_00_01_2D => SyscallCode::SECP256R1_DOUBLE,
+            0x00_00_01_2E => SyscallCode::SECP256R1_DECOMPRESS,
+            0x00_01_01_2F => SyscallCode::POSEIDON2_PERMUTE,
+            0x00_01_01_30 => SyscallCode::BLAKE3_COMPRESS,
+            0x00_40_01_31 => SyscallCode::SHA512_EXTEND,
+            0x00_01_01_32 => SyscallCode::SHA512_COMPRESS,
+            0x00_01_01_33 => SyscallCode::AES128_ENCRYPT,
+            0x00_01_01_34 => SyscallCode::AES256_ENCRYPT,
+            0x00_01_01_35 => SyscallCode::RIPEMD160_COMPRESS,
+            0x00_01_01_36 => SyscallCode::UINT320_ADD,
+            0x00_01_01_37 => SyscallCode::UINT320_MUL,
+            0x00_01_01_38 => SyscallCode::UINT384_ADD,
+            0x00_01_01_39 => SyscallCode::UINT384_MUL,
             _ => panic!("invalid syscall number: {value}"),
         }
     }
@@ -191,6 +249,12 @@ impl SyscallCode {
         (self as u32).to_le_bytes()[2].into()
     }
 
+    /// Get whether the syscall is a precompile, executed by its own chip.
+    #[must_use]
+    pub fn is_precompile(self) -> bool {
+        self.should_send() == 1
+    }
+
     /// Map a syscall to another one in order to coalesce their counts.
     #[must_use]
     #[allow(clippy::match_same_arms)]
@@ -202,6 +266,8 @@ impl SyscallCode {
             SyscallCode::BLS12381_FP_SUB => SyscallCode::BLS12381_FP_ADD,
             SyscallCode::BLS12381_FP_MUL => SyscallCode::BLS12381_FP_ADD,
             SyscallCode::BLS12381_FP2_SUB => SyscallCode::BLS12381_FP2_ADD,
+            SyscallCode::UINT320_MUL => SyscallCode::UINT320_ADD,
+            SyscallCode::UINT384_MUL => SyscallCode::UINT384_ADD,
             _ => *self,
         }
     }
@@ -31,25 +31,18 @@ impl Syscall for HintReadSyscall {
         assert!(!ctx.rt.unconstrained, "hint read should not be used in a unconstrained block");
         assert_eq!(vec.len() as u32, len, "hint input stream read length mismatch");
         assert_eq!(ptr % 4, 0, "hint read address not aligned to 4 bytes");
-        // Iterate through the vec in 4-byte chunks
-        for i in (0..len).step_by(4) {
-            // Get each byte in the chunk
-            let b1 = vec[i as usize];
-            // In case the vec is not a multiple of 4, right-pad with 0s. This is fine because we
-            // are assuming the word is uninitialized, so filling it with 0s makes sense.
-            let b2 = vec.get(i as usize + 1).copied().unwrap_or(0);
-            let b3 = vec.get(i as usize + 2).copied().unwrap_or(0);
-            let b4 = vec.get(i as usize + 3).copied().unwrap_or(0);
-            let word = u32::from_le_bytes([b1, b2, b3, b4]);
+        // Pack the vec into words, right-padding the last one with 0s. This is fine because we
+        // are assuming the words are uninitialized, so filling them with 0s makes sense.
+        let words = vec.chunks(4).map(|chunk| {
+            let mut bytes = [0; 4];
+            bytes[..chunk.len()].copy_from_slice(chunk);
+            u32::from_le_bytes(bytes)
+        });
 
-            // Save the data into runtime state so the runtime will use the desired data instead of
-            // 0 when first reading/writing from this address.
-            ctx.rt
-                .state
-                .uninitialized_memory
-                .entry(ptr + i)
-                .and_modify(|_| panic!("hint read address is initialized already"))
-                .or_insert(word);
+        // Save the data into runtime state a page at a time, so the runtime will use the desired
+        // data instead of 0 when first reading/writing from these addresses.
+        if let Err(addr) = ctx.rt.state.uninitialized_memory.try_insert_words(ptr, words) {
+            panic!("hint read address 0x{addr:08x} is initialized already");
         }
         None
     }
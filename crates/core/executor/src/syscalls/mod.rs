@@ -22,23 +22,32 @@ pub use code::*;
 pub use context::*;
 use hint::{HintLenSyscall, HintReadSyscall};
 use precompiles::{
+    aes::AesEncryptSyscall,
+    blake3::Blake3CompressSyscall,
     edwards::{add::EdwardsAddAssignSyscall, decompress::EdwardsDecompressSyscall},
     fptower::{Fp2AddSubSyscall, Fp2MulSyscall, FpOpSyscall},
     keccak256::permute::Keccak256PermuteSyscall,
+    poseidon2::Poseidon2PermuteSyscall,
+    ripemd160::Ripemd160CompressSyscall,
     sha256::{compress::Sha256CompressSyscall, extend::Sha256ExtendSyscall},
+    sha512::{compress::Sha512CompressSyscall, extend::Sha512ExtendSyscall},
     uint256::Uint256MulSyscall,
+    uint_mod::UintModSyscall,
     weierstrass::{
         add::WeierstrassAddAssignSyscall, decompress::WeierstrassDecompressSyscall,
         double::WeierstrassDoubleAssignSyscall,
     },
 };
+pub use write::{FD_ARGS, FD_CYCLE_COUNT, FD_GETENV, FD_PANIC};
 
 use sp1_curves::{
     edwards::ed25519::{Ed25519, Ed25519Parameters},
+    uint::{U320Field, U384Field},
     weierstrass::{
         bls12_381::{Bls12381, Bls12381BaseField},
         bn254::{Bn254, Bn254BaseField},
         secp256k1::Secp256k1,
+        secp256r1::Secp256r1,
     },
 };
 use unconstrained::{EnterUnconstrainedSyscall, ExitUnconstrainedSyscall};
@@ -69,6 +78,7 @@ pub trait Syscall: Send + Sync {
 
 /// Creates the default syscall map.
 #[must_use]
+#[allow(clippy::too_many_lines)]
 pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
     let mut syscall_map = HashMap::<SyscallCode, Arc<dyn Syscall>>::default();
 
@@ -78,6 +88,16 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::SHA_COMPRESS, Arc::new(Sha256CompressSyscall));
 
+    syscall_map.insert(SyscallCode::SHA512_EXTEND, Arc::new(Sha512ExtendSyscall));
+
+    syscall_map.insert(SyscallCode::SHA512_COMPRESS, Arc::new(Sha512CompressSyscall));
+
+    syscall_map.insert(SyscallCode::AES128_ENCRYPT, Arc::new(AesEncryptSyscall::new(false)));
+
+    syscall_map.insert(SyscallCode::AES256_ENCRYPT, Arc::new(AesEncryptSyscall::new(true)));
+
+    syscall_map.insert(SyscallCode::RIPEMD160_COMPRESS, Arc::new(Ripemd160CompressSyscall));
+
     syscall_map.insert(SyscallCode::ED_ADD, Arc::new(EdwardsAddAssignSyscall::<Ed25519>::new()));
 
     syscall_map.insert(
@@ -102,6 +122,21 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
         Arc::new(WeierstrassDecompressSyscall::<Secp256k1>::new()),
     );
 
+    syscall_map.insert(
+        SyscallCode::SECP256R1_ADD,
+        Arc::new(WeierstrassAddAssignSyscall::<Secp256r1>::new()),
+    );
+
+    syscall_map.insert(
+        SyscallCode::SECP256R1_DOUBLE,
+        Arc::new(WeierstrassDoubleAssignSyscall::<Secp256r1>::new()),
+    );
+
+    syscall_map.insert(
+        SyscallCode::SECP256R1_DECOMPRESS,
+        Arc::new(WeierstrassDecompressSyscall::<Secp256r1>::new()),
+    );
+
     syscall_map
         .insert(SyscallCode::BN254_ADD, Arc::new(WeierstrassAddAssignSyscall::<Bn254>::new()));
 
@@ -122,6 +157,30 @@ pub fn default_syscall_map() -> HashMap<SyscallCode, Arc<dyn Syscall>> {
 
     syscall_map.insert(SyscallCode::UINT256_MUL, Arc::new(Uint256MulSyscall));
 
+    syscall_map.insert(
+        SyscallCode::UINT320_ADD,
+        Arc::new(UintModSyscall::<U320Field>::new(FieldOperation::Add)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::UINT320_MUL,
+        Arc::new(UintModSyscall::<U320Field>::new(FieldOperation::Mul)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::UINT384_ADD,
+        Arc::new(UintModSyscall::<U384Field>::new(FieldOperation::Add)),
+    );
+
+    syscall_map.insert(
+        SyscallCode::UINT384_MUL,
+        Arc::new(UintModSyscall::<U384Field>::new(FieldOperation::Mul)),
+    );
+
+    syscall_map.insert(SyscallCode::POSEIDON2_PERMUTE, Arc::new(Poseidon2PermuteSyscall::new()));
+
+    syscall_map.insert(SyscallCode::BLAKE3_COMPRESS, Arc::new(Blake3CompressSyscall));
+
     syscall_map.insert(
         SyscallCode::BLS12381_FP_ADD,
         Arc::new(FpOpSyscall::<Bls12381BaseField>::new(FieldOperation::Add)),
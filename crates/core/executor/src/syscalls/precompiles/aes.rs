@@ -0,0 +1,122 @@
+use crate::{
+    events::{
+        aes_xtime, AesEncryptEvent, AES128_NUM_ROUNDS, AES256_NUM_ROUNDS, AES_BLOCK_WORDS, AES_SBOX,
+    },
+    syscalls::{Syscall, SyscallContext},
+};
+
+/// Encrypts the 16-byte `block` with the expanded `round_keys`, four little-endian words per round
+/// and one more for the initial key addition. The number of rounds follows from the number of
+/// round keys.
+#[must_use]
+pub fn aes_encrypt_block(block: [u8; 16], round_keys: &[u32]) -> [u8; 16] {
+    let num_rounds = round_keys.len() / AES_BLOCK_WORDS - 1;
+    let round_key = |round: usize| -> [u8; 16] {
+        let mut key = [0; 16];
+        for (bytes, word) in key.chunks_exact_mut(4).zip(&round_keys[4 * round..4 * round + 4]) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        key
+    };
+
+    let mut state = block;
+    for (byte, key) in state.iter_mut().zip(round_key(0)) {
+        *byte ^= key;
+    }
+    for round in 1..=num_rounds {
+        // SubBytes and ShiftRows, the bytes being in column-major order.
+        let s: [u8; 16] = core::array::from_fn(|i| {
+            let (col, row) = (i / 4, i % 4);
+            AES_SBOX[state[4 * ((col + row) % 4) + row] as usize]
+        });
+        if round < num_rounds {
+            // MixColumns.
+            for col in 0..4 {
+                let a = &s[4 * col..4 * col + 4];
+                for row in 0..4 {
+                    state[4 * col + row] = aes_xtime(a[row])
+                        ^ aes_xtime(a[(row + 1) % 4])
+                        ^ a[(row + 1) % 4]
+                        ^ a[(row + 2) % 4]
+                        ^ a[(row + 3) % 4];
+                }
+            }
+        } else {
+            state = s;
+        }
+        for (byte, key) in state.iter_mut().zip(round_key(round)) {
+            *byte ^= key;
+        }
+    }
+    state
+}
+
+pub(crate) struct AesEncryptSyscall {
+    is_aes256: bool,
+}
+
+impl AesEncryptSyscall {
+    pub const fn new(is_aes256: bool) -> Self {
+        Self { is_aes256 }
+    }
+}
+
+impl Syscall for AesEncryptSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let block_ptr = arg1;
+        if block_ptr % 4 != 0 {
+            panic!();
+        }
+        let round_keys_ptr = arg2;
+        if round_keys_ptr % 4 != 0 {
+            panic!();
+        }
+        let num_rounds = if self.is_aes256 { AES256_NUM_ROUNDS } else { AES128_NUM_ROUNDS };
+
+        // Read the block with `slice_unsafe`, since it is overwritten with the ciphertext later.
+        let input: [u32; AES_BLOCK_WORDS] =
+            rt.slice_unsafe(block_ptr, AES_BLOCK_WORDS).try_into().unwrap();
+
+        // Read the round keys.
+        let (round_key_read_records, round_keys) =
+            rt.mr_slice(round_keys_ptr, AES_BLOCK_WORDS * (num_rounds + 1));
+
+        let mut block = [0; 16];
+        for (bytes, word) in block.chunks_exact_mut(4).zip(input) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        let ciphertext = aes_encrypt_block(block, &round_keys);
+        let output: [u32; AES_BLOCK_WORDS] = core::array::from_fn(|i| {
+            u32::from_le_bytes(ciphertext[4 * i..4 * i + 4].try_into().unwrap())
+        });
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let block_write_records = rt.mw_slice(block_ptr, &output);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().aes_encrypt_events.push(AesEncryptEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            is_aes256: self.is_aes256,
+            block_ptr,
+            round_keys_ptr,
+            input,
+            output,
+            round_keys,
+            block_write_records,
+            round_key_read_records,
+        });
+
+        None
+    }
+}
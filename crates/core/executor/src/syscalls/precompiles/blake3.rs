@@ -0,0 +1,105 @@
+use crate::{
+    events::{Blake3CompressEvent, BLAKE3_STATE_SIZE},
+    syscalls::{Syscall, SyscallContext},
+};
+
+/// The indices of the message words used by each round, the message being permuted between
+/// rounds.
+pub const BLAKE3_MSG_SCHEDULE: [[usize; BLAKE3_STATE_SIZE]; 7] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// The indices of the state words mixed by each `G` function of a round: the columns, then the
+/// diagonals of the state.
+pub const BLAKE3_G_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The BLAKE3 compression function on the full initial `state`, returning the 16 words of its
+/// extended output: the new chaining value followed by the words used for the XOF.
+pub fn blake3_compress(
+    state: &[u32; BLAKE3_STATE_SIZE],
+    msg: &[u32; BLAKE3_STATE_SIZE],
+) -> [u32; BLAKE3_STATE_SIZE] {
+    let mut v = *state;
+    for schedule in &BLAKE3_MSG_SCHEDULE {
+        for (j, &[a, b, c, d]) in BLAKE3_G_INDICES.iter().enumerate() {
+            let (mx, my) = (msg[schedule[2 * j]], msg[schedule[2 * j + 1]]);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(mx);
+            v[d] = (v[d] ^ v[a]).rotate_right(16);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(12);
+            v[a] = v[a].wrapping_add(v[b]).wrapping_add(my);
+            v[d] = (v[d] ^ v[a]).rotate_right(8);
+            v[c] = v[c].wrapping_add(v[d]);
+            v[b] = (v[b] ^ v[c]).rotate_right(7);
+        }
+    }
+    core::array::from_fn(|i| if i < 8 { v[i] ^ v[i + 8] } else { v[i] ^ state[i - 8] })
+}
+
+pub(crate) struct Blake3CompressSyscall;
+
+impl Syscall for Blake3CompressSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let state_ptr = arg1;
+        if state_ptr % 4 != 0 {
+            panic!();
+        }
+        let msg_ptr = arg2;
+        if msg_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // Read the state with `slice_unsafe`, since it is overwritten with the result later.
+        let pre_state: [u32; BLAKE3_STATE_SIZE] =
+            rt.slice_unsafe(state_ptr, BLAKE3_STATE_SIZE).try_into().unwrap();
+
+        // Read the message block.
+        let (msg_read_records, msg) = rt.mr_slice(msg_ptr, BLAKE3_STATE_SIZE);
+        let msg: [u32; BLAKE3_STATE_SIZE] = msg.try_into().unwrap();
+
+        let post_state = blake3_compress(&pre_state, &msg);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let state_write_records = rt.mw_slice(state_ptr, &post_state);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().blake3_compress_events.push(Blake3CompressEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            state_ptr,
+            msg_ptr,
+            pre_state,
+            post_state,
+            msg,
+            state_write_records,
+            msg_read_records,
+        });
+
+        None
+    }
+}
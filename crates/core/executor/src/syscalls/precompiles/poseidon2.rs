@@ -0,0 +1,65 @@
+use p3_baby_bear::BabyBear;
+use p3_field::{AbstractField, PrimeField32};
+use p3_symmetric::Permutation;
+use sp1_stark::{inner_perm, InnerPerm};
+
+use crate::{
+    events::{Poseidon2PermuteEvent, POSEIDON2_WIDTH},
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct Poseidon2PermuteSyscall {
+    /// The permutation of the prover, over `BabyBear` with a width of 16.
+    perm: InnerPerm,
+}
+
+impl Poseidon2PermuteSyscall {
+    pub(crate) fn new() -> Self {
+        Self { perm: inner_perm() }
+    }
+}
+
+impl Syscall for Poseidon2PermuteSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let state_ptr = arg1;
+        if state_ptr % 4 != 0 {
+            panic!();
+        }
+        if arg2 != 0 {
+            panic!("Expected arg2 to be 0, got {arg2}");
+        }
+
+        // Read the state with `slice_unsafe`, since it is overwritten with the result later.
+        let pre_state: [u32; POSEIDON2_WIDTH] =
+            rt.slice_unsafe(state_ptr, POSEIDON2_WIDTH).try_into().unwrap();
+
+        let mut state = pre_state.map(BabyBear::from_wrapped_u32);
+        self.perm.permute_mut(&mut state);
+        let post_state = state.map(|x| x.as_canonical_u32());
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let state_write_records = rt.mw_slice(state_ptr, &post_state);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().poseidon2_permute_events.push(Poseidon2PermuteEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            state_ptr,
+            pre_state,
+            post_state,
+            state_write_records,
+        });
+
+        None
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    events::{
+        ripemd160_f, Ripemd160CompressEvent, RIPEMD160_BLOCK_SIZE, RIPEMD160_K_LEFT,
+        RIPEMD160_K_RIGHT, RIPEMD160_NUM_STEPS, RIPEMD160_R_LEFT, RIPEMD160_R_RIGHT,
+        RIPEMD160_STATE_SIZE, RIPEMD160_S_LEFT, RIPEMD160_S_RIGHT,
+    },
+    syscalls::{Syscall, SyscallContext},
+};
+
+/// A step of a line of the compression on the working variables `[a, b, c, d, e]`, with the
+/// boolean function and the constant of its round, the message word `x` and the rotation `s`.
+#[must_use]
+#[allow(clippy::many_single_char_names)]
+pub const fn ripemd160_step(
+    [a, b, c, d, e]: [u32; RIPEMD160_STATE_SIZE],
+    f: usize,
+    k: u32,
+    x: u32,
+    s: u32,
+) -> [u32; RIPEMD160_STATE_SIZE] {
+    let t = a
+        .wrapping_add(ripemd160_f(f, b, c, d))
+        .wrapping_add(x)
+        .wrapping_add(k)
+        .rotate_left(s)
+        .wrapping_add(e);
+    [e, t, b, c.rotate_left(10), d]
+}
+
+/// The RIPEMD-160 compression of the message `block` into `state`.
+#[must_use]
+pub fn ripemd160_compress(
+    state: &[u32; RIPEMD160_STATE_SIZE],
+    block: &[u32; RIPEMD160_BLOCK_SIZE],
+) -> [u32; RIPEMD160_STATE_SIZE] {
+    let mut left = *state;
+    let mut right = *state;
+    for j in 0..RIPEMD160_NUM_STEPS {
+        let round = j / 16;
+        left = ripemd160_step(
+            left,
+            round,
+            RIPEMD160_K_LEFT[round],
+            block[RIPEMD160_R_LEFT[j]],
+            RIPEMD160_S_LEFT[j],
+        );
+        right = ripemd160_step(
+            right,
+            4 - round,
+            RIPEMD160_K_RIGHT[round],
+            block[RIPEMD160_R_RIGHT[j]],
+            RIPEMD160_S_RIGHT[j],
+        );
+    }
+    core::array::from_fn(|i| {
+        state[(i + 1) % 5].wrapping_add(left[(i + 2) % 5]).wrapping_add(right[(i + 3) % 5])
+    })
+}
+
+pub(crate) struct Ripemd160CompressSyscall;
+
+impl Syscall for Ripemd160CompressSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+        let state_ptr = arg1;
+        if state_ptr % 4 != 0 {
+            panic!();
+        }
+        let block_ptr = arg2;
+        if block_ptr % 4 != 0 {
+            panic!();
+        }
+
+        // Read the state with `slice_unsafe`, since it is overwritten with the result later.
+        let pre_state: [u32; RIPEMD160_STATE_SIZE] =
+            rt.slice_unsafe(state_ptr, RIPEMD160_STATE_SIZE).try_into().unwrap();
+
+        // Read the message block.
+        let (block_read_records, block) = rt.mr_slice(block_ptr, RIPEMD160_BLOCK_SIZE);
+        let block: [u32; RIPEMD160_BLOCK_SIZE] = block.try_into().unwrap();
+
+        let post_state = ripemd160_compress(&pre_state, &block);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        let state_write_records = rt.mw_slice(state_ptr, &post_state);
+
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().ripemd160_compress_events.push(Ripemd160CompressEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk,
+            state_ptr,
+            block_ptr,
+            pre_state,
+            post_state,
+            block,
+            state_write_records,
+            block_read_records,
+        });
+
+        None
+    }
+}
@@ -0,0 +1,78 @@
+use super::read_u64;
+use crate::{
+    events::Sha512ExtendEvent,
+    syscalls::{Syscall, SyscallContext},
+};
+
+pub(crate) struct Sha512ExtendSyscall;
+
+impl Syscall for Sha512ExtendSyscall {
+    fn num_extra_cycles(&self) -> u32 {
+        64
+    }
+
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk_init = rt.clk;
+        let w_ptr = arg1;
+        assert!(arg2 == 0, "arg2 must be 0");
+        assert!(w_ptr % 8 == 0, "w_ptr must be 8-byte aligned");
+
+        let mut w_i_minus_15_reads = Vec::new();
+        let mut w_i_minus_2_reads = Vec::new();
+        let mut w_i_minus_16_reads = Vec::new();
+        let mut w_i_minus_7_reads = Vec::new();
+        let mut w_i_writes = Vec::new();
+        for i in 16..80 {
+            // Read w[i-15].
+            let (record, w_i_minus_15) = read_u64(rt, w_ptr + (i - 15) * 8);
+            w_i_minus_15_reads.push(record);
+
+            // Compute `s0`.
+            let s0 =
+                w_i_minus_15.rotate_right(1) ^ w_i_minus_15.rotate_right(8) ^ (w_i_minus_15 >> 7);
+
+            // Read w[i-2].
+            let (record, w_i_minus_2) = read_u64(rt, w_ptr + (i - 2) * 8);
+            w_i_minus_2_reads.push(record);
+
+            // Compute `s1`.
+            let s1 =
+                w_i_minus_2.rotate_right(19) ^ w_i_minus_2.rotate_right(61) ^ (w_i_minus_2 >> 6);
+
+            // Read w[i-16].
+            let (record, w_i_minus_16) = read_u64(rt, w_ptr + (i - 16) * 8);
+            w_i_minus_16_reads.push(record);
+
+            // Read w[i-7].
+            let (record, w_i_minus_7) = read_u64(rt, w_ptr + (i - 7) * 8);
+            w_i_minus_7_reads.push(record);
+
+            // Compute `w_i`.
+            let w_i = s1.wrapping_add(w_i_minus_16).wrapping_add(s0).wrapping_add(w_i_minus_7);
+
+            // Write w[i].
+            let records = rt.mw_slice(w_ptr + i * 8, &[w_i as u32, (w_i >> 32) as u32]);
+            w_i_writes.push(records.try_into().unwrap());
+            rt.clk += 1;
+        }
+
+        // Push the SHA-512 extend event.
+        let lookup_id = rt.syscall_lookup_id;
+        let shard = rt.current_shard();
+        let channel = rt.current_channel();
+        rt.record_mut().sha512_extend_events.push(Sha512ExtendEvent {
+            lookup_id,
+            shard,
+            channel,
+            clk: clk_init,
+            w_ptr,
+            w_i_minus_15_reads,
+            w_i_minus_2_reads,
+            w_i_minus_16_reads,
+            w_i_minus_7_reads,
+            w_i_writes,
+        });
+
+        None
+    }
+}
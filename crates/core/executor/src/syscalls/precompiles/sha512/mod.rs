@@ -0,0 +1,10 @@
+pub mod compress;
+pub mod extend;
+
+use crate::{events::MemoryReadRecord, syscalls::SyscallContext};
+
+/// Reads the double word at `addr` as two words, the low one first.
+fn read_u64(rt: &mut SyscallContext, addr: u32) -> ([MemoryReadRecord; 2], u64) {
+    let (records, values) = rt.mr_slice(addr, 2);
+    (records.try_into().unwrap(), values[0] as u64 | (values[1] as u64) << 32)
+}
@@ -0,0 +1,101 @@
+use std::marker::PhantomData;
+
+use num::{BigUint, One, Zero};
+use sp1_curves::{
+    params::NumWords,
+    uint::{UintModField, UintType},
+};
+use sp1_primitives::consts::{words_to_bytes_le_vec, WORD_SIZE};
+use typenum::Unsigned;
+
+use crate::{
+    events::{FieldOperation, UintModEvent},
+    syscalls::{Syscall, SyscallContext},
+};
+
+/// The modular addition or multiplication of the wide integers of `P`.
+///
+/// As with the uint256 multiplication, the modulus is read after y, and a zero modulus stands for
+/// `2^bits`.
+pub(crate) struct UintModSyscall<P> {
+    op: FieldOperation,
+    _marker: PhantomData<P>,
+}
+
+impl<P> UintModSyscall<P> {
+    pub const fn new(op: FieldOperation) -> Self {
+        Self { op, _marker: PhantomData }
+    }
+}
+
+impl<P: UintModField> Syscall for UintModSyscall<P> {
+    fn execute(&self, rt: &mut SyscallContext, arg1: u32, arg2: u32) -> Option<u32> {
+        let clk = rt.clk;
+
+        let x_ptr = arg1;
+        if x_ptr % 4 != 0 {
+            panic!();
+        }
+        let y_ptr = arg2;
+        if y_ptr % 4 != 0 {
+            panic!();
+        }
+
+        let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+
+        // First read the words for the x value. We can read a slice_unsafe here because we write
+        // the computed result to x later.
+        let x = rt.slice_unsafe(x_ptr, num_words);
+
+        // Read the y value, and the modulus stored after it.
+        let (y_memory_records, y) = rt.mr_slice(y_ptr, num_words);
+        let modulus_ptr = y_ptr + (num_words * WORD_SIZE) as u32;
+        let (modulus_memory_records, modulus) = rt.mr_slice(modulus_ptr, num_words);
+
+        let uint_x = BigUint::from_bytes_le(&words_to_bytes_le_vec(&x));
+        let uint_y = BigUint::from_bytes_le(&words_to_bytes_le_vec(&y));
+        let mut uint_modulus = BigUint::from_bytes_le(&words_to_bytes_le_vec(&modulus));
+        if uint_modulus.is_zero() {
+            uint_modulus = BigUint::one() << (num_words * WORD_SIZE * 8);
+        }
+
+        let result = match self.op {
+            FieldOperation::Add => (uint_x + uint_y) % uint_modulus,
+            FieldOperation::Mul => (uint_x * uint_y) % uint_modulus,
+            _ => panic!("Unsupported operation"),
+        };
+        let mut result = result.to_u32_digits();
+        result.resize(num_words, 0);
+
+        // Increment clk so that the write is not at the same cycle as the read.
+        rt.clk += 1;
+        // Write the result to x and keep track of the memory records.
+        let x_memory_records = rt.mw_slice(x_ptr, &result);
+
+        let event = UintModEvent {
+            lookup_id: rt.syscall_lookup_id,
+            shard: rt.current_shard(),
+            channel: rt.current_channel(),
+            clk,
+            op: self.op,
+            x_ptr,
+            x,
+            y_ptr,
+            y,
+            modulus,
+            x_memory_records,
+            y_memory_records,
+            modulus_memory_records,
+        };
+        match P::UINT_TYPE {
+            UintType::Uint320 => rt.record_mut().uint320_mod_events.push(event),
+            UintType::Uint384 => rt.record_mut().uint384_mod_events.push(event),
+        }
+
+        None
+    }
+
+    fn num_extra_cycles(&self) -> u32 {
+        1
+    }
+}
@@ -23,6 +23,7 @@ impl<E: EllipticCurve> Syscall for WeierstrassAddAssignSyscall<E> {
         let event = create_ec_add_event::<E>(rt, arg1, arg2);
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => rt.record_mut().secp256k1_add_events.push(event),
+            CurveType::Secp256r1 => rt.record_mut().secp256r1_add_events.push(event),
             CurveType::Bn254 => rt.record_mut().bn254_add_events.push(event),
             CurveType::Bls12381 => rt.record_mut().bls12381_add_events.push(event),
             _ => panic!("Unsupported curve"),
@@ -23,6 +23,7 @@ impl<E: EllipticCurve> Syscall for WeierstrassDecompressSyscall<E> {
         let event = create_ec_decompress_event::<E>(rt, arg1, arg2);
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => rt.record_mut().k256_decompress_events.push(event),
+            CurveType::Secp256r1 => rt.record_mut().p256_decompress_events.push(event),
             CurveType::Bls12381 => rt.record_mut().bls12381_decompress_events.push(event),
             _ => panic!("Unsupported curve"),
         }
@@ -23,6 +23,7 @@ impl<E: EllipticCurve> Syscall for WeierstrassDoubleAssignSyscall<E> {
         let event = create_ec_double_event::<E>(rt, arg1, arg2);
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => rt.record_mut().secp256k1_double_events.push(event),
+            CurveType::Secp256r1 => rt.record_mut().secp256r1_double_events.push(event),
             CurveType::Bn254 => rt.record_mut().bn254_double_events.push(event),
             CurveType::Bls12381 => rt.record_mut().bls12381_double_events.push(event),
             _ => panic!("Unsupported curve"),
@@ -1,9 +1,39 @@
 use sp1_primitives::consts::num_to_comma_separated;
 
-use crate::{Executor, Register};
+use crate::{Executor, OutputWriter, Register};
 
 use super::{Syscall, SyscallContext};
 
+/// The file descriptor through which programs request their arguments, see
+/// [`SP1Context::args`](crate::SP1Context::args).
+///
+/// The number of arguments, as 4 little-endian bytes, and then each argument are added to the
+/// input stream. Must be kept in sync with `sp1_zkvm::syscalls::FD_ARGS`.
+pub const FD_ARGS: u32 = 6;
+
+/// The file descriptor through which programs request their environment variables, see
+/// [`SP1Context::env`](crate::SP1Context::env).
+///
+/// The number of variables, as 4 little-endian bytes, followed by the name and the value of each
+/// variable, are added to the input stream. Must be kept in sync with
+/// `sp1_zkvm::syscalls::FD_GETENV`.
+pub const FD_GETENV: u32 = 7;
+
+/// The file descriptor through which the panic hook of programs reports the location and the
+/// message of a panic, separated by a newline, before halting.
+///
+/// The panic is returned as [`ExecutionError::GuestPanicked`](crate::ExecutionError::GuestPanicked)
+/// if the program halts with a non-zero exit code. Must be kept in sync with
+/// `sp1_zkvm::syscalls::FD_PANIC`.
+pub const FD_PANIC: u32 = 8;
+
+/// The file descriptor through which programs request the number of cycles executed so far.
+///
+/// The low 32 bits of the count, as 4 little-endian bytes, are added to the input stream. Like
+/// every hint, the count isn't constrained by the proof. Must be kept in sync with
+/// `sp1_zkvm::syscalls::FD_CYCLE_COUNT`.
+pub const FD_CYCLE_COUNT: u32 = 10;
+
 pub(crate) struct WriteSyscall;
 
 impl Syscall for WriteSyscall {
@@ -12,10 +42,10 @@ impl Syscall for WriteSyscall {
     /// If stdout (fd = 1):
     /// - If the stream is a cycle tracker, either log the cycle tracker or accumulate it in the
     ///   report.
-    /// - Else, print the stream to stdout.
+    /// - Else, write the stream to the stdout writer of the context, or print it to stdout.
     ///
     /// If stderr (fd = 2):
-    /// - Print the stream to stderr.
+    /// - Write the stream to the stderr writer of the context, or print it to stdout.
     ///
     /// If fd = 3:
     /// - Update the public value stream.
@@ -23,7 +53,17 @@ impl Syscall for WriteSyscall {
     /// If fd = 4:
     /// - Update the input stream.
     ///
-    /// If the fd matches a hook in the hook registry, invoke the hook.
+    /// If fd = [`FD_ARGS`] or [`FD_GETENV`]:
+    /// - Add the arguments or the environment variables of the program to the input stream.
+    ///
+    /// If fd = [`FD_PANIC`]:
+    /// - Record the panic of the program.
+    ///
+    /// If fd = [`FD_CYCLE_COUNT`]:
+    /// - Add the number of cycles executed so far to the input stream.
+    ///
+    /// If the fd matches a hook in the hook registry, invoke the hook. If the hook fails, the
+    /// execution fails with [`crate::ExecutionError::HookFailed`].
     ///
     /// Else, log a warning.
     #[allow(clippy::pedantic)]
@@ -40,6 +80,7 @@ impl Syscall for WriteSyscall {
             let s = core::str::from_utf8(slice).unwrap();
             match parse_cycle_tracker_command(s) {
                 Some(command) => handle_cycle_tracker_command(rt, command),
+                None if rt.stdout.is_some() => write_output(rt.stdout.as_ref(), slice),
                 None => {
                     // If the string does not match any known command, print it to stdout.
                     let flush_s = update_io_buf(ctx, fd, s);
@@ -48,6 +89,8 @@ impl Syscall for WriteSyscall {
                     }
                 }
             }
+        } else if fd == 2 && rt.stderr.is_some() {
+            write_output(rt.stderr.as_ref(), slice);
         } else if fd == 2 {
             let s = core::str::from_utf8(slice).unwrap();
             let flush_s = update_io_buf(ctx, fd, s);
@@ -58,11 +101,40 @@ impl Syscall for WriteSyscall {
             rt.state.public_values_stream.extend_from_slice(slice);
         } else if fd == 4 {
             rt.state.input_stream.push(slice.to_vec());
-        } else if let Some(mut hook) = rt.hook_registry.get(fd) {
-            let res = hook.invoke_hook(rt.hook_env(), slice);
-            // Add result vectors to the beginning of the stream.
+        } else if fd == FD_ARGS {
+            let argc = (rt.state.args.len() as u32).to_le_bytes().to_vec();
+            let args = rt.state.args.iter().map(|arg| arg.as_bytes().to_vec());
+            let res = std::iter::once(argc).chain(args).collect::<Vec<_>>();
+            let ptr = rt.state.input_stream_ptr;
+            rt.state.input_stream.splice(ptr..ptr, res);
+        } else if fd == FD_GETENV {
+            let count = (rt.state.env.len() as u32).to_le_bytes().to_vec();
+            let vars = rt
+                .state
+                .env
+                .iter()
+                .flat_map(|(key, value)| [key.as_bytes().to_vec(), value.as_bytes().to_vec()]);
+            let res = std::iter::once(count).chain(vars).collect::<Vec<_>>();
             let ptr = rt.state.input_stream_ptr;
             rt.state.input_stream.splice(ptr..ptr, res);
+        } else if fd == FD_PANIC {
+            let s = String::from_utf8_lossy(slice);
+            let (location, message) = s.split_once('\n').unwrap_or(("", s.as_ref()));
+            let location = (!location.is_empty()).then(|| location.to_string());
+            rt.guest_panic = Some((message.to_string(), location));
+        } else if fd == FD_CYCLE_COUNT {
+            let count = (rt.state.global_clk as u32).to_le_bytes().to_vec();
+            let ptr = rt.state.input_stream_ptr;
+            rt.state.input_stream.insert(ptr, count);
+        } else if let Some(result) = rt.invoke_hook(fd, slice) {
+            match result {
+                Ok(res) => {
+                    // Add result vectors to the beginning of the stream.
+                    let ptr = rt.state.input_stream_ptr;
+                    rt.state.input_stream.splice(ptr..ptr, res);
+                }
+                Err(err) => rt.hook_failure = Some((fd, err)),
+            }
         } else {
             tracing::warn!("tried to write to unknown file descriptor {fd}");
         }
@@ -137,6 +209,15 @@ fn end_cycle_tracker(rt: &mut Executor, name: &str) -> Option<u64> {
     None
 }
 
+/// Write the output of the program to `writer`, if any.
+fn write_output(writer: Option<&OutputWriter>, bytes: &[u8]) {
+    if let Some(writer) = writer {
+        if let Err(err) = writer.lock().unwrap().write_all(bytes) {
+            tracing::warn!("failed to write the output of the program: {err}");
+        }
+    }
+}
+
 /// Update the io buffer for the given file descriptor with the given string.
 #[allow(clippy::mut_mut)]
 fn update_io_buf(ctx: &mut SyscallContext, fd: u32, s: &str) -> Vec<String> {
@@ -0,0 +1,154 @@
+use serde::Serialize;
+
+use crate::{context::OutputWriter, Opcode};
+
+/// The format of the instruction-level trace written by the executor, see
+/// [`SP1ContextBuilder::trace_export`](crate::SP1ContextBuilder::trace_export).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TraceFormat {
+    /// One JSON object per line and per instruction, with one key per selected column.
+    ///
+    /// Columnar formats such as Parquet can be produced from it with standard tooling.
+    #[default]
+    JsonLines,
+}
+
+/// The columns of the instruction-level trace written by the executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TraceColumns {
+    /// The global clock of the instruction, `clk`.
+    pub clk: bool,
+    /// The program counter of the instruction, `pc`.
+    pub pc: bool,
+    /// The mnemonic of the opcode of the instruction, `opcode`.
+    pub opcode: bool,
+    /// The values of the operands of the instruction, `a`, `b` and `c`.
+    pub operands: bool,
+    /// The values of the 32 registers after the instruction, `registers`.
+    pub registers: bool,
+    /// The memory word accessed by a load or a store, `memory`, with its `addr`, its `value`
+    /// after the access and whether it was a `write`.
+    pub memory: bool,
+}
+
+impl Default for TraceColumns {
+    fn default() -> Self {
+        Self { clk: true, pc: true, opcode: true, operands: true, registers: false, memory: true }
+    }
+}
+
+/// The options of the instruction-level trace written by the executor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceExportOpts {
+    /// The format of the trace.
+    pub format: TraceFormat,
+    /// The columns of the trace.
+    pub columns: TraceColumns,
+    /// Only write one every `sample_interval` instructions, counted by the global clock. Every
+    /// instruction is written if it is `1`.
+    pub sample_interval: u64,
+}
+
+impl Default for TraceExportOpts {
+    fn default() -> Self {
+        Self {
+            format: TraceFormat::default(),
+            columns: TraceColumns::default(),
+            sample_interval: 1,
+        }
+    }
+}
+
+/// The memory access of an instruction in the trace.
+#[derive(Serialize)]
+pub(crate) struct TraceMemoryAccess {
+    pub(crate) addr: u32,
+    pub(crate) value: u32,
+    pub(crate) write: bool,
+}
+
+/// An instruction in the trace, with every column.
+pub(crate) struct TraceRow {
+    pub(crate) clk: u64,
+    pub(crate) pc: u32,
+    pub(crate) opcode: Opcode,
+    pub(crate) operands: [u32; 3],
+    pub(crate) registers: [u32; 32],
+    pub(crate) memory: Option<TraceMemoryAccess>,
+}
+
+/// An instruction in the trace, with the selected columns.
+#[derive(Serialize)]
+struct SelectedTraceRow<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    clk: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pc: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    opcode: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    a: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    b: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    c: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    registers: Option<&'a [u32; 32]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<&'a TraceMemoryAccess>,
+}
+
+/// Writes the instruction-level trace of an execution.
+pub(crate) struct TraceExporter<'a> {
+    writer: OutputWriter<'a>,
+    opts: TraceExportOpts,
+    /// Whether writing the trace failed, after which it isn't written anymore.
+    failed: bool,
+}
+
+impl<'a> TraceExporter<'a> {
+    pub(crate) fn new(writer: OutputWriter<'a>, opts: TraceExportOpts) -> Self {
+        Self { writer, opts, failed: false }
+    }
+
+    /// Whether the instruction at the global clock `clk` is written.
+    pub(crate) fn samples(&self, clk: u64) -> bool {
+        !self.failed && clk % self.opts.sample_interval.max(1) == 0
+    }
+
+    /// Writes `row`, with the selected columns.
+    pub(crate) fn export(&mut self, row: &TraceRow) {
+        let columns = self.opts.columns;
+        let selected = SelectedTraceRow {
+            clk: columns.clk.then_some(row.clk),
+            pc: columns.pc.then_some(row.pc),
+            opcode: columns.opcode.then(|| row.opcode.mnemonic()),
+            a: columns.operands.then_some(row.operands[0]),
+            b: columns.operands.then_some(row.operands[1]),
+            c: columns.operands.then_some(row.operands[2]),
+            registers: columns.registers.then_some(&row.registers),
+            memory: row.memory.as_ref().filter(|_| columns.memory),
+        };
+        let result = match self.opts.format {
+            TraceFormat::JsonLines => {
+                let mut writer = self.writer.lock().unwrap();
+                serde_json::to_writer(&mut *writer, &selected)
+                    .map_err(std::io::Error::from)
+                    .and_then(|()| writer.write_all(b"\n"))
+            }
+        };
+        if let Err(err) = result {
+            tracing::warn!("failed to write the execution trace, no longer writing it: {err}");
+            self.failed = true;
+        }
+    }
+
+    /// Flushes the writer of the trace.
+    pub(crate) fn flush(&mut self) {
+        if let Err(err) = self.writer.lock().unwrap().flush() {
+            tracing::warn!("failed to flush the execution trace: {err}");
+        }
+    }
+}
@@ -0,0 +1,505 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{IntoParallelRefIterator, ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{AluEvent, ByteLookupEvent, ByteRecord},
+    ExecutionRecord, Opcode, Program,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::{
+    air::{MachineAir, SP1AirBuilder},
+    Word,
+};
+
+use crate::{air::WordAirBuilder, utils::pad_to_power_of_two};
+
+/// The number of main trace columns for `BitManipChip`.
+pub const NUM_BITMANIP_COLS: usize = size_of::<BitManipCols<u8>>();
+
+/// The opcodes of the Zba, Zbb and Zbs extensions, in the order of their selectors.
+pub const BITMANIP_OPCODES: [Opcode; 24] = [
+    Opcode::ANDN,
+    Opcode::ORN,
+    Opcode::XNOR,
+    Opcode::CLZ,
+    Opcode::CTZ,
+    Opcode::CPOP,
+    Opcode::MAX,
+    Opcode::MAXU,
+    Opcode::MIN,
+    Opcode::MINU,
+    Opcode::SEXTB,
+    Opcode::SEXTH,
+    Opcode::ZEXTH,
+    Opcode::ROL,
+    Opcode::ROR,
+    Opcode::ORCB,
+    Opcode::REV8,
+    Opcode::SH1ADD,
+    Opcode::SH2ADD,
+    Opcode::SH3ADD,
+    Opcode::BCLR,
+    Opcode::BEXT,
+    Opcode::BINV,
+    Opcode::BSET,
+];
+
+/// A chip that implements the bit manipulation instructions of the Zba, Zbb and Zbs extensions.
+///
+/// The operands and the result are decomposed into bits, and most instructions are constrained
+/// bit by bit. The shift amount or bit index, the low 5 bits of `c`, is one-hot encoded.
+#[derive(Default)]
+pub struct BitManipChip;
+
+/// The column layout for the chip.
+#[derive(AlignedBorrow, Default, Clone, Copy)]
+#[repr(C)]
+pub struct BitManipCols<T> {
+    /// The shard number, used for byte lookup table.
+    pub shard: T,
+
+    /// The channel number, used for byte lookup table.
+    pub channel: T,
+
+    /// The nonce of the operation.
+    pub nonce: T,
+
+    /// The output operand.
+    pub a: Word<T>,
+
+    /// The first input operand.
+    pub b: Word<T>,
+
+    /// The second input operand.
+    pub c: Word<T>,
+
+    /// The bits of `a`, least significant first.
+    pub a_bits: [T; 32],
+
+    /// The bits of `b`, least significant first.
+    pub b_bits: [T; 32],
+
+    /// The bits of `c`, least significant first.
+    pub c_bits: [T; 32],
+
+    /// The one-hot encoding of the low 5 bits of `c`.
+    pub index: [T; 32],
+
+    /// For CLZ, whether the bits of `b` from the most significant one down to each bit are all
+    /// zero. For CTZ, whether the bits from the least significant one up to each bit are.
+    pub zeros: [T; 32],
+
+    /// For ORC.B, whether each byte of `b` is non-zero.
+    pub byte_nonzero: [T; 4],
+
+    /// For ORC.B, the inverse of each non-zero byte of `b`.
+    pub byte_inverse: [T; 4],
+
+    /// For SH1ADD, SH2ADD and SH3ADD, the carries of the byte-wise addition.
+    pub carry: [T; 4],
+
+    /// For MIN, MAX, MINU and MAXU, the bytes of `b - c`.
+    pub diff: Word<T>,
+
+    /// For MIN, MAX, MINU and MAXU, the borrows of the byte-wise subtraction `b - c`.
+    pub borrow: [T; 4],
+
+    /// For MIN and MAX, whether the signs of `b` and `c` differ.
+    pub sign_differs: T,
+
+    /// For MIN, MAX, MINU and MAXU, whether `b < c`.
+    pub b_lt_c: T,
+
+    /// Whether the opcode is the one of [`BITMANIP_OPCODES`] at the same index.
+    pub selectors: [T; 24],
+}
+
+/// The index of the selector of `opcode`.
+fn selector(opcode: Opcode) -> usize {
+    BITMANIP_OPCODES
+        .iter()
+        .position(|&other| other == opcode)
+        .unwrap_or_else(|| panic!("not a bit manipulation opcode: {opcode}"))
+}
+
+impl<F: PrimeField> MachineAir<F> for BitManipChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "BitManip".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let rows = input
+            .bitmanip_events
+            .par_iter()
+            .map(|event| {
+                let mut row = [F::zero(); NUM_BITMANIP_COLS];
+                let cols: &mut BitManipCols<F> = row.as_mut_slice().borrow_mut();
+                let mut blu = Vec::new();
+                self.event_to_row(event, cols, &mut blu);
+                row
+            })
+            .collect::<Vec<_>>();
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), NUM_BITMANIP_COLS);
+
+        // Pad the trace to a power of two.
+        pad_to_power_of_two::<NUM_BITMANIP_COLS, F>(&mut trace.values);
+
+        for i in 0..trace.height() {
+            let cols: &mut BitManipCols<F> =
+                trace.values[i * NUM_BITMANIP_COLS..(i + 1) * NUM_BITMANIP_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.bitmanip_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .bitmanip_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    let mut row = [F::zero(); NUM_BITMANIP_COLS];
+                    let cols: &mut BitManipCols<F> = row.as_mut_slice().borrow_mut();
+                    self.event_to_row(event, cols, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.bitmanip_events.is_empty()
+    }
+}
+
+impl BitManipChip {
+    /// Create a row from an event.
+    fn event_to_row<F: PrimeField>(
+        &self,
+        event: &AluEvent,
+        cols: &mut BitManipCols<F>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let (a, b, c) = (event.a, event.b, event.c);
+        let bit = |value: u32, i: usize| (value >> i) & 1 == 1;
+
+        cols.shard = F::from_canonical_u32(event.shard);
+        cols.channel = F::from_canonical_u8(event.channel);
+        cols.a = Word::from(a);
+        cols.b = Word::from(b);
+        cols.c = Word::from(c);
+        for i in 0..32 {
+            cols.a_bits[i] = F::from_bool(bit(a, i));
+            cols.b_bits[i] = F::from_bool(bit(b, i));
+            cols.c_bits[i] = F::from_bool(bit(c, i));
+        }
+        cols.index[(c & 31) as usize] = F::one();
+        cols.selectors[selector(event.opcode)] = F::one();
+
+        match event.opcode {
+            Opcode::CLZ => {
+                let mut zeros = true;
+                for i in (0..32).rev() {
+                    zeros &= !bit(b, i);
+                    cols.zeros[i] = F::from_bool(zeros);
+                }
+            }
+            Opcode::CTZ => {
+                let mut zeros = true;
+                for i in 0..32 {
+                    zeros &= !bit(b, i);
+                    cols.zeros[i] = F::from_bool(zeros);
+                }
+            }
+            Opcode::ORCB => {
+                for (j, byte) in b.to_le_bytes().into_iter().enumerate() {
+                    cols.byte_nonzero[j] = F::from_bool(byte != 0);
+                    cols.byte_inverse[j] =
+                        F::from_canonical_u8(byte).try_inverse().unwrap_or(F::zero());
+                }
+            }
+            Opcode::SH1ADD | Opcode::SH2ADD | Opcode::SH3ADD => {
+                let shift = match event.opcode {
+                    Opcode::SH1ADD => 1,
+                    Opcode::SH2ADD => 2,
+                    _ => 3,
+                };
+                let mut carry = 0;
+                for (j, (shifted, c_byte)) in
+                    (b << shift).to_le_bytes().into_iter().zip(c.to_le_bytes()).enumerate()
+                {
+                    carry = (u32::from(shifted) + u32::from(c_byte) + carry) >> 8;
+                    cols.carry[j] = F::from_canonical_u32(carry);
+                }
+            }
+            Opcode::MAX | Opcode::MAXU | Opcode::MIN | Opcode::MINU => {
+                let diff = b.wrapping_sub(c);
+                let mut borrow = 0;
+                for (j, (b_byte, c_byte)) in
+                    b.to_le_bytes().into_iter().zip(c.to_le_bytes()).enumerate()
+                {
+                    borrow = u32::from(u32::from(b_byte) < u32::from(c_byte) + borrow);
+                    cols.borrow[j] = F::from_canonical_u32(borrow);
+                }
+                cols.diff = Word::from(diff);
+                cols.sign_differs = F::from_bool(bit(b ^ c, 31));
+                let b_lt_c = match event.opcode {
+                    Opcode::MAX | Opcode::MIN => (b as i32) < (c as i32),
+                    _ => b < c,
+                };
+                cols.b_lt_c = F::from_bool(b_lt_c);
+                blu.add_u8_range_checks(event.shard, event.channel, &diff.to_le_bytes());
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<F> BaseAir<F> for BitManipChip {
+    fn width(&self) -> usize {
+        NUM_BITMANIP_COLS
+    }
+}
+
+impl<AB> Air<AB> for BitManipChip
+where
+    AB: SP1AirBuilder,
+{
+    #[allow(clippy::too_many_lines)]
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &BitManipCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &BitManipCols<AB::Var> = (*next).borrow();
+
+        let one = AB::Expr::one();
+        let pow2 = |i: usize| AB::F::from_canonical_u32(1 << i);
+        let is = |opcode: Opcode| local.selectors[selector(opcode)];
+        let (a, b, c) = (&local.a_bits, &local.b_bits, &local.c_bits);
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Constrain the selectors and get the cpu opcode of the row.
+        let mut is_real = AB::Expr::zero();
+        let mut opcode = AB::Expr::zero();
+        for (is_op, op) in local.selectors.into_iter().zip(BITMANIP_OPCODES) {
+            builder.assert_bool(is_op);
+            is_real = is_real + is_op;
+            opcode += is_op * op.as_field::<AB::F>();
+        }
+        builder.assert_bool(is_real.clone());
+
+        // Decompose the operands and the result into bits.
+        for (word, bits) in [(&local.a, a), (&local.b, b), (&local.c, c)] {
+            for (j, byte) in word.0.iter().enumerate() {
+                let value = (0..8).fold(AB::Expr::zero(), |acc, k| acc + bits[8 * j + k] * pow2(k));
+                builder.assert_eq(*byte, value);
+            }
+            for bit in bits {
+                builder.assert_bool(*bit);
+            }
+        }
+
+        // One-hot encode the low 5 bits of `c`.
+        let mut index_sum = AB::Expr::zero();
+        let mut index_value = AB::Expr::zero();
+        for (k, index) in local.index.into_iter().enumerate() {
+            builder.assert_bool(index);
+            index_sum = index_sum + index;
+            index_value += index * AB::F::from_canonical_usize(k);
+        }
+        let shift = (0..5).fold(AB::Expr::zero(), |acc, i| acc + c[i] * pow2(i));
+        builder.when(is_real.clone()).assert_one(index_sum);
+        builder.when(is_real.clone()).assert_eq(index_value, shift);
+        let index = &local.index;
+
+        // The instructions constrained bit by bit.
+        let mut bitwise = |opcode: Opcode, bit: &dyn Fn(usize) -> AB::Expr| {
+            for i in 0..32 {
+                builder.when(is(opcode)).assert_eq(a[i], bit(i));
+            }
+        };
+        bitwise(Opcode::ANDN, &|i| b[i] * (one.clone() - c[i]));
+        bitwise(Opcode::ORN, &|i| {
+            let not_c = one.clone() - c[i];
+            not_c.clone() + b[i] - b[i] * not_c
+        });
+        bitwise(Opcode::XNOR, &|i| one.clone() - b[i] - c[i] + b[i] * c[i] * AB::F::two());
+        bitwise(Opcode::SEXTB, &|i| b[i.min(7)].into());
+        bitwise(Opcode::SEXTH, &|i| b[i.min(15)].into());
+        bitwise(Opcode::ZEXTH, &|i| if i < 16 { b[i].into() } else { AB::Expr::zero() });
+        bitwise(Opcode::REV8, &|i| b[8 * (3 - i / 8) + i % 8].into());
+        bitwise(Opcode::ORCB, &|i| local.byte_nonzero[i / 8].into());
+        bitwise(Opcode::ROL, &|i| {
+            (0..32).fold(AB::Expr::zero(), |acc, k| acc + index[k] * b[(i + 32 - k) % 32])
+        });
+        bitwise(Opcode::ROR, &|i| {
+            (0..32).fold(AB::Expr::zero(), |acc, k| acc + index[k] * b[(i + k) % 32])
+        });
+        bitwise(Opcode::BCLR, &|i| b[i] * (one.clone() - index[i]));
+        bitwise(Opcode::BSET, &|i| b[i] + index[i] - b[i] * index[i]);
+        bitwise(Opcode::BINV, &|i| b[i] + index[i] - b[i] * index[i] * AB::F::two());
+        bitwise(Opcode::BEXT, &|i| {
+            if i == 0 {
+                (0..32).fold(AB::Expr::zero(), |acc, k| acc + index[k] * b[k])
+            } else {
+                AB::Expr::zero()
+            }
+        });
+
+        // ORC.B: whether each byte of `b` is non-zero.
+        for j in 0..4 {
+            builder.assert_bool(local.byte_nonzero[j]);
+            let mut builder = builder.when(is(Opcode::ORCB));
+            builder.assert_eq(local.byte_nonzero[j], local.b[j] * local.byte_inverse[j]);
+            builder.assert_zero(local.b[j] * (one.clone() - local.byte_nonzero[j]));
+        }
+
+        // CLZ, CTZ and CPOP: the result is a count of at most 32, in the low 6 bits.
+        let count = (0..6).fold(AB::Expr::zero(), |acc, i| acc + a[i] * pow2(i));
+        let zeros = &local.zeros;
+        let zeros_count = zeros.iter().fold(AB::Expr::zero(), |acc, zero| acc + *zero);
+        for opcode in [Opcode::CLZ, Opcode::CTZ, Opcode::CPOP] {
+            for bit in &a[6..] {
+                builder.when(is(opcode)).assert_zero(*bit);
+            }
+        }
+        builder.when(is(Opcode::CLZ)).assert_eq(zeros[31], one.clone() - b[31]);
+        builder.when(is(Opcode::CTZ)).assert_eq(zeros[0], one.clone() - b[0]);
+        for i in 0..31 {
+            builder.when(is(Opcode::CLZ)).assert_eq(zeros[i], zeros[i + 1] * (one.clone() - b[i]));
+            builder
+                .when(is(Opcode::CTZ))
+                .assert_eq(zeros[i + 1], zeros[i] * (one.clone() - b[i + 1]));
+        }
+        builder.when(is(Opcode::CLZ) + is(Opcode::CTZ)).assert_eq(count.clone(), zeros_count);
+        let popcount = b.iter().fold(AB::Expr::zero(), |acc, bit| acc + *bit);
+        builder.when(is(Opcode::CPOP)).assert_eq(count, popcount);
+
+        // SH1ADD, SH2ADD and SH3ADD: add `c` to `b` shifted left, byte by byte.
+        for carry in local.carry {
+            builder.assert_bool(carry);
+        }
+        for (opcode, shift) in [(Opcode::SH1ADD, 1), (Opcode::SH2ADD, 2), (Opcode::SH3ADD, 3)] {
+            for j in 0..4 {
+                let shifted = (0..8)
+                    .filter(|k| 8 * j + k >= shift)
+                    .fold(AB::Expr::zero(), |acc, k| acc + b[8 * j + k - shift] * pow2(k));
+                let carry_in = if j == 0 { AB::Expr::zero() } else { local.carry[j - 1].into() };
+                builder.when(is(opcode)).assert_eq(
+                    shifted + local.c[j] + carry_in,
+                    local.a[j] + local.carry[j] * AB::F::from_canonical_u32(256),
+                );
+            }
+        }
+
+        // MIN, MAX, MINU and MAXU: compare `b` and `c` through the borrow of `b - c`.
+        let is_signed = is(Opcode::MIN) + is(Opcode::MAX);
+        let is_unsigned = is(Opcode::MINU) + is(Opcode::MAXU);
+        let is_min = is(Opcode::MIN) + is(Opcode::MINU);
+        let is_max = is(Opcode::MAX) + is(Opcode::MAXU);
+        let is_comparison = is_signed.clone() + is_unsigned.clone();
+        for borrow in local.borrow {
+            builder.assert_bool(borrow);
+        }
+        for j in 0..4 {
+            let borrow_in = if j == 0 { AB::Expr::zero() } else { local.borrow[j - 1].into() };
+            builder.when(is_comparison.clone()).assert_eq(
+                local.b[j] - local.c[j] - borrow_in,
+                local.diff[j] - local.borrow[j] * AB::F::from_canonical_u32(256),
+            );
+        }
+        builder.slice_range_check_u8(&local.diff.0, local.shard, local.channel, is_comparison);
+        let lt_unsigned = local.borrow[3];
+        let (sign_b, sign_c) = (b[31], c[31]);
+        builder
+            .when(is_signed.clone())
+            .assert_eq(local.sign_differs, sign_b + sign_c - sign_b * sign_c * AB::F::two());
+        builder
+            .when(is_signed)
+            .assert_eq(local.b_lt_c, lt_unsigned + local.sign_differs * (sign_b - lt_unsigned));
+        builder.when(is_unsigned).assert_eq(local.b_lt_c, lt_unsigned);
+        for j in 0..4 {
+            builder
+                .when(is_min.clone())
+                .assert_eq(local.a[j], local.c[j] + local.b_lt_c * (local.b[j] - local.c[j]));
+            builder
+                .when(is_max.clone())
+                .assert_eq(local.a[j], local.b[j] + local.b_lt_c * (local.c[j] - local.b[j]));
+        }
+
+        // Receive the arguments.
+        builder.receive_alu(
+            opcode,
+            local.a,
+            local.b,
+            local.c,
+            local.shard,
+            local.channel,
+            local.nonce,
+            is_real,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use p3_baby_bear::BabyBear;
+    use p3_matrix::dense::RowMajorMatrix;
+    use sp1_core_executor::{bitmanip, events::AluEvent, ExecutionRecord};
+    use sp1_stark::{air::MachineAir, baby_bear_poseidon2::BabyBearPoseidon2, StarkGenericConfig};
+
+    use crate::utils::{uni_stark_prove, uni_stark_verify};
+
+    use super::{BitManipChip, BITMANIP_OPCODES};
+
+    #[test]
+    fn prove_babybear() {
+        let config = BabyBearPoseidon2::new();
+        let mut challenger = config.challenger();
+
+        let operands = [(0, 0), (1, 33), (0x12345678, 0x80000001), (u32::MAX, 7), (0x00ff0080, 31)];
+        let mut shard = ExecutionRecord::default();
+        shard.bitmanip_events = BITMANIP_OPCODES
+            .into_iter()
+            .flat_map(|opcode| {
+                operands.map(|(b, c)| AluEvent::new(0, 0, 0, opcode, bitmanip(opcode, b, c), b, c))
+            })
+            .collect();
+        let chip = BitManipChip::default();
+        let trace: RowMajorMatrix<BabyBear> =
+            chip.generate_trace(&shard, &mut ExecutionRecord::default());
+        let proof = uni_stark_prove::<BabyBearPoseidon2, _>(&config, &chip, &mut challenger, trace);
+
+        let mut challenger = config.challenger();
+        uni_stark_verify(&config, &chip, &mut challenger, &proof).unwrap();
+    }
+}
@@ -87,6 +87,7 @@ impl<AB: SP1AirBuilder + PairBuilder> Air<AB> for ByteChip<AB::F> {
                         channel_f,
                         mult,
                     ),
+                    ByteOpcode::AesSbox => unreachable!("the AES S-box has its own table"),
                 }
             }
         }
@@ -106,6 +106,7 @@ impl<F: Field> ByteChip<F> {
                             col.value_u16 = F::from_canonical_u32(v);
                             ByteLookupEvent::new(shard, channel, *opcode, v as u16, 0, 0, 0)
                         }
+                        ByteOpcode::AesSbox => unreachable!("the AES S-box has its own table"),
                     };
                 }
             }
@@ -45,6 +45,10 @@ impl<F: Field> MachineAir<F> for ByteChip<F> {
 
         let shard = input.public_values.execution_shard;
         for (lookup, mult) in input.byte_lookups.get(&shard).unwrap_or(&HashMap::new()).iter() {
+            // The S-box lookups are received by the `AesSbox` table.
+            if lookup.opcode == ByteOpcode::AesSbox {
+                continue;
+            }
             let row = if lookup.opcode != ByteOpcode::U16Range {
                 (((lookup.b as u16) << 8) + lookup.c as u16) as usize
             } else {
@@ -2,7 +2,9 @@ use crate::utils::Buffer;
 use k256::sha2::{Digest, Sha256};
 use num_bigint::BigUint;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 use sp1_stark::{baby_bear_poseidon2::BabyBearPoseidon2, ShardProof, StarkVerifyingKey};
+use thiserror::Error;
 
 /// Standard input for the prover.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -20,6 +22,157 @@ pub struct SP1PublicValues {
     buffer: Buffer,
 }
 
+/// An error decoding [SP1PublicValues] into a typed value.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PublicValuesError {
+    #[error("failed to decode the public values: {0}")]
+    Decode(String),
+    #[error("{0} bytes of the public values are left over after decoding")]
+    TrailingBytes(usize),
+}
+
+/// The type of an input read by a program, used to encode JSON values with
+/// [SP1Stdin::from_json] exactly as [SP1Stdin::write] encodes the matching Rust value.
+///
+/// Schemas can themselves be loaded from JSON, e.g. `{"struct": [["n", "u32"], ["key",
+/// "bytes"]]}` or `{"array": ["u8", 32]}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputSchema {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    String,
+    /// A `Vec<u8>`, given as a hex string with an optional `0x` prefix.
+    Bytes,
+    /// A `Vec<T>`, given as a JSON array.
+    Vec(Box<InputSchema>),
+    /// A `[T; N]`, given as a JSON array of exactly `N` elements.
+    Array(Box<InputSchema>, usize),
+    /// An `Option<T>`, given as `null` or the value.
+    Option(Box<InputSchema>),
+    /// A tuple, given as a JSON array.
+    Tuple(Vec<InputSchema>),
+    /// A struct with the given fields in declaration order, given as a JSON object.
+    Struct(Vec<(String, InputSchema)>),
+}
+
+/// An error building a [SP1Stdin] from JSON or hex.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum StdinError {
+    #[error("expected {expected} inputs, found {found}")]
+    InputCount { expected: usize, found: usize },
+    #[error("expected {expected} at {path}")]
+    Mismatch { path: String, expected: String },
+    #[error("input {index} is not valid hex: {reason}")]
+    InvalidHex { index: usize, reason: String },
+}
+
+impl InputSchema {
+    /// Appends the bincode encoding of `value` to `out`.
+    fn encode(&self, value: &Value, path: &str, out: &mut Vec<u8>) -> Result<(), StdinError> {
+        let mismatch = |expected: &str| StdinError::Mismatch {
+            path: path.to_string(),
+            expected: expected.to_string(),
+        };
+        macro_rules! int {
+            ($ty:ty) => {{
+                let n: Option<$ty> = match value {
+                    Value::Number(n) => n
+                        .as_u64()
+                        .and_then(|n| <$ty>::try_from(n).ok())
+                        .or_else(|| n.as_i64().and_then(|n| <$ty>::try_from(n).ok())),
+                    // Integers that don't fit in a JSON number are given as decimal strings.
+                    Value::String(s) => s.parse().ok(),
+                    _ => None,
+                };
+                let n = n.ok_or_else(|| mismatch(stringify!($ty)))?;
+                bincode::serialize_into(out, &n).expect("serialization failed");
+            }};
+        }
+        match self {
+            Self::Bool => {
+                let b = value.as_bool().ok_or_else(|| mismatch("a bool"))?;
+                bincode::serialize_into(out, &b).expect("serialization failed");
+            }
+            Self::U8 => int!(u8),
+            Self::U16 => int!(u16),
+            Self::U32 => int!(u32),
+            Self::U64 => int!(u64),
+            Self::U128 => int!(u128),
+            Self::I8 => int!(i8),
+            Self::I16 => int!(i16),
+            Self::I32 => int!(i32),
+            Self::I64 => int!(i64),
+            Self::I128 => int!(i128),
+            Self::String => {
+                let s = value.as_str().ok_or_else(|| mismatch("a string"))?;
+                bincode::serialize_into(out, s).expect("serialization failed");
+            }
+            Self::Bytes => {
+                let bytes = value
+                    .as_str()
+                    .and_then(|s| hex::decode(s.trim_start_matches("0x")).ok())
+                    .ok_or_else(|| mismatch("a hex string"))?;
+                bincode::serialize_into(out, &bytes).expect("serialization failed");
+            }
+            Self::Vec(elem) => {
+                let values = value.as_array().ok_or_else(|| mismatch("an array"))?;
+                bincode::serialize_into(&mut *out, &(values.len() as u64))
+                    .expect("serialization failed");
+                for (i, value) in values.iter().enumerate() {
+                    elem.encode(value, &format!("{path}[{i}]"), out)?;
+                }
+            }
+            Self::Array(elem, len) => {
+                let values = value
+                    .as_array()
+                    .filter(|values| values.len() == *len)
+                    .ok_or_else(|| mismatch(&format!("an array of {len} elements")))?;
+                for (i, value) in values.iter().enumerate() {
+                    elem.encode(value, &format!("{path}[{i}]"), out)?;
+                }
+            }
+            Self::Option(inner) => match value {
+                Value::Null => out.push(0),
+                value => {
+                    out.push(1);
+                    inner.encode(value, path, out)?;
+                }
+            },
+            Self::Tuple(elems) => {
+                let values = value
+                    .as_array()
+                    .filter(|values| values.len() == elems.len())
+                    .ok_or_else(|| mismatch(&format!("an array of {} elements", elems.len())))?;
+                for (i, (elem, value)) in elems.iter().zip(values).enumerate() {
+                    elem.encode(value, &format!("{path}[{i}]"), out)?;
+                }
+            }
+            Self::Struct(fields) => {
+                let object = value.as_object().ok_or_else(|| mismatch("an object"))?;
+                for (name, field) in fields {
+                    let path = format!("{path}.{name}");
+                    let value = object.get(name).ok_or_else(|| StdinError::Mismatch {
+                        path: path.clone(),
+                        expected: "a field".to_string(),
+                    })?;
+                    field.encode(value, &path, out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl SP1Stdin {
     /// Create a new `SP1Stdin`.
     pub const fn new() -> Self {
@@ -31,6 +184,50 @@ impl SP1Stdin {
         Self { buffer: vec![data.to_vec()], ptr: 0, proofs: Vec::new() }
     }
 
+    /// Create a `SP1Stdin` from a JSON array holding one value per input, each encoded according
+    /// to the matching entry of `schema` as if it had been written with [Self::write].
+    ///
+    /// ```
+    /// use sp1_core_machine::io::{InputSchema, SP1Stdin};
+    ///
+    /// let schema = [InputSchema::U32, InputSchema::Vec(Box::new(InputSchema::String))];
+    /// let value = serde_json::json!([10, ["a", "b"]]);
+    /// let stdin = SP1Stdin::from_json(&schema, &value).unwrap();
+    ///
+    /// let mut expected = SP1Stdin::new();
+    /// expected.write(&10u32);
+    /// expected.write(&vec!["a".to_string(), "b".to_string()]);
+    /// assert_eq!(stdin.buffer, expected.buffer);
+    /// ```
+    pub fn from_json(schema: &[InputSchema], value: &Value) -> Result<Self, StdinError> {
+        let values = value.as_array().ok_or_else(|| StdinError::Mismatch {
+            path: "$".to_string(),
+            expected: "an array of inputs".to_string(),
+        })?;
+        if values.len() != schema.len() {
+            return Err(StdinError::InputCount { expected: schema.len(), found: values.len() });
+        }
+        let mut stdin = Self::new();
+        for (i, (schema, value)) in schema.iter().zip(values).enumerate() {
+            let mut input = Vec::new();
+            schema.encode(value, &format!("$[{i}]"), &mut input)?;
+            stdin.write_vec(input);
+        }
+        Ok(stdin)
+    }
+
+    /// Create a `SP1Stdin` from hex strings, with an optional `0x` prefix, each holding the raw
+    /// bytes of one input as if it had been written with [Self::write_slice].
+    pub fn from_hex_parts(parts: &[&str]) -> Result<Self, StdinError> {
+        let mut stdin = Self::new();
+        for (index, part) in parts.iter().enumerate() {
+            let bytes = hex::decode(part.trim().trim_start_matches("0x"))
+                .map_err(|err| StdinError::InvalidHex { index, reason: err.to_string() })?;
+            stdin.write_vec(bytes);
+        }
+        Ok(stdin)
+    }
+
     /// Read a value from the buffer.
     pub fn read<T: DeserializeOwned>(&mut self) -> T {
         let result: T =
@@ -103,6 +300,22 @@ impl SP1PublicValues {
         self.buffer.read_slice(slice);
     }
 
+    /// Decode all of the public values into `T` at once, from the start of the buffer.
+    ///
+    /// Values committed one after the other with `sp1_zkvm::io::commit` decode as a tuple or a
+    /// struct with the same fields in the same order. Fails if the bytes don't decode into `T` or
+    /// if bytes are left over, so that a mismatch with the committed values is caught instead of
+    /// silently misread.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, PublicValuesError> {
+        let mut bytes = self.buffer.data.as_slice();
+        let value = bincode::deserialize_from(&mut bytes)
+            .map_err(|err| PublicValuesError::Decode(err.to_string()))?;
+        if !bytes.is_empty() {
+            return Err(PublicValuesError::TrailingBytes(bytes.len()));
+        }
+        Ok(value)
+    }
+
     /// Write a value to the buffer.
     pub fn write<T: Serialize>(&mut self, data: &T) {
         self.buffer.write(data);
@@ -195,4 +408,67 @@ mod tests {
 
         assert_eq!(hash, expected_hash_biguint);
     }
+
+    #[test]
+    fn test_stdin_from_json() {
+        let schema: Vec<InputSchema> = serde_json::from_str(
+            r#"["bool", {"option": "u64"}, {"array": ["u8", 2]}, {"struct": [["n", "i32"], ["key", "bytes"]]}, "u128"]"#,
+        )
+        .unwrap();
+        let value = serde_json::json!([
+            true,
+            null,
+            [1, 2],
+            { "n": -3, "key": "0xbeef" },
+            "340282366920938463463374607431768211455"
+        ]);
+        let stdin = SP1Stdin::from_json(&schema, &value).unwrap();
+
+        let mut expected = SP1Stdin::new();
+        expected.write(&true);
+        expected.write(&None::<u64>);
+        expected.write(&[1u8, 2]);
+        expected.write(&(-3i32, vec![0xbeu8, 0xef]));
+        expected.write(&u128::MAX);
+        assert_eq!(stdin.buffer, expected.buffer);
+
+        let value = serde_json::json!([true, 1, [1, 2], { "n": 256 }, "0"]);
+        assert_eq!(
+            SP1Stdin::from_json(&schema, &value).unwrap_err(),
+            StdinError::Mismatch { path: "$[3].key".to_string(), expected: "a field".to_string() }
+        );
+        assert_eq!(
+            SP1Stdin::from_json(&schema[..1], &value).unwrap_err(),
+            StdinError::InputCount { expected: 1, found: 5 }
+        );
+    }
+
+    #[test]
+    fn test_stdin_from_hex_parts() {
+        let stdin = SP1Stdin::from_hex_parts(&["0x0102", "ff", ""]).unwrap();
+        assert_eq!(stdin.buffer, vec![vec![1, 2], vec![0xff], vec![]]);
+        assert!(matches!(
+            SP1Stdin::from_hex_parts(&["0x01", "0xz"]),
+            Err(StdinError::InvalidHex { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_decode_public_values() {
+        let mut public_values = SP1PublicValues::new();
+        public_values.write(&10u32);
+        public_values.write(&"fib".to_string());
+        public_values.write(&[1u64, 2, 3]);
+
+        let decoded = public_values.decode::<(u32, String, [u64; 3])>().unwrap();
+        assert_eq!(decoded, (10, "fib".to_string(), [1, 2, 3]));
+        assert_eq!(
+            public_values.decode::<(u32, String)>(),
+            Err(PublicValuesError::TrailingBytes(24))
+        );
+        assert!(matches!(
+            public_values.decode::<(u32, String, [u64; 4])>(),
+            Err(PublicValuesError::Decode(_))
+        ));
+    }
 }
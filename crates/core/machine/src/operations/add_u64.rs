@@ -0,0 +1,111 @@
+use sp1_core_executor::events::ByteRecord;
+use sp1_primitives::consts::WORD_SIZE;
+use sp1_stark::{air::SP1AirBuilder, Word};
+
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, Field};
+use sp1_derive::AlignedBorrow;
+
+use crate::air::WordAirBuilder;
+
+/// The number of bytes in a double word.
+pub const DWORD_SIZE: usize = 2 * WORD_SIZE;
+
+/// A set of columns needed to compute the add of two double words, each given as its low and high
+/// words.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct AddU64Operation<T> {
+    /// The result of `a + b`.
+    pub value: [Word<T>; 2],
+
+    /// Trace.
+    pub carry: [T; DWORD_SIZE - 1],
+}
+
+impl<F: Field> AddU64Operation<F> {
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        a_u64: u64,
+        b_u64: u64,
+    ) -> u64 {
+        let expected = a_u64.wrapping_add(b_u64);
+        self.value = u64_to_words(expected);
+        let a = a_u64.to_le_bytes();
+        let b = b_u64.to_le_bytes();
+
+        let mut carry = 0u32;
+        for i in 0..DWORD_SIZE - 1 {
+            carry = ((a[i] as u32) + (b[i] as u32) + carry) >> 8;
+            self.carry[i] = F::from_canonical_u32(carry);
+        }
+
+        // Range check
+        {
+            record.add_u8_range_checks(shard, channel, &a);
+            record.add_u8_range_checks(shard, channel, &b);
+            record.add_u8_range_checks(shard, channel, &expected.to_le_bytes());
+        }
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        a: [Word<AB::Var>; 2],
+        b: [Word<AB::Var>; 2],
+        cols: AddU64Operation<AB::Var>,
+        shard: AB::Var,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: AB::Expr,
+    ) {
+        let one = AB::Expr::one();
+        let base = AB::F::from_canonical_u32(256);
+
+        let mut builder_is_real = builder.when(is_real.clone());
+
+        // For each limb, assert that difference between the carried result and the non-carried
+        // result is either zero or the base.
+        for i in 0..DWORD_SIZE {
+            let (w, j) = (i / WORD_SIZE, i % WORD_SIZE);
+            let mut overflow = a[w][j] + b[w][j] - cols.value[w][j];
+            if i > 0 {
+                overflow += cols.carry[i - 1].into();
+            }
+            builder_is_real.assert_zero(overflow.clone() * (overflow.clone() - base));
+
+            if i < DWORD_SIZE - 1 {
+                // If the carry is one, then the overflow must be the base.
+                builder_is_real.assert_zero(cols.carry[i] * (overflow.clone() - base));
+
+                // If the carry is not one, then the overflow must be zero.
+                builder_is_real.assert_zero((cols.carry[i] - one.clone()) * overflow);
+
+                // Assert that the carry is either zero or one.
+                builder_is_real.assert_bool(cols.carry[i]);
+            }
+        }
+        builder_is_real.assert_bool(is_real.clone());
+
+        // Range check each byte.
+        {
+            for (a, b, value) in itertools::izip!(a, b, cols.value) {
+                builder.slice_range_check_u8(&a.0, shard, channel.clone(), is_real.clone());
+                builder.slice_range_check_u8(&b.0, shard, channel.clone(), is_real.clone());
+                builder.slice_range_check_u8(&value.0, shard, channel.clone(), is_real.clone());
+            }
+        }
+    }
+}
+
+/// The low and high words of a double word.
+pub fn u64_to_words<F: Field>(value: u64) -> [Word<F>; 2] {
+    [Word::from(value as u32), Word::from((value >> 32) as u32)]
+}
+
+/// The double word with the given low and high words.
+pub fn u64_from_words<F: Field>(words: &[Word<F>; 2]) -> u64 {
+    words[0].to_u32() as u64 | (words[1].to_u32() as u64) << 32
+}
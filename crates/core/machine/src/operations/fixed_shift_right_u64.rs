@@ -0,0 +1,165 @@
+use p3_field::{AbstractField, Field};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord},
+    ByteOpcode,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_primitives::consts::WORD_SIZE;
+use sp1_stark::{air::SP1AirBuilder, Word};
+
+use super::{u64_from_words, DWORD_SIZE};
+use crate::bytes::utils::shr_carry;
+
+/// A set of columns needed to compute `>>` of a double word with a fixed offset R.
+///
+/// Note that we decompose shifts into a byte shift and a bit shift.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct FixedShiftRightU64Operation<T> {
+    /// The output value.
+    pub value: [Word<T>; 2],
+
+    /// The shift output of `shrcarry` on each byte of a double word.
+    pub shift: [Word<T>; 2],
+
+    /// The carry ouytput of `shrcarry` on each byte of a double word.
+    pub carry: [Word<T>; 2],
+}
+
+impl<F: Field> FixedShiftRightU64Operation<F> {
+    pub const fn nb_bytes_to_shift(rotation: usize) -> usize {
+        rotation / 8
+    }
+
+    pub const fn nb_bits_to_shift(rotation: usize) -> usize {
+        rotation % 8
+    }
+
+    pub const fn carry_multiplier(rotation: usize) -> u32 {
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        1 << (8 - nb_bits_to_shift)
+    }
+
+    pub fn populate(
+        &mut self,
+        record: &mut impl ByteRecord,
+        shard: u32,
+        channel: u8,
+        input: u64,
+        rotation: usize,
+    ) -> u64 {
+        let input_bytes = input.to_le_bytes();
+        let expected = input >> rotation;
+
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // Perform the byte shift.
+        let input_bytes_rotated: [u8; DWORD_SIZE] = core::array::from_fn(|i| {
+            if i + nb_bytes_to_shift < DWORD_SIZE {
+                input_bytes[i + nb_bytes_to_shift]
+            } else {
+                0
+            }
+        });
+
+        // For each byte, calculate the shift and carry. If it's not the first byte, calculate the
+        // new byte value using the current shifted byte and the last carry.
+        let mut first_shift = F::zero();
+        let mut last_carry = F::zero();
+        for i in (0..DWORD_SIZE).rev() {
+            let (w, j) = (i / WORD_SIZE, i % WORD_SIZE);
+            let b = input_bytes_rotated[i];
+            let c = nb_bits_to_shift as u8;
+
+            let (shift, carry) = shr_carry(b, c);
+
+            let byte_event = ByteLookupEvent {
+                shard,
+                channel,
+                opcode: ByteOpcode::ShrCarry,
+                a1: shift as u16,
+                a2: carry,
+                b,
+                c,
+            };
+            record.add_byte_lookup_event(byte_event);
+
+            self.shift[w][j] = F::from_canonical_u8(shift);
+            self.carry[w][j] = F::from_canonical_u8(carry);
+
+            if i == DWORD_SIZE - 1 {
+                first_shift = self.shift[w][j];
+            } else {
+                self.value[w][j] = self.shift[w][j] + last_carry * carry_multiplier;
+            }
+
+            last_carry = self.carry[w][j];
+        }
+
+        // For the first byte, we don't move over the carry as this is a shift, not a rotate.
+        self.value[1][WORD_SIZE - 1] = first_shift;
+
+        // Assert the answer is correct.
+        assert_eq!(u64_from_words(&self.value), expected);
+
+        expected
+    }
+
+    pub fn eval<AB: SP1AirBuilder>(
+        builder: &mut AB,
+        input: [Word<AB::Var>; 2],
+        rotation: usize,
+        cols: FixedShiftRightU64Operation<AB::Var>,
+        shard: AB::Var,
+        channel: impl Into<AB::Expr> + Clone,
+        is_real: AB::Var,
+    ) {
+        // Compute some constants with respect to the rotation needed for the rotation.
+        let nb_bytes_to_shift = Self::nb_bytes_to_shift(rotation);
+        let nb_bits_to_shift = Self::nb_bits_to_shift(rotation);
+        let carry_multiplier = AB::F::from_canonical_u32(Self::carry_multiplier(rotation));
+
+        // Perform the byte shift.
+        let input_bytes_rotated: [AB::Expr; DWORD_SIZE] = core::array::from_fn(|i| {
+            let k = i + nb_bytes_to_shift;
+            if k < DWORD_SIZE {
+                input[k / WORD_SIZE][k % WORD_SIZE].into()
+            } else {
+                AB::Expr::zero()
+            }
+        });
+
+        // For each byte, calculate the shift and carry. If it's not the first byte, calculate the
+        // new byte value using the current shifted byte and the last carry.
+        let mut first_shift = AB::Expr::zero();
+        let mut last_carry = AB::Expr::zero();
+        for i in (0..DWORD_SIZE).rev() {
+            let (w, j) = (i / WORD_SIZE, i % WORD_SIZE);
+            builder.send_byte_pair(
+                AB::F::from_canonical_u32(ByteOpcode::ShrCarry as u32),
+                cols.shift[w][j],
+                cols.carry[w][j],
+                input_bytes_rotated[i].clone(),
+                AB::F::from_canonical_usize(nb_bits_to_shift),
+                shard,
+                channel.clone(),
+                is_real,
+            );
+
+            if i == DWORD_SIZE - 1 {
+                first_shift = cols.shift[w][j].into();
+            } else {
+                builder
+                    .assert_eq(cols.value[w][j], cols.shift[w][j] + last_carry * carry_multiplier);
+            }
+
+            last_carry = cols.carry[w][j].into();
+        }
+
+        // For the first byte, we don't move over the carry as this is a shift, not a rotate.
+        builder.assert_eq(cols.value[1][WORD_SIZE - 1], first_shift);
+    }
+}
@@ -1,5 +1,5 @@
 use p3_field::{AbstractField, Field};
-use sp1_core_executor::{events::ByteRecord, ByteOpcode, ExecutionRecord};
+use sp1_core_executor::{events::ByteRecord, ByteOpcode};
 use sp1_derive::AlignedBorrow;
 use sp1_primitives::consts::WORD_SIZE;
 use sp1_stark::{air::SP1AirBuilder, Word};
@@ -15,7 +15,7 @@ pub struct OrOperation<T> {
 impl<F: Field> OrOperation<F> {
     pub fn populate(
         &mut self,
-        record: &mut ExecutionRecord,
+        record: &mut impl ByteRecord,
         shard: u32,
         channel: u8,
         x: u32,
@@ -207,6 +207,8 @@ mod tests {
                 pc_start: 0,
                 pc_base: 0,
                 memory_image: BTreeMap::new(),
+                debug_info: None,
+                symbols: None,
             }),
             ..Default::default()
         };
@@ -1,9 +1,9 @@
 use p3_baby_bear::BabyBear;
 use sp1_core_executor::{syscalls::SyscallCode, ExecutionReport, Opcode};
 
-use crate::riscv::RiscvAirDiscriminants;
+use crate::{riscv::RiscvAirDiscriminants, syscall::precompiles::aes::sbox::NUM_AES_SBOX_ROWS};
 
-use super::RiscvAir;
+use super::{RiscvAir, INCLUDE_UNRELEASED_CHIPS};
 
 pub trait CostEstimator {
     /// Estimates the trace area of the execution.
@@ -42,6 +42,20 @@ impl CostEstimator for ExecutionReport {
         total_area += (sha_compress_events as u64) * costs[&RiscvAirDiscriminants::Sha256Compress];
         total_chips += 1;
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let sha512_extend_events =
+                *self.syscall_counts.get(&SyscallCode::SHA512_EXTEND).unwrap_or(&0);
+            total_area +=
+                (sha512_extend_events as u64) * costs[&RiscvAirDiscriminants::Sha512Extend];
+            total_chips += 1;
+
+            let sha512_compress_events =
+                *self.syscall_counts.get(&SyscallCode::SHA512_COMPRESS).unwrap_or(&0);
+            total_area +=
+                (sha512_compress_events as u64) * costs[&RiscvAirDiscriminants::Sha512Compress];
+            total_chips += 1;
+        }
+
         let ed_add_events = *self.syscall_counts.get(&SyscallCode::ED_ADD).unwrap_or(&0);
         total_area += (ed_add_events as u64) * costs[&RiscvAirDiscriminants::Ed25519Add];
         total_chips += 1;
@@ -69,6 +83,26 @@ impl CostEstimator for ExecutionReport {
             (secp256k1_double_events as u64) * costs[&RiscvAirDiscriminants::Secp256k1Double];
         total_chips += 1;
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let p256_decompress_events =
+                *self.syscall_counts.get(&SyscallCode::SECP256R1_DECOMPRESS).unwrap_or(&0);
+            total_area +=
+                (p256_decompress_events as u64) * costs[&RiscvAirDiscriminants::P256Decompress];
+            total_chips += 1;
+
+            let secp256r1_add_events =
+                *self.syscall_counts.get(&SyscallCode::SECP256R1_ADD).unwrap_or(&0);
+            total_area +=
+                (secp256r1_add_events as u64) * costs[&RiscvAirDiscriminants::Secp256r1Add];
+            total_chips += 1;
+
+            let secp256r1_double_events =
+                *self.syscall_counts.get(&SyscallCode::SECP256R1_DOUBLE).unwrap_or(&0);
+            total_area +=
+                (secp256r1_double_events as u64) * costs[&RiscvAirDiscriminants::Secp256r1Double];
+            total_chips += 1;
+        }
+
         let keccak256_permute_events =
             *self.syscall_counts.get(&SyscallCode::KECCAK_PERMUTE).unwrap_or(&0);
         total_area += (keccak256_permute_events as u64) * costs[&RiscvAirDiscriminants::KeccakP];
@@ -98,6 +132,48 @@ impl CostEstimator for ExecutionReport {
         total_area += (uint256_mul_events as u64) * costs[&RiscvAirDiscriminants::Uint256Mul];
         total_chips += 1;
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let poseidon2_permute_events =
+                *self.syscall_counts.get(&SyscallCode::POSEIDON2_PERMUTE).unwrap_or(&0);
+            total_area +=
+                (poseidon2_permute_events as u64) * costs[&RiscvAirDiscriminants::Poseidon2Permute];
+            total_chips += 1;
+
+            let blake3_compress_events =
+                *self.syscall_counts.get(&SyscallCode::BLAKE3_COMPRESS).unwrap_or(&0);
+            total_area +=
+                (blake3_compress_events as u64) * costs[&RiscvAirDiscriminants::Blake3Compress];
+            total_chips += 1;
+
+            let aes_encrypt_events =
+                *self.syscall_counts.get(&SyscallCode::AES128_ENCRYPT).unwrap_or(&0)
+                    + *self.syscall_counts.get(&SyscallCode::AES256_ENCRYPT).unwrap_or(&0);
+            total_area += (aes_encrypt_events as u64) * costs[&RiscvAirDiscriminants::AesEncrypt];
+            total_chips += 1;
+
+            // The S-box table has a row for each byte in every shard.
+            total_area += (NUM_AES_SBOX_ROWS as u64) * costs[&RiscvAirDiscriminants::AesSbox];
+            total_chips += 1;
+
+            let ripemd160_compress_events =
+                *self.syscall_counts.get(&SyscallCode::RIPEMD160_COMPRESS).unwrap_or(&0);
+            total_area += (ripemd160_compress_events as u64)
+                * costs[&RiscvAirDiscriminants::Ripemd160Compress];
+            total_chips += 1;
+
+            let uint320_mod_events =
+                *self.syscall_counts.get(&SyscallCode::UINT320_ADD).unwrap_or(&0)
+                    + *self.syscall_counts.get(&SyscallCode::UINT320_MUL).unwrap_or(&0);
+            total_area += (uint320_mod_events as u64) * costs[&RiscvAirDiscriminants::Uint320Mod];
+            total_chips += 1;
+
+            let uint384_mod_events =
+                *self.syscall_counts.get(&SyscallCode::UINT384_ADD).unwrap_or(&0)
+                    + *self.syscall_counts.get(&SyscallCode::UINT384_MUL).unwrap_or(&0);
+            total_area += (uint384_mod_events as u64) * costs[&RiscvAirDiscriminants::Uint384Mod];
+            total_chips += 1;
+        }
+
         let bls12381_fp_events =
             *self.syscall_counts.get(&SyscallCode::BLS12381_FP_ADD).unwrap_or(&0)
                 + *self.syscall_counts.get(&SyscallCode::BLS12381_FP_SUB).unwrap_or(&0)
@@ -176,6 +252,17 @@ impl CostEstimator for ExecutionReport {
         total_area += (shift_left_events as u64) * costs[&RiscvAirDiscriminants::ShiftLeft];
         total_chips += 1;
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let bitmanip_events = self
+                .opcode_counts
+                .iter()
+                .filter(|(opcode, _)| opcode.is_bitmanip())
+                .map(|(_, count)| *count)
+                .sum::<u64>();
+            total_area += bitmanip_events * costs[&RiscvAirDiscriminants::BitManip];
+            total_chips += 1;
+        }
+
         let lt_events = *self.opcode_counts.get(&Opcode::SLT).unwrap_or(&0)
             + *self.opcode_counts.get(&Opcode::SLTU).unwrap_or(&0);
         total_area += (lt_events as u64) * costs[&RiscvAirDiscriminants::Lt];
@@ -18,16 +18,25 @@ use tracing::instrument;
 /// A module for importing all the different RISC-V chips.
 pub(crate) mod riscv_chips {
     pub use crate::{
-        alu::{AddSubChip, BitwiseChip, DivRemChip, LtChip, MulChip, ShiftLeft, ShiftRightChip},
+        alu::{
+            AddSubChip, BitManipChip, BitwiseChip, DivRemChip, LtChip, MulChip, ShiftLeft,
+            ShiftRightChip,
+        },
         bytes::ByteChip,
         cpu::CpuChip,
         memory::MemoryChip,
         program::ProgramChip,
         syscall::precompiles::{
+            aes::{AesEncryptChip, AesSboxChip},
+            blake3::Blake3CompressChip,
             edwards::{EdAddAssignChip, EdDecompressChip},
             keccak256::KeccakPermuteChip,
+            poseidon2::Poseidon2PermuteChip,
+            ripemd160::Ripemd160CompressChip,
             sha256::{ShaCompressChip, ShaExtendChip},
+            sha512::{Sha512CompressChip, Sha512ExtendChip},
             uint256::Uint256MulChip,
+            uint_mod::UintModChip,
             weierstrass::{
                 WeierstrassAddAssignChip, WeierstrassDecompressChip, WeierstrassDoubleAssignChip,
             },
@@ -35,13 +44,21 @@ pub(crate) mod riscv_chips {
     };
     pub use sp1_curves::{
         edwards::{ed25519::Ed25519Parameters, EdwardsCurve},
+        uint::{U320Field, U384Field},
         weierstrass::{
             bls12_381::Bls12381Parameters, bn254::Bn254Parameters, secp256k1::Secp256k1Parameters,
-            SwCurve,
+            secp256r1::Secp256r1Parameters, SwCurve,
         },
     };
 }
 
+/// Whether the chips added since the current [crate::SP1_CIRCUIT_VERSION] are part of the machine.
+///
+/// The circuit artifacts of the current version are built for the machine without them, so until
+/// the next circuit release is cut they are only included when testing this crate. Programs using
+/// their instructions or precompiles can be executed, but not proven.
+pub const INCLUDE_UNRELEASED_CHIPS: bool = cfg!(test);
+
 /// An AIR for encoding RISC-V execution.
 ///
 /// This enum contains all the different AIRs that are used in the Sp1 RISC-V IOP. Each variant is
@@ -68,6 +85,8 @@ pub enum RiscvAir<F: PrimeField32> {
     ShiftLeft(ShiftLeft),
     /// An AIR for RISC-V SRL and SRA instruction.
     ShiftRight(ShiftRightChip),
+    /// An AIR for the RISC-V Zba, Zbb and Zbs bit manipulation instructions.
+    BitManip(BitManipChip),
     /// A lookup table for byte operations.
     ByteLookup(ByteChip<F>),
     /// A table for initializing the memory state.
@@ -80,6 +99,10 @@ pub enum RiscvAir<F: PrimeField32> {
     Sha256Extend(ShaExtendChip),
     /// A precompile for sha256 compress.
     Sha256Compress(ShaCompressChip),
+    /// A precompile for sha512 extend.
+    Sha512Extend(Sha512ExtendChip),
+    /// A precompile for sha512 compress.
+    Sha512Compress(Sha512CompressChip),
     /// A precompile for addition on the Elliptic curve ed25519.
     Ed25519Add(EdAddAssignChip<EdwardsCurve<Ed25519Parameters>>),
     /// A precompile for decompressing a point on the Edwards curve ed25519.
@@ -90,6 +113,12 @@ pub enum RiscvAir<F: PrimeField32> {
     Secp256k1Add(WeierstrassAddAssignChip<SwCurve<Secp256k1Parameters>>),
     /// A precompile for doubling a point on the Elliptic curve secp256k1.
     Secp256k1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256k1Parameters>>),
+    /// A precompile for decompressing a point on the P256 curve.
+    P256Decompress(WeierstrassDecompressChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for addition on the Elliptic curve secp256r1.
+    Secp256r1Add(WeierstrassAddAssignChip<SwCurve<Secp256r1Parameters>>),
+    /// A precompile for doubling a point on the Elliptic curve secp256r1.
+    Secp256r1Double(WeierstrassDoubleAssignChip<SwCurve<Secp256r1Parameters>>),
     /// A precompile for the Keccak permutation.
     KeccakP(KeccakPermuteChip),
     /// A precompile for addition on the Elliptic curve bn254.
@@ -102,6 +131,20 @@ pub enum RiscvAir<F: PrimeField32> {
     Bls12381Double(WeierstrassDoubleAssignChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for uint256 mul.
     Uint256Mul(Uint256MulChip),
+    /// A precompile for the Poseidon2 permutation over BabyBear.
+    Poseidon2Permute(Poseidon2PermuteChip),
+    /// A precompile for the BLAKE3 compression function.
+    Blake3Compress(Blake3CompressChip),
+    /// A precompile for the AES-128 and AES-256 block encryptions.
+    AesEncrypt(AesEncryptChip),
+    /// A lookup table for the AES S-box.
+    AesSbox(AesSboxChip),
+    /// A precompile for the RIPEMD-160 compression function.
+    Ripemd160Compress(Ripemd160CompressChip),
+    /// A precompile for uint320 modular addition and multiplication.
+    Uint320Mod(UintModChip<U320Field>),
+    /// A precompile for uint384 modular addition and multiplication.
+    Uint384Mod(UintModChip<U384Field>),
     /// A precompile for decompressing a point on the BLS12-381 curve.
     Bls12381Decompress(WeierstrassDecompressChip<SwCurve<Bls12381Parameters>>),
     /// A precompile for BLS12-381 fp operation.
@@ -158,6 +201,17 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Sha256Compress, 80 * sha_compress.cost());
         chips.push(sha_compress);
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let sha512_extend = Chip::new(RiscvAir::Sha512Extend(Sha512ExtendChip::default()));
+            costs.insert(RiscvAirDiscriminants::Sha512Extend, 64 * sha512_extend.cost());
+            chips.push(sha512_extend);
+
+            let sha512_compress =
+                Chip::new(RiscvAir::Sha512Compress(Sha512CompressChip::default()));
+            costs.insert(RiscvAirDiscriminants::Sha512Compress, 80 * sha512_compress.cost());
+            chips.push(sha512_compress);
+        }
+
         let ed_add_assign = Chip::new(RiscvAir::Ed25519Add(EdAddAssignChip::<
             EdwardsCurve<Ed25519Parameters>,
         >::new()));
@@ -189,6 +243,29 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Secp256k1Double, secp256k1_double_assign.cost());
         chips.push(secp256k1_double_assign);
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let p256_decompress = Chip::new(RiscvAir::P256Decompress(WeierstrassDecompressChip::<
+                SwCurve<Secp256r1Parameters>,
+            >::with_lsb_rule(
+            )));
+            costs.insert(RiscvAirDiscriminants::P256Decompress, p256_decompress.cost());
+            chips.push(p256_decompress);
+
+            let secp256r1_add_assign =
+                Chip::new(RiscvAir::Secp256r1Add(WeierstrassAddAssignChip::<
+                    SwCurve<Secp256r1Parameters>,
+                >::new()));
+            costs.insert(RiscvAirDiscriminants::Secp256r1Add, secp256r1_add_assign.cost());
+            chips.push(secp256r1_add_assign);
+
+            let secp256r1_double_assign =
+                Chip::new(RiscvAir::Secp256r1Double(WeierstrassDoubleAssignChip::<
+                    SwCurve<Secp256r1Parameters>,
+                >::new()));
+            costs.insert(RiscvAirDiscriminants::Secp256r1Double, secp256r1_double_assign.cost());
+            chips.push(secp256r1_double_assign);
+        }
+
         let keccak_permute = Chip::new(RiscvAir::KeccakP(KeccakPermuteChip::new()));
         costs.insert(RiscvAirDiscriminants::KeccakP, 24 * keccak_permute.cost());
         chips.push(keccak_permute);
@@ -221,6 +298,38 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Uint256Mul, uint256_mul.cost());
         chips.push(uint256_mul);
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let poseidon2_permute =
+                Chip::new(RiscvAir::Poseidon2Permute(Poseidon2PermuteChip::new()));
+            costs.insert(RiscvAirDiscriminants::Poseidon2Permute, poseidon2_permute.cost());
+            chips.push(poseidon2_permute);
+
+            let blake3_compress = Chip::new(RiscvAir::Blake3Compress(Blake3CompressChip::new()));
+            costs.insert(RiscvAirDiscriminants::Blake3Compress, blake3_compress.cost());
+            chips.push(blake3_compress);
+
+            let aes_encrypt = Chip::new(RiscvAir::AesEncrypt(AesEncryptChip::new()));
+            costs.insert(RiscvAirDiscriminants::AesEncrypt, aes_encrypt.cost());
+            chips.push(aes_encrypt);
+
+            let aes_sbox = Chip::new(RiscvAir::AesSbox(AesSboxChip::new()));
+            costs.insert(RiscvAirDiscriminants::AesSbox, aes_sbox.cost());
+            chips.push(aes_sbox);
+
+            let ripemd160_compress =
+                Chip::new(RiscvAir::Ripemd160Compress(Ripemd160CompressChip::new()));
+            costs.insert(RiscvAirDiscriminants::Ripemd160Compress, 80 * ripemd160_compress.cost());
+            chips.push(ripemd160_compress);
+
+            let uint320_mod = Chip::new(RiscvAir::Uint320Mod(UintModChip::<U320Field>::new()));
+            costs.insert(RiscvAirDiscriminants::Uint320Mod, uint320_mod.cost());
+            chips.push(uint320_mod);
+
+            let uint384_mod = Chip::new(RiscvAir::Uint384Mod(UintModChip::<U384Field>::new()));
+            costs.insert(RiscvAirDiscriminants::Uint384Mod, uint384_mod.cost());
+            chips.push(uint384_mod);
+        }
+
         let bls12381_fp = Chip::new(RiscvAir::Bls12381Fp(FpOpChip::<Bls12381BaseField>::new()));
         costs.insert(RiscvAirDiscriminants::Bls12381Fp, bls12381_fp.cost());
         chips.push(bls12381_fp);
@@ -284,6 +393,12 @@ impl<F: PrimeField32> RiscvAir<F> {
         costs.insert(RiscvAirDiscriminants::Lt, lt.cost());
         chips.push(lt);
 
+        if INCLUDE_UNRELEASED_CHIPS {
+            let bitmanip = Chip::new(RiscvAir::BitManip(BitManipChip::default()));
+            costs.insert(RiscvAirDiscriminants::BitManip, bitmanip.cost());
+            chips.push(bitmanip);
+        }
+
         let memory_init =
             Chip::new(RiscvAir::MemoryInit(MemoryChip::new(MemoryChipType::Initialize)));
         costs.insert(RiscvAirDiscriminants::MemoryInit, memory_init.cost());
@@ -325,6 +440,7 @@ impl<F: PrimeField32> core::hash::Hash for RiscvAir<F> {
 pub mod tests {
 
     use crate::{
+        alu::BITMANIP_OPCODES,
         io::SP1Stdin,
         riscv::RiscvAir,
         utils,
@@ -442,6 +558,20 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_bitmanip_prove() {
+        setup_logger();
+        for opcode in BITMANIP_OPCODES {
+            let instructions = vec![
+                Instruction::new(Opcode::ADD, 29, 0, 0x8000_0f05, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, 37, false, true),
+                Instruction::new(opcode, 31, 29, 30, false, false),
+            ];
+            let program = Program::new(instructions, 0, 0);
+            run_test::<CpuProver<_, _>>(program).unwrap();
+        }
+    }
+
     #[test]
     fn test_divrem_prove() {
         setup_logger();
@@ -0,0 +1,224 @@
+use core::borrow::Borrow;
+use std::array;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::{
+    events::{AES128_NUM_ROUNDS, AES_BLOCK_WORDS},
+    syscalls::SyscallCode,
+    ByteOpcode,
+};
+use sp1_stark::{air::SP1AirBuilder, Word};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::XorOperation,
+};
+
+use super::{
+    columns::{AesEncryptCols, SubBytesCols, NUM_AES_ENCRYPT_COLS},
+    rotate_right_bytes, shift_rows, AesEncryptChip, AES128_ROUND_KEY_WORDS, AES256_ROUND_KEY_WORDS,
+    NUM_FULL_ROUNDS,
+};
+
+impl<F> BaseAir<F> for AesEncryptChip {
+    fn width(&self) -> usize {
+        NUM_AES_ENCRYPT_COLS
+    }
+}
+
+impl<AB> Air<AB> for AesEncryptChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &AesEncryptCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &AesEncryptCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        builder.assert_bool(local.is_real);
+        builder.assert_bool(local.is_aes256);
+        builder.when_not(local.is_real).assert_zero(local.is_aes256);
+
+        let key = |round: usize| -> [Word<AB::Var>; AES_BLOCK_WORDS] {
+            array::from_fn(|col| *local.round_keys_mem[4 * round + col].value())
+        };
+
+        // The initial key addition of the plaintext, the previous value of the block.
+        let plaintext: [Word<AB::Var>; AES_BLOCK_WORDS] =
+            array::from_fn(|col| local.block_mem[col].prev_value);
+        for col in 0..AES_BLOCK_WORDS {
+            XorOperation::<AB::F>::eval(
+                builder,
+                plaintext[col],
+                key(0)[col],
+                local.add_round_key[col],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+        let mut state: [Word<AB::Var>; AES_BLOCK_WORDS] =
+            array::from_fn(|col| local.add_round_key[col].value);
+
+        // Run the rounds with a `MixColumns` step. Those after the ninth are only run by AES-256.
+        let mut states = Vec::with_capacity(NUM_FULL_ROUNDS);
+        for (i, round) in local.rounds.iter().enumerate() {
+            let is_real = if i + 1 < AES128_NUM_ROUNDS { local.is_real } else { local.is_aes256 };
+            eval_sub_bytes(builder, &round.sub_bytes, state, local.shard, local.channel, is_real);
+            let a = shift_rows(round.sub_bytes.value);
+            let d = shift_rows(round.sub_bytes.xtime);
+            for col in 0..AES_BLOCK_WORDS {
+                let mix = &round.mix_columns[col];
+                let terms = [
+                    (d[col], rotate_right_bytes(d[col], 1)),
+                    (mix[0].value, rotate_right_bytes(a[col], 1)),
+                    (mix[1].value, rotate_right_bytes(a[col], 2)),
+                    (mix[2].value, rotate_right_bytes(a[col], 3)),
+                ];
+                for (cols, (x, y)) in mix.iter().zip(terms) {
+                    XorOperation::<AB::F>::eval(
+                        builder,
+                        x,
+                        y,
+                        *cols,
+                        local.shard,
+                        local.channel,
+                        is_real,
+                    );
+                }
+                XorOperation::<AB::F>::eval(
+                    builder,
+                    mix[3].value,
+                    key(i + 1)[col],
+                    round.add_round_key[col],
+                    local.shard,
+                    local.channel,
+                    is_real,
+                );
+            }
+            state = array::from_fn(|col| round.add_round_key[col].value);
+            states.push(state);
+        }
+
+        // Select the input and the key of the last round.
+        let aes128_state = states[AES128_NUM_ROUNDS - 2];
+        let aes256_state = states[NUM_FULL_ROUNDS - 1];
+        let aes128_key = key(AES128_NUM_ROUNDS);
+        let aes256_key = key(NUM_FULL_ROUNDS + 1);
+        for col in 0..AES_BLOCK_WORDS {
+            for i in 0..4 {
+                builder.when(local.is_real).assert_eq(
+                    local.final_state[col][i],
+                    aes128_state[col][i]
+                        + local.is_aes256 * (aes256_state[col][i] - aes128_state[col][i]),
+                );
+                builder.when(local.is_real).assert_eq(
+                    local.final_key[col][i],
+                    aes128_key[col][i]
+                        + local.is_aes256 * (aes256_key[col][i] - aes128_key[col][i]),
+                );
+            }
+        }
+
+        // Run the last round, which has no `MixColumns` step.
+        eval_sub_bytes(
+            builder,
+            &local.final_sub_bytes,
+            local.final_state,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        let a = shift_rows(local.final_sub_bytes.value);
+        for col in 0..AES_BLOCK_WORDS {
+            XorOperation::<AB::F>::eval(
+                builder,
+                a[col],
+                local.final_key[col],
+                local.output[col],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            builder
+                .when(local.is_real)
+                .assert_word_eq(*local.block_mem[col].value(), local.output[col].value);
+        }
+
+        // Read the round keys, the last ones only for AES-256.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.round_keys_ptr,
+            &local.round_keys_mem[..AES128_ROUND_KEY_WORDS],
+            local.is_real,
+        );
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.round_keys_ptr + AB::F::from_canonical_usize(4 * AES128_ROUND_KEY_WORDS),
+            &local.round_keys_mem[AES128_ROUND_KEY_WORDS..AES256_ROUND_KEY_WORDS],
+            local.is_aes256,
+        );
+
+        // Read and write the block.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into() + AB::Expr::one(),
+            local.block_ptr,
+            &local.block_mem,
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        let aes128_id = AB::F::from_canonical_u32(SyscallCode::AES128_ENCRYPT.syscall_id());
+        let aes256_id = AB::F::from_canonical_u32(SyscallCode::AES256_ENCRYPT.syscall_id());
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            local.is_aes256 * (aes256_id - aes128_id) + aes128_id,
+            local.block_ptr,
+            local.round_keys_ptr,
+            local.is_real,
+        );
+    }
+}
+
+/// Constrains the `SubBytes` step of `state`, looking up the S-box of each of its bytes.
+fn eval_sub_bytes<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    cols: &SubBytesCols<AB::Var>,
+    state: [Word<AB::Var>; AES_BLOCK_WORDS],
+    shard: AB::Var,
+    channel: AB::Var,
+    is_real: AB::Var,
+) {
+    for col in 0..AES_BLOCK_WORDS {
+        for i in 0..4 {
+            builder.send_byte_pair(
+                AB::F::from_canonical_u32(ByteOpcode::AesSbox as u32),
+                cols.value[col][i],
+                cols.xtime[col][i],
+                state[col][i],
+                AB::F::zero(),
+                shard,
+                channel,
+                is_real,
+            );
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use core::mem::size_of;
+
+use sp1_core_executor::events::AES_BLOCK_WORDS;
+use sp1_derive::AlignedBorrow;
+use sp1_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::XorOperation,
+};
+
+use super::{AES256_ROUND_KEY_WORDS, NUM_FULL_ROUNDS};
+
+/// AesEncryptCols is the column layout for the AES block encryption.
+///
+/// The encryption takes a single row. `ShiftRows` is a byte permutation of the state, so it
+/// doesn't have columns. The rounds that only AES-256 has are unconstrained for AES-128.
+#[derive(AlignedBorrow)]
+#[repr(C)]
+pub(crate) struct AesEncryptCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub block_ptr: T,
+    pub round_keys_ptr: T,
+
+    /// Whether the block is encrypted with AES-256 rather than AES-128.
+    pub is_aes256: T,
+
+    /// Memory columns for the round keys, the last of which are only read by AES-256.
+    pub round_keys_mem: [MemoryReadCols<T>; AES256_ROUND_KEY_WORDS],
+
+    /// Memory columns for the block, whose previous values are the plaintext and whose values are
+    /// the ciphertext.
+    pub block_mem: [MemoryWriteCols<T>; AES_BLOCK_WORDS],
+
+    /// The initial key addition.
+    pub add_round_key: [XorOperation<T>; AES_BLOCK_WORDS],
+
+    /// The rounds with a `MixColumns` step, all but the last round.
+    pub rounds: [RoundCols<T>; NUM_FULL_ROUNDS],
+
+    /// The state and the round key of the last round, selected from those of AES-128 and AES-256.
+    pub final_state: [Word<T>; AES_BLOCK_WORDS],
+    pub final_key: [Word<T>; AES_BLOCK_WORDS],
+
+    /// The last round, without `MixColumns`.
+    pub final_sub_bytes: SubBytesCols<T>,
+    pub output: [XorOperation<T>; AES_BLOCK_WORDS],
+
+    pub is_real: T,
+}
+
+/// The columns of the `SubBytes` step: the S-box of each byte of the state, and its product by
+/// `x` for `MixColumns`.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub(crate) struct SubBytesCols<T> {
+    pub value: [Word<T>; AES_BLOCK_WORDS],
+    pub xtime: [Word<T>; AES_BLOCK_WORDS],
+}
+
+/// The columns of a round with a `MixColumns` step.
+///
+/// Each byte `b_i` of a column of the output of `MixColumns` is
+/// `2 a_i ^ 2 a_{i+1} ^ a_{i+1} ^ a_{i+2} ^ a_{i+3}`, for the bytes `a_i` of the column of the
+/// shifted state, which is computed on the whole column word at once with byte rotations.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub(crate) struct RoundCols<T> {
+    pub sub_bytes: SubBytesCols<T>,
+    pub mix_columns: [[XorOperation<T>; 4]; AES_BLOCK_WORDS],
+    pub add_round_key: [XorOperation<T>; AES_BLOCK_WORDS],
+}
+
+pub const NUM_AES_ENCRYPT_COLS: usize = size_of::<AesEncryptCols<u8>>();
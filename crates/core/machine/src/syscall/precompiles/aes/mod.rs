@@ -0,0 +1,150 @@
+mod air;
+pub mod columns;
+pub mod sbox;
+mod trace;
+
+pub use sbox::AesSboxChip;
+
+use std::array;
+
+use sp1_core_executor::events::{AES128_NUM_ROUNDS, AES256_NUM_ROUNDS, AES_BLOCK_WORDS};
+use sp1_stark::Word;
+
+/// The number of round key words of AES-128.
+pub const AES128_ROUND_KEY_WORDS: usize = AES_BLOCK_WORDS * (AES128_NUM_ROUNDS + 1);
+
+/// The number of round key words of AES-256.
+pub const AES256_ROUND_KEY_WORDS: usize = AES_BLOCK_WORDS * (AES256_NUM_ROUNDS + 1);
+
+/// The number of rounds with a `MixColumns` step for AES-256, all but the last one. AES-128 only
+/// uses the first [`AES128_NUM_ROUNDS`] - 1 of them.
+pub const NUM_FULL_ROUNDS: usize = AES256_NUM_ROUNDS - 1;
+
+/// A chip that implements the AES-128 and AES-256 block encryptions.
+///
+/// Each row encrypts the block at the first argument of a syscall in place, with the expanded
+/// round keys at its second argument: 44 words for AES-128 and 60 for AES-256, the bytes of each
+/// round key in the order of the block. The state is stored column by column, so that each word
+/// of the block is a column of the state.
+#[derive(Default)]
+pub struct AesEncryptChip;
+
+impl AesEncryptChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// The `ShiftRows` step, rotating the row `r` of the state left by `r` bytes.
+fn shift_rows<T: Copy>(state: [Word<T>; AES_BLOCK_WORDS]) -> [Word<T>; AES_BLOCK_WORDS] {
+    array::from_fn(|col| Word(array::from_fn(|row| state[(col + row) % AES_BLOCK_WORDS][row])))
+}
+
+/// Rotates the little-endian `word` right by a number of whole `bytes`.
+fn rotate_right_bytes<T: Copy>(word: Word<T>, bytes: usize) -> Word<T> {
+    Word(array::from_fn(|i| word[(i + bytes) % 4]))
+}
+
+#[cfg(test)]
+pub mod encrypt_tests {
+    use sp1_core_executor::{
+        events::{AES_BLOCK_WORDS, AES_SBOX},
+        syscalls::SyscallCode,
+        Executor, Instruction, Opcode, Program,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const BLOCK_PTR: u32 = 100;
+    const ROUND_KEYS_PTR: u32 = 200;
+
+    /// The AES key expansion of `key`, as little-endian words.
+    pub fn expand_key(key: &[u8]) -> Vec<u32> {
+        let nk = key.len() / 4;
+        let num_words = 4 * (nk + 7);
+        let mut w: Vec<[u8; 4]> = key.chunks_exact(4).map(|c| c.try_into().unwrap()).collect();
+        let mut rcon = 1u8;
+        for i in nk..num_words {
+            let mut t = w[i - 1];
+            if i % nk == 0 {
+                t = [t[1], t[2], t[3], t[0]].map(|b| AES_SBOX[b as usize]);
+                t[0] ^= rcon;
+                rcon = (rcon << 1) ^ if rcon & 0x80 != 0 { 0x1b } else { 0 };
+            } else if nk > 6 && i % nk == 4 {
+                t = t.map(|b| AES_SBOX[b as usize]);
+            }
+            w.push(core::array::from_fn(|j| w[i - nk][j] ^ t[j]));
+        }
+        w.into_iter().map(u32::from_le_bytes).collect()
+    }
+
+    /// A program encrypting `block` with `key`, at [`BLOCK_PTR`].
+    pub fn aes_encrypt_program(key: &[u8], block: [u8; 16]) -> Program {
+        let syscall = match key.len() {
+            16 => SyscallCode::AES128_ENCRYPT,
+            32 => SyscallCode::AES256_ENCRYPT,
+            _ => panic!("invalid AES key length"),
+        };
+        let block = block.chunks_exact(4).map(|c| u32::from_le_bytes(c.try_into().unwrap()));
+        let mut instructions = vec![];
+        for (ptr, words) in
+            [(BLOCK_PTR, block.collect::<Vec<_>>()), (ROUND_KEYS_PTR, expand_key(key))]
+        {
+            for (i, word) in words.into_iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, BLOCK_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, ROUND_KEYS_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    fn encrypt(key: &[u8], block: [u8; 16]) -> String {
+        let program = aes_encrypt_program(key, block);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        let ciphertext = (0..AES_BLOCK_WORDS as u32)
+            .flat_map(|i| runtime.state.memory.get(BLOCK_PTR + 4 * i).unwrap().value.to_le_bytes())
+            .collect::<Vec<_>>();
+        hex::encode(ciphertext)
+    }
+
+    #[test]
+    pub fn test_aes_encrypt_program_execute() {
+        utils::setup_logger();
+
+        // The examples of FIPS 197, appendix C.
+        let block = core::array::from_fn(|i| 0x11 * i as u8);
+        let key = (0..32).collect::<Vec<u8>>();
+        assert_eq!(encrypt(&key[..16], block), "69c4e0d86a7b0430d8cdb78070b4c55a");
+        assert_eq!(encrypt(&key, block), "8ea2b7ca516745bfeafc49904b496089");
+    }
+
+    #[test]
+    fn test_aes128_encrypt_prove_babybear() {
+        utils::setup_logger();
+
+        let key = (0..16u8).map(|i| i.wrapping_mul(0x13)).collect::<Vec<_>>();
+        let program = aes_encrypt_program(&key, core::array::from_fn(|i| i as u8));
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_aes256_encrypt_prove_babybear() {
+        utils::setup_logger();
+
+        let key = (0..32u8).map(|i| i.wrapping_mul(0x13)).collect::<Vec<_>>();
+        let program = aes_encrypt_program(&key, core::array::from_fn(|i| i as u8));
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+use core::{
+    borrow::{Borrow, BorrowMut},
+    mem::size_of,
+};
+
+use hashbrown::HashMap;
+use p3_air::{Air, BaseAir, PairBuilder};
+use p3_field::{AbstractField, Field, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{aes_xtime, AES_SBOX},
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{MachineAir, SP1AirBuilder};
+
+use crate::bytes::NUM_BYTE_LOOKUP_CHANNELS;
+
+/// The number of rows of the S-box table, one for each byte.
+pub const NUM_AES_SBOX_ROWS: usize = 1 << 8;
+
+/// The number of preprocessed columns for `AesSboxChip`.
+pub const NUM_AES_SBOX_PREPROCESSED_COLS: usize = size_of::<AesSboxPreprocessedCols<u8>>();
+
+/// The number of multiplicity columns for `AesSboxChip`.
+pub const NUM_AES_SBOX_MULT_COLS: usize = size_of::<AesSboxMultCols<u8>>();
+
+#[derive(Debug, Clone, Copy, AlignedBorrow)]
+#[repr(C)]
+pub struct AesSboxPreprocessedCols<T> {
+    /// The input byte.
+    pub b: T,
+
+    /// The AES S-box of `b`, and its product by `x` in the field of AES.
+    pub sbox: T,
+    pub sbox_xtime: T,
+}
+
+/// For each byte in the preprocessed table, tracks the number of times its S-box is looked up on
+/// each channel.
+#[derive(Debug, Clone, Copy, AlignedBorrow)]
+#[repr(C)]
+pub struct AesSboxMultCols<T> {
+    /// Shard number is tracked so that the multiplicities do not overflow.
+    pub shard: T,
+
+    /// The multiplicities of the lookups on each channel.
+    pub multiplicities: [T; NUM_BYTE_LOOKUP_CHANNELS as usize],
+}
+
+/// A lookup table for the AES S-box.
+///
+/// The [`ByteOpcode::AesSbox`] lookups of [`super::AesEncryptChip`] are received by this table
+/// rather than by the byte table, so that the layout of the byte table, which every shard uses,
+/// doesn't depend on the AES precompile.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AesSboxChip;
+
+impl AesSboxChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F: Field> BaseAir<F> for AesSboxChip {
+    fn width(&self) -> usize {
+        NUM_AES_SBOX_MULT_COLS
+    }
+}
+
+impl<F: PrimeField32> MachineAir<F> for AesSboxChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "AesSbox".to_string()
+    }
+
+    fn preprocessed_width(&self) -> usize {
+        NUM_AES_SBOX_PREPROCESSED_COLS
+    }
+
+    fn generate_preprocessed_trace(&self, _program: &Self::Program) -> Option<RowMajorMatrix<F>> {
+        let mut trace = RowMajorMatrix::new(
+            vec![F::zero(); NUM_AES_SBOX_ROWS * NUM_AES_SBOX_PREPROCESSED_COLS],
+            NUM_AES_SBOX_PREPROCESSED_COLS,
+        );
+        for (b, sbox) in AES_SBOX.into_iter().enumerate() {
+            let cols: &mut AesSboxPreprocessedCols<F> = trace.row_mut(b).borrow_mut();
+            cols.b = F::from_canonical_usize(b);
+            cols.sbox = F::from_canonical_u8(sbox);
+            cols.sbox_xtime = F::from_canonical_u8(aes_xtime(sbox));
+        }
+        Some(trace)
+    }
+
+    fn generate_dependencies(&self, _input: &ExecutionRecord, _output: &mut ExecutionRecord) {
+        // Do nothing since this chip has no dependencies.
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut trace = RowMajorMatrix::new(
+            vec![F::zero(); NUM_AES_SBOX_MULT_COLS * NUM_AES_SBOX_ROWS],
+            NUM_AES_SBOX_MULT_COLS,
+        );
+
+        let shard = input.public_values.execution_shard;
+        for (lookup, mult) in input.byte_lookups.get(&shard).unwrap_or(&HashMap::new()).iter() {
+            if lookup.opcode != ByteOpcode::AesSbox {
+                continue;
+            }
+            let cols: &mut AesSboxMultCols<F> = trace.row_mut(lookup.b as usize).borrow_mut();
+            cols.multiplicities[lookup.channel as usize] += F::from_canonical_usize(*mult);
+            cols.shard = F::from_canonical_u32(shard);
+        }
+
+        trace
+    }
+
+    fn included(&self, _shard: &Self::Record) -> bool {
+        // Preprocessed tables are opened in every shard.
+        true
+    }
+}
+
+impl<AB: SP1AirBuilder + PairBuilder> Air<AB> for AesSboxChip {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local_mult = main.row_slice(0);
+        let local_mult: &AesSboxMultCols<AB::Var> = (*local_mult).borrow();
+
+        let prep = builder.preprocessed();
+        let prep = prep.row_slice(0);
+        let local: &AesSboxPreprocessedCols<AB::Var> = (*prep).borrow();
+
+        for channel in 0..NUM_BYTE_LOOKUP_CHANNELS {
+            builder.receive_byte_pair(
+                ByteOpcode::AesSbox.as_field::<AB::F>(),
+                local.sbox,
+                local.sbox_xtime,
+                local.b,
+                AB::F::zero(),
+                local_mult.shard,
+                AB::F::from_canonical_u8(channel),
+                local_mult.multiplicities[channel as usize],
+            );
+        }
+    }
+}
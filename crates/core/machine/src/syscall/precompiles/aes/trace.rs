@@ -0,0 +1,176 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{
+        aes_xtime, ByteLookupEvent, ByteRecord, AES128_NUM_ROUNDS, AES256_NUM_ROUNDS,
+        AES_BLOCK_WORDS, AES_SBOX,
+    },
+    ByteOpcode, ExecutionRecord, Program,
+};
+use sp1_stark::{air::MachineAir, Word};
+
+use crate::utils::pad_rows;
+
+use super::{
+    columns::{AesEncryptCols, SubBytesCols, NUM_AES_ENCRYPT_COLS},
+    shift_rows, AesEncryptChip,
+};
+
+impl<F: PrimeField32> MachineAir<F> for AesEncryptChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "AesEncrypt".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut new_byte_lookup_events = Vec::new();
+
+        let mut rows = input
+            .aes_encrypt_events
+            .iter()
+            .map(|event| {
+                let mut row = [F::zero(); NUM_AES_ENCRYPT_COLS];
+                let cols: &mut AesEncryptCols<F> = row.as_mut_slice().borrow_mut();
+                let blu = &mut new_byte_lookup_events;
+                let (shard, channel) = (event.shard, event.channel);
+
+                cols.is_real = F::one();
+                cols.is_aes256 = F::from_bool(event.is_aes256);
+                cols.shard = F::from_canonical_u32(shard);
+                cols.channel = F::from_canonical_u8(channel);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.block_ptr = F::from_canonical_u32(event.block_ptr);
+                cols.round_keys_ptr = F::from_canonical_u32(event.round_keys_ptr);
+
+                for (i, record) in event.round_key_read_records.iter().enumerate() {
+                    cols.round_keys_mem[i].populate(channel, *record, blu);
+                }
+                for (i, record) in event.block_write_records.iter().enumerate() {
+                    cols.block_mem[i].populate(channel, *record, blu);
+                }
+
+                let num_rounds =
+                    if event.is_aes256 { AES256_NUM_ROUNDS } else { AES128_NUM_ROUNDS };
+                let key = |round: usize, col: usize| event.round_keys[4 * round + col];
+
+                let mut state = event.input;
+                for (col, word) in state.iter_mut().enumerate() {
+                    *word =
+                        cols.add_round_key[col].populate(blu, shard, channel, *word, key(0, col));
+                }
+
+                // Run the rounds with a `MixColumns` step.
+                for round in 1..num_rounds {
+                    let round_cols = &mut cols.rounds[round - 1];
+                    let (value, xtime) =
+                        populate_sub_bytes(&mut round_cols.sub_bytes, blu, shard, channel, state);
+                    let (a, d) = (shift_rows_u32(value), shift_rows_u32(xtime));
+                    for col in 0..AES_BLOCK_WORDS {
+                        let mix = &mut round_cols.mix_columns[col];
+                        let x =
+                            mix[0].populate(blu, shard, channel, d[col], d[col].rotate_right(8));
+                        let x = mix[1].populate(blu, shard, channel, x, a[col].rotate_right(8));
+                        let x = mix[2].populate(blu, shard, channel, x, a[col].rotate_right(16));
+                        let x = mix[3].populate(blu, shard, channel, x, a[col].rotate_right(24));
+                        state[col] = round_cols.add_round_key[col].populate(
+                            blu,
+                            shard,
+                            channel,
+                            x,
+                            key(round, col),
+                        );
+                    }
+                }
+
+                // Run the last round.
+                for col in 0..AES_BLOCK_WORDS {
+                    cols.final_state[col] = Word::from(state[col]);
+                    cols.final_key[col] = Word::from(key(num_rounds, col));
+                }
+                let (value, _) =
+                    populate_sub_bytes(&mut cols.final_sub_bytes, blu, shard, channel, state);
+                let a = shift_rows_u32(value);
+                for col in 0..AES_BLOCK_WORDS {
+                    let value = cols.output[col].populate(
+                        blu,
+                        shard,
+                        channel,
+                        a[col],
+                        key(num_rounds, col),
+                    );
+                    debug_assert_eq!(value, event.output[col]);
+                }
+
+                row
+            })
+            .collect::<Vec<_>>();
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_AES_ENCRYPT_COLS]);
+
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_AES_ENCRYPT_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut AesEncryptCols<F> =
+                trace.values[i * NUM_AES_ENCRYPT_COLS..(i + 1) * NUM_AES_ENCRYPT_COLS].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.aes_encrypt_events.is_empty()
+    }
+}
+
+/// Populates the `SubBytes` columns of `state`, and returns the substituted state and its product
+/// by `x`.
+fn populate_sub_bytes<F: PrimeField32>(
+    cols: &mut SubBytesCols<F>,
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    state: [u32; AES_BLOCK_WORDS],
+) -> ([u32; AES_BLOCK_WORDS], [u32; AES_BLOCK_WORDS]) {
+    let mut value = [0; AES_BLOCK_WORDS];
+    let mut xtime = [0; AES_BLOCK_WORDS];
+    for (col, word) in state.into_iter().enumerate() {
+        let bytes = word.to_le_bytes();
+        let sbox = bytes.map(|b| AES_SBOX[b as usize]);
+        let sbox_xtime = sbox.map(aes_xtime);
+        for ((b, s), x) in bytes.into_iter().zip(sbox).zip(sbox_xtime) {
+            blu.add_byte_lookup_event(ByteLookupEvent::new(
+                shard,
+                channel,
+                ByteOpcode::AesSbox,
+                s as u16,
+                x,
+                b,
+                0,
+            ));
+        }
+        value[col] = u32::from_le_bytes(sbox);
+        xtime[col] = u32::from_le_bytes(sbox_xtime);
+        cols.value[col] = Word::from(value[col]);
+        cols.xtime[col] = Word::from(xtime[col]);
+    }
+    (value, xtime)
+}
+
+/// The `ShiftRows` step on a state of little-endian words.
+fn shift_rows_u32(state: [u32; AES_BLOCK_WORDS]) -> [u32; AES_BLOCK_WORDS] {
+    shift_rows(state.map(|word| Word(word.to_le_bytes()))).map(|word| u32::from_le_bytes(word.0))
+}
@@ -0,0 +1,194 @@
+use core::borrow::Borrow;
+use std::array;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::syscalls::SyscallCode;
+use sp1_stark::{air::SP1AirBuilder, Word};
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+};
+
+use super::{
+    columns::{Blake3CompressCols, GFunctionCols, NUM_BLAKE3_COMPRESS_COLS},
+    Blake3CompressChip, G_INDICES, MSG_SCHEDULE, STATE_SIZE,
+};
+
+impl<F> BaseAir<F> for Blake3CompressChip {
+    fn width(&self) -> usize {
+        NUM_BLAKE3_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Blake3CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Blake3CompressCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &Blake3CompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        builder.assert_bool(local.is_real);
+
+        // The input of the compression is the previous state in memory.
+        let pre_state: [Word<AB::Var>; STATE_SIZE] =
+            array::from_fn(|i| local.state_mem[i].prev_value);
+        let msg: [Word<AB::Var>; STATE_SIZE] = array::from_fn(|i| *local.msg_mem[i].value());
+
+        // Run the rounds.
+        let mut v = pre_state;
+        for (round, schedule) in MSG_SCHEDULE.iter().enumerate() {
+            for (j, &[a, b, c, d]) in G_INDICES.iter().enumerate() {
+                [v[a], v[b], v[c], v[d]] = eval_g(
+                    builder,
+                    &local.g[round][j],
+                    [v[a], v[b], v[c], v[d]],
+                    msg[schedule[2 * j]],
+                    msg[schedule[2 * j + 1]],
+                    local.shard,
+                    local.channel,
+                    local.is_real,
+                );
+            }
+        }
+
+        // The output is written to memory: the state xored with its second half for the first
+        // eight words, and the second half of the state xored with the chaining value for the last
+        // eight.
+        for i in 0..STATE_SIZE {
+            let y = if i < 8 { v[i + 8] } else { pre_state[i - 8] };
+            XorOperation::<AB::F>::eval(
+                builder,
+                v[i],
+                y,
+                local.output[i],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            builder
+                .when(local.is_real)
+                .assert_word_eq(*local.state_mem[i].value(), local.output[i].value);
+        }
+
+        // Read the message block.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.msg_ptr,
+            &local.msg_mem,
+            local.is_real,
+        );
+
+        // Read and write the state.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into() + AB::Expr::one(),
+            local.state_ptr,
+            &local.state_mem,
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::BLAKE3_COMPRESS.syscall_id()),
+            local.state_ptr,
+            local.msg_ptr,
+            local.is_real,
+        );
+    }
+}
+
+/// Constrains a `G` function mixing `[a, b, c, d]` with `mx` and `my`, and returns the mixed
+/// words.
+#[allow(clippy::too_many_arguments)]
+fn eval_g<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    cols: &GFunctionCols<AB::Var>,
+    [a, b, c, d]: [Word<AB::Var>; 4],
+    mx: Word<AB::Var>,
+    my: Word<AB::Var>,
+    shard: AB::Var,
+    channel: AB::Var,
+    is_real: AB::Var,
+) -> [Word<AB::Var>; 4] {
+    AddOperation::<AB::F>::eval(builder, a, b, cols.a_plus_b, shard, channel, is_real.into());
+    let a = cols.a_plus_b.value;
+    AddOperation::<AB::F>::eval(
+        builder,
+        a,
+        mx,
+        cols.a_plus_b_plus_mx,
+        shard,
+        channel,
+        is_real.into(),
+    );
+    let a = cols.a_plus_b_plus_mx.value;
+    XorOperation::<AB::F>::eval(builder, d, a, cols.d_xor_a, shard, channel, is_real);
+    let d = rotate_right_bytes(cols.d_xor_a.value, 2);
+    AddOperation::<AB::F>::eval(builder, c, d, cols.c_plus_d, shard, channel, is_real.into());
+    let c = cols.c_plus_d.value;
+    XorOperation::<AB::F>::eval(builder, b, c, cols.b_xor_c, shard, channel, is_real);
+    FixedRotateRightOperation::<AB::F>::eval(
+        builder,
+        cols.b_xor_c.value,
+        12,
+        cols.b_xor_c_rr_12,
+        shard,
+        channel,
+        is_real,
+    );
+    let b = cols.b_xor_c_rr_12.value;
+
+    AddOperation::<AB::F>::eval(builder, a, b, cols.a_plus_b_2, shard, channel, is_real.into());
+    let a = cols.a_plus_b_2.value;
+    AddOperation::<AB::F>::eval(
+        builder,
+        a,
+        my,
+        cols.a_plus_b_plus_my,
+        shard,
+        channel,
+        is_real.into(),
+    );
+    let a = cols.a_plus_b_plus_my.value;
+    XorOperation::<AB::F>::eval(builder, d, a, cols.d_xor_a_2, shard, channel, is_real);
+    let d = rotate_right_bytes(cols.d_xor_a_2.value, 1);
+    AddOperation::<AB::F>::eval(builder, c, d, cols.c_plus_d_2, shard, channel, is_real.into());
+    let c = cols.c_plus_d_2.value;
+    XorOperation::<AB::F>::eval(builder, b, c, cols.b_xor_c_2, shard, channel, is_real);
+    FixedRotateRightOperation::<AB::F>::eval(
+        builder,
+        cols.b_xor_c_2.value,
+        7,
+        cols.b_xor_c_2_rr_7,
+        shard,
+        channel,
+        is_real,
+    );
+    let b = cols.b_xor_c_2_rr_7.value;
+
+    [a, b, c, d]
+}
+
+/// Rotates the little-endian `word` right by a number of whole `bytes`.
+fn rotate_right_bytes<T: Copy>(word: Word<T>, bytes: usize) -> Word<T> {
+    Word(array::from_fn(|i| word[(i + bytes) % 4]))
+}
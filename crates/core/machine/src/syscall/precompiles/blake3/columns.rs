@@ -0,0 +1,63 @@
+use core::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{AddOperation, FixedRotateRightOperation, XorOperation},
+};
+
+use super::{NUM_G_PER_ROUND, NUM_ROUNDS, STATE_SIZE};
+
+/// Blake3CompressCols is the column layout for the BLAKE3 compression function.
+///
+/// The compression takes a single row. The rotations by 16 and 8 bits are byte permutations, so
+/// they don't have columns.
+#[derive(AlignedBorrow)]
+#[repr(C)]
+pub(crate) struct Blake3CompressCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub state_ptr: T,
+    pub msg_ptr: T,
+
+    /// Memory columns for the message block.
+    pub msg_mem: [MemoryReadCols<T>; STATE_SIZE],
+
+    /// Memory columns for the state, whose previous values are the input of the compression and
+    /// whose values are its output.
+    pub state_mem: [MemoryWriteCols<T>; STATE_SIZE],
+
+    /// The `G` functions of each round.
+    pub g: [[GFunctionCols<T>; NUM_G_PER_ROUND]; NUM_ROUNDS],
+
+    /// The output of the compression, the state after the rounds xored with its second half or
+    /// with the chaining value.
+    pub output: [XorOperation<T>; STATE_SIZE],
+
+    pub is_real: T,
+}
+
+/// The columns of a `G` function mixing the words `a`, `b`, `c` and `d` of the state with the
+/// message words `mx` and `my`.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub(crate) struct GFunctionCols<T> {
+    pub a_plus_b: AddOperation<T>,
+    pub a_plus_b_plus_mx: AddOperation<T>,
+    pub d_xor_a: XorOperation<T>,
+    pub c_plus_d: AddOperation<T>,
+    pub b_xor_c: XorOperation<T>,
+    pub b_xor_c_rr_12: FixedRotateRightOperation<T>,
+
+    pub a_plus_b_2: AddOperation<T>,
+    pub a_plus_b_plus_my: AddOperation<T>,
+    pub d_xor_a_2: XorOperation<T>,
+    pub c_plus_d_2: AddOperation<T>,
+    pub b_xor_c_2: XorOperation<T>,
+    pub b_xor_c_2_rr_7: FixedRotateRightOperation<T>,
+}
+
+pub const NUM_BLAKE3_COMPRESS_COLS: usize = size_of::<Blake3CompressCols<u8>>();
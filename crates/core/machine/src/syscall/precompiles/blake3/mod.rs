@@ -0,0 +1,124 @@
+mod air;
+pub mod columns;
+mod trace;
+
+/// The number of words in the state and in the message block.
+pub const STATE_SIZE: usize = 16;
+
+pub const NUM_ROUNDS: usize = 7;
+
+/// The number of `G` functions mixing the state in each round.
+pub const NUM_G_PER_ROUND: usize = 8;
+
+/// The indices of the message words used by each round, the message being permuted between
+/// rounds.
+pub const MSG_SCHEDULE: [[usize; STATE_SIZE]; NUM_ROUNDS] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8],
+    [3, 4, 10, 12, 13, 2, 7, 14, 6, 5, 9, 0, 11, 15, 8, 1],
+    [10, 7, 12, 9, 14, 3, 13, 15, 4, 0, 11, 2, 5, 8, 1, 6],
+    [12, 13, 9, 11, 15, 10, 14, 8, 7, 2, 5, 3, 0, 1, 6, 4],
+    [9, 14, 11, 5, 8, 12, 15, 1, 13, 3, 0, 10, 2, 6, 4, 7],
+    [11, 15, 5, 0, 1, 9, 8, 6, 14, 10, 2, 12, 3, 4, 7, 13],
+];
+
+/// The indices of the state words mixed by each `G` function of a round: the columns, then the
+/// diagonals of the state.
+pub const G_INDICES: [[usize; 4]; NUM_G_PER_ROUND] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// A chip that implements the BLAKE3 compression function.
+///
+/// Each row compresses the message block at the second argument of a syscall into the 16 words
+/// of the state at its first argument: the chaining value, the first four words of the IV, the
+/// counter, the block length and the flags. The state is overwritten with the 16 words of the
+/// extended output of the compression.
+#[derive(Default)]
+pub struct Blake3CompressChip;
+
+impl Blake3CompressChip {
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    use super::STATE_SIZE;
+
+    const STATE_PTR: u32 = 100;
+    const MSG_PTR: u32 = 200;
+
+    const IV: [u32; 8] = [
+        0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB,
+        0x5BE0CD19,
+    ];
+
+    /// A program compressing `msg` into `state`, at [`MSG_PTR`] and [`STATE_PTR`].
+    pub fn blake3_compress_program(state: [u32; STATE_SIZE], msg: [u32; STATE_SIZE]) -> Program {
+        let mut instructions = vec![];
+        for (ptr, words) in [(STATE_PTR, state), (MSG_PTR, msg)] {
+            for (i, word) in words.into_iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::BLAKE3_COMPRESS as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, STATE_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, MSG_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    pub fn test_blake3_compress_program_execute() {
+        utils::setup_logger();
+
+        // The only block of the empty input: an empty root chunk.
+        let mut state = [0; STATE_SIZE];
+        state[..8].copy_from_slice(&IV);
+        state[8..12].copy_from_slice(&IV[..4]);
+        // The CHUNK_START, CHUNK_END and ROOT flags.
+        state[15] = 1 | 2 | 8;
+        let program = blake3_compress_program(state, [0; STATE_SIZE]);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let hash = (0..8)
+            .flat_map(|i| runtime.state.memory.get(STATE_PTR + 4 * i).unwrap().value.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hex::encode(hash),
+            "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+        );
+    }
+
+    #[test]
+    fn test_blake3_compress_prove_babybear() {
+        utils::setup_logger();
+
+        let state = core::array::from_fn(|i| i as u32);
+        let msg = core::array::from_fn(|i| 0x01010101 * i as u32);
+        let program = blake3_compress_program(state, msg);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
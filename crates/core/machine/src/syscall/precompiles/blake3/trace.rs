@@ -0,0 +1,129 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{events::ByteRecord, ExecutionRecord, Program};
+use sp1_stark::air::MachineAir;
+
+use crate::utils::pad_rows;
+
+use super::{
+    columns::{Blake3CompressCols, GFunctionCols, NUM_BLAKE3_COMPRESS_COLS},
+    Blake3CompressChip, G_INDICES, MSG_SCHEDULE, STATE_SIZE,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Blake3CompressChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Blake3Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut new_byte_lookup_events = Vec::new();
+
+        let mut rows = input
+            .blake3_compress_events
+            .iter()
+            .map(|event| {
+                let mut row = [F::zero(); NUM_BLAKE3_COMPRESS_COLS];
+                let cols: &mut Blake3CompressCols<F> = row.as_mut_slice().borrow_mut();
+                let blu = &mut new_byte_lookup_events;
+                let (shard, channel) = (event.shard, event.channel);
+
+                cols.is_real = F::one();
+                cols.shard = F::from_canonical_u32(shard);
+                cols.channel = F::from_canonical_u8(channel);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+                cols.msg_ptr = F::from_canonical_u32(event.msg_ptr);
+
+                for i in 0..STATE_SIZE {
+                    cols.msg_mem[i].populate(channel, event.msg_read_records[i], blu);
+                    cols.state_mem[i].populate(channel, event.state_write_records[i], blu);
+                }
+
+                // Run the rounds.
+                let mut v = event.pre_state;
+                for (round, schedule) in MSG_SCHEDULE.iter().enumerate() {
+                    for (j, &[a, b, c, d]) in G_INDICES.iter().enumerate() {
+                        let (mx, my) = (event.msg[schedule[2 * j]], event.msg[schedule[2 * j + 1]]);
+                        [v[a], v[b], v[c], v[d]] = populate_g(
+                            &mut cols.g[round][j],
+                            blu,
+                            shard,
+                            channel,
+                            [v[a], v[b], v[c], v[d]],
+                            mx,
+                            my,
+                        );
+                    }
+                }
+
+                // Compute the output.
+                for i in 0..STATE_SIZE {
+                    let y = if i < 8 { v[i + 8] } else { event.pre_state[i - 8] };
+                    let value = cols.output[i].populate(blu, shard, channel, v[i], y);
+                    debug_assert_eq!(value, event.post_state[i]);
+                }
+
+                row
+            })
+            .collect::<Vec<_>>();
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || [F::zero(); NUM_BLAKE3_COMPRESS_COLS]);
+
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_BLAKE3_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Blake3CompressCols<F> = trace.values
+                [i * NUM_BLAKE3_COMPRESS_COLS..(i + 1) * NUM_BLAKE3_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.blake3_compress_events.is_empty()
+    }
+}
+
+/// Populates the columns of a `G` function mixing `[a, b, c, d]` with `mx` and `my`, and returns
+/// the mixed words.
+fn populate_g<F: PrimeField32>(
+    cols: &mut GFunctionCols<F>,
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    [a, b, c, d]: [u32; 4],
+    mx: u32,
+    my: u32,
+) -> [u32; 4] {
+    let a = cols.a_plus_b.populate(blu, shard, channel, a, b);
+    let a = cols.a_plus_b_plus_mx.populate(blu, shard, channel, a, mx);
+    let d = cols.d_xor_a.populate(blu, shard, channel, d, a).rotate_right(16);
+    let c = cols.c_plus_d.populate(blu, shard, channel, c, d);
+    let b = cols.b_xor_c.populate(blu, shard, channel, b, c);
+    let b = cols.b_xor_c_rr_12.populate(blu, shard, channel, b, 12);
+
+    let a = cols.a_plus_b_2.populate(blu, shard, channel, a, b);
+    let a = cols.a_plus_b_plus_my.populate(blu, shard, channel, a, my);
+    let d = cols.d_xor_a_2.populate(blu, shard, channel, d, a).rotate_right(8);
+    let c = cols.c_plus_d_2.populate(blu, shard, channel, c, d);
+    let b = cols.b_xor_c_2.populate(blu, shard, channel, b, c);
+    let b = cols.b_xor_c_2_rr_7.populate(blu, shard, channel, b, 7);
+
+    [a, b, c, d]
+}
@@ -1,6 +1,12 @@
+pub mod aes;
+pub mod blake3;
 pub mod edwards;
 pub mod fptower;
 pub mod keccak256;
+pub mod poseidon2;
+pub mod ripemd160;
 pub mod sha256;
+pub mod sha512;
 pub mod uint256;
+pub mod uint_mod;
 pub mod weierstrass;
@@ -0,0 +1,174 @@
+use core::borrow::Borrow;
+use std::array;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::syscalls::SyscallCode;
+use sp1_primitives::RC_16_30_U32;
+use sp1_stark::air::SP1AirBuilder;
+
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::{value_as_limbs, MemoryCols},
+    operations::BabyBearWordRangeChecker,
+};
+
+use super::{
+    columns::{Poseidon2PermuteCols, NUM_POSEIDON2_PERMUTE_COLS},
+    external_linear_layer, internal_linear_layer, Poseidon2PermuteChip, NUM_EXTERNAL_ROUNDS,
+    NUM_INTERNAL_ROUNDS, WIDTH,
+};
+
+impl<F> BaseAir<F> for Poseidon2PermuteChip {
+    fn width(&self) -> usize {
+        NUM_POSEIDON2_PERMUTE_COLS
+    }
+}
+
+impl<AB> Air<AB> for Poseidon2PermuteChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &Poseidon2PermuteCols<AB::Var> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &Poseidon2PermuteCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        builder.assert_bool(local.is_real);
+
+        // The input of the permutation is the previous state in memory, each word read as a
+        // BabyBear element. The initial linear layer is applied to it before the first round.
+        let mut input: [AB::Expr; WIDTH] =
+            array::from_fn(|i| local.state_mem[i].prev_value.reduce::<AB>());
+        external_linear_layer(&mut input);
+        for i in 0..WIDTH {
+            builder
+                .when(local.is_real)
+                .assert_eq(local.external_rounds_state[0][i], input[i].clone());
+        }
+
+        // The rounds are constrained on every row, the padding rows permuting zeros.
+        for r in 0..NUM_EXTERNAL_ROUNDS / 2 {
+            eval_external_round(builder, local, r);
+        }
+        eval_internal_rounds(builder, local);
+        for r in NUM_EXTERNAL_ROUNDS / 2..NUM_EXTERNAL_ROUNDS {
+            eval_external_round(builder, local, r);
+        }
+
+        // The output is written to memory as canonical BabyBear elements.
+        for i in 0..WIDTH {
+            let value = *local.state_mem[i].value();
+            builder.when(local.is_real).assert_eq(value.reduce::<AB>(), local.output_state[i]);
+            BabyBearWordRangeChecker::<AB::F>::range_check(
+                builder,
+                value,
+                local.output_range_checks[i],
+                local.is_real.into(),
+            );
+        }
+        builder.slice_range_check_u8(
+            &value_as_limbs(&local.state_mem),
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Read and write the state.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into() + AB::Expr::one(),
+            local.state_ptr,
+            &local.state_mem,
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::POSEIDON2_PERMUTE.syscall_id()),
+            local.state_ptr,
+            AB::Expr::zero(),
+            local.is_real,
+        );
+    }
+}
+
+/// Constrains the external round `r`, from its state to the state of the next round.
+fn eval_external_round<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    local: &Poseidon2PermuteCols<AB::Var>,
+    r: usize,
+) {
+    let external_state = local.external_rounds_state[r];
+
+    // Add the round constants.
+    let round = if r < NUM_EXTERNAL_ROUNDS / 2 { r } else { r + NUM_INTERNAL_ROUNDS };
+    let add_rc: [AB::Expr; WIDTH] = array::from_fn(|i| {
+        external_state[i].into() + AB::F::from_wrapped_u32(RC_16_30_U32[round][i])
+    });
+
+    // Apply the sboxes, with a column for the cube to keep the degree at 3.
+    let mut state: [AB::Expr; WIDTH] = array::from_fn(|i| {
+        let sbox_deg_3 = local.external_rounds_sbox[r][i];
+        builder.assert_eq(sbox_deg_3, add_rc[i].clone() * add_rc[i].clone() * add_rc[i].clone());
+        sbox_deg_3.into() * sbox_deg_3.into() * add_rc[i].clone()
+    });
+
+    // Apply the linear layer.
+    external_linear_layer(&mut state);
+
+    let next_state = if r == NUM_EXTERNAL_ROUNDS / 2 - 1 {
+        local.internal_rounds_state
+    } else if r == NUM_EXTERNAL_ROUNDS - 1 {
+        local.output_state
+    } else {
+        local.external_rounds_state[r + 1]
+    };
+    for i in 0..WIDTH {
+        builder.assert_eq(next_state[i], state[i].clone());
+    }
+}
+
+/// Constrains the internal rounds, from their state to the state of the second half of the
+/// external rounds.
+fn eval_internal_rounds<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    local: &Poseidon2PermuteCols<AB::Var>,
+) {
+    let mut state: [AB::Expr; WIDTH] = array::from_fn(|i| local.internal_rounds_state[i].into());
+    for r in 0..NUM_INTERNAL_ROUNDS {
+        // Add the round constant to the first element of the state.
+        let round = r + NUM_EXTERNAL_ROUNDS / 2;
+        let add_rc = if r == 0 { state[0].clone() } else { local.internal_rounds_s0[r - 1].into() }
+            + AB::F::from_wrapped_u32(RC_16_30_U32[round][0]);
+
+        // Apply the sbox.
+        let sbox_deg_3 = local.internal_rounds_sbox[r];
+        builder.assert_eq(sbox_deg_3, add_rc.clone() * add_rc.clone() * add_rc.clone());
+        state[0] = sbox_deg_3.into() * sbox_deg_3.into() * add_rc;
+
+        // Apply the linear layer.
+        internal_linear_layer(&mut state);
+
+        if r < NUM_INTERNAL_ROUNDS - 1 {
+            builder.assert_eq(local.internal_rounds_s0[r], state[0].clone());
+        }
+    }
+
+    let external_state = local.external_rounds_state[NUM_EXTERNAL_ROUNDS / 2];
+    for i in 0..WIDTH {
+        builder.assert_eq(external_state[i], state[i].clone());
+    }
+}
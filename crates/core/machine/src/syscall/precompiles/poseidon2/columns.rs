@@ -0,0 +1,50 @@
+use core::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::{memory::MemoryWriteCols, operations::BabyBearWordRangeChecker};
+
+use super::{NUM_EXTERNAL_ROUNDS, NUM_INTERNAL_ROUNDS, WIDTH};
+
+/// Poseidon2PermuteCols is the column layout for the Poseidon2 permutation.
+///
+/// The permutation takes a single row. The outputs of the sboxes and of most of the internal rounds
+/// don't have columns, since the linear layers after them are inlined in the constraints.
+#[derive(AlignedBorrow)]
+#[repr(C)]
+pub(crate) struct Poseidon2PermuteCols<T> {
+    pub shard: T,
+    pub channel: T,
+    pub clk: T,
+    pub nonce: T,
+    pub state_ptr: T,
+
+    /// Memory columns for the state, whose previous values are the input of the permutation and
+    /// whose values are its output.
+    pub state_mem: [MemoryWriteCols<T>; WIDTH],
+
+    /// The range checks of the output words to canonical BabyBear elements.
+    pub output_range_checks: [BabyBearWordRangeChecker<T>; WIDTH],
+
+    /// The state at the start of each external round.
+    pub external_rounds_state: [[T; WIDTH]; NUM_EXTERNAL_ROUNDS],
+
+    /// The state at the start of the internal rounds.
+    pub internal_rounds_state: [T; WIDTH],
+
+    /// The first element of the state after each internal round but the last.
+    pub internal_rounds_s0: [T; NUM_INTERNAL_ROUNDS - 1],
+
+    /// The cubes of the inputs of the sboxes of the external rounds.
+    pub external_rounds_sbox: [[T; WIDTH]; NUM_EXTERNAL_ROUNDS],
+
+    /// The cubes of the inputs of the sboxes of the internal rounds.
+    pub internal_rounds_sbox: [T; NUM_INTERNAL_ROUNDS],
+
+    /// The output of the permutation.
+    pub output_state: [T; WIDTH],
+
+    pub is_real: T,
+}
+
+pub const NUM_POSEIDON2_PERMUTE_COLS: usize = size_of::<Poseidon2PermuteCols<u8>>();
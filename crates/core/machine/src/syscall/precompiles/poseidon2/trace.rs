@@ -0,0 +1,158 @@
+use std::borrow::BorrowMut;
+
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{ByteRecord, POSEIDON2_WIDTH},
+    ExecutionRecord, Program,
+};
+use sp1_primitives::RC_16_30_U32;
+use sp1_stark::air::MachineAir;
+
+use crate::utils::{pad_rows, words_to_bytes_le_vec};
+
+use super::{
+    columns::{Poseidon2PermuteCols, NUM_POSEIDON2_PERMUTE_COLS},
+    external_linear_layer, internal_linear_layer, Poseidon2PermuteChip, NUM_EXTERNAL_ROUNDS,
+    NUM_INTERNAL_ROUNDS, WIDTH,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Poseidon2PermuteChip {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Poseidon2Permute".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut new_byte_lookup_events = Vec::new();
+
+        let mut rows = input
+            .poseidon2_permute_events
+            .iter()
+            .map(|event| {
+                let mut row = [F::zero(); NUM_POSEIDON2_PERMUTE_COLS];
+                let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+
+                cols.is_real = F::one();
+                cols.shard = F::from_canonical_u32(event.shard);
+                cols.channel = F::from_canonical_u8(event.channel);
+                cols.clk = F::from_canonical_u32(event.clk);
+                cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+
+                for i in 0..POSEIDON2_WIDTH {
+                    cols.state_mem[i].populate(
+                        event.channel,
+                        event.state_write_records[i],
+                        &mut new_byte_lookup_events,
+                    );
+                    cols.output_range_checks[i].populate(event.post_state[i]);
+                }
+                new_byte_lookup_events.add_u8_range_checks(
+                    event.shard,
+                    event.channel,
+                    &words_to_bytes_le_vec(&event.post_state),
+                );
+
+                let output =
+                    Self::populate_permutation(cols, event.pre_state.map(F::from_wrapped_u32));
+                debug_assert_eq!(output.map(|x| x.as_canonical_u32()), event.post_state);
+
+                row
+            })
+            .collect::<Vec<_>>();
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        // The permutation is constrained on every row, so the padding rows permute zeros.
+        pad_rows(&mut rows, || {
+            let mut row = [F::zero(); NUM_POSEIDON2_PERMUTE_COLS];
+            let cols: &mut Poseidon2PermuteCols<F> = row.as_mut_slice().borrow_mut();
+            Self::populate_permutation(cols, [F::zero(); WIDTH]);
+            row
+        });
+
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_POSEIDON2_PERMUTE_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Poseidon2PermuteCols<F> = trace.values
+                [i * NUM_POSEIDON2_PERMUTE_COLS..(i + 1) * NUM_POSEIDON2_PERMUTE_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.poseidon2_permute_events.is_empty()
+    }
+}
+
+impl Poseidon2PermuteChip {
+    /// Populates the permutation columns of `cols` to permute `input`, and returns the output.
+    fn populate_permutation<F: PrimeField32>(
+        cols: &mut Poseidon2PermuteCols<F>,
+        input: [F; WIDTH],
+    ) -> [F; WIDTH] {
+        let mut state = input;
+        external_linear_layer(&mut state);
+
+        for r in 0..NUM_EXTERNAL_ROUNDS {
+            // The internal rounds run between the two halves of the external rounds.
+            if r == NUM_EXTERNAL_ROUNDS / 2 {
+                cols.internal_rounds_state = state;
+                state = Self::populate_internal_rounds(cols);
+            }
+            cols.external_rounds_state[r] = state;
+            state = Self::populate_external_round(cols, r);
+        }
+
+        cols.output_state = state;
+        state
+    }
+
+    /// Populates the sboxes of the external round `r`, and returns the state after it.
+    fn populate_external_round<F: PrimeField32>(
+        cols: &mut Poseidon2PermuteCols<F>,
+        r: usize,
+    ) -> [F; WIDTH] {
+        let round = if r < NUM_EXTERNAL_ROUNDS / 2 { r } else { r + NUM_INTERNAL_ROUNDS };
+        let mut state = cols.external_rounds_state[r];
+        for i in 0..WIDTH {
+            let add_rc = state[i] + F::from_wrapped_u32(RC_16_30_U32[round][i]);
+            let sbox_deg_3 = add_rc * add_rc * add_rc;
+            cols.external_rounds_sbox[r][i] = sbox_deg_3;
+            state[i] = sbox_deg_3 * sbox_deg_3 * add_rc;
+        }
+        external_linear_layer(&mut state);
+        state
+    }
+
+    /// Populates the columns of the internal rounds, and returns the state after them.
+    fn populate_internal_rounds<F: PrimeField32>(cols: &mut Poseidon2PermuteCols<F>) -> [F; WIDTH] {
+        let mut state = cols.internal_rounds_state;
+        for r in 0..NUM_INTERNAL_ROUNDS {
+            // Only the first element of the state goes through an sbox.
+            let round = r + NUM_EXTERNAL_ROUNDS / 2;
+            let add_rc = state[0] + F::from_wrapped_u32(RC_16_30_U32[round][0]);
+            let sbox_deg_3 = add_rc * add_rc * add_rc;
+            cols.internal_rounds_sbox[r] = sbox_deg_3;
+            state[0] = sbox_deg_3 * sbox_deg_3 * add_rc;
+            internal_linear_layer(&mut state);
+
+            if r < NUM_INTERNAL_ROUNDS - 1 {
+                cols.internal_rounds_s0[r] = state[0];
+            }
+        }
+        state
+    }
+}
@@ -0,0 +1,369 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::{
+    events::{
+        RIPEMD160_K_LEFT, RIPEMD160_K_RIGHT, RIPEMD160_NUM_STEPS, RIPEMD160_R_LEFT,
+        RIPEMD160_R_RIGHT, RIPEMD160_STATE_SIZE, RIPEMD160_S_LEFT, RIPEMD160_S_RIGHT,
+    },
+    syscalls::SyscallCode,
+};
+use sp1_stark::{
+    air::{BaseAirBuilder, SP1AirBuilder},
+    Word,
+};
+
+use super::{Ripemd160CompressChip, Ripemd160CompressCols, StepCols, NUM_RIPEMD160_COMPRESS_COLS};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{
+        Add4Operation, AddOperation, AndOperation, FixedRotateRightOperation, NotOperation,
+        OrOperation, XorOperation,
+    },
+    syscall::precompiles::sha512::eval_rounds,
+};
+
+impl<F> BaseAir<F> for Ripemd160CompressChip {
+    fn width(&self) -> usize {
+        NUM_RIPEMD160_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Ripemd160CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Ripemd160CompressCols<AB::Var> = (*local).borrow();
+        let next: &Ripemd160CompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Evaluate the control flags.
+        eval_rounds(
+            builder,
+            &local.step,
+            &next.step,
+            local.is_real,
+            next.is_real,
+            local.start,
+            local.end,
+        );
+
+        // Copy over the inputs, the state and the message block until the last step.
+        let last_step = local.step[RIPEMD160_NUM_STEPS - 1];
+        for (local_input, next_input) in [
+            (local.shard, next.shard),
+            (local.channel, next.channel),
+            (local.clk, next.clk),
+            (local.state_ptr, next.state_ptr),
+            (local.block_ptr, next.block_ptr),
+        ] {
+            builder.when_transition().when_not(last_step).assert_eq(local_input, next_input);
+        }
+        for (local_word, next_word) in
+            local.h_prev.iter().chain(&local.block).zip(next.h_prev.iter().chain(&next.block))
+        {
+            builder.when_transition().when_not(last_step).assert_word_eq(*local_word, *next_word);
+        }
+
+        // Read the message block on the first step.
+        for (word, mem) in local.block.iter().zip(local.block_mem.iter()) {
+            builder.when(local.start).assert_word_eq(*word, *mem.value());
+        }
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.block_ptr,
+            &local.block_mem,
+            local.start,
+        );
+
+        // The working variables of both lines on the first step are the state.
+        for line in [&local.left, &local.right] {
+            let state = [line.a, line.b, line.c, line.d, line.e];
+            for (word, h) in state.iter().zip(local.h_prev.iter()) {
+                builder.when(local.start).assert_word_eq(*word, *h);
+            }
+        }
+
+        // The flags of the rounds of sixteen steps.
+        let round: [AB::Expr; 5] = core::array::from_fn(|i| {
+            local.step[16 * i..16 * (i + 1)].iter().fold(AB::Expr::zero(), |acc, &flag| acc + flag)
+        });
+
+        // Run the steps of the lines, the right line using the boolean functions in the reverse
+        // order.
+        let left = eval_step(
+            builder,
+            local,
+            &local.left,
+            &round,
+            false,
+            RIPEMD160_K_LEFT,
+            &RIPEMD160_R_LEFT,
+            &RIPEMD160_S_LEFT,
+        );
+        let right = eval_step(
+            builder,
+            local,
+            &local.right,
+            &round,
+            true,
+            RIPEMD160_K_RIGHT,
+            &RIPEMD160_R_RIGHT,
+            &RIPEMD160_S_RIGHT,
+        );
+
+        // The working variables of the next step are the output of this one.
+        for (output, next_line) in [(left, &next.left), (right, &next.right)] {
+            let next_state = [next_line.a, next_line.b, next_line.c, next_line.d, next_line.e];
+            for (word, next_word) in output.iter().zip(next_state.iter()) {
+                builder
+                    .when_transition()
+                    .when(local.is_real - local.end)
+                    .assert_word_eq(*word, *next_word);
+            }
+        }
+
+        // On the last step, combine the outputs of the lines with the state and write it to
+        // memory.
+        for i in 0..RIPEMD160_STATE_SIZE {
+            let [first, second] = local.finalize_add[i];
+            AddOperation::<AB::F>::eval(
+                builder,
+                local.h_prev[(i + 1) % 5],
+                left[(i + 2) % 5],
+                first,
+                local.shard,
+                local.channel,
+                local.end.into(),
+            );
+            AddOperation::<AB::F>::eval(
+                builder,
+                first.value,
+                right[(i + 3) % 5],
+                second,
+                local.shard,
+                local.channel,
+                local.end.into(),
+            );
+            let mem = &local.h_mem[i];
+            builder.when(local.end).assert_word_eq(*mem.prev_value(), local.h_prev[i]);
+            builder.when(local.end).assert_word_eq(*mem.value(), second.value);
+        }
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::one(),
+            local.state_ptr,
+            &local.h_mem,
+            local.end,
+        );
+
+        // Receive syscall event in the first row of each syscall.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::RIPEMD160_COMPRESS.syscall_id()),
+            local.state_ptr,
+            local.block_ptr,
+            local.start,
+        );
+    }
+}
+
+/// Constrains a step of a line, with the flags of the rounds, whether the line uses the boolean
+/// functions in the reverse order, the constants of the rounds and the message indices and
+/// rotations of the steps, and returns the next working variables.
+#[allow(clippy::too_many_arguments)]
+fn eval_step<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    local: &Ripemd160CompressCols<AB::Var>,
+    cols: &StepCols<AB::Var>,
+    round: &[AB::Expr; 5],
+    reversed: bool,
+    k: [u32; 5],
+    r: &[usize; RIPEMD160_NUM_STEPS],
+    s: &[u32; RIPEMD160_NUM_STEPS],
+) -> [Word<AB::Var>; RIPEMD160_STATE_SIZE] {
+    let (shard, channel, is_real) = (local.shard, local.channel, local.is_real);
+    let (b, c, d) = (cols.b, cols.c, cols.d);
+
+    // Constrain `x` to be the message word of the step, and `k` the constant of the round.
+    for byte in 0..4 {
+        let x =
+            local.step.iter().zip(r).fold(AB::Expr::zero(), |acc, (&flag, &index)| {
+                acc + flag * local.block[index][byte]
+            });
+        builder.when(is_real).assert_eq(cols.x[byte], x);
+
+        let k = round.iter().cloned().zip(k).fold(AB::Expr::zero(), |acc, (flag, k)| {
+            acc + flag * AB::F::from_canonical_u8(k.to_le_bytes()[byte])
+        });
+        builder.when(is_real).assert_eq(cols.k[byte], k);
+    }
+
+    // f1 := b xor c xor d
+    XorOperation::<AB::F>::eval(builder, b, c, cols.b_xor_c, shard, channel, is_real);
+    XorOperation::<AB::F>::eval(builder, cols.b_xor_c.value, d, cols.f1, shard, channel, is_real);
+
+    // f2 := (b and c) xor ((not b) and d)
+    AndOperation::<AB::F>::eval(builder, b, c, cols.b_and_c, shard, channel, is_real);
+    NotOperation::<AB::F>::eval(builder, b, cols.b_not, shard, channel, is_real);
+    AndOperation::<AB::F>::eval(
+        builder,
+        cols.b_not.value,
+        d,
+        cols.b_not_and_d,
+        shard,
+        channel,
+        is_real,
+    );
+    XorOperation::<AB::F>::eval(
+        builder,
+        cols.b_and_c.value,
+        cols.b_not_and_d.value,
+        cols.f2,
+        shard,
+        channel,
+        is_real,
+    );
+
+    // f3 := (b or (not c)) xor d
+    NotOperation::<AB::F>::eval(builder, c, cols.c_not, shard, channel, is_real);
+    OrOperation::<AB::F>::eval(
+        builder,
+        b,
+        cols.c_not.value,
+        cols.b_or_c_not,
+        shard,
+        channel,
+        is_real,
+    );
+    XorOperation::<AB::F>::eval(
+        builder,
+        cols.b_or_c_not.value,
+        d,
+        cols.f3,
+        shard,
+        channel,
+        is_real,
+    );
+
+    // f4 := (b and d) xor (c and (not d))
+    AndOperation::<AB::F>::eval(builder, b, d, cols.b_and_d, shard, channel, is_real);
+    NotOperation::<AB::F>::eval(builder, d, cols.d_not, shard, channel, is_real);
+    AndOperation::<AB::F>::eval(
+        builder,
+        c,
+        cols.d_not.value,
+        cols.c_and_d_not,
+        shard,
+        channel,
+        is_real,
+    );
+    XorOperation::<AB::F>::eval(
+        builder,
+        cols.b_and_d.value,
+        cols.c_and_d_not.value,
+        cols.f4,
+        shard,
+        channel,
+        is_real,
+    );
+
+    // f5 := b xor (c or (not d))
+    OrOperation::<AB::F>::eval(
+        builder,
+        c,
+        cols.d_not.value,
+        cols.c_or_d_not,
+        shard,
+        channel,
+        is_real,
+    );
+    XorOperation::<AB::F>::eval(
+        builder,
+        b,
+        cols.c_or_d_not.value,
+        cols.f5,
+        shard,
+        channel,
+        is_real,
+    );
+
+    // Select the boolean function of the round.
+    let fs = [cols.f1.value, cols.f2.value, cols.f3.value, cols.f4.value, cols.f5.value];
+    for byte in 0..4 {
+        let f = fs.iter().enumerate().fold(AB::Expr::zero(), |acc, (i, f)| {
+            let uses_f = if reversed { &round[4 - i] } else { &round[i] };
+            acc + uses_f.clone() * f[byte]
+        });
+        builder.when(is_real).assert_eq(cols.f[byte], f);
+    }
+
+    // a + f + x + k
+    Add4Operation::<AB::F>::eval(
+        builder, cols.a, cols.f, cols.x, cols.k, shard, channel, is_real, cols.sum,
+    );
+
+    // Rotate the sum left by the rotation of the step, through its bits.
+    let two = AB::F::from_canonical_u32(2);
+    for &bit in cols.sum_bits.iter() {
+        builder.assert_bool(bit);
+    }
+    for byte in 0..4 {
+        let bits = &cols.sum_bits[8 * byte..8 * (byte + 1)];
+        let value = bits.iter().rev().fold(AB::Expr::zero(), |acc, &bit| acc * two + bit);
+        builder.when(is_real).assert_eq(cols.sum.value[byte], value);
+    }
+    let uses_rotation: [(usize, AB::Expr); 11] = core::array::from_fn(|i| {
+        let rotation = i + 5;
+        let flag = local
+            .step
+            .iter()
+            .zip(s)
+            .filter(|(_, &step_rotation)| step_rotation as usize == rotation)
+            .fold(AB::Expr::zero(), |acc, (&flag, _)| acc + flag);
+        (rotation, flag)
+    });
+    let rotated_bits: [AB::Expr; 32] = core::array::from_fn(|i| {
+        uses_rotation.iter().fold(AB::Expr::zero(), |acc, (rotation, flag)| {
+            acc + flag.clone() * cols.sum_bits[(i + 32 - rotation) % 32]
+        })
+    });
+    for byte in 0..4 {
+        let value = rotated_bits[8 * byte..8 * (byte + 1)]
+            .iter()
+            .rev()
+            .fold(AB::Expr::zero(), |acc, bit| acc * two + bit.clone());
+        builder.when(is_real).assert_eq(cols.sum_rotated[byte], value);
+    }
+
+    // t := rol_s(a + f + x + k) + e
+    AddOperation::<AB::F>::eval(
+        builder,
+        cols.sum_rotated,
+        cols.e,
+        cols.t,
+        shard,
+        channel,
+        is_real.into(),
+    );
+
+    // c rotateleft 10
+    FixedRotateRightOperation::<AB::F>::eval(builder, c, 22, cols.c_rl_10, shard, channel, is_real);
+
+    [cols.e, cols.t.value, b, cols.c_rl_10.value, d]
+}
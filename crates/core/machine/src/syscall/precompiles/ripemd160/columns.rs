@@ -0,0 +1,120 @@
+use std::mem::size_of;
+
+use sp1_core_executor::events::{RIPEMD160_BLOCK_SIZE, RIPEMD160_NUM_STEPS, RIPEMD160_STATE_SIZE};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{
+        Add4Operation, AddOperation, AndOperation, FixedRotateRightOperation, NotOperation,
+        OrOperation, XorOperation,
+    },
+};
+
+pub const NUM_RIPEMD160_COMPRESS_COLS: usize = size_of::<Ripemd160CompressCols<u8>>();
+
+/// A set of columns needed to compute the RIPEMD-160 compression function.
+///
+/// Each ripemd160 compress syscall is processed over 80 rows, one per step of the two lines of the
+/// compression. The working variables of both lines on the first row are the state, and those of
+/// each next row are the output of the steps of the previous one. The message block is read on
+/// the first row and carried over to the others.
+#[derive(AlignedBorrow, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Ripemd160CompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub state_ptr: T,
+    pub block_ptr: T,
+
+    /// Which step of the compression we are currently processing.
+    pub step: [T; RIPEMD160_NUM_STEPS],
+
+    /// Whether the current row is the first of a syscall and is real.
+    pub start: T,
+    /// Whether the current row is the last of a syscall and is real.
+    pub end: T,
+
+    /// The state before the compression.
+    pub h_prev: [Word<T>; RIPEMD160_STATE_SIZE],
+
+    /// The message block.
+    pub block: [Word<T>; RIPEMD160_BLOCK_SIZE],
+    /// On the first row, the reads of the message block.
+    pub block_mem: [MemoryReadCols<T>; RIPEMD160_BLOCK_SIZE],
+
+    /// The steps of the left and the right lines.
+    pub left: StepCols<T>,
+    pub right: StepCols<T>,
+
+    /// On the last row, the sums of a word of the state and of a word of each line's output.
+    pub finalize_add: [[AddOperation<T>; 2]; RIPEMD160_STATE_SIZE],
+    /// On the last row, the writes of the compressed state.
+    pub h_mem: [MemoryWriteCols<T>; RIPEMD160_STATE_SIZE],
+
+    pub is_real: T,
+}
+
+/// The columns of a step of a line of the compression.
+///
+/// The five boolean functions of the rounds are computed on every row, and the one of the current
+/// round is selected by the step flags.
+#[derive(AlignedBorrow, Default, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct StepCols<T> {
+    /// The working variables.
+    pub a: Word<T>,
+    pub b: Word<T>,
+    pub c: Word<T>,
+    pub d: Word<T>,
+    pub e: Word<T>,
+
+    /// The message word of the step.
+    pub x: Word<T>,
+    /// The constant of the round.
+    pub k: Word<T>,
+
+    pub b_xor_c: XorOperation<T>,
+    /// `f1 := b xor c xor d`.
+    pub f1: XorOperation<T>,
+
+    pub b_and_c: AndOperation<T>,
+    pub b_not: NotOperation<T>,
+    pub b_not_and_d: AndOperation<T>,
+    /// `f2 := (b and c) or ((not b) and d)`, as a xor since the two sides are disjoint.
+    pub f2: XorOperation<T>,
+
+    pub c_not: NotOperation<T>,
+    pub b_or_c_not: OrOperation<T>,
+    /// `f3 := (b or (not c)) xor d`.
+    pub f3: XorOperation<T>,
+
+    pub b_and_d: AndOperation<T>,
+    pub d_not: NotOperation<T>,
+    pub c_and_d_not: AndOperation<T>,
+    /// `f4 := (b and d) or (c and (not d))`, as a xor since the two sides are disjoint.
+    pub f4: XorOperation<T>,
+
+    pub c_or_d_not: OrOperation<T>,
+    /// `f5 := b xor (c or (not d))`.
+    pub f5: XorOperation<T>,
+
+    /// The boolean function of the round.
+    pub f: Word<T>,
+
+    /// `a + f + x + k`.
+    pub sum: Add4Operation<T>,
+    /// The bits of the sum, to rotate it by the rotation of the step.
+    pub sum_bits: [T; 32],
+    /// The sum rotated left by the rotation of the step.
+    pub sum_rotated: Word<T>,
+
+    /// `t := rol_s(a + f + x + k) + e`, the next value of `b`.
+    pub t: AddOperation<T>,
+    /// `c rotateleft 10`, the next value of `d`.
+    pub c_rl_10: FixedRotateRightOperation<T>,
+}
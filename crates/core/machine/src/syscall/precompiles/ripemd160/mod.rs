@@ -0,0 +1,91 @@
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+
+/// Implements the RIPEMD-160 compression function. The inputs to the syscall are a pointer to the
+/// 5 word state and a pointer to the 16 word message block, and the state is overwritten with the
+/// compressed state.
+///
+/// In the AIR, each RIPEMD-160 compress syscall takes up 80 rows, each doing a step of both lines
+/// of the compression. The first row also reads the message block, and the last row combines the
+/// outputs of the lines with the state and writes the result to memory.
+#[derive(Default)]
+pub struct Ripemd160CompressChip;
+
+impl Ripemd160CompressChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use sp1_core_executor::{
+        events::RIPEMD160_STATE_SIZE, syscalls::SyscallCode, Executor, Instruction, Opcode, Program,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const STATE_PTR: u32 = 100;
+    const BLOCK_PTR: u32 = 200;
+
+    /// The initial state of RIPEMD-160.
+    const IV: [u32; RIPEMD160_STATE_SIZE] =
+        [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+    /// A program hashing `"abc"`: it stores its padded block at [`BLOCK_PTR`] and the initial state
+    /// at [`STATE_PTR`], and compresses the block.
+    pub fn ripemd160_abc_program() -> Program {
+        let mut block = [0u32; 16];
+        block[0] = 0x80636261;
+        block[14] = 24;
+
+        let mut instructions = vec![];
+        for (ptr, words) in [(STATE_PTR, &IV[..]), (BLOCK_PTR, &block[..])] {
+            for (i, &word) in words.iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(Opcode::ADD, 30, 0, ptr + i as u32 * 4, false, true),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(
+                Opcode::ADD,
+                5,
+                0,
+                SyscallCode::RIPEMD160_COMPRESS as u32,
+                false,
+                true,
+            ),
+            Instruction::new(Opcode::ADD, 10, 0, STATE_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, BLOCK_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_ripemd160_compress_program_execute() {
+        utils::setup_logger();
+        let program = ripemd160_abc_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let hash = (0..RIPEMD160_STATE_SIZE as u32)
+            .flat_map(|i| runtime.state.memory.get(STATE_PTR + 4 * i).unwrap().value.to_le_bytes())
+            .collect::<Vec<_>>();
+        assert_eq!(hex::encode(hash), "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc");
+    }
+
+    #[test]
+    fn test_ripemd160_compress_prove() {
+        utils::setup_logger();
+        let program = ripemd160_abc_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
@@ -0,0 +1,226 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{
+        ripemd160_f, ByteLookupEvent, ByteRecord, Ripemd160CompressEvent, RIPEMD160_K_LEFT,
+        RIPEMD160_K_RIGHT, RIPEMD160_NUM_STEPS, RIPEMD160_R_LEFT, RIPEMD160_R_RIGHT,
+        RIPEMD160_STATE_SIZE, RIPEMD160_S_LEFT, RIPEMD160_S_RIGHT,
+    },
+    ExecutionRecord, Program,
+};
+use sp1_stark::{air::MachineAir, Word};
+
+use super::{Ripemd160CompressChip, Ripemd160CompressCols, StepCols, NUM_RIPEMD160_COMPRESS_COLS};
+use crate::{syscall::precompiles::sha512::populate_rounds, utils::pad_rows};
+
+impl<F: PrimeField32> MachineAir<F> for Ripemd160CompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Ripemd160Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+        for event in input.ripemd160_compress_events.iter() {
+            self.event_to_rows(event, &mut Some(&mut rows), &mut Vec::new());
+        }
+
+        let num_real_rows = rows.len();
+        pad_rows(&mut rows, || [F::zero(); NUM_RIPEMD160_COMPRESS_COLS]);
+
+        // Set the step columns for the padded rows.
+        for (i, row) in rows.iter_mut().enumerate().skip(num_real_rows) {
+            let cols: &mut Ripemd160CompressCols<F> = row.as_mut_slice().borrow_mut();
+            populate_rounds(&mut cols.step, i);
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_RIPEMD160_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Ripemd160CompressCols<F> = trace.values
+                [i * NUM_RIPEMD160_COMPRESS_COLS..(i + 1) * NUM_RIPEMD160_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.ripemd160_compress_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .ripemd160_compress_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.ripemd160_compress_events.is_empty()
+    }
+}
+
+impl Ripemd160CompressChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Ripemd160CompressEvent,
+        rows: &mut Option<&mut Vec<[F; NUM_RIPEMD160_COMPRESS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+        let channel = event.channel;
+
+        let mut left = event.pre_state;
+        let mut right = event.pre_state;
+        for j in 0..RIPEMD160_NUM_STEPS {
+            let mut row = [F::zero(); NUM_RIPEMD160_COMPRESS_COLS];
+            let cols: &mut Ripemd160CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.state_ptr = F::from_canonical_u32(event.state_ptr);
+            cols.block_ptr = F::from_canonical_u32(event.block_ptr);
+            cols.is_real = F::one();
+            populate_rounds(&mut cols.step, j);
+            cols.start = F::from_bool(j == 0);
+            cols.end = F::from_bool(j == RIPEMD160_NUM_STEPS - 1);
+            cols.h_prev = event.pre_state.map(Word::from);
+            cols.block = event.block.map(Word::from);
+
+            if j == 0 {
+                for (mem, record) in cols.block_mem.iter_mut().zip(&event.block_read_records) {
+                    mem.populate(channel, *record, blu);
+                }
+            }
+
+            let round = j / 16;
+            left = populate_step(
+                &mut cols.left,
+                blu,
+                shard,
+                channel,
+                left,
+                round,
+                RIPEMD160_K_LEFT[round],
+                event.block[RIPEMD160_R_LEFT[j]],
+                RIPEMD160_S_LEFT[j],
+            );
+            right = populate_step(
+                &mut cols.right,
+                blu,
+                shard,
+                channel,
+                right,
+                4 - round,
+                RIPEMD160_K_RIGHT[round],
+                event.block[RIPEMD160_R_RIGHT[j]],
+                RIPEMD160_S_RIGHT[j],
+            );
+
+            if j == RIPEMD160_NUM_STEPS - 1 {
+                for i in 0..RIPEMD160_STATE_SIZE {
+                    let [first, second] = &mut cols.finalize_add[i];
+                    let sum = first.populate(
+                        blu,
+                        shard,
+                        channel,
+                        event.pre_state[(i + 1) % 5],
+                        left[(i + 2) % 5],
+                    );
+                    let sum = second.populate(blu, shard, channel, sum, right[(i + 3) % 5]);
+                    debug_assert_eq!(sum, event.post_state[i]);
+                    cols.h_mem[i].populate(channel, event.state_write_records[i], blu);
+                }
+            }
+
+            if let Some(rows) = rows.as_mut() {
+                rows.push(row);
+            }
+        }
+    }
+}
+
+/// Populates a step of a line on the working variables `[a, b, c, d, e]`, with the boolean function
+/// `f_index` and the constant `k` of its round, the message word `x` and the rotation `s`, and
+/// returns the next working variables.
+#[allow(clippy::too_many_arguments)]
+fn populate_step<F: PrimeField32>(
+    cols: &mut StepCols<F>,
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    [a, b, c, d, e]: [u32; RIPEMD160_STATE_SIZE],
+    f_index: usize,
+    k: u32,
+    x: u32,
+    s: u32,
+) -> [u32; RIPEMD160_STATE_SIZE] {
+    cols.a = Word::from(a);
+    cols.b = Word::from(b);
+    cols.c = Word::from(c);
+    cols.d = Word::from(d);
+    cols.e = Word::from(e);
+    cols.x = Word::from(x);
+    cols.k = Word::from(k);
+
+    let b_xor_c = cols.b_xor_c.populate(blu, shard, channel, b, c);
+    let f1 = cols.f1.populate(blu, shard, channel, b_xor_c, d);
+
+    let b_and_c = cols.b_and_c.populate(blu, shard, channel, b, c);
+    let b_not = cols.b_not.populate(blu, shard, channel, b);
+    let b_not_and_d = cols.b_not_and_d.populate(blu, shard, channel, b_not, d);
+    let f2 = cols.f2.populate(blu, shard, channel, b_and_c, b_not_and_d);
+
+    let c_not = cols.c_not.populate(blu, shard, channel, c);
+    let b_or_c_not = cols.b_or_c_not.populate(blu, shard, channel, b, c_not);
+    let f3 = cols.f3.populate(blu, shard, channel, b_or_c_not, d);
+
+    let b_and_d = cols.b_and_d.populate(blu, shard, channel, b, d);
+    let d_not = cols.d_not.populate(blu, shard, channel, d);
+    let c_and_d_not = cols.c_and_d_not.populate(blu, shard, channel, c, d_not);
+    let f4 = cols.f4.populate(blu, shard, channel, b_and_d, c_and_d_not);
+
+    let c_or_d_not = cols.c_or_d_not.populate(blu, shard, channel, c, d_not);
+    let f5 = cols.f5.populate(blu, shard, channel, b, c_or_d_not);
+
+    let f = [f1, f2, f3, f4, f5][f_index];
+    debug_assert_eq!(f, ripemd160_f(f_index, b, c, d));
+    cols.f = Word::from(f);
+
+    let sum = cols.sum.populate(blu, shard, channel, a, f, x, k);
+    cols.sum_bits = core::array::from_fn(|i| F::from_canonical_u32((sum >> i) & 1));
+    let sum_rotated = sum.rotate_left(s);
+    cols.sum_rotated = Word::from(sum_rotated);
+
+    let t = cols.t.populate(blu, shard, channel, sum_rotated, e);
+    let c_rl_10 = cols.c_rl_10.populate(blu, shard, channel, c, 22);
+
+    [e, t, b, c_rl_10, d]
+}
@@ -0,0 +1,365 @@
+use core::borrow::Borrow;
+
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::syscalls::SyscallCode;
+use sp1_stark::{
+    air::{BaseAirBuilder, SP1AirBuilder},
+    Word,
+};
+
+use super::{
+    Sha512CompressChip, Sha512CompressCols, NUM_SHA512_COMPRESS_COLS, NUM_SHA512_COMPRESS_ROUNDS,
+    SHA512_COMPRESS_K,
+};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    memory::MemoryCols,
+    operations::{AddU64Operation, AndOperation, FixedRotateRightU64Operation, NotOperation},
+    syscall::precompiles::sha512::{eval_rounds, eval_u64_xor, round_index, u64_value},
+};
+
+impl<F> BaseAir<F> for Sha512CompressChip {
+    fn width(&self) -> usize {
+        NUM_SHA512_COMPRESS_COLS
+    }
+}
+
+impl<AB> Air<AB> for Sha512CompressChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512CompressCols<AB::Var> = (*local).borrow();
+        let next: &Sha512CompressCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Evaluate the control flags.
+        eval_rounds(
+            builder,
+            &local.round,
+            &next.round,
+            local.is_real,
+            next.is_real,
+            local.start,
+            local.end,
+        );
+
+        // Copy over the inputs and the hash state until the last round.
+        let last_round = local.round[NUM_SHA512_COMPRESS_ROUNDS - 1];
+        for (local_input, next_input) in [
+            (local.shard, next.shard),
+            (local.channel, next.channel),
+            (local.clk, next.clk),
+            (local.w_ptr, next.w_ptr),
+            (local.h_ptr, next.h_ptr),
+        ] {
+            builder.when_transition().when_not(last_round).assert_eq(local_input, next_input);
+        }
+        for (local_h, next_h) in local.h_prev.iter().zip(next.h_prev.iter()) {
+            for i in 0..2 {
+                builder
+                    .when_transition()
+                    .when_not(last_round)
+                    .assert_word_eq(local_h[i], next_h[i]);
+            }
+        }
+
+        // The working variables of the first round are the hash state.
+        let state = [local.a, local.b, local.c, local.d, local.e, local.f, local.g, local.h];
+        for (word, h) in state.iter().zip(local.h_prev.iter()) {
+            for i in 0..2 {
+                builder.when(local.start).assert_word_eq(word[i], h[i]);
+            }
+        }
+
+        // Constrain `k` to be the constant of the current round.
+        for byte in 0..8 {
+            let k = local
+                .round
+                .iter()
+                .zip(SHA512_COMPRESS_K)
+                .fold(AB::Expr::zero(), |acc, (&flag, k)| {
+                    acc + flag * AB::F::from_canonical_u8(k.to_le_bytes()[byte])
+                });
+            builder.assert_eq(local.k[byte / 4][byte % 4], k);
+        }
+
+        // Read w[i].
+        let i = round_index::<AB, NUM_SHA512_COMPRESS_ROUNDS>(&local.round);
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.w_ptr + i * AB::F::from_canonical_u32(8),
+            &local.w_i,
+            local.is_real,
+        );
+
+        // Compute `S1`.
+        for (rotation, cols) in [(14, local.e_rr_14), (18, local.e_rr_18), (41, local.e_rr_41)] {
+            FixedRotateRightU64Operation::<AB::F>::eval(
+                builder,
+                local.e,
+                rotation,
+                cols,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+        // (e rightrotate 14) xor (e rightrotate 18)
+        eval_u64_xor(
+            builder,
+            local.e_rr_14.value,
+            local.e_rr_18.value,
+            local.s1_intermediate,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // S1 := ((e rightrotate 14) xor (e rightrotate 18)) xor (e rightrotate 41)
+        eval_u64_xor(
+            builder,
+            local.s1_intermediate.map(|xor| xor.value),
+            local.e_rr_41.value,
+            local.s1,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        let s1 = local.s1.map(|xor| xor.value);
+
+        // Compute `ch`.
+        for i in 0..2 {
+            // e and f
+            AndOperation::<AB::F>::eval(
+                builder,
+                local.e[i],
+                local.f[i],
+                local.e_and_f[i],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            // not e
+            NotOperation::<AB::F>::eval(
+                builder,
+                local.e[i],
+                local.e_not[i],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            // (not e) and g
+            AndOperation::<AB::F>::eval(
+                builder,
+                local.e_not[i].value,
+                local.g[i],
+                local.e_not_and_g[i],
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+        // ch := (e and f) xor ((not e) and g)
+        eval_u64_xor(
+            builder,
+            local.e_and_f.map(|and| and.value),
+            local.e_not_and_g.map(|and| and.value),
+            local.ch,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        let ch = local.ch.map(|xor| xor.value);
+
+        // temp1 := h + S1 + ch + k[i] + w[i]
+        let mut temp1 = local.h;
+        for (cols, addend) in local.temp1.into_iter().zip([s1, ch, local.k, u64_value(&local.w_i)])
+        {
+            AddU64Operation::<AB::F>::eval(
+                builder,
+                temp1,
+                addend,
+                cols,
+                local.shard,
+                local.channel,
+                local.is_real.into(),
+            );
+            temp1 = cols.value;
+        }
+
+        // Compute `S0`.
+        for (rotation, cols) in [(28, local.a_rr_28), (34, local.a_rr_34), (39, local.a_rr_39)] {
+            FixedRotateRightU64Operation::<AB::F>::eval(
+                builder,
+                local.a,
+                rotation,
+                cols,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+        }
+        // (a rightrotate 28) xor (a rightrotate 34)
+        eval_u64_xor(
+            builder,
+            local.a_rr_28.value,
+            local.a_rr_34.value,
+            local.s0_intermediate,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // S0 := ((a rightrotate 28) xor (a rightrotate 34)) xor (a rightrotate 39)
+        eval_u64_xor(
+            builder,
+            local.s0_intermediate.map(|xor| xor.value),
+            local.a_rr_39.value,
+            local.s0,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Compute `maj`.
+        for (x, y, cols) in [
+            (local.a, local.b, local.a_and_b),
+            (local.a, local.c, local.a_and_c),
+            (local.b, local.c, local.b_and_c),
+        ] {
+            for i in 0..2 {
+                AndOperation::<AB::F>::eval(
+                    builder,
+                    x[i],
+                    y[i],
+                    cols[i],
+                    local.shard,
+                    local.channel,
+                    local.is_real,
+                );
+            }
+        }
+        // (a and b) xor (a and c)
+        eval_u64_xor(
+            builder,
+            local.a_and_b.map(|and| and.value),
+            local.a_and_c.map(|and| and.value),
+            local.maj_intermediate,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // maj := ((a and b) xor (a and c)) xor (b and c)
+        eval_u64_xor(
+            builder,
+            local.maj_intermediate.map(|xor| xor.value),
+            local.b_and_c.map(|and| and.value),
+            local.maj,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // temp2 := S0 + maj
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.s0.map(|xor| xor.value),
+            local.maj.map(|xor| xor.value),
+            local.temp2,
+            local.shard,
+            local.channel,
+            local.is_real.into(),
+        );
+
+        // d + temp1
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.d,
+            temp1,
+            local.d_add_temp1,
+            local.shard,
+            local.channel,
+            local.is_real.into(),
+        );
+
+        // temp1 + temp2
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            temp1,
+            local.temp2.value,
+            local.temp1_add_temp2,
+            local.shard,
+            local.channel,
+            local.is_real.into(),
+        );
+
+        // The working variables of the next round are the output of this one.
+        let next_state: [[Word<AB::Var>; 2]; 8] = [
+            local.temp1_add_temp2.value,
+            local.a,
+            local.b,
+            local.c,
+            local.d_add_temp1.value,
+            local.e,
+            local.f,
+            local.g,
+        ];
+        let next_row_state = [next.a, next.b, next.c, next.d, next.e, next.f, next.g, next.h];
+        for (word, next_word) in next_state.iter().zip(next_row_state.iter()) {
+            for i in 0..2 {
+                builder
+                    .when_transition()
+                    .when(local.is_real - local.end)
+                    .assert_word_eq(word[i], next_word[i]);
+            }
+        }
+
+        // On the last round, add the output to the hash state and write it to memory.
+        for (j, (h, word)) in local.h_prev.iter().zip(next_state).enumerate() {
+            AddU64Operation::<AB::F>::eval(
+                builder,
+                *h,
+                word,
+                local.finalize_add[j],
+                local.shard,
+                local.channel,
+                local.end.into(),
+            );
+            for i in 0..2 {
+                let mem = &local.h_mem[2 * j + i];
+                builder.when(local.end).assert_word_eq(*mem.prev_value(), h[i]);
+                builder
+                    .when(local.end)
+                    .assert_word_eq(*mem.value(), local.finalize_add[j].value[i]);
+            }
+        }
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk + AB::F::one(),
+            local.h_ptr,
+            &local.h_mem,
+            local.end,
+        );
+
+        // Receive syscall event in the first row of each syscall.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::SHA512_COMPRESS.syscall_id()),
+            local.w_ptr,
+            local.h_ptr,
+            local.start,
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+use sp1_stark::Word;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{
+        AddU64Operation, AndOperation, FixedRotateRightU64Operation, NotOperation, XorOperation,
+    },
+};
+
+use super::NUM_SHA512_COMPRESS_ROUNDS;
+
+pub const NUM_SHA512_COMPRESS_COLS: usize = size_of::<Sha512CompressCols<u8>>();
+
+/// A set of columns needed to compute the SHA-512 compression function.
+///
+/// Each sha512 compress syscall is processed over 80 rows, one per round of the compression. The
+/// working variables of the first row are the hash state, and those of each next row are the
+/// output of the round of the previous one. The last row adds the output of its round to the hash
+/// state, and writes the result back to memory.
+///
+/// The double words are given as their low and high words, and the bitwise operations on them are
+/// done a word at a time.
+#[derive(AlignedBorrow, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Sha512CompressCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub w_ptr: T,
+    pub h_ptr: T,
+
+    /// Which round of the compression we are currently processing.
+    pub round: [T; NUM_SHA512_COMPRESS_ROUNDS],
+
+    /// Whether the current row is the first of a syscall and is real.
+    pub start: T,
+    /// Whether the current row is the last of a syscall and is real.
+    pub end: T,
+
+    /// The hash state before the compression.
+    pub h_prev: [[Word<T>; 2]; 8],
+
+    pub a: [Word<T>; 2],
+    pub b: [Word<T>; 2],
+    pub c: [Word<T>; 2],
+    pub d: [Word<T>; 2],
+    pub e: [Word<T>; 2],
+    pub f: [Word<T>; 2],
+    pub g: [Word<T>; 2],
+    pub h: [Word<T>; 2],
+
+    /// The read of w[i].
+    pub w_i: [MemoryReadCols<T>; 2],
+
+    /// Current value of K[i].
+    pub k: [Word<T>; 2],
+
+    pub e_rr_14: FixedRotateRightU64Operation<T>,
+    pub e_rr_18: FixedRotateRightU64Operation<T>,
+    pub e_rr_41: FixedRotateRightU64Operation<T>,
+    pub s1_intermediate: [XorOperation<T>; 2],
+    /// `S1 := (e rightrotate 14) xor (e rightrotate 18) xor (e rightrotate 41)`.
+    pub s1: [XorOperation<T>; 2],
+
+    pub e_and_f: [AndOperation<T>; 2],
+    pub e_not: [NotOperation<T>; 2],
+    pub e_not_and_g: [AndOperation<T>; 2],
+    /// `ch := (e and f) xor ((not e) and g)`.
+    pub ch: [XorOperation<T>; 2],
+
+    /// `temp1 := h + S1 + ch + k[i] + w[i]`, summed from left to right.
+    pub temp1: [AddU64Operation<T>; 4],
+
+    pub a_rr_28: FixedRotateRightU64Operation<T>,
+    pub a_rr_34: FixedRotateRightU64Operation<T>,
+    pub a_rr_39: FixedRotateRightU64Operation<T>,
+    pub s0_intermediate: [XorOperation<T>; 2],
+    /// `S0 := (a rightrotate 28) xor (a rightrotate 34) xor (a rightrotate 39)`.
+    pub s0: [XorOperation<T>; 2],
+
+    pub a_and_b: [AndOperation<T>; 2],
+    pub a_and_c: [AndOperation<T>; 2],
+    pub b_and_c: [AndOperation<T>; 2],
+    pub maj_intermediate: [XorOperation<T>; 2],
+    /// `maj := (a and b) xor (a and c) xor (b and c)`.
+    pub maj: [XorOperation<T>; 2],
+
+    /// `temp2 := S0 + maj`.
+    pub temp2: AddU64Operation<T>,
+
+    /// The next value of `e` is `d + temp1`.
+    pub d_add_temp1: AddU64Operation<T>,
+    /// The next value of `a` is `temp1 + temp2`.
+    pub temp1_add_temp2: AddU64Operation<T>,
+
+    /// On the last row, the sums of the hash state and of the output of the last round.
+    pub finalize_add: [AddU64Operation<T>; 8],
+    /// On the last row, the writes of the compressed hash state.
+    pub h_mem: [MemoryWriteCols<T>; 16],
+
+    pub is_real: T,
+}
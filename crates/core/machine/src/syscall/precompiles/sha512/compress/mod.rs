@@ -0,0 +1,190 @@
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+
+/// The number of rounds of the compression, one per row.
+pub const NUM_SHA512_COMPRESS_ROUNDS: usize = 80;
+
+pub const SHA512_COMPRESS_K: [u64; NUM_SHA512_COMPRESS_ROUNDS] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+/// Implements the SHA-512 compress operation which loops over i = [0, 79] and modifies A-H in each
+/// iteration. The inputs to the syscall are a pointer to the 80 double word array W and a pointer
+/// to the 8 double word array H.
+///
+/// In the AIR, each SHA-512 compress syscall takes up 80 rows, one per iteration. The last row also
+/// adds the working variables to H and writes the result to memory.
+#[derive(Default)]
+pub struct Sha512CompressChip;
+
+impl Sha512CompressChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+#[cfg(test)]
+pub mod compress_tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const W_PTR: u32 = 104;
+    const H_PTR: u32 = 1000;
+
+    /// The initial hash state of SHA-512.
+    const IV: [u64; 8] = [
+        0x6a09e667f3bcc908,
+        0xbb67ae8584caa73b,
+        0x3c6ef372fe94f82b,
+        0xa54ff53a5f1d36f1,
+        0x510e527fade682d1,
+        0x9b05688c2b3e6c1f,
+        0x1f83d9abfb41bd6b,
+        0x5be0cd19137e2179,
+    ];
+
+    /// A program hashing `"abc"`: it stores its padded block at [`W_PTR`] and the initial hash
+    /// state at [`H_PTR`], extends the block into the message schedule and compresses it.
+    pub fn sha512_abc_program() -> Program {
+        let mut block = [0u64; 16];
+        block[0] = 0x6162638000000000;
+        block[15] = 24;
+
+        let mut instructions = vec![];
+        for (ptr, words) in [(W_PTR, &block[..]), (H_PTR, &IV[..])] {
+            for (i, &word) in words.iter().enumerate() {
+                for (j, half) in [word as u32, (word >> 32) as u32].into_iter().enumerate() {
+                    let addr = ptr + 8 * i as u32 + 4 * j as u32;
+                    instructions.extend(vec![
+                        Instruction::new(Opcode::ADD, 29, 0, half, false, true),
+                        Instruction::new(Opcode::ADD, 30, 0, addr, false, true),
+                        Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                    ]);
+                }
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::SHA512_EXTEND as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, W_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::SHA512_COMPRESS as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, W_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, H_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_sha512_compress_program_execute() {
+        utils::setup_logger();
+        let program = sha512_abc_program();
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        let hash = (0..8)
+            .flat_map(|i| {
+                let word = |addr: u32| runtime.state.memory.get(addr).unwrap().value as u64;
+                let addr = H_PTR + 8 * i;
+                (word(addr) | word(addr + 4) << 32).to_be_bytes()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            hex::encode(hash),
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a\
+             2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+    }
+
+    #[test]
+    fn test_sha512_compress_prove() {
+        utils::setup_logger();
+        let program = sha512_abc_program();
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
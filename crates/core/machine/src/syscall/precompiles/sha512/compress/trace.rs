@@ -0,0 +1,222 @@
+use std::borrow::BorrowMut;
+
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, Sha512CompressEvent},
+    ExecutionRecord, Program,
+};
+use sp1_stark::air::MachineAir;
+
+use super::{
+    Sha512CompressChip, Sha512CompressCols, NUM_SHA512_COMPRESS_COLS, NUM_SHA512_COMPRESS_ROUNDS,
+    SHA512_COMPRESS_K,
+};
+use crate::{
+    operations::{u64_to_words, AndOperation, NotOperation},
+    syscall::precompiles::sha512::{populate_rounds, populate_u64_read, populate_u64_xor},
+    utils::pad_rows,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Sha512CompressChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Sha512Compress".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+        for event in input.sha512_compress_events.iter() {
+            self.event_to_rows(event, &mut Some(&mut rows), &mut Vec::new());
+        }
+
+        let num_real_rows = rows.len();
+        pad_rows(&mut rows, || [F::zero(); NUM_SHA512_COMPRESS_COLS]);
+
+        // Set the round and k columns for the padded rows.
+        for (i, row) in rows.iter_mut().enumerate().skip(num_real_rows) {
+            let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+            populate_rounds(&mut cols.round, i);
+            cols.k = u64_to_words(SHA512_COMPRESS_K[i % NUM_SHA512_COMPRESS_ROUNDS]);
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_SHA512_COMPRESS_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Sha512CompressCols<F> = trace.values
+                [i * NUM_SHA512_COMPRESS_COLS..(i + 1) * NUM_SHA512_COMPRESS_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.sha512_compress_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .sha512_compress_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.sha512_compress_events.is_empty()
+    }
+}
+
+impl Sha512CompressChip {
+    #[allow(clippy::many_single_char_names)]
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Sha512CompressEvent,
+        rows: &mut Option<&mut Vec<[F; NUM_SHA512_COMPRESS_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+        let channel = event.channel;
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = event.h;
+        for j in 0..NUM_SHA512_COMPRESS_ROUNDS {
+            let mut row = [F::zero(); NUM_SHA512_COMPRESS_COLS];
+            let cols: &mut Sha512CompressCols<F> = row.as_mut_slice().borrow_mut();
+
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+            cols.h_ptr = F::from_canonical_u32(event.h_ptr);
+            cols.is_real = F::one();
+            populate_rounds(&mut cols.round, j);
+            cols.start = F::from_bool(j == 0);
+            cols.end = F::from_bool(j == NUM_SHA512_COMPRESS_ROUNDS - 1);
+            cols.h_prev = event.h.map(u64_to_words);
+
+            cols.a = u64_to_words(a);
+            cols.b = u64_to_words(b);
+            cols.c = u64_to_words(c);
+            cols.d = u64_to_words(d);
+            cols.e = u64_to_words(e);
+            cols.f = u64_to_words(f);
+            cols.g = u64_to_words(g);
+            cols.h = u64_to_words(h);
+
+            let w_i = populate_u64_read(&mut cols.w_i, channel, event.w_i_read_records[j], blu);
+            let k = SHA512_COMPRESS_K[j];
+            cols.k = u64_to_words(k);
+
+            let e_rr_14 = cols.e_rr_14.populate(blu, shard, channel, e, 14);
+            let e_rr_18 = cols.e_rr_18.populate(blu, shard, channel, e, 18);
+            let e_rr_41 = cols.e_rr_41.populate(blu, shard, channel, e, 41);
+            let s1_intermediate =
+                populate_u64_xor(&mut cols.s1_intermediate, blu, shard, channel, e_rr_14, e_rr_18);
+            let s1 = populate_u64_xor(&mut cols.s1, blu, shard, channel, s1_intermediate, e_rr_41);
+
+            let e_and_f = populate_u64_and(&mut cols.e_and_f, blu, shard, channel, e, f);
+            let e_not = populate_u64_not(&mut cols.e_not, blu, shard, channel, e);
+            let e_not_and_g =
+                populate_u64_and(&mut cols.e_not_and_g, blu, shard, channel, e_not, g);
+            let ch = populate_u64_xor(&mut cols.ch, blu, shard, channel, e_and_f, e_not_and_g);
+
+            let mut temp1 = h;
+            for (add, addend) in cols.temp1.iter_mut().zip([s1, ch, k, w_i]) {
+                temp1 = add.populate(blu, shard, channel, temp1, addend);
+            }
+
+            let a_rr_28 = cols.a_rr_28.populate(blu, shard, channel, a, 28);
+            let a_rr_34 = cols.a_rr_34.populate(blu, shard, channel, a, 34);
+            let a_rr_39 = cols.a_rr_39.populate(blu, shard, channel, a, 39);
+            let s0_intermediate =
+                populate_u64_xor(&mut cols.s0_intermediate, blu, shard, channel, a_rr_28, a_rr_34);
+            let s0 = populate_u64_xor(&mut cols.s0, blu, shard, channel, s0_intermediate, a_rr_39);
+
+            let a_and_b = populate_u64_and(&mut cols.a_and_b, blu, shard, channel, a, b);
+            let a_and_c = populate_u64_and(&mut cols.a_and_c, blu, shard, channel, a, c);
+            let b_and_c = populate_u64_and(&mut cols.b_and_c, blu, shard, channel, b, c);
+            let maj_intermediate =
+                populate_u64_xor(&mut cols.maj_intermediate, blu, shard, channel, a_and_b, a_and_c);
+            let maj =
+                populate_u64_xor(&mut cols.maj, blu, shard, channel, maj_intermediate, b_and_c);
+
+            let temp2 = cols.temp2.populate(blu, shard, channel, s0, maj);
+
+            let d_add_temp1 = cols.d_add_temp1.populate(blu, shard, channel, d, temp1);
+            let temp1_add_temp2 = cols.temp1_add_temp2.populate(blu, shard, channel, temp1, temp2);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d_add_temp1;
+            d = c;
+            c = b;
+            b = a;
+            a = temp1_add_temp2;
+
+            if j == NUM_SHA512_COMPRESS_ROUNDS - 1 {
+                let v = [a, b, c, d, e, f, g, h];
+                for i in 0..8 {
+                    cols.finalize_add[i].populate(blu, shard, channel, event.h[i], v[i]);
+                }
+                for (mem, record) in cols.h_mem.iter_mut().zip(event.h_write_records) {
+                    mem.populate(channel, record, blu);
+                }
+            }
+
+            if let Some(rows) = rows.as_mut() {
+                rows.push(row);
+            }
+        }
+    }
+}
+
+/// Populates the and of two double words, a word at a time, and returns it.
+fn populate_u64_and<F: PrimeField32>(
+    cols: &mut [AndOperation<F>; 2],
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let lo = cols[0].populate(blu, shard, channel, x as u32, y as u32);
+    let hi = cols[1].populate(blu, shard, channel, (x >> 32) as u32, (y >> 32) as u32);
+    lo as u64 | (hi as u64) << 32
+}
+
+/// Populates the not of a double word, a word at a time, and returns it.
+fn populate_u64_not<F: PrimeField32>(
+    cols: &mut [NotOperation<F>; 2],
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    x: u64,
+) -> u64 {
+    let lo = cols[0].populate(blu, shard, channel, x as u32);
+    let hi = cols[1].populate(blu, shard, channel, (x >> 32) as u32);
+    lo as u64 | (hi as u64) << 32
+}
@@ -0,0 +1,245 @@
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::AbstractField;
+use p3_matrix::Matrix;
+use sp1_core_executor::syscalls::SyscallCode;
+use sp1_stark::air::SP1AirBuilder;
+
+use super::{Sha512ExtendChip, Sha512ExtendCols, NUM_SHA512_EXTEND_COLS, NUM_SHA512_EXTEND_ROUNDS};
+use crate::{
+    air::{MemoryAirBuilder, WordAirBuilder},
+    operations::{AddU64Operation, FixedRotateRightU64Operation, FixedShiftRightU64Operation},
+    syscall::precompiles::sha512::{eval_rounds, eval_u64_xor, round_index, u64_value},
+};
+
+use core::borrow::Borrow;
+use sp1_stark::air::BaseAirBuilder;
+
+impl<F> BaseAir<F> for Sha512ExtendChip {
+    fn width(&self) -> usize {
+        NUM_SHA512_EXTEND_COLS
+    }
+}
+
+impl<AB> Air<AB> for Sha512ExtendChip
+where
+    AB: SP1AirBuilder,
+{
+    fn eval(&self, builder: &mut AB) {
+        // Initialize columns.
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Sha512ExtendCols<AB::Var> = (*local).borrow();
+        let next: &Sha512ExtendCols<AB::Var> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Evaluate the control flags.
+        eval_rounds(
+            builder,
+            &local.round,
+            &next.round,
+            local.is_real,
+            next.is_real,
+            local.start,
+            local.end,
+        );
+
+        // Copy over the inputs until the result has been computed (every 64 rows).
+        let last_round = local.round[NUM_SHA512_EXTEND_ROUNDS - 1];
+        builder.when_transition().when_not(last_round).assert_eq(local.shard, next.shard);
+        builder.when_transition().when_not(last_round).assert_eq(local.clk, next.clk);
+        builder.when_transition().when_not(last_round).assert_eq(local.channel, next.channel);
+        builder.when_transition().when_not(last_round).assert_eq(local.w_ptr, next.w_ptr);
+
+        // The iteration `i - 16` of the current row, and the address of `w[i - offset]`.
+        let j = round_index::<AB, NUM_SHA512_EXTEND_ROUNDS>(&local.round);
+        let clk = local.clk + j.clone();
+        let w_addr = |offset: u32| {
+            local.w_ptr
+                + (j.clone() + AB::F::from_canonical_u32(16) - AB::F::from_canonical_u32(offset))
+                    * AB::F::from_canonical_u32(8)
+        };
+
+        // Read w[i-15], w[i-2], w[i-16] and w[i-7].
+        for (offset, cols) in [
+            (15, &local.w_i_minus_15),
+            (2, &local.w_i_minus_2),
+            (16, &local.w_i_minus_16),
+            (7, &local.w_i_minus_7),
+        ] {
+            builder.eval_memory_access_slice(
+                local.shard,
+                local.channel,
+                clk.clone(),
+                w_addr(offset),
+                cols,
+                local.is_real,
+            );
+        }
+
+        // Compute `s0`.
+        let w_i_minus_15 = u64_value(&local.w_i_minus_15);
+        // w[i-15] rightrotate 1.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            1,
+            local.w_i_minus_15_rr_1,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-15] rightrotate 8.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            8,
+            local.w_i_minus_15_rr_8,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-15] rightshift 7.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_15,
+            7,
+            local.w_i_minus_15_rs_7,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8)
+        eval_u64_xor(
+            builder,
+            local.w_i_minus_15_rr_1.value,
+            local.w_i_minus_15_rr_8.value,
+            local.s0_intermediate,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)
+        let s0_intermediate = local.s0_intermediate.map(|xor| xor.value);
+        eval_u64_xor(
+            builder,
+            s0_intermediate,
+            local.w_i_minus_15_rs_7.value,
+            local.s0,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Compute `s1`.
+        let w_i_minus_2 = u64_value(&local.w_i_minus_2);
+        // w[i-2] rightrotate 19.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            19,
+            local.w_i_minus_2_rr_19,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-2] rightrotate 61.
+        FixedRotateRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            61,
+            local.w_i_minus_2_rr_61,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // w[i-2] rightshift 6.
+        FixedShiftRightU64Operation::<AB::F>::eval(
+            builder,
+            w_i_minus_2,
+            6,
+            local.w_i_minus_2_rs_6,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61)
+        eval_u64_xor(
+            builder,
+            local.w_i_minus_2_rr_19.value,
+            local.w_i_minus_2_rr_61.value,
+            local.s1_intermediate,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+        // s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)
+        let s1_intermediate = local.s1_intermediate.map(|xor| xor.value);
+        eval_u64_xor(
+            builder,
+            s1_intermediate,
+            local.w_i_minus_2_rs_6.value,
+            local.s1,
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // s2 := w[i-16] + s0 + w[i-7] + s1.
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            u64_value(&local.w_i_minus_16),
+            local.s0.map(|xor| xor.value),
+            local.s2_intermediate_0,
+            local.shard,
+            local.channel,
+            local.is_real.into(),
+        );
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.s2_intermediate_0.value,
+            u64_value(&local.w_i_minus_7),
+            local.s2_intermediate_1,
+            local.shard,
+            local.channel,
+            local.is_real.into(),
+        );
+        AddU64Operation::<AB::F>::eval(
+            builder,
+            local.s2_intermediate_1.value,
+            local.s1.map(|xor| xor.value),
+            local.s2,
+            local.shard,
+            local.channel,
+            local.is_real.into(),
+        );
+
+        // Write `s2` to `w[i]`.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            clk,
+            w_addr(0),
+            &local.w_i,
+            local.is_real,
+        );
+
+        let w_i = u64_value(&local.w_i);
+        builder.when(local.is_real).assert_word_eq(w_i[0], local.s2.value[0]);
+        builder.when(local.is_real).assert_word_eq(w_i[1], local.s2.value[1]);
+
+        // Receive syscall event in the first row of each syscall.
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            AB::F::from_canonical_u32(SyscallCode::SHA512_EXTEND.syscall_id()),
+            local.w_ptr,
+            AB::Expr::zero(),
+            local.start,
+        );
+    }
+}
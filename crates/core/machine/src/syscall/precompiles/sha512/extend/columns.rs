@@ -0,0 +1,74 @@
+use std::mem::size_of;
+
+use sp1_derive::AlignedBorrow;
+
+use crate::{
+    memory::{MemoryReadCols, MemoryWriteCols},
+    operations::{
+        AddU64Operation, FixedRotateRightU64Operation, FixedShiftRightU64Operation, XorOperation,
+    },
+};
+
+use super::NUM_SHA512_EXTEND_ROUNDS;
+
+pub const NUM_SHA512_EXTEND_COLS: usize = size_of::<Sha512ExtendCols<u8>>();
+
+/// A set of columns needed to compute one iteration of the SHA-512 message schedule extension.
+///
+/// The double words are given as their low and high words, and the bitwise operations on them are
+/// done a word at a time.
+#[derive(AlignedBorrow, Debug, Clone, Copy)]
+#[repr(C)]
+pub struct Sha512ExtendCols<T> {
+    /// Inputs.
+    pub shard: T,
+    pub channel: T,
+    pub nonce: T,
+    pub clk: T,
+    pub w_ptr: T,
+
+    /// Which iteration of the extension we are currently processing, for `i = 16 + j`.
+    pub round: [T; NUM_SHA512_EXTEND_ROUNDS],
+
+    /// Whether the current row is the first of a syscall and is real.
+    pub start: T,
+    /// Whether the current row is the last of a syscall and is real.
+    pub end: T,
+
+    /// Inputs to `s0`.
+    pub w_i_minus_15: [MemoryReadCols<T>; 2],
+    pub w_i_minus_15_rr_1: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_15_rr_8: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_15_rs_7: FixedShiftRightU64Operation<T>,
+    pub s0_intermediate: [XorOperation<T>; 2],
+
+    /// `s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift 7)`.
+    pub s0: [XorOperation<T>; 2],
+
+    /// Inputs to `s1`.
+    pub w_i_minus_2: [MemoryReadCols<T>; 2],
+    pub w_i_minus_2_rr_19: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_2_rr_61: FixedRotateRightU64Operation<T>,
+    pub w_i_minus_2_rs_6: FixedShiftRightU64Operation<T>,
+    pub s1_intermediate: [XorOperation<T>; 2],
+
+    /// `s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift 6)`.
+    pub s1: [XorOperation<T>; 2],
+
+    /// Inputs to `s2`.
+    pub w_i_minus_16: [MemoryReadCols<T>; 2],
+    pub w_i_minus_7: [MemoryReadCols<T>; 2],
+
+    /// `w[i-16] + s0`.
+    pub s2_intermediate_0: AddU64Operation<T>,
+    /// `w[i-16] + s0 + w[i-7]`.
+    pub s2_intermediate_1: AddU64Operation<T>,
+    /// `w[i] := w[i-16] + s0 + w[i-7] + s1`.
+    pub s2: AddU64Operation<T>,
+
+    /// Result.
+    pub w_i: [MemoryWriteCols<T>; 2],
+
+    /// Selector.
+    pub is_real: T,
+}
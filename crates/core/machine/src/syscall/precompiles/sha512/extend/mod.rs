@@ -0,0 +1,102 @@
+mod air;
+mod columns;
+mod trace;
+
+pub use columns::*;
+
+/// The number of iterations of the extension, one per row.
+pub const NUM_SHA512_EXTEND_ROUNDS: usize = 64;
+
+/// Implements the SHA-512 extension operation which loops over i = [16, 79] and modifies w[i] in
+/// each iteration. The only input to the syscall is the 8byte-aligned pointer to the w array of 80
+/// double words.
+///
+/// In the AIR, each SHA-512 extend syscall takes up 64 rows, where each row corresponds to a single
+/// iteration of the loop.
+#[derive(Default)]
+pub struct Sha512ExtendChip;
+
+impl Sha512ExtendChip {
+    pub const fn new() -> Self {
+        Self {}
+    }
+}
+
+pub fn sha512_extend(w: &mut [u64]) {
+    for i in 16..80 {
+        let s0 = w[i - 15].rotate_right(1) ^ w[i - 15].rotate_right(8) ^ (w[i - 15] >> 7);
+        let s1 = w[i - 2].rotate_right(19) ^ w[i - 2].rotate_right(61) ^ (w[i - 2] >> 6);
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+}
+
+#[cfg(test)]
+pub mod extend_tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    use super::sha512_extend;
+
+    const W_PTR: u32 = 104;
+
+    /// The first 16 double words of the message schedule of the tests.
+    fn message() -> Vec<u64> {
+        (0..16).map(|i| 0x0123456789abcdefu64.rotate_left(4 * i) ^ i as u64).collect()
+    }
+
+    /// A program storing `w` at [`W_PTR`], and extending it.
+    pub fn sha512_extend_program(w: &[u64]) -> Program {
+        let mut instructions = vec![];
+        for (i, &w_i) in w.iter().enumerate() {
+            for (j, word) in [w_i as u32, (w_i >> 32) as u32].into_iter().enumerate() {
+                instructions.extend(vec![
+                    Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                    Instruction::new(
+                        Opcode::ADD,
+                        30,
+                        0,
+                        W_PTR + 8 * i as u32 + 4 * j as u32,
+                        false,
+                        true,
+                    ),
+                    Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+                ]);
+            }
+        }
+        instructions.extend(vec![
+            Instruction::new(Opcode::ADD, 5, 0, SyscallCode::SHA512_EXTEND as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, W_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, 0, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    #[test]
+    fn test_sha512_extend_program_execute() {
+        utils::setup_logger();
+        let mut w = message();
+        w.resize(80, 0);
+        let program = sha512_extend_program(&w);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+
+        sha512_extend(&mut w);
+        for (i, w_i) in w.into_iter().enumerate() {
+            let word = |addr: u32| runtime.state.memory.get(addr).unwrap().value as u64;
+            let addr = W_PTR + 8 * i as u32;
+            assert_eq!(word(addr) | word(addr + 4) << 32, w_i);
+        }
+    }
+
+    #[test]
+    fn test_sha512_extend_prove() {
+        utils::setup_logger();
+        let mut w = message();
+        w.resize(80, 0);
+        let program = sha512_extend_program(&w);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+}
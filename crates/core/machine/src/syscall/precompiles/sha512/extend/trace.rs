@@ -0,0 +1,193 @@
+use hashbrown::HashMap;
+use itertools::Itertools;
+use p3_field::PrimeField32;
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use p3_maybe_rayon::prelude::{ParallelIterator, ParallelSlice};
+use sp1_core_executor::{
+    events::{ByteLookupEvent, ByteRecord, Sha512ExtendEvent},
+    ExecutionRecord, Program,
+};
+use sp1_stark::air::MachineAir;
+use std::borrow::BorrowMut;
+
+use super::{Sha512ExtendChip, Sha512ExtendCols, NUM_SHA512_EXTEND_COLS, NUM_SHA512_EXTEND_ROUNDS};
+use crate::{
+    syscall::precompiles::sha512::{populate_rounds, populate_u64_read, populate_u64_xor},
+    utils::pad_rows,
+};
+
+impl<F: PrimeField32> MachineAir<F> for Sha512ExtendChip {
+    type Record = ExecutionRecord;
+
+    type Program = Program;
+
+    fn name(&self) -> String {
+        "Sha512Extend".to_string()
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        _: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let mut rows = Vec::new();
+        for event in input.sha512_extend_events.iter() {
+            self.event_to_rows(event, &mut Some(&mut rows), &mut Vec::new());
+        }
+
+        let num_real_rows = rows.len();
+        pad_rows(&mut rows, || [F::zero(); NUM_SHA512_EXTEND_COLS]);
+        for (i, row) in rows.iter_mut().enumerate().skip(num_real_rows) {
+            let cols: &mut Sha512ExtendCols<F> = row.as_mut_slice().borrow_mut();
+            populate_rounds(&mut cols.round, i);
+        }
+
+        // Convert the trace to a row major matrix.
+        let mut trace = RowMajorMatrix::new(
+            rows.into_iter().flatten().collect::<Vec<_>>(),
+            NUM_SHA512_EXTEND_COLS,
+        );
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut Sha512ExtendCols<F> = trace.values
+                [i * NUM_SHA512_EXTEND_COLS..(i + 1) * NUM_SHA512_EXTEND_COLS]
+                .borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn generate_dependencies(&self, input: &Self::Record, output: &mut Self::Record) {
+        let chunk_size = std::cmp::max(input.sha512_extend_events.len() / num_cpus::get(), 1);
+
+        let blu_batches = input
+            .sha512_extend_events
+            .par_chunks(chunk_size)
+            .map(|events| {
+                let mut blu: HashMap<u32, HashMap<ByteLookupEvent, usize>> = HashMap::new();
+                events.iter().for_each(|event| {
+                    self.event_to_rows::<F>(event, &mut None, &mut blu);
+                });
+                blu
+            })
+            .collect::<Vec<_>>();
+
+        output.add_sharded_byte_lookup_events(blu_batches.iter().collect_vec());
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        !shard.sha512_extend_events.is_empty()
+    }
+}
+
+impl Sha512ExtendChip {
+    fn event_to_rows<F: PrimeField32>(
+        &self,
+        event: &Sha512ExtendEvent,
+        rows: &mut Option<&mut Vec<[F; NUM_SHA512_EXTEND_COLS]>>,
+        blu: &mut impl ByteRecord,
+    ) {
+        let shard = event.shard;
+        let channel = event.channel;
+        for j in 0..NUM_SHA512_EXTEND_ROUNDS {
+            let mut row = [F::zero(); NUM_SHA512_EXTEND_COLS];
+            let cols: &mut Sha512ExtendCols<F> = row.as_mut_slice().borrow_mut();
+            cols.is_real = F::one();
+            populate_rounds(&mut cols.round, j);
+            cols.start = F::from_bool(j == 0);
+            cols.end = F::from_bool(j == NUM_SHA512_EXTEND_ROUNDS - 1);
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.w_ptr = F::from_canonical_u32(event.w_ptr);
+
+            let w_i_minus_15 = populate_u64_read(
+                &mut cols.w_i_minus_15,
+                channel,
+                event.w_i_minus_15_reads[j],
+                blu,
+            );
+            let w_i_minus_2 =
+                populate_u64_read(&mut cols.w_i_minus_2, channel, event.w_i_minus_2_reads[j], blu);
+            let w_i_minus_16 = populate_u64_read(
+                &mut cols.w_i_minus_16,
+                channel,
+                event.w_i_minus_16_reads[j],
+                blu,
+            );
+            let w_i_minus_7 =
+                populate_u64_read(&mut cols.w_i_minus_7, channel, event.w_i_minus_7_reads[j], blu);
+
+            // `s0 := (w[i-15] rightrotate 1) xor (w[i-15] rightrotate 8) xor (w[i-15] rightshift
+            // 7)`.
+            let w_i_minus_15_rr_1 =
+                cols.w_i_minus_15_rr_1.populate(blu, shard, channel, w_i_minus_15, 1);
+            let w_i_minus_15_rr_8 =
+                cols.w_i_minus_15_rr_8.populate(blu, shard, channel, w_i_minus_15, 8);
+            let w_i_minus_15_rs_7 =
+                cols.w_i_minus_15_rs_7.populate(blu, shard, channel, w_i_minus_15, 7);
+            let s0_intermediate = populate_u64_xor(
+                &mut cols.s0_intermediate,
+                blu,
+                shard,
+                channel,
+                w_i_minus_15_rr_1,
+                w_i_minus_15_rr_8,
+            );
+            let s0 = populate_u64_xor(
+                &mut cols.s0,
+                blu,
+                shard,
+                channel,
+                s0_intermediate,
+                w_i_minus_15_rs_7,
+            );
+
+            // `s1 := (w[i-2] rightrotate 19) xor (w[i-2] rightrotate 61) xor (w[i-2] rightshift
+            // 6)`.
+            let w_i_minus_2_rr_19 =
+                cols.w_i_minus_2_rr_19.populate(blu, shard, channel, w_i_minus_2, 19);
+            let w_i_minus_2_rr_61 =
+                cols.w_i_minus_2_rr_61.populate(blu, shard, channel, w_i_minus_2, 61);
+            let w_i_minus_2_rs_6 =
+                cols.w_i_minus_2_rs_6.populate(blu, shard, channel, w_i_minus_2, 6);
+            let s1_intermediate = populate_u64_xor(
+                &mut cols.s1_intermediate,
+                blu,
+                shard,
+                channel,
+                w_i_minus_2_rr_19,
+                w_i_minus_2_rr_61,
+            );
+            let s1 = populate_u64_xor(
+                &mut cols.s1,
+                blu,
+                shard,
+                channel,
+                s1_intermediate,
+                w_i_minus_2_rs_6,
+            );
+
+            // Compute `s2`.
+            let s2_intermediate_0 =
+                cols.s2_intermediate_0.populate(blu, shard, channel, w_i_minus_16, s0);
+            let s2_intermediate_1 = cols.s2_intermediate_1.populate(
+                blu,
+                shard,
+                channel,
+                s2_intermediate_0,
+                w_i_minus_7,
+            );
+            cols.s2.populate(blu, shard, channel, s2_intermediate_1, s1);
+
+            cols.w_i[0].populate(channel, event.w_i_writes[j][0], blu);
+            cols.w_i[1].populate(channel, event.w_i_writes[j][1], blu);
+
+            if let Some(rows) = rows.as_mut() {
+                rows.push(row);
+            }
+        }
+    }
+}
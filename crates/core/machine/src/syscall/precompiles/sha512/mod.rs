@@ -0,0 +1,117 @@
+mod compress;
+mod extend;
+
+pub use compress::*;
+pub use extend::*;
+
+use p3_air::AirBuilder;
+use p3_field::{AbstractField, Field, PrimeField32};
+use sp1_core_executor::events::{ByteRecord, MemoryReadRecord};
+use sp1_stark::{
+    air::{BaseAirBuilder, SP1AirBuilder},
+    Word,
+};
+
+use crate::{
+    memory::{MemoryCols, MemoryReadCols},
+    operations::XorOperation,
+};
+
+/// Populates the one-hot round flags of the `row`-th row of a table whose syscalls each take up
+/// `N` rows.
+pub(crate) fn populate_rounds<F: Field, const N: usize>(round: &mut [F; N], row: usize) {
+    *round = [F::zero(); N];
+    round[row % N] = F::one();
+}
+
+/// Constrains the one-hot round flags of a table whose syscalls each take up `N` rows, one row per
+/// round, starting on the first row of the table.
+///
+/// The real rows are constrained to be whole syscalls, and `start` and `end` to flag the first and
+/// last rows of the real syscalls.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn eval_rounds<AB: SP1AirBuilder, const N: usize>(
+    builder: &mut AB,
+    round: &[AB::Var; N],
+    next_round: &[AB::Var; N],
+    is_real: AB::Var,
+    next_is_real: AB::Var,
+    start: AB::Var,
+    end: AB::Var,
+) {
+    // Each row is in exactly one round.
+    for &flag in round.iter() {
+        builder.assert_bool(flag);
+    }
+    builder.assert_one(round.iter().fold(AB::Expr::zero(), |acc, &flag| acc + flag));
+
+    // The rounds start on the first row, and cycle through the rows.
+    builder.when_first_row().assert_one(round[0]);
+    for i in 0..N {
+        builder.when_transition().assert_eq(next_round[(i + 1) % N], round[i]);
+    }
+
+    // All the rows of a syscall are real or padding, and the table doesn't end in the middle of
+    // one.
+    builder.assert_bool(is_real);
+    builder.when_transition().when_not(round[N - 1]).assert_eq(is_real, next_is_real);
+    builder.when_last_row().when(is_real).assert_one(round[N - 1]);
+
+    builder.assert_eq(start, round[0] * is_real);
+    builder.assert_eq(end, round[N - 1] * is_real);
+}
+
+/// The index of the round flagged by the one-hot `round`.
+pub(crate) fn round_index<AB: SP1AirBuilder, const N: usize>(round: &[AB::Var; N]) -> AB::Expr {
+    round
+        .iter()
+        .enumerate()
+        .fold(AB::Expr::zero(), |acc, (i, &flag)| acc + flag * AB::F::from_canonical_usize(i))
+}
+
+/// Populates the reads of the low and high words of a double word, and returns the double word.
+fn populate_u64_read<F: PrimeField32>(
+    cols: &mut [MemoryReadCols<F>; 2],
+    channel: u8,
+    records: [MemoryReadRecord; 2],
+    blu: &mut impl ByteRecord,
+) -> u64 {
+    cols[0].populate(channel, records[0], blu);
+    cols[1].populate(channel, records[1], blu);
+    records[0].value as u64 | (records[1].value as u64) << 32
+}
+
+/// Populates the xor of two double words, a word at a time, and returns it.
+fn populate_u64_xor<F: PrimeField32>(
+    cols: &mut [XorOperation<F>; 2],
+    blu: &mut impl ByteRecord,
+    shard: u32,
+    channel: u8,
+    x: u64,
+    y: u64,
+) -> u64 {
+    let lo = cols[0].populate(blu, shard, channel, x as u32, y as u32);
+    let hi = cols[1].populate(blu, shard, channel, (x >> 32) as u32, (y >> 32) as u32);
+    lo as u64 | (hi as u64) << 32
+}
+
+/// Constrains the xor of two double words, a word at a time.
+#[allow(clippy::too_many_arguments)]
+fn eval_u64_xor<AB: SP1AirBuilder>(
+    builder: &mut AB,
+    a: [Word<AB::Var>; 2],
+    b: [Word<AB::Var>; 2],
+    cols: [XorOperation<AB::Var>; 2],
+    shard: AB::Var,
+    channel: AB::Var,
+    is_real: AB::Var,
+) {
+    for i in 0..2 {
+        XorOperation::<AB::F>::eval(builder, a[i], b[i], cols[i], shard, channel, is_real);
+    }
+}
+
+/// The double word accessed by the memory accesses of its low and high words.
+fn u64_value<T: Copy>(cols: &[impl MemoryCols<T>; 2]) -> [Word<T>; 2] {
+    [*cols[0].value(), *cols[1].value()]
+}
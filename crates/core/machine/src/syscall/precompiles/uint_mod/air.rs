@@ -0,0 +1,367 @@
+use crate::{
+    memory::{value_as_limbs, MemoryReadCols, MemoryWriteCols},
+    operations::field::field_op::FieldOpCols,
+};
+
+use crate::{
+    air::MemoryAirBuilder,
+    operations::{field::range::FieldLtCols, IsZeroOperation},
+    utils::{limbs_from_access, limbs_from_prev_access, pad_rows, words_to_bytes_le_vec},
+};
+
+use generic_array::GenericArray;
+use num::{BigUint, Zero};
+use p3_air::{Air, AirBuilder, BaseAir};
+use p3_field::{AbstractField, PrimeField32};
+use p3_matrix::{dense::RowMajorMatrix, Matrix};
+use sp1_core_executor::{
+    events::{ByteRecord, FieldOperation},
+    syscalls::SyscallCode,
+    ExecutionRecord, Program,
+};
+use sp1_curves::{
+    params::{Limbs, NumLimbs, NumWords},
+    uint::{UintModField, UintType},
+};
+use sp1_derive::AlignedBorrow;
+use sp1_stark::air::{BaseAirBuilder, MachineAir, Polynomial, SP1AirBuilder};
+use std::{
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+    mem::size_of,
+};
+use typenum::Unsigned;
+
+/// The number of columns in the UintModCols.
+pub const fn num_uint_mod_cols<P: UintModField>() -> usize {
+    size_of::<UintModCols<u8, P>>()
+}
+
+/// A chip for the modular additions and multiplications of the wide integers of `P`, with the
+/// modulus given with each operation as for [`crate::syscall::precompiles::uint256`].
+pub struct UintModChip<P> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: UintModField> UintModChip<P> {
+    pub const fn new() -> Self {
+        Self { _marker: PhantomData }
+    }
+
+    /// The syscalls of the addition and the multiplication.
+    const fn syscall_codes() -> (SyscallCode, SyscallCode) {
+        match P::UINT_TYPE {
+            UintType::Uint320 => (SyscallCode::UINT320_ADD, SyscallCode::UINT320_MUL),
+            UintType::Uint384 => (SyscallCode::UINT384_ADD, SyscallCode::UINT384_MUL),
+        }
+    }
+}
+
+/// A set of columns for the UintMod operation.
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct UintModCols<T, P: UintModField> {
+    /// The shard number of the syscall.
+    pub shard: T,
+
+    /// The byte lookup channel.
+    pub channel: T,
+
+    /// The clock cycle of the syscall.
+    pub clk: T,
+
+    /// The nonce of the operation.
+    pub nonce: T,
+
+    /// The pointer to the first input.
+    pub x_ptr: T,
+
+    /// The pointer to the second input, which contains the y value and the modulus.
+    pub y_ptr: T,
+
+    /// Whether the operation is an addition.
+    pub is_add: T,
+
+    /// Whether the operation is a multiplication.
+    pub is_mul: T,
+
+    // Memory columns.
+    // x_memory is written to with the result, which is why it is of type MemoryWriteCols.
+    pub x_memory: GenericArray<MemoryWriteCols<T>, P::WordsFieldElement>,
+    pub y_memory: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
+    pub modulus_memory: GenericArray<MemoryReadCols<T>, P::WordsFieldElement>,
+
+    /// Columns for checking if modulus is zero. If it's zero, then use 2^bits as the effective
+    /// modulus.
+    pub modulus_is_zero: IsZeroOperation<T>,
+
+    /// Column that is equal to is_real * (1 - modulus_is_zero.result).
+    pub modulus_is_not_zero: T,
+
+    // Output values. We compute (x + y) % modulus or (x * y) % modulus.
+    pub output: FieldOpCols<T, P>,
+
+    pub output_range_check: FieldLtCols<T, P>,
+
+    pub is_real: T,
+}
+
+impl<F: PrimeField32, P: UintModField> MachineAir<F> for UintModChip<P> {
+    type Record = ExecutionRecord;
+    type Program = Program;
+
+    fn name(&self) -> String {
+        match P::UINT_TYPE {
+            UintType::Uint320 => "Uint320Mod".to_string(),
+            UintType::Uint384 => "Uint384Mod".to_string(),
+        }
+    }
+
+    fn generate_trace(
+        &self,
+        input: &ExecutionRecord,
+        output: &mut ExecutionRecord,
+    ) -> RowMajorMatrix<F> {
+        let events = match P::UINT_TYPE {
+            UintType::Uint320 => &input.uint320_mod_events,
+            UintType::Uint384 => &input.uint384_mod_events,
+        };
+        let num_cols = num_uint_mod_cols::<P>();
+        let num_words = <P as NumWords>::WordsFieldElement::USIZE;
+
+        let mut rows = Vec::new();
+        let mut new_byte_lookup_events = Vec::new();
+
+        for event in events {
+            let mut row = vec![F::zero(); num_cols];
+            let cols: &mut UintModCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            let x = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.x));
+            let y = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.y));
+            let modulus = BigUint::from_bytes_le(&words_to_bytes_le_vec(&event.modulus));
+
+            // Assign basic values to the columns.
+            cols.is_real = F::one();
+            cols.shard = F::from_canonical_u32(event.shard);
+            cols.channel = F::from_canonical_u8(event.channel);
+            cols.clk = F::from_canonical_u32(event.clk);
+            cols.x_ptr = F::from_canonical_u32(event.x_ptr);
+            cols.y_ptr = F::from_canonical_u32(event.y_ptr);
+            cols.is_add = F::from_bool(event.op == FieldOperation::Add);
+            cols.is_mul = F::from_bool(event.op == FieldOperation::Mul);
+
+            // Populate memory columns.
+            for i in 0..num_words {
+                cols.x_memory[i].populate(
+                    event.channel,
+                    event.x_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+                cols.y_memory[i].populate(
+                    event.channel,
+                    event.y_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+                cols.modulus_memory[i].populate(
+                    event.channel,
+                    event.modulus_memory_records[i],
+                    &mut new_byte_lookup_events,
+                );
+            }
+
+            let modulus_bytes = words_to_bytes_le_vec(&event.modulus);
+            let modulus_byte_sum = modulus_bytes.iter().map(|b| *b as u32).sum::<u32>();
+            IsZeroOperation::populate(&mut cols.modulus_is_zero, modulus_byte_sum);
+
+            // Populate the output column.
+            let effective_modulus = if modulus.is_zero() { P::modulus() } else { modulus.clone() };
+            let result = cols.output.populate_with_modulus(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &x,
+                &y,
+                &effective_modulus,
+                event.op,
+            );
+
+            cols.modulus_is_not_zero = F::one() - cols.modulus_is_zero.result;
+            if cols.modulus_is_not_zero == F::one() {
+                cols.output_range_check.populate(
+                    &mut new_byte_lookup_events,
+                    event.shard,
+                    event.channel,
+                    &result,
+                    &effective_modulus,
+                );
+            }
+
+            rows.push(row);
+        }
+        output.add_byte_lookup_events(new_byte_lookup_events);
+
+        pad_rows(&mut rows, || {
+            let mut row = vec![F::zero(); num_cols];
+            let cols: &mut UintModCols<F, P> = row.as_mut_slice().borrow_mut();
+
+            let zero = BigUint::zero();
+            cols.is_add = F::one();
+            cols.output.populate(&mut vec![], 0, 0, &zero, &zero, FieldOperation::Add);
+
+            row
+        });
+
+        // Convert the trace to a row major matrix.
+        let mut trace =
+            RowMajorMatrix::new(rows.into_iter().flatten().collect::<Vec<_>>(), num_cols);
+
+        // Write the nonces to the trace.
+        for i in 0..trace.height() {
+            let cols: &mut UintModCols<F, P> =
+                trace.values[i * num_cols..(i + 1) * num_cols].borrow_mut();
+            cols.nonce = F::from_canonical_usize(i);
+        }
+
+        trace
+    }
+
+    fn included(&self, shard: &Self::Record) -> bool {
+        match P::UINT_TYPE {
+            UintType::Uint320 => !shard.uint320_mod_events.is_empty(),
+            UintType::Uint384 => !shard.uint384_mod_events.is_empty(),
+        }
+    }
+}
+
+impl<F, P: UintModField> BaseAir<F> for UintModChip<P> {
+    fn width(&self) -> usize {
+        num_uint_mod_cols::<P>()
+    }
+}
+
+impl<AB, P: UintModField> Air<AB> for UintModChip<P>
+where
+    AB: SP1AirBuilder,
+    Limbs<AB::Var, <P as NumLimbs>::Limbs>: Copy,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &UintModCols<AB::Var, P> = (*local).borrow();
+        let next = main.row_slice(1);
+        let next: &UintModCols<AB::Var, P> = (*next).borrow();
+
+        // Constrain the incrementing nonce.
+        builder.when_first_row().assert_zero(local.nonce);
+        builder.when_transition().assert_eq(local.nonce + AB::Expr::one(), next.nonce);
+
+        // Each row is exactly one of the operations.
+        builder.assert_bool(local.is_add);
+        builder.assert_bool(local.is_mul);
+        builder.assert_one(local.is_add + local.is_mul);
+
+        // We are computing (x op y) % modulus. The value of x is stored in the "prev_value" of
+        // the x_memory, since we write to it later.
+        let x_limbs: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_prev_access(&local.x_memory);
+        let y_limbs: Limbs<AB::Var, <P as NumLimbs>::Limbs> = limbs_from_access(&local.y_memory);
+        let modulus_limbs: Limbs<AB::Var, <P as NumLimbs>::Limbs> =
+            limbs_from_access(&local.modulus_memory);
+
+        // If the modulus is zero, then we don't perform the modulus operation.
+        // Evaluate the modulus_is_zero operation by summing each byte of the modulus. The sum will
+        // not overflow because we are summing at most 48 bytes.
+        let modulus_byte_sum =
+            modulus_limbs.0.iter().fold(AB::Expr::zero(), |acc, &limb| acc + limb);
+        IsZeroOperation::<AB::F>::eval(
+            builder,
+            modulus_byte_sum,
+            local.modulus_is_zero,
+            local.is_real.into(),
+        );
+
+        // If the modulus is zero, we'll actually use 2^bits as the modulus, so nothing happens.
+        // Otherwise, we use the modulus passed in.
+        let modulus_is_zero = local.modulus_is_zero.result;
+        let coeff_2_bits =
+            P::MODULUS.iter().map(|&b| AB::Expr::from_canonical_u8(b)).collect::<Vec<_>>();
+        let modulus_polynomial: Polynomial<AB::Expr> = modulus_limbs.into();
+        let p_modulus: Polynomial<AB::Expr> = modulus_polynomial
+            * (AB::Expr::one() - modulus_is_zero.into())
+            + Polynomial::from_coefficients(&coeff_2_bits) * modulus_is_zero.into();
+
+        // Evaluate the modular addition or multiplication.
+        local.output.eval_variable(
+            builder,
+            &x_limbs,
+            &y_limbs,
+            &p_modulus,
+            local.is_add,
+            AB::F::zero(),
+            local.is_mul,
+            AB::F::zero(),
+            local.shard,
+            local.channel,
+            local.is_real,
+        );
+
+        // Verify the range of the output if the modulus is not zero. Also, check the value of
+        // modulus_is_not_zero.
+        local.output_range_check.eval(
+            builder,
+            &local.output.result,
+            &modulus_limbs,
+            local.shard,
+            local.channel,
+            local.modulus_is_not_zero,
+        );
+        builder.assert_eq(
+            local.modulus_is_not_zero,
+            local.is_real * (AB::Expr::one() - modulus_is_zero.into()),
+        );
+
+        // Assert that the correct result is being written to x_memory.
+        builder
+            .when(local.is_real)
+            .assert_all_eq(local.output.result, value_as_limbs(&local.x_memory));
+
+        // Read and write x.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into() + AB::Expr::one(),
+            local.x_ptr,
+            &local.x_memory,
+            local.is_real,
+        );
+
+        // Evaluate the y_ptr memory access. We concatenate y and modulus into a single array since
+        // we read it contiguously from the y_ptr memory location.
+        builder.eval_memory_access_slice(
+            local.shard,
+            local.channel,
+            local.clk.into(),
+            local.y_ptr,
+            &[local.y_memory.as_slice(), local.modulus_memory.as_slice()].concat(),
+            local.is_real,
+        );
+
+        // Receive the arguments.
+        let (add_code, mul_code) = Self::syscall_codes();
+        let syscall_id = local.is_add * AB::F::from_canonical_u32(add_code.syscall_id())
+            + local.is_mul * AB::F::from_canonical_u32(mul_code.syscall_id());
+        builder.receive_syscall(
+            local.shard,
+            local.channel,
+            local.clk,
+            local.nonce,
+            syscall_id,
+            local.x_ptr,
+            local.y_ptr,
+            local.is_real,
+        );
+
+        // Assert that is_real is a boolean.
+        builder.assert_bool(local.is_real);
+    }
+}
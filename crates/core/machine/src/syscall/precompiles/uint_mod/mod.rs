@@ -0,0 +1,95 @@
+mod air;
+
+pub use air::*;
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::{syscalls::SyscallCode, Executor, Instruction, Opcode, Program};
+    use sp1_curves::{
+        params::FieldParameters,
+        uint::{U320Field, U384Field},
+        utils::biguint_from_limbs,
+    };
+    use sp1_stark::{CpuProver, SP1CoreOpts};
+
+    use crate::utils::{self, run_test};
+
+    const X_PTR: u32 = 100;
+    const Y_PTR: u32 = 400;
+
+    /// A program running `syscall` on the `x` stored at [`X_PTR`], and the `y` and modulus stored
+    /// from [`Y_PTR`].
+    fn uint_mod_program(syscall: SyscallCode, x: &[u32], y: &[u32], modulus: &[u32]) -> Program {
+        let mut instructions = vec![];
+        let words = x
+            .iter()
+            .map(|&w| (X_PTR, w))
+            .enumerate()
+            .chain(y.iter().chain(modulus).map(|&w| (Y_PTR, w)).enumerate());
+        for (i, (ptr, word)) in words {
+            instructions.extend([
+                Instruction::new(Opcode::ADD, 29, 0, word, false, true),
+                Instruction::new(Opcode::ADD, 30, 0, ptr + 4 * i as u32, false, true),
+                Instruction::new(Opcode::SW, 29, 30, 0, false, true),
+            ]);
+        }
+        instructions.extend([
+            Instruction::new(Opcode::ADD, 5, 0, syscall as u32, false, true),
+            Instruction::new(Opcode::ADD, 10, 0, X_PTR, false, true),
+            Instruction::new(Opcode::ADD, 11, 0, Y_PTR, false, true),
+            Instruction::new(Opcode::ECALL, 5, 10, 11, false, false),
+        ]);
+        Program::new(instructions, 0, 0)
+    }
+
+    /// Runs `syscall`, and returns the words written to x.
+    fn execute(syscall: SyscallCode, x: &[u32], y: &[u32], modulus: &[u32]) -> Vec<u32> {
+        let program = uint_mod_program(syscall, x, y, modulus);
+        let mut runtime = Executor::new(program, SP1CoreOpts::default());
+        runtime.run().unwrap();
+        (0..x.len()).map(|i| runtime.word(X_PTR + 4 * i as u32)).collect()
+    }
+
+    #[test]
+    fn test_uint_mod_execute() {
+        // (2^320 - 1)^2 = 2^640 - 2^321 + 1 is 1 modulo 2^320.
+        let max = [u32::MAX; 10];
+        let mut one = [0; 10];
+        one[0] = 1;
+        assert_eq!(execute(SyscallCode::UINT320_MUL, &max, &max, &[0; 10]), one);
+
+        // With the modulus 2^320 - 1, 2^320 - 2 + 2 is 1.
+        let mut x = max;
+        x[0] -= 1;
+        let mut two = [0; 10];
+        two[0] = 2;
+        assert_eq!(execute(SyscallCode::UINT320_ADD, &x, &two, &max), one);
+
+        // 3 * 5 is 15 modulo 2^384 - 1.
+        let (mut three, mut five, mut fifteen) = ([0; 12], [0; 12], [0; 12]);
+        three[0] = 3;
+        five[0] = 5;
+        fifteen[0] = 15;
+        assert_eq!(execute(SyscallCode::UINT384_MUL, &three, &five, &[u32::MAX; 12]), fifteen);
+    }
+
+    #[test]
+    fn test_uint_mod_prove() {
+        utils::setup_logger();
+        let mut modulus = [u32::MAX; 12];
+        modulus[0] = 0xffff_fffd;
+        for syscall in [SyscallCode::UINT384_ADD, SyscallCode::UINT384_MUL] {
+            let program =
+                uint_mod_program(syscall, &[0x1234_5678; 12], &[0x9abc_def0; 12], &modulus);
+            run_test::<CpuProver<_, _>>(program).unwrap();
+        }
+        let program = uint_mod_program(SyscallCode::UINT320_MUL, &[7; 10], &[11; 10], &[0; 10]);
+        run_test::<CpuProver<_, _>>(program).unwrap();
+    }
+
+    #[test]
+    fn test_uint_mod_moduli() {
+        assert_eq!(biguint_from_limbs(U320Field::MODULUS), U320Field::modulus());
+        assert_eq!(biguint_from_limbs(U384Field::MODULUS), U384Field::modulus());
+    }
+}
@@ -17,7 +17,10 @@ use sp1_core_executor::{
 };
 use sp1_curves::{
     params::{limbs_from_vec, FieldParameters, Limbs, NumLimbs, NumWords},
-    weierstrass::{bls12_381::bls12381_sqrt, secp256k1::secp256k1_sqrt, WeierstrassParameters},
+    weierstrass::{
+        bls12_381::bls12381_sqrt, secp256k1::secp256k1_sqrt, secp256r1::secp256r1_sqrt,
+        WeierstrassParameters,
+    },
     CurveType, EllipticCurve,
 };
 use sp1_derive::AlignedBorrow;
@@ -69,6 +72,15 @@ pub struct LexicographicChoiceCols<T, P: FieldParameters + NumWords> {
     pub when_neg_y_res_is_lt: T,
 }
 
+/// The columns of the `ax` term of the curve equation `y^2 = x^3 + ax + b`, only present for the
+/// curves with a nonzero `a`, after any [LexicographicChoiceCols].
+#[derive(Debug, Clone, AlignedBorrow)]
+#[repr(C)]
+pub struct LinearTermCols<T, P: FieldParameters + NumWords> {
+    pub(crate) ax: FieldOpCols<T, P>,
+    pub(crate) x_3_plus_b_plus_ax: FieldOpCols<T, P>,
+}
+
 /// The convention for choosing the decompressed `y` value given a sign bit.
 pub enum SignChoiceRule {
     /// Lease significant bit convention.
@@ -102,14 +114,35 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDecompressChip<E> {
         Self { sign_rule: SignChoiceRule::Lexicographic, _marker: PhantomData::<E> }
     }
 
+    /// Whether the curve has a nonzero `a`, whose term takes the [LinearTermCols].
+    fn has_linear_term() -> bool {
+        !E::a_int().is_zero()
+    }
+
+    /// The offset of the [LinearTermCols] in a row.
+    fn linear_term_offset(&self) -> usize {
+        num_weierstrass_decompress_cols::<E::BaseField>()
+            + match self.sign_rule {
+                SignChoiceRule::LeastSignificantBit => 0,
+                SignChoiceRule::Lexicographic => {
+                    size_of::<LexicographicChoiceCols<u8, E::BaseField>>()
+                }
+            }
+    }
+
     fn populate_field_ops<F: PrimeField32>(
+        &self,
         record: &mut impl ByteRecord,
         shard: u32,
         channel: u8,
-        cols: &mut WeierstrassDecompressCols<F, E::BaseField>,
+        row: &mut [F],
         x: BigUint,
     ) {
-        // Y = sqrt(x^3 + b)
+        let (cols, choice_and_linear_cols) =
+            row.split_at_mut(num_weierstrass_decompress_cols::<E::BaseField>());
+        let cols: &mut WeierstrassDecompressCols<F, E::BaseField> = cols.borrow_mut();
+
+        // Y = sqrt(x^3 + ax + b)
         cols.range_x.populate(record, shard, channel, &x, &E::BaseField::modulus());
         let x_2 =
             cols.x_2.populate(record, shard, channel, &x.clone(), &x.clone(), FieldOperation::Mul);
@@ -117,13 +150,32 @@ impl<E: EllipticCurve + WeierstrassParameters> WeierstrassDecompressChip<E> {
         let b = E::b_int();
         let x_3_plus_b =
             cols.x_3_plus_b.populate(record, shard, channel, &x_3, &b, FieldOperation::Add);
+        let y_squared = if Self::has_linear_term() {
+            let offset =
+                self.linear_term_offset() - num_weierstrass_decompress_cols::<E::BaseField>();
+            let linear_cols: &mut LinearTermCols<F, E::BaseField> =
+                choice_and_linear_cols[offset..].borrow_mut();
+            let a = E::a_int();
+            let ax = linear_cols.ax.populate(record, shard, channel, &x, &a, FieldOperation::Mul);
+            linear_cols.x_3_plus_b_plus_ax.populate(
+                record,
+                shard,
+                channel,
+                &x_3_plus_b,
+                &ax,
+                FieldOperation::Add,
+            )
+        } else {
+            x_3_plus_b
+        };
 
         let sqrt_fn = match E::CURVE_TYPE {
             CurveType::Secp256k1 => secp256k1_sqrt,
+            CurveType::Secp256r1 => secp256r1_sqrt,
             CurveType::Bls12381 => bls12381_sqrt,
             _ => panic!("Unsupported curve"),
         };
-        let y = cols.y.populate(record, shard, channel, &x_3_plus_b, sqrt_fn);
+        let y = cols.y.populate(record, shard, channel, &y_squared, sqrt_fn);
 
         let zero = BigUint::zero();
         cols.neg_y.populate(record, shard, channel, &zero, &y, FieldOperation::Sub);
@@ -139,6 +191,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn name(&self) -> String {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => "Secp256k1Decompress".to_string(),
+            CurveType::Secp256r1 => "Secp256r1Decompress".to_string(),
             CurveType::Bls12381 => "Bls12381Decompress".to_string(),
             _ => panic!("Unsupported curve"),
         }
@@ -151,6 +204,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     ) -> RowMajorMatrix<F> {
         let events = match E::CURVE_TYPE {
             CurveType::Secp256k1 => &input.k256_decompress_events,
+            CurveType::Secp256r1 => &input.p256_decompress_events,
             CurveType::Bls12381 => &input.bls12381_decompress_events,
             _ => panic!("Unsupported curve"),
         };
@@ -166,6 +220,15 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
         for i in 0..events.len() {
             let event = events[i].clone();
             let mut row = vec![F::zero(); width];
+            let x = BigUint::from_bytes_le(&event.x_bytes);
+            self.populate_field_ops(
+                &mut new_byte_lookup_events,
+                event.shard,
+                event.channel,
+                &mut row,
+                x,
+            );
+
             let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
                 row[0..weierstrass_width].borrow_mut();
 
@@ -177,15 +240,6 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             cols.ptr = F::from_canonical_u32(event.ptr);
             cols.sign_bit = F::from_bool(event.sign_bit);
 
-            let x = BigUint::from_bytes_le(&event.x_bytes);
-            Self::populate_field_ops(
-                &mut new_byte_lookup_events,
-                event.shard,
-                event.channel,
-                cols,
-                x,
-            );
-
             for i in 0..cols.x_access.len() {
                 cols.x_access[i].populate(
                     event.channel,
@@ -204,7 +258,7 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
             if matches!(self.sign_rule, SignChoiceRule::Lexicographic) {
                 let lsb = cols.y.lsb;
                 let choice_cols: &mut LexicographicChoiceCols<F, E::BaseField> =
-                    row[weierstrass_width..width].borrow_mut();
+                    row[weierstrass_width..self.linear_term_offset()].borrow_mut();
 
                 let decompressed_y = BigUint::from_bytes_le(&event.decompressed_y_bytes);
                 let neg_y = &modulus - &decompressed_y;
@@ -261,18 +315,18 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
 
         pad_rows(&mut rows, || {
             let mut row = vec![F::zero(); width];
-            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
-                row.as_mut_slice()[0..weierstrass_width].borrow_mut();
 
-            // take X of the generator as a dummy value to make sure Y^2 = X^3 + b holds
+            // take X of the generator as a dummy value to make sure Y^2 = X^3 + aX + b holds
             let dummy_value = E::generator().0;
             let dummy_bytes = dummy_value.to_bytes_le();
             let words = bytes_to_words_le_vec(&dummy_bytes);
+            self.populate_field_ops(&mut vec![], 0, 0, &mut row, dummy_value);
+
+            let cols: &mut WeierstrassDecompressCols<F, E::BaseField> =
+                row.as_mut_slice()[0..weierstrass_width].borrow_mut();
             for i in 0..cols.x_access.len() {
                 cols.x_access[i].access.value = words[i].into();
             }
-
-            Self::populate_field_ops(&mut vec![], 0, 0, cols, dummy_value);
             row
         });
 
@@ -291,20 +345,20 @@ impl<F: PrimeField32, E: EllipticCurve + WeierstrassParameters> MachineAir<F>
     fn included(&self, shard: &Self::Record) -> bool {
         match E::CURVE_TYPE {
             CurveType::Secp256k1 => !shard.k256_decompress_events.is_empty(),
+            CurveType::Secp256r1 => !shard.p256_decompress_events.is_empty(),
             CurveType::Bls12381 => !shard.bls12381_decompress_events.is_empty(),
             _ => panic!("Unsupported curve"),
         }
     }
 }
 
-impl<F, E: EllipticCurve> BaseAir<F> for WeierstrassDecompressChip<E> {
+impl<F, E: EllipticCurve + WeierstrassParameters> BaseAir<F> for WeierstrassDecompressChip<E> {
     fn width(&self) -> usize {
-        num_weierstrass_decompress_cols::<E::BaseField>()
-            + match self.sign_rule {
-                SignChoiceRule::LeastSignificantBit => 0,
-                SignChoiceRule::Lexicographic => {
-                    size_of::<LexicographicChoiceCols<u8, E::BaseField>>()
-                }
+        self.linear_term_offset()
+            + if Self::has_linear_term() {
+                size_of::<LinearTermCols<u8, E::BaseField>>()
+            } else {
+                0
             }
     }
 }
@@ -374,6 +428,34 @@ where
             local.channel,
             local.is_real,
         );
+        let y_squared = if Self::has_linear_term() {
+            let offset = self.linear_term_offset();
+            let linear_cols: &LinearTermCols<AB::Var, E::BaseField> = (*local_slice)
+                [offset..offset + size_of::<LinearTermCols<u8, E::BaseField>>()]
+                .borrow();
+            let a_const = E::BaseField::to_limbs_field::<AB::F, _>(&E::a_int());
+            linear_cols.ax.eval(
+                builder,
+                &x,
+                &a_const,
+                FieldOperation::Mul,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            linear_cols.x_3_plus_b_plus_ax.eval(
+                builder,
+                &local.x_3_plus_b.result,
+                &linear_cols.ax.result,
+                FieldOperation::Add,
+                local.shard,
+                local.channel,
+                local.is_real,
+            );
+            linear_cols.x_3_plus_b_plus_ax.result
+        } else {
+            local.x_3_plus_b.result
+        };
 
         local.neg_y.eval(
             builder,
@@ -385,14 +467,7 @@ where
             local.is_real,
         );
 
-        local.y.eval(
-            builder,
-            &local.x_3_plus_b.result,
-            local.y.lsb,
-            local.shard,
-            local.channel,
-            local.is_real,
-        );
+        local.y.eval(builder, &y_squared, local.y.lsb, local.shard, local.channel, local.is_real);
 
         let y_limbs: Limbs<AB::Var, <E::BaseField as NumLimbs>::Limbs> =
             limbs_from_access(&local.y_access);
@@ -535,6 +610,9 @@ where
             CurveType::Secp256k1 => {
                 AB::F::from_canonical_u32(SyscallCode::SECP256K1_DECOMPRESS.syscall_id())
             }
+            CurveType::Secp256r1 => {
+                AB::F::from_canonical_u32(SyscallCode::SECP256R1_DECOMPRESS.syscall_id())
+            }
             CurveType::Bls12381 => {
                 AB::F::from_canonical_u32(SyscallCode::BLS12381_DECOMPRESS.syscall_id())
             }
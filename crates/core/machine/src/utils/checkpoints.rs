@@ -0,0 +1,150 @@
+use std::{
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sp1_core_executor::ExecutionState;
+
+/// The file the index of a [`CheckpointStore`] is kept in.
+const INDEX_FILE: &str = "index.bin";
+
+/// The index entry of a checkpoint in a [`CheckpointStore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointEntry {
+    /// The number of cycles executed before the checkpoint.
+    pub global_clk: u64,
+    /// Whether the checkpoint is the last one of the execution.
+    pub done: bool,
+}
+
+/// The checkpoints of an execution, streamed to files in a directory along with an index of them.
+///
+/// Each checkpoint is written as soon as the execution reaches it and is only read back when its
+/// shards are traced, so the memory used by the execution doesn't grow with its length. The index
+/// is replaced atomically after every checkpoint, so that other processes, e.g. prover workers,
+/// can read the checkpoints written so far with [`Self::open`] while the execution goes on.
+///
+/// The directory must only hold the checkpoints of one execution at a time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpointStore {
+    dir: PathBuf,
+    entries: Vec<CheckpointEntry>,
+}
+
+impl CheckpointStore {
+    /// Creates an empty store in `dir`, replacing the index of any previous execution in it.
+    pub fn create(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let store = Self { dir: dir.into(), entries: Vec::new() };
+        fs::create_dir_all(&store.dir)?;
+        store.write_index()?;
+        Ok(store)
+    }
+
+    /// Opens the store written to `dir` by another [`CheckpointStore`].
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let mut store = Self { dir: dir.into(), entries: Vec::new() };
+        store.refresh()?;
+        Ok(store)
+    }
+
+    /// Reloads the index, picking up the checkpoints written since the store was opened.
+    pub fn refresh(&mut self) -> io::Result<()> {
+        let file = File::open(self.dir.join(INDEX_FILE))?;
+        self.entries = bincode::deserialize_from(io::BufReader::new(file))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(())
+    }
+
+    /// The directory of the store.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The index of the checkpoints, in the order of the execution.
+    pub fn entries(&self) -> &[CheckpointEntry] {
+        &self.entries
+    }
+
+    /// The number of checkpoints in the store.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the store holds no checkpoint.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the last checkpoint of the execution is in the store.
+    pub fn is_complete(&self) -> bool {
+        self.entries.last().is_some_and(|entry| entry.done)
+    }
+
+    /// Writes `checkpoint` as the next checkpoint of the execution, `done` if it is the last one,
+    /// and returns its file, positioned at its start.
+    pub fn push(&mut self, checkpoint: &ExecutionState, done: bool) -> io::Result<File> {
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(self.path(self.entries.len()))?;
+        checkpoint.save(&mut file)?;
+        self.entries.push(CheckpointEntry { global_clk: checkpoint.global_clk, done });
+        self.write_index()?;
+        Ok(file)
+    }
+
+    /// Opens the file of the checkpoint at `index`, e.g. to generate its shards with a
+    /// [`ShardRecordGenerator`](super::ShardRecordGenerator).
+    pub fn checkpoint(&self, index: usize) -> io::Result<File> {
+        File::open(self.path(index))
+    }
+
+    /// Loads the checkpoint at `index`.
+    pub fn load(&self, index: usize) -> io::Result<ExecutionState> {
+        let file = self.checkpoint(index)?;
+        bincode::deserialize_from(io::BufReader::new(file))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The path of the checkpoint at `index`.
+    fn path(&self, index: usize) -> PathBuf {
+        self.dir.join(format!("checkpoint-{index}.bin"))
+    }
+
+    /// Replaces the index, through a temporary file so that readers never see a partial one.
+    fn write_index(&self) -> io::Result<()> {
+        let mut file = tempfile::NamedTempFile::new_in(&self.dir)?;
+        let bytes = bincode::serialize(&self.entries)
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+        io::Write::write_all(&mut file, &bytes)?;
+        file.persist(self.dir.join(INDEX_FILE))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut store = CheckpointStore::create(dir.path()).unwrap();
+        let mut reader = CheckpointStore::open(dir.path()).unwrap();
+        assert!(reader.is_empty());
+
+        let mut checkpoint = ExecutionState::new(0);
+        store.push(&checkpoint, false).unwrap();
+        checkpoint.global_clk = 100;
+        store.push(&checkpoint, true).unwrap();
+
+        reader.refresh().unwrap();
+        assert_eq!(reader.entries(), store.entries());
+        assert!(reader.is_complete());
+        assert_eq!(reader.load(1).unwrap().global_clk, 100);
+    }
+}
@@ -1,4 +1,5 @@
 mod buffer;
+mod checkpoints;
 pub mod concurrency;
 mod logger;
 #[cfg(any(test, feature = "programs"))]
@@ -9,6 +10,7 @@ mod span;
 mod tracer;
 
 pub use buffer::*;
+pub use checkpoints::*;
 pub use logger::*;
 pub use prove::*;
 pub use serde::*;
@@ -1,10 +1,15 @@
 use std::{
     collections::VecDeque,
-    fs::File,
+    fs::{self, File},
     io::{
         Seek, {self},
     },
-    sync::{mpsc::sync_channel, Arc, Mutex},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::sync_channel,
+        Arc, Mutex,
+    },
 };
 use web_time::Instant;
 
@@ -23,18 +28,19 @@ use p3_field::PrimeField32;
 use crate::riscv::cost::CostEstimator;
 use crate::{
     io::{SP1PublicValues, SP1Stdin},
-    utils::{chunk_vec, concurrency::TurnBasedSync},
+    utils::{chunk_vec, concurrency::TurnBasedSync, CheckpointStore},
 };
 use sp1_core_executor::events::sorted_table_lines;
 
 use sp1_core_executor::{
-    subproof::NoOpSubproofVerifier, ExecutionError, ExecutionRecord, ExecutionReport, Executor,
-    Program, SP1Context,
+    report_progress, subproof::NoOpSubproofVerifier, CancellationToken, ExecutionError,
+    ExecutionRecord, ExecutionReport, ExecutionState, Executor, Program, ProofEvent, ProofStage,
+    SP1Context,
 };
 use sp1_stark::{
     air::{MachineAir, PublicValues},
     Com, CpuProver, DebugConstraintBuilder, InteractionBuilder, MachineProof, MachineProver,
-    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SP1CoreOpts,
+    MachineRecord, OpeningProof, PcsProverData, ProverConstraintFolder, SP1CoreOpts, ShardProof,
     StarkGenericConfig, StarkMachine, StarkProvingKey, StarkVerifyingKey, UniConfig, Val,
     VerifierConstraintFolder,
 };
@@ -126,6 +132,18 @@ where
     PcsProverData<SC>: Send + Sync,
 {
     // Setup the runtime.
+    let cancellation_token = context.cancellation_token.clone();
+    let progress = context.progress.clone();
+    let shard_proofs_dir = context.shard_proofs_dir.clone();
+    let shard_proofs_dir = shard_proofs_dir.as_deref();
+    let mut checkpoint_store = context
+        .checkpoints_dir
+        .clone()
+        .map(CheckpointStore::create)
+        .transpose()
+        .map_err(SP1CoreProverError::IoError)?;
+    let is_cancelled =
+        move || cancellation_token.as_ref().is_some_and(CancellationToken::is_cancelled);
     let mut runtime = Executor::with_context(program.clone(), opts, context);
     runtime.write_vecs(&stdin.buffer);
     for proof in stdin.proofs.iter() {
@@ -138,8 +156,10 @@ where
     // Record the start of the process.
     let proving_start = Instant::now();
     let span = tracing::Span::current().clone();
+    let progress = progress.as_ref();
     std::thread::scope(move |s| {
         let _span = span.enter();
+        report_progress(progress, ProofEvent::StageStarted(ProofStage::Execute));
 
         // Spawn the checkpoint generator thread.
         let checkpoint_generator_span = tracing::Span::current().clone();
@@ -159,12 +179,10 @@ where
                         let (checkpoint, done) =
                             runtime.execute_state().map_err(SP1CoreProverError::ExecutionError)?;
 
-                        // Save the checkpoint to a temp file.
-                        let mut checkpoint_file =
-                            tempfile::tempfile().map_err(SP1CoreProverError::IoError)?;
-                        checkpoint
-                            .save(&mut checkpoint_file)
-                            .map_err(SP1CoreProverError::IoError)?;
+                        // Save the checkpoint to the store or a temp file.
+                        let checkpoint_file =
+                            save_checkpoint(checkpoint_store.as_mut(), &checkpoint, done)
+                                .map_err(SP1CoreProverError::IoError)?;
 
                         // Send the checkpoint.
                         checkpoints_tx.send((index, checkpoint_file, done)).unwrap();
@@ -231,49 +249,14 @@ where
                             // Wait for our turn to update the state.
                             record_gen_sync.wait_for_turn(index);
 
-                            // Update the public values & prover state for the shards which contain
-                            // "cpu events".
-                            let mut state = state.lock().unwrap();
-                            for record in records.iter_mut() {
-                                state.shard += 1;
-                                state.execution_shard = record.public_values.execution_shard;
-                                state.start_pc = record.public_values.start_pc;
-                                state.next_pc = record.public_values.next_pc;
-                                state.committed_value_digest =
-                                    record.public_values.committed_value_digest;
-                                state.deferred_proofs_digest =
-                                    record.public_values.deferred_proofs_digest;
-                                record.public_values = *state;
-                            }
-
-                            // Defer events that are too expensive to include in every shard.
-                            let mut deferred = deferred.lock().unwrap();
-                            for record in records.iter_mut() {
-                                deferred.append(&mut record.defer());
-                            }
-
-                            // See if any deferred shards are ready to be commited to.
-                            let mut deferred = deferred.split(done, opts.split_opts);
-
-                            // Update the public values & prover state for the shards which do not
-                            // contain "cpu events" before committing to them.
-                            if !done {
-                                state.execution_shard += 1;
-                            }
-                            for record in deferred.iter_mut() {
-                                state.shard += 1;
-                                state.previous_init_addr_bits =
-                                    record.public_values.previous_init_addr_bits;
-                                state.last_init_addr_bits =
-                                    record.public_values.last_init_addr_bits;
-                                state.previous_finalize_addr_bits =
-                                    record.public_values.previous_finalize_addr_bits;
-                                state.last_finalize_addr_bits =
-                                    record.public_values.last_finalize_addr_bits;
-                                state.start_pc = state.next_pc;
-                                record.public_values = *state;
-                            }
-                            records.append(&mut deferred);
+                            // Assign the shards of the checkpoint after the previous ones.
+                            assign_shards(
+                                &mut records,
+                                &mut state.lock().unwrap(),
+                                &mut deferred.lock().unwrap(),
+                                done,
+                                opts,
+                            );
 
                             // Collect the checkpoints to be used again in the phase 2 prover.
                             let mut checkpoints = checkpoints.lock().unwrap();
@@ -329,10 +312,12 @@ where
         let phase_1_prover_span = tracing::Span::current().clone();
         let phase_1_prover_handle = s.spawn(move || {
             let _span = phase_1_prover_span.enter();
+            let mut num_shards = 0;
             tracing::debug_span!("phase 1 prover").in_scope(|| {
                 for (records, traces) in p1_records_and_traces_rx.iter() {
                     tracing::debug_span!("batch").in_scope(|| {
                         let span = tracing::Span::current().clone();
+                        num_shards += records.len();
 
                         // Collect the public values.
                         let public_values = records
@@ -366,25 +351,36 @@ where
                 }
             });
 
-            challenger
+            (challenger, num_shards)
         });
 
         // Wait until the checkpoint generator handle has fully finished.
-        let public_values_stream = checkpoint_generator_handle.join().unwrap().unwrap();
+        // Execution errors, including a cancellation, stop the checkpoint generator. The remaining
+        // threads wind down once the checkpoints it sent are processed.
+        let public_values_stream = checkpoint_generator_handle.join().unwrap()?;
 
         // Wait until the records and traces have been fully generated.
         p1_record_and_trace_gen_handles.into_iter().for_each(|handle| handle.join().unwrap());
 
         // Wait until the phase 1 prover has completely finished.
-        let challenger = phase_1_prover_handle.join().unwrap();
+        let (challenger, num_shards) = phase_1_prover_handle.join().unwrap();
+        report_progress(
+            progress,
+            ProofEvent::StageFinished {
+                stage: ProofStage::Execute,
+                elapsed: proving_start.elapsed(),
+            },
+        );
+        let core_start = Instant::now();
+        report_progress(progress, ProofEvent::StageStarted(ProofStage::Core));
 
         // Spawn the phase 2 record generator thread.
         let p2_record_gen_sync = Arc::new(TurnBasedSync::new());
         let p2_trace_gen_sync = Arc::new(TurnBasedSync::new());
-        let (p2_records_and_traces_tx, p2_records_and_traces_rx) =
-            sync_channel::<(Vec<ExecutionRecord>, Vec<Vec<(String, RowMajorMatrix<Val<SC>>)>>)>(
-                opts.records_and_traces_channel_capacity,
-            );
+        let (p2_records_and_traces_tx, p2_records_and_traces_rx) = sync_channel::<(
+            Vec<ExecutionRecord>,
+            Vec<Option<Vec<(String, RowMajorMatrix<Val<SC>>)>>>,
+        )>(opts.records_and_traces_channel_capacity);
         let p2_records_and_traces_tx = Arc::new(Mutex::new(p2_records_and_traces_tx));
 
         let report_aggregate = Arc::new(Mutex::new(ExecutionReport::default()));
@@ -401,12 +397,18 @@ where
             let state = Arc::clone(&state);
             let deferred = Arc::clone(&deferred);
             let program = program.clone();
+            let is_cancelled = is_cancelled.clone();
 
             let span = tracing::Span::current().clone();
             let handle = s.spawn(move || {
                 let _span = span.enter();
                 tracing::debug_span!("phase 2 trace generation").in_scope(|| {
                     loop {
+                        // Stop tracing checkpoints once the proof is cancelled.
+                        if is_cancelled() {
+                            break;
+                        }
+
                         // Receive the latest checkpoint.
                         let received = { checkpoints.lock().unwrap().pop_front() };
                         if let Some((index, mut checkpoint, done)) = received {
@@ -424,57 +426,28 @@ where
                             // Wait for our turn to update the state.
                             record_gen_sync.wait_for_turn(index);
 
-                            // Update the public values & prover state for the shards which contain
-                            // "cpu events".
-                            let mut state = state.lock().unwrap();
-                            for record in records.iter_mut() {
-                                state.shard += 1;
-                                state.execution_shard = record.public_values.execution_shard;
-                                state.start_pc = record.public_values.start_pc;
-                                state.next_pc = record.public_values.next_pc;
-                                state.committed_value_digest =
-                                    record.public_values.committed_value_digest;
-                                state.deferred_proofs_digest =
-                                    record.public_values.deferred_proofs_digest;
-                                record.public_values = *state;
-                            }
-
-                            // Defer events that are too expensive to include in every shard.
-                            let mut deferred = deferred.lock().unwrap();
-                            for record in records.iter_mut() {
-                                deferred.append(&mut record.defer());
-                            }
-
-                            // See if any deferred shards are ready to be commited to.
-                            let mut deferred = deferred.split(done, opts.split_opts);
-
-                            // Update the public values & prover state for the shards which do not
-                            // contain "cpu events" before committing to them.
-                            if !done {
-                                state.execution_shard += 1;
-                            }
-                            for record in deferred.iter_mut() {
-                                state.shard += 1;
-                                state.previous_init_addr_bits =
-                                    record.public_values.previous_init_addr_bits;
-                                state.last_init_addr_bits =
-                                    record.public_values.last_init_addr_bits;
-                                state.previous_finalize_addr_bits =
-                                    record.public_values.previous_finalize_addr_bits;
-                                state.last_finalize_addr_bits =
-                                    record.public_values.last_finalize_addr_bits;
-                                state.start_pc = state.next_pc;
-                                record.public_values = *state;
-                            }
-                            records.append(&mut deferred);
+                            // Assign the shards of the checkpoint after the previous ones.
+                            assign_shards(
+                                &mut records,
+                                &mut state.lock().unwrap(),
+                                &mut deferred.lock().unwrap(),
+                                done,
+                                opts,
+                            );
 
                             // Let another worker update the state.
                             record_gen_sync.advance_turn();
 
-                            // Generate the traces.
+                            // Generate the traces, except for the shards that look already
+                            // proven.
                             let traces = records
                                 .par_iter()
-                                .map(|record| prover.generate_traces(record))
+                                .map(|record| {
+                                    let proven = shard_proofs_dir.is_some_and(|dir| {
+                                        shard_proof_path(dir, record.public_values.shard).exists()
+                                    });
+                                    (!proven).then(|| prover.generate_traces(record))
+                                })
                                 .collect::<Vec<_>>();
 
                             trace_gen_sync.wait_for_turn(index);
@@ -508,6 +481,27 @@ where
         let p2_prover_handle = s.spawn(move || {
             let _span = p2_prover_span.enter();
             let mut shard_proofs = Vec::new();
+            let num_proved = AtomicUsize::new(0);
+
+            // Prove a shard, or load its proof if it was persisted by an interrupted proof.
+            let prove_shard = |record: ExecutionRecord, traces: Option<_>| {
+                let shard = record.public_values.shard;
+                if let Some(proof) = shard_proofs_dir.and_then(|dir| load_shard_proof(dir, &record))
+                {
+                    tracing::info!("resuming from the proof of shard {}", shard);
+                    return proof;
+                }
+                // The persisted proof is unusable, so generate the traces it skipped.
+                let traces = traces.unwrap_or_else(|| prover.generate_traces(&record));
+                let data = prover.commit(record, traces);
+                let proof = prover.open(pk, data, &mut challenger.clone()).unwrap();
+                if let Some(dir) = shard_proofs_dir {
+                    if let Err(err) = store_shard_proof(dir, shard, &proof) {
+                        tracing::warn!("failed to persist the proof of shard {}: {}", shard, err);
+                    }
+                }
+                proof
+            };
             tracing::debug_span!("phase 2 prover").in_scope(|| {
                 for (records, traces) in p2_records_and_traces_rx.into_iter() {
                     tracing::debug_span!("batch").in_scope(|| {
@@ -516,8 +510,13 @@ where
                             records.into_par_iter().zip(traces.into_par_iter()).map(
                                 |(record, traces)| {
                                     let _span = span.enter();
-                                    let data = prover.commit(record, traces);
-                                    prover.open(pk, data, &mut challenger.clone()).unwrap()
+                                    let proof = prove_shard(record, traces);
+                                    let proved = num_proved.fetch_add(1, Ordering::Relaxed) + 1;
+                                    report_progress(
+                                        progress,
+                                        ProofEvent::ShardProved { proved, total: num_shards },
+                                    );
+                                    proof
                                 },
                             ),
                         );
@@ -532,6 +531,13 @@ where
 
         // Wait until the phase 2 prover has finished.
         let shard_proofs = p2_prover_handle.join().unwrap();
+        if is_cancelled() {
+            return Err(SP1CoreProverError::ExecutionError(ExecutionError::Cancelled));
+        }
+        report_progress(
+            progress,
+            ProofEvent::StageFinished { stage: ProofStage::Core, elapsed: core_start.elapsed() },
+        );
 
         // Log some of the `ExecutionReport` information.
         let report_aggregate = report_aggregate.lock().unwrap();
@@ -578,6 +584,180 @@ where
     })
 }
 
+/// Assigns the shards of the records traced from a checkpoint, following the shards of the previous
+/// checkpoints tracked by `state`, and appends the deferred records ready to be proven.
+fn assign_shards(
+    records: &mut Vec<ExecutionRecord>,
+    state: &mut PublicValues<u32, u32>,
+    deferred: &mut ExecutionRecord,
+    done: bool,
+    opts: SP1CoreOpts,
+) {
+    // Update the public values & prover state for the shards which contain
+    // "cpu events".
+    for record in records.iter_mut() {
+        state.shard += 1;
+        state.execution_shard = record.public_values.execution_shard;
+        state.start_pc = record.public_values.start_pc;
+        state.next_pc = record.public_values.next_pc;
+        state.committed_value_digest = record.public_values.committed_value_digest;
+        state.deferred_proofs_digest = record.public_values.deferred_proofs_digest;
+        record.public_values = *state;
+    }
+
+    // Defer events that are too expensive to include in every shard.
+    for record in records.iter_mut() {
+        deferred.append(&mut record.defer());
+    }
+
+    // See if any deferred shards are ready to be commited to.
+    let mut ready = deferred.split(done, opts.split_opts);
+
+    // Update the public values & prover state for the shards which do not
+    // contain "cpu events" before committing to them.
+    if !done {
+        state.execution_shard += 1;
+    }
+    for record in ready.iter_mut() {
+        state.shard += 1;
+        state.previous_init_addr_bits = record.public_values.previous_init_addr_bits;
+        state.last_init_addr_bits = record.public_values.last_init_addr_bits;
+        state.previous_finalize_addr_bits = record.public_values.previous_finalize_addr_bits;
+        state.last_finalize_addr_bits = record.public_values.last_finalize_addr_bits;
+        state.start_pc = state.next_pc;
+        record.public_values = *state;
+    }
+    records.append(&mut ready);
+}
+
+/// Executes `program` on `stdin`, saving a checkpoint of the execution before every batch of
+/// shards.
+///
+/// Returns the checkpoints, each along with whether it is the last one, the public values stream
+/// and the number of cycles of the execution. The shards of the checkpoints are generated with a
+/// [`ShardRecordGenerator`], e.g. to prove them on other machines.
+///
+/// The checkpoints are written to a [`CheckpointStore`] in the `checkpoints_dir` of `context` if
+/// it is set, and to temp files otherwise.
+pub fn execute_checkpoints(
+    program: Program,
+    stdin: &SP1Stdin,
+    opts: SP1CoreOpts,
+    context: SP1Context,
+) -> Result<(Vec<(File, bool)>, Vec<u8>, u64), SP1CoreProverError> {
+    let mut checkpoint_store = context
+        .checkpoints_dir
+        .clone()
+        .map(CheckpointStore::create)
+        .transpose()
+        .map_err(SP1CoreProverError::IoError)?;
+    let mut runtime = Executor::with_context(program, opts, context);
+    runtime.write_vecs(&stdin.buffer);
+    for proof in stdin.proofs.iter() {
+        runtime.write_proof(proof.0.clone(), proof.1.clone());
+    }
+
+    let mut checkpoints = Vec::new();
+    loop {
+        let (checkpoint, done) =
+            runtime.execute_state().map_err(SP1CoreProverError::ExecutionError)?;
+        let checkpoint_file = save_checkpoint(checkpoint_store.as_mut(), &checkpoint, done)
+            .map_err(SP1CoreProverError::IoError)?;
+        checkpoints.push((checkpoint_file, done));
+        if done {
+            return Ok((checkpoints, runtime.state.public_values_stream, runtime.state.global_clk));
+        }
+    }
+}
+
+/// Saves `checkpoint` to `store` if the checkpoints are streamed to a directory, otherwise to a
+/// temp file, and returns the file positioned at its start.
+fn save_checkpoint(
+    store: Option<&mut CheckpointStore>,
+    checkpoint: &ExecutionState,
+    done: bool,
+) -> io::Result<File> {
+    match store {
+        Some(store) => store.push(checkpoint, done),
+        None => {
+            let mut file = tempfile::tempfile()?;
+            checkpoint.save(&mut file)?;
+            Ok(file)
+        }
+    }
+}
+
+/// Generates the records of the shards of an execution from its checkpoints, as the core prover
+/// does.
+///
+/// The checkpoints must be passed in the order of the execution, since the shards of a checkpoint
+/// depend on the ones before it.
+pub struct ShardRecordGenerator {
+    program: Program,
+    opts: SP1CoreOpts,
+    state: PublicValues<u32, u32>,
+    deferred: ExecutionRecord,
+}
+
+impl ShardRecordGenerator {
+    /// Creates a generator of the shards of an execution of `program`.
+    pub fn new(program: Program, opts: SP1CoreOpts) -> Self {
+        let deferred = ExecutionRecord::new(program.clone().into());
+        Self { program, opts, state: PublicValues::<u32, u32>::default().reset(), deferred }
+    }
+
+    /// Generates the records of the shards of the next checkpoint, `done` if it is the last one.
+    pub fn generate<SC: StarkGenericConfig>(
+        &mut self,
+        machine: &StarkMachine<SC, RiscvAir<SC::Val>>,
+        checkpoint: &mut File,
+        done: bool,
+    ) -> Vec<ExecutionRecord>
+    where
+        SC::Val: PrimeField32,
+    {
+        let (mut records, _) = trace_checkpoint(self.program.clone(), checkpoint, self.opts);
+        reset_seek(checkpoint);
+        machine.generate_dependencies(&mut records, &self.opts);
+        assign_shards(&mut records, &mut self.state, &mut self.deferred, done, self.opts);
+        records
+    }
+}
+
+/// The path the proof of `shard` is persisted to in `dir`.
+fn shard_proof_path(dir: &Path, shard: u32) -> PathBuf {
+    dir.join(format!("shard-{shard}.proof"))
+}
+
+/// Loads the proof of the shard of `record` persisted in `dir`, if any.
+///
+/// Proofs that can't be read or deserialized, or whose public values (and so shard index) differ
+/// from those of `record`, are treated as missing.
+fn load_shard_proof<SC: StarkGenericConfig>(
+    dir: &Path,
+    record: &ExecutionRecord,
+) -> Option<ShardProof<SC>> {
+    let file = File::open(shard_proof_path(dir, record.public_values.shard)).ok()?;
+    let proof: ShardProof<SC> = bincode::deserialize_from(io::BufReader::new(file)).ok()?;
+    (proof.public_values == record.public_values::<Val<SC>>()).then_some(proof)
+}
+
+/// Persists the proof of `shard` to `dir`, through a temporary file so that a proof interrupted
+/// while writing is never loaded.
+fn store_shard_proof<SC: StarkGenericConfig>(
+    dir: &Path,
+    shard: u32,
+    proof: &ShardProof<SC>,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    let bytes =
+        bincode::serialize(proof).map_err(|err| io::Error::new(io::ErrorKind::Other, err))?;
+    io::Write::write_all(&mut file, &bytes)?;
+    file.persist(shard_proof_path(dir, shard))?;
+    Ok(())
+}
+
 /// Runs a program and returns the public values stream.
 pub fn run_test_io<P: MachineProver<BabyBearPoseidon2, RiscvAir<BabyBear>>>(
     program: Program,
@@ -85,6 +85,35 @@ impl SP1CudaProver {
     /// Creates a new [SP1Prover] that runs inside a Docker container and returns a
     /// [SP1ProverClient] that can be used to communicate with the container.
     pub fn new() -> Self {
+        Self::start("all", None)
+    }
+
+    /// Creates a new [SP1Prover] like [Self::new], proving on the CUDA device with index
+    /// `device_id` only.
+    ///
+    /// If `memory_limit` is set, it is passed to the proving server as `SP1_GPU_MEMORY_LIMIT`, the
+    /// maximum GPU memory it may use in bytes.
+    pub fn with_device(device_id: usize, memory_limit: Option<u64>) -> Self {
+        Self::start(&format!("device={device_id}"), memory_limit)
+    }
+
+    /// Whether a CUDA device with index `device_id` is present, as listed by `nvidia-smi`.
+    pub fn device_available(device_id: usize) -> bool {
+        let Ok(output) = Command::new("nvidia-smi")
+            .args(["--query-gpu=index", "--format=csv,noheader"])
+            .output()
+        else {
+            return false;
+        };
+        output.status.success()
+            && String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|index| index.trim().parse::<usize>().ok() == Some(device_id))
+    }
+
+    /// Starts the proving server on the GPUs selected by `gpus`, in the syntax of
+    /// `docker run --gpus`.
+    fn start(gpus: &str, memory_limit: Option<u64>) -> Self {
         let container_name = "sp1-gpu";
         let image_name = "jtguibas/sp1-gpu:v1.2.0-rc1";
 
@@ -100,18 +129,19 @@ impl SP1CudaProver {
 
         // Start the docker container.
         let rust_log_level = std::env::var("RUST_LOG").unwrap_or("none".to_string());
-        let mut child = Command::new("sudo")
+        let mut command = Command::new("sudo");
+        command.args(["docker", "run", "-e", format!("RUST_LOG={}", rust_log_level).as_str()]);
+        if let Some(memory_limit) = memory_limit {
+            command.args(["-e", format!("SP1_GPU_MEMORY_LIMIT={}", memory_limit).as_str()]);
+        }
+        let mut child = command
             .args([
-                "docker",
-                "run",
-                "-e",
-                format!("RUST_LOG={}", rust_log_level).as_str(),
                 "-p",
                 "3000:3000",
                 "--rm",
                 "--runtime=nvidia",
                 "--gpus",
-                "all",
+                gpus,
                 "--name",
                 container_name,
                 image_name,
@@ -0,0 +1,97 @@
+//! Parameters of the wide unsigned integers of the modular arithmetic precompiles, which take the
+//! modulus of each operation as an input.
+
+use typenum::{U40, U48, U79, U95};
+
+use num::{BigUint, One};
+use serde::{Deserialize, Serialize};
+
+use crate::params::{FieldParameters, NumLimbs, NumWords};
+
+/// The width of the integers of a modular arithmetic precompile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum UintType {
+    /// 320-bit integers.
+    Uint320,
+    /// 384-bit integers.
+    Uint384,
+}
+
+/// The parameters of the integers of a modular arithmetic precompile.
+///
+/// As with [`crate::uint256::U256Field`], the `FieldParameters` are only used for their modulus,
+/// `2^bits`, which is the modulus of the operations given a zero modulus.
+pub trait UintModField: FieldParameters + NumWords {
+    /// The width of the integers.
+    const UINT_TYPE: UintType;
+}
+
+/// The parameters of 320-bit integers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct U320Field;
+
+impl FieldParameters for U320Field {
+    /// The modulus, `2^320`, as a little-endian array of 41 bytes.
+    const MODULUS: &'static [u8] = &[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 15;
+
+    fn modulus() -> BigUint {
+        BigUint::one() << 320
+    }
+}
+
+impl NumLimbs for U320Field {
+    type Limbs = U40;
+    // As for `U256Field`, the witness has an extra limb for the modulus `2^320`.
+    type Witness = U79;
+}
+
+impl UintModField for U320Field {
+    const UINT_TYPE: UintType = UintType::Uint320;
+}
+
+/// The parameters of 384-bit integers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct U384Field;
+
+impl FieldParameters for U384Field {
+    /// The modulus, `2^384`, as a little-endian array of 49 bytes.
+    const MODULUS: &'static [u8] = &[
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ];
+
+    /// A rough witness-offset estimate given the size of the limbs and the size of the field.
+    const WITNESS_OFFSET: usize = 1usize << 15;
+
+    fn modulus() -> BigUint {
+        BigUint::one() << 384
+    }
+}
+
+impl NumLimbs for U384Field {
+    type Limbs = U48;
+    // As for `U256Field`, the witness has an extra limb for the modulus `2^384`.
+    type Witness = U95;
+}
+
+impl UintModField for U384Field {
+    const UINT_TYPE: UintType = UintType::Uint384;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::biguint_from_limbs;
+
+    #[test]
+    fn test_uint_moduli() {
+        assert_eq!(biguint_from_limbs(U320Field::MODULUS), U320Field::modulus());
+        assert_eq!(biguint_from_limbs(U384Field::MODULUS), U384Field::modulus());
+    }
+}
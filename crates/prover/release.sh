@@ -24,6 +24,13 @@ fi
 # Put the version in the build directory
 echo "$COMMIT_HASH $VERSION" > ./build/SP1_COMMIT
 
+# Record the digest of every artifact so installations can be checked for corruption
+(cd ./build && find . -type f ! -name 'MANIFEST.sha256' ! -name 'srs.bin' ! -name 'srs_lagrange.bin' -exec sha256sum {} + > MANIFEST.sha256)
+if [ $? -ne 0 ]; then
+    echo "Failed to create the artifacts manifest."
+    exit 1
+fi
+
 # Create archive named after the commit hash
 ARCHIVE_NAME="${VERSION}.tar.gz"
 cd $FILE_TO_UPLOAD
@@ -34,10 +41,39 @@ if [ $? -ne 0 ]; then
     exit 1
 fi
 
+# Create the archives with only the Plonk or Groth16 artifacts, so users can download just the
+# ones they need.
+PLONK_ARCHIVE_NAME="${VERSION}-plonk.tar.gz"
+GROTH16_ARCHIVE_NAME="${VERSION}-groth16.tar.gz"
+cd $FILE_TO_UPLOAD
+tar --exclude='srs.bin' --exclude='srs_lagrange.bin' --exclude='groth16_*' --exclude='SP1VerifierGroth16.sol' -czvf "../$PLONK_ARCHIVE_NAME" .
+tar --exclude='srs.bin' --exclude='srs_lagrange.bin' --exclude='plonk_*' --exclude='SP1VerifierPlonk.sol' -czvf "../$GROTH16_ARCHIVE_NAME" .
+cd -
+if [ $? -ne 0 ]; then
+    echo "Failed to create split archives."
+    exit 1
+fi
+
+# Create the checksum manifest for the archive, which the SDK verifies before extraction.
+CHECKSUM_NAME="${VERSION}.sha256"
+sha256sum "$ARCHIVE_NAME" "$PLONK_ARCHIVE_NAME" "$GROTH16_ARCHIVE_NAME" > "$CHECKSUM_NAME"
+if [ $? -ne 0 ]; then
+    echo "Failed to create checksum manifest."
+    exit 1
+fi
+
 # Upload the file to S3, naming it after the current commit hash
-aws s3 cp "$ARCHIVE_NAME" "s3://$S3_BUCKET/$ARCHIVE_NAME"
+for ARCHIVE in "$ARCHIVE_NAME" "$PLONK_ARCHIVE_NAME" "$GROTH16_ARCHIVE_NAME"; do
+    aws s3 cp "$ARCHIVE" "s3://$S3_BUCKET/$ARCHIVE"
+    if [ $? -ne 0 ]; then
+        echo "Failed to upload $ARCHIVE to S3."
+        exit 1
+    fi
+done
+
+aws s3 cp "$CHECKSUM_NAME" "s3://$S3_BUCKET/$CHECKSUM_NAME"
 if [ $? -ne 0 ]; then
-    echo "Failed to upload file to S3."
+    echo "Failed to upload checksum manifest to S3."
     exit 1
 fi
 
@@ -21,7 +21,11 @@ pub mod verify;
 use std::{
     borrow::Borrow,
     path::Path,
-    sync::{mpsc::sync_channel, Arc, Mutex, OnceLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::sync_channel,
+        Arc, Mutex, OnceLock,
+    },
     thread,
 };
 
@@ -31,7 +35,10 @@ use p3_baby_bear::BabyBear;
 use p3_challenger::CanObserve;
 use p3_field::{AbstractField, PrimeField};
 use p3_matrix::dense::RowMajorMatrix;
-use sp1_core_executor::{ExecutionError, ExecutionReport, Executor, Program, SP1Context};
+use sp1_core_executor::{
+    report_progress, ExecutionError, ExecutionReport, Executor, Program, ProgressCallback,
+    ProofEvent, SP1Context,
+};
 pub use sp1_core_machine::io::SP1Stdin;
 use sp1_core_machine::{
     riscv::RiscvAir,
@@ -46,7 +53,9 @@ use sp1_recursion_core::{
     stark::{config::BabyBearPoseidon2Outer, RecursionAir},
 };
 pub use sp1_recursion_gnark_ffi::proof::{Groth16Bn254Proof, PlonkBn254Proof};
-use sp1_recursion_gnark_ffi::{groth16_bn254::Groth16Bn254Prover, plonk_bn254::PlonkBn254Prover};
+pub use sp1_recursion_gnark_ffi::{
+    groth16_bn254::Groth16Bn254Prover, plonk_bn254::PlonkBn254Prover,
+};
 use sp1_recursion_program::hints::Hintable;
 pub use sp1_recursion_program::machine::{
     ReduceProgramType, SP1CompressMemoryLayout, SP1DeferredMemoryLayout, SP1RecursionMemoryLayout,
@@ -370,6 +379,19 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
         proof: SP1CoreProof,
         deferred_proofs: Vec<ShardProof<InnerSC>>,
         opts: SP1ProverOpts,
+    ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
+        self.compress_with_progress(vk, proof, deferred_proofs, opts, None)
+    }
+
+    /// Reduce shards proofs to a single shard proof using the recursion prover, reporting every
+    /// recursive proof of the reduction tree to `progress`.
+    pub fn compress_with_progress(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1CoreProof,
+        deferred_proofs: Vec<ShardProof<InnerSC>>,
+        opts: SP1ProverOpts,
+        progress: Option<&ProgressCallback>,
     ) -> Result<SP1ReduceProof<InnerSC>, SP1RecursionProverError> {
         // Set the batch size for the reduction tree.
         let batch_size = 2;
@@ -401,6 +423,18 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
             expected_height += 1;
         }
 
+        // Count the proofs in the tree: every layer halves the number of proofs, up to the one
+        // proof at the expected height, which is compressed once more into the root.
+        let mut num_proofs = num_first_layer_inputs;
+        let mut num_layer_proofs = num_first_layer_inputs;
+        for _ in 0..expected_height {
+            num_layer_proofs = (num_layer_proofs + 1) / 2;
+            num_proofs += num_layer_proofs;
+        }
+        num_proofs += 1;
+        let num_proved = AtomicUsize::new(0);
+        let num_proved = &num_proved;
+
         // Generate the proofs.
         let span = tracing::Span::current().clone();
         let proof = thread::scope(|s| {
@@ -591,6 +625,15 @@ impl<C: SP1ProverComponents> SP1Prover<C> {
                                     .unwrap()
                                     .send((index, height, proof, program_type))
                                     .unwrap();
+                                report_progress(
+                                    progress,
+                                    ProofEvent::RecursionProved {
+                                        proved: num_proved.fetch_add(1, Ordering::Relaxed) + 1,
+                                        total: num_proofs,
+                                        height,
+                                        max_height: expected_height + 1,
+                                    },
+                                );
 
                                 // Advance the turn.
                                 prover_sync.advance_turn();
@@ -9,6 +9,7 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sp1_core_machine::{
     io::{SP1PublicValues, SP1Stdin},
     riscv::RiscvAir,
+    SP1_CIRCUIT_VERSION,
 };
 use sp1_primitives::poseidon2_hash;
 use sp1_recursion_core::{air::RecursionPublicValues, stark::config::BabyBearPoseidon2Outer};
@@ -72,6 +73,90 @@ impl HashableKey for SP1VerifyingKey {
     }
 }
 
+/// A format a [SP1VerifyingKey] can be exported in with [SP1VerifyingKey::export].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VkExportFormat {
+    /// The bincode serialization of the key, which [SP1VerifyingKey::import] reads back.
+    Bytes,
+    /// The bincode serialization of the key as a `0x`-prefixed hex string.
+    Hex,
+    /// The digest of the key as a `0x`-prefixed bytes32 hex string, the `programVKey` expected
+    /// by the Solidity verifiers. See [HashableKey::bytes32].
+    Bytes32,
+    /// A JSON [VkDescriptor].
+    Json,
+}
+
+/// A self-describing export of a [SP1VerifyingKey], for configuration files and registries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VkDescriptor {
+    /// The circuit version the key was generated with.
+    pub version: String,
+    /// The hash function the digests are computed with.
+    pub hash_algorithm: String,
+    /// The digest of the key as a bytes32 hex string, see [HashableKey::bytes32].
+    pub bytes32: String,
+    /// The digest of the key as BabyBear elements, as verified inside the zkVM.
+    pub hash_u32: [u32; DIGEST_SIZE],
+    /// The key itself, in [VkExportFormat::Hex].
+    pub vk: String,
+}
+
+impl SP1VerifyingKey {
+    /// Exports the key in the given format.
+    ///
+    /// [VkExportFormat::Bytes] is binary, every other format is UTF-8 text.
+    pub fn export(&self, format: VkExportFormat) -> Vec<u8> {
+        match format {
+            VkExportFormat::Bytes => bincode::serialize(self).expect("failed to serialize vk"),
+            VkExportFormat::Hex => {
+                format!("0x{}", hex::encode(self.export(VkExportFormat::Bytes))).into_bytes()
+            }
+            VkExportFormat::Bytes32 => self.bytes32().into_bytes(),
+            VkExportFormat::Json => {
+                serde_json::to_vec_pretty(&self.descriptor()).expect("failed to serialize vk")
+            }
+        }
+    }
+
+    /// Imports a key exported with [Self::export] in [VkExportFormat::Bytes],
+    /// [VkExportFormat::Hex] or [VkExportFormat::Json]. The digest exported in
+    /// [VkExportFormat::Bytes32] can't be turned back into a key.
+    pub fn import(bytes: &[u8], format: VkExportFormat) -> Result<Self> {
+        match format {
+            VkExportFormat::Bytes => Ok(bincode::deserialize(bytes)?),
+            VkExportFormat::Hex => {
+                let hex = std::str::from_utf8(bytes)?.trim();
+                Self::import(&hex::decode(hex.trim_start_matches("0x"))?, VkExportFormat::Bytes)
+            }
+            VkExportFormat::Bytes32 => {
+                Err(anyhow::anyhow!("a verifying key can't be imported from its digest"))
+            }
+            VkExportFormat::Json => {
+                let descriptor: VkDescriptor = serde_json::from_slice(bytes)?;
+                let vk = Self::import(descriptor.vk.as_bytes(), VkExportFormat::Hex)?;
+                if vk.bytes32() != descriptor.bytes32 {
+                    return Err(anyhow::anyhow!(
+                        "the vk doesn't match the digest of the descriptor"
+                    ));
+                }
+                Ok(vk)
+            }
+        }
+    }
+
+    /// The [VkDescriptor] of the key, exported by [VkExportFormat::Json].
+    pub fn descriptor(&self) -> VkDescriptor {
+        VkDescriptor {
+            version: SP1_CIRCUIT_VERSION.to_string(),
+            hash_algorithm: "poseidon2-babybear".to_string(),
+            bytes32: self.bytes32(),
+            hash_u32: self.hash_u32(),
+            vk: String::from_utf8(self.export(VkExportFormat::Hex)).expect("hex is utf-8"),
+        }
+    }
+}
+
 impl<SC: StarkGenericConfig<Val = BabyBear, Domain = TwoAdicMultiplicativeCoset<BabyBear>>>
     HashableKey for StarkVerifyingKey<SC>
 where
@@ -116,6 +116,21 @@ func ProveGroth16Bn254(dataDir *C.char, witnessPath *C.char) *C.C_Groth16Bn254Pr
 	return structPtr
 }
 
+//export LoadPlonkBn254
+func LoadPlonkBn254(dataDir *C.char) {
+	sp1.LoadPlonk(C.GoString(dataDir))
+}
+
+//export LoadGroth16Bn254
+func LoadGroth16Bn254(dataDir *C.char) {
+	sp1.LoadGroth16(C.GoString(dataDir))
+}
+
+//export UnloadBn254Circuits
+func UnloadBn254Circuits(dataDir *C.char) {
+	sp1.UnloadCircuits(C.GoString(dataDir))
+}
+
 //export BuildGroth16Bn254
 func BuildGroth16Bn254(dataDir *C.char) {
 	// Sanity check the required arguments have been provided.
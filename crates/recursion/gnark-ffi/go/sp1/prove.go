@@ -4,20 +4,81 @@ import (
 	"bufio"
 	"encoding/json"
 	"os"
+	"sync"
 
 	"github.com/consensys/gnark-crypto/ecc"
 	"github.com/consensys/gnark/backend/groth16"
 	"github.com/consensys/gnark/backend/plonk"
+	"github.com/consensys/gnark/constraint"
 	"github.com/consensys/gnark/frontend"
 )
 
-func ProvePlonk(dataDir string, witnessPath string) Proof {
-	// Sanity check the required arguments have been provided.
-	if dataDir == "" {
-		panic("dataDirStr is required")
+// A Plonk circuit with its proving and verifying keys.
+type plonkCircuit struct {
+	scs constraint.ConstraintSystem
+	pk  plonk.ProvingKey
+	vk  plonk.VerifyingKey
+}
+
+// A Groth16 circuit with its proving and verifying keys.
+type groth16Circuit struct {
+	r1cs constraint.ConstraintSystem
+	pk   groth16.ProvingKey
+	vk   groth16.VerifyingKey
+}
+
+// The circuits kept in memory across proofs, keyed by their data directory. Circuits are added
+// with LoadPlonk and LoadGroth16, and removed with UnloadCircuits.
+var (
+	residentMutex   sync.Mutex
+	residentPlonk   = map[string]*plonkCircuit{}
+	residentGroth16 = map[string]*groth16Circuit{}
+)
+
+// LoadPlonk reads the Plonk circuit in dataDir and keeps it in memory for the following proofs.
+func LoadPlonk(dataDir string) {
+	if residentPlonkCircuit(dataDir) != nil {
+		return
 	}
-	os.Setenv("CONSTRAINTS_JSON", dataDir+"/"+constraintsJsonFile)
+	circuit := readPlonkCircuit(dataDir)
+	residentMutex.Lock()
+	defer residentMutex.Unlock()
+	residentPlonk[dataDir] = circuit
+}
+
+// LoadGroth16 reads the Groth16 circuit in dataDir and keeps it in memory for the following
+// proofs.
+func LoadGroth16(dataDir string) {
+	if residentGroth16Circuit(dataDir) != nil {
+		return
+	}
+	circuit := readGroth16Circuit(dataDir)
+	residentMutex.Lock()
+	defer residentMutex.Unlock()
+	residentGroth16[dataDir] = circuit
+}
+
+// UnloadCircuits releases the circuits in dataDir loaded with LoadPlonk and LoadGroth16.
+func UnloadCircuits(dataDir string) {
+	residentMutex.Lock()
+	defer residentMutex.Unlock()
+	delete(residentPlonk, dataDir)
+	delete(residentGroth16, dataDir)
+}
 
+func residentPlonkCircuit(dataDir string) *plonkCircuit {
+	residentMutex.Lock()
+	defer residentMutex.Unlock()
+	return residentPlonk[dataDir]
+}
+
+func residentGroth16Circuit(dataDir string) *groth16Circuit {
+	residentMutex.Lock()
+	defer residentMutex.Unlock()
+	return residentGroth16[dataDir]
+}
+
+func readPlonkCircuit(dataDir string) *plonkCircuit {
 	// Read the R1CS.
 	scsFile, err := os.Open(dataDir + "/" + plonkCircuitPath)
 	if err != nil {
@@ -46,6 +107,55 @@ func ProvePlonk(dataDir string, witnessPath string) Proof {
 	vk.ReadFrom(vkFile)
 	defer vkFile.Close()
 
+	return &plonkCircuit{scs: scs, pk: pk, vk: vk}
+}
+
+func readGroth16Circuit(dataDir string) *groth16Circuit {
+	// Read the R1CS.
+	r1csFile, err := os.Open(dataDir + "/" + groth16CircuitPath)
+	if err != nil {
+		panic(err)
+	}
+	r1cs := groth16.NewCS(ecc.BN254)
+	r1cs.ReadFrom(r1csFile)
+	defer r1csFile.Close()
+
+	// Read the proving key.
+	pkFile, err := os.Open(dataDir + "/" + groth16PkPath)
+	if err != nil {
+		panic(err)
+	}
+	pk := groth16.NewProvingKey(ecc.BN254)
+	bufReader := bufio.NewReaderSize(pkFile, 1024*1024)
+	pk.UnsafeReadFrom(bufReader)
+	defer pkFile.Close()
+
+	// Read the verifier key.
+	vkFile, err := os.Open(dataDir + "/" + groth16VkPath)
+	if err != nil {
+		panic(err)
+	}
+	vk := groth16.NewVerifyingKey(ecc.BN254)
+	vk.ReadFrom(vkFile)
+	defer vkFile.Close()
+
+	return &groth16Circuit{r1cs: r1cs, pk: pk, vk: vk}
+}
+
+func ProvePlonk(dataDir string, witnessPath string) Proof {
+	// Sanity check the required arguments have been provided.
+	if dataDir == "" {
+		panic("dataDirStr is required")
+	}
+	os.Setenv("CONSTRAINTS_JSON", dataDir+"/"+constraintsJsonFile)
+
+	// Use the resident circuit if it was loaded, otherwise read it from disk for this proof.
+	circuit := residentPlonkCircuit(dataDir)
+	if circuit == nil {
+		circuit = readPlonkCircuit(dataDir)
+	}
+	scs, pk, vk := circuit.scs, circuit.pk, circuit.vk
+
 	// Read the file.
 	data, err := os.ReadFile(witnessPath)
 	if err != nil {
@@ -93,33 +203,12 @@ func ProveGroth16(dataDir string, witnessPath string) Proof {
 	os.Setenv("CONSTRAINTS_JSON", dataDir+"/"+constraintsJsonFile)
 	os.Setenv("GROTH16", "1")
 
-	// Read the R1CS.
-	r1csFile, err := os.Open(dataDir + "/" + groth16CircuitPath)
-	if err != nil {
-		panic(err)
-	}
-	r1cs := groth16.NewCS(ecc.BN254)
-	r1cs.ReadFrom(r1csFile)
-	defer r1csFile.Close()
-
-	// Read the proving key.
-	pkFile, err := os.Open(dataDir + "/" + groth16PkPath)
-	if err != nil {
-		panic(err)
-	}
-	pk := groth16.NewProvingKey(ecc.BN254)
-	bufReader := bufio.NewReaderSize(pkFile, 1024*1024)
-	pk.UnsafeReadFrom(bufReader)
-	defer pkFile.Close()
-
-	// Read the verifier key.
-	vkFile, err := os.Open(dataDir + "/" + groth16VkPath)
-	if err != nil {
-		panic(err)
+	// Use the resident circuit if it was loaded, otherwise read it from disk for this proof.
+	circuit := residentGroth16Circuit(dataDir)
+	if circuit == nil {
+		circuit = readGroth16Circuit(dataDir)
 	}
-	vk := groth16.NewVerifyingKey(ecc.BN254)
-	vk.ReadFrom(vkFile)
-	defer vkFile.Close()
+	r1cs, pk, vk := circuit.r1cs, circuit.pk, circuit.vk
 
 	// Read the file.
 	data, err := os.ReadFile(witnessPath)
@@ -91,6 +91,15 @@ pub fn prove_groth16_bn254(data_dir: &str, witness_path: &str) -> Groth16Bn254Pr
     }
 }
 
+// Every docker proof runs in a fresh container, so circuits can't be kept in memory across proofs
+// and loading them is a no-op.
+
+pub fn load_plonk_bn254(_data_dir: &str) {}
+
+pub fn load_groth16_bn254(_data_dir: &str) {}
+
+pub fn unload_bn254_circuits(_data_dir: &str) {}
+
 fn build(system: ProofSystem, data_dir: &str) -> Result<()> {
     let circuit_dir = if data_dir.ends_with("dev") { "/circuit_dev" } else { "/circuit" };
     let mounts = [(data_dir, circuit_dir)];
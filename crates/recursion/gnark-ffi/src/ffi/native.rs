@@ -64,6 +64,17 @@ enum ProveFunction {
     Groth16(unsafe extern "C" fn(*mut c_char, *mut c_char) -> *mut C_Groth16Bn254Proof),
 }
 
+fn load(system: ProofSystem, data_dir: &str) {
+    let data_dir = CString::new(data_dir).expect("CString::new failed");
+    let load_fn = match system {
+        ProofSystem::Plonk => bind::LoadPlonkBn254,
+        ProofSystem::Groth16 => bind::LoadGroth16Bn254,
+    };
+    unsafe {
+        load_fn(data_dir.as_ptr() as *mut c_char);
+    }
+}
+
 fn build(system: ProofSystem, data_dir: &str) {
     let data_dir = CString::new(data_dir).expect("CString::new failed");
     unsafe {
@@ -163,6 +174,10 @@ pub fn test_plonk_bn254(witness_json: &str, constraints_json: &str) {
     test(ProofSystem::Plonk, witness_json, constraints_json)
 }
 
+pub fn load_plonk_bn254(data_dir: &str) {
+    load(ProofSystem::Plonk, data_dir)
+}
+
 pub fn build_groth16_bn254(data_dir: &str) {
     build(ProofSystem::Groth16, data_dir)
 }
@@ -187,6 +202,17 @@ pub fn test_groth16_bn254(witness_json: &str, constraints_json: &str) {
     test(ProofSystem::Groth16, witness_json, constraints_json)
 }
 
+pub fn load_groth16_bn254(data_dir: &str) {
+    load(ProofSystem::Groth16, data_dir)
+}
+
+pub fn unload_bn254_circuits(data_dir: &str) {
+    let data_dir = CString::new(data_dir).expect("CString::new failed");
+    unsafe {
+        bind::UnloadBn254Circuits(data_dir.as_ptr() as *mut c_char);
+    }
+}
+
 pub fn test_babybear_poseidon2() {
     unsafe {
         let err_ptr = bind::TestPoseidonBabyBear2();
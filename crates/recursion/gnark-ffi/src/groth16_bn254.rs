@@ -5,7 +5,10 @@ use std::{
 };
 
 use crate::{
-    ffi::{build_groth16_bn254, prove_groth16_bn254, test_groth16_bn254, verify_groth16_bn254},
+    ffi::{
+        build_groth16_bn254, load_groth16_bn254, prove_groth16_bn254, test_groth16_bn254,
+        unload_bn254_circuits, verify_groth16_bn254,
+    },
     witness::GnarkWitness,
     Groth16Bn254Proof,
 };
@@ -29,6 +32,19 @@ impl Groth16Bn254Prover {
         Self
     }
 
+    /// Loads the circuit and keys in `build_dir` into memory, so that the following proofs with
+    /// the same `build_dir` don't read them from disk again. Released with [Self::unload].
+    ///
+    /// Only the native prover keeps circuits in memory; with docker this is a no-op.
+    pub fn load(build_dir: &Path) {
+        load_groth16_bn254(build_dir.to_str().unwrap());
+    }
+
+    /// Releases the circuit and keys loaded from `build_dir` with [Self::load].
+    pub fn unload(build_dir: &Path) {
+        unload_bn254_circuits(build_dir.to_str().unwrap());
+    }
+
     pub fn get_vkey_hash(build_dir: &Path) -> [u8; 32] {
         let vkey_path = build_dir.join("groth16_vk.bin");
         let vk_bin_bytes = std::fs::read(vkey_path).unwrap();
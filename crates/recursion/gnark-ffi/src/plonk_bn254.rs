@@ -5,7 +5,10 @@ use std::{
 };
 
 use crate::{
-    ffi::{build_plonk_bn254, prove_plonk_bn254, test_plonk_bn254, verify_plonk_bn254},
+    ffi::{
+        build_plonk_bn254, load_plonk_bn254, prove_plonk_bn254, test_plonk_bn254,
+        unload_bn254_circuits, verify_plonk_bn254,
+    },
     witness::GnarkWitness,
     PlonkBn254Proof,
 };
@@ -28,6 +31,19 @@ impl PlonkBn254Prover {
         Self
     }
 
+    /// Loads the circuit and keys in `build_dir` into memory, so that the following proofs with
+    /// the same `build_dir` don't read them from disk again. Released with [Self::unload].
+    ///
+    /// Only the native prover keeps circuits in memory; with docker this is a no-op.
+    pub fn load(build_dir: &Path) {
+        load_plonk_bn254(build_dir.to_str().unwrap());
+    }
+
+    /// Releases the circuit and keys loaded from `build_dir` with [Self::load].
+    pub fn unload(build_dir: &Path) {
+        unload_bn254_circuits(build_dir.to_str().unwrap());
+    }
+
     pub fn get_vkey_hash(build_dir: &Path) -> [u8; 32] {
         let vkey_path = build_dir.join("plonk_vk.bin");
         let vk_bin_bytes = std::fs::read(vkey_path).unwrap();
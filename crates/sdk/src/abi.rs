@@ -0,0 +1,38 @@
+//! # ABI Decoding
+//!
+//! Decodes public values committed as ABI-encoded Solidity types, the encoding the onchain
+//! verifier forwards to contracts.
+
+use alloy_sol_types::SolType;
+use sp1_core_machine::io::{PublicValuesError, SP1PublicValues};
+
+/// Decodes `public_values` as the ABI encoding of the Solidity type `T`, such as a tuple of
+/// Solidity types or a struct declared with `alloy_sol_types::sol!`.
+///
+/// The public values must have been committed as `T::abi_encode(&value)`. They are validated
+/// against `T`, so values that are out of range for their Solidity type or bytes that are left
+/// over fail to decode.
+///
+/// ### Examples
+/// ```
+/// use alloy_sol_types::{sol_data::Uint, SolType};
+/// use sp1_sdk::{abi::decode_abi, SP1PublicValues};
+///
+/// type PublicValues = (Uint<32>, Uint<32>, Uint<32>);
+///
+/// let public_values = SP1PublicValues::from(&PublicValues::abi_encode(&(10u32, 34u32, 55u32)));
+/// let (n, a, b) = decode_abi::<PublicValues>(&public_values).unwrap();
+/// assert_eq!((n, a, b), (10, 34, 55));
+/// ```
+pub fn decode_abi<T: SolType>(
+    public_values: &SP1PublicValues,
+) -> Result<T::RustType, PublicValuesError> {
+    let bytes = public_values.as_slice();
+    let value =
+        T::abi_decode(bytes, true).map_err(|err| PublicValuesError::Decode(err.to_string()))?;
+    let encoded_len = T::abi_encoded_size(&value);
+    if bytes.len() > encoded_len {
+        return Err(PublicValuesError::TrailingBytes(bytes.len() - encoded_len));
+    }
+    Ok(value)
+}
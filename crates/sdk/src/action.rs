@@ -1,18 +1,31 @@
-use sp1_core_executor::{ExecutionReport, HookEnv, SP1ContextBuilder};
+use sp1_core_executor::{
+    CancellationToken, ExecutionReport, HookEnv, HookError, HookRegistrationError, ProofEvent,
+    ReplayLog, SP1Context, SP1ContextBuilder, TraceExportOpts,
+};
 use sp1_core_machine::io::{SP1PublicValues, SP1Stdin};
 use sp1_prover::{components::DefaultProverComponents, SP1ProvingKey};
 
-use anyhow::{Ok, Result};
-use sp1_stark::{SP1CoreOpts, SP1ProverOpts};
-use std::time::Duration;
+use anyhow::Result;
+use sp1_stark::{SP1CoreOpts, SP1ProverOpts, SplitOpts};
+use std::{
+    io::Write,
+    mem::take,
+    path::PathBuf,
+    sync::{mpsc, Arc, Mutex},
+    time::Duration,
+};
 
-use crate::{provers::ProofOpts, Prover, SP1ProofKind, SP1ProofWithPublicValues};
+use crate::{
+    cache::ProofCache, error::SP1SdkError, memory::MemoryBudget, provers::ProofOpts, Prover,
+    SP1ProofKind, SP1ProofMode, SP1ProofWithPublicValues,
+};
 
 /// Builder to prepare and configure execution of a program on an input.
 /// May be run with [Self::run].
 pub struct Execute<'a> {
     prover: &'a dyn Prover<DefaultProverComponents>,
     context_builder: SP1ContextBuilder<'a>,
+    hook_error: Option<HookRegistrationError>,
     elf: &'a [u8],
     stdin: SP1Stdin,
 }
@@ -27,16 +40,31 @@ impl<'a> Execute<'a> {
         elf: &'a [u8],
         stdin: SP1Stdin,
     ) -> Self {
-        Self { prover, elf, stdin, context_builder: Default::default() }
+        Self { prover, elf, stdin, context_builder: Default::default(), hook_error: None }
     }
 
     /// Execute the program on the input, consuming the built action `self`.
-    pub fn run(self) -> Result<(SP1PublicValues, ExecutionReport)> {
-        let Self { prover, elf, stdin, mut context_builder } = self;
+    pub fn run(self) -> Result<(SP1PublicValues, ExecutionReport), SP1SdkError> {
+        let Self { prover, elf, stdin, mut context_builder, hook_error } = self;
+        if let Some(err) = hook_error {
+            return Err(err.into());
+        }
         let context = context_builder.build();
         Ok(prover.sp1_prover().execute(elf, &stdin, context)?)
     }
 
+    /// Execute the program on the input like [Self::run], also returning the standard output
+    /// and error the program wrote.
+    ///
+    /// The output is captured instead of being sent to the writers set with [Self::stdout] and
+    /// [Self::stderr].
+    pub fn run_with_output(mut self) -> Result<ExecutionOutput, SP1SdkError> {
+        let (stdout, stderr) = (SharedBuffer::default(), SharedBuffer::default());
+        self.context_builder.stdout(stdout.clone()).stderr(stderr.clone());
+        let (public_values, report) = self.run()?;
+        Ok(ExecutionOutput { public_values, report, stdout: stdout.take(), stderr: stderr.take() })
+    }
+
     /// Add a runtime [Hook](super::Hook) into the context.
     ///
     /// Hooks may be invoked from within SP1 by writing to the specified file descriptor `fd`
@@ -51,6 +79,48 @@ impl<'a> Execute<'a> {
         self
     }
 
+    /// Add a runtime hook into the context under `name`, see
+    /// [SP1ContextBuilder::named_hook].
+    ///
+    /// Programs invoke the hook by writing to [`sp1_zkvm::io::hook_fd`]`(name)` with
+    /// [`sp1_zkvm::io::write`], and read the returned data with successive calls to
+    /// [`sp1_zkvm::io::read`]. If the hook returns an error, the execution fails with it, and is
+    /// retryable if the error is a [HookError::retryable].
+    ///
+    /// Registering two hooks under the same name, or under names mapping to the same file
+    /// descriptor, makes [Self::run] fail with a [HookRegistrationError].
+    pub fn with_named_hook(
+        mut self,
+        name: &str,
+        mut f: impl FnMut(&[u8]) -> Result<Vec<Vec<u8>>> + Send + Sync + 'a,
+    ) -> Self {
+        if self.hook_error.is_none() {
+            let registered =
+                self.context_builder.named_hook(name, move |_, buf| f(buf).map_err(hook_error));
+            self.hook_error = registered.err();
+        }
+        self
+    }
+
+    /// Add a handler of the requests programs send with [`sp1_zkvm::io::request`]`(name, ..)`,
+    /// see [SP1ContextBuilder::request_handler].
+    ///
+    /// The handler runs when the request is sent and returns the response the program receives.
+    /// Its responses are recorded, so that re-executing the program while proving doesn't invoke
+    /// it again. Registration errors are reported as for [Self::with_named_hook].
+    pub fn with_request_handler(
+        mut self,
+        name: &str,
+        mut f: impl FnMut(&[u8]) -> Result<Vec<u8>> + Send + Sync + 'a,
+    ) -> Self {
+        if self.hook_error.is_none() {
+            let registered =
+                self.context_builder.request_handler(name, move |buf| f(buf).map_err(hook_error));
+            self.hook_error = registered.err();
+        }
+        self
+    }
+
     /// Avoid registering the default hooks in the runtime.
     ///
     /// It is not necessary to call this to override hooks --- instead, simply
@@ -60,14 +130,158 @@ impl<'a> Execute<'a> {
         self
     }
 
+    /// Set the arguments of the program, returned by `std::env::args` inside SP1.
+    pub fn args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.context_builder.args(args);
+        self
+    }
+
+    /// Set an environment variable of the program, returned by `std::env::var` inside SP1.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the size of the guest memory, for programs built with a matching `max_memory`.
+    ///
+    /// Loads and stores past it fail with
+    /// [sp1_core_executor::ExecutionError::InvalidMemoryAccess].
+    pub fn max_memory(mut self, max_memory: u32) -> Self {
+        self.context_builder.max_memory(max_memory);
+        self
+    }
+
+    /// Fail loads of memory the program never initialized, rather than reading zero.
+    ///
+    /// Such loads fail with [sp1_core_executor::ExecutionError::InvalidMemoryAccess], reporting
+    /// the pc and address of the load. Initialization is tracked per word.
+    pub fn strict_memory(mut self) -> Self {
+        self.context_builder.strict_memory();
+        self
+    }
+
     /// Set the maximum number of cpu cycles to use for execution.
     ///
     /// If the cycle limit is exceeded, execution will return
-    /// [sp1_core_machine::runtime::ExecutionError::ExceededCycleLimit].
+    /// [sp1_core_executor::ExecutionError::CycleLimitExceeded].
     pub fn max_cycles(mut self, max_cycles: u64) -> Self {
         self.context_builder.max_cycles(max_cycles);
         self
     }
+
+    /// Attribute the cycles of the execution to the functions of the program, using the symbol
+    /// table of its ELF.
+    ///
+    /// The cycles of each function are returned in
+    /// [ExecutionReport::function_cycles], ranked by [ExecutionReport::function_profile], without
+    /// changing the program. It slows down the execution, and needs an ELF that isn't stripped.
+    pub fn function_profile(mut self) -> Self {
+        self.context_builder.function_profile();
+        self
+    }
+
+    /// Count the runs of the instruction at each program counter.
+    ///
+    /// The histogram is returned in [ExecutionReport::pc_counts], and the hottest basic blocks
+    /// of the program are listed by [ExecutionReport::hot_blocks] and logged at the end of the
+    /// execution.
+    pub fn pc_histogram(mut self) -> Self {
+        self.context_builder.pc_histogram();
+        self
+    }
+
+    /// Set the wall-clock time the execution may run for.
+    ///
+    /// Once it elapses, execution stops within a few thousand cycles and returns
+    /// [sp1_core_executor::ExecutionError::TimedOut], even if the program loops without syscalls.
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.context_builder.max_duration(max_duration);
+        self
+    }
+
+    /// Set the token to cancel the execution with.
+    ///
+    /// Once the token is cancelled, execution stops within a few thousand cycles and returns
+    /// [sp1_core_executor::ExecutionError::Cancelled].
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation_token(token);
+        self
+    }
+
+    /// Send the standard output of the program to `writer`, rather than printing it prefixed
+    /// with `stdout: `.
+    pub fn stdout(mut self, writer: impl Write + Send + 'a) -> Self {
+        self.context_builder.stdout(writer);
+        self
+    }
+
+    /// Send the standard error of the program to `writer`, rather than printing it prefixed
+    /// with `stderr: `.
+    pub fn stderr(mut self, writer: impl Write + Send + 'a) -> Self {
+        self.context_builder.stderr(writer);
+        self
+    }
+    /// Write the instruction-level trace of the execution to `writer`, see
+    /// [SP1ContextBuilder::trace_export].
+    pub fn trace_export(mut self, writer: impl Write + Send + 'a, opts: TraceExportOpts) -> Self {
+        self.context_builder.trace_export(writer, opts);
+        self
+    }
+
+    /// Record the stdin of the execution and the responses of its hooks to `writer`, see
+    /// [SP1ContextBuilder::record_replay].
+    pub fn record_replay(mut self, writer: impl Write + Send + 'a) -> Self {
+        self.context_builder.record_replay(writer);
+        self
+    }
+
+    /// Execute the program again as recorded in `log`, read with [ReplayLog::read], see
+    /// [SP1ContextBuilder::replay].
+    ///
+    /// The stdin of this action is replaced by the recorded one, and the hooks aren't invoked.
+    pub fn replay(mut self, log: ReplayLog) -> Self {
+        self.context_builder.replay(log);
+        self
+    }
+}
+
+/// The result of [Execute::run_with_output].
+#[derive(Debug, Clone)]
+pub struct ExecutionOutput {
+    /// The public values committed by the program.
+    pub public_values: SP1PublicValues,
+    /// The report of the execution.
+    pub report: ExecutionReport,
+    /// The bytes the program wrote to its standard output, file descriptor 1.
+    pub stdout: Vec<u8>,
+    /// The bytes the program wrote to its standard error, file descriptor 2.
+    pub stderr: Vec<u8>,
+}
+
+/// A buffer the output of a program is captured in, shared with the executor writing it.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Vec<u8> {
+        take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Converts the error of a named hook, keeping whether it is retryable if it is a [HookError].
+fn hook_error(err: anyhow::Error) -> HookError {
+    err.downcast::<HookError>().unwrap_or_else(|err| HookError::new(format!("{err:#}")))
 }
 
 /// Builder to prepare and configure proving execution of a program on an input.
@@ -76,11 +290,15 @@ pub struct Prove<'a> {
     prover: &'a dyn Prover<DefaultProverComponents>,
     kind: SP1ProofKind,
     context_builder: SP1ContextBuilder<'a>,
+    hook_error: Option<HookRegistrationError>,
     pk: &'a SP1ProvingKey,
     stdin: SP1Stdin,
     core_opts: SP1CoreOpts,
     recursion_opts: SP1CoreOpts,
     timeout: Option<Duration>,
+    proof_cache: Option<&'a ProofCache>,
+    checkpoint_dir: Option<PathBuf>,
+    memory_budget: Option<MemoryBudget>,
 }
 
 impl<'a> Prove<'a> {
@@ -99,29 +317,61 @@ impl<'a> Prove<'a> {
             pk,
             stdin,
             context_builder: Default::default(),
+            hook_error: None,
             core_opts: SP1CoreOpts::default(),
             recursion_opts: SP1CoreOpts::recursion(),
             timeout: None,
+            proof_cache: None,
+            checkpoint_dir: None,
+            memory_budget: None,
         }
     }
 
     /// Prove the execution of the program on the input, consuming the built action `self`.
-    pub fn run(self) -> Result<SP1ProofWithPublicValues> {
+    pub fn run(self) -> Result<SP1ProofWithPublicValues, SP1SdkError> {
         let Self {
             prover,
             kind,
             pk,
             stdin,
             mut context_builder,
+            hook_error,
             core_opts,
             recursion_opts,
             timeout,
+            proof_cache,
+            checkpoint_dir,
+            memory_budget,
         } = self;
+        if let Some(err) = hook_error {
+            return Err(err.into());
+        }
         let opts = SP1ProverOpts { core_opts, recursion_opts };
-        let proof_opts = ProofOpts { sp1_prover_opts: opts, timeout };
+        let proof_opts =
+            ProofOpts { sp1_prover_opts: opts, timeout, checkpoint_dir, memory_budget };
         let context = context_builder.build();
 
-        prover.prove(pk, stdin, proof_opts, context, kind)
+        let Some(cache) = proof_cache else {
+            return prover
+                .prove(pk, stdin, proof_opts, context, kind)
+                .map_err(SP1SdkError::proving);
+        };
+        let key = ProofCache::key(&pk.vk, &stdin, kind, prover.id());
+        if let Some(proof) = cache.load(&key) {
+            match prover.verify(&proof, &pk.vk) {
+                Err(err) => tracing::warn!("discarding the cached proof {}: {}", key, err),
+                _ => {
+                    tracing::info!("using the cached proof {}", key);
+                    return Ok(proof);
+                }
+            }
+        }
+        let proof =
+            prover.prove(pk, stdin, proof_opts, context, kind).map_err(SP1SdkError::proving)?;
+        if let Err(err) = cache.store(&key, &proof) {
+            tracing::warn!("failed to cache the proof {}: {}", key, err);
+        }
+        Ok(proof)
     }
 
     /// Set the proof kind to the core mode. This is the default.
@@ -148,6 +398,13 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Set the proof mode, e.g. one parsed from a configuration file. Equivalent to calling
+    /// [Self::core], [Self::compressed], [Self::plonk] or [Self::groth16].
+    pub fn mode(mut self, mode: SP1ProofMode) -> Self {
+        self.kind = mode.into();
+        self
+    }
+
     /// Add a runtime [Hook](super::Hook) into the context.
     ///
     /// Hooks may be invoked from within SP1 by writing to the specified file descriptor `fd`
@@ -162,6 +419,48 @@ impl<'a> Prove<'a> {
         self
     }
 
+    /// Add a runtime hook into the context under `name`, see
+    /// [SP1ContextBuilder::named_hook].
+    ///
+    /// Programs invoke the hook by writing to [`sp1_zkvm::io::hook_fd`]`(name)` with
+    /// [`sp1_zkvm::io::write`], and read the returned data with successive calls to
+    /// [`sp1_zkvm::io::read`]. If the hook returns an error, the execution fails with it, and is
+    /// retryable if the error is a [HookError::retryable].
+    ///
+    /// Registering two hooks under the same name, or under names mapping to the same file
+    /// descriptor, makes [Self::run] fail with a [HookRegistrationError].
+    pub fn with_named_hook(
+        mut self,
+        name: &str,
+        mut f: impl FnMut(&[u8]) -> Result<Vec<Vec<u8>>> + Send + Sync + 'a,
+    ) -> Self {
+        if self.hook_error.is_none() {
+            let registered =
+                self.context_builder.named_hook(name, move |_, buf| f(buf).map_err(hook_error));
+            self.hook_error = registered.err();
+        }
+        self
+    }
+
+    /// Add a handler of the requests programs send with [`sp1_zkvm::io::request`]`(name, ..)`,
+    /// see [SP1ContextBuilder::request_handler].
+    ///
+    /// The handler runs when the request is sent and returns the response the program receives.
+    /// Its responses are recorded, so that re-executing the program while proving doesn't invoke
+    /// it again. Registration errors are reported as for [Self::with_named_hook].
+    pub fn with_request_handler(
+        mut self,
+        name: &str,
+        mut f: impl FnMut(&[u8]) -> Result<Vec<u8>> + Send + Sync + 'a,
+    ) -> Self {
+        if self.hook_error.is_none() {
+            let registered =
+                self.context_builder.request_handler(name, move |buf| f(buf).map_err(hook_error));
+            self.hook_error = registered.err();
+        }
+        self
+    }
+
     /// Avoid registering the default hooks in the runtime.
     ///
     /// It is not necessary to call this to override hooks --- instead, simply
@@ -171,38 +470,353 @@ impl<'a> Prove<'a> {
         self
     }
 
-    /// Set the shard size for proving.
+    /// Set all of the options of the core prover at once, replacing the options set so far.
+    ///
+    /// Defaults to [SP1CoreOpts::default], which reads the `SHARD_SIZE`, `SHARD_BATCH_SIZE`,
+    /// `SPLIT_THRESHOLD`, `TRACE_GEN_WORKERS`, `CHECKPOINTS_CHANNEL_CAPACITY` and
+    /// `RECORDS_AND_TRACES_CHANNEL_CAPACITY` environment variables.
+    pub fn core_opts(mut self, opts: SP1CoreOpts) -> Self {
+        self.core_opts = opts;
+        self
+    }
+
+    /// Set all of the options of the recursion prover at once, replacing the options set so far.
+    ///
+    /// Defaults to [SP1CoreOpts::recursion].
+    pub fn recursion_opts(mut self, opts: SP1CoreOpts) -> Self {
+        self.recursion_opts = opts;
+        self
+    }
+
+    /// Set the shard size for proving, in cycles.
+    ///
+    /// Smaller shards lower the memory used to prove each shard, at the cost of more shards to
+    /// prove and compress.
     pub fn shard_size(mut self, value: usize) -> Self {
         self.core_opts.shard_size = value;
         self
     }
 
-    /// Set the shard batch size for proving.
+    /// Set the shard batch size for proving, the number of shards whose traces are generated and
+    /// committed to together.
+    ///
+    /// Larger batches prove more shards in parallel, at the cost of memory.
     pub fn shard_batch_size(mut self, value: usize) -> Self {
         self.core_opts.shard_batch_size = value;
         self
     }
 
+    /// Set the number of workers used to generate traces while proving.
+    pub fn trace_gen_workers(mut self, value: usize) -> Self {
+        self.core_opts.trace_gen_workers = value;
+        self
+    }
+
     /// Set whether we should reconstruct commitments while proving.
+    ///
+    /// Reconstructing the commitments of the shards in the second phase of proving saves the
+    /// memory of keeping the traces of the first phase around, at the cost of generating them
+    /// twice.
     pub fn reconstruct_commitments(mut self, value: bool) -> Self {
         self.core_opts.reconstruct_commitments = value;
         self
     }
 
+    /// Set the thresholds above which the events of precompiles and memory are split into
+    /// separate shards.
+    pub fn split_opts(mut self, value: SplitOpts) -> Self {
+        self.core_opts.split_opts = value;
+        self
+    }
+
+    /// Set the number of checkpoints of the execution buffered ahead of the trace generation.
+    pub fn checkpoints_channel_capacity(mut self, value: usize) -> Self {
+        self.core_opts.checkpoints_channel_capacity = value;
+        self
+    }
+
+    /// Set the number of generated records and traces buffered ahead of the prover.
+    pub fn records_and_traces_channel_capacity(mut self, value: usize) -> Self {
+        self.core_opts.records_and_traces_channel_capacity = value;
+        self
+    }
+
+    /// Set the arguments of the program, returned by `std::env::args` inside SP1.
+    pub fn args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.context_builder.args(args);
+        self
+    }
+
+    /// Set an environment variable of the program, returned by `std::env::var` inside SP1.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.context_builder.env(key, value);
+        self
+    }
+
+    /// Set the size of the guest memory, for programs built with a matching `max_memory`.
+    ///
+    /// Loads and stores past it fail with
+    /// [sp1_core_executor::ExecutionError::InvalidMemoryAccess].
+    pub fn max_memory(mut self, max_memory: u32) -> Self {
+        self.context_builder.max_memory(max_memory);
+        self
+    }
+
     /// Set the maximum number of cpu cycles to use for execution.
     ///
     /// If the cycle limit is exceeded, execution will return
-    /// [sp1_core_machine::runtime::ExecutionError::ExceededCycleLimit].
+    /// [sp1_core_executor::ExecutionError::CycleLimitExceeded].
     pub fn cycle_limit(mut self, cycle_limit: u64) -> Self {
         self.context_builder.max_cycles(cycle_limit);
         self
     }
 
+    /// Set the wall-clock time the execution of the program may run for.
+    ///
+    /// Once it elapses, the execution stops within a few thousand cycles and proving fails with
+    /// [sp1_core_executor::ExecutionError::TimedOut]. Unlike [Self::timeout], it only bounds the
+    /// execution, not the proving of the shards executed.
+    pub fn max_duration(mut self, max_duration: Duration) -> Self {
+        self.context_builder.max_duration(max_duration);
+        self
+    }
+
+    /// Set the token to cancel the proof with.
+    ///
+    /// Once the token is cancelled, local proving stops at the next shard or recursion stage and
+    /// returns [sp1_core_executor::ExecutionError::Cancelled].
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.context_builder.cancellation_token(token);
+        self
+    }
+
+    /// Set the callback receiving the progress of the proof's generation, such as the stage it is
+    /// in and the number of shards proven so far.
+    ///
+    /// The callback may be invoked from several threads at once. Stages of proofs requested from
+    /// the network prover are not reported.
+    pub fn progress(mut self, f: impl Fn(&ProofEvent) + Send + Sync + 'a) -> Self {
+        self.context_builder.progress(f);
+        self
+    }
+
+    /// Cache the proof in `cache`, returning a previously cached proof of the same program on the
+    /// same input instead of proving again.
+    ///
+    /// Cached proofs are verified before they are returned, and proven again if they are invalid.
+    pub fn proof_cache(mut self, cache: &'a ProofCache) -> Self {
+        self.proof_cache = Some(cache);
+        self
+    }
+
+    /// Persist the progress of the proof to `dir`, so that a proof interrupted by a crash or a
+    /// preemption resumes from where it stopped when it is run again with the same `dir`.
+    ///
+    /// The shard proofs and the compressed proof are persisted as they are generated, in a
+    /// subdirectory specific to the program and input, which is removed once the proof
+    /// completes. Only supported by the CPU prover.
+    pub fn checkpoint_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.checkpoint_dir = Some(dir.into());
+        self
+    }
+
+    /// Stream the checkpoints of the execution to files in `dir`, along with an index of them,
+    /// rather than to temporary files.
+    ///
+    /// Useful for programs with billions of cycles when temporary files are kept in memory. The
+    /// checkpoints can be read as they are written with a
+    /// [CheckpointStore](sp1_core_machine::utils::CheckpointStore).
+    pub fn execution_checkpoints_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.context_builder.checkpoints_dir(dir);
+        self
+    }
+
+    /// Keep the memory used by proving under `bytes`, see [MemoryBudget].
+    ///
+    /// The shard batch size is lowered and the stages of proving are serialized as needed to stay
+    /// under the budget. If even a single shard doesn't fit, the proof fails up front with
+    /// [MemoryBudgetError](crate::memory::MemoryBudgetError). Only enforced by the CPU and distributed provers.
+    pub fn memory_budget(mut self, bytes: u64) -> Self {
+        self.memory_budget = Some(MemoryBudget::new(bytes));
+        self
+    }
+
     /// Set the timeout for the proof's generation.
     ///
-    /// This parameter is only used when the prover is run in network mode.
+    /// When proving locally, the proof is abandoned at the next shard boundary or between two
+    /// stages once the timeout elapses, and [Self::run] fails with
+    /// [SP1SdkError::TimedOut] listing the stages that completed. The CUDA prover ignores it. In
+    /// network mode, the proof request is abandoned instead.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
         self
     }
 }
+
+/// Builder to prepare and configure proving the execution of a program on many inputs.
+/// May be run with [Self::run] or [Self::for_each].
+///
+/// The inputs share the proving key and are proven by a pool of workers, each proving one input
+/// at a time.
+pub struct ProveBatch<'a> {
+    prover: &'a dyn Prover<DefaultProverComponents>,
+    kind: SP1ProofKind,
+    pk: &'a SP1ProvingKey,
+    stdins: Vec<SP1Stdin>,
+    opts: ProofOpts,
+    concurrency: usize,
+}
+
+impl<'a> ProveBatch<'a> {
+    /// Prepare to prove the execution of the given program with each of the given inputs.
+    ///
+    /// Prefer using [ProverClient::prove_batch](super::ProverClient::prove_batch).
+    /// See there for more documentation.
+    pub fn new(
+        prover: &'a dyn Prover<DefaultProverComponents>,
+        pk: &'a SP1ProvingKey,
+        stdins: impl IntoIterator<Item = SP1Stdin>,
+    ) -> Self {
+        let sp1_prover_opts = SP1ProverOpts {
+            core_opts: SP1CoreOpts::default(),
+            recursion_opts: SP1CoreOpts::recursion(),
+        };
+        Self {
+            prover,
+            kind: Default::default(),
+            pk,
+            stdins: stdins.into_iter().collect(),
+            opts: ProofOpts {
+                sp1_prover_opts,
+                timeout: None,
+                checkpoint_dir: None,
+                memory_budget: None,
+            },
+            concurrency: 1,
+        }
+    }
+
+    /// Prove the execution of the program on every input, consuming the built action `self`.
+    ///
+    /// Returns the results in the order of the inputs, once every input has been proven.
+    pub fn run(self) -> Vec<Result<SP1ProofWithPublicValues, SP1SdkError>> {
+        let mut results: Vec<Option<Result<SP1ProofWithPublicValues, SP1SdkError>>> =
+            self.stdins.iter().map(|_| None).collect();
+        self.for_each(|index, result| results[index] = Some(result));
+        results.into_iter().map(|result| result.expect("every input is proven")).collect()
+    }
+
+    /// Prove the execution of the program on every input, consuming the built action `self`.
+    ///
+    /// Calls `f` on the calling thread with the index of each input and the result of proving it
+    /// as soon as it completes, so results arrive out of order when proving concurrently.
+    pub fn for_each(self, mut f: impl FnMut(usize, Result<SP1ProofWithPublicValues, SP1SdkError>)) {
+        let Self { prover, kind, pk, stdins, opts, concurrency } = self;
+        let workers = concurrency.clamp(1, stdins.len().max(1));
+        let queue = Mutex::new(stdins.into_iter().enumerate());
+        let (tx, rx) = mpsc::channel();
+        std::thread::scope(|s| {
+            for _ in 0..workers {
+                let (queue, opts, tx) = (&queue, &opts, tx.clone());
+                s.spawn(move || loop {
+                    let Some((index, stdin)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    let result = prover
+                        .prove(pk, stdin, opts.clone(), SP1Context::default(), kind)
+                        .map_err(SP1SdkError::proving);
+                    if tx.send((index, result)).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(tx);
+            for (index, result) in rx {
+                f(index, result);
+            }
+        });
+    }
+
+    /// Set the number of inputs proven at the same time. Defaults to 1.
+    ///
+    /// Every local proof already uses all cores of the machine, so proving concurrently mostly
+    /// helps to overlap the stages of different proofs, at the cost of memory. When proving on
+    /// the network, this is the number of proofs requested at once.
+    pub fn concurrency(mut self, value: usize) -> Self {
+        self.concurrency = value;
+        self
+    }
+
+    /// Set the proof kind to the core mode. This is the default.
+    pub fn core(mut self) -> Self {
+        self.kind = SP1ProofKind::Core;
+        self
+    }
+
+    /// Set the proof kind to the compressed mode.
+    pub fn compressed(mut self) -> Self {
+        self.kind = SP1ProofKind::Compressed;
+        self
+    }
+
+    /// Set the proof mode to the plonk bn254 mode.
+    pub fn plonk(mut self) -> Self {
+        self.kind = SP1ProofKind::Plonk;
+        self
+    }
+
+    /// Set the proof mode to the groth16 bn254 mode.
+    pub fn groth16(mut self) -> Self {
+        self.kind = SP1ProofKind::Groth16;
+        self
+    }
+
+    /// Set the proof mode, e.g. one parsed from a configuration file. Equivalent to calling
+    /// [Self::core], [Self::compressed], [Self::plonk] or [Self::groth16].
+    pub fn mode(mut self, mode: SP1ProofMode) -> Self {
+        self.kind = mode.into();
+        self
+    }
+
+    /// Set all of the options of the core prover at once, see [Prove::core_opts].
+    pub fn core_opts(mut self, opts: SP1CoreOpts) -> Self {
+        self.opts.sp1_prover_opts.core_opts = opts;
+        self
+    }
+
+    /// Set the shard size for proving.
+    pub fn shard_size(mut self, value: usize) -> Self {
+        self.opts.sp1_prover_opts.core_opts.shard_size = value;
+        self
+    }
+
+    /// Set the shard batch size for proving.
+    pub fn shard_batch_size(mut self, value: usize) -> Self {
+        self.opts.sp1_prover_opts.core_opts.shard_batch_size = value;
+        self
+    }
+
+    /// Set the number of workers used to generate traces while proving.
+    pub fn trace_gen_workers(mut self, value: usize) -> Self {
+        self.opts.sp1_prover_opts.core_opts.trace_gen_workers = value;
+        self
+    }
+
+    /// Keep the memory used by each proof under `bytes`, see [Prove::memory_budget].
+    pub fn memory_budget(mut self, bytes: u64) -> Self {
+        self.opts.memory_budget = Some(MemoryBudget::new(bytes));
+        self
+    }
+
+    /// Set the timeout for the generation of each proof, see [Prove::timeout].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.opts.timeout = Some(timeout);
+        self
+    }
+
+    /// Replace the options every proof is generated with.
+    pub(crate) fn opts(mut self, opts: ProofOpts) -> Self {
+        self.opts = opts;
+        self
+    }
+}
@@ -0,0 +1,122 @@
+//! # Proof Aggregation
+//!
+//! A built-in program that verifies many compressed proofs recursively, so that they can be
+//! aggregated into a single proof without writing a custom guest program, and
+//! [WriteDeferredProof] to verify proofs recursively from a custom one.
+
+use sp1_core_machine::io::SP1Stdin;
+use sp1_prover::{HashableKey, SP1VerifyingKey};
+use thiserror::Error;
+
+use crate::{SP1Proof, SP1ProofWithPublicValues};
+
+/// The ELF of the aggregation program, built from `programs/aggregation`.
+pub const AGGREGATION_ELF: &[u8] =
+    include_bytes!("../programs/aggregation/elf/riscv32im-succinct-zkvm-elf");
+
+/// An error preparing proofs for aggregation.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AggregationError {
+    #[error("no proofs to aggregate")]
+    Empty,
+    #[error("got {proofs} proofs but {vks} verifying keys")]
+    LengthMismatch { proofs: usize, vks: usize },
+    #[error("proof {0} is not a compressed proof, only compressed proofs can be aggregated")]
+    NotCompressed(usize),
+}
+
+/// Writes proofs to an [SP1Stdin] to be verified recursively by the program.
+pub trait WriteDeferredProof {
+    /// Writes the compressed `proof` of the program with verifying key `vk`, to be verified by
+    /// the program with `sp1_zkvm::lib::verify_deferred`.
+    ///
+    /// Along with the proof, the digest of `vk` and the public values of the proof are written
+    /// to the input, so the program doesn't need to be given them separately. Proofs are read by
+    /// the program in the order they are written.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin, WriteDeferredProof};
+    ///
+    /// # let inner_elf: &[u8] = &[];
+    /// # let outer_elf: &[u8] = &[];
+    /// let client = ProverClient::new();
+    /// let (inner_pk, inner_vk) = client.setup(inner_elf);
+    /// let inner = client.prove(&inner_pk, SP1Stdin::new()).compressed().run().unwrap();
+    ///
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write_deferred_proof(&inner, &inner_vk).unwrap();
+    /// let (outer_pk, _) = client.setup(outer_elf);
+    /// let outer = client.prove(&outer_pk, stdin).run().unwrap();
+    /// ```
+    fn write_deferred_proof(
+        &mut self,
+        proof: &SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+    ) -> Result<(), AggregationError>;
+}
+
+impl WriteDeferredProof for SP1Stdin {
+    fn write_deferred_proof(
+        &mut self,
+        proof: &SP1ProofWithPublicValues,
+        vk: &SP1VerifyingKey,
+    ) -> Result<(), AggregationError> {
+        let SP1Proof::Compressed(shard_proof) = &proof.proof else {
+            return Err(AggregationError::NotCompressed(self.proofs.len()));
+        };
+        self.write(&(vk.hash_u32(), proof.public_values.to_vec()));
+        self.write_proof(shard_proof.clone(), vk.vk.clone());
+        Ok(())
+    }
+}
+
+/// Prepares the input of [AGGREGATION_ELF] verifying each of `proofs` against the verifying key
+/// at the same index of `vks`.
+pub fn aggregation_stdin(
+    proofs: &[SP1ProofWithPublicValues],
+    vks: &[SP1VerifyingKey],
+) -> Result<SP1Stdin, AggregationError> {
+    if proofs.is_empty() {
+        return Err(AggregationError::Empty);
+    }
+    if proofs.len() != vks.len() {
+        return Err(AggregationError::LengthMismatch { proofs: proofs.len(), vks: vks.len() });
+    }
+
+    let mut stdin = SP1Stdin::new();
+    stdin.write::<Vec<[u32; 8]>>(&vks.iter().map(|vk| vk.hash_u32()).collect());
+    stdin.write::<Vec<Vec<u8>>>(&proofs.iter().map(|p| p.public_values.to_vec()).collect());
+
+    // The proofs aren't read by the program, they are witnessed by the prover as deferred proofs.
+    for (index, (proof, vk)) in proofs.iter().zip(vks).enumerate() {
+        let SP1Proof::Compressed(shard_proof) = &proof.proof else {
+            return Err(AggregationError::NotCompressed(index));
+        };
+        stdin.write_proof(shard_proof.clone(), vk.vk.clone());
+    }
+    Ok(stdin)
+}
+
+/// The public values committed by [AGGREGATION_ELF] when aggregating proofs with the given
+/// verifying keys and public values.
+///
+/// The encoding is `vks.len() || vks || public_values[0].len() || public_values[0] || ...`, with
+/// the lengths as big-endian `u32`s, matching `abi.encodePacked` in Solidity.
+pub fn commit_proof_pairs(vks: &[SP1VerifyingKey], public_values: &[Vec<u8>]) -> Vec<u8> {
+    assert_eq!(vks.len(), public_values.len());
+    let mut res = Vec::with_capacity(
+        4 + vks.len() * 32 + public_values.iter().map(|values| 4 + values.len()).sum::<usize>(),
+    );
+    res.extend_from_slice(&(vks.len() as u32).to_be_bytes());
+    for vk in vks {
+        for word in vk.hash_u32() {
+            res.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+    for values in public_values {
+        res.extend_from_slice(&(values.len() as u32).to_be_bytes());
+        res.extend_from_slice(values);
+    }
+    res
+}
@@ -0,0 +1,330 @@
+//! # Proof and Key Caches
+//!
+//! On-disk caches of proofs and keys, so that proving the same program on the same input twice,
+//! or setting up the same program in every process, such as across CI runs, only does the work
+//! once, and the in-memory cache of keys a [ProverClient](crate::ProverClient) keeps across
+//! calls to its setup.
+
+use std::{
+    collections::VecDeque,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use sp1_core_machine::{io::SP1Stdin, SP1_CIRCUIT_VERSION};
+use sp1_prover::{
+    components::DefaultProverComponents, HashableKey, SP1ProvingKey, SP1VerifyingKey,
+};
+
+use crate::{provers::ProverType, Prover, SP1ProofKind, SP1ProofWithPublicValues};
+
+/// The environment variable used to enable the proof cache, set to the directory it is stored in.
+pub const SP1_PROOF_CACHE_DIR_ENV: &str = "SP1_PROOF_CACHE_DIR";
+
+/// An on-disk cache of [SP1ProofWithPublicValues].
+///
+/// Proofs are keyed by the hash of the verifying key, the digest of the stdin, the proof kind,
+/// the prover that generated them and the circuit version, so a proof is only reused for exactly
+/// the request it was generated for. Cached proofs are verified before they are returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCache {
+    dir: PathBuf,
+}
+
+impl ProofCache {
+    /// Creates a cache storing the proofs in `dir`, which is created on the first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Creates a cache in the directory set in `SP1_PROOF_CACHE_DIR`, if it is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(SP1_PROOF_CACHE_DIR_ENV).map(Self::new)
+    }
+
+    /// The directory the proofs are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The key a proof of the program with verifying key `vk` on `stdin` is cached under.
+    pub fn key(
+        vk: &SP1VerifyingKey,
+        stdin: &SP1Stdin,
+        kind: SP1ProofKind,
+        prover: ProverType,
+    ) -> String {
+        let stdin = bincode::serialize(stdin).expect("failed to serialize stdin");
+        let mut hasher = Sha256::new();
+        hasher.update(vk.bytes32().as_bytes());
+        hasher.update(Sha256::digest(stdin));
+        hasher.update(format!("{:?}", kind).as_bytes());
+        hasher.update(format!("{:?}", prover).as_bytes());
+        hasher.update(SP1_CIRCUIT_VERSION.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+
+    /// Loads the proof cached under `key`, if any.
+    ///
+    /// Entries that can't be read or deserialized, such as ones written by an incompatible
+    /// version of the SDK, are treated as missing.
+    pub fn load(&self, key: &str) -> Option<SP1ProofWithPublicValues> {
+        let bytes = fs::read(self.path(key)).ok()?;
+        SP1ProofWithPublicValues::from_bincode(&bytes).ok()
+    }
+
+    /// Stores `proof` under `key`, replacing any previous entry.
+    ///
+    /// The proof is written to a temporary file first, so concurrent readers never observe a
+    /// partially written entry.
+    pub fn store(&self, key: &str, proof: &SP1ProofWithPublicValues) -> Result<()> {
+        write_atomically(&self.path(key), &bincode::serialize(proof)?)
+    }
+
+    /// Removes the proof cached under `key`, if any.
+    pub fn remove(&self, key: &str) -> Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Removes every cached proof.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.proof"))
+    }
+}
+
+/// The environment variable used to enable the key cache, set to the directory it is stored in.
+pub const SP1_KEY_CACHE_DIR_ENV: &str = "SP1_KEY_CACHE_DIR";
+
+/// An on-disk cache of the keys returned by [Prover::setup].
+///
+/// The keys of a program are stored under `<dir>/<circuit version>/<sha256 of the ELF>`, so they
+/// are only reused for the exact same ELF and version of SP1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl KeyCache {
+    /// Creates a cache storing the keys in `dir`, which is created on the first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// The default directory of the cache, `~/.sp1/keys`.
+    pub fn default_dir() -> PathBuf {
+        dirs::home_dir()
+            .expect("no home directory found, set the key cache directory explicitly")
+            .join(".sp1")
+            .join("keys")
+    }
+
+    /// Creates a cache in the directory set in `SP1_KEY_CACHE_DIR`, if it is set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var_os(SP1_KEY_CACHE_DIR_ENV).map(Self::new)
+    }
+
+    /// The directory the keys are stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the keys of `elf`, loading them from the cache or running `prover.setup` and
+    /// caching its result if they aren't cached yet.
+    pub fn setup(
+        &self,
+        prover: &dyn Prover<DefaultProverComponents>,
+        elf: &[u8],
+    ) -> (SP1ProvingKey, SP1VerifyingKey) {
+        if let Some(keys) = self.load(elf) {
+            return keys;
+        }
+        let (pk, vk) = prover.setup(elf);
+        if let Err(err) = self.store(elf, &pk, &vk) {
+            tracing::warn!("failed to cache the keys in {}: {}", self.dir.display(), err);
+        }
+        (pk, vk)
+    }
+
+    /// Loads the cached keys of `elf`, if any.
+    ///
+    /// Entries that can't be read or deserialized are treated as missing.
+    pub fn load(&self, elf: &[u8]) -> Option<(SP1ProvingKey, SP1VerifyingKey)> {
+        let dir = self.path(elf);
+        let pk: SP1ProvingKey = bincode::deserialize(&fs::read(dir.join("pk.bin")).ok()?).ok()?;
+        let vk = bincode::deserialize(&fs::read(dir.join("vk.bin")).ok()?).ok()?;
+        (pk.elf == elf).then_some((pk, vk))
+    }
+
+    /// Stores the keys of `elf`, replacing any previous entry.
+    pub fn store(&self, elf: &[u8], pk: &SP1ProvingKey, vk: &SP1VerifyingKey) -> Result<()> {
+        let dir = self.path(elf);
+        write_atomically(&dir.join("vk.bin"), &bincode::serialize(vk)?)?;
+        write_atomically(&dir.join("pk.bin"), &bincode::serialize(pk)?)
+    }
+
+    /// Removes every cached key.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, elf: &[u8]) -> PathBuf {
+        self.dir.join(SP1_CIRCUIT_VERSION).join(hex::encode(Sha256::digest(elf)))
+    }
+}
+
+/// The number of programs whose keys a [ProverClient](crate::ProverClient) keeps in memory by
+/// default.
+pub const DEFAULT_SETUP_CACHE_CAPACITY: usize = 4;
+
+/// An in-memory LRU cache of the keys of the most recently set up programs, keyed by the sha256
+/// of their ELF.
+pub(crate) struct SetupCache<K = (SP1ProvingKey, SP1VerifyingKey)> {
+    capacity: usize,
+    /// The entries, the most recently used first.
+    entries: Mutex<VecDeque<([u8; 32], K)>>,
+}
+
+impl<K: Clone> SetupCache<K> {
+    /// Creates a cache holding the keys of up to `capacity` programs. A capacity of zero disables
+    /// the cache.
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self { capacity, entries: Mutex::new(VecDeque::with_capacity(capacity)) }
+    }
+
+    /// Returns the keys of `elf`, running `setup` and caching its result, evicting the least
+    /// recently used entry if the cache is full, if they aren't cached yet.
+    ///
+    /// The lock isn't held while `setup` runs, so concurrent setups of different programs don't
+    /// wait for each other.
+    pub(crate) fn get_or_setup(&self, elf: &[u8], setup: impl FnOnce() -> K) -> K {
+        if self.capacity == 0 {
+            return setup();
+        }
+        let digest: [u8; 32] = Sha256::digest(elf).into();
+        {
+            let mut entries = self.entries.lock().unwrap();
+            if let Some(index) = entries.iter().position(|(d, _)| *d == digest) {
+                let entry = entries.remove(index).unwrap();
+                let keys = entry.1.clone();
+                entries.push_front(entry);
+                return keys;
+            }
+        }
+        let keys = setup();
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.iter().any(|(d, _)| *d == digest) {
+            entries.truncate(self.capacity - 1);
+            entries.push_front((digest, keys.clone()));
+        }
+        keys
+    }
+}
+
+impl<K: Clone> Default for SetupCache<K> {
+    fn default() -> Self {
+        Self::new(DEFAULT_SETUP_CACHE_CAPACITY)
+    }
+}
+
+impl<K> std::fmt::Debug for SetupCache<K> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetupCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.entries.lock().unwrap().len())
+            .finish()
+    }
+}
+
+/// Writes `bytes` to `path` through a temporary file, so that concurrent readers never observe a
+/// partially written file.
+pub(crate) fn write_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let dir = path.parent().expect("cache entries are stored in a directory");
+    fs::create_dir_all(dir)?;
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(bytes)?;
+    file.persist(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_machine::io::SP1PublicValues;
+    use sp1_prover::PlonkBn254Proof;
+
+    use super::*;
+    use crate::SP1Proof;
+
+    #[test]
+    fn test_store_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = ProofCache::new(dir.path().join("proofs"));
+        let proof = SP1ProofWithPublicValues {
+            proof: SP1Proof::Plonk(PlonkBn254Proof {
+                public_inputs: ["1".to_string(), "2".to_string()],
+                encoded_proof: "".to_string(),
+                raw_proof: "".to_string(),
+                plonk_vkey_hash: [0; 32],
+            }),
+            stdin: SP1Stdin::new(),
+            public_values: SP1PublicValues::from(&[1, 2, 3]),
+            sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+            metadata: None,
+        };
+
+        assert!(cache.load("key").is_none());
+        cache.store("key", &proof).unwrap();
+        let loaded = cache.load("key").unwrap();
+        assert_eq!(loaded.public_values.as_slice(), proof.public_values.as_slice());
+
+        cache.remove("key").unwrap();
+        assert!(cache.load("key").is_none());
+        cache.remove("key").unwrap();
+        cache.clear().unwrap();
+    }
+
+    #[test]
+    fn test_setup_cache_evicts_least_recently_used() {
+        let cache = SetupCache::new(2);
+        let setups = std::cell::Cell::new(0);
+        let setup = |elf: &[u8]| {
+            cache.get_or_setup(elf, || {
+                setups.set(setups.get() + 1);
+                elf.to_vec()
+            })
+        };
+
+        assert_eq!(setup("a".as_bytes()), b"a");
+        assert_eq!(setup("b".as_bytes()), b"b");
+        assert_eq!(setup("a".as_bytes()), b"a");
+        assert_eq!(setups.get(), 2);
+
+        // `b` is the least recently used, so caching `c` evicts it.
+        setup("c".as_bytes());
+        setup("a".as_bytes());
+        assert_eq!(setups.get(), 3);
+        setup("b".as_bytes());
+        assert_eq!(setups.get(), 4);
+
+        let disabled = SetupCache::new(0);
+        disabled.get_or_setup(b"a", || setups.set(setups.get() + 1));
+        disabled.get_or_setup(b"a", || setups.set(setups.get() + 1));
+        assert_eq!(setups.get(), 6);
+    }
+}
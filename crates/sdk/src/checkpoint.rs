@@ -0,0 +1,133 @@
+//! # Proof Checkpoints
+//!
+//! Persists the progress of a local proof to disk, so that a proof interrupted by a crash or a
+//! preemption resumes from where it stopped instead of starting over.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use sp1_core_machine::{io::SP1Stdin, SP1_CIRCUIT_VERSION};
+use sp1_prover::{HashableKey, SP1VerifyingKey};
+use sp1_stark::SP1ProverOpts;
+
+use crate::{cache::write_atomically, SP1ProofKind, SP1ProofWithPublicValues};
+
+/// The progress of a proof of one program on one input, persisted to a directory.
+///
+/// The directory holds every shard proof generated so far, then the core proof once every shard
+/// is proven, then the compressed proof once the shard proofs are compressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofCheckpoint {
+    dir: PathBuf,
+}
+
+impl ProofCheckpoint {
+    /// The checkpoint of the proof of the program with verifying key `vk` on `stdin` with the
+    /// prover options `opts`, stored in a subdirectory of `root` specific to the program, the
+    /// input, the options and the circuit version.
+    ///
+    /// The options are part of the key because the shard size and the split options change how
+    /// the execution is sharded, so the shard proofs of one set of options don't resume another.
+    pub fn new(
+        root: impl AsRef<Path>,
+        vk: &SP1VerifyingKey,
+        stdin: &SP1Stdin,
+        opts: &SP1ProverOpts,
+    ) -> Self {
+        let stdin = bincode::serialize(stdin).expect("failed to serialize stdin");
+        let opts = bincode::serialize(opts).expect("failed to serialize the prover options");
+        let mut hasher = Sha256::new();
+        hasher.update(vk.bytes32().as_bytes());
+        hasher.update(Sha256::digest(stdin));
+        hasher.update(Sha256::digest(opts));
+        hasher.update(SP1_CIRCUIT_VERSION.as_bytes());
+        Self { dir: root.as_ref().join(hex::encode(hasher.finalize())) }
+    }
+
+    /// The directory the checkpoint is stored in.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The directory the shard proofs are persisted to while proving the shards.
+    pub fn shard_proofs_dir(&self) -> PathBuf {
+        self.dir.join("shards")
+    }
+
+    /// Loads the furthest proof persisted on the way to a proof of the given kind, if any.
+    pub fn resume(&self, kind: SP1ProofKind) -> Option<SP1ProofWithPublicValues> {
+        let compressed =
+            (kind != SP1ProofKind::Core).then(|| self.load(SP1ProofKind::Compressed)).flatten();
+        compressed.or_else(|| self.load(SP1ProofKind::Core))
+    }
+
+    /// Loads the persisted proof of the given kind, if any.
+    ///
+    /// Proofs that can't be read or deserialized are treated as missing.
+    pub fn load(&self, kind: SP1ProofKind) -> Option<SP1ProofWithPublicValues> {
+        let bytes = fs::read(self.path(kind)).ok()?;
+        let proof = SP1ProofWithPublicValues::from_bincode(&bytes).ok()?;
+        (SP1ProofKind::from(&proof.proof) == kind).then_some(proof)
+    }
+
+    /// Persists `proof`, replacing any previous proof of the same kind.
+    pub fn store(&self, proof: &SP1ProofWithPublicValues) -> Result<()> {
+        write_atomically(&self.path(SP1ProofKind::from(&proof.proof)), &bincode::serialize(proof)?)
+    }
+
+    /// Removes the checkpoint.
+    pub fn clear(&self) -> Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, kind: SP1ProofKind) -> PathBuf {
+        self.dir.join(format!("{:?}.proof", kind).to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_machine::io::SP1PublicValues;
+
+    use super::*;
+    use crate::{Prover, SP1Proof};
+
+    #[test]
+    fn test_store_and_resume() {
+        let dir = tempfile::tempdir().unwrap();
+        let (_, vk) = crate::MockProver::new().setup(include_bytes!(
+            "../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf"
+        ));
+        let opts = SP1ProverOpts::default();
+        let checkpoint = ProofCheckpoint::new(dir.path(), &vk, &SP1Stdin::new(), &opts);
+        let proof = SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(vec![]),
+            stdin: SP1Stdin::new(),
+            public_values: SP1PublicValues::from(&[1, 2, 3]),
+            sp1_version: SP1_CIRCUIT_VERSION.to_string(),
+            metadata: None,
+        };
+
+        assert!(checkpoint.resume(SP1ProofKind::Groth16).is_none());
+        checkpoint.store(&proof).unwrap();
+        assert!(checkpoint.load(SP1ProofKind::Compressed).is_none());
+        let resumed = checkpoint.resume(SP1ProofKind::Groth16).unwrap();
+        assert!(matches!(resumed.proof, SP1Proof::Core(_)));
+
+        let mut other_opts = opts;
+        other_opts.core_opts.shard_size /= 2;
+        let other = ProofCheckpoint::new(dir.path(), &vk, &SP1Stdin::new(), &other_opts);
+        assert_ne!(other.dir(), checkpoint.dir());
+        assert!(other.resume(SP1ProofKind::Core).is_none());
+
+        checkpoint.clear().unwrap();
+        assert!(checkpoint.resume(SP1ProofKind::Core).is_none());
+    }
+}
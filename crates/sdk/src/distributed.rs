@@ -0,0 +1,541 @@
+//! # Distributed Proving
+//!
+//! Splits the core proof of a program across remote [Worker] processes.
+//!
+//! The [Coordinator] executes the program locally and generates the records of its shards, then
+//! dispatches them to the workers in two rounds, as the core prover does on a single machine:
+//! first to commit to every shard, then, once the commitments are observed, to prove every shard.
+//! The shard proofs are compressed, shrunk and wrapped locally.
+//!
+//! Workers and coordinators talk over TCP, with every message sent as its length, a little-endian
+//! `u64`, followed by its bincode encoding.
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    sync::{
+        mpsc::{self, sync_channel},
+        Arc, Mutex,
+    },
+};
+
+use anyhow::{anyhow, Context, Result};
+use p3_baby_bear::BabyBear;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp1_core_executor::{report_stage, ExecutionRecord, Program, ProofStage, SP1Context};
+use sp1_core_machine::{
+    io::{SP1PublicValues, SP1Stdin},
+    utils::{execute_checkpoints, ShardRecordGenerator},
+    SP1_CIRCUIT_VERSION,
+};
+use sp1_prover::{
+    components::DefaultProverComponents, CoreSC, SP1CoreProof, SP1CoreProofData, SP1Prover,
+};
+use sp1_stark::{
+    Challenger, Com, MachineProver, MachineRecord, SP1CoreOpts, ShardProof, StarkGenericConfig,
+    StarkProvingKey,
+};
+
+use crate::{
+    install::InstallConfig,
+    provers::{proof_metadata, Deadline, ProofOpts, ProverType, StageRecorder},
+    CpuProver, Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1VerifyingKey,
+};
+
+/// A request from a [Coordinator] to a [Worker].
+#[derive(Serialize, Deserialize)]
+pub enum WorkerRequest {
+    /// Sets the proving key of the program the following requests are about. Sent first on every
+    /// connection.
+    Setup {
+        /// The circuit version of the coordinator, which must match the worker's.
+        version: String,
+        /// The proving key of the program.
+        pk: Box<SP1ProvingKey>,
+    },
+    /// Commits to the traces of a shard.
+    Commit(Box<ExecutionRecord>),
+    /// Sets the commitments and public values of every shard, in order, observed by the
+    /// challenger proofs are opened with.
+    Observe(Vec<(Com<CoreSC>, Vec<BabyBear>)>),
+    /// Proves a shard.
+    Prove(Box<ExecutionRecord>),
+}
+
+/// The response of a [Worker] to a [WorkerRequest].
+#[derive(Serialize, Deserialize)]
+pub enum WorkerResponse {
+    /// The request was applied.
+    Ready,
+    /// The commitment to the traces of a shard, and the public values observed along with it.
+    Committed {
+        /// The commitment to the main traces of the shard.
+        commit: Com<CoreSC>,
+        /// The public values of the shard.
+        public_values: Vec<BabyBear>,
+    },
+    /// The proof of a shard.
+    Proved(Box<ShardProof<CoreSC>>),
+    /// The request failed.
+    Error(String),
+}
+
+/// A worker process, committing to and proving the shards dispatched by a [Coordinator].
+///
+/// ### Examples
+/// ```no_run
+/// use sp1_sdk::distributed::Worker;
+/// use std::net::TcpListener;
+///
+/// let listener = TcpListener::bind("0.0.0.0:3100").unwrap();
+/// Worker::new().serve(listener).unwrap();
+/// ```
+pub struct Worker {
+    prover: Arc<SP1Prover<DefaultProverComponents>>,
+}
+
+impl Worker {
+    /// Creates a new [Worker].
+    pub fn new() -> Self {
+        Self { prover: Arc::new(SP1Prover::new()) }
+    }
+
+    /// Serves the coordinators connecting to `listener`, each on its own thread, until the
+    /// listener fails.
+    pub fn serve(self, listener: TcpListener) -> Result<()> {
+        for stream in listener.incoming() {
+            let stream = stream?;
+            let prover = self.prover.clone();
+            std::thread::spawn(move || {
+                let peer = stream.peer_addr().map(|addr| addr.to_string()).unwrap_or_default();
+                if let Err(err) = serve_connection(&prover, stream) {
+                    tracing::warn!("connection with coordinator {} failed: {}", peer, err);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for Worker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Answers the requests of a coordinator on `stream` until it disconnects.
+fn serve_connection(prover: &SP1Prover, mut stream: TcpStream) -> Result<()> {
+    let mut pk: Option<StarkProvingKey<CoreSC>> = None;
+    let mut challenger: Option<Challenger<CoreSC>> = None;
+    loop {
+        let request = match read_message::<WorkerRequest>(&mut stream) {
+            Err(err) if is_disconnect(&err) => return Ok(()),
+            request => request?,
+        };
+        let response = match request {
+            WorkerRequest::Setup { version, .. } if version != SP1_CIRCUIT_VERSION => {
+                WorkerResponse::Error(format!(
+                    "version mismatch: the worker runs {} but the coordinator runs {}",
+                    SP1_CIRCUIT_VERSION, version
+                ))
+            }
+            WorkerRequest::Setup { pk: setup, .. } => {
+                pk = Some(setup.pk);
+                challenger = None;
+                WorkerResponse::Ready
+            }
+            WorkerRequest::Observe(observations) => match &pk {
+                Some(pk) => {
+                    let core_prover = &prover.core_prover;
+                    let mut observed = core_prover.config().challenger();
+                    pk.observe_into(&mut observed);
+                    for (commit, public_values) in observations {
+                        core_prover.observe(&mut observed, commit, &public_values);
+                    }
+                    challenger = Some(observed);
+                    WorkerResponse::Ready
+                }
+                None => WorkerResponse::Error("no proving key was set up".to_string()),
+            },
+            WorkerRequest::Commit(record) => {
+                let core_prover = &prover.core_prover;
+                let public_values = record.public_values::<BabyBear>()
+                    [0..core_prover.machine().num_pv_elts()]
+                    .to_vec();
+                let traces = core_prover.generate_traces(&record);
+                let data = core_prover.commit(*record, traces);
+                WorkerResponse::Committed { commit: data.main_commit, public_values }
+            }
+            WorkerRequest::Prove(record) => match (&pk, &challenger) {
+                (Some(pk), Some(challenger)) => {
+                    let core_prover = &prover.core_prover;
+                    let traces = core_prover.generate_traces(&record);
+                    let data = core_prover.commit(*record, traces);
+                    match core_prover.open(pk, data, &mut challenger.clone()) {
+                        Err(err) => WorkerResponse::Error(err.to_string()),
+                        Ok(proof) => WorkerResponse::Proved(Box::new(proof)),
+                    }
+                }
+                _ => WorkerResponse::Error("the commitments were not observed".to_string()),
+            },
+        };
+        write_message(&mut stream, &response)?;
+    }
+}
+
+/// Dispatches the shards of core proofs to remote [Worker]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coordinator {
+    workers: Vec<String>,
+}
+
+impl Coordinator {
+    /// Creates a coordinator dispatching the shards to the workers listening at the given
+    /// addresses, e.g. `10.0.0.2:3100`.
+    pub fn new(workers: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { workers: workers.into_iter().map(Into::into).collect() }
+    }
+
+    /// The addresses of the workers.
+    pub fn workers(&self) -> &[String] {
+        &self.workers
+    }
+
+    /// Generates the core proof of the program with proving key `pk` on `stdin`, proving its
+    /// shards on the workers.
+    ///
+    /// Fails if any worker can't be reached or fails to prove a shard.
+    pub fn prove_core<'a>(
+        &self,
+        prover: &'a SP1Prover,
+        pk: &SP1ProvingKey,
+        stdin: &SP1Stdin,
+        opts: SP1CoreOpts,
+        mut context: SP1Context<'a>,
+    ) -> Result<SP1CoreProof> {
+        if self.workers.is_empty() {
+            return Err(anyhow!("no workers to dispatch the shards to"));
+        }
+        context.subproof_verifier.replace(Arc::new(prover));
+        let program = Program::from(&pk.elf)
+            .map_err(|err| anyhow!("failed to decode the program: {}", err))?;
+        let (mut checkpoints, public_values_stream, cycles) =
+            execute_checkpoints(program.clone(), stdin, opts, context)?;
+        let machine = prover.core_prover.machine();
+        let setup = WorkerRequest::Setup {
+            version: SP1_CIRCUIT_VERSION.to_string(),
+            pk: Box::new(pk.clone()),
+        };
+
+        // Commit to every shard, regenerating the shards from the checkpoints.
+        let committed = self.dispatch(&[setup], |send| {
+            let mut generator = ShardRecordGenerator::new(program.clone(), opts);
+            for (checkpoint, done) in checkpoints.iter_mut() {
+                for record in generator.generate(machine, checkpoint, *done) {
+                    send(WorkerRequest::Commit(Box::new(record)))?;
+                }
+            }
+            Ok(())
+        })?;
+        let observations = committed
+            .into_iter()
+            .map(|response| match response {
+                WorkerResponse::Committed { commit, public_values } => Ok((commit, public_values)),
+                _ => Err(anyhow!("unexpected response to a commit request")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        tracing::info!("committed to {} shards", observations.len());
+
+        // Prove every shard against the challenger that observed all the commitments.
+        let setup = WorkerRequest::Setup {
+            version: SP1_CIRCUIT_VERSION.to_string(),
+            pk: Box::new(pk.clone()),
+        };
+        let proved = self.dispatch(&[setup, WorkerRequest::Observe(observations)], |send| {
+            let mut generator = ShardRecordGenerator::new(program.clone(), opts);
+            for (checkpoint, done) in checkpoints.iter_mut() {
+                for record in generator.generate(machine, checkpoint, *done) {
+                    send(WorkerRequest::Prove(Box::new(record)))?;
+                }
+            }
+            Ok(())
+        })?;
+        let shard_proofs = proved
+            .into_iter()
+            .map(|response| match response {
+                WorkerResponse::Proved(proof) => Ok(*proof),
+                _ => Err(anyhow!("unexpected response to a prove request")),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(SP1CoreProof {
+            proof: SP1CoreProofData(shard_proofs),
+            stdin: stdin.clone(),
+            public_values: SP1PublicValues::from(&public_values_stream),
+            cycles,
+        })
+    }
+
+    /// Sends the requests passed to `send` by `generate` to the workers, each worker sending the
+    /// requests of `preamble` first. Returns the responses in the order of the requests.
+    fn dispatch(
+        &self,
+        preamble: &[WorkerRequest],
+        generate: impl FnOnce(&mut dyn FnMut(WorkerRequest) -> Result<()>) -> Result<()>,
+    ) -> Result<Vec<WorkerResponse>> {
+        let (jobs_tx, jobs_rx) = sync_channel::<(usize, WorkerRequest)>(self.workers.len());
+        let jobs_rx = Arc::new(Mutex::new(jobs_rx));
+        let (results_tx, results_rx) = mpsc::channel::<Result<(usize, WorkerResponse)>>();
+
+        std::thread::scope(|s| {
+            for worker in &self.workers {
+                let jobs_rx = jobs_rx.clone();
+                let results_tx = results_tx.clone();
+                s.spawn(move || {
+                    let result =
+                        WorkerConnection::connect(worker, preamble).and_then(|mut conn| loop {
+                            let job = jobs_rx.lock().unwrap().recv();
+                            let Ok((index, request)) = job else { return Ok(()) };
+                            let response = conn.call(&request)?;
+                            let _ = results_tx.send(Ok((index, response)));
+                        });
+                    if let Err(err) = result {
+                        let _ = results_tx.send(Err(err.context(format!("worker {worker}"))));
+                    }
+                });
+            }
+            // Once every worker is gone, sending more requests fails instead of blocking.
+            drop(jobs_rx);
+            drop(results_tx);
+
+            let mut count = 0;
+            let generated = generate(&mut |request| {
+                jobs_tx.send((count, request)).map_err(|_| anyhow!("every worker disconnected"))?;
+                count += 1;
+                Ok(())
+            });
+            drop(jobs_tx);
+
+            let mut responses: Vec<Option<WorkerResponse>> = Vec::new();
+            let mut failure = None;
+            for result in results_rx {
+                match result {
+                    Err(err) => failure = failure.or(Some(err)),
+                    Ok((index, response)) => {
+                        if responses.len() <= index {
+                            responses.resize_with(index + 1, || None);
+                        }
+                        responses[index] = Some(response);
+                    }
+                }
+            }
+            if let Some(err) = failure {
+                return Err(err);
+            }
+            generated?;
+            responses.resize_with(count, || None);
+            responses
+                .into_iter()
+                .map(|response| match response {
+                    Some(WorkerResponse::Error(err)) => Err(anyhow!("worker failed: {}", err)),
+                    Some(response) => Ok(response),
+                    None => Err(anyhow!("a request was left unanswered")),
+                })
+                .collect()
+        })
+    }
+}
+
+/// A connection from a [Coordinator] to a [Worker].
+struct WorkerConnection {
+    stream: TcpStream,
+}
+
+impl WorkerConnection {
+    /// Connects to the worker at `addr`, sending it the requests of `preamble`.
+    fn connect(addr: &str, preamble: &[WorkerRequest]) -> Result<Self> {
+        let stream = TcpStream::connect(addr).context("failed to connect")?;
+        stream.set_nodelay(true)?;
+        let mut conn = Self { stream };
+        for request in preamble {
+            match conn.call(request)? {
+                WorkerResponse::Ready => {}
+                WorkerResponse::Error(err) => return Err(anyhow!("setup failed: {}", err)),
+                _ => return Err(anyhow!("unexpected response to a setup request")),
+            }
+        }
+        Ok(conn)
+    }
+
+    /// Sends `request` and waits for its response.
+    fn call(&mut self, request: &WorkerRequest) -> Result<WorkerResponse> {
+        write_message(&mut self.stream, request)?;
+        read_message(&mut self.stream)
+    }
+}
+
+/// Writes `message` to `stream` as its length followed by its bincode encoding.
+fn write_message<T: Serialize>(stream: &mut impl Write, message: &T) -> Result<()> {
+    let bytes = bincode::serialize(message)?;
+    stream.write_all(&(bytes.len() as u64).to_le_bytes())?;
+    stream.write_all(&bytes)?;
+    stream.flush()?;
+    Ok(())
+}
+
+/// Reads a message written with [write_message] from `stream`.
+fn read_message<T: DeserializeOwned>(stream: &mut impl Read) -> Result<T> {
+    let mut len = [0u8; 8];
+    stream.read_exact(&mut len)?;
+    let mut bytes = vec![0u8; u64::from_le_bytes(len) as usize];
+    stream.read_exact(&mut bytes)?;
+    Ok(bincode::deserialize(&bytes)?)
+}
+
+/// Whether `err` is the peer closing the connection between two messages.
+fn is_disconnect(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<std::io::Error>().is_some_and(|err| err.kind() == ErrorKind::UnexpectedEof)
+}
+
+/// An implementation of [crate::ProverClient] proving the shards on remote [Worker]s through a
+/// [Coordinator], and the rest of the proof locally.
+pub struct DistributedProver {
+    cpu: CpuProver,
+    coordinator: Coordinator,
+}
+
+impl DistributedProver {
+    /// Creates a new [DistributedProver] dispatching the shards with `coordinator`.
+    pub fn new(coordinator: Coordinator) -> Self {
+        Self { cpu: CpuProver::new(), coordinator }
+    }
+
+    /// Sets the root directory under which the circuit artifacts are installed and looked up.
+    pub fn with_circuits_dir(mut self, circuits_dir: impl Into<PathBuf>) -> Self {
+        self.cpu = self.cpu.with_circuits_dir(circuits_dir);
+        self
+    }
+
+    /// Sets where the circuit artifacts are installed, looked up and downloaded from.
+    pub fn with_install_config(mut self, install: InstallConfig) -> Self {
+        self.cpu = self.cpu.with_install_config(install);
+        self
+    }
+
+    /// Proves the shards on the workers, and the stages that follow locally.
+    fn prove_stages<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        mut opts: ProofOpts,
+        mut context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        opts.sp1_prover_opts = opts.fitted_prover_opts()?;
+        let recorder = StageRecorder::default();
+        let progress = recorder.callback(context.progress.take());
+        let cancellation_token = context.cancellation_token.clone();
+
+        // Generate the core proof on the workers.
+        let proof = report_stage(Some(&progress), ProofStage::Core, || {
+            self.coordinator.prove_core(
+                self.sp1_prover(),
+                pk,
+                &stdin,
+                opts.sp1_prover_opts.core_opts,
+                context,
+            )
+        })?;
+        let metadata = proof_metadata(self.id(), proof.cycles, proof.proof.0.len());
+        let proof = SP1ProofWithPublicValues {
+            proof: SP1Proof::Core(proof.proof.0),
+            stdin,
+            public_values: proof.public_values,
+            sp1_version: self.version().to_string(),
+            metadata: Some(metadata),
+        };
+        if kind == SP1ProofKind::Core {
+            return Ok(recorder.finish(proof));
+        }
+
+        let proof = self.cpu.reduce(
+            &pk.vk,
+            proof,
+            kind,
+            opts,
+            cancellation_token.as_ref(),
+            Some(&progress),
+        )?;
+        Ok(recorder.finish(proof))
+    }
+}
+
+impl Prover<DefaultProverComponents> for DistributedProver {
+    fn id(&self) -> ProverType {
+        ProverType::Distributed
+    }
+
+    fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
+        self.cpu.setup(elf)
+    }
+
+    fn sp1_prover(&self) -> &SP1Prover<DefaultProverComponents> {
+        self.cpu.sp1_prover()
+    }
+
+    fn install_config(&self) -> InstallConfig {
+        self.cpu.install_config()
+    }
+
+    fn prove<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        mut context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        let deadline = opts.timeout.map(|timeout| Deadline::start(timeout, &mut context));
+        let result = self.prove_stages(pk, stdin, opts, context, kind);
+        match deadline {
+            Some(deadline) => deadline.finish(result),
+            None => result,
+        }
+    }
+
+    fn wrap(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
+        kind: SP1ProofKind,
+        opts: ProofOpts,
+    ) -> Result<SP1ProofWithPublicValues> {
+        self.cpu.wrap(vk, proof, kind, opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{utils, ProverClient};
+
+    #[test]
+    fn test_e2e_prove_core_distributed() {
+        utils::setup_logger();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || Worker::new().serve(listener));
+
+        // Two connections to the same worker, to dispatch to several workers.
+        let client = ProverClient::distributed([addr.clone(), addr]);
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).run().unwrap();
+        client.verify(&proof, &vk).unwrap();
+    }
+}
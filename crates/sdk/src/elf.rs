@@ -0,0 +1,99 @@
+//! # ELF Files
+//!
+//! Loading programs straight from their ELF file, memory-mapped rather than read into memory, so
+//! that large programs aren't copied around before the setup.
+
+use std::{fs::File, ops::Deref, path::Path};
+
+use memmap2::Mmap;
+use thiserror::Error;
+
+/// The `e_machine` of RISC-V executables.
+const EM_RISCV: u16 = 0xF3;
+
+/// The `e_type` of executables.
+const ET_EXEC: u16 = 2;
+
+/// An error opening an ELF file.
+#[derive(Error, Debug)]
+pub enum ElfError {
+    #[error("failed to open the ELF file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("not an ELF file")]
+    NotElf,
+    #[error("the ELF file must be a 32-bit little-endian RISC-V executable")]
+    Unsupported,
+}
+
+/// A memory-mapped ELF file, dereferencing to its bytes.
+///
+/// Only the header is read when the file is opened, the rest of the file is paged in as it is
+/// accessed. The file must not be modified while it is mapped.
+#[derive(Debug)]
+pub struct MappedElf {
+    mmap: Mmap,
+}
+
+impl MappedElf {
+    /// Maps the ELF file at `path`, checking that it is a 32-bit little-endian RISC-V
+    /// executable.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ElfError> {
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only, and the file is documented to not be modified while
+        // it is mapped.
+        let mmap = unsafe { Mmap::map(&file)? };
+        validate_header(&mmap)?;
+        Ok(Self { mmap })
+    }
+}
+
+impl Deref for MappedElf {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.mmap
+    }
+}
+
+impl AsRef<[u8]> for MappedElf {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+/// Checks the identification, type and machine of the ELF header of `bytes`.
+fn validate_header(bytes: &[u8]) -> Result<(), ElfError> {
+    if bytes.len() < 20 || bytes[..4] != *b"\x7fELF" {
+        return Err(ElfError::NotElf);
+    }
+    let (class, data) = (bytes[4], bytes[5]);
+    let e_type = u16::from_le_bytes([bytes[16], bytes[17]]);
+    let e_machine = u16::from_le_bytes([bytes[18], bytes[19]]);
+    if class != 1 || data != 1 || e_type != ET_EXEC || e_machine != EM_RISCV {
+        return Err(ElfError::Unsupported);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn test_open() {
+        let mut header = vec![0u8; 52];
+        header[..6].copy_from_slice(b"\x7fELF\x01\x01");
+        header[16..20].copy_from_slice(&[2, 0, 0xF3, 0]);
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&header).unwrap();
+        let elf = MappedElf::open(file.path()).unwrap();
+        assert_eq!(&*elf, header.as_slice());
+
+        header[18] = 0x3E;
+        assert!(matches!(validate_header(&header), Err(ElfError::Unsupported)));
+        assert!(matches!(validate_header(b"#!/bin/sh"), Err(ElfError::NotElf)));
+        assert!(matches!(MappedElf::open("/nonexistent"), Err(ElfError::Io(_))));
+    }
+}
@@ -0,0 +1,158 @@
+//! # Errors
+//!
+//! The [SP1SdkError] returned by the public APIs of the SDK, classifying failures so that callers
+//! can tell missing artifacts apart from failed executions, failed proofs or network issues.
+
+use std::fmt::{Debug, Display};
+
+use sp1_core_executor::{ExecutionError, HookRegistrationError};
+use sp1_core_machine::utils::SP1CoreProverError;
+use thiserror::Error;
+
+#[cfg(feature = "network")]
+use crate::network::NetworkError;
+use crate::{
+    aggregation::AggregationError, elf::ElfError, install::InstallError, memory::MemoryBudgetError,
+    provers::ProofTimedOut, SP1VerificationError,
+};
+
+/// An error returned by the SDK.
+#[derive(Error, Debug)]
+pub enum SP1SdkError {
+    /// The circuit artifacts couldn't be installed.
+    #[error("failed to install the circuit artifacts: {0}")]
+    Install(#[from] InstallError),
+    /// The program failed to execute, e.g. it panicked, exceeded its cycle limit or was
+    /// cancelled.
+    #[error("failed to execute the program: {0}")]
+    Execution(#[from] ExecutionError),
+    /// The execution succeeded but generating its proof failed.
+    #[error("failed to generate the proof: {0}")]
+    Proving(anyhow::Error),
+    /// Turning an existing proof into a proof of another kind failed.
+    #[error("failed to wrap the proof: {0}")]
+    Wrapping(anyhow::Error),
+    /// A local proof wasn't done within its timeout.
+    #[error("{0}")]
+    TimedOut(#[from] ProofTimedOut),
+    /// A proof failed to verify.
+    #[error("failed to verify the proof: {0}")]
+    Verification(#[from] SP1VerificationError),
+    /// A request to the prover network failed.
+    #[cfg(feature = "network")]
+    #[error("prover network request failed: {0}")]
+    Network(#[from] NetworkError),
+    /// The request can't be fulfilled as configured, e.g. its inputs or options are invalid.
+    #[error("invalid request: {0}")]
+    InvalidRequest(anyhow::Error),
+}
+
+impl SP1SdkError {
+    /// Classifies an error raised while proving, falling back to [SP1SdkError::Proving].
+    pub(crate) fn proving(err: anyhow::Error) -> Self {
+        Self::classify(err).unwrap_or_else(Self::Proving)
+    }
+
+    /// Classifies an error raised while wrapping, falling back to [SP1SdkError::Wrapping].
+    pub(crate) fn wrapping(err: anyhow::Error) -> Self {
+        Self::classify(err).unwrap_or_else(Self::Wrapping)
+    }
+
+    /// Returns a reference to the underlying error if it is of type `E`, like
+    /// [anyhow::Error::downcast_ref].
+    pub fn downcast_ref<E: Display + Debug + Send + Sync + 'static>(&self) -> Option<&E> {
+        match self {
+            Self::Proving(err) | Self::Wrapping(err) | Self::InvalidRequest(err) => {
+                err.downcast_ref()
+            }
+            Self::Install(err) => (err as &dyn std::any::Any).downcast_ref(),
+            Self::Execution(err) => (err as &dyn std::any::Any).downcast_ref(),
+            Self::TimedOut(err) => (err as &dyn std::any::Any).downcast_ref(),
+            Self::Verification(err) => (err as &dyn std::any::Any).downcast_ref(),
+            #[cfg(feature = "network")]
+            Self::Network(err) => (err as &dyn std::any::Any).downcast_ref(),
+        }
+    }
+
+    /// Recovers the typed errors raised by the provers, which return them as [anyhow::Error].
+    fn classify(err: anyhow::Error) -> Result<Self, anyhow::Error> {
+        let err = match err.downcast::<SP1SdkError>() {
+            Ok(err) => return Ok(err),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<SP1CoreProverError>() {
+            Ok(SP1CoreProverError::ExecutionError(err)) => return Ok(Self::Execution(err)),
+            Ok(err) => anyhow::Error::from(err),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<ExecutionError>() {
+            Ok(err) => return Ok(Self::Execution(err)),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<InstallError>() {
+            Ok(err) => return Ok(Self::Install(err)),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<ProofTimedOut>() {
+            Ok(err) => return Ok(Self::TimedOut(err)),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<SP1VerificationError>() {
+            Ok(err) => return Ok(Self::Verification(err)),
+            Err(err) => err,
+        };
+        #[cfg(feature = "network")]
+        let err = match err.downcast::<NetworkError>() {
+            Ok(err) => return Ok(Self::Network(err)),
+            Err(err) => err,
+        };
+        if err.is::<MemoryBudgetError>()
+            || err.is::<HookRegistrationError>()
+            || err.is::<AggregationError>()
+            || err.is::<ElfError>()
+        {
+            return Ok(Self::InvalidRequest(err));
+        }
+        Err(err)
+    }
+}
+
+impl From<anyhow::Error> for SP1SdkError {
+    fn from(err: anyhow::Error) -> Self {
+        Self::proving(err)
+    }
+}
+
+macro_rules! impl_invalid_request {
+    ($($err:ty),*) => {$(
+        impl From<$err> for SP1SdkError {
+            fn from(err: $err) -> Self {
+                Self::InvalidRequest(err.into())
+            }
+        }
+    )*};
+}
+
+impl_invalid_request!(MemoryBudgetError, HookRegistrationError, AggregationError, ElfError);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify() {
+        let err = SP1SdkError::from(anyhow::Error::from(ExecutionError::Cancelled));
+        assert!(matches!(err, SP1SdkError::Execution(ExecutionError::Cancelled)));
+
+        let err = SP1CoreProverError::ExecutionError(ExecutionError::HaltWithNonZeroExitCode(1));
+        let err = SP1SdkError::from(anyhow::Error::from(err));
+        assert!(matches!(err, SP1SdkError::Execution(ExecutionError::HaltWithNonZeroExitCode(1))));
+
+        let err = SP1SdkError::from(anyhow::Error::from(AggregationError::Empty));
+        assert_eq!(err.downcast_ref::<AggregationError>(), Some(&AggregationError::Empty));
+        assert!(matches!(err, SP1SdkError::InvalidRequest(_)));
+
+        let err = SP1SdkError::wrapping(anyhow::anyhow!("shrink failed"));
+        assert!(matches!(err, SP1SdkError::Wrapping(_)));
+    }
+}
@@ -0,0 +1,321 @@
+//! # Cost Estimation
+//!
+//! Estimates the cost of proving a program by executing it and pricing the resulting
+//! [ExecutionReport] with a [GasModel], and how long proving it locally takes with a
+//! [HostCalibration].
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use p3_baby_bear::BabyBear;
+use p3_field::AbstractField;
+use serde::{Deserialize, Serialize};
+use sp1_core_executor::{syscalls::SyscallCode, ExecutionReport};
+use sp1_core_machine::{riscv::cost::CostEstimator, SP1_CIRCUIT_VERSION};
+use sp1_stark::SP1CoreOpts;
+
+use crate::{cache::write_atomically, memory::MemoryBudget, SP1ProofMode};
+
+/// A pricing model turning the cycle and syscall counts of an execution into gas, and gas into a
+/// cost.
+///
+/// The default model charges one unit of gas per cycle, nothing extra for syscalls, and prices
+/// one unit of gas at `1.0`, so that the cost is the number of cycles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GasModel {
+    /// The gas charged for every execution, regardless of its size.
+    pub base_gas: u64,
+    /// The gas charged per cycle.
+    pub gas_per_cycle: u64,
+    /// The gas charged per invocation of a syscall, on top of the cycles it takes. Syscalls
+    /// missing from the map are charged [Self::default_syscall_gas].
+    pub syscall_gas: HashMap<SyscallCode, u64>,
+    /// The gas charged per invocation of a syscall missing from [Self::syscall_gas].
+    pub default_syscall_gas: u64,
+    /// The price of one unit of gas, in the unit costs are quoted in.
+    pub price_per_gas: f64,
+}
+
+impl GasModel {
+    /// Sets the gas charged per invocation of `syscall`.
+    pub fn with_syscall_gas(mut self, syscall: SyscallCode, gas: u64) -> Self {
+        self.syscall_gas.insert(syscall, gas);
+        self
+    }
+
+    /// Prices the execution described by `report`.
+    pub fn estimate(&self, report: &ExecutionReport) -> CostEstimate {
+        let cycles = report.total_instruction_count();
+        let syscalls = report.total_syscall_count();
+        let syscall_gas = report
+            .syscall_counts
+            .iter()
+            .map(|(syscall, count)| {
+                let gas =
+                    self.syscall_gas.get(syscall).copied().unwrap_or(self.default_syscall_gas);
+                gas.saturating_mul(*count)
+            })
+            .fold(0u64, u64::saturating_add);
+        let gas = self
+            .base_gas
+            .saturating_add(self.gas_per_cycle.saturating_mul(cycles))
+            .saturating_add(syscall_gas);
+        CostEstimate {
+            cycles,
+            syscalls,
+            gas,
+            trace_area: report.estimate_area(),
+            cost: gas as f64 * self.price_per_gas,
+        }
+    }
+}
+
+impl Default for GasModel {
+    fn default() -> Self {
+        Self {
+            base_gas: 0,
+            gas_per_cycle: 1,
+            syscall_gas: HashMap::new(),
+            default_syscall_gas: 0,
+            price_per_gas: 1.0,
+        }
+    }
+}
+
+/// The estimated cost of proving an execution, as priced by a [GasModel].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    /// The number of cycles executed.
+    pub cycles: u64,
+    /// The number of syscalls invoked.
+    pub syscalls: u64,
+    /// The gas charged for the execution.
+    pub gas: u64,
+    /// The estimated trace area of the proof, see [CostEstimator::estimate_area].
+    pub trace_area: u64,
+    /// The cost of the gas, in the unit of [GasModel::price_per_gas].
+    pub cost: f64,
+}
+
+/// The proving throughput of a host, used to predict how long proving an execution locally
+/// takes with [Self::predict].
+///
+/// The defaults are coarse figures for a 16-core x86 machine. [Self::measure] scales them by a
+/// short benchmark of the host's field arithmetic, and [Self::host] caches the result in
+/// `~/.sp1/calibration.json` so the benchmark runs once per machine and version of SP1.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HostCalibration {
+    /// The trace area proven per second in the core stage, see [CostEstimator::estimate_area].
+    pub core_area_per_sec: f64,
+    /// The time taken to compress each shard proof.
+    pub compress_per_shard: Duration,
+    /// The time taken to shrink and wrap the compressed proof into a BN254-friendly proof.
+    pub shrink_wrap: Duration,
+    /// The time taken to generate the Plonk proof of the wrapped proof.
+    pub plonk: Duration,
+    /// The time taken to generate the Groth16 proof of the wrapped proof.
+    pub groth16: Duration,
+    /// The peak memory of generating the Plonk proof, in bytes.
+    pub plonk_memory: u64,
+    /// The peak memory of generating the Groth16 proof, in bytes.
+    pub groth16_memory: u64,
+}
+
+/// The field operations per second of the machine the [HostCalibration] defaults correspond to,
+/// as measured by [HostCalibration::measure].
+const REFERENCE_FIELD_OPS_PER_SEC: f64 = 4.0e9;
+
+/// The field operations run by each thread of the benchmark of [HostCalibration::measure].
+const BENCHMARK_OPS_PER_THREAD: u64 = 1 << 24;
+
+impl Default for HostCalibration {
+    fn default() -> Self {
+        Self {
+            core_area_per_sec: 2.0e9,
+            compress_per_shard: Duration::from_secs(4),
+            shrink_wrap: Duration::from_secs(30),
+            plonk: Duration::from_secs(150),
+            groth16: Duration::from_secs(60),
+            plonk_memory: 24 << 30,
+            groth16_memory: 12 << 30,
+        }
+    }
+}
+
+impl HostCalibration {
+    /// Benchmarks the field arithmetic of the host on all of its cores, and scales the defaults
+    /// accordingly. Takes well under a second.
+    ///
+    /// The Plonk and Groth16 provers are pinned to their defaults, as they don't run on
+    /// the same field.
+    pub fn measure() -> Self {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let start = Instant::now();
+        std::thread::scope(|scope| {
+            for i in 0..threads {
+                scope.spawn(move || {
+                    let mut x = BabyBear::from_canonical_usize(i + 1);
+                    let y = BabyBear::from_canonical_u32(0x12345);
+                    for _ in 0..BENCHMARK_OPS_PER_THREAD {
+                        x = std::hint::black_box(x * y + y);
+                    }
+                    x
+                });
+            }
+        });
+        let ops = (threads as u64 * BENCHMARK_OPS_PER_THREAD) as f64;
+        let speed = ops / start.elapsed().as_secs_f64() / REFERENCE_FIELD_OPS_PER_SEC;
+        Self::default().scaled(speed)
+    }
+
+    /// The calibration of this host, loaded from the cache or measured and cached on the first
+    /// call in a process.
+    pub fn host() -> Self {
+        static HOST: OnceLock<HostCalibration> = OnceLock::new();
+        *HOST.get_or_init(|| {
+            let path = Self::cache_path();
+            let cached = path
+                .as_ref()
+                .and_then(|path| std::fs::read(path).ok())
+                .and_then(|bytes| serde_json::from_slice(&bytes).ok());
+            if let Some(calibration) = cached {
+                return calibration;
+            }
+            let calibration = Self::measure();
+            if let Some(path) = path {
+                let bytes = serde_json::to_vec_pretty(&calibration).expect("failed to serialize");
+                if let Err(err) = write_atomically(&path, &bytes) {
+                    tracing::warn!("failed to cache the host calibration: {}", err);
+                }
+            }
+            calibration
+        })
+    }
+
+    /// `~/.sp1/calibration/<circuit version>-<threads>.json`, as the throughput depends on both.
+    fn cache_path() -> Option<PathBuf> {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let name = format!("{SP1_CIRCUIT_VERSION}-{threads}.json");
+        dirs::home_dir().map(|home| home.join(".sp1").join("calibration").join(name))
+    }
+
+    /// The calibration of a host `speed` times as fast as the one `self` describes.
+    fn scaled(self, speed: f64) -> Self {
+        let speed = if speed.is_finite() && speed > 0.0 { speed } else { 1.0 };
+        Self {
+            core_area_per_sec: self.core_area_per_sec * speed,
+            compress_per_shard: self.compress_per_shard.div_f64(speed),
+            shrink_wrap: self.shrink_wrap.div_f64(speed),
+            ..self
+        }
+    }
+
+    /// Predicts the time and peak memory of proving the execution described by `report` locally
+    /// with `opts`, in every [SP1ProofMode].
+    ///
+    /// The prediction accounts for the cycles and the syscalls of the execution through its
+    /// trace area, and for the number of shards it is split into. It doesn't account for
+    /// deferred proofs or the time taken to download the circuit artifacts.
+    pub fn predict(
+        &self,
+        report: &ExecutionReport,
+        opts: &SP1CoreOpts,
+    ) -> HashMap<SP1ProofMode, ProvingTimeEstimate> {
+        let shards = report.total_instruction_count().div_ceil(opts.shard_size.max(1) as u64);
+        let core = report.estimate_area() as f64 / self.core_area_per_sec;
+        let core = Duration::try_from_secs_f64(core).unwrap_or(Duration::MAX);
+        let compressed =
+            core.saturating_add(self.compress_per_shard.saturating_mul(shards.max(1) as u32));
+        let wrapped = compressed.saturating_add(self.shrink_wrap);
+        let memory = MemoryBudget::estimate(opts);
+        HashMap::from([
+            (SP1ProofMode::Core, ProvingTimeEstimate { duration: core, peak_memory: memory }),
+            (
+                SP1ProofMode::Compressed,
+                ProvingTimeEstimate { duration: compressed, peak_memory: memory },
+            ),
+            (
+                SP1ProofMode::Plonk,
+                ProvingTimeEstimate {
+                    duration: wrapped.saturating_add(self.plonk),
+                    peak_memory: memory.max(self.plonk_memory),
+                },
+            ),
+            (
+                SP1ProofMode::Groth16,
+                ProvingTimeEstimate {
+                    duration: wrapped.saturating_add(self.groth16),
+                    peak_memory: memory.max(self.groth16_memory),
+                },
+            ),
+        ])
+    }
+}
+
+/// The predicted cost of proving an execution locally, see [HostCalibration::predict].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProvingTimeEstimate {
+    /// The predicted time to generate the proof.
+    pub duration: Duration,
+    /// The predicted peak memory of generating the proof, in bytes.
+    pub peak_memory: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::Opcode;
+
+    use super::*;
+
+    #[test]
+    fn test_estimate() {
+        let mut report = ExecutionReport::default();
+        report.opcode_counts.insert(Opcode::ADD, 90);
+        report.opcode_counts.insert(Opcode::ECALL, 10);
+        report.syscall_counts.insert(SyscallCode::SHA_EXTEND, 4);
+        report.syscall_counts.insert(SyscallCode::WRITE, 6);
+
+        let model = GasModel {
+            base_gas: 1000,
+            gas_per_cycle: 2,
+            default_syscall_gas: 1,
+            price_per_gas: 0.5,
+            ..Default::default()
+        }
+        .with_syscall_gas(SyscallCode::SHA_EXTEND, 100);
+        let estimate = model.estimate(&report);
+
+        assert_eq!(estimate.cycles, 100);
+        assert_eq!(estimate.syscalls, 10);
+        assert_eq!(estimate.gas, 1000 + 2 * 100 + 4 * 100 + 6);
+        assert_eq!(estimate.cost, 803.0);
+    }
+
+    #[test]
+    fn test_predict() {
+        let mut report = ExecutionReport::default();
+        report.opcode_counts.insert(Opcode::ADD, 3 << 20);
+        let opts = SP1CoreOpts { shard_size: 1 << 20, ..Default::default() };
+
+        let calibration = HostCalibration::default();
+        let predictions = calibration.predict(&report, &opts);
+        let core = predictions[&SP1ProofMode::Core];
+        let compressed = predictions[&SP1ProofMode::Compressed];
+        let groth16 = predictions[&SP1ProofMode::Groth16];
+        assert_eq!(compressed.duration, core.duration + 3 * calibration.compress_per_shard);
+        assert_eq!(
+            groth16.duration,
+            compressed.duration + calibration.shrink_wrap + calibration.groth16
+        );
+        assert_eq!(groth16.peak_memory, calibration.groth16_memory.max(core.peak_memory));
+
+        // A host twice as fast proves the core stage in half the time.
+        let faster = calibration.scaled(2.0).predict(&report, &opts)[&SP1ProofMode::Core];
+        let halved = core.duration.as_secs_f64() / 2.0;
+        assert!((faster.duration.as_secs_f64() - halved).abs() < 1e-6);
+    }
+}
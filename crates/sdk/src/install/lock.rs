@@ -0,0 +1,76 @@
+use std::{
+    fs::{File, OpenOptions},
+    path::{Path, PathBuf},
+};
+
+use fs2::FileExt;
+
+use super::InstallError;
+
+/// An advisory lock on the artifacts of a circuit version, held while they are installed so that
+/// concurrent installers don't extract into the same directory.
+///
+/// The lock is released when the value is dropped, or when the process exits.
+pub(crate) struct InstallLock {
+    file: File,
+}
+
+impl InstallLock {
+    /// Acquires the lock on the artifacts of `version` in `circuits_dir`, blocking until it is
+    /// available. If another process holds the lock, `on_wait` is called with the path of the
+    /// lockfile before waiting.
+    pub(crate) fn acquire(
+        circuits_dir: &Path,
+        version: &str,
+        on_wait: impl FnOnce(&Path),
+    ) -> Result<Self, InstallError> {
+        std::fs::create_dir_all(circuits_dir)?;
+        let path = lock_path(circuits_dir, version);
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(&path)?;
+        if file.try_lock_exclusive().is_err() {
+            on_wait(&path);
+            file.lock_exclusive()?;
+        }
+        Ok(Self { file })
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// The path of the lockfile guarding the artifacts of the circuit version `version`.
+fn lock_path(circuits_dir: &Path, version: &str) -> PathBuf {
+    circuits_dir.join(format!(".{}.lock", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn test_install_lock() {
+        let dir = tempfile::tempdir().unwrap();
+        let lock =
+            InstallLock::acquire(dir.path(), "v1.2.0", |_| panic!("the lock is free")).unwrap();
+
+        // The artifacts of other versions are installed independently.
+        drop(InstallLock::acquire(dir.path(), "v1.1.0", |_| panic!("the lock is free")).unwrap());
+
+        let (waiting_tx, waiting_rx) = mpsc::channel();
+        std::thread::scope(|scope| {
+            let handle = scope.spawn(|| {
+                InstallLock::acquire(dir.path(), "v1.2.0", |_| waiting_tx.send(()).unwrap())
+                    .unwrap()
+            });
+            // Only release the lock once the other installer is waiting for it.
+            waiting_rx.recv().unwrap();
+            drop(lock);
+            handle.join().unwrap();
+        });
+    }
+}
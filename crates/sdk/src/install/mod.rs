@@ -0,0 +1,1214 @@
+mod lock;
+mod progress;
+mod retry;
+mod verify;
+
+pub use progress::{InstallProgress, QuietProgress, TerminalProgress};
+pub use retry::{RetryPolicy, SP1_CIRCUIT_DOWNLOAD_ATTEMPTS_ENV};
+#[cfg(feature = "network")]
+pub use verify::repair_installation;
+pub use verify::{
+    verify_installation, verify_installation_with, InstallationReport, ARTIFACTS_MANIFEST_FILE,
+};
+
+use cfg_if::cfg_if;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use strum_macros::{Display, EnumString};
+use tar::Archive;
+use thiserror::Error;
+
+#[cfg(feature = "network")]
+use {
+    crate::block_on_send,
+    futures::{future::try_join_all, StreamExt},
+    reqwest::{
+        header::{ACCEPT_RANGES, CONTENT_LENGTH, RANGE},
+        Client, StatusCode,
+    },
+    std::{cmp::min, fs::OpenOptions, io::Write},
+};
+
+use crate::SP1_CIRCUIT_VERSION;
+use lock::InstallLock;
+#[cfg(feature = "network")]
+use retry::{with_retries, AttemptError};
+
+/// The base URL for the S3 bucket containing the ciruit artifacts.
+pub const CIRCUIT_ARTIFACTS_URL_BASE: &str = "https://sp1-circuits.s3-us-east-2.amazonaws.com";
+
+/// Errors that can occur while installing the circuit artifacts.
+#[derive(Error, Debug)]
+pub enum InstallError {
+    #[error("failed to download {url}: {message}")]
+    Download { url: String, message: String },
+    #[error("failed to download {url} after {attempts} attempts: {message}")]
+    RetriesExhausted { url: String, attempts: u32, message: String },
+    #[error("no checksum manifest at {url}, the circuit artifacts can't be verified")]
+    MissingManifest { url: String },
+    #[error("checksum manifest at {url} has no entry for {file_name}")]
+    MissingChecksum { url: String, file_name: String },
+    #[error("checksum mismatch for {file_name}: expected {expected}, got {actual}")]
+    ChecksumMismatch { file_name: String, expected: String, actual: String },
+    #[error("failed to extract circuit artifacts: {0}")]
+    Extract(String),
+    #[error("invalid circuit artifacts: {0}")]
+    InvalidArtifacts(String),
+    #[error("circuit artifacts are already installed at {0}")]
+    AlreadyInstalled(PathBuf),
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// The environment variable used to override the root directory of the circuit artifacts.
+pub const SP1_CIRCUITS_DIR_ENV: &str = "SP1_CIRCUITS_DIR";
+
+/// Gets the root directory under which the circuit artifacts of every version are installed.
+///
+/// Defaults to `~/.sp1/circuits`, and can be redirected by setting `SP1_CIRCUITS_DIR`.
+pub fn circuits_dir() -> PathBuf {
+    match std::env::var_os(SP1_CIRCUITS_DIR_ENV) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()
+            .expect("no home directory found, set SP1_CIRCUITS_DIR to the artifacts directory")
+            .join(".sp1")
+            .join("circuits"),
+    }
+}
+
+/// The environment variable used to override where the circuit artifacts are downloaded from.
+///
+/// The value is either a base URL or a URL template, see [InstallConfig::url_template].
+pub const SP1_CIRCUIT_ARTIFACTS_URL_ENV: &str = "SP1_CIRCUIT_ARTIFACTS_URL";
+
+/// Gets the URL template the circuit artifacts are downloaded from.
+///
+/// Defaults to [CIRCUIT_ARTIFACTS_URL_BASE], and can be redirected to a mirror by setting
+/// `SP1_CIRCUIT_ARTIFACTS_URL`.
+pub fn circuit_artifacts_url() -> String {
+    match std::env::var(SP1_CIRCUIT_ARTIFACTS_URL_ENV) {
+        Ok(url) if !url.is_empty() => url,
+        _ => CIRCUIT_ARTIFACTS_URL_BASE.to_string(),
+    }
+}
+
+/// The environment variable used to set how many connections the circuit artifacts are
+/// downloaded over.
+pub const SP1_CIRCUIT_DOWNLOAD_CONCURRENCY_ENV: &str = "SP1_CIRCUIT_DOWNLOAD_CONCURRENCY";
+
+/// The number of connections the circuit artifacts are downloaded over by default.
+pub const DEFAULT_DOWNLOAD_CONCURRENCY: usize = 4;
+
+/// Gets the number of connections the circuit artifacts are downloaded over.
+///
+/// Defaults to [DEFAULT_DOWNLOAD_CONCURRENCY], and can be changed by setting
+/// `SP1_CIRCUIT_DOWNLOAD_CONCURRENCY`.
+pub fn download_concurrency() -> usize {
+    std::env::var(SP1_CIRCUIT_DOWNLOAD_CONCURRENCY_ENV)
+        .ok()
+        .and_then(|concurrency| concurrency.parse().ok())
+        .filter(|&concurrency| concurrency > 0)
+        .unwrap_or(DEFAULT_DOWNLOAD_CONCURRENCY)
+}
+
+/// Whether `version` looks like a circuit version, such as `v1.2.0-rc1`.
+///
+/// Versions read from untrusted sources, such as the tag of a proof, are checked with this before
+/// they are used in paths and URLs.
+pub fn is_circuit_version(version: &str) -> bool {
+    version.starts_with('v')
+        && version.len() <= 64
+        && version.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        && !version.contains("..")
+}
+
+/// Configures where the circuit artifacts are installed and where they are downloaded from.
+#[derive(Clone)]
+pub struct InstallConfig {
+    /// The root directory under which the circuit artifacts of every version are installed.
+    pub circuits_dir: PathBuf,
+    /// The circuit version whose artifacts are installed. Defaults to [SP1_CIRCUIT_VERSION];
+    /// other versions are only useful to verify proofs generated by previous releases of SP1.
+    pub version: String,
+    /// The location the artifacts are downloaded from.
+    ///
+    /// This is either a base URL, in which case files are fetched from `<base>/<file>`, or a
+    /// template containing `{file}`. Templates may also contain `{version}`, which expands to the
+    /// circuit version, so mirrors with a layout like `https://mirror/sp1/{version}/{file}` work.
+    pub url_template: String,
+    /// The number of connections the artifacts tarball is downloaded over. Each connection
+    /// fetches a contiguous byte range of the tarball.
+    pub download_concurrency: usize,
+    /// How failed downloads are retried. Interrupted downloads resume where they stopped.
+    pub retry: RetryPolicy,
+    /// The proxy the artifacts are downloaded through, e.g. `http://proxy.internal:3128`.
+    ///
+    /// When unset, the proxy configured in the `HTTPS_PROXY`, `HTTP_PROXY` and `ALL_PROXY`
+    /// environment variables is used, honoring `NO_PROXY`.
+    pub proxy: Option<String>,
+    /// Whether artifacts without a checksum manifest are installed without being checked, which is
+    /// reported through [InstallProgress::unverified]. Defaults to `false`, in which case a missing
+    /// manifest fails the installation with [InstallError::MissingManifest].
+    pub allow_unverified: bool,
+    /// Receives updates on the progress of the installation. Defaults to [TerminalProgress], set
+    /// it to [QuietProgress] to silence all output.
+    pub progress: Arc<dyn InstallProgress>,
+}
+
+impl InstallConfig {
+    /// Creates a new [InstallConfig] that installs the artifacts under `circuits_dir`.
+    ///
+    /// The remaining settings are read from the `SP1_CIRCUIT_ARTIFACTS_URL` and
+    /// `SP1_CIRCUIT_DOWNLOAD_CONCURRENCY` environment variables, falling back to the defaults when
+    /// they are unset.
+    pub fn new(circuits_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            circuits_dir: circuits_dir.into(),
+            version: SP1_CIRCUIT_VERSION.to_string(),
+            url_template: circuit_artifacts_url(),
+            download_concurrency: download_concurrency(),
+            retry: RetryPolicy::from_env(),
+            proxy: None,
+            allow_unverified: false,
+            progress: Arc::new(TerminalProgress::default()),
+        }
+    }
+
+    /// Installs the artifacts of the circuit version `version` instead of the current one.
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Silences all output while installing the artifacts.
+    pub fn quiet(mut self) -> Self {
+        self.progress = Arc::new(QuietProgress);
+        self
+    }
+
+    /// Creates a new [InstallConfig] from the environment, installing the artifacts under the
+    /// directory returned by [circuits_dir].
+    pub fn from_env() -> Self {
+        Self::new(circuits_dir())
+    }
+
+    /// The directory the artifacts of [Self::version] are installed to.
+    pub fn build_dir(&self) -> PathBuf {
+        self.circuits_dir.join(&self.version)
+    }
+
+    /// The URL `file_name` is downloaded from.
+    pub fn artifact_url(&self, file_name: &str) -> String {
+        let template = if self.url_template.contains("{file}") {
+            self.url_template.clone()
+        } else {
+            format!("{}/{{file}}", self.url_template.trim_end_matches('/'))
+        };
+        template.replace("{version}", &self.version).replace("{file}", file_name)
+    }
+}
+
+impl Default for InstallConfig {
+    fn default() -> Self {
+        Self::from_env()
+    }
+}
+
+impl fmt::Debug for InstallConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InstallConfig")
+            .field("circuits_dir", &self.circuits_dir)
+            .field("version", &self.version)
+            .field("url_template", &self.url_template)
+            .field("download_concurrency", &self.download_concurrency)
+            .field("retry", &self.retry)
+            .field("proxy", &self.proxy)
+            .field("allow_unverified", &self.allow_unverified)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A subset of the circuit artifacts, needed to generate and verify one kind of wrapped proof.
+///
+/// Every subset is installed into the same versioned directory, so the Plonk and Groth16
+/// artifacts can be installed independently of each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum ArtifactsType {
+    /// The artifacts for Plonk proofs.
+    Plonk,
+    /// The artifacts for Groth16 proofs.
+    Groth16,
+    /// The artifacts for every kind of wrapped proof.
+    All,
+}
+
+impl ArtifactsType {
+    /// The file name of the tarball containing this subset of the artifacts.
+    pub fn file_name(self) -> String {
+        self.file_name_for(SP1_CIRCUIT_VERSION)
+    }
+
+    /// The file name of the tarball containing this subset of the artifacts of the circuit
+    /// version `version`.
+    pub fn file_name_for(self, version: &str) -> String {
+        match self {
+            ArtifactsType::All => format!("{}.tar.gz", version),
+            _ => format!("{}-{}.tar.gz", version, self),
+        }
+    }
+
+    /// Whether this subset of the artifacts is installed in `build_dir`.
+    pub fn is_installed(self, build_dir: &Path) -> bool {
+        self.required_files().iter().all(|file| build_dir.join(file).exists())
+    }
+
+    /// Whether the artifact file `file_name` belongs to this subset of the artifacts.
+    fn includes(self, file_name: &str) -> bool {
+        match self {
+            ArtifactsType::Plonk => {
+                !file_name.starts_with("groth16_") && file_name != "SP1VerifierGroth16.sol"
+            }
+            ArtifactsType::Groth16 => {
+                !file_name.starts_with("plonk_") && file_name != "SP1VerifierPlonk.sol"
+            }
+            ArtifactsType::All => true,
+        }
+    }
+
+    /// The files that must be present for this subset of the artifacts to be usable.
+    fn required_files(self) -> &'static [&'static str] {
+        match self {
+            ArtifactsType::Plonk => &["plonk_pk.bin", "plonk_vk.bin"],
+            ArtifactsType::Groth16 => &["groth16_pk.bin", "groth16_vk.bin"],
+            ArtifactsType::All => {
+                &["plonk_pk.bin", "plonk_vk.bin", "groth16_pk.bin", "groth16_vk.bin"]
+            }
+        }
+    }
+
+    /// Guesses the subset of the artifacts contained in the tarball named `file_name`.
+    fn from_file_name(file_name: &str) -> Self {
+        [ArtifactsType::Plonk, ArtifactsType::Groth16]
+            .into_iter()
+            .find(|artifacts_type| file_name.ends_with(&format!("-{}.tar.gz", artifacts_type)))
+            .unwrap_or(ArtifactsType::All)
+    }
+}
+
+/// Tries to install the circuit artifacts of `artifacts_type` if they are not already installed.
+pub fn try_install_circuit_artifacts(artifacts_type: ArtifactsType) -> PathBuf {
+    try_install_circuit_artifacts_with(&InstallConfig::from_env(), artifacts_type)
+}
+
+/// Tries to install the circuit artifacts of `artifacts_type` under `circuits_dir` if they are
+/// not already installed.
+///
+/// The artifacts are placed in a subdirectory named after the current circuit version.
+pub fn try_install_circuit_artifacts_in(
+    circuits_dir: &Path,
+    artifacts_type: ArtifactsType,
+) -> PathBuf {
+    try_install_circuit_artifacts_with(&InstallConfig::new(circuits_dir), artifacts_type)
+}
+
+/// Tries to install the circuit artifacts of `artifacts_type` according to `config` if they are
+/// not already installed.
+///
+/// Panics if the artifacts can't be installed, see [install_circuit_artifacts_with] for a version
+/// that returns the error instead.
+pub fn try_install_circuit_artifacts_with(
+    config: &InstallConfig,
+    artifacts_type: ArtifactsType,
+) -> PathBuf {
+    match install_circuit_artifacts_with(config, artifacts_type) {
+        Ok(build_dir) => build_dir,
+        Err(err) => panic!("failed to install circuit artifacts: {}", err),
+    }
+}
+
+/// Installs the circuit artifacts of `artifacts_type` according to `config` if they are not
+/// already installed, returning the directory they are installed in.
+///
+/// Failed downloads are retried according to [InstallConfig::retry] before an error is returned.
+pub fn install_circuit_artifacts_with(
+    config: &InstallConfig,
+    artifacts_type: ArtifactsType,
+) -> Result<PathBuf, InstallError> {
+    cfg_if! {
+        if #[cfg(feature = "network")] {
+            block_on_send(install_circuit_artifacts_async(config, artifacts_type))
+        } else {
+            let build_dir = config.build_dir();
+            if artifacts_type.is_installed(&build_dir) {
+                config.progress.already_installed(artifacts_type, &build_dir);
+            }
+            Ok(build_dir)
+        }
+    }
+}
+
+/// Installs the circuit artifacts of `artifacts_type` according to `config` if they are not
+/// already installed, returning the directory they are installed in.
+///
+/// Unlike [try_install_circuit_artifacts_with], this never blocks the calling thread, so it can
+/// be awaited from inside any tokio runtime.
+#[cfg(feature = "network")]
+pub async fn install_circuit_artifacts_async(
+    config: &InstallConfig,
+    artifacts_type: ArtifactsType,
+) -> Result<PathBuf, InstallError> {
+    let build_dir = config.build_dir();
+    if artifacts_type.is_installed(&build_dir) {
+        config.progress.already_installed(artifacts_type, &build_dir);
+        return Ok(build_dir);
+    }
+
+    // Another process may be installing the same artifacts. Wait for it to finish, and check
+    // again once it's done so that the artifacts aren't downloaded twice.
+    let (circuits_dir, version, progress) =
+        (config.circuits_dir.clone(), config.version.clone(), config.progress.clone());
+    let _lock = spawn_blocking(move || {
+        InstallLock::acquire(&circuits_dir, &version, |path| progress.waiting_for_lock(path))
+    })
+    .await?;
+    if artifacts_type.is_installed(&build_dir) {
+        config.progress.already_installed(artifacts_type, &build_dir);
+        return Ok(build_dir);
+    }
+
+    config.progress.started(artifacts_type, &build_dir);
+    download_circuit_artifacts(config, &build_dir, artifacts_type).await?;
+    Ok(build_dir)
+}
+
+/// Install the latest circuit artifacts of `artifacts_type`.
+///
+/// This function will download the latest circuit artifacts from the S3 bucket (or the mirror set
+/// in `SP1_CIRCUIT_ARTIFACTS_URL`), verify them against the published checksum manifest and
+/// extract them to the directory specified by [install_circuit_artifacts_dir()]. Releases without
+/// a checksum manifest are rejected with [InstallError::MissingManifest].
+#[cfg(feature = "network")]
+pub fn install_circuit_artifacts(
+    build_dir: PathBuf,
+    artifacts_type: ArtifactsType,
+) -> Result<(), InstallError> {
+    let config = InstallConfig::from_env();
+    let circuits_dir = build_dir.parent().unwrap_or(Path::new("."));
+    let _lock = InstallLock::acquire(circuits_dir, &config.version, |path| {
+        config.progress.waiting_for_lock(path)
+    })?;
+    block_on_send(download_circuit_artifacts(&config, &build_dir, artifacts_type))
+}
+
+/// Runs the blocking filesystem work `f` without stalling the async runtime.
+#[cfg(feature = "network")]
+async fn spawn_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> Result<T, InstallError> + Send + 'static,
+) -> Result<T, InstallError> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()))
+}
+
+/// Downloads the circuit artifacts of `artifacts_type` from the location in `config` and extracts
+/// them to `build_dir`.
+#[cfg(feature = "network")]
+async fn download_circuit_artifacts(
+    config: &InstallConfig,
+    build_dir: &Path,
+    artifacts_type: ArtifactsType,
+) -> Result<(), InstallError> {
+    // Fetch the checksum manifest. Releases that predate the manifests are only installed unchecked
+    // if the config allows it.
+    let manifest_name = format!("{}.sha256", config.version);
+    let manifest_url = config.artifact_url(&manifest_name);
+    let client = http_client(config).map_err(|err| InstallError::Download {
+        url: manifest_url.clone(),
+        message: err.to_string(),
+    })?;
+    let manifest = fetch_optional_bytes(config, &client, &manifest_url).await?;
+    if manifest.is_none() {
+        if !config.allow_unverified {
+            return Err(InstallError::MissingManifest { url: manifest_url });
+        }
+        config.progress.unverified(&manifest_url);
+    }
+    let manifest = manifest.map(|manifest| String::from_utf8_lossy(&manifest).into_owned());
+    let (file_name, expected) =
+        select_tarball(manifest.as_deref(), &manifest_url, artifacts_type, &config.version)?;
+
+    // Download the artifacts.
+    let download_url = config.artifact_url(&file_name);
+    let partial_path = partial_download_path(&config.circuits_dir, &file_name);
+    std::fs::create_dir_all(&config.circuits_dir)?;
+    with_retries(&config.retry, &download_url, config.progress.as_ref(), || async {
+        download_file_parallel(
+            &client,
+            &download_url,
+            &partial_path,
+            config.download_concurrency,
+            config.progress.as_ref(),
+        )
+        .await
+        .map_err(AttemptError::from)
+    })
+    .await?;
+
+    // Verify the tarball before anything is written to the build directory. A corrupted partial
+    // download can't be resumed, so throw it away and start from scratch on the next attempt.
+    if let Some(expected) = expected {
+        let checked_path = partial_path.clone();
+        let checksum =
+            spawn_blocking(move || verify_checksum(&checked_path, &file_name, &expected));
+        if let Err(err) = checksum.await {
+            std::fs::remove_file(&partial_path)?;
+            return Err(err);
+        }
+    }
+
+    // Extract the tarball to the build directory.
+    config.progress.extracting(&partial_path);
+    let (archive, target, version) =
+        (partial_path.clone(), build_dir.to_path_buf(), config.version.clone());
+    spawn_blocking(move || unpack_artifacts(&archive, &target, &version)).await?;
+    std::fs::remove_file(&partial_path)?;
+
+    config.progress.done(&download_url, build_dir);
+    Ok(())
+}
+
+/// Picks the tarball of `artifacts_type` to download for `version`, and the digest it is checked
+/// against, from the checksum manifest published at `manifest_url`.
+///
+/// Releases that predate the split bundles only publish the tarball with every artifact, and so do
+/// the releases without a manifest, whose tarball is installed unchecked if
+/// [InstallConfig::allow_unverified] is set.
+#[cfg(feature = "network")]
+fn select_tarball(
+    manifest: Option<&str>,
+    manifest_url: &str,
+    artifacts_type: ArtifactsType,
+    version: &str,
+) -> Result<(String, Option<String>), InstallError> {
+    let Some(manifest) = manifest else {
+        return Ok((ArtifactsType::All.file_name_for(version), None));
+    };
+    [artifacts_type, ArtifactsType::All]
+        .into_iter()
+        .map(|artifacts_type| artifacts_type.file_name_for(version))
+        .find_map(|file_name| {
+            let expected = parse_checksum_manifest(manifest, &file_name)?;
+            Some((file_name, Some(expected)))
+        })
+        .ok_or_else(|| InstallError::MissingChecksum {
+            url: manifest_url.to_string(),
+            file_name: artifacts_type.file_name_for(version),
+        })
+}
+
+/// Installs the circuit artifacts from a tarball on the local filesystem, for machines that can't
+/// reach the artifacts bucket.
+///
+/// The tarball must be the one published for the current circuit version. If a checksum manifest
+/// named `<version>.sha256` sits next to it, the tarball is verified against it first. Without a
+/// manifest the tarball can't be checked,
+/// so it is rejected with [InstallError::MissingManifest] unless [InstallConfig::allow_unverified]
+/// is set. Returns the directory the artifacts were extracted to.
+pub fn install_circuit_artifacts_from_file(path: &Path) -> Result<PathBuf, InstallError> {
+    install_circuit_artifacts_from_file_with(&InstallConfig::from_env(), path)
+}
+
+/// Installs the circuit artifacts from a local tarball into the directory configured in `config`.
+///
+/// See [install_circuit_artifacts_from_file].
+pub fn install_circuit_artifacts_from_file_with(
+    config: &InstallConfig,
+    path: &Path,
+) -> Result<PathBuf, InstallError> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let build_dir = config.build_dir();
+    let _lock = InstallLock::acquire(&config.circuits_dir, &config.version, |lock_path| {
+        config.progress.waiting_for_lock(lock_path)
+    })?;
+    if ArtifactsType::from_file_name(&file_name).is_installed(&build_dir) {
+        return Err(InstallError::AlreadyInstalled(build_dir));
+    }
+
+    let manifest_path = path.with_file_name(format!("{}.sha256", config.version));
+    if manifest_path.exists() {
+        let manifest = std::fs::read_to_string(&manifest_path)?;
+        let expected = parse_checksum_manifest(&manifest, &file_name)
+            .or_else(|| {
+                let file_name = ArtifactsType::All.file_name_for(&config.version);
+                parse_checksum_manifest(&manifest, &file_name)
+            })
+            .ok_or_else(|| InstallError::MissingChecksum {
+                url: manifest_path.display().to_string(),
+                file_name: file_name.clone(),
+            })?;
+        verify_checksum(path, &file_name, &expected)?;
+    } else if config.allow_unverified {
+        config.progress.unverified(&manifest_path.display().to_string());
+    } else {
+        return Err(InstallError::MissingManifest { url: manifest_path.display().to_string() });
+    }
+
+    config.progress.extracting(path);
+    unpack_artifacts(path, &build_dir, &config.version)?;
+    config.progress.done(&path.display().to_string(), &build_dir);
+    Ok(build_dir)
+}
+
+/// A version of the circuit artifacts installed under the circuits directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledArtifacts {
+    /// The circuit version, i.e. the name of the directory.
+    pub version: String,
+    /// The directory the artifacts are installed in.
+    pub path: PathBuf,
+    /// The total size of the artifacts in bytes.
+    pub size: u64,
+}
+
+/// Lists the versions of the circuit artifacts installed under `circuits_dir`, sorted by version.
+pub fn installed_artifacts(circuits_dir: &Path) -> Result<Vec<InstalledArtifacts>, InstallError> {
+    if !circuits_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut installed = Vec::new();
+    for entry in std::fs::read_dir(circuits_dir)? {
+        let entry = entry?;
+        // Hidden directories are the staging directories of installs in progress.
+        if !entry.file_type()?.is_dir() || entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        installed.push(InstalledArtifacts {
+            version: entry.file_name().to_string_lossy().into_owned(),
+            size: dir_size(&path)?,
+            path,
+        });
+    }
+    installed.sort_by(|a, b| a.version.cmp(&b.version));
+    Ok(installed)
+}
+
+/// Deletes the installed circuit artifacts of every version not listed in `keep`, returning the
+/// versions that were removed.
+///
+/// The artifacts of the current circuit version are always kept.
+pub fn prune_old_artifacts(keep: &[&str]) -> Result<Vec<InstalledArtifacts>, InstallError> {
+    prune_old_artifacts_in(&circuits_dir(), keep)
+}
+
+/// Deletes the circuit artifacts installed under `circuits_dir` of every version not listed in
+/// `keep`. See [prune_old_artifacts].
+pub fn prune_old_artifacts_in(
+    circuits_dir: &Path,
+    keep: &[&str],
+) -> Result<Vec<InstalledArtifacts>, InstallError> {
+    let mut pruned = Vec::new();
+    for artifacts in installed_artifacts(circuits_dir)? {
+        if artifacts.version == SP1_CIRCUIT_VERSION || keep.contains(&artifacts.version.as_str()) {
+            continue;
+        }
+        std::fs::remove_dir_all(&artifacts.path)?;
+        pruned.push(artifacts);
+    }
+    Ok(pruned)
+}
+
+/// Computes the total size of the files in `dir`, recursively.
+fn dir_size(dir: &Path) -> Result<u64, InstallError> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            size += dir_size(&entry.path())?;
+        } else if file_type.is_file() {
+            size += entry.metadata()?.len();
+        }
+    }
+    Ok(size)
+}
+
+/// The directory where the circuit artifacts will be stored.
+pub fn install_circuit_artifacts_dir() -> PathBuf {
+    circuits_dir().join(SP1_CIRCUIT_VERSION)
+}
+
+/// The path the tarball named `file_name` is downloaded to before extraction.
+///
+/// The file lives next to the build directories so an interrupted download survives across runs
+/// and can be resumed.
+#[cfg(feature = "network")]
+fn partial_download_path(circuits_dir: &Path, file_name: &str) -> PathBuf {
+    circuits_dir.join(format!("{}.partial", file_name))
+}
+
+/// Builds the HTTP client used to download the artifacts, routed through the proxy in `config`.
+///
+/// Without an explicit proxy, `reqwest` picks up the proxy environment variables by itself.
+#[cfg(feature = "network")]
+fn http_client(config: &InstallConfig) -> reqwest::Result<Client> {
+    let mut builder = Client::builder();
+    if let Some(proxy) = &config.proxy {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build()
+}
+
+/// Fetches the small file at `url`, such as the checksum manifest, into memory, or returns `None`
+/// if the server reports that it doesn't exist.
+///
+/// Failed requests are retried according to the retry policy of `config`, except for client
+/// errors such as a missing file.
+#[cfg(feature = "network")]
+async fn fetch_optional_bytes(
+    config: &InstallConfig,
+    client: &Client,
+    url: &str,
+) -> Result<Option<Vec<u8>>, InstallError> {
+    let classify = |err: reqwest::Error| {
+        if err.status().is_some_and(|status| status.is_client_error()) {
+            AttemptError::Permanent(err.to_string())
+        } else {
+            AttemptError::Transient(err.to_string())
+        }
+    };
+    with_retries(&config.retry, url, config.progress.as_ref(), || async {
+        let res = client.get(url).send().await.map_err(classify)?;
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let bytes = res.error_for_status().map_err(classify)?.bytes().await.map_err(classify)?;
+        Ok(Some(bytes.to_vec()))
+    })
+    .await
+}
+
+/// Parses a checksum manifest in the format produced by `sha256sum` and returns the digest for
+/// `file_name`, if there is one.
+fn parse_checksum_manifest(manifest: &str, file_name: &str) -> Option<String> {
+    checksum_manifest_entries(manifest)
+        .find_map(|(name, digest)| (name == file_name).then_some(digest))
+}
+
+/// Iterates over the `(file name, lowercase digest)` entries of a `sha256sum` manifest.
+fn checksum_manifest_entries(manifest: &str) -> impl Iterator<Item = (&str, String)> {
+    manifest.lines().filter_map(|line| {
+        let mut parts = line.split_whitespace();
+        let digest = parts.next()?;
+        // `sha256sum` prefixes the file name with `*` in binary mode.
+        let name = parts.next()?.trim_start_matches('*');
+        Some((name.trim_start_matches("./"), digest.to_lowercase()))
+    })
+}
+
+/// Extracts the artifacts tarball at `archive` into `build_dir` and checks that they were built
+/// for the circuit version `version`.
+///
+/// The tarball is extracted into a staging directory first and only moved into `build_dir` once it
+/// was fully extracted, so a failure never leaves behind files that look like a valid
+/// installation, nor touches the artifacts that are already installed.
+fn unpack_artifacts(archive: &Path, build_dir: &Path, version: &str) -> Result<(), InstallError> {
+    let parent = build_dir.parent().unwrap_or(Path::new("."));
+    std::fs::create_dir_all(parent)?;
+    let staging_dir = tempfile::tempdir_in(parent)?;
+    extract_artifacts(archive, staging_dir.path())?;
+    check_version(staging_dir.path(), version)?;
+
+    std::fs::create_dir_all(build_dir)?;
+    for entry in std::fs::read_dir(staging_dir.path())? {
+        let entry = entry?;
+        let target = build_dir.join(entry.file_name());
+        if target.is_dir() {
+            std::fs::remove_dir_all(&target)?;
+        } else if target.exists() {
+            std::fs::remove_file(&target)?;
+        }
+        std::fs::rename(entry.path(), target)?;
+    }
+    Ok(())
+}
+
+/// Checks the version recorded in the `SP1_COMMIT` file of the extracted artifacts, if any.
+fn check_version(build_dir: &Path, expected: &str) -> Result<(), InstallError> {
+    let commit_path = build_dir.join("SP1_COMMIT");
+    if !commit_path.exists() {
+        return Ok(());
+    }
+    // The file is written by the release script as `<commit hash> <version>`.
+    let contents = std::fs::read_to_string(commit_path)?;
+    match contents.split_whitespace().nth(1) {
+        Some(version) if version == expected => Ok(()),
+        version => Err(InstallError::InvalidArtifacts(format!(
+            "expected artifacts for version {}, found {}",
+            expected,
+            version.unwrap_or("an unknown version")
+        ))),
+    }
+}
+
+/// Extracts the gzipped tarball at `archive` into `build_dir`.
+///
+/// Entries that would be unpacked outside of `build_dir` are skipped.
+fn extract_artifacts(archive: &Path, build_dir: &Path) -> Result<(), InstallError> {
+    let file = std::fs::File::open(archive)?;
+    Archive::new(GzDecoder::new(file))
+        .unpack(build_dir)
+        .map_err(|err| InstallError::Extract(format!("{}: {}", archive.display(), err)))
+}
+
+/// Computes the SHA-256 digest of the file at `path` as a lowercase hex string.
+pub fn sha256_file(path: &Path) -> Result<String, InstallError> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Checks that the SHA-256 digest of the file at `path` matches `expected`.
+fn verify_checksum(path: &Path, file_name: &str, expected: &str) -> Result<(), InstallError> {
+    let actual = sha256_file(path)?;
+    if actual != expected {
+        return Err(InstallError::ChecksumMismatch {
+            file_name: file_name.to_string(),
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Download the file, reporting the progress to `progress`.
+///
+/// The bytes are written to `path`. If `path` already holds the beginning of the file from an
+/// interrupted download, only the remaining bytes are requested with an HTTP `Range` header. When
+/// the server ignores the range, the download restarts from scratch.
+#[cfg(feature = "network")]
+pub async fn download_file(
+    client: &Client,
+    url: &str,
+    path: &Path,
+    progress: &dyn InstallProgress,
+) -> std::result::Result<(), String> {
+    let offset = std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0);
+
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={}-", offset));
+    }
+    let res = request.send().await.or(Err(format!("Failed to GET from '{}'", &url)))?;
+
+    // The partial file already covers the whole resource, the checksum will tell if it's valid.
+    if offset > 0 && res.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        return Ok(());
+    }
+    let res =
+        res.error_for_status().map_err(|err| format!("Failed to GET from '{}': {}", url, err))?;
+
+    let resumed = res.status() == StatusCode::PARTIAL_CONTENT;
+    let start = if resumed { offset } else { 0 };
+    let total_size = start
+        + res.content_length().ok_or(format!("Failed to get content length from '{}'", &url))?;
+
+    let mut file = if resumed {
+        OpenOptions::new().append(true).open(path)
+    } else {
+        std::fs::File::create(path)
+    }
+    .or(Err(format!("Failed to open '{}'", path.display())))?;
+
+    progress.download_started(url, total_size);
+    progress.bytes_downloaded(start);
+
+    let mut stream = res.bytes_stream();
+    while let Some(item) = stream.next().await {
+        let chunk = item.or(Err("Error while downloading file"))?;
+        file.write_all(&chunk).or(Err("Error while writing to file"))?;
+        progress.bytes_downloaded(chunk.len() as u64);
+    }
+    file.flush().or(Err("Error while flushing file"))?;
+
+    let size = file.metadata().or(Err("Error while reading file metadata"))?.len();
+    if size != total_size {
+        return Err(format!(
+            "Incomplete download from '{}': got {} of {} bytes, run again to resume",
+            url, size, total_size
+        ));
+    }
+
+    Ok(())
+}
+
+/// Downloads `url` into `path` over up to `concurrency` connections, each fetching a contiguous
+/// byte range of the file.
+///
+/// Every range is written to its own part file next to `path`, so an interrupted download resumes
+/// each range where it stopped. The parts are concatenated into `path` once all of them are
+/// complete. Falls back to [download_file] if the server doesn't support range requests.
+#[cfg(feature = "network")]
+pub async fn download_file_parallel(
+    client: &Client,
+    url: &str,
+    path: &Path,
+    concurrency: usize,
+    progress: &dyn InstallProgress,
+) -> std::result::Result<(), String> {
+    if concurrency <= 1 {
+        return download_file(client, url, path, progress).await;
+    }
+
+    let res = client
+        .head(url)
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|err| format!("Failed to HEAD '{}': {}", url, err))?;
+    let accepts_ranges = res.headers().get(ACCEPT_RANGES).is_some_and(|value| value == "bytes");
+    // `Response::content_length` reports the size of the (empty) body of a HEAD response, so read
+    // the header directly.
+    let total_size = res
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok())
+        .filter(|&size| size > 0);
+    let Some(total_size) = total_size.filter(|_| accepts_ranges) else {
+        return download_file(client, url, path, progress).await;
+    };
+
+    let parts = split_ranges(total_size, concurrency)
+        .into_iter()
+        .enumerate()
+        .map(|(i, range)| (part_path(path, i), range))
+        .collect::<Vec<_>>();
+
+    progress.download_started(url, total_size);
+    try_join_all(
+        parts
+            .iter()
+            .map(|(part, (start, end))| download_range(client, url, part, *start, *end, progress)),
+    )
+    .await?;
+
+    // Reassemble the parts in order.
+    let mut file =
+        std::fs::File::create(path).or(Err(format!("Failed to create '{}'", path.display())))?;
+    for (part, _) in &parts {
+        let mut part_file =
+            std::fs::File::open(part).or(Err(format!("Failed to open '{}'", part.display())))?;
+        std::io::copy(&mut part_file, &mut file).or(Err("Error while reassembling file"))?;
+    }
+    file.flush().or(Err("Error while flushing file"))?;
+
+    let size = file.metadata().or(Err("Error while reading file metadata"))?.len();
+    if size != total_size {
+        return Err(format!(
+            "Incomplete download from '{}': got {} of {} bytes",
+            url, size, total_size
+        ));
+    }
+    for (part, _) in &parts {
+        std::fs::remove_file(part).or(Err(format!("Failed to remove '{}'", part.display())))?;
+    }
+
+    Ok(())
+}
+
+/// Downloads the byte range `start..end` of `url` into `part`, resuming from its current length.
+#[cfg(feature = "network")]
+async fn download_range(
+    client: &Client,
+    url: &str,
+    part: &Path,
+    start: u64,
+    end: u64,
+    progress: &dyn InstallProgress,
+) -> std::result::Result<(), String> {
+    let expected = end - start;
+    let mut offset = std::fs::metadata(part).map(|metadata| metadata.len()).unwrap_or(0);
+    if offset > expected {
+        // Something else wrote to the part file, start the range over.
+        std::fs::remove_file(part).or(Err(format!("Failed to remove '{}'", part.display())))?;
+        offset = 0;
+    }
+    progress.bytes_downloaded(offset);
+    if offset == expected {
+        return Ok(());
+    }
+
+    let res = client
+        .get(url)
+        .header(RANGE, format!("bytes={}-{}", start + offset, end - 1))
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|err| format!("Failed to GET from '{}': {}", url, err))?;
+    if res.status() != StatusCode::PARTIAL_CONTENT {
+        return Err(format!("Server ignored the range request for '{}'", url));
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(part)
+        .or(Err(format!("Failed to open '{}'", part.display())))?;
+    let mut stream = res.bytes_stream();
+    while let Some(item) = stream.next().await {
+        let chunk = item.or(Err("Error while downloading file"))?;
+        file.write_all(&chunk).or(Err("Error while writing to file"))?;
+        progress.bytes_downloaded(chunk.len() as u64);
+    }
+    file.flush().or(Err("Error while flushing file"))?;
+
+    let size = file.metadata().or(Err("Error while reading file metadata"))?.len();
+    if size != expected {
+        return Err(format!(
+            "Incomplete download of bytes {}..{} from '{}': got {} of {} bytes, run again to resume",
+            start, end, url, size, expected
+        ));
+    }
+
+    Ok(())
+}
+
+/// Splits `0..total_size` into at most `count` contiguous, non-empty ranges of similar size.
+#[cfg(feature = "network")]
+fn split_ranges(total_size: u64, count: usize) -> Vec<(u64, u64)> {
+    let chunk_size = total_size.div_ceil(count.max(1) as u64).max(1);
+    (0..total_size)
+        .step_by(chunk_size as usize)
+        .map(|start| (start, min(start + chunk_size, total_size)))
+        .collect()
+}
+
+/// The path the byte range with the given index of a parallel download into `path` is written to.
+#[cfg(feature = "network")]
+fn part_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".part{}", index));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_checksum_manifest() {
+        let manifest = "\
+            0123abcd  v1.2.0.tar.gz\n\
+            DEADBEEF *./v1.3.0.tar.gz\n";
+        assert_eq!(parse_checksum_manifest(manifest, "v1.2.0.tar.gz").as_deref(), Some("0123abcd"));
+        assert_eq!(parse_checksum_manifest(manifest, "v1.3.0.tar.gz").as_deref(), Some("deadbeef"));
+        assert_eq!(parse_checksum_manifest(manifest, "v1.4.0.tar.gz"), None);
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn test_select_tarball() {
+        let version = "v1.2.0";
+        let plonk = ArtifactsType::Plonk.file_name_for(version);
+        let all = ArtifactsType::All.file_name_for(version);
+
+        // Without a manifest, the full tarball is installed unchecked.
+        assert_eq!(
+            select_tarball(None, "url", ArtifactsType::Plonk, version).unwrap(),
+            (all.clone(), None)
+        );
+
+        let manifest = format!("0123abcd  {}\n4567cdef  {}\n", all, plonk);
+        assert_eq!(
+            select_tarball(Some(&manifest), "url", ArtifactsType::Plonk, version).unwrap(),
+            (plonk, Some("4567cdef".to_string()))
+        );
+        assert_eq!(
+            select_tarball(Some(&manifest), "url", ArtifactsType::Groth16, version).unwrap(),
+            (all, Some("0123abcd".to_string()))
+        );
+        assert!(matches!(
+            select_tarball(Some(""), "url", ArtifactsType::Groth16, version),
+            Err(InstallError::MissingChecksum { .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_checksum() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"sp1").unwrap();
+        let digest = sha256_file(file.path()).unwrap();
+        assert!(verify_checksum(file.path(), "sp1", &digest).is_ok());
+        assert!(matches!(
+            verify_checksum(file.path(), "sp1", "00"),
+            Err(InstallError::ChecksumMismatch { .. })
+        ));
+    }
+
+    /// Writes a gzipped tarball containing `entries` to `path`.
+    fn write_tarball(path: &Path, entries: &[(&str, &[u8])]) {
+        let encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(path).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_cksum();
+            builder.append_data(&mut header, format!("./{}", name), *contents).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_extract_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join("artifacts.tar.gz");
+        write_tarball(&archive_path, &[("SP1_COMMIT", b"sp1")]);
+
+        let build_dir = dir.path().join("build");
+        std::fs::create_dir_all(&build_dir).unwrap();
+        extract_artifacts(&archive_path, &build_dir).unwrap();
+        assert_eq!(std::fs::read(build_dir.join("SP1_COMMIT")).unwrap(), b"sp1");
+
+        std::fs::write(&archive_path, b"not a tarball").unwrap();
+        assert!(matches!(
+            extract_artifacts(&archive_path, &build_dir),
+            Err(InstallError::Extract(_))
+        ));
+    }
+
+    #[test]
+    fn test_install_circuit_artifacts_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive_path = dir.path().join(ArtifactsType::Groth16.file_name());
+        let commit = format!("abc1234 {}\n", SP1_CIRCUIT_VERSION);
+        write_tarball(
+            &archive_path,
+            &[
+                ("SP1_COMMIT", commit.as_bytes()),
+                ("groth16_pk.bin", b"pk"),
+                ("groth16_vk.bin", b"vk"),
+            ],
+        );
+
+        let config = InstallConfig::new(dir.path().join("circuits"));
+
+        // A tarball without a manifest next to it is only installed if unverified installs are
+        // allowed.
+        assert!(matches!(
+            install_circuit_artifacts_from_file_with(&config, &archive_path),
+            Err(InstallError::MissingManifest { .. })
+        ));
+        assert!(!config.build_dir().exists());
+
+        // A manifest next to the tarball that doesn't match is rejected.
+        let manifest_path = dir.path().join(format!("{}.sha256", SP1_CIRCUIT_VERSION));
+        let file_name = ArtifactsType::Groth16.file_name();
+        std::fs::write(&manifest_path, format!("00  {}\n", file_name)).unwrap();
+        assert!(matches!(
+            install_circuit_artifacts_from_file_with(&config, &archive_path),
+            Err(InstallError::ChecksumMismatch { .. })
+        ));
+        assert!(!config.build_dir().exists());
+
+        let digest = sha256_file(&archive_path).unwrap();
+        std::fs::write(&manifest_path, format!("{}  {}\n", digest, file_name)).unwrap();
+        let build_dir = install_circuit_artifacts_from_file_with(&config, &archive_path).unwrap();
+        assert_eq!(build_dir, config.build_dir());
+        assert!(ArtifactsType::Groth16.is_installed(&build_dir));
+        assert!(!ArtifactsType::Plonk.is_installed(&build_dir));
+
+        assert!(matches!(
+            install_circuit_artifacts_from_file_with(&config, &archive_path),
+            Err(InstallError::AlreadyInstalled(_))
+        ));
+    }
+
+    #[test]
+    fn test_prune_old_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        for version in ["v1.0.0", "v1.1.0", SP1_CIRCUIT_VERSION] {
+            let version_dir = dir.path().join(version).join("groth16");
+            std::fs::create_dir_all(&version_dir).unwrap();
+            std::fs::write(version_dir.join("vk.bin"), b"vk").unwrap();
+        }
+        std::fs::write(dir.path().join("v1.0.0.tar.gz.partial"), b"").unwrap();
+
+        let installed = installed_artifacts(dir.path()).unwrap();
+        assert_eq!(installed.len(), 3);
+        assert!(installed.iter().all(|artifacts| artifacts.size == 2));
+
+        let pruned = prune_old_artifacts_in(dir.path(), &["v1.1.0"]).unwrap();
+        assert_eq!(pruned.iter().map(|a| a.version.as_str()).collect::<Vec<_>>(), ["v1.0.0"]);
+        assert!(!dir.path().join("v1.0.0").exists());
+        assert!(dir.path().join("v1.1.0").exists());
+        assert!(dir.path().join(SP1_CIRCUIT_VERSION).exists());
+    }
+
+    #[test]
+    fn test_is_circuit_version() {
+        assert!(is_circuit_version(SP1_CIRCUIT_VERSION));
+        assert!(is_circuit_version("v1.0.5-testnet"));
+        assert!(!is_circuit_version("../../etc"));
+        assert!(!is_circuit_version("v1/../../etc"));
+        assert!(!is_circuit_version(""));
+    }
+
+    #[test]
+    fn test_artifact_url() {
+        let config = |url_template: &str| InstallConfig {
+            url_template: url_template.to_string(),
+            ..InstallConfig::new("/tmp/circuits")
+        };
+        assert_eq!(
+            config("https://mirror.internal/sp1/").artifact_url("a.tar.gz"),
+            "https://mirror.internal/sp1/a.tar.gz"
+        );
+        assert_eq!(
+            config("https://mirror.internal/{version}/{file}?raw=1").artifact_url("a.tar.gz"),
+            format!("https://mirror.internal/{}/a.tar.gz?raw=1", SP1_CIRCUIT_VERSION)
+        );
+        assert_eq!(
+            config("https://mirror.internal/{version}/{file}")
+                .with_version("v1.0.0")
+                .artifact_url(&ArtifactsType::Plonk.file_name_for("v1.0.0")),
+            "https://mirror.internal/v1.0.0/v1.0.0-plonk.tar.gz"
+        );
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn test_split_ranges() {
+        assert_eq!(split_ranges(10, 3), vec![(0, 4), (4, 8), (8, 10)]);
+        assert_eq!(split_ranges(2, 4), vec![(0, 1), (1, 2)]);
+        assert_eq!(split_ranges(10, 1), vec![(0, 10)]);
+        assert_eq!(
+            part_path(Path::new("/tmp/v1.2.0.tar.gz.partial"), 1),
+            Path::new("/tmp/v1.2.0.tar.gz.partial.part1")
+        );
+    }
+
+    #[test]
+    fn test_artifacts_type() {
+        for artifacts_type in [ArtifactsType::Plonk, ArtifactsType::Groth16, ArtifactsType::All] {
+            assert_eq!(ArtifactsType::from_file_name(&artifacts_type.file_name()), artifacts_type);
+            assert_eq!(artifacts_type.to_string().parse::<ArtifactsType>(), Ok(artifacts_type));
+        }
+        assert_eq!(
+            ArtifactsType::Groth16.file_name(),
+            format!("{}-groth16.tar.gz", SP1_CIRCUIT_VERSION)
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::{path::Path, sync::Mutex, time::Duration};
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+use super::ArtifactsType;
+use crate::SP1_CIRCUIT_VERSION;
+
+/// Receives updates on the progress of installing the circuit artifacts.
+///
+/// Every method has an empty default implementation, so implementors only need to handle the
+/// events they are interested in. [TerminalProgress] is used by default, and [QuietProgress]
+/// silences all output.
+pub trait InstallProgress: Send + Sync {
+    /// Called when the artifacts of `artifacts_type` are already installed in `build_dir`.
+    fn already_installed(&self, _artifacts_type: ArtifactsType, _build_dir: &Path) {}
+
+    /// Called when another process is installing the artifacts, holding the lockfile at `path`.
+    /// The installation resumes once that process is done.
+    fn waiting_for_lock(&self, _path: &Path) {}
+
+    /// Called when the artifacts of `artifacts_type` start being installed to `build_dir`.
+    fn started(&self, _artifacts_type: ArtifactsType, _build_dir: &Path) {}
+
+    /// Called at the start of every attempt at downloading `url`, once its total size is known.
+    ///
+    /// The count of downloaded bytes starts over from zero, as the bytes recovered from a previous
+    /// attempt are reported again.
+    fn download_started(&self, _url: &str, _total_size: u64) {}
+
+    /// Called whenever `bytes` more bytes of the current attempt were downloaded. Bytes recovered
+    /// from an interrupted download that is resumed are reported as well.
+    fn bytes_downloaded(&self, _bytes: u64) {}
+
+    /// Called when attempt number `attempt` at downloading `url` failed with `error`, and the
+    /// download is tried again after `delay`.
+    fn retrying(&self, _url: &str, _attempt: u32, _delay: Duration, _error: &str) {}
+
+    /// Called when there is no checksum manifest at `manifest`, a URL or a local path, so the
+    /// artifacts are installed without being checked.
+    fn unverified(&self, _manifest: &str) {}
+
+    /// Called when the tarball at `archive` starts being extracted.
+    fn extracting(&self, _archive: &Path) {}
+
+    /// Called once the artifacts from `source`, a URL or a local path, are installed in
+    /// `build_dir`.
+    fn done(&self, _source: &str, _build_dir: &Path) {}
+}
+
+/// An [InstallProgress] that prints messages and shows a progress bar in the terminal.
+#[derive(Default)]
+pub struct TerminalProgress {
+    bar: Mutex<Option<ProgressBar>>,
+}
+
+impl InstallProgress for TerminalProgress {
+    fn already_installed(&self, artifacts_type: ArtifactsType, build_dir: &Path) {
+        println!(
+            "[sp1] {} circuit artifacts already seem to exist at {}. if you want to re-download them, delete the directory",
+            artifacts_type,
+            build_dir.display()
+        );
+    }
+
+    fn waiting_for_lock(&self, path: &Path) {
+        println!(
+            "[sp1] another process is installing the circuit artifacts, waiting for it to release {}",
+            path.display()
+        );
+    }
+
+    fn started(&self, artifacts_type: ArtifactsType, build_dir: &Path) {
+        println!(
+            "[sp1] {} circuit artifacts for version {} do not exist at {}. downloading...",
+            artifacts_type,
+            build_dir.file_name().map_or(SP1_CIRCUIT_VERSION.into(), |dir| dir.to_string_lossy()),
+            build_dir.display()
+        );
+    }
+
+    fn download_started(&self, _url: &str, total_size: u64) {
+        let mut bar = self.bar.lock().unwrap();
+        // A retry restarts the count of an existing bar.
+        if let Some(pb) = bar.as_ref() {
+            pb.set_length(total_size);
+            pb.reset();
+            return;
+        }
+        let pb = ProgressBar::new(total_size);
+        pb.set_style(ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})").unwrap()
+            .progress_chars("#>-"));
+        *bar = Some(pb);
+    }
+
+    fn bytes_downloaded(&self, bytes: u64) {
+        if let Some(pb) = self.bar.lock().unwrap().as_ref() {
+            pb.inc(bytes);
+        }
+    }
+
+    fn retrying(&self, url: &str, attempt: u32, delay: Duration, error: &str) {
+        let message = format!(
+            "[sp1] attempt {} to download {} failed: {}. retrying in {:.1}s",
+            attempt,
+            url,
+            error,
+            delay.as_secs_f64()
+        );
+        match self.bar.lock().unwrap().as_ref() {
+            Some(pb) => pb.suspend(|| println!("{}", message)),
+            None => println!("{}", message),
+        }
+    }
+
+    fn unverified(&self, manifest: &str) {
+        println!(
+            "[sp1] warning: no checksum manifest found at {}, the circuit artifacts are installed without checking their integrity",
+            manifest
+        );
+    }
+
+    fn extracting(&self, _archive: &Path) {
+        if let Some(pb) = self.bar.lock().unwrap().take() {
+            pb.finish();
+        }
+    }
+
+    fn done(&self, source: &str, build_dir: &Path) {
+        println!("[sp1] installed {} to {}", source, build_dir.display());
+    }
+}
+
+/// An [InstallProgress] that doesn't report anything.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct QuietProgress;
+
+impl InstallProgress for QuietProgress {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_terminal_progress_retry() {
+        let progress = TerminalProgress::default();
+        progress.download_started("url", 10);
+        progress.bytes_downloaded(4);
+
+        // The retry reports the 4 bytes it resumes from again, which must not be counted twice.
+        progress.retrying("url", 1, Duration::ZERO, "timeout");
+        progress.download_started("url", 10);
+        progress.bytes_downloaded(4);
+        progress.bytes_downloaded(2);
+        assert_eq!(progress.bar.lock().unwrap().as_ref().unwrap().position(), 6);
+    }
+}
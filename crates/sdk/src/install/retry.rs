@@ -0,0 +1,196 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+
+#[cfg(feature = "network")]
+use std::future::Future;
+
+#[cfg(feature = "network")]
+use super::{InstallError, InstallProgress};
+
+/// The environment variable used to set how many times a download of the circuit artifacts is
+/// attempted before giving up.
+pub const SP1_CIRCUIT_DOWNLOAD_ATTEMPTS_ENV: &str = "SP1_CIRCUIT_DOWNLOAD_ATTEMPTS";
+
+/// How failed downloads of the circuit artifacts, and failed requests to the prover network, are
+/// retried.
+///
+/// The delay before the `n`-th retry is `initial_backoff * 2^(n - 1)`, capped at `max_backoff`,
+/// and then randomly shortened by up to `jitter` of its length so that many machines recovering
+/// from the same outage don't hit the bucket in lockstep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// The number of times a download or request is attempted, including the first attempt.
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    pub initial_backoff: Duration,
+    /// The upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+    /// The fraction of every delay, between `0.0` and `1.0`, that is randomized.
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// A policy that attempts every download exactly once.
+    pub fn none() -> Self {
+        Self { max_attempts: 1, ..Self::default() }
+    }
+
+    /// Creates the default policy, attempting downloads as many times as set in
+    /// `SP1_CIRCUIT_DOWNLOAD_ATTEMPTS`.
+    pub fn from_env() -> Self {
+        let max_attempts = std::env::var(SP1_CIRCUIT_DOWNLOAD_ATTEMPTS_ENV)
+            .ok()
+            .and_then(|attempts| attempts.parse().ok())
+            .filter(|&attempts| attempts > 0);
+        match max_attempts {
+            Some(max_attempts) => Self { max_attempts, ..Self::default() },
+            None => Self::default(),
+        }
+    }
+
+    /// The delay to wait for after the failed attempt number `attempt`, counting from 1.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        let delay = self.initial_backoff.saturating_mul(1 << exponent).min(self.max_backoff);
+        let jitter = self.jitter.clamp(0.0, 1.0) * random_fraction();
+        delay.mul_f64(1.0 - jitter)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            jitter: 0.25,
+        }
+    }
+}
+
+/// A uniformly distributed number in `[0, 1)`, good enough to spread out retries.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(0);
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// The reason a single attempt at a download failed.
+#[cfg(feature = "network")]
+pub(crate) enum AttemptError {
+    /// The failure may go away on its own, such as a dropped connection or a server error.
+    Transient(String),
+    /// Trying again won't help, such as when the file doesn't exist.
+    Permanent(String),
+}
+
+#[cfg(feature = "network")]
+impl From<String> for AttemptError {
+    fn from(message: String) -> Self {
+        AttemptError::Transient(message)
+    }
+}
+
+/// Runs `attempt` until it succeeds, fails permanently or `policy` runs out of attempts, sleeping
+/// between attempts.
+#[cfg(feature = "network")]
+pub(crate) async fn with_retries<T, F, Fut>(
+    policy: &RetryPolicy,
+    url: &str,
+    progress: &dyn InstallProgress,
+    mut attempt: F,
+) -> Result<T, InstallError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AttemptError>>,
+{
+    let max_attempts = policy.max_attempts.max(1);
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let message = match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(AttemptError::Permanent(message)) => {
+                return Err(InstallError::Download { url: url.to_string(), message })
+            }
+            Err(AttemptError::Transient(message)) => message,
+        };
+        if attempts >= max_attempts {
+            return Err(InstallError::RetriesExhausted { url: url.to_string(), attempts, message });
+        }
+
+        let delay = policy.backoff(attempts);
+        progress.retrying(url, attempts, delay, &message);
+        tokio::time::sleep(delay).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy { jitter: 0.0, ..RetryPolicy::default() };
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(10), Duration::from_secs(30));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(30));
+
+        let policy = RetryPolicy { jitter: 0.5, ..RetryPolicy::default() };
+        for _ in 0..100 {
+            let delay = policy.backoff(2);
+            assert!(delay > Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn test_with_retries() {
+        use crate::install::QuietProgress;
+        use std::cell::Cell;
+
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_attempts: 3,
+            ..RetryPolicy::default()
+        };
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_time().build().unwrap();
+
+        // Transient failures are retried until the attempt succeeds.
+        let calls = Cell::new(0);
+        let result = runtime.block_on(with_retries(&policy, "url", &QuietProgress, || {
+            calls.set(calls.get() + 1);
+            let call = calls.get();
+            async move {
+                if call < 3 {
+                    Err(AttemptError::Transient("timeout".to_string()))
+                } else {
+                    Ok(call)
+                }
+            }
+        }));
+        assert_eq!(result.unwrap(), 3);
+
+        // Once every attempt is used up, the last error is returned.
+        calls.set(0);
+        let result = runtime.block_on(with_retries(&policy, "url", &QuietProgress, || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(AttemptError::Transient("timeout".to_string())) }
+        }));
+        assert!(matches!(result, Err(InstallError::RetriesExhausted { attempts: 3, .. })));
+        assert_eq!(calls.get(), 3);
+
+        // Permanent failures are not retried.
+        calls.set(0);
+        let result = runtime.block_on(with_retries(&policy, "url", &QuietProgress, || {
+            calls.set(calls.get() + 1);
+            async { Err::<(), _>(AttemptError::Permanent("not found".to_string())) }
+        }));
+        assert!(matches!(result, Err(InstallError::Download { .. })));
+        assert_eq!(calls.get(), 1);
+    }
+}
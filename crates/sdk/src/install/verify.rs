@@ -0,0 +1,138 @@
+use super::{checksum_manifest_entries, sha256_file, ArtifactsType, InstallConfig, InstallError};
+
+#[cfg(feature = "network")]
+use {
+    super::{download_circuit_artifacts, InstallLock},
+    crate::block_on_send,
+};
+
+/// The name of the manifest shipped inside the artifacts, listing the SHA-256 digest of every file.
+pub const ARTIFACTS_MANIFEST_FILE: &str = "MANIFEST.sha256";
+
+/// The outcome of checking the integrity of installed circuit artifacts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InstallationReport {
+    /// The files that are expected but missing.
+    pub missing: Vec<String>,
+    /// The files whose digest doesn't match the manifest.
+    pub corrupt: Vec<String>,
+}
+
+impl InstallationReport {
+    /// Whether every expected file is present and intact.
+    pub fn is_ok(&self) -> bool {
+        self.missing.is_empty() && self.corrupt.is_empty()
+    }
+}
+
+/// Checks the circuit artifacts of `artifacts_type` installed in the default directory. See
+/// [verify_installation_with].
+pub fn verify_installation(
+    artifacts_type: ArtifactsType,
+) -> Result<InstallationReport, InstallError> {
+    verify_installation_with(&InstallConfig::from_env(), artifacts_type)
+}
+
+/// Checks that the circuit artifacts of `artifacts_type` installed according to `config` are
+/// complete and uncorrupted.
+///
+/// Every file listed in the [ARTIFACTS_MANIFEST_FILE] shipped with the artifacts is hashed and
+/// compared against the manifest. Artifacts from releases without a manifest are only checked for
+/// the presence of their key files.
+pub fn verify_installation_with(
+    config: &InstallConfig,
+    artifacts_type: ArtifactsType,
+) -> Result<InstallationReport, InstallError> {
+    let build_dir = config.build_dir();
+    let mut report = InstallationReport::default();
+
+    let manifest_path = build_dir.join(ARTIFACTS_MANIFEST_FILE);
+    if !manifest_path.exists() {
+        report.missing = artifacts_type
+            .required_files()
+            .iter()
+            .filter(|file| !build_dir.join(file).exists())
+            .map(|file| file.to_string())
+            .collect();
+        return Ok(report);
+    }
+
+    let manifest = std::fs::read_to_string(&manifest_path)?;
+    for (file_name, expected) in checksum_manifest_entries(&manifest) {
+        if !artifacts_type.includes(file_name) {
+            continue;
+        }
+        let path = build_dir.join(file_name);
+        if !path.exists() {
+            report.missing.push(file_name.to_string());
+        } else if sha256_file(&path)? != expected {
+            report.corrupt.push(file_name.to_string());
+        }
+    }
+    Ok(report)
+}
+
+/// Checks the circuit artifacts of `artifacts_type` installed according to `config`, and downloads
+/// and extracts them again if any file is missing or corrupt.
+///
+/// Returns the report of the check that was done before repairing.
+#[cfg(feature = "network")]
+pub fn repair_installation(
+    config: &InstallConfig,
+    artifacts_type: ArtifactsType,
+) -> Result<InstallationReport, InstallError> {
+    let report = verify_installation_with(config, artifacts_type)?;
+    if report.is_ok() {
+        return Ok(report);
+    }
+
+    let _lock = InstallLock::acquire(&config.circuits_dir, &config.version, |path| {
+        config.progress.waiting_for_lock(path)
+    })?;
+    let build_dir = config.build_dir();
+    config.progress.started(artifacts_type, &build_dir);
+    block_on_send(download_circuit_artifacts(config, &build_dir, artifacts_type))?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_installation() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = InstallConfig::new(dir.path());
+        let build_dir = config.build_dir();
+        std::fs::create_dir_all(&build_dir).unwrap();
+
+        // Without a manifest, only the presence of the key files is checked.
+        std::fs::write(build_dir.join("groth16_pk.bin"), b"pk").unwrap();
+        let report = verify_installation_with(&config, ArtifactsType::Groth16).unwrap();
+        assert_eq!(report.missing, vec!["groth16_vk.bin".to_string()]);
+
+        std::fs::write(build_dir.join("groth16_vk.bin"), b"vk").unwrap();
+        std::fs::write(build_dir.join("plonk_pk.bin"), b"pk").unwrap();
+        let mut manifest = String::new();
+        for file in ["groth16_pk.bin", "groth16_vk.bin", "plonk_pk.bin", "plonk_vk.bin"] {
+            let digest = if build_dir.join(file).exists() {
+                sha256_file(&build_dir.join(file)).unwrap()
+            } else {
+                "00".to_string()
+            };
+            manifest.push_str(&format!("{}  ./{}\n", digest, file));
+        }
+        std::fs::write(build_dir.join(ARTIFACTS_MANIFEST_FILE), manifest).unwrap();
+
+        assert!(verify_installation_with(&config, ArtifactsType::Groth16).unwrap().is_ok());
+        let report = verify_installation_with(&config, ArtifactsType::Plonk).unwrap();
+        assert_eq!(report.missing, vec!["plonk_vk.bin".to_string()]);
+        assert!(report.corrupt.is_empty());
+
+        // A truncated file, like one left behind by an interrupted extraction, is corrupt.
+        std::fs::write(build_dir.join("groth16_pk.bin"), b"p").unwrap();
+        let report = verify_installation_with(&config, ArtifactsType::All).unwrap();
+        assert_eq!(report.corrupt, vec!["groth16_pk.bin".to_string()]);
+        assert_eq!(report.missing, vec!["plonk_vk.bin".to_string()]);
+    }
+}
@@ -10,45 +10,136 @@
 pub mod proto {
     pub mod network;
 }
+#[cfg(feature = "abi")]
+pub mod abi;
 pub mod action;
+pub mod aggregation;
 pub mod artifacts;
+pub mod cache;
+pub mod checkpoint;
+pub mod distributed;
+pub mod elf;
+pub mod error;
+pub mod estimate;
 pub mod install;
+pub mod memory;
 #[cfg(feature = "network")]
 pub mod network;
 #[cfg(feature = "network")]
-pub use crate::network::prover::NetworkProver;
+pub use crate::network::{
+    hybrid::HybridProver,
+    prover::{NetworkProver, NetworkProverBuilder},
+};
 #[cfg(feature = "cuda")]
 pub use crate::provers::CudaProver;
 
 pub mod proof;
 pub mod provers;
+pub mod session;
 pub mod utils {
     pub use sp1_core_machine::utils::setup_logger;
 }
 
+pub use aggregation::WriteDeferredProof;
+use anyhow::Result;
+use cache::SetupCache;
+pub use cache::{KeyCache, ProofCache, DEFAULT_SETUP_CACHE_CAPACITY};
 use cfg_if::cfg_if;
+pub use elf::MappedElf;
+pub use error::SP1SdkError;
+pub use estimate::{CostEstimate, GasModel, HostCalibration, ProvingTimeEstimate};
+use futures::channel::oneshot;
+use memory::MemoryBudget;
 pub use proof::*;
 pub use provers::SP1VerificationError;
+pub use session::ProvingSession;
 use sp1_prover::components::DefaultProverComponents;
 
-use std::env;
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
 #[cfg(feature = "network")]
-use {std::future::Future, tokio::task::block_in_place};
+use {
+    std::future::Future,
+    tokio::{runtime::RuntimeFlavor, task::block_in_place},
+};
 
-pub use provers::{CpuProver, MockProver, Prover};
+use provers::ProofOpts;
+pub use provers::{
+    CpuProver, GpuOpts, MockBehavior, MockFailure, MockProver, MockProverError, ProofTimedOut,
+    Prover, ProverMode,
+};
 
-pub use sp1_core_executor::{ExecutionReport, HookEnv, SP1Context, SP1ContextBuilder};
-pub use sp1_core_machine::{io::SP1Stdin, riscv::cost::CostEstimator, SP1_CIRCUIT_VERSION};
+pub use sp1_core_executor::{
+    CancellationToken, ExecutionError, ExecutionReport, HookEnv, HookRegistrationError, ProofEvent,
+    ProofStage, SP1Context, SP1ContextBuilder,
+};
+pub use sp1_core_machine::{
+    io::{InputSchema, PublicValuesError, SP1PublicValues, SP1Stdin, StdinError},
+    riscv::cost::CostEstimator,
+    SP1_CIRCUIT_VERSION,
+};
 pub use sp1_prover::{
     CoreSC, HashableKey, InnerSC, OuterSC, PlonkBn254Proof, SP1Prover, SP1ProvingKey,
-    SP1VerifyingKey,
+    SP1VerifyingKey, VkDescriptor, VkExportFormat,
 };
+pub use sp1_stark::{SP1CoreOpts, SP1ProverOpts, SplitOpts};
 
 /// A client for interacting with SP1.
 pub struct ProverClient {
     /// The underlying prover implementation.
-    pub prover: Box<dyn Prover<DefaultProverComponents>>,
+    ///
+    /// The prover is shared behind an [Arc] so that [Self::prove_async] and
+    /// [Self::execute_async] can hand it to their proving thread. This is a breaking change from
+    /// earlier releases, where the field was a `Box<dyn Prover>`: code moving the prover out of
+    /// the client should hold an `Arc<dyn Prover>` instead, and can still borrow it as a
+    /// `&dyn Prover` with `client.prover.as_ref()`.
+    pub prover: Arc<dyn Prover<DefaultProverComponents>>,
+    defaults: ProveDefaults,
+}
+
+/// The options applied to every request made through a [ProverClient], as set on its
+/// [ProverClientBuilder]. Proving options that are not set keep the defaults of [action::Prove].
+#[derive(Debug, Clone, Default)]
+struct ProveDefaults {
+    timeout: Option<Duration>,
+    shard_size: Option<usize>,
+    shard_batch_size: Option<usize>,
+    trace_gen_workers: Option<usize>,
+    memory_budget: Option<u64>,
+    proof_cache: Option<ProofCache>,
+    key_cache: Option<KeyCache>,
+    setup_cache: Arc<SetupCache>,
+    gas_model: GasModel,
+    verify_any_version: bool,
+}
+
+impl ProveDefaults {
+    /// The options proofs are generated with, when requested without an [action::Prove].
+    fn proof_opts(&self) -> ProofOpts {
+        let mut core_opts = SP1CoreOpts::default();
+        if let Some(shard_size) = self.shard_size {
+            core_opts.shard_size = shard_size;
+        }
+        if let Some(shard_batch_size) = self.shard_batch_size {
+            core_opts.shard_batch_size = shard_batch_size;
+        }
+        if let Some(trace_gen_workers) = self.trace_gen_workers {
+            core_opts.trace_gen_workers = trace_gen_workers;
+        }
+        let sp1_prover_opts = SP1ProverOpts { core_opts, recursion_opts: SP1CoreOpts::recursion() };
+        ProofOpts {
+            sp1_prover_opts,
+            timeout: self.timeout,
+            checkpoint_dir: None,
+            memory_budget: self.memory_budget.map(MemoryBudget::new),
+        }
+    }
 }
 
 impl ProverClient {
@@ -69,33 +160,28 @@ impl ProverClient {
     /// let client = ProverClient::new();
     /// ```
     pub fn new() -> Self {
-        #[cfg(debug_assertions)]
-        panic!("sp1-sdk must be built in release mode. please compile with the --release flag.");
+        Self::builder().build()
+    }
 
-        #[allow(unreachable_code)]
-        match env::var("SP1_PROVER").unwrap_or("local".to_string()).to_lowercase().as_str() {
-            "mock" => Self { prover: Box::new(MockProver::new()) },
-            "local" => Self {
-                #[cfg(not(feature = "cuda"))]
-                prover: Box::new(CpuProver::new()),
-                #[cfg(feature = "cuda")]
-                prover: Box::new(CudaProver::new()),
-            },
-            "network" => {
-                cfg_if! {
-                    if #[cfg(feature = "network")] {
-                        Self {
-                            prover: Box::new(NetworkProver::new()),
-                        }
-                    } else {
-                        panic!("network feature is not enabled")
-                    }
-                }
-            }
-            _ => panic!(
-                "invalid value for SP1_PROVER enviroment variable: expected 'local', 'mock', or 'network'"
-            ),
-        }
+    /// Creates a new [ProverClientBuilder] to configure a [ProverClient] programmatically.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use sp1_sdk::{ProverClient, ProverMode};
+    ///
+    /// let client = ProverClient::builder()
+    ///     .mode(ProverMode::Local)
+    ///     .circuits_dir("/mnt/cache/circuits")
+    ///     .artifacts_url("https://mirror.internal/sp1-circuits")
+    ///     .shard_size(1 << 21)
+    ///     .timeout(Duration::from_secs(30 * 60))
+    ///     .build();
+    /// ```
+    pub fn builder() -> ProverClientBuilder {
+        ProverClientBuilder::default()
     }
 
     /// Creates a new [ProverClient] with the mock prover.
@@ -111,7 +197,28 @@ impl ProverClient {
     /// let client = ProverClient::mock();
     /// ```
     pub fn mock() -> Self {
-        Self { prover: Box::new(MockProver::new()) }
+        Self { prover: Arc::new(MockProver::new()), defaults: ProveDefaults::default() }
+    }
+
+    /// Creates a new [ProverClient] with a mock prover that injects latency and failures, to
+    /// exercise the error handling of an application.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use sp1_sdk::{MockBehavior, ProverClient};
+    /// use std::time::Duration;
+    ///
+    /// let behavior = MockBehavior::default()
+    ///     .latency(Duration::from_millis(10), Duration::from_millis(500))
+    ///     .fail_with("out of memory");
+    /// let client = ProverClient::mock_with_behavior(behavior);
+    /// ```
+    pub fn mock_with_behavior(behavior: MockBehavior) -> Self {
+        Self {
+            prover: Arc::new(MockProver::with_behavior(behavior)),
+            defaults: ProveDefaults::default(),
+        }
     }
 
     /// Creates a new [ProverClient] with the local prover.
@@ -127,7 +234,46 @@ impl ProverClient {
     /// let client = ProverClient::local();
     /// ```
     pub fn local() -> Self {
-        Self { prover: Box::new(CpuProver::new()) }
+        Self { prover: Arc::new(CpuProver::new()), defaults: ProveDefaults::default() }
+    }
+
+    /// Creates a new [ProverClient] proving the shards of the programs on the remote workers
+    /// listening at the given addresses, and the rest of the proofs locally.
+    ///
+    /// Start the workers with [distributed::Worker::serve].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use sp1_sdk::ProverClient;
+    ///
+    /// let client = ProverClient::distributed(["10.0.0.2:3100", "10.0.0.3:3100"]);
+    /// ```
+    pub fn distributed(workers: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let coordinator = distributed::Coordinator::new(workers);
+        Self {
+            prover: Arc::new(distributed::DistributedProver::new(coordinator)),
+            defaults: ProveDefaults::default(),
+        }
+    }
+
+    /// Creates a new [ProverClient] requesting proofs from the prover network, and proving them
+    /// locally when the network can't, see [HybridProver].
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use std::{sync::Arc, time::Duration};
+    ///
+    /// use sp1_sdk::{CpuProver, HybridProver, NetworkProver, ProverClient};
+    ///
+    /// let prover = HybridProver::new(NetworkProver::new(), Arc::new(CpuProver::new()))
+    ///     .with_network_timeout(Duration::from_secs(600));
+    /// let client = ProverClient::hybrid(prover);
+    /// ```
+    #[cfg(feature = "network")]
+    pub fn hybrid(prover: HybridProver) -> Self {
+        Self { prover: Arc::new(prover), defaults: ProveDefaults::default() }
     }
 
     /// Creates a new [ProverClient] with the network prover.
@@ -146,7 +292,8 @@ impl ProverClient {
         cfg_if! {
             if #[cfg(feature = "network")] {
                 Self {
-                    prover: Box::new(NetworkProver::new()),
+                    prover: Arc::new(NetworkProver::new()),
+                    defaults: ProveDefaults::default(),
                 }
             } else {
                 panic!("network feature is not enabled")
@@ -182,6 +329,61 @@ impl ProverClient {
         action::Execute::new(self.prover.as_ref(), elf, stdin)
     }
 
+    /// Estimates the cost of proving the given program on the given input, by executing it and
+    /// pricing the execution with the [GasModel] set on the [ProverClientBuilder].
+    ///
+    /// Returns the estimate together with the execution report it was derived from.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    /// let (estimate, _) = client.estimate(elf, &stdin).unwrap();
+    /// println!("{} cycles, {} gas", estimate.cycles, estimate.gas);
+    /// ```
+    pub fn estimate(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+    ) -> Result<(CostEstimate, ExecutionReport), SP1SdkError> {
+        let (_, report) = self.execute(elf, stdin.clone()).run()?;
+        Ok((self.defaults.gas_model.estimate(&report), report))
+    }
+
+    /// Predicts the time and peak memory of proving the execution described by `report` locally
+    /// in every [SP1ProofMode], with the options set on the [ProverClientBuilder].
+    ///
+    /// The prediction is based on the calibration of this host, see [HostCalibration::host],
+    /// which runs a short benchmark the first time it is needed on a machine. Compare it with
+    /// the deadline of a request to decide whether to prove it locally or on the network.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use sp1_sdk::{ProverClient, SP1ProofMode, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::local();
+    /// let (_, report) = client.execute(elf, SP1Stdin::new()).run().unwrap();
+    /// let predictions = client.predict_proving_time(&report);
+    /// if predictions[&SP1ProofMode::Groth16].duration > Duration::from_secs(10 * 60) {
+    ///     println!("proving on the network");
+    /// }
+    /// ```
+    pub fn predict_proving_time(
+        &self,
+        report: &ExecutionReport,
+    ) -> HashMap<SP1ProofMode, ProvingTimeEstimate> {
+        let opts = self.defaults.proof_opts();
+        let core_opts = opts.fitted_prover_opts().unwrap_or(opts.sp1_prover_opts).core_opts;
+        HostCalibration::host().predict(report, &core_opts)
+    }
+
     /// Prepare to prove the execution of the given program with the given input in the default
     /// mode. The returned [action::Prove] may be configured via its methods before running.
     /// For example, calling [action::Prove::compress] sets the mode to compressed mode.
@@ -212,7 +414,232 @@ impl ProverClient {
     /// let proof = client.prove(&pk, stdin).run().unwrap();
     /// ```
     pub fn prove<'a>(&'a self, pk: &'a SP1ProvingKey, stdin: SP1Stdin) -> action::Prove<'a> {
-        action::Prove::new(self.prover.as_ref(), pk, stdin)
+        let mut prove = action::Prove::new(self.prover.as_ref(), pk, stdin);
+        if let Some(timeout) = self.defaults.timeout {
+            prove = prove.timeout(timeout);
+        }
+        if let Some(shard_size) = self.defaults.shard_size {
+            prove = prove.shard_size(shard_size);
+        }
+        if let Some(shard_batch_size) = self.defaults.shard_batch_size {
+            prove = prove.shard_batch_size(shard_batch_size);
+        }
+        if let Some(trace_gen_workers) = self.defaults.trace_gen_workers {
+            prove = prove.trace_gen_workers(trace_gen_workers);
+        }
+        if let Some(bytes) = self.defaults.memory_budget {
+            prove = prove.memory_budget(bytes);
+        }
+        if let Some(cache) = &self.defaults.proof_cache {
+            prove = prove.proof_cache(cache);
+        }
+        prove
+    }
+
+    /// Prepare to prove the execution of the given program with the given input in `mode`, for
+    /// when the mode is only known at runtime. Equivalent to [Self::prove] followed by
+    /// [action::Prove::mode].
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1ProofMode, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    ///
+    /// let mode: SP1ProofMode = std::env::var("PROOF_MODE").unwrap().parse().unwrap();
+    /// let proof = client.prove_with_mode(&pk, &stdin, mode).run().unwrap();
+    /// client.verify(&proof, &vk).unwrap();
+    /// ```
+    pub fn prove_with_mode<'a>(
+        &'a self,
+        pk: &'a SP1ProvingKey,
+        stdin: &SP1Stdin,
+        mode: SP1ProofMode,
+    ) -> action::Prove<'a> {
+        self.prove(pk, stdin.clone()).mode(mode)
+    }
+
+    /// Prepare to prove the execution of the given program with each of the given inputs.
+    ///
+    /// The proving key is shared across the inputs, so the setup work is only done once. The
+    /// options set on the [ProverClientBuilder] apply to every proof.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let stdins = (0..100usize).map(|n| {
+    ///     let mut stdin = SP1Stdin::new();
+    ///     stdin.write(&n);
+    ///     stdin
+    /// });
+    ///
+    /// client.prove_batch(&pk, stdins).compressed().concurrency(2).for_each(|index, proof| {
+    ///     client.verify(&proof.unwrap(), &vk).unwrap();
+    ///     println!("proved input {index}");
+    /// });
+    /// ```
+    pub fn prove_batch<'a>(
+        &'a self,
+        pk: &'a SP1ProvingKey,
+        stdins: impl IntoIterator<Item = SP1Stdin>,
+    ) -> action::ProveBatch<'a> {
+        action::ProveBatch::new(self.prover.as_ref(), pk, stdins).opts(self.defaults.proof_opts())
+    }
+
+    /// Turns an existing core or compressed proof into a proof of the given kind, e.g. a Groth16
+    /// proof to verify onchain, without executing the program again.
+    ///
+    /// Only the stages of proof generation that follow the given proof are run: a compressed
+    /// proof is shrunk and wrapped, a core proof is compressed first.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1ProofKind, SP1ProofWithPublicValues};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (_, vk) = client.setup(elf);
+    /// let compressed = SP1ProofWithPublicValues::load("proof.bin").unwrap();
+    /// let proof = client.wrap(&vk, compressed, SP1ProofKind::Groth16).unwrap();
+    /// client.verify(&proof, &vk).unwrap();
+    /// ```
+    pub fn wrap(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues, SP1SdkError> {
+        self.prover.wrap(vk, proof, kind, self.defaults.proof_opts()).map_err(SP1SdkError::wrapping)
+    }
+
+    /// Aggregates compressed proofs into a single proof of the given kind, with the built-in
+    /// [aggregation::AGGREGATION_ELF] program.
+    ///
+    /// Each proof is verified recursively against the verifying key at the same index of `vks`.
+    /// The aggregated proof commits to the verifying keys and public values of every proof, as
+    /// encoded by [aggregation::commit_proof_pairs], and verifies against the key returned by
+    /// `client.setup(AGGREGATION_ELF)`.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{aggregation::AGGREGATION_ELF, ProverClient, SP1ProofKind, SP1Stdin};
+    ///
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let proofs = (10..13usize)
+    ///     .map(|n| {
+    ///         let mut stdin = SP1Stdin::new();
+    ///         stdin.write(&n);
+    ///         client.prove(&pk, stdin).compressed().run().unwrap()
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let vks = vec![vk; proofs.len()];
+    /// let aggregated = client.aggregate(&proofs, &vks, SP1ProofKind::Groth16).unwrap();
+    /// let (_, aggregation_vk) = client.setup(AGGREGATION_ELF);
+    /// client.verify(&aggregated, &aggregation_vk).unwrap();
+    /// ```
+    pub fn aggregate(
+        &self,
+        proofs: &[SP1ProofWithPublicValues],
+        vks: &[SP1VerifyingKey],
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues, SP1SdkError> {
+        let stdin = aggregation::aggregation_stdin(proofs, vks)?;
+        let (pk, _) = self.setup(aggregation::AGGREGATION_ELF);
+        let prove = self.prove(&pk, stdin);
+        match kind {
+            SP1ProofKind::Core => Err(SP1SdkError::InvalidRequest(anyhow::anyhow!(
+                "aggregated proofs must be compressed, plonk or groth16"
+            ))),
+            SP1ProofKind::Compressed => prove.compressed().run(),
+            SP1ProofKind::Plonk => prove.plonk().run(),
+            SP1ProofKind::Groth16 => prove.groth16().run(),
+        }
+    }
+
+    /// Proves the execution of the given program with the given input in the given mode, without
+    /// blocking the calling thread.
+    ///
+    /// Local proofs are generated on a dedicated thread, so the returned future can be awaited
+    /// from any async runtime. Proofs requested from the prover network are awaited directly. The
+    /// options set on the [ProverClientBuilder] apply, but runtime hooks can't be registered; use
+    /// [Self::prove] for those.
+    ///
+    /// Dropping the future cancels local proving at the next shard or recursion stage.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1ProofKind, SP1Stdin};
+    ///
+    /// # async fn prove() -> anyhow::Result<()> {
+    /// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client.setup(elf);
+    /// let mut stdin = SP1Stdin::new();
+    /// stdin.write(&10usize);
+    /// let proof = client.prove_async(&pk, stdin, SP1ProofKind::Compressed).await?;
+    /// client.verify(&proof, &vk)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prove_async(
+        &self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues, SP1SdkError> {
+        let opts = self.defaults.proof_opts();
+        #[cfg(feature = "network")]
+        if let Some(network) = self.prover.network_prover() {
+            let proof = network.prove(&pk.elf, stdin, kind.into(), opts.timeout).await;
+            return proof.map_err(SP1SdkError::proving);
+        }
+
+        let (prover, pk) = (self.prover.clone(), pk.clone());
+        spawn_prover_thread(move |cancellation_token| {
+            let context =
+                SP1Context { cancellation_token: Some(cancellation_token), ..Default::default() };
+            prover.prove(&pk, stdin, opts, context, kind)
+        })
+        .await
+        .map_err(SP1SdkError::proving)
+    }
+
+    /// Executes the given program on the given input (without generating a proof), without
+    /// blocking the calling thread.
+    ///
+    /// The program is executed on a dedicated thread, so the returned future can be awaited from
+    /// any async runtime, and dropping it cancels the execution at the next shard boundary. Use
+    /// [Self::execute] to register runtime hooks or set a cycle limit.
+    pub async fn execute_async(
+        &self,
+        elf: &[u8],
+        stdin: SP1Stdin,
+    ) -> Result<(SP1PublicValues, ExecutionReport), SP1SdkError> {
+        let (prover, elf) = (self.prover.clone(), elf.to_vec());
+        spawn_prover_thread(move |cancellation_token| {
+            let context =
+                SP1Context { cancellation_token: Some(cancellation_token), ..Default::default() };
+            Ok(prover.sp1_prover().execute(&elf, &stdin, context)?)
+        })
+        .await
+        .map_err(SP1SdkError::proving)
+    }
+
+    /// Starts a [ProvingSession] proving the given program many times, keeping its proving key
+    /// and optionally the Plonk and Groth16 circuits in memory across proofs.
+    pub fn session(&self, elf: &[u8]) -> ProvingSession<'_> {
+        ProvingSession::new(self, elf)
     }
 
     /// Verifies that the given proof is valid and matches the given verification key produced by
@@ -230,11 +657,19 @@ impl ProverClient {
     /// let proof = client.prove(&pk, stdin).run().unwrap();
     /// client.verify(&proof, &vk).unwrap();
     /// ```
+    ///
+    /// Proofs generated by other versions of SP1 are rejected with
+    /// [SP1VerificationError::VersionMismatch], unless the client was built with
+    /// [ProverClientBuilder::verify_any_version]. In that case `vk` must be the verifying key the
+    /// version of the proof produced, as the key of a program changes between versions.
     pub fn verify(
         &self,
         proof: &SP1ProofWithPublicValues,
         vk: &SP1VerifyingKey,
     ) -> Result<(), SP1VerificationError> {
+        if self.defaults.verify_any_version {
+            return self.prover.verify_any_version(proof, vk);
+        }
         self.prover.verify(proof, vk)
     }
 
@@ -262,7 +697,34 @@ impl ProverClient {
     /// let (pk, vk) = client.setup(elf);
     /// ```
     pub fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
-        self.prover.setup(elf)
+        self.defaults.setup_cache.get_or_setup(elf, || match &self.defaults.key_cache {
+            Some(cache) => cache.setup(self.prover.as_ref(), elf),
+            None => self.prover.setup(elf),
+        })
+    }
+
+    /// Setup the program in the ELF file at `path`, see [Self::setup].
+    ///
+    /// The file is memory-mapped with [MappedElf] instead of being read into memory, and its
+    /// header is checked to be a RISC-V executable before running the setup, so a wrong path
+    /// fails with [SP1SdkError::InvalidRequest] instead of a panic.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{ProverClient, SP1Stdin};
+    ///
+    /// let client = ProverClient::new();
+    /// let (pk, vk) = client
+    ///     .setup_from_path("examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf")
+    ///     .unwrap();
+    /// let proof = client.prove(&pk, SP1Stdin::new()).run().unwrap();
+    /// ```
+    pub fn setup_from_path(
+        &self,
+        path: impl AsRef<Path>,
+    ) -> Result<(SP1ProvingKey, SP1VerifyingKey), SP1SdkError> {
+        let elf = MappedElf::open(path)?;
+        Ok(self.setup(&elf))
     }
 }
 
@@ -272,10 +734,361 @@ impl Default for ProverClient {
     }
 }
 
+/// A builder for [ProverClient].
+///
+/// Options that are not set fall back to their environment variables, so `build()` on a fresh
+/// builder behaves exactly like [ProverClient::new].
+#[derive(Clone, Default)]
+pub struct ProverClientBuilder {
+    mode: Option<ProverMode>,
+    defaults: ProveDefaults,
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    network_rpc_urls: Vec<String>,
+    #[cfg_attr(not(feature = "network"), allow(dead_code))]
+    private_key: Option<String>,
+    #[cfg(feature = "network")]
+    network_auth: Option<network::auth::NetworkAuth>,
+    circuits_dir: Option<PathBuf>,
+    artifacts_url: Option<String>,
+    download_concurrency: Option<usize>,
+    download_retry: Option<install::RetryPolicy>,
+    artifacts_proxy: Option<String>,
+    install_progress: Option<Arc<dyn install::InstallProgress>>,
+    gpu: Option<GpuOpts>,
+}
+
+impl ProverClientBuilder {
+    /// Sets the prover used under the hood.
+    ///
+    /// Overrides the `SP1_PROVER` environment variable.
+    pub fn mode(mut self, mode: ProverMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Sets the default timeout for proof generation, see [action::Prove::timeout].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.defaults.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the default shard size for proving, see [action::Prove::shard_size].
+    ///
+    /// Overrides the `SHARD_SIZE` environment variable.
+    pub fn shard_size(mut self, shard_size: usize) -> Self {
+        self.defaults.shard_size = Some(shard_size);
+        self
+    }
+
+    /// Sets the default shard batch size for proving, see [action::Prove::shard_batch_size].
+    ///
+    /// Overrides the `SHARD_BATCH_SIZE` environment variable.
+    pub fn shard_batch_size(mut self, shard_batch_size: usize) -> Self {
+        self.defaults.shard_batch_size = Some(shard_batch_size);
+        self
+    }
+
+    /// Sets the default number of workers generating traces while proving, see
+    /// [action::Prove::trace_gen_workers].
+    ///
+    /// Overrides the `TRACE_GEN_WORKERS` environment variable.
+    pub fn trace_gen_workers(mut self, trace_gen_workers: usize) -> Self {
+        self.defaults.trace_gen_workers = Some(trace_gen_workers);
+        self
+    }
+
+    /// Sets the default memory budget of local proving, in bytes, see
+    /// [action::Prove::memory_budget].
+    pub fn memory_budget(mut self, bytes: u64) -> Self {
+        self.defaults.memory_budget = Some(bytes);
+        self
+    }
+
+    /// Caches the proofs generated with [ProverClient::prove] in `dir`, see
+    /// [action::Prove::proof_cache].
+    ///
+    /// Overrides the `SP1_PROOF_CACHE_DIR` environment variable. Proofs are only cached if either
+    /// is set.
+    pub fn proof_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.defaults.proof_cache = Some(ProofCache::new(dir));
+        self
+    }
+
+    /// Caches the keys returned by [ProverClient::setup] in `dir`, loading them instead of
+    /// running the setup again when the ELF is unchanged. [KeyCache::default_dir] is the
+    /// conventional location.
+    ///
+    /// Overrides the `SP1_KEY_CACHE_DIR` environment variable. Keys are only cached if either is
+    /// set.
+    pub fn key_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.defaults.key_cache = Some(KeyCache::new(dir));
+        self
+    }
+
+    /// Sets the number of programs whose keys [ProverClient::setup] keeps in memory, so that
+    /// setting up one of the most recently set up programs again returns its keys without
+    /// running the setup. Defaults to [DEFAULT_SETUP_CACHE_CAPACITY], zero disables it.
+    ///
+    /// The keys of large programs take hundreds of megabytes, so services proving many different
+    /// programs should bound this to the programs they prove most often.
+    pub fn setup_cache_capacity(mut self, capacity: usize) -> Self {
+        self.defaults.setup_cache = Arc::new(SetupCache::new(capacity));
+        self
+    }
+
+    /// Makes [ProverClient::verify] accept the Plonk and Groth16 proofs of previous versions of
+    /// SP1, see [Prover::verify_any_version].
+    ///
+    /// The circuit artifacts of the version a proof was generated with are downloaded on demand,
+    /// from the same location as the current ones. These releases aren't signed, so the release
+    /// key isn't required for them. The proofs of a previous version must be verified against
+    /// the verifying key that version produced for the program.
+    pub fn verify_any_version(mut self) -> Self {
+        self.defaults.verify_any_version = true;
+        self
+    }
+
+    /// Sets the pricing model [ProverClient::estimate] estimates costs with.
+    pub fn gas_model(mut self, gas_model: GasModel) -> Self {
+        self.defaults.gas_model = gas_model;
+        self
+    }
+
+    /// Sets the RPC endpoint of the prover network, used in [ProverMode::Network].
+    ///
+    /// Overrides the `PROVER_NETWORK_RPC` environment variable.
+    pub fn network_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.network_rpc_urls = vec![rpc_url.into()];
+        self
+    }
+
+    /// Sets the RPC endpoints of the prover network, the primary first, used in
+    /// [ProverMode::Network]. Requests fail over to the next endpoints while the primary can't be
+    /// reached.
+    ///
+    /// Overrides the `PROVER_NETWORK_RPC` environment variable.
+    pub fn network_rpc_urls(
+        mut self,
+        rpc_urls: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.network_rpc_urls = rpc_urls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the private key used to authenticate with the prover network, used in
+    /// [ProverMode::Network].
+    ///
+    /// Overrides the `SP1_PRIVATE_KEY` environment variable.
+    pub fn private_key(mut self, private_key: impl Into<String>) -> Self {
+        self.private_key = Some(private_key.into());
+        self
+    }
+
+    /// Sets how requests to the prover network are authenticated, e.g. with a keystore or a
+    /// remote signer, used in [ProverMode::Network]. Takes precedence over [Self::private_key].
+    #[cfg(feature = "network")]
+    pub fn network_auth(mut self, auth: network::auth::NetworkAuth) -> Self {
+        self.network_auth = Some(auth);
+        self
+    }
+
+    /// Sets the root directory under which the circuit artifacts are installed and looked up.
+    ///
+    /// Overrides the `SP1_CIRCUITS_DIR` environment variable.
+    pub fn circuits_dir(mut self, circuits_dir: impl Into<PathBuf>) -> Self {
+        self.circuits_dir = Some(circuits_dir.into());
+        self
+    }
+
+    /// Sets the location the circuit artifacts are downloaded from, e.g. an internal mirror of
+    /// the artifacts bucket.
+    ///
+    /// Accepts a base URL or a URL template, see [install::InstallConfig::url_template].
+    /// Overrides the `SP1_CIRCUIT_ARTIFACTS_URL` environment variable.
+    pub fn artifacts_url(mut self, artifacts_url: impl Into<String>) -> Self {
+        self.artifacts_url = Some(artifacts_url.into());
+        self
+    }
+
+    /// Sets the number of connections the circuit artifacts are downloaded over.
+    ///
+    /// Overrides the `SP1_CIRCUIT_DOWNLOAD_CONCURRENCY` environment variable.
+    pub fn download_concurrency(mut self, download_concurrency: usize) -> Self {
+        self.download_concurrency = Some(download_concurrency);
+        self
+    }
+
+    /// Sets how failed downloads of the circuit artifacts are retried, e.g.
+    /// [install::RetryPolicy::none] to fail on the first error.
+    ///
+    /// Overrides the `SP1_CIRCUIT_DOWNLOAD_ATTEMPTS` environment variable.
+    pub fn download_retry(mut self, policy: install::RetryPolicy) -> Self {
+        self.download_retry = Some(policy);
+        self
+    }
+
+    /// Sets the proxy the circuit artifacts are downloaded through.
+    ///
+    /// Takes precedence over the `HTTPS_PROXY`, `HTTP_PROXY` and `ALL_PROXY` environment
+    /// variables.
+    pub fn artifacts_proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.artifacts_proxy = Some(proxy.into());
+        self
+    }
+
+    /// Sets the callback that receives updates on the progress of installing the circuit
+    /// artifacts, e.g. [install::QuietProgress] to silence the terminal output.
+    pub fn install_progress(mut self, progress: impl install::InstallProgress + 'static) -> Self {
+        self.install_progress = Some(Arc::new(progress));
+        self
+    }
+
+    /// Proves on the GPU selected by `opts` in [ProverMode::Local], running the core and
+    /// recursion provers on the CUDA backend.
+    ///
+    /// Requires the `cuda` feature. If it isn't enabled or the device isn't found, the client
+    /// falls back to the CPU prover with a warning.
+    ///
+    /// ### Examples
+    ///
+    /// ```no_run
+    /// use sp1_sdk::{GpuOpts, ProverClient};
+    ///
+    /// let client = ProverClient::builder()
+    ///     .gpu(GpuOpts { device_id: 1, memory_limit: Some(24 << 30) })
+    ///     .build();
+    /// ```
+    pub fn gpu(mut self, opts: GpuOpts) -> Self {
+        self.gpu = Some(opts);
+        self
+    }
+
+    /// Builds the [ProverClient].
+    ///
+    /// Unless set with [Self::mode], the prover used under the hood is selected with the
+    /// `SP1_PROVER` enviroment variable. See [ProverClient::new] for the supported values.
+    #[allow(unreachable_code)]
+    pub fn build(self) -> ProverClient {
+        #[cfg(debug_assertions)]
+        panic!("sp1-sdk must be built in release mode. please compile with the --release flag.");
+
+        let mut install = match self.circuits_dir {
+            Some(circuits_dir) => install::InstallConfig::new(circuits_dir),
+            None => install::InstallConfig::from_env(),
+        };
+        if let Some(artifacts_url) = self.artifacts_url {
+            install.url_template = artifacts_url;
+        }
+        if let Some(download_concurrency) = self.download_concurrency {
+            install.download_concurrency = download_concurrency;
+        }
+        if let Some(policy) = self.download_retry {
+            install.retry = policy;
+        }
+        install.proxy = self.artifacts_proxy;
+        if let Some(progress) = self.install_progress {
+            install.progress = progress;
+        }
+        let mut defaults = self.defaults;
+        if defaults.proof_cache.is_none() {
+            defaults.proof_cache = ProofCache::from_env();
+        }
+        if defaults.key_cache.is_none() {
+            defaults.key_cache = KeyCache::from_env();
+        }
+        match self.mode.unwrap_or_else(ProverMode::from_env) {
+            ProverMode::Mock => ProverClient { prover: Arc::new(MockProver::new()), defaults },
+            ProverMode::Local => ProverClient { prover: local_prover(self.gpu, install), defaults },
+            ProverMode::Network => {
+                cfg_if! {
+                    if #[cfg(feature = "network")] {
+                        let mut builder = NetworkProver::builder().install_config(install);
+                        if let Some(private_key) = self.private_key {
+                            builder = builder.private_key(private_key);
+                        }
+                        if let Some(auth) = self.network_auth {
+                            builder = builder.auth(auth);
+                        }
+                        if !self.network_rpc_urls.is_empty() {
+                            builder = builder.rpc_urls(self.network_rpc_urls);
+                        }
+                        let prover = builder.build();
+                        ProverClient { prover: Arc::new(prover), defaults }
+                    } else {
+                        panic!("network feature is not enabled")
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The prover of [ProverMode::Local], proving on the GPU selected by `gpu` if it is present.
+fn local_prover(
+    gpu: Option<GpuOpts>,
+    install: install::InstallConfig,
+) -> Arc<dyn Prover<DefaultProverComponents>> {
+    cfg_if! {
+        if #[cfg(feature = "cuda")] {
+            match gpu {
+                None => return Arc::new(CudaProver::new().with_install_config(install)),
+                Some(gpu) if CudaProver::gpu_available(gpu) => {
+                    return Arc::new(CudaProver::with_gpu(gpu).with_install_config(install))
+                }
+                Some(gpu) => tracing::warn!(
+                    "CUDA device {} not found, falling back to the CPU prover",
+                    gpu.device_id
+                ),
+            }
+        } else {
+            if gpu.is_some() {
+                tracing::warn!(
+                    "the `cuda` feature of sp1-sdk is not enabled, falling back to the CPU prover"
+                );
+            }
+        }
+    }
+    Arc::new(CpuProver::new().with_install_config(install))
+}
+
+/// Runs the CPU-bound work `f` on a dedicated thread, returning a future that resolves to its
+/// result. Panics in `f` are propagated to the caller awaiting the future.
+///
+/// `f` is handed a [CancellationToken] that is cancelled when the future is dropped.
+fn spawn_prover_thread<T: Send + 'static>(
+    f: impl FnOnce(CancellationToken) -> Result<T> + Send + 'static,
+) -> impl std::future::Future<Output = Result<T>> {
+    let cancellation_token = CancellationToken::new();
+    let guard = CancelOnDrop(cancellation_token.clone());
+    let (tx, rx) = oneshot::channel();
+    let spawned = std::thread::Builder::new().name("sp1-prover".to_string()).spawn(move || {
+        let _ = tx.send(panic::catch_unwind(AssertUnwindSafe(|| f(cancellation_token))));
+    });
+    async move {
+        let _guard = guard;
+        spawned?;
+        match rx.await {
+            Ok(Ok(result)) => result,
+            Ok(Err(err)) => panic::resume_unwind(err),
+            Err(_) => Err(anyhow::anyhow!("the prover thread exited without a result")),
+        }
+    }
+}
+
+/// Cancels its token when dropped.
+struct CancelOnDrop(CancellationToken);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
+
 /// Utility method for blocking on an async function.
 ///
 /// If we're already in a tokio runtime, we'll block in place. Otherwise, we'll create a new
-/// runtime.
+/// runtime. A current-thread runtime can't be blocked in place, see [block_on_send] for futures
+/// that may be driven from one.
 #[cfg(feature = "network")]
 pub fn block_on<T>(fut: impl Future<Output = T>) -> T {
     // Handle case if we're already in an tokio runtime.
@@ -288,10 +1101,36 @@ pub fn block_on<T>(fut: impl Future<Output = T>) -> T {
     }
 }
 
+/// Blocks on `fut` like [block_on], but also from inside a current-thread tokio runtime, where the
+/// future is driven by a fresh runtime on a helper thread instead, which is why it must be [Send].
+#[cfg(feature = "network")]
+pub fn block_on_send<F>(fut: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::CurrentThread => {
+            std::thread::scope(|scope| {
+                scope
+                    .spawn(|| block_on(fut))
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+        }
+        _ => block_on(fut),
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
-    use crate::{utils, CostEstimator, ProverClient, SP1Stdin};
+    use std::time::Duration;
+
+    use crate::{
+        utils, CostEstimator, MockBehavior, MockProverError, ProverClient, SP1ProofKind,
+        SP1ProofWithPublicValues, SP1Stdin,
+    };
 
     #[test]
     fn test_execute() {
@@ -340,6 +1179,73 @@ mod tests {
         client.verify(&proof, &vk).unwrap();
     }
 
+    #[test]
+    fn test_e2e_wrap_mock() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let compressed = client.prove(&pk, stdin).compressed().run().unwrap();
+        let proof = client.wrap(&vk, compressed.clone(), SP1ProofKind::Groth16).unwrap();
+        assert_eq!(proof.public_values.as_slice(), compressed.public_values.as_slice());
+        client.verify(&proof, &vk).unwrap();
+        assert!(client.wrap(&vk, proof, SP1ProofKind::Plonk).is_err());
+    }
+
+    #[test]
+    fn test_e2e_prove_batch_mock() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let stdins = (0..4usize).map(|n| {
+            let mut stdin = SP1Stdin::new();
+            stdin.write(&n);
+            stdin
+        });
+        let proofs = client.prove_batch(&pk, stdins).plonk().concurrency(2).run();
+        assert_eq!(proofs.len(), 4);
+        for proof in proofs {
+            client.verify(&proof.unwrap(), &vk).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_e2e_prove_mock_failures() {
+        utils::setup_logger();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+
+        let client = ProverClient::mock_with_behavior(MockBehavior::default().fail_with("oom"));
+        let (pk, _) = client.setup(elf);
+        let err = client.prove(&pk, stdin.clone()).plonk().run().unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<MockProverError>(),
+            Some(&MockProverError::Failed("oom".to_string()))
+        );
+
+        let timeout = Duration::from_millis(10);
+        let client =
+            ProverClient::mock_with_behavior(MockBehavior::default().timeout_after(timeout));
+        let err = client.prove(&pk, stdin.clone()).plonk().run().unwrap_err();
+        assert_eq!(err.downcast_ref::<MockProverError>(), Some(&MockProverError::Timeout(timeout)));
+
+        let client = ProverClient::mock_with_behavior(
+            MockBehavior::default()
+                .latency(Duration::ZERO, Duration::from_millis(10))
+                .invalid_public_values(),
+        );
+        let (pk, vk) = client.setup(elf);
+        let proof = client.prove(&pk, stdin).plonk().run().unwrap();
+        assert!(client.verify(&proof, &vk).is_err());
+    }
+
     #[test]
     fn test_e2e_prove_plonk_mock() {
         utils::setup_logger();
@@ -352,4 +1258,75 @@ mod tests {
         let proof = client.prove(&pk, stdin).plonk().run().unwrap();
         client.verify(&proof, &vk).unwrap();
     }
+
+    #[test]
+    fn test_proof_metadata_mock() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, _) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof = client.prove(&pk, stdin).compressed().run().unwrap();
+        let metadata = proof.metadata.clone().unwrap();
+        assert!(metadata.cycles > 0);
+        assert!(metadata.execution_duration().is_some());
+        assert_eq!(metadata.prover, "Mock");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("proof.bin");
+        proof.save(&path).unwrap();
+        assert_eq!(SP1ProofWithPublicValues::load(&path).unwrap().metadata, Some(metadata));
+
+        // Proofs serialized before the metadata was added still load.
+        let legacy = bincode::serialize(&(
+            &proof.proof,
+            &proof.stdin,
+            &proof.public_values,
+            &proof.sp1_version,
+        ))
+        .unwrap();
+        let loaded = SP1ProofWithPublicValues::from_bincode(&legacy).unwrap();
+        assert!(loaded.metadata.is_none());
+        assert_eq!(loaded.public_values.as_slice(), proof.public_values.as_slice());
+    }
+
+    #[test]
+    fn test_execute_async() {
+        utils::setup_logger();
+        let client = ProverClient::local();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let (_, report) = futures::executor::block_on(client.execute_async(elf, stdin)).unwrap();
+        tracing::info!("gas = {}", report.estimate_gas());
+    }
+
+    #[cfg(feature = "network")]
+    #[test]
+    fn test_block_on() {
+        // Futures that aren't `Send` can be blocked on outside of a current-thread runtime.
+        let value = std::rc::Rc::new(1);
+        assert_eq!(crate::block_on(async { *value }), 1);
+
+        let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+        assert_eq!(runtime.block_on(async { crate::block_on_send(async { 2 }) }), 2);
+    }
+
+    #[test]
+    fn test_e2e_prove_async_mock() {
+        utils::setup_logger();
+        let client = ProverClient::mock();
+        let elf =
+            include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+        let (pk, vk) = client.setup(elf);
+        let mut stdin = SP1Stdin::new();
+        stdin.write(&10usize);
+        let proof =
+            futures::executor::block_on(client.prove_async(&pk, stdin, SP1ProofKind::Plonk))
+                .unwrap();
+        client.verify(&proof, &vk).unwrap();
+    }
 }
@@ -0,0 +1,135 @@
+//! # Memory Budgets
+//!
+//! Bounds the memory used by local proving, so that a proof that doesn't fit on the machine fails
+//! with a [MemoryBudgetError] up front instead of being killed by the kernel halfway through.
+
+use sp1_stark::{SP1CoreOpts, SP1ProverOpts};
+use thiserror::Error;
+
+/// The estimated memory taken by each cycle of a shard whose traces are in flight, in bytes.
+///
+/// Measured on the RISC-V machine with the default split options, rounded up. Precompile-heavy
+/// programs can take more, so budgets should leave some headroom.
+pub const BYTES_PER_SHARD_CYCLE: u64 = 640;
+
+/// The estimated memory taken by proving regardless of the shard sizes, in bytes, covering the
+/// proving key, the recursion programs and the executor.
+pub const BASE_MEMORY: u64 = 4 << 30;
+
+/// An error fitting the prover options to a [MemoryBudget].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryBudgetError {
+    #[error(
+        "the memory budget of {budget} bytes is too small, proving takes at least {required} \
+         bytes, lower the shard size or raise the budget"
+    )]
+    TooSmall { budget: u64, required: u64 },
+}
+
+/// A limit on the memory used by local proving.
+///
+/// When the options of a proof are estimated to exceed the budget, [Self::fit] trades speed for
+/// memory: it first serializes the stages of proving, generating the traces of one batch at a
+/// time with a single worker, then shrinks the shard batch size down to a single shard. The shard
+/// size is never changed, since it determines the shape of the proof.
+///
+/// The estimate is coarse, see [BYTES_PER_SHARD_CYCLE] and [BASE_MEMORY].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryBudget {
+    bytes: u64,
+}
+
+impl MemoryBudget {
+    /// Creates a budget of `bytes` bytes.
+    pub fn new(bytes: u64) -> Self {
+        Self { bytes }
+    }
+
+    /// The budget, in bytes.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// The estimated peak memory of proving with `opts`, in bytes.
+    pub fn estimate(opts: &SP1CoreOpts) -> u64 {
+        // The batch being proven, the batches being generated by the workers and the batches
+        // buffered between them.
+        let batches =
+            opts.trace_gen_workers as u64 + opts.records_and_traces_channel_capacity as u64 + 1;
+        let shards = batches.saturating_mul(opts.shard_batch_size as u64);
+        BASE_MEMORY.saturating_add(
+            shards.saturating_mul(opts.shard_size as u64).saturating_mul(BYTES_PER_SHARD_CYCLE),
+        )
+    }
+
+    /// Adjusts the core and recursion options of `opts` to stay under the budget, failing if
+    /// even proving a single shard at a time exceeds it.
+    pub fn fit(&self, opts: SP1ProverOpts) -> Result<SP1ProverOpts, MemoryBudgetError> {
+        Ok(SP1ProverOpts {
+            core_opts: self.fit_core(opts.core_opts)?,
+            recursion_opts: self.fit_core(opts.recursion_opts)?,
+        })
+    }
+
+    fn fit_core(&self, mut opts: SP1CoreOpts) -> Result<SP1CoreOpts, MemoryBudgetError> {
+        if Self::estimate(&opts) <= self.bytes {
+            return Ok(opts);
+        }
+
+        // Serialize the stages, keeping a single batch in flight between them.
+        opts.trace_gen_workers = 1;
+        opts.records_and_traces_channel_capacity = 1;
+        opts.reconstruct_commitments = true;
+
+        while Self::estimate(&opts) > self.bytes {
+            if opts.shard_batch_size <= 1 {
+                return Err(MemoryBudgetError::TooSmall {
+                    budget: self.bytes,
+                    required: Self::estimate(&opts),
+                });
+            }
+            opts.shard_batch_size /= 2;
+        }
+        tracing::info!(
+            "proving with a shard batch size of {} to stay under the memory budget of {} bytes",
+            opts.shard_batch_size,
+            self.bytes
+        );
+        Ok(opts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let core_opts = SP1CoreOpts {
+            shard_size: 1 << 20,
+            shard_batch_size: 16,
+            trace_gen_workers: 4,
+            ..Default::default()
+        };
+        let opts = SP1ProverOpts { core_opts, recursion_opts: core_opts };
+
+        let unbounded = MemoryBudget::new(u64::MAX).fit(opts).unwrap();
+        assert_eq!(unbounded.core_opts.shard_batch_size, 16);
+        assert_eq!(unbounded.core_opts.trace_gen_workers, 4);
+
+        let budget = MemoryBudget::new(BASE_MEMORY + 8 * (1 << 20) * BYTES_PER_SHARD_CYCLE);
+        let fitted = budget.fit(opts).unwrap().core_opts;
+        assert_eq!(fitted.trace_gen_workers, 1);
+        assert_eq!(fitted.shard_batch_size, 2);
+        assert!(MemoryBudget::estimate(&fitted) <= budget.bytes());
+
+        let err = MemoryBudget::new(BASE_MEMORY).fit(opts).unwrap_err();
+        assert_eq!(
+            err,
+            MemoryBudgetError::TooSmall {
+                budget: BASE_MEMORY,
+                required: BASE_MEMORY + 3 * (1 << 20) * BYTES_PER_SHARD_CYCLE
+            }
+        );
+    }
+}
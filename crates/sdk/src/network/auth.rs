@@ -1,11 +1,9 @@
-use std::{borrow::Cow, str::FromStr};
+use std::{borrow::Cow, path::Path, str::FromStr, sync::Arc};
 
 use alloy_sol_types::{sol, Eip712Domain, SolStruct};
-use anyhow::Result;
-use ethers::{
-    signers::{LocalWallet, Signer},
-    types::H256,
-};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use ethers::{signers::LocalWallet, types::H256};
 
 use crate::proto::network::UnclaimReason;
 
@@ -38,6 +36,42 @@ sol! {
         uint64 nonce;
         string proof_id;
     }
+
+    struct CancelProof {
+        uint64 nonce;
+        string proof_id;
+    }
+
+    struct RegisterProgram {
+        uint64 nonce;
+        bytes32 program_hash;
+    }
+}
+
+/// Signs the messages authenticating the requests to the Succinct prover network with a
+/// secp256k1 key.
+///
+/// Implemented by [LocalWallet] for keys held in memory. Implement it to keep the key in an HSM
+/// or a remote signing service, and pass the signer to [NetworkAuth::from_signer].
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// The address of the account the key belongs to.
+    fn address(&self) -> [u8; 20];
+
+    /// Signs the EIP-712 signing hash `hash`, returning the 65 bytes of the signature in the
+    /// `r || s || v` format.
+    async fn sign_hash(&self, hash: [u8; 32]) -> Result<Vec<u8>>;
+}
+
+#[async_trait]
+impl Signer for LocalWallet {
+    fn address(&self) -> [u8; 20] {
+        ethers::signers::Signer::address(self).0
+    }
+
+    async fn sign_hash(&self, hash: [u8; 32]) -> Result<Vec<u8>> {
+        Ok(LocalWallet::sign_hash(self, H256(hash))?.to_vec())
+    }
 }
 
 /// Handles authentication for the Succinct prover network. All interactions that could potentially
@@ -46,15 +80,34 @@ sol! {
 /// The messages themselves follow EIP-712, where the domain is "succinct" and the TypeStruct
 /// changes depending on which endpoint is being used. Documentation for EIP-712 can be found at:
 /// https://eips.ethereum.org/EIPS/eip-712
+#[derive(Clone)]
 pub struct NetworkAuth {
-    // Holds a secp256k1 private key.
-    wallet: LocalWallet,
+    // Signs with the secp256k1 key.
+    signer: Arc<dyn Signer>,
 }
 
 impl NetworkAuth {
+    /// Authenticates with the hex-encoded secp256k1 `private_key`.
+    ///
+    /// Panics if the private key is invalid.
     pub fn new(private_key: &str) -> Self {
         let wallet = LocalWallet::from_str(private_key).unwrap();
-        Self { wallet }
+        Self::from_signer(wallet)
+    }
+
+    /// Authenticates with the key held by `signer`.
+    pub fn from_signer(signer: impl Signer + 'static) -> Self {
+        Self { signer: Arc::new(signer) }
+    }
+
+    /// Authenticates with the key of the encrypted JSON keystore at `path`, in the scrypt or
+    /// pbkdf2 format of the Web3 Secret Storage Definition used by geth, decrypted with
+    /// `password`.
+    pub fn from_keystore(path: impl AsRef<Path>, password: impl AsRef<[u8]>) -> Result<Self> {
+        let path = path.as_ref();
+        let wallet = LocalWallet::decrypt_keystore(path, password)
+            .with_context(|| format!("failed to decrypt the keystore {}", path.display()))?;
+        Ok(Self::from_signer(wallet))
     }
 
     /// Gets the EIP-712 domain separator for the Succinct prover network.
@@ -68,15 +121,14 @@ impl NetworkAuth {
 
     /// Gets the address of the auth's account, derived from the secp256k1 private key.
     pub fn get_address(&self) -> [u8; 20] {
-        self.wallet.address().0
+        self.signer.address()
     }
 
     // Generic function to sign a message based on the SolStruct.
     async fn sign_message<T: SolStruct>(&self, type_struct: T) -> Result<Vec<u8>> {
         let domain_separator = Self::get_domain_separator();
         let message_hash = type_struct.eip712_signing_hash(&domain_separator);
-        let signature = self.wallet.sign_hash(H256(message_hash.0))?;
-        Ok(signature.to_vec())
+        self.signer.sign_hash(message_hash.0).await
     }
 
     /// Signs a message to to request ot create a proof.
@@ -122,4 +174,37 @@ impl NetworkAuth {
         let type_struct = FulfillProof { nonce, proof_id: proof_id.to_string() };
         self.sign_message(type_struct).await
     }
+
+    /// Signs a message to register the program with the sha256 hash `program_hash`.
+    pub async fn sign_register_program_message(
+        &self,
+        nonce: u64,
+        program_hash: [u8; 32],
+    ) -> Result<Vec<u8>> {
+        let type_struct = RegisterProgram { nonce, program_hash: program_hash.into() };
+        self.sign_message(type_struct).await
+    }
+
+    /// Signs a message to cancel a proof that was requested by the signer.
+    pub async fn sign_cancel_proof_message(&self, nonce: u64, proof_id: &str) -> Result<Vec<u8>> {
+        let type_struct = CancelProof { nonce, proof_id: proof_id.to_string() };
+        self.sign_message(type_struct).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::core::rand::thread_rng;
+
+    use super::*;
+
+    #[test]
+    fn test_from_keystore() {
+        let dir = tempfile::tempdir().unwrap();
+        let (wallet, name) =
+            LocalWallet::new_keystore(dir.path(), &mut thread_rng(), "password", None).unwrap();
+        let auth = NetworkAuth::from_keystore(dir.path().join(name), "password").unwrap();
+        assert_eq!(auth.get_address(), Signer::address(&wallet));
+        assert!(NetworkAuth::from_keystore(dir.path().join("missing"), "password").is_err());
+    }
 }
@@ -1,7 +1,16 @@
-use std::{env, time::Duration};
+use std::{
+    env,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
 
 use crate::{
-    network::auth::NetworkAuth,
+    install::RetryPolicy,
+    network::{
+        auth::NetworkAuth,
+        limit::{RateLimit, RateLimiter},
+        ProgramId, ProofRequestOpts,
+    },
     proto::network::{UnclaimProofRequest, UnclaimReason},
 };
 use anyhow::{Context, Ok, Result};
@@ -17,10 +26,10 @@ use std::{
 use twirp::{Client as TwirpClient, ClientError};
 
 use crate::proto::network::{
-    ClaimProofRequest, ClaimProofResponse, CreateProofRequest, FulfillProofRequest,
-    FulfillProofResponse, GetNonceRequest, GetProofRequestsRequest, GetProofRequestsResponse,
-    GetProofStatusRequest, GetProofStatusResponse, NetworkServiceClient, ProofMode, ProofStatus,
-    SubmitProofRequest,
+    CancelProofRequest, ClaimProofRequest, ClaimProofResponse, CreateProofRequest,
+    FulfillProofRequest, FulfillProofResponse, GetNonceRequest, GetProofRequestsRequest,
+    GetProofRequestsResponse, GetProofStatusRequest, GetProofStatusResponse, NetworkServiceClient,
+    ProofMode, ProofStatus, RegisterProgramRequest, SubmitProofRequest,
 };
 
 /// The default RPC endpoint for the Succinct prover network.
@@ -30,54 +39,196 @@ pub const DEFAULT_PROVER_NETWORK_RPC: &str = "https://rpc.succinct.xyz/";
 const TIMEOUT: Duration = Duration::from_secs(60 * 60);
 
 pub struct NetworkClient {
-    pub rpc: TwirpClient,
+    /// The primary RPC endpoint, which requests are sent to first.
+    pub rpc_url: String,
     pub http: HttpClientWithMiddleware,
     pub auth: NetworkAuth,
+    /// How the idempotent requests, such as polling the status of a proof, are retried.
+    pub retry: RetryPolicy,
+    /// The RPC endpoints of the prover network, the primary first.
+    endpoints: Vec<Endpoint>,
+    /// The index of the endpoint requests are currently sent to.
+    active: AtomicUsize,
+    /// Queues the requests over the rate limit.
+    limiter: RateLimiter,
+}
+
+/// An RPC endpoint of the prover network.
+struct Endpoint {
+    url: String,
+    rpc: TwirpClient,
 }
 
 impl NetworkClient {
-    /// Returns the currently configured RPC endpoint for the Succinct prover network.
+    /// Returns the currently configured RPC endpoint for the Succinct prover network, the first
+    /// of [Self::rpc_urls].
     pub fn rpc_url() -> String {
-        env::var("PROVER_NETWORK_RPC").unwrap_or_else(|_| DEFAULT_PROVER_NETWORK_RPC.to_string())
+        Self::rpc_urls().swap_remove(0)
+    }
+
+    /// Returns the currently configured RPC endpoints for the Succinct prover network, read from
+    /// the comma-separated `PROVER_NETWORK_RPC` environment variable, or
+    /// [DEFAULT_PROVER_NETWORK_RPC] if it is unset.
+    pub fn rpc_urls() -> Vec<String> {
+        let urls: Vec<String> = env::var("PROVER_NETWORK_RPC")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(String::from)
+            .collect();
+        if urls.is_empty() {
+            vec![DEFAULT_PROVER_NETWORK_RPC.to_string()]
+        } else {
+            urls
+        }
     }
 
     /// Create a new NetworkClient with the given private key for authentication.
     pub fn new(private_key: &str) -> Self {
-        let auth = NetworkAuth::new(private_key);
+        Self::new_with_opts(private_key, &Self::rpc_urls(), None, RetryPolicy::default())
+    }
 
-        let twirp_http_client = HttpClient::builder()
-            .pool_max_idle_per_host(0)
-            .pool_idle_timeout(Duration::from_secs(240))
-            .build()
-            .unwrap();
+    /// Create a new NetworkClient with the given private key for authentication, talking to the
+    /// prover network at `rpc_url`.
+    pub fn new_with_rpc_url(private_key: &str, rpc_url: &str) -> Self {
+        Self::new_with_opts(private_key, &[rpc_url], None, RetryPolicy::default())
+    }
 
-        let rpc_url = Self::rpc_url();
-        let rpc =
-            TwirpClient::new(Url::parse(&rpc_url).unwrap(), twirp_http_client, vec![]).unwrap();
+    /// Create a new NetworkClient talking to the prover network at `rpc_urls`, failing every
+    /// request not answered within `request_timeout` and retrying the idempotent ones with
+    /// `retry`.
+    ///
+    /// Requests are sent to the first endpoint, and fail over to the next ones while it can't be
+    /// reached. Panics if `rpc_urls` is empty or one of them isn't a valid URL.
+    pub fn new_with_opts(
+        private_key: &str,
+        rpc_urls: &[impl AsRef<str>],
+        request_timeout: Option<Duration>,
+        retry: RetryPolicy,
+    ) -> Self {
+        Self::new_with_auth(NetworkAuth::new(private_key), rpc_urls, request_timeout, retry)
+    }
 
-        let http_client = HttpClient::builder()
-            .pool_max_idle_per_host(0)
-            .pool_idle_timeout(Duration::from_secs(240))
-            .build()
-            .unwrap();
+    /// Like [Self::new_with_opts], authenticating with `auth` rather than a private key.
+    pub fn new_with_auth(
+        auth: NetworkAuth,
+        rpc_urls: &[impl AsRef<str>],
+        request_timeout: Option<Duration>,
+        retry: RetryPolicy,
+    ) -> Self {
+        assert!(!rpc_urls.is_empty(), "at least one prover network RPC endpoint must be set");
+
+        let http_client = || {
+            let mut builder = HttpClient::builder()
+                .pool_max_idle_per_host(0)
+                .pool_idle_timeout(Duration::from_secs(240));
+            if let Some(timeout) = request_timeout {
+                builder = builder.timeout(timeout);
+            }
+            builder.build().unwrap()
+        };
 
-        Self { auth, rpc, http: http_client.into() }
+        let endpoints: Vec<Endpoint> = rpc_urls
+            .iter()
+            .map(|url| Endpoint {
+                url: url.as_ref().to_string(),
+                rpc: TwirpClient::new(Url::parse(url.as_ref()).unwrap(), http_client(), vec![])
+                    .unwrap(),
+            })
+            .collect();
+
+        Self {
+            rpc_url: endpoints[0].url.clone(),
+            auth,
+            http: http_client().into(),
+            retry,
+            endpoints,
+            active: AtomicUsize::new(0),
+            limiter: RateLimiter::new(RateLimit::default()),
+        }
+    }
+
+    /// Limits the requests sent to the RPC endpoints to `limit`, queueing the others.
+    pub fn with_rate_limit(mut self, limit: RateLimit) -> Self {
+        self.limiter = RateLimiter::new(limit);
+        self
+    }
+
+    /// The RPC endpoint requests are currently sent to.
+    pub fn active_rpc_url(&self) -> &str {
+        &self.endpoints[self.active.load(Ordering::Relaxed)].url
+    }
+
+    /// Checks which RPC endpoints can be reached, returning whether each of them was, in the
+    /// order they were configured.
+    ///
+    /// Requests are then sent to the first reachable endpoint, so that they go back to the
+    /// primary endpoint once it recovers.
+    pub async fn health_check(&self) -> Vec<(String, bool)> {
+        let request = GetNonceRequest { address: self.auth.get_address().to_vec() };
+        let checks = self.endpoints.iter().map(|endpoint| async {
+            let reachable = match endpoint.rpc.get_nonce(request.clone()).await {
+                Err(err) => !is_unreachable(&err, true),
+                StdOk(_) => true,
+            };
+            (endpoint.url.clone(), reachable)
+        });
+        let health = join_all(checks).await;
+        if let Some(index) = health.iter().position(|(_, reachable)| *reachable) {
+            self.active.store(index, Ordering::Relaxed);
+        }
+        health
+    }
+
+    /// Runs the idempotent request `request` until it succeeds or [Self::retry] runs out of
+    /// attempts, returning the last error.
+    pub(crate) async fn with_retries<T, F, Fut>(&self, what: &str, mut request: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let max_attempts = self.retry.max_attempts.max(1);
+        let mut attempts = 0;
+        loop {
+            attempts += 1;
+            match request().await {
+                StdOk(value) => return Ok(value),
+                Err(err) if attempts >= max_attempts => return Err(err),
+                Err(err) => {
+                    let delay = self.retry.backoff(attempts);
+                    log::warn!(
+                        "{} failed (attempt {}/{}), retrying in {:?}: {:#}",
+                        what,
+                        attempts,
+                        max_attempts,
+                        delay,
+                        err
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
     }
 
     /// Gets the latest nonce for this auth's account.
     pub async fn get_nonce(&self) -> Result<u64> {
         let res = self
-            .with_error_handling(
-                self.rpc.get_nonce(GetNonceRequest { address: self.auth.get_address().to_vec() }),
-            )
+            .with_retries("getting the nonce", || {
+                let request = GetNonceRequest { address: self.auth.get_address().to_vec() };
+                self.call(true, move |rpc| rpc.get_nonce(request.clone()))
+            })
             .await?;
         Ok(res.nonce)
     }
 
     /// Upload a file to the specified url.
     async fn upload_file(&self, url: &str, data: Vec<u8>) -> Result<()> {
-        self.http.put(url).body(data).send().await?;
-        Ok(())
+        self.with_retries("uploading a file", || async {
+            self.http.put(url).body(data.clone()).send().await?.error_for_status()?;
+            Ok(())
+        })
+        .await
     }
 
     /// Get the status of a given proof. If the status is ProofFulfilled, the proof is also
@@ -86,32 +237,50 @@ impl NetworkClient {
         &self,
         proof_id: &str,
     ) -> Result<(GetProofStatusResponse, Option<P>)> {
-        let res = self
-            .with_error_handling(
-                self.rpc.get_proof_status(GetProofStatusRequest { proof_id: proof_id.to_string() }),
-            )
-            .await
-            .context("Failed to get proof status")?;
-
+        let res = self.get_proof_status_response(proof_id).await?;
         let proof = match res.status() {
             ProofStatus::ProofFulfilled => {
                 log::info!("Proof request fulfilled");
-                let proof_bytes = self
-                    .http
-                    .get(res.proof_url.as_ref().expect("no proof url"))
+                Some(self.download_proof(&res).await?)
+            }
+            _ => None,
+        };
+        Ok((res, proof))
+    }
+
+    /// Get the status of a given proof, without downloading the proof if it is fulfilled.
+    pub async fn get_proof_status_response(
+        &self,
+        proof_id: &str,
+    ) -> Result<GetProofStatusResponse> {
+        self.with_retries("getting the proof status", || async {
+            let request = GetProofStatusRequest { proof_id: proof_id.to_string() };
+            self.call(true, move |rpc| rpc.get_proof_status(request.clone()))
+                .await
+                .context("Failed to get proof status")
+        })
+        .await
+    }
+
+    /// Downloads the proof of a fulfilled proof request, given the response to its status.
+    pub async fn download_proof<P: DeserializeOwned>(
+        &self,
+        res: &GetProofStatusResponse,
+    ) -> Result<P> {
+        let url = res.proof_url.as_ref().context("Fulfilled proof has no proof url")?;
+        let proof_bytes = self
+            .with_retries("downloading the proof", || async {
+                self.http
+                    .get(url)
                     .send()
                     .await
                     .context("Failed to send HTTP request for proof")?
                     .bytes()
                     .await
-                    .context("Failed to load proof bytes")?;
-
-                Some(bincode::deserialize(&proof_bytes).context("Failed to deserialize proof")?)
-            }
-            _ => None,
-        };
-
-        Ok((res, proof))
+                    .context("Failed to load proof bytes")
+            })
+            .await?;
+        bincode::deserialize(&proof_bytes).context("Failed to deserialize proof")
     }
 
     /// Get all the proof requests for a given status.
@@ -119,10 +288,8 @@ impl NetworkClient {
         &self,
         status: ProofStatus,
     ) -> Result<GetProofRequestsResponse> {
-        self.with_error_handling(
-            self.rpc.get_proof_requests(GetProofRequestsRequest { status: status.into() }),
-        )
-        .await
+        let request = GetProofRequestsRequest { status: status.into() };
+        self.call(true, move |rpc| rpc.get_proof_requests(request.clone())).await
     }
 
     /// Creates a proof request for the given ELF and stdin.
@@ -133,43 +300,104 @@ impl NetworkClient {
         mode: ProofMode,
         version: &str,
     ) -> Result<String> {
-        let start = SystemTime::now();
-        let since_the_epoch = start.duration_since(UNIX_EPOCH).expect("Invalid start time");
-        let deadline = since_the_epoch.as_secs() + TIMEOUT.as_secs();
+        self.create_proof_with_opts(elf, stdin, mode, version, ProofRequestOpts::default()).await
+    }
+
+    /// Creates a proof request for the given ELF and stdin, with the fee and priority hints of
+    /// `opts`.
+    pub async fn create_proof_with_opts(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+        version: &str,
+        opts: ProofRequestOpts,
+    ) -> Result<String> {
+        self.create_proof_of(Program::Elf(elf), stdin, mode, version, opts).await
+    }
+
+    /// Creates a proof request for the program registered as `program` and the given stdin,
+    /// uploading only the stdin.
+    pub async fn create_proof_for_program(
+        &self,
+        program: ProgramId,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+        version: &str,
+        opts: ProofRequestOpts,
+    ) -> Result<String> {
+        self.create_proof_of(Program::Registered(program), stdin, mode, version, opts).await
+    }
+
+    /// Registers the program `elf` with the network, uploading it unless it is already
+    /// registered, so that proofs of it can be requested with [Self::create_proof_for_program].
+    pub async fn register_program(&self, elf: &[u8]) -> Result<ProgramId> {
+        let program = ProgramId::of(elf);
+        let nonce = self.get_nonce().await?;
+        let signature = self.auth.sign_register_program_message(nonce, program.0).await?;
+        let request = RegisterProgramRequest { signature, nonce, program_hash: program.0.to_vec() };
+        let res = self.call(false, move |rpc| rpc.register_program(request.clone())).await?;
+        if !res.program_url.is_empty() {
+            self.upload_file(&res.program_url, bincode::serialize(elf)?).await?;
+        }
+        Ok(program)
+    }
+
+    /// Creates a proof request for `program` and the given stdin.
+    async fn create_proof_of(
+        &self,
+        program: Program<'_>,
+        stdin: &SP1Stdin,
+        mode: ProofMode,
+        version: &str,
+        opts: ProofRequestOpts,
+    ) -> Result<String> {
+        let deadline = Self::request_deadline();
 
         let nonce = self.get_nonce().await?;
         let create_proof_signature =
             self.auth.sign_create_proof_message(nonce, deadline, mode.into(), version).await?;
 
-        let res = self
-            .with_error_handling(self.rpc.create_proof(CreateProofRequest {
-                signature: create_proof_signature.to_vec(),
-                nonce,
-                deadline,
-                mode: mode.into(),
-                version: version.to_string(),
-            }))
-            .await?;
+        let request = CreateProofRequest {
+            signature: create_proof_signature.to_vec(),
+            nonce,
+            deadline,
+            mode: mode.into(),
+            version: version.to_string(),
+            max_fee: opts.max_fee,
+            priority: opts.priority.into(),
+            program_hash: match program {
+                Program::Elf(_) => None,
+                Program::Registered(program) => Some(program.0.to_vec()),
+            },
+            require_tee: opts.require_tee,
+        };
+        let res = self.call(false, move |rpc| rpc.create_proof(request.clone())).await?;
 
-        let program_bytes = bincode::serialize(elf)?;
         let stdin_bytes = bincode::serialize(&stdin)?;
-        let program_promise = self.upload_file(&res.program_url, program_bytes);
-        let stdin_promise = self.upload_file(&res.stdin_url, stdin_bytes);
-        let v = vec![program_promise, stdin_promise];
-        let mut results = join_all(v).await;
-        results.pop().expect("Failed to upload stdin")?;
-        results.pop().expect("Failed to upload program")?;
+        match program {
+            Program::Elf(elf) => {
+                let program_bytes = bincode::serialize(elf)?;
+                let program_promise = self.upload_file(&res.program_url, program_bytes);
+                let stdin_promise = self.upload_file(&res.stdin_url, stdin_bytes);
+                let v = vec![program_promise, stdin_promise];
+                let mut results = join_all(v).await;
+                results.pop().expect("Failed to upload stdin")?;
+                results.pop().expect("Failed to upload program")?;
+            }
+            Program::Registered(_) => self.upload_file(&res.stdin_url, stdin_bytes).await?,
+        }
 
         let nonce = self.get_nonce().await?;
         let submit_proof_signature =
             self.auth.sign_submit_proof_message(nonce, &res.proof_id).await?;
 
-        self.with_error_handling(self.rpc.submit_proof(SubmitProofRequest {
+        let request = SubmitProofRequest {
             signature: submit_proof_signature.to_vec(),
             nonce,
             proof_id: res.proof_id.clone(),
-        }))
-        .await?;
+        };
+        self.call(false, move |rpc| rpc.submit_proof(request.clone())).await?;
 
         Ok(res.proof_id)
     }
@@ -180,12 +408,8 @@ impl NetworkClient {
         let nonce = self.get_nonce().await?;
         let signature = self.auth.sign_claim_proof_message(nonce, proof_id).await?;
 
-        self.with_error_handling(self.rpc.claim_proof(ClaimProofRequest {
-            signature,
-            nonce,
-            proof_id: proof_id.to_string(),
-        }))
-        .await
+        let request = ClaimProofRequest { signature, nonce, proof_id: proof_id.to_string() };
+        self.call(false, move |rpc| rpc.claim_proof(request.clone())).await
     }
 
     /// Unclaim a proof that was claimed. This should only be called if the proof has not been
@@ -203,15 +427,28 @@ impl NetworkClient {
             .sign_unclaim_proof_message(nonce, proof_id.clone(), reason, description.clone())
             .await?;
 
-        self.with_error_handling(self.rpc.unclaim_proof(UnclaimProofRequest {
-            signature,
-            nonce,
-            proof_id,
-            reason: reason.into(),
-            description,
-        }))
-        .await?;
+        let request =
+            UnclaimProofRequest { signature, nonce, proof_id, reason: reason.into(), description };
+        self.call(false, move |rpc| rpc.unclaim_proof(request.clone())).await?;
+
+        Ok(())
+    }
+
+    /// The deadline of a proof request created now, in seconds since the Unix epoch.
+    pub(crate) fn request_deadline() -> u64 {
+        let start = SystemTime::now();
+        let since_the_epoch = start.duration_since(UNIX_EPOCH).expect("Invalid start time");
+        since_the_epoch.as_secs() + TIMEOUT.as_secs()
+    }
 
+    /// Cancel a proof that was requested by this auth's account and isn't fulfilled yet. Returns
+    /// an error if the proof is not in a PROOF_REQUESTED or PROOF_CLAIMED state or if the caller
+    /// is not the requester.
+    pub async fn cancel_proof(&self, proof_id: &str) -> Result<()> {
+        let nonce = self.get_nonce().await?;
+        let signature = self.auth.sign_cancel_proof_message(nonce, proof_id).await?;
+        let request = CancelProofRequest { signature, nonce, proof_id: proof_id.to_string() };
+        self.call(false, move |rpc| rpc.cancel_proof(request.clone())).await?;
         Ok(())
     }
 
@@ -220,24 +457,44 @@ impl NetworkClient {
     pub async fn fulfill_proof(&self, proof_id: &str) -> Result<FulfillProofResponse> {
         let nonce = self.get_nonce().await?;
         let signature = self.auth.sign_fulfill_proof_message(nonce, proof_id).await?;
-        let res = self
-            .with_error_handling(self.rpc.fulfill_proof(FulfillProofRequest {
-                signature,
-                nonce,
-                proof_id: proof_id.to_string(),
-            }))
-            .await?;
+        let request = FulfillProofRequest { signature, nonce, proof_id: proof_id.to_string() };
+        let res = self.call(false, move |rpc| rpc.fulfill_proof(request.clone())).await?;
 
         Ok(res)
     }
 
-    /// Awaits the future, then handles Succinct prover network errors.
-    async fn with_error_handling<T, F>(&self, future: F) -> Result<T>
+    /// Sends the request built by `request` to the active endpoint, failing over to the next
+    /// endpoints while it can't be reached, then handles Succinct prover network errors.
+    ///
+    /// Requests that aren't `idempotent` only fail over when the connection to the endpoint
+    /// failed, as a request that timed out may still have been processed.
+    async fn call<'a, T, F, Fut>(&'a self, idempotent: bool, mut request: F) -> Result<T>
     where
-        F: Future<Output = std::result::Result<T, ClientError>>,
+        F: FnMut(&'a TwirpClient) -> Fut,
+        Fut: Future<Output = std::result::Result<T, ClientError>>,
     {
-        let result = future.await;
-        self.handle_twirp_error(result)
+        let _permit = self.limiter.acquire().await;
+        let start = self.active.load(Ordering::Relaxed);
+        let mut result = None;
+        for offset in 0..self.endpoints.len() {
+            let index = (start + offset) % self.endpoints.len();
+            let endpoint = &self.endpoints[index];
+            match request(&endpoint.rpc).await {
+                Err(err) if is_unreachable(&err, idempotent) => {
+                    log::warn!("Prover network endpoint {} is unreachable: {}", endpoint.url, err);
+                    result = Some(Err(err));
+                }
+                reached => {
+                    if index != start {
+                        log::info!("Failed over to prover network endpoint {}", endpoint.url);
+                        self.active.store(index, Ordering::Relaxed);
+                    }
+                    result = Some(reached);
+                    break;
+                }
+            }
+        }
+        self.handle_twirp_error(result.expect("the client has at least one endpoint"))
     }
 
     /// Handles Twirp errors by formatting them into more readable error messages.
@@ -252,3 +509,21 @@ impl NetworkClient {
         }
     }
 }
+
+/// The program of a proof request.
+#[derive(Clone, Copy)]
+enum Program<'a> {
+    /// Uploaded along with the request.
+    Elf(&'a [u8]),
+    /// Registered with [NetworkClient::register_program] beforehand.
+    Registered(ProgramId),
+}
+
+/// Whether `err` means the endpoint couldn't be reached, rather than that it rejected the request.
+/// Timeouts only count for `idempotent` requests.
+fn is_unreachable(err: &ClientError, idempotent: bool) -> bool {
+    match err {
+        ClientError::ReqwestError(err) => err.is_connect() || (idempotent && err.is_timeout()),
+        _ => false,
+    }
+}
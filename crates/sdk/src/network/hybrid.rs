@@ -0,0 +1,202 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::Result;
+use sp1_core_executor::{ExecutionError, SP1Context};
+use sp1_core_machine::io::SP1Stdin;
+use sp1_prover::{components::DefaultProverComponents, SP1Prover};
+
+use crate::{
+    block_on,
+    install::InstallConfig,
+    network::{pricing::PriceEstimate, prover::NetworkProver},
+    proto::network::ProofMode,
+    provers::{ProofOpts, ProverType},
+    Prover, SP1ProofKind, SP1ProofMode, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
+};
+
+/// An implementation of [crate::ProverClient] that requests proofs from the prover network, and
+/// falls back to proving locally when the network can't deliver them.
+///
+/// A proof is proven locally, with the prover passed to [HybridProver::new], if:
+/// - requesting it from the network would cost more than [HybridProver::with_budget],
+/// - the network can't be reached or doesn't fulfill the request,
+/// - the network doesn't deliver it within [HybridProver::with_network_timeout], in which case
+///   the network request is cancelled so it isn't paid for twice.
+///
+/// Programs that fail to execute fail right away, as they would fail locally too.
+///
+/// ### Examples
+/// ```no_run
+/// use std::{sync::Arc, time::Duration};
+///
+/// use sp1_sdk::{CpuProver, HybridProver, NetworkProver, ProverClient};
+///
+/// let prover = HybridProver::new(NetworkProver::new(), Arc::new(CpuProver::new()))
+///     .with_network_timeout(Duration::from_secs(10 * 60))
+///     .with_budget(5.0);
+/// let client = ProverClient::hybrid(prover);
+/// ```
+pub struct HybridProver {
+    network: NetworkProver,
+    local: Arc<dyn Prover<DefaultProverComponents>>,
+    network_timeout: Option<Duration>,
+    budget: Option<f64>,
+}
+
+/// A proof generated by a [HybridProver], with the path that produced it.
+#[derive(Debug)]
+pub struct HybridProof {
+    /// The proof.
+    pub proof: SP1ProofWithPublicValues,
+    /// Which prover generated the proof.
+    pub path: ProofPath,
+}
+
+/// Which prover generated the proof of a [HybridProver].
+#[derive(Debug)]
+pub enum ProofPath {
+    /// The prover network generated the proof.
+    Network,
+    /// The local prover generated the proof, because the network couldn't.
+    Local(FallbackReason),
+}
+
+/// Why a [HybridProver] proved locally.
+#[derive(Debug)]
+pub enum FallbackReason {
+    /// The estimated fee of the network request exceeded the budget.
+    OverBudget(PriceEstimate),
+    /// The network request failed, e.g. because the network couldn't be reached or the proof
+    /// wasn't delivered in time.
+    Network(anyhow::Error),
+}
+
+impl HybridProver {
+    /// Creates a new [HybridProver] requesting proofs with `network` and falling back to
+    /// `local`, e.g. a [crate::CpuProver] or a `CudaProver`.
+    pub fn new(network: NetworkProver, local: Arc<dyn Prover<DefaultProverComponents>>) -> Self {
+        Self { network, local, network_timeout: None, budget: None }
+    }
+
+    /// Sets the time the network has to deliver a proof before falling back to the local prover.
+    /// The network gets as long as it takes by default.
+    pub fn with_network_timeout(mut self, timeout: Duration) -> Self {
+        self.network_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum fee of a network request, in the fee currency of
+    /// [NetworkProverBuilder::pricing](super::prover::NetworkProverBuilder::pricing). Proofs
+    /// estimated to cost more are proven locally. Unlimited by default.
+    pub fn with_budget(mut self, budget: f64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// The network prover proofs are requested from first.
+    pub fn network(&self) -> &NetworkProver {
+        &self.network
+    }
+
+    /// Proves the execution of `pk` on `stdin` like [Prover::prove], returning which path
+    /// produced the proof along with it.
+    ///
+    /// `opts` and `context` only apply to the local prover.
+    pub fn prove_hybrid<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<HybridProof> {
+        let reason = match self.try_network(&pk.elf, &stdin, kind)? {
+            Ok(proof) => {
+                tracing::info!("proof generated by the prover network");
+                return Ok(HybridProof { proof, path: ProofPath::Network });
+            }
+            Err(reason) => reason,
+        };
+        tracing::warn!("falling back to the local prover: {:?}", reason);
+        let proof = self.local.prove(pk, stdin, opts, context, kind)?;
+        Ok(HybridProof { proof, path: ProofPath::Local(reason) })
+    }
+
+    /// Requests the proof from the network, returning why it must be proven locally instead if
+    /// it can't be. Fails if the program fails to execute.
+    fn try_network(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        kind: SP1ProofKind,
+    ) -> Result<Result<SP1ProofWithPublicValues, FallbackReason>> {
+        if let Some(budget) = self.budget {
+            let estimate = self.network.estimate_price(elf, stdin, SP1ProofMode::from(kind))?;
+            if estimate.fee > budget {
+                return Ok(Err(FallbackReason::OverBudget(estimate)));
+            }
+        }
+        match block_on(self.prove_on_network(elf, stdin.clone(), kind.into())) {
+            Ok(proof) => Ok(Ok(proof)),
+            Err(err) if err.is::<ExecutionError>() => Err(err),
+            Err(err) => Ok(Err(FallbackReason::Network(err))),
+        }
+    }
+
+    /// Requests the proof from the network and waits for it, cancelling the request if it isn't
+    /// delivered.
+    async fn prove_on_network(
+        &self,
+        elf: &[u8],
+        stdin: SP1Stdin,
+        mode: ProofMode,
+    ) -> Result<SP1ProofWithPublicValues> {
+        let proof_id = self.network.request_proof(elf, stdin, mode).await?;
+        let proof = self.network.wait_network_proof(&proof_id, self.network_timeout).await;
+        if proof.is_err() {
+            if let Err(err) = self.network.cancel(&proof_id).await {
+                tracing::warn!("failed to cancel the network request {}: {:#}", proof_id, err);
+            }
+        }
+        proof
+    }
+}
+
+impl Prover<DefaultProverComponents> for HybridProver {
+    fn id(&self) -> ProverType {
+        ProverType::Hybrid
+    }
+
+    fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
+        self.local.setup(elf)
+    }
+
+    fn sp1_prover(&self) -> &SP1Prover {
+        self.local.sp1_prover()
+    }
+
+    fn install_config(&self) -> InstallConfig {
+        self.local.install_config()
+    }
+
+    fn prove<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        Ok(self.prove_hybrid(pk, stdin, opts, context, kind)?.proof)
+    }
+
+    fn wrap(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
+        kind: SP1ProofKind,
+        opts: ProofOpts,
+    ) -> Result<SP1ProofWithPublicValues> {
+        self.local.wrap(vk, proof, kind, opts)
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use tokio::{
+    sync::{Mutex, Semaphore, SemaphorePermit},
+    time::{sleep_until, Instant},
+};
+
+/// Limits the requests a [NetworkProver](super::prover::NetworkProver) sends to the RPC of the
+/// prover network, so that bulk submissions stay within the rate limits of the network rather
+/// than failing. See
+/// [NetworkProverBuilder::rate_limit](super::prover::NetworkProverBuilder::rate_limit).
+///
+/// Requests over the limits wait for their turn, in the order they were made, across all the
+/// callers sharing the prover. Uploads and downloads of programs, inputs and proofs don't go
+/// through the RPC and aren't limited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The maximum number of requests in flight at once. Unlimited if `None`.
+    pub max_concurrent: Option<usize>,
+    /// The maximum number of requests started per second. Unlimited if `None`.
+    pub max_per_second: Option<u32>,
+}
+
+/// Enforces a [RateLimit].
+pub(crate) struct RateLimiter {
+    concurrent: Option<Semaphore>,
+    interval: Option<Duration>,
+    /// The earliest time the next request may start.
+    next_start: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: RateLimit) -> Self {
+        Self {
+            concurrent: limit.max_concurrent.map(|max| Semaphore::new(max.max(1))),
+            interval: limit.max_per_second.map(|max| Duration::from_secs(1) / max.max(1)),
+            next_start: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Waits for the turn of a request, returning the permit to hold while it is in flight.
+    ///
+    /// Both the semaphore and the mutex queue their waiters in order, so requests start in the
+    /// order they were made.
+    pub(crate) async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.concurrent {
+            Some(semaphore) => {
+                Some(semaphore.acquire().await.expect("the semaphore is never closed"))
+            }
+            None => None,
+        };
+        if let Some(interval) = self.interval {
+            let start = {
+                let mut next_start = self.next_start.lock().await;
+                let start = (*next_start).max(Instant::now());
+                *next_start = start + interval;
+                start
+            };
+            sleep_until(start).await;
+        }
+        permit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rate_limiter() {
+        let limiter =
+            RateLimiter::new(RateLimit { max_concurrent: Some(1), max_per_second: Some(20) });
+        let start = Instant::now();
+        let permit = limiter.acquire().await;
+        assert!(tokio::time::timeout(Duration::from_millis(100), limiter.acquire()).await.is_err());
+        drop(permit);
+        drop(limiter.acquire().await);
+        drop(limiter.acquire().await);
+        // The first request starts right away, the ones after it are 50ms apart.
+        assert!(start.elapsed() >= Duration::from_millis(100));
+
+        let unlimited = RateLimiter::new(RateLimit::default());
+        let (first, second) = (unlimited.acquire().await, unlimited.acquire().await);
+        assert!(first.is_none() && second.is_none());
+    }
+}
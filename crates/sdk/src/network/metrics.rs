@@ -0,0 +1,131 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::{proto::network::GetProofStatusResponse, NetworkProofMetadata};
+
+/// The times a proof request was observed in while its status was polled, used when the network
+/// doesn't report when the request changed state.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RequestTimeline {
+    /// When the status of the request was first fetched, standing in for when it was created.
+    pub(crate) first_checked: Option<SystemTime>,
+    /// When the request was first seen claimed.
+    pub(crate) claimed: Option<SystemTime>,
+}
+
+impl RequestTimeline {
+    /// The metadata of the request `request_id`, fulfilled as described by `response`, fetched
+    /// at `fulfilled_checked`.
+    ///
+    /// The times reported by the network are preferred over the polled ones, which are only
+    /// accurate to the poll interval. A request that was never seen claimed is counted as queued
+    /// until it was seen fulfilled.
+    pub(crate) fn metadata(
+        &self,
+        request_id: &str,
+        response: &GetProofStatusResponse,
+        fulfilled_checked: SystemTime,
+    ) -> NetworkProofMetadata {
+        let reported = |secs: Option<u64>| secs.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        let fulfilled = reported(response.fulfilled_at).unwrap_or(fulfilled_checked);
+        let claimed = reported(response.claimed_at).or(self.claimed).unwrap_or(fulfilled);
+        let created = reported(response.created_at).or(self.first_checked).unwrap_or(claimed);
+        let elapsed =
+            |from: SystemTime, to: SystemTime| to.duration_since(from).unwrap_or_default();
+        NetworkProofMetadata {
+            request_id: request_id.to_string(),
+            queue_time: elapsed(created, claimed),
+            prove_time: elapsed(claimed, fulfilled),
+            prover: response.prover.as_ref().map(|prover| format!("0x{}", hex::encode(prover))),
+            attestation: response.attestation.clone(),
+        }
+    }
+}
+
+/// The metrics of the proof requests a [NetworkProver](super::prover::NetworkProver) waited
+/// for over its lifetime, see
+/// [NetworkProver::metrics](super::prover::NetworkProver::metrics).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NetworkMetrics {
+    /// The number of requests fulfilled.
+    pub fulfilled: u64,
+    /// The number of requests that won't be fulfilled.
+    pub unfulfilled: u64,
+    /// The total time the fulfilled requests waited for a prover to claim them.
+    pub total_queue_time: Duration,
+    /// The longest time a fulfilled request waited for a prover to claim it.
+    pub max_queue_time: Duration,
+    /// The total time the provers took to fulfill the requests they claimed.
+    pub total_prove_time: Duration,
+    /// The longest time a prover took to fulfill a request it claimed.
+    pub max_prove_time: Duration,
+    /// The number of requests fulfilled by every prover, by hex-encoded address, for the
+    /// requests the network reported the prover of.
+    pub fulfilled_by: HashMap<String, u64>,
+}
+
+impl NetworkMetrics {
+    /// The mean time the fulfilled requests waited for a prover to claim them.
+    pub fn mean_queue_time(&self) -> Option<Duration> {
+        self.mean(self.total_queue_time)
+    }
+
+    /// The mean time the provers took to fulfill the requests they claimed.
+    pub fn mean_prove_time(&self) -> Option<Duration> {
+        self.mean(self.total_prove_time)
+    }
+
+    fn mean(&self, total: Duration) -> Option<Duration> {
+        (self.fulfilled > 0).then(|| total / self.fulfilled as u32)
+    }
+
+    /// Records a fulfilled request.
+    pub(crate) fn record_fulfilled(&mut self, metadata: &NetworkProofMetadata) {
+        self.fulfilled += 1;
+        self.total_queue_time += metadata.queue_time;
+        self.max_queue_time = self.max_queue_time.max(metadata.queue_time);
+        self.total_prove_time += metadata.prove_time;
+        self.max_prove_time = self.max_prove_time.max(metadata.prove_time);
+        if let Some(prover) = &metadata.prover {
+            *self.fulfilled_by.entry(prover.clone()).or_default() += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        let timeline = RequestTimeline { first_checked: Some(at(100)), claimed: Some(at(130)) };
+        let polled = timeline.metadata("id", &GetProofStatusResponse::default(), at(200));
+        assert_eq!(polled.queue_time, Duration::from_secs(30));
+        assert_eq!(polled.prove_time, Duration::from_secs(70));
+        assert_eq!(polled.prover, None);
+        assert_eq!(polled.attestation, None);
+
+        let response = GetProofStatusResponse {
+            prover: Some(vec![0xab; 2]),
+            created_at: Some(90),
+            claimed_at: Some(120),
+            fulfilled_at: Some(190),
+            attestation: Some(vec![1, 2, 3]),
+            ..Default::default()
+        };
+        let reported = timeline.metadata("id", &response, at(200));
+        assert_eq!(reported.queue_time, Duration::from_secs(30));
+        assert_eq!(reported.prove_time, Duration::from_secs(70));
+        assert_eq!(reported.prover.as_deref(), Some("0xabab"));
+        assert_eq!(reported.attestation, Some(vec![1, 2, 3]));
+
+        let mut metrics = NetworkMetrics::default();
+        metrics.record_fulfilled(&polled);
+        metrics.record_fulfilled(&reported);
+        assert_eq!(metrics.mean_prove_time(), Some(Duration::from_secs(70)));
+        assert_eq!(metrics.fulfilled_by["0xabab"], 1);
+    }
+}
@@ -1,3 +1,73 @@
 pub mod auth;
 pub mod client;
+pub mod hybrid;
+pub mod limit;
+pub mod metrics;
+pub mod pricing;
 pub mod prover;
+pub mod status;
+
+use std::{fmt, time::Duration};
+
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::proto::network::ProofPriority;
+
+/// An error requesting a proof from the prover network.
+#[derive(Error, Debug)]
+pub enum NetworkError {
+    #[error("proof generation timed out after {0:?}")]
+    Timeout(Duration),
+    #[error("proof generation failed: {0}")]
+    Unfulfilled(String),
+    #[error("request to the prover network failed: {0:#}")]
+    Request(anyhow::Error),
+}
+
+/// The options attached to a proof request: fee and priority hints, so that urgent proofs can
+/// outbid batch workloads when the network is busy, and the environment the proof must be
+/// generated in.
+///
+/// The options aren't part of the signed request message yet, so they are only honoured by
+/// network deployments that support them, and ignored by the others.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProofRequestOpts {
+    /// The maximum fee to pay for the proof, in the smallest unit of the fee currency. The
+    /// request isn't fulfilled if it can't be for that fee. `None` for no limit.
+    pub max_fee: Option<u64>,
+    /// The priority of the request.
+    pub priority: ProofPriority,
+    /// Only let provers generate the proof inside an attested trusted execution environment
+    /// (TEE), so that the program and its input are only processed inside the enclave.
+    ///
+    /// The attestation report of the enclave is returned in
+    /// [NetworkProofMetadata::attestation](crate::NetworkProofMetadata::attestation), and
+    /// waiting for the proof fails if the network doesn't return one, e.g. because the
+    /// deployment ignored the option.
+    pub require_tee: bool,
+}
+
+impl Default for ProofRequestOpts {
+    fn default() -> Self {
+        Self { max_fee: None, priority: ProofPriority::Normal, require_tee: false }
+    }
+}
+
+/// The ID of a program registered with the prover network, the sha256 hash of its ELF. See
+/// [NetworkProver::register_program](prover::NetworkProver::register_program).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ProgramId(pub [u8; 32]);
+
+impl ProgramId {
+    /// The ID of the program `elf`.
+    pub fn of(elf: &[u8]) -> Self {
+        Self(Sha256::digest(elf).into())
+    }
+}
+
+impl fmt::Display for ProgramId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
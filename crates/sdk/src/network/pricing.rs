@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::{CostEstimate, GasModel, SP1ProofMode};
+
+/// The fees of the prover network, used by
+/// [NetworkProver::estimate_price](super::prover::NetworkProver::estimate_price) to price a
+/// request before it is submitted.
+///
+/// The prover network doesn't publish its prices over its RPC, so they are configured with
+/// [NetworkProverBuilder::pricing](super::prover::NetworkProverBuilder::pricing) from the
+/// published price list. The default prices one cycle at one unit of the fee currency with no
+/// flat fees, which only gives relative prices.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NetworkPricing {
+    /// Prices the execution of the program, in the fee currency.
+    pub gas_model: GasModel,
+    /// The flat fee charged on top of the gas for the proofs of each mode, covering the constant
+    /// cost of compressing and wrapping a proof. Modes missing from the map aren't charged one.
+    pub mode_fees: HashMap<SP1ProofMode, f64>,
+}
+
+impl NetworkPricing {
+    /// Sets the flat fee charged for the proofs of `mode`.
+    pub fn with_mode_fee(mut self, mode: SP1ProofMode, fee: f64) -> Self {
+        self.mode_fees.insert(mode, fee);
+        self
+    }
+
+    /// The price of a proof in `mode` of the execution priced as `cost`.
+    pub fn price(&self, cost: CostEstimate, mode: SP1ProofMode) -> PriceEstimate {
+        let mode_fee = self.mode_fees.get(&mode).copied().unwrap_or(0.0);
+        PriceEstimate { mode, cost, mode_fee, fee: cost.cost + mode_fee }
+    }
+}
+
+/// The estimated fee of a proof request, see
+/// [NetworkProver::estimate_price](super::prover::NetworkProver::estimate_price).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceEstimate {
+    /// The mode of the requested proof.
+    pub mode: SP1ProofMode,
+    /// The cost of the execution, priced with [NetworkPricing::gas_model].
+    pub cost: CostEstimate,
+    /// The flat fee of [Self::mode].
+    pub mode_fee: f64,
+    /// The total estimated fee, in the fee currency.
+    pub fee: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use sp1_core_executor::{ExecutionReport, Opcode};
+
+    use super::*;
+
+    #[test]
+    fn test_price() {
+        let mut report = ExecutionReport::default();
+        report.opcode_counts.insert(Opcode::ADD, 1000);
+        let pricing = NetworkPricing {
+            gas_model: GasModel { price_per_gas: 0.01, ..Default::default() },
+            ..Default::default()
+        }
+        .with_mode_fee(SP1ProofMode::Groth16, 5.0);
+        let cost = pricing.gas_model.estimate(&report);
+
+        assert_eq!(pricing.price(cost, SP1ProofMode::Groth16).fee, 15.0);
+        assert_eq!(pricing.price(cost, SP1ProofMode::Core).fee, 10.0);
+    }
+}
@@ -1,12 +1,31 @@
 use std::{
+    collections::HashSet,
     env,
+    future::Future,
+    ops::ControlFlow,
+    path::PathBuf,
+    sync::{mpsc, Arc, Mutex},
     time::{Duration, Instant},
 };
 
 use crate::{
-    network::client::{NetworkClient, DEFAULT_PROVER_NETWORK_RPC},
-    proto::network::{ProofMode, ProofStatus},
-    Prover, SP1Context, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
+    install::{InstallConfig, RetryPolicy},
+    network::{
+        auth::{NetworkAuth, Signer},
+        client::{NetworkClient, DEFAULT_PROVER_NETWORK_RPC},
+        limit::RateLimit,
+        metrics::{NetworkMetrics, RequestTimeline},
+        pricing::{NetworkPricing, PriceEstimate},
+        status::{
+            ProofNotification, ProofRequestFilter, ProofRequestState, ProofRequestStatus,
+            ProofRequestSummary,
+        },
+        NetworkError, ProgramId, ProofRequestOpts,
+    },
+    proof::LegacySP1ProofWithPublicValues,
+    proto::network::{GetProofStatusResponse, ProofMode},
+    NetworkProofMetadata, Prover, SP1Context, SP1ProofKind, SP1ProofMode, SP1ProofWithPublicValues,
+    SP1ProvingKey, SP1VerifyingKey,
 };
 use anyhow::Result;
 use serde::de::DeserializeOwned;
@@ -17,37 +36,385 @@ use sp1_stark::SP1ProverOpts;
 #[cfg(feature = "network")]
 use {crate::block_on, tokio::time::sleep};
 
-use crate::provers::{CpuProver, ProofOpts, ProverType};
+use crate::provers::{proof_metadata, CpuProver, ProofOpts, ProverType};
+
+/// The default interval between two polls of the status of a proof request.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(2);
 
 /// An implementation of [crate::ProverClient] that can generate proofs on a remote RPC server.
 pub struct NetworkProver {
-    client: NetworkClient,
+    client: Arc<NetworkClient>,
     local_prover: CpuProver,
+    poll_interval: Duration,
+    pricing: NetworkPricing,
+    request_opts: ProofRequestOpts,
+    cycle_limit: Option<u64>,
+    /// The programs registered with [Self::register_program].
+    registered: Mutex<HashSet<ProgramId>>,
+    /// The requests made with [ProofRequestOpts::require_tee], whose proofs must come with an
+    /// attestation report.
+    tee_requests: Mutex<HashSet<String>>,
+    metrics: Mutex<NetworkMetrics>,
+}
+
+/// The parameters of a proof request that passed [NetworkProver::simulate], as it would be
+/// submitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatedRequest {
+    /// The mode of the requested proof.
+    pub mode: ProofMode,
+    /// The circuit version the proof would be requested for.
+    pub version: String,
+    /// The nonce the request would be signed with.
+    pub nonce: u64,
+    /// The deadline of the request, in seconds since the Unix epoch.
+    pub deadline: u64,
+    /// The address of the account the request would be made by.
+    pub requester: [u8; 20],
+    /// The fee and priority hints the request would carry.
+    pub opts: ProofRequestOpts,
+    /// The number of cycles the program executed in.
+    pub cycles: u64,
+    /// The size of the program that would be uploaded, in bytes.
+    pub program_size: usize,
+    /// The size of the input that would be uploaded, in bytes.
+    pub stdin_size: usize,
+    /// The estimated fee of the request, see [NetworkProver::estimate_price].
+    pub estimate: PriceEstimate,
+}
+
+/// A builder for [NetworkProver], to configure how it talks to the prover network.
+///
+/// ### Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use sp1_sdk::{install::RetryPolicy, NetworkProver};
+///
+/// let prover = NetworkProver::builder()
+///     .private_key("0x...")
+///     .request_timeout(Duration::from_secs(30))
+///     .poll_interval(Duration::from_secs(5))
+///     .retry(RetryPolicy { max_attempts: 10, ..RetryPolicy::default() })
+///     .build();
+/// ```
+#[derive(Default)]
+pub struct NetworkProverBuilder {
+    auth: Option<NetworkAuth>,
+    private_key: Option<String>,
+    rpc_urls: Vec<String>,
+    request_timeout: Option<Duration>,
+    poll_interval: Option<Duration>,
+    retry: Option<RetryPolicy>,
+    install: Option<InstallConfig>,
+    pricing: NetworkPricing,
+    request_opts: ProofRequestOpts,
+    cycle_limit: Option<u64>,
+    rate_limit: RateLimit,
+}
+
+impl NetworkProverBuilder {
+    /// Sets the private key used to authenticate with the prover network.
+    ///
+    /// Defaults to the `SP1_PRIVATE_KEY` environment variable. Otherwise, the key is decrypted
+    /// from the keystore at `SP1_KEYSTORE_PATH` with the password `SP1_KEYSTORE_PASSWORD`, see
+    /// [NetworkAuth::from_keystore].
+    pub fn private_key(mut self, private_key: impl Into<String>) -> Self {
+        self.private_key = Some(private_key.into());
+        self
+    }
+
+    /// Sets how requests to the prover network are authenticated, e.g. with a key decrypted
+    /// from a keystore with [NetworkAuth::from_keystore]. Takes precedence over
+    /// [Self::private_key].
+    pub fn auth(mut self, auth: NetworkAuth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Authenticates requests to the prover network with `signer`, e.g. an HSM or a remote
+    /// signing service holding the key. Takes precedence over [Self::private_key].
+    pub fn signer(self, signer: impl Signer + 'static) -> Self {
+        self.auth(NetworkAuth::from_signer(signer))
+    }
+
+    /// Sets the RPC endpoint of the prover network.
+    ///
+    /// Defaults to the comma-separated endpoints of the `PROVER_NETWORK_RPC` environment
+    /// variable, or [DEFAULT_PROVER_NETWORK_RPC] if it is unset.
+    pub fn rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_urls = vec![rpc_url.into()];
+        self
+    }
+
+    /// Sets the RPC endpoints of the prover network, the primary first.
+    ///
+    /// Requests are sent to the primary endpoint, and fail over to the next ones in turn while
+    /// it can't be reached, so that submitting and polling proofs survive an endpoint going
+    /// down. Use [NetworkProver::health_check] to go back to the primary once it recovers.
+    pub fn rpc_urls(mut self, rpc_urls: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.rpc_urls = rpc_urls.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the time after which a single request to the prover network is abandoned, and
+    /// retried if it is idempotent. Requests don't time out by default.
+    ///
+    /// This isn't the time a proof may take, see [crate::action::Prove::timeout].
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the interval between two polls of the status of a proof request. Defaults to
+    /// [DEFAULT_POLL_INTERVAL].
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Sets how the requests that are safe to repeat, such as polling the status of a proof or
+    /// uploading the program, are retried when they fail. Defaults to [RetryPolicy::default],
+    /// use [RetryPolicy::none] to fail on the first error.
+    ///
+    /// Creating and submitting the proof request isn't retried, as it could request the proof
+    /// twice.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Sets where the circuit artifacts used for verification are installed, looked up and
+    /// downloaded from.
+    pub fn install_config(mut self, install: InstallConfig) -> Self {
+        self.install = Some(install);
+        self
+    }
+
+    /// Sets the fees [NetworkProver::estimate_price] prices requests with.
+    pub fn pricing(mut self, pricing: NetworkPricing) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Sets the fee and priority hints attached to every proof request, e.g. a low priority for
+    /// batch workloads. Defaults to a normal priority without a maximum fee.
+    pub fn request_opts(mut self, opts: ProofRequestOpts) -> Self {
+        self.request_opts = opts;
+        self
+    }
+
+    /// Sets the maximum number of cycles the prover network accepts to prove. Requests for
+    /// longer executions fail during the local simulation, before they are submitted. Unlimited
+    /// by default.
+    pub fn cycle_limit(mut self, cycle_limit: u64) -> Self {
+        self.cycle_limit = Some(cycle_limit);
+        self
+    }
+
+    /// Limits the requests sent to the prover network, queueing the requests over the limit
+    /// until their turn so that bulk submissions don't trip the rate limits of the network.
+    /// Unlimited by default.
+    ///
+    /// The limit is shared by every caller of the prover, and every request counts, including
+    /// the polls of the status of proofs.
+    pub fn rate_limit(mut self, limit: RateLimit) -> Self {
+        self.rate_limit = limit;
+        self
+    }
+
+    /// Builds the [NetworkProver].
+    ///
+    /// Panics if no key is set and neither `SP1_PRIVATE_KEY` nor `SP1_KEYSTORE_PATH` is set, if
+    /// the keystore can't be decrypted, or if an RPC endpoint isn't a valid URL.
+    pub fn build(self) -> NetworkProver {
+        let auth = self.auth.unwrap_or_else(|| {
+            match self.private_key.or_else(|| env::var("SP1_PRIVATE_KEY").ok()) {
+                Some(private_key) => NetworkAuth::new(&private_key),
+                None => {
+                    let path = env::var("SP1_KEYSTORE_PATH").expect(
+                        "SP1_PRIVATE_KEY or SP1_KEYSTORE_PATH must be set for remote proving",
+                    );
+                    let password = env::var("SP1_KEYSTORE_PASSWORD").unwrap_or_default();
+                    NetworkAuth::from_keystore(path, password).unwrap()
+                }
+            }
+        });
+        let rpc_urls =
+            if self.rpc_urls.is_empty() { NetworkClient::rpc_urls() } else { self.rpc_urls };
+        log::info!("Client circuit version: {}", SP1_CIRCUIT_VERSION);
+
+        let client = NetworkClient::new_with_auth(
+            auth,
+            &rpc_urls,
+            self.request_timeout,
+            self.retry.unwrap_or_default(),
+        )
+        .with_rate_limit(self.rate_limit);
+        let local_prover = match self.install {
+            Some(install) => CpuProver::new().with_install_config(install),
+            None => CpuProver::new(),
+        };
+        NetworkProver {
+            client: Arc::new(client),
+            local_prover,
+            poll_interval: self.poll_interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+            pricing: self.pricing,
+            request_opts: self.request_opts,
+            cycle_limit: self.cycle_limit,
+            registered: Mutex::default(),
+            tee_requests: Mutex::default(),
+            metrics: Mutex::default(),
+        }
+    }
 }
 
 impl NetworkProver {
-    /// Creates a new [NetworkProver] with the private key set in `SP1_PRIVATE_KEY`.
+    /// Creates a new [NetworkProverBuilder] to configure a [NetworkProver].
+    pub fn builder() -> NetworkProverBuilder {
+        NetworkProverBuilder::default()
+    }
+
+    /// Creates a new [NetworkProver] with the private key set in `SP1_PRIVATE_KEY`, or decrypted
+    /// from the keystore at `SP1_KEYSTORE_PATH`.
     pub fn new() -> Self {
-        let private_key = env::var("SP1_PRIVATE_KEY")
-            .unwrap_or_else(|_| panic!("SP1_PRIVATE_KEY must be set for remote proving"));
-        Self::new_from_key(&private_key)
+        Self::builder().build()
     }
 
     /// Creates a new [NetworkProver] with the given private key.
     pub fn new_from_key(private_key: &str) -> Self {
-        let version = SP1_CIRCUIT_VERSION;
-        log::info!("Client circuit version: {}", version);
+        Self::builder().private_key(private_key).build()
+    }
+
+    /// Creates a new [NetworkProver] with the given private key, requesting proofs from the
+    /// prover network at `rpc_url`.
+    pub fn new_from_key_and_rpc_url(private_key: &str, rpc_url: &str) -> Self {
+        Self::builder().private_key(private_key).rpc_url(rpc_url).build()
+    }
+
+    /// Sets the root directory under which the circuit artifacts used for verification are
+    /// installed and looked up.
+    pub fn with_circuits_dir(mut self, circuits_dir: impl Into<PathBuf>) -> Self {
+        self.local_prover = self.local_prover.with_circuits_dir(circuits_dir);
+        self
+    }
+
+    /// Sets where the circuit artifacts used for verification are installed, looked up and
+    /// downloaded from.
+    pub fn with_install_config(mut self, install: InstallConfig) -> Self {
+        self.local_prover = self.local_prover.with_install_config(install);
+        self
+    }
+
+    /// Estimates the fee of requesting a proof of `elf` on `stdin` in `mode`, by executing the
+    /// program locally and pricing the execution with [NetworkProverBuilder::pricing].
+    ///
+    /// Fails if the execution fails, in which case the request would fail too.
+    pub fn estimate_price(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        mode: SP1ProofMode,
+    ) -> Result<PriceEstimate> {
+        let (_, report) = self.local_prover.sp1_prover().execute(elf, stdin, Default::default())?;
+        let cost = self.pricing.gas_model.estimate(&report);
+        Ok(self.pricing.price(cost, mode))
+    }
 
-        let local_prover = CpuProver::new();
-        Self { client: NetworkClient::new(private_key), local_prover }
+    /// Checks that a request for a proof of `elf` on `stdin` in `mode` would be accepted,
+    /// without submitting it, and returns the parameters it would be submitted with.
+    ///
+    /// The program is executed locally within [NetworkProverBuilder::cycle_limit], and the
+    /// account is authenticated with the network. The network doesn't expose the balance of
+    /// accounts, so compare [SimulatedRequest::estimate] to the balance to check that it covers
+    /// the fee.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{NetworkProver, SP1ProofMode, SP1Stdin};
+    ///
+    /// # async fn simulate(elf: &[u8]) -> anyhow::Result<()> {
+    /// let prover = NetworkProver::new();
+    /// let request = prover.simulate(elf, &SP1Stdin::new(), SP1ProofMode::Groth16).await?;
+    /// println!("{} cycles, estimated fee {}", request.cycles, request.estimate.fee);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn simulate(
+        &self,
+        elf: &[u8],
+        stdin: &SP1Stdin,
+        mode: SP1ProofMode,
+    ) -> Result<SimulatedRequest> {
+        let (_, report) =
+            self.local_prover.sp1_prover().execute(elf, stdin, self.simulation_context())?;
+        let nonce = self.client.get_nonce().await?;
+        let cost = self.pricing.gas_model.estimate(&report);
+        Ok(SimulatedRequest {
+            mode: SP1ProofKind::from(mode).into(),
+            version: SP1_CIRCUIT_VERSION.to_string(),
+            nonce,
+            deadline: NetworkClient::request_deadline(),
+            requester: self.client.auth.get_address(),
+            opts: self.request_opts,
+            cycles: report.total_instruction_count(),
+            program_size: bincode::serialized_size(elf)? as usize,
+            stdin_size: bincode::serialized_size(stdin)? as usize,
+            estimate: self.pricing.price(cost, mode),
+        })
+    }
+
+    /// The context programs are executed in before they are requested.
+    fn simulation_context<'a>(&self) -> SP1Context<'a> {
+        let mut builder = SP1Context::builder();
+        if let Some(cycle_limit) = self.cycle_limit {
+            builder.max_cycles(cycle_limit);
+        }
+        builder.build()
     }
 
     /// Requests a proof from the prover network, returning the proof ID.
+    ///
+    /// The request carries the fee and priority hints set with
+    /// [NetworkProverBuilder::request_opts].
     pub async fn request_proof(
         &self,
         elf: &[u8],
         stdin: SP1Stdin,
         mode: ProofMode,
+    ) -> Result<String> {
+        self.request_proof_with_opts(elf, stdin, mode, self.request_opts).await
+    }
+
+    /// Requests a proof from the prover network with the options of `opts`,
+    /// returning the proof ID.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use sp1_sdk::{
+    ///     network::ProofRequestOpts, proto::network::{ProofMode, ProofPriority}, NetworkProver,
+    ///     SP1Stdin,
+    /// };
+    ///
+    /// # async fn request(elf: &[u8]) -> anyhow::Result<()> {
+    /// let prover = NetworkProver::new();
+    /// let opts = ProofRequestOpts {
+    ///     max_fee: Some(1_000_000),
+    ///     priority: ProofPriority::High,
+    ///     ..Default::default()
+    /// };
+    /// let proof_id =
+    ///     prover.request_proof_with_opts(elf, SP1Stdin::new(), ProofMode::Groth16, opts).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn request_proof_with_opts(
+        &self,
+        elf: &[u8],
+        stdin: SP1Stdin,
+        mode: ProofMode,
+        opts: ProofRequestOpts,
     ) -> Result<String> {
         let client = &self.client;
 
@@ -55,20 +422,139 @@ impl NetworkProver {
 
         if !skip_simulation {
             let (_, report) =
-                self.local_prover.sp1_prover().execute(elf, &stdin, Default::default())?;
+                self.local_prover.sp1_prover().execute(elf, &stdin, self.simulation_context())?;
             log::info!("Simulation complete, cycles: {}", report.total_instruction_count());
         } else {
             log::info!("Skipping simulation");
         }
 
         let version = SP1_CIRCUIT_VERSION;
-        let proof_id = client.create_proof(elf, &stdin, mode, version).await?;
-        log::info!("Created {}", proof_id);
+        let program = ProgramId::of(elf);
+        let proof_id = if self.registered.lock().unwrap().contains(&program) {
+            client.create_proof_for_program(program, &stdin, mode, version, opts).await?
+        } else {
+            client.create_proof_with_opts(elf, &stdin, mode, version, opts).await?
+        };
+        self.log_created(&proof_id, opts);
+        Ok(proof_id)
+    }
+
+    /// Uploads the program `elf` to the prover network once, returning the ID proofs of it can
+    /// be requested with through [Self::request_proof_for_program].
+    ///
+    /// Proofs of registered programs requested with [Self::request_proof] only upload the
+    /// stdin too.
+    pub async fn register_program(&self, elf: &[u8]) -> Result<ProgramId> {
+        let program = self.client.register_program(elf).await?;
+        self.registered.lock().unwrap().insert(program);
+        log::info!("Registered program {}", program);
+        Ok(program)
+    }
 
-        if NetworkClient::rpc_url() == DEFAULT_PROVER_NETWORK_RPC {
+    /// Requests a proof of the program registered as `program` from the prover network,
+    /// uploading only `stdin`, and returns the proof ID.
+    ///
+    /// The program isn't simulated locally first, as its ELF isn't at hand.
+    pub async fn request_proof_for_program(
+        &self,
+        program: ProgramId,
+        stdin: SP1Stdin,
+        mode: ProofMode,
+    ) -> Result<String> {
+        let proof_id = self
+            .client
+            .create_proof_for_program(program, &stdin, mode, SP1_CIRCUIT_VERSION, self.request_opts)
+            .await?;
+        self.log_created(&proof_id, self.request_opts);
+        Ok(proof_id)
+    }
+
+    /// Logs the creation of the proof request `proof_id` made with `opts`, remembering whether
+    /// its proof must come with an attestation report.
+    fn log_created(&self, proof_id: &str, opts: ProofRequestOpts) {
+        log::info!("Created {}", proof_id);
+        if opts.require_tee {
+            self.tee_requests.lock().unwrap().insert(proof_id.to_string());
+        }
+        if self.client.active_rpc_url() == DEFAULT_PROVER_NETWORK_RPC {
             log::info!("View in explorer: https://explorer.succinct.xyz/{}", proof_id);
         }
-        Ok(proof_id)
+    }
+
+    /// Checks which RPC endpoints of the prover network can be reached, see
+    /// [NetworkClient::health_check].
+    pub async fn health_check(&self) -> Vec<(String, bool)> {
+        self.client.health_check().await
+    }
+
+    /// Lists the proof requests passing `filter`, by default the pending requests of this
+    /// account, e.g. to find orphaned requests to [Self::cancel].
+    ///
+    /// The network only returns the 10 most recent requests of every status, of all requesters,
+    /// so older requests aren't listed.
+    pub async fn list_requests(
+        &self,
+        filter: &ProofRequestFilter,
+    ) -> Result<Vec<ProofRequestSummary>> {
+        let address = self.client.auth.get_address();
+        let mut requests = Vec::new();
+        for status in filter.statuses() {
+            let res = self.client.get_proof_requests(status).await?;
+            requests.extend(
+                res.proofs
+                    .into_iter()
+                    .filter(|proof| filter.matches(proof, &address))
+                    .map(|proof| ProofRequestSummary::new(proof, status)),
+            );
+        }
+        Ok(requests)
+    }
+
+    /// Cancels the proof request `request_id` of this account, if it isn't fulfilled yet.
+    ///
+    /// Fails if the request was already fulfilled or made by another account.
+    pub async fn cancel(&self, request_id: &str) -> Result<()> {
+        self.client.cancel_proof(request_id).await?;
+        log::info!("Cancelled {}", request_id);
+        Ok(())
+    }
+
+    /// Fetches the status of the proof request `request_id`.
+    pub async fn get_status(&self, request_id: &str) -> Result<ProofRequestStatus> {
+        let res = self.client.get_proof_status_response(request_id).await?;
+        Ok(ProofRequestStatus::from_response(request_id, &res))
+    }
+
+    /// Polls the status of the proof request `request_id` until it is fulfilled, calling
+    /// `on_status` with every status fetched, and returns the proof.
+    ///
+    /// Returns `None` as soon as `on_status` returns [ControlFlow::Break], leaving the request
+    /// on the network, and fails with [NetworkError::Unfulfilled] if the request can't be
+    /// fulfilled.
+    ///
+    /// ### Examples
+    /// ```no_run
+    /// use std::ops::ControlFlow;
+    ///
+    /// use sp1_sdk::{network::status::ProofRequestState, NetworkProver};
+    ///
+    /// # async fn wait(prover: &NetworkProver, request_id: &str) -> anyhow::Result<()> {
+    /// let proof = prover
+    ///     .wait_with(request_id, |status| {
+    ///         println!("{}: {:?} since {:?}", status.request_id, status.state, status.state_since);
+    ///         ControlFlow::Continue(())
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_with(
+        &self,
+        request_id: &str,
+        on_status: impl FnMut(&ProofRequestStatus) -> ControlFlow<()>,
+    ) -> Result<Option<SP1ProofWithPublicValues>> {
+        let proof = self.poll(request_id, on_status).await?;
+        Ok(proof.map(|(proof, network)| with_network_metadata(proof, network)))
     }
 
     /// Waits for a proof to be generated and returns the proof. If a timeout is supplied, the
@@ -78,41 +564,197 @@ impl NetworkProver {
         proof_id: &str,
         timeout: Option<Duration>,
     ) -> Result<P> {
-        let client = &self.client;
-        let mut is_claimed = false;
+        Ok(self.wait_proof_with_metadata(proof_id, timeout).await?.0)
+    }
+
+    /// Waits for the proof of `proof_id` like [Self::wait_proof], returning it with the
+    /// [ProofMetadata::network](crate::ProofMetadata::network) metadata of the request.
+    pub(crate) async fn wait_network_proof(
+        &self,
+        proof_id: &str,
+        timeout: Option<Duration>,
+    ) -> Result<SP1ProofWithPublicValues> {
+        let (proof, network) = self.wait_proof_with_metadata(proof_id, timeout).await?;
+        Ok(with_network_metadata(proof, network))
+    }
+
+    /// Waits for the proof of `proof_id` like [Self::wait_proof], returning it with the
+    /// metadata of the request.
+    async fn wait_proof_with_metadata<P: DeserializeOwned>(
+        &self,
+        proof_id: &str,
+        timeout: Option<Duration>,
+    ) -> Result<(P, NetworkProofMetadata)> {
         let start_time = Instant::now();
-        loop {
-            if let Some(timeout) = timeout {
-                if start_time.elapsed() > timeout {
-                    return Err(anyhow::anyhow!("Proof generation timed out."));
+        let mut timed_out = None;
+        let proof = self
+            .poll(proof_id, |status| {
+                if let Some(timeout) = timeout.filter(|timeout| start_time.elapsed() > *timeout) {
+                    timed_out = Some(timeout);
+                    return ControlFlow::Break(());
                 }
-            }
+                if status.state == ProofRequestState::Assigned
+                    && status.state_since == status.checked_at
+                {
+                    log::info!("Proof request claimed, proving...");
+                }
+                ControlFlow::Continue(())
+            })
+            .await?;
+        match (proof, timed_out) {
+            (Some(proof), _) => Ok(proof),
+            (None, timeout) => Err(NetworkError::Timeout(timeout.unwrap_or_default()).into()),
+        }
+    }
 
-            let (status, maybe_proof) = client.get_proof_status::<P>(proof_id).await?;
+    /// The metrics of the proof requests this prover waited for so far, aggregated over its
+    /// lifetime.
+    ///
+    /// Requests are only counted once their proof was waited for, with [Self::prove],
+    /// [Self::wait_proof] or [Self::wait_with].
+    pub fn metrics(&self) -> NetworkMetrics {
+        self.metrics.lock().unwrap().clone()
+    }
 
-            match status.status() {
-                ProofStatus::ProofFulfilled => {
-                    return Ok(maybe_proof.unwrap());
-                }
-                ProofStatus::ProofClaimed => {
-                    if !is_claimed {
-                        log::info!("Proof request claimed, proving...");
-                        is_claimed = true;
-                    }
+    /// Polls the status of `request_id` until it is fulfilled or `on_status` stops it, and
+    /// downloads the proof along with the metadata of the request.
+    async fn poll<P: DeserializeOwned>(
+        &self,
+        request_id: &str,
+        mut on_status: impl FnMut(&ProofRequestStatus) -> ControlFlow<()>,
+    ) -> Result<Option<(P, NetworkProofMetadata)>> {
+        let mut timeline = RequestTimeline::default();
+        let terminal =
+            poll_until_terminal(&self.client, self.poll_interval, request_id, |status| {
+                timeline.first_checked.get_or_insert(status.checked_at);
+                if status.state == ProofRequestState::Assigned {
+                    timeline.claimed.get_or_insert(status.state_since);
                 }
-                ProofStatus::ProofUnclaimed => {
-                    return Err(anyhow::anyhow!(
-                        "Proof generation failed: {}",
-                        status.unclaim_description()
-                    ));
+                on_status(status)
+            })
+            .await?;
+        match terminal {
+            None => Ok(None),
+            Some((
+                ProofRequestStatus { state: ProofRequestState::Unfulfillable(reason), .. },
+                _,
+            )) => {
+                self.metrics.lock().unwrap().unfulfilled += 1;
+                Err(NetworkError::Unfulfilled(reason).into())
+            }
+            Some((status, res)) => {
+                log::info!("Proof request fulfilled");
+                let metadata = timeline.metadata(request_id, &res, status.checked_at);
+                self.metrics.lock().unwrap().record_fulfilled(&metadata);
+                let require_tee = self.tee_requests.lock().unwrap().remove(request_id);
+                if require_tee && metadata.attestation.is_none() {
+                    return Err(NetworkError::Unfulfilled(
+                        "the proof required a TEE but came without an attestation report"
+                            .to_string(),
+                    )
+                    .into());
                 }
-                _ => {}
+                Ok(Some((self.client.download_proof(&res).await?, metadata)))
             }
-            sleep(Duration::from_secs(2)).await;
         }
     }
 
+    /// Subscribes to the completion of the proof request `request_id`.
+    ///
+    /// The status of the request is polled in the background, and a single
+    /// [ProofNotification] is sent on the returned channel once the request is fulfilled or
+    /// can't be fulfilled, or the error that stopped the polling.
+    pub fn subscribe(
+        &self,
+        request_id: &str,
+    ) -> mpsc::Receiver<std::result::Result<ProofNotification, NetworkError>> {
+        let (sender, receiver) = mpsc::channel();
+        self.watch(request_id, move |_, notification| async move {
+            // The subscriber may have gone away, in which case there is no one to notify.
+            let _ = sender.send(notification);
+        });
+        receiver
+    }
+
+    /// Sends the [ProofNotification] of the proof request `request_id` as JSON to the HTTPS
+    /// endpoint `url` once the request is fulfilled or can't be fulfilled.
+    ///
+    /// The prover network doesn't call webhooks itself, so the status of the request is polled
+    /// in the background of this process, which must outlive the request. The delivery is
+    /// retried with the [RetryPolicy] of the prover, and the returned handle resolves to its
+    /// outcome.
+    pub fn notify_webhook(
+        &self,
+        request_id: &str,
+        url: &str,
+    ) -> Result<std::thread::JoinHandle<Result<()>>> {
+        let url = reqwest::Url::parse(url).map_err(|err| NetworkError::Request(err.into()))?;
+        if url.scheme() != "https" {
+            return Err(NetworkError::Request(anyhow::anyhow!(
+                "webhooks must be HTTPS endpoints, got {}",
+                url
+            ))
+            .into());
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.watch(request_id, move |client, notification| async move {
+            let result = async {
+                let body = serde_json::to_vec(&notification?)?;
+                client
+                    .with_retries("calling the webhook", || async {
+                        client
+                            .http
+                            .post(url.clone())
+                            .header(reqwest::header::CONTENT_TYPE, "application/json")
+                            .body(body.clone())
+                            .send()
+                            .await?
+                            .error_for_status()?;
+                        Ok(())
+                    })
+                    .await
+            };
+            let _ = sender.send(result.await);
+        });
+        Ok(std::thread::spawn(move || {
+            receiver.recv().unwrap_or_else(|_| Err(anyhow::anyhow!("the webhook task panicked")))
+        }))
+    }
+
+    /// Polls the status of `request_id` on a background thread until it is terminal, and hands
+    /// its notification to `deliver`.
+    fn watch<F, Fut>(&self, request_id: &str, deliver: F)
+    where
+        F: FnOnce(Arc<NetworkClient>, std::result::Result<ProofNotification, NetworkError>) -> Fut
+            + Send
+            + 'static,
+        Fut: Future<Output = ()>,
+    {
+        let (client, interval, request_id) =
+            (self.client.clone(), self.poll_interval, request_id.to_string());
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("failed to create a runtime");
+            runtime.block_on(async move {
+                let terminal = poll_until_terminal(&client, interval, &request_id, |_| {
+                    ControlFlow::Continue(())
+                });
+                let notification = match terminal.await {
+                    Ok(status) => Ok(ProofNotification::new(
+                        &status.expect("polling is only stopped once the request is terminal").0,
+                    )),
+                    Err(err) => Err(NetworkError::Request(err)),
+                };
+                deliver(client.clone(), notification).await;
+            });
+        });
+    }
+
     /// Requests a proof from the prover network and waits for it to be generated.
+    ///
+    /// Fails with a [NetworkError].
     pub async fn prove(
         &self,
         elf: &[u8],
@@ -120,8 +762,58 @@ impl NetworkProver {
         mode: ProofMode,
         timeout: Option<Duration>,
     ) -> Result<SP1ProofWithPublicValues> {
-        let proof_id = self.request_proof(elf, stdin, mode).await?;
-        self.wait_proof(&proof_id, timeout).await
+        let proof = async {
+            let proof_id = self.request_proof(elf, stdin, mode).await?;
+            self.wait_network_proof(&proof_id, timeout).await
+        };
+        proof.await.map_err(|err| {
+            if err.is::<NetworkError>() {
+                err
+            } else {
+                NetworkError::Request(err).into()
+            }
+        })
+    }
+}
+
+/// The proof the network served without metadata, with the metadata of its request.
+fn with_network_metadata(
+    proof: LegacySP1ProofWithPublicValues,
+    network: NetworkProofMetadata,
+) -> SP1ProofWithPublicValues {
+    let mut proof = SP1ProofWithPublicValues::from(proof);
+    let mut metadata = proof_metadata(ProverType::Network, 0, 0);
+    metadata.network = Some(network);
+    proof.metadata = Some(metadata);
+    proof
+}
+
+/// Polls the status of `request_id` every `interval` until it is terminal, calling `on_status`
+/// with every status fetched and tracking since when the request is in its state.
+///
+/// Returns the terminal status and the response it was read from, or `None` as soon as
+/// `on_status` returns [ControlFlow::Break].
+async fn poll_until_terminal(
+    client: &NetworkClient,
+    interval: Duration,
+    request_id: &str,
+    mut on_status: impl FnMut(&ProofRequestStatus) -> ControlFlow<()>,
+) -> Result<Option<(ProofRequestStatus, GetProofStatusResponse)>> {
+    let mut previous: Option<ProofRequestStatus> = None;
+    loop {
+        let res = client.get_proof_status_response(request_id).await?;
+        let mut status = ProofRequestStatus::from_response(request_id, &res);
+        if let Some(previous) = previous.filter(|previous| previous.state == status.state) {
+            status.state_since = previous.state_since;
+        }
+        if on_status(&status).is_break() {
+            return Ok(None);
+        }
+        if status.state.is_terminal() {
+            return Ok(Some((status, res)));
+        }
+        previous = Some(status);
+        sleep(interval).await;
     }
 }
 
@@ -138,6 +830,14 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         self.local_prover.sp1_prover()
     }
 
+    fn install_config(&self) -> InstallConfig {
+        self.local_prover.install_config()
+    }
+
+    fn network_prover(&self) -> Option<&NetworkProver> {
+        Some(self)
+    }
+
     fn prove<'a>(
         &'a self,
         pk: &SP1ProvingKey,
@@ -147,6 +847,9 @@ impl Prover<DefaultProverComponents> for NetworkProver {
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
         warn_if_not_default(&opts.sp1_prover_opts, &context);
+        if opts.checkpoint_dir.is_some() {
+            tracing::warn!("checkpoint_dir will be ignored, the network prover can't be resumed");
+        }
         block_on(self.prove(&pk.elf, stdin, kind.into(), opts.timeout))
     }
 }
@@ -165,7 +868,7 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("custom SP1ProverOpts are currently unsupported by the network prover");
     }
     // Exhaustive match is done to ensure we update the warnings if the types change.
-    let SP1Context { hook_registry, subproof_verifier, .. } = context;
+    let SP1Context { hook_registry, subproof_verifier, cancellation_token, progress, .. } = context;
     if hook_registry.is_some() {
         tracing::warn!("non-default context.hook_registry will be ignored: {:?}", hook_registry);
         tracing::warn!("custom runtime hooks are currently unsupported by the network prover");
@@ -175,6 +878,14 @@ fn warn_if_not_default(opts: &SP1ProverOpts, context: &SP1Context) {
         tracing::warn!("non-default context.subproof_verifier will be ignored");
         tracing::warn!("custom subproof verifiers are currently unsupported by the network prover");
     }
+    if cancellation_token.is_some() {
+        tracing::warn!("context.cancellation_token will be ignored once the proof is requested");
+        tracing::warn!("proofs requested from the network prover can't be cancelled");
+    }
+    if progress.is_some() {
+        tracing::warn!("context.progress will be ignored");
+        tracing::warn!("progress callbacks are currently unsupported by the network prover");
+    }
 }
 
 impl From<SP1ProofKind> for ProofMode {
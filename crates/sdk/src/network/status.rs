@@ -0,0 +1,197 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::proto::network::{GetProofStatusResponse, ProofMode, ProofStatus, RequestedProof};
+
+/// The state of a proof request on the prover network.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProofRequestState {
+    /// The request is waiting for a prover to claim it.
+    Queued,
+    /// A prover claimed the request and committed to fulfilling it.
+    Assigned,
+    /// The prover assigned to the request started proving it.
+    ///
+    /// The network doesn't report this yet, claimed requests are [Self::Assigned] until they are
+    /// fulfilled.
+    Proving,
+    /// The proof is available for download.
+    Fulfilled,
+    /// The request won't be fulfilled, for the given reason.
+    Unfulfillable(String),
+}
+
+impl ProofRequestState {
+    /// Whether the request won't change state anymore.
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Fulfilled | Self::Unfulfillable(_))
+    }
+}
+
+/// The status of a proof request, as returned by
+/// [NetworkProver::get_status](super::prover::NetworkProver::get_status).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofRequestStatus {
+    /// The ID of the request.
+    pub request_id: String,
+    /// The state of the request.
+    pub state: ProofRequestState,
+    /// When the status was fetched.
+    pub checked_at: SystemTime,
+    /// When the request was first seen in its current state. Only tracked across the polls of
+    /// [NetworkProver::wait_with](super::prover::NetworkProver::wait_with), otherwise
+    /// [Self::checked_at].
+    pub state_since: SystemTime,
+}
+
+impl ProofRequestStatus {
+    /// The status of `request_id` described by the response of the network, fetched now.
+    pub(crate) fn from_response(request_id: &str, response: &GetProofStatusResponse) -> Self {
+        let state = match response.status() {
+            ProofStatus::ProofUnspecifiedStatus
+            | ProofStatus::ProofPreparing
+            | ProofStatus::ProofRequested => ProofRequestState::Queued,
+            ProofStatus::ProofClaimed => ProofRequestState::Assigned,
+            ProofStatus::ProofFulfilled => ProofRequestState::Fulfilled,
+            ProofStatus::ProofUnclaimed => {
+                ProofRequestState::Unfulfillable(response.unclaim_description().to_string())
+            }
+        };
+        let now = SystemTime::now();
+        Self { request_id: request_id.to_string(), state, checked_at: now, state_since: now }
+    }
+}
+
+/// The notification delivered once a proof request reaches a terminal state, see
+/// [NetworkProver::subscribe](super::prover::NetworkProver::subscribe) and
+/// [NetworkProver::notify_webhook](super::prover::NetworkProver::notify_webhook).
+///
+/// Webhooks receive it as the JSON body of a `POST` request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofNotification {
+    /// The ID of the request.
+    pub proof_id: String,
+    /// Whether the proof was fulfilled and is available for download.
+    pub fulfilled: bool,
+    /// Why the request won't be fulfilled, if it wasn't.
+    pub reason: Option<String>,
+    /// When the terminal state was observed, in seconds since the Unix epoch.
+    pub completed_at: u64,
+}
+
+impl ProofNotification {
+    /// The notification of the terminal `status`.
+    pub(crate) fn new(status: &ProofRequestStatus) -> Self {
+        let reason = match &status.state {
+            ProofRequestState::Unfulfillable(reason) => Some(reason.clone()),
+            _ => None,
+        };
+        Self {
+            proof_id: status.request_id.clone(),
+            fulfilled: status.state == ProofRequestState::Fulfilled,
+            reason,
+            completed_at: status
+                .checked_at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs()),
+        }
+    }
+}
+
+/// Which proof requests [NetworkProver::list_requests](super::prover::NetworkProver::list_requests)
+/// lists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProofRequestFilter {
+    /// The statuses of the requests to list. Defaults to the pending requests, both requested and
+    /// claimed, if empty.
+    pub statuses: Vec<ProofStatus>,
+    /// Only list the requests for proofs of this mode.
+    pub mode: Option<ProofMode>,
+    /// List the requests of every requester, rather than only those of this account.
+    pub all_requesters: bool,
+}
+
+impl ProofRequestFilter {
+    /// The statuses of the requests to list.
+    pub(crate) fn statuses(&self) -> Vec<ProofStatus> {
+        if self.statuses.is_empty() {
+            vec![ProofStatus::ProofRequested, ProofStatus::ProofClaimed]
+        } else {
+            self.statuses.clone()
+        }
+    }
+
+    /// Whether the request `proof` passes the filter, for the account `address`.
+    pub(crate) fn matches(&self, proof: &RequestedProof, address: &[u8]) -> bool {
+        (self.all_requesters || proof.requester == address)
+            && self.mode.map_or(true, |mode| proof.mode() == mode)
+    }
+}
+
+/// A proof request listed by
+/// [NetworkProver::list_requests](super::prover::NetworkProver::list_requests).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofRequestSummary {
+    /// The ID of the request.
+    pub request_id: String,
+    /// The mode of the requested proof.
+    pub mode: ProofMode,
+    /// The status of the request when it was listed.
+    pub status: ProofStatus,
+    /// The address of the account that requested the proof.
+    pub requester: Vec<u8>,
+}
+
+impl ProofRequestSummary {
+    pub(crate) fn new(proof: RequestedProof, status: ProofStatus) -> Self {
+        Self { mode: proof.mode(), request_id: proof.proof_id, status, requester: proof.requester }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_response() {
+        let response = |status: ProofStatus| GetProofStatusResponse {
+            status: status.into(),
+            unclaim_description: Some("invalid program".to_string()),
+            ..Default::default()
+        };
+        let state = |status| ProofRequestStatus::from_response("id", &response(status)).state;
+        assert_eq!(state(ProofStatus::ProofRequested), ProofRequestState::Queued);
+        assert_eq!(state(ProofStatus::ProofClaimed), ProofRequestState::Assigned);
+        assert_eq!(state(ProofStatus::ProofFulfilled), ProofRequestState::Fulfilled);
+        assert_eq!(
+            state(ProofStatus::ProofUnclaimed),
+            ProofRequestState::Unfulfillable("invalid program".to_string())
+        );
+        assert!(state(ProofStatus::ProofFulfilled).is_terminal());
+        assert!(!state(ProofStatus::ProofClaimed).is_terminal());
+    }
+
+    #[test]
+    fn test_filter() {
+        let proof = |mode: ProofMode, requester: u8| RequestedProof {
+            proof_id: "id".to_string(),
+            mode: mode.into(),
+            requester: vec![requester; 20],
+        };
+        let own = [1u8; 20];
+        let filter = ProofRequestFilter::default();
+        assert!(filter.matches(&proof(ProofMode::Core, 1), &own));
+        assert!(!filter.matches(&proof(ProofMode::Core, 2), &own));
+        assert_eq!(filter.statuses(), [ProofStatus::ProofRequested, ProofStatus::ProofClaimed]);
+
+        let filter = ProofRequestFilter {
+            mode: Some(ProofMode::Groth16),
+            all_requesters: true,
+            ..Default::default()
+        };
+        assert!(filter.matches(&proof(ProofMode::Groth16, 2), &own));
+        assert!(!filter.matches(&proof(ProofMode::Core, 1), &own));
+    }
+}
@@ -1,9 +1,11 @@
-use std::{fmt::Debug, fs::File, path::Path};
+use std::{fmt::Debug, fs::File, path::Path, str::FromStr, time::Duration};
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use sp1_core_executor::ProofStage;
 use sp1_core_machine::io::{SP1PublicValues, SP1Stdin};
 use strum_macros::{EnumDiscriminants, EnumTryAs};
+use thiserror::Error;
 
 use sp1_prover::{CoreSC, Groth16Bn254Proof, InnerSC, PlonkBn254Proof};
 use sp1_stark::{MachineVerificationError, ShardProof};
@@ -20,6 +22,153 @@ pub enum SP1Proof {
     Groth16(Groth16Bn254Proof),
 }
 
+/// The mode to generate a proof in, for selecting it at runtime, e.g. from a configuration file
+/// or a command line flag.
+///
+/// Parses from and serializes to `core`, `compressed`, `plonk` and `groth16`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SP1ProofMode {
+    /// A proof of every shard of the execution, see [SP1Proof::Core].
+    #[default]
+    Core,
+    /// A constant size STARK proof, see [SP1Proof::Compressed].
+    Compressed,
+    /// A Plonk proof verifiable onchain, see [SP1Proof::Plonk].
+    Plonk,
+    /// A Groth16 proof verifiable onchain, see [SP1Proof::Groth16].
+    Groth16,
+}
+
+/// An error parsing a [SP1ProofMode].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("unknown proof mode {0:?}, expected one of core, compressed, plonk or groth16")]
+pub struct ParseProofModeError(pub String);
+
+impl SP1ProofMode {
+    /// The name of the mode, as parsed by [FromStr].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Core => "core",
+            Self::Compressed => "compressed",
+            Self::Plonk => "plonk",
+            Self::Groth16 => "groth16",
+        }
+    }
+}
+
+impl std::fmt::Display for SP1ProofMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SP1ProofMode {
+    type Err = ParseProofModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "core" => Ok(Self::Core),
+            "compressed" => Ok(Self::Compressed),
+            "plonk" => Ok(Self::Plonk),
+            "groth16" => Ok(Self::Groth16),
+            _ => Err(ParseProofModeError(s.to_string())),
+        }
+    }
+}
+
+impl From<SP1ProofMode> for SP1ProofKind {
+    fn from(mode: SP1ProofMode) -> Self {
+        match mode {
+            SP1ProofMode::Core => Self::Core,
+            SP1ProofMode::Compressed => Self::Compressed,
+            SP1ProofMode::Plonk => Self::Plonk,
+            SP1ProofMode::Groth16 => Self::Groth16,
+        }
+    }
+}
+
+impl From<SP1ProofKind> for SP1ProofMode {
+    fn from(kind: SP1ProofKind) -> Self {
+        match kind {
+            SP1ProofKind::Core => Self::Core,
+            SP1ProofKind::Compressed => Self::Compressed,
+            SP1ProofKind::Plonk => Self::Plonk,
+            SP1ProofKind::Groth16 => Self::Groth16,
+        }
+    }
+}
+
+/// Metadata recorded while generating a proof, describing the execution and how long proving it
+/// took.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    /// The number of cycles executed.
+    pub cycles: u64,
+    /// The number of shards the execution was split into.
+    pub shards: usize,
+    /// The time each stage of proof generation took, in the order the stages finished.
+    pub stages: Vec<(ProofStage, Duration)>,
+    /// The prover that generated the proof, e.g. `Cpu`.
+    pub prover: String,
+    /// The version of the SDK that generated the proof.
+    pub prover_version: String,
+    /// How the prover network generated the proof, for proofs requested from it.
+    ///
+    /// The network doesn't report the execution and stages of its proofs, so the other fields
+    /// are zero or empty for them.
+    #[serde(default)]
+    pub network: Option<NetworkProofMetadata>,
+}
+
+/// How the prover network generated a proof, see [ProofMetadata::network].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkProofMetadata {
+    /// The ID of the proof request.
+    pub request_id: String,
+    /// The time the request waited for a prover to claim it.
+    pub queue_time: Duration,
+    /// The time the prover took to fulfill the request once it claimed it.
+    pub prove_time: Duration,
+    /// The hex-encoded address of the prover that fulfilled the request, if the network
+    /// reported it.
+    pub prover: Option<String>,
+    /// The attestation report of the trusted execution environment the proof was generated in,
+    /// for requests that required one, to be verified against the attestation service of the
+    /// enclave's vendor when auditing.
+    #[serde(default)]
+    pub attestation: Option<Vec<u8>>,
+}
+
+impl ProofMetadata {
+    /// The time spent in `stage`, if it ran.
+    pub fn duration(&self, stage: ProofStage) -> Option<Duration> {
+        self.stages.iter().filter(|(s, _)| *s == stage).map(|(_, d)| *d).reduce(|a, b| a + b)
+    }
+
+    /// The time spent executing the program.
+    ///
+    /// Execution overlaps with proving the shards, so this isn't included in the core proving
+    /// time, but it isn't additive with it either.
+    pub fn execution_duration(&self) -> Option<Duration> {
+        self.duration(ProofStage::Execute)
+    }
+
+    /// The time spent recursively compressing the shard proofs.
+    pub fn compress_duration(&self) -> Option<Duration> {
+        self.duration(ProofStage::Compress)
+    }
+
+    /// The time spent shrinking and wrapping the compressed proof, and generating the final
+    /// Plonk or Groth16 proof.
+    pub fn wrap_duration(&self) -> Option<Duration> {
+        [ProofStage::Shrink, ProofStage::Wrap, ProofStage::Plonk, ProofStage::Groth16]
+            .into_iter()
+            .filter_map(|stage| self.duration(stage))
+            .reduce(|a, b| a + b)
+    }
+}
+
 /// A proof generated with SP1, bundled together with stdin, public values, and the SP1 version.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SP1ProofWithPublicValues {
@@ -27,6 +176,31 @@ pub struct SP1ProofWithPublicValues {
     pub stdin: SP1Stdin,
     pub public_values: SP1PublicValues,
     pub sp1_version: String,
+    /// How the proof was generated, if the prover recorded it.
+    #[serde(default)]
+    pub metadata: Option<ProofMetadata>,
+}
+
+/// The layout of [SP1ProofWithPublicValues] before [ProofMetadata] was added, still produced by
+/// older SDKs and the prover network.
+#[derive(Deserialize)]
+pub(crate) struct LegacySP1ProofWithPublicValues {
+    proof: SP1Proof,
+    stdin: SP1Stdin,
+    public_values: SP1PublicValues,
+    sp1_version: String,
+}
+
+impl From<LegacySP1ProofWithPublicValues> for SP1ProofWithPublicValues {
+    fn from(legacy: LegacySP1ProofWithPublicValues) -> Self {
+        Self {
+            proof: legacy.proof,
+            stdin: legacy.stdin,
+            public_values: legacy.public_values,
+            sp1_version: legacy.sp1_version,
+            metadata: None,
+        }
+    }
 }
 
 impl SP1ProofWithPublicValues {
@@ -38,8 +212,18 @@ impl SP1ProofWithPublicValues {
 
     /// Loads a proof from a path.
     pub fn load(path: impl AsRef<Path>) -> Result<Self> {
-        bincode::deserialize_from(File::open(path).expect("failed to open file"))
-            .map_err(Into::into)
+        Self::from_bincode(&std::fs::read(path).expect("failed to open file"))
+    }
+
+    /// Deserializes a proof from its bincode encoding, also accepting proofs serialized without
+    /// [ProofMetadata].
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self> {
+        match bincode::deserialize(bytes) {
+            Ok(proof) => Ok(proof),
+            Err(err) => bincode::deserialize::<LegacySP1ProofWithPublicValues>(bytes)
+                .map(Into::into)
+                .map_err(|_| err.into()),
+        }
     }
 
     /// Returns the raw proof as a string.
@@ -79,3 +263,24 @@ impl SP1ProofWithPublicValues {
 pub type SP1CoreProofVerificationError = MachineVerificationError<CoreSC>;
 
 pub type SP1CompressedProofVerificationError = MachineVerificationError<InnerSC>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proof_mode() {
+        for mode in [
+            SP1ProofMode::Core,
+            SP1ProofMode::Compressed,
+            SP1ProofMode::Plonk,
+            SP1ProofMode::Groth16,
+        ] {
+            assert_eq!(mode.to_string().parse::<SP1ProofMode>(), Ok(mode));
+            assert_eq!(serde_json::to_string(&mode).unwrap(), format!("\"{mode}\""));
+            assert_eq!(SP1ProofMode::from(SP1ProofKind::from(mode)), mode);
+        }
+        assert_eq!(" Groth16".parse::<SP1ProofMode>(), Ok(SP1ProofMode::Groth16));
+        assert_eq!("fast".parse::<SP1ProofMode>(), Err(ParseProofModeError("fast".to_string())));
+    }
+}
@@ -19,6 +19,21 @@ pub struct CreateProofRequest {
     /// The client version used, in the form of an 8-character git commit hash.
     #[prost(string, tag = "5")]
     pub version: ::prost::alloc::string::String,
+    /// The maximum fee the requester is willing to pay for the proof, in the smallest unit of the
+    /// fee currency. Unset for no limit.
+    #[prost(uint64, optional, tag = "6")]
+    pub max_fee: ::core::option::Option<u64>,
+    /// The priority of the proof request, used to order requests when the network is busy.
+    #[prost(enumeration = "ProofPriority", tag = "7")]
+    pub priority: i32,
+    /// The sha256 hash of a program registered with register program, to prove it without
+    /// uploading it again. The program URL of the response is empty if set.
+    #[prost(bytes = "vec", optional, tag = "8")]
+    pub program_hash: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// Whether the proof must be generated inside an attested trusted execution environment, so
+    /// that the program and stdin are only processed inside it.
+    #[prost(bool, tag = "9")]
+    pub require_tee: bool,
 }
 /// The response for creating a proof.
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -28,13 +43,38 @@ pub struct CreateProofResponse {
     /// The proof identifier.
     #[prost(string, tag = "1")]
     pub proof_id: ::prost::alloc::string::String,
-    /// The URL to upload the ELF file.
+    /// The URL to upload the ELF file, empty if the proof is of a registered program.
     #[prost(string, tag = "2")]
     pub program_url: ::prost::alloc::string::String,
     /// The URL to upload the standard input (stdin).
     #[prost(string, tag = "3")]
     pub stdin_url: ::prost::alloc::string::String,
 }
+/// The request to register a program, so that proofs of it can be requested by its hash without
+/// uploading it again.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterProgramRequest {
+    /// The signature of the message.
+    #[prost(bytes = "vec", tag = "1")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// The nonce for the account.
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    /// The sha256 hash of the program.
+    #[prost(bytes = "vec", tag = "3")]
+    pub program_hash: ::prost::alloc::vec::Vec<u8>,
+}
+/// The response for registering a program.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RegisterProgramResponse {
+    /// The URL to upload the ELF file, empty if the program is already registered.
+    #[prost(string, tag = "1")]
+    pub program_url: ::prost::alloc::string::String,
+}
 /// The request to submit a proof, the second step in requesting a proof. MUST be called when the
 /// proof is in a PROOF_REQUESTED state and MUST be called after uploading the program and stdin to
 /// the URLs provided during create proof.
@@ -116,6 +156,27 @@ pub struct UnclaimProofRequest {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UnclaimProofResponse {}
+/// The request to cancel a proof, aborting it before it is fulfilled. MUST be called when the proof is
+/// in a PROOF_REQUESTED or PROOF_CLAIMED state and MUST be called by the requester of the proof.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelProofRequest {
+    /// The signature of the message.
+    #[prost(bytes = "vec", tag = "1")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// The nonce for the account.
+    #[prost(uint64, tag = "2")]
+    pub nonce: u64,
+    /// The proof identifier.
+    #[prost(string, tag = "3")]
+    pub proof_id: ::prost::alloc::string::String,
+}
+/// The response for cancelling a proof, empty on success.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelProofResponse {}
 /// The request to fulfill a proof. MUST be called after the proof has been uploaded and MUST be called
 /// when the proof is in a PROOF_CLAIMED state.
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -223,6 +284,22 @@ pub struct GetProofStatusResponse {
     /// If the proof was unclaimed, the description detailing why.
     #[prost(string, optional, tag = "4")]
     pub unclaim_description: ::core::option::Option<::prost::alloc::string::String>,
+    /// The address of the prover that claimed the proof, if it was claimed.
+    #[prost(bytes = "vec", optional, tag = "5")]
+    pub prover: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    /// When the proof was requested, in seconds since the Unix epoch.
+    #[prost(uint64, optional, tag = "6")]
+    pub created_at: ::core::option::Option<u64>,
+    /// When the proof was claimed, in seconds since the Unix epoch.
+    #[prost(uint64, optional, tag = "7")]
+    pub claimed_at: ::core::option::Option<u64>,
+    /// When the proof was fulfilled, in seconds since the Unix epoch.
+    #[prost(uint64, optional, tag = "8")]
+    pub fulfilled_at: ::core::option::Option<u64>,
+    /// The attestation report of the trusted execution environment the proof was generated in,
+    /// if it was generated in one.
+    #[prost(bytes = "vec", optional, tag = "9")]
+    pub attestation: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
 }
 /// The request to get proof requests by a given status.
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -335,6 +412,55 @@ impl ProofMode {
         }
     }
 }
+/// The priority of a proof request.
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    ::prost::Enumeration,
+)]
+#[repr(i32)]
+pub enum ProofPriority {
+    /// Unspecified priority, treated as normal priority.
+    Unspecified = 0,
+    /// For batch workloads, fulfilled once the requests of higher priorities are.
+    Low = 1,
+    /// The default priority.
+    Normal = 2,
+    /// For urgent proofs, fulfilled before the requests of lower priorities.
+    High = 3,
+}
+impl ProofPriority {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ProofPriority::Unspecified => "PROOF_PRIORITY_UNSPECIFIED",
+            ProofPriority::Low => "PROOF_PRIORITY_LOW",
+            ProofPriority::Normal => "PROOF_PRIORITY_NORMAL",
+            ProofPriority::High => "PROOF_PRIORITY_HIGH",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PROOF_PRIORITY_UNSPECIFIED" => Some(Self::Unspecified),
+            "PROOF_PRIORITY_LOW" => Some(Self::Low),
+            "PROOF_PRIORITY_NORMAL" => Some(Self::Normal),
+            "PROOF_PRIORITY_HIGH" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
 /// The status of a proof request.
 #[derive(
     serde::Serialize,
@@ -501,6 +627,11 @@ pub trait NetworkService {
         ctx: twirp::Context,
         req: CreateProofRequest,
     ) -> Result<CreateProofResponse, twirp::TwirpErrorResponse>;
+    async fn register_program(
+        &self,
+        ctx: twirp::Context,
+        req: RegisterProgramRequest,
+    ) -> Result<RegisterProgramResponse, twirp::TwirpErrorResponse>;
     async fn submit_proof(
         &self,
         ctx: twirp::Context,
@@ -516,6 +647,11 @@ pub trait NetworkService {
         ctx: twirp::Context,
         req: UnclaimProofRequest,
     ) -> Result<UnclaimProofResponse, twirp::TwirpErrorResponse>;
+    async fn cancel_proof(
+        &self,
+        ctx: twirp::Context,
+        req: CancelProofRequest,
+    ) -> Result<CancelProofResponse, twirp::TwirpErrorResponse>;
     async fn fulfill_proof(
         &self,
         ctx: twirp::Context,
@@ -558,6 +694,12 @@ where
                 api.create_proof(ctx, req).await
             },
         )
+        .route(
+            "/RegisterProgram",
+            |api: std::sync::Arc<T>, ctx: twirp::Context, req: RegisterProgramRequest| async move {
+                api.register_program(ctx, req).await
+            },
+        )
         .route(
             "/SubmitProof",
             |api: std::sync::Arc<T>, ctx: twirp::Context, req: SubmitProofRequest| async move {
@@ -576,6 +718,12 @@ where
                 api.unclaim_proof(ctx, req).await
             },
         )
+        .route(
+            "/CancelProof",
+            |api: std::sync::Arc<T>, ctx: twirp::Context, req: CancelProofRequest| async move {
+                api.cancel_proof(ctx, req).await
+            },
+        )
         .route(
             "/FulfillProof",
             |api: std::sync::Arc<T>, ctx: twirp::Context, req: FulfillProofRequest| async move {
@@ -620,6 +768,10 @@ pub trait NetworkServiceClient: Send + Sync + std::fmt::Debug {
         &self,
         req: CreateProofRequest,
     ) -> Result<CreateProofResponse, twirp::ClientError>;
+    async fn register_program(
+        &self,
+        req: RegisterProgramRequest,
+    ) -> Result<RegisterProgramResponse, twirp::ClientError>;
     async fn submit_proof(
         &self,
         req: SubmitProofRequest,
@@ -632,6 +784,10 @@ pub trait NetworkServiceClient: Send + Sync + std::fmt::Debug {
         &self,
         req: UnclaimProofRequest,
     ) -> Result<UnclaimProofResponse, twirp::ClientError>;
+    async fn cancel_proof(
+        &self,
+        req: CancelProofRequest,
+    ) -> Result<CancelProofResponse, twirp::ClientError>;
     async fn fulfill_proof(
         &self,
         req: FulfillProofRequest,
@@ -664,6 +820,13 @@ impl NetworkServiceClient for twirp::client::Client {
         let url = self.base_url.join("network.NetworkService/CreateProof")?;
         self.request(url, req).await
     }
+    async fn register_program(
+        &self,
+        req: RegisterProgramRequest,
+    ) -> Result<RegisterProgramResponse, twirp::ClientError> {
+        let url = self.base_url.join("network.NetworkService/RegisterProgram")?;
+        self.request(url, req).await
+    }
     async fn submit_proof(
         &self,
         req: SubmitProofRequest,
@@ -685,6 +848,13 @@ impl NetworkServiceClient for twirp::client::Client {
         let url = self.base_url.join("network.NetworkService/UnclaimProof")?;
         self.request(url, req).await
     }
+    async fn cancel_proof(
+        &self,
+        req: CancelProofRequest,
+    ) -> Result<CancelProofResponse, twirp::ClientError> {
+        let url = self.base_url.join("network.NetworkService/CancelProof")?;
+        self.request(url, req).await
+    }
     async fn fulfill_proof(
         &self,
         req: FulfillProofRequest,
@@ -1,10 +1,21 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
-use sp1_core_executor::SP1Context;
-use sp1_prover::{components::DefaultProverComponents, SP1Prover, SP1Stdin};
+use sp1_core_executor::{
+    report_stage, CancellationToken, ProgressCallback, ProofStage, SP1Context,
+};
+use sp1_prover::{
+    components::DefaultProverComponents, SP1CoreProof, SP1CoreProofData, SP1Prover, SP1ReduceProof,
+    SP1Stdin,
+};
 
 use crate::{
-    install::try_install_circuit_artifacts, provers::ProofOpts, Prover, SP1Proof, SP1ProofKind,
-    SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
+    checkpoint::ProofCheckpoint,
+    install::{install_circuit_artifacts_with, ArtifactsType, InstallConfig},
+    provers::{
+        core_prover_error, ensure_not_cancelled, proof_metadata, Deadline, ProofOpts, StageRecorder,
+    },
+    Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerifyingKey,
 };
 
 use super::ProverType;
@@ -12,73 +23,102 @@ use super::ProverType;
 /// An implementation of [crate::ProverClient] that can generate end-to-end proofs locally.
 pub struct CpuProver {
     prover: SP1Prover<DefaultProverComponents>,
+    install: InstallConfig,
 }
 
 impl CpuProver {
     /// Creates a new [LocalProver].
     pub fn new() -> Self {
         let prover = SP1Prover::new();
-        Self { prover }
+        Self::from_prover(prover)
     }
 
     /// Creates a new [LocalProver] from an existing [SP1Prover].
     pub fn from_prover(prover: SP1Prover<DefaultProverComponents>) -> Self {
-        Self { prover }
+        Self { prover, install: InstallConfig::from_env() }
     }
-}
 
-impl Prover<DefaultProverComponents> for CpuProver {
-    fn id(&self) -> ProverType {
-        ProverType::Cpu
+    /// Sets the root directory under which the circuit artifacts are installed and looked up.
+    pub fn with_circuits_dir(mut self, circuits_dir: impl Into<PathBuf>) -> Self {
+        self.install.circuits_dir = circuits_dir.into();
+        self
     }
 
-    fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
-        self.prover.setup(elf)
+    /// Sets where the circuit artifacts are installed, looked up and downloaded from.
+    pub fn with_install_config(mut self, install: InstallConfig) -> Self {
+        self.install = install;
+        self
     }
 
-    fn sp1_prover(&self) -> &SP1Prover<DefaultProverComponents> {
-        &self.prover
-    }
-
-    fn prove<'a>(
-        &'a self,
-        pk: &SP1ProvingKey,
-        stdin: SP1Stdin,
-        opts: ProofOpts,
-        context: SP1Context<'a>,
+    /// Runs the stages of proof generation that follow the core proof, turning a core or
+    /// compressed `proof` into a proof of the given kind.
+    pub(crate) fn reduce(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
         kind: SP1ProofKind,
+        opts: ProofOpts,
+        cancellation_token: Option<&CancellationToken>,
+        progress: Option<&ProgressCallback>,
     ) -> Result<SP1ProofWithPublicValues> {
-        // Generate the core proof.
-        let proof = self.prover.prove_core(pk, &stdin, opts.sp1_prover_opts, context)?;
-        if kind == SP1ProofKind::Core {
-            return Ok(SP1ProofWithPublicValues {
-                proof: SP1Proof::Core(proof.proof.0),
-                stdin: proof.stdin,
-                public_values: proof.public_values,
-                sp1_version: self.version().to_string(),
-            });
-        }
+        let ensure_not_cancelled = || ensure_not_cancelled(cancellation_token);
+        let SP1ProofWithPublicValues { proof, stdin, public_values, metadata, .. } = proof;
 
-        let deferred_proofs = stdin.proofs.iter().map(|p| p.0.clone()).collect();
-        let public_values = proof.public_values.clone();
+        let reduce_proof = match proof {
+            SP1Proof::Core(shard_proofs) => {
+                let deferred_proofs = stdin.proofs.iter().map(|p| p.0.clone()).collect();
+                let core_proof = SP1CoreProof {
+                    proof: SP1CoreProofData(shard_proofs),
+                    stdin: stdin.clone(),
+                    public_values: public_values.clone(),
+                    cycles: 0,
+                };
 
-        // Generate the compressed proof.
-        let reduce_proof =
-            self.prover.compress(&pk.vk, proof, deferred_proofs, opts.sp1_prover_opts)?;
-        if kind == SP1ProofKind::Compressed {
-            return Ok(SP1ProofWithPublicValues {
-                proof: SP1Proof::Compressed(reduce_proof.proof),
-                stdin,
-                public_values,
-                sp1_version: self.version().to_string(),
-            });
+                // Generate the compressed proof.
+                ensure_not_cancelled()?;
+                report_stage(progress, ProofStage::Compress, || {
+                    self.prover.compress_with_progress(
+                        vk,
+                        core_proof,
+                        deferred_proofs,
+                        opts.sp1_prover_opts,
+                        progress,
+                    )
+                })?
+            }
+            SP1Proof::Compressed(proof) => SP1ReduceProof { proof },
+            SP1Proof::Plonk(_) | SP1Proof::Groth16(_) => {
+                return Err(anyhow::anyhow!("only core and compressed proofs can be wrapped"))
+            }
+        };
+        match kind {
+            SP1ProofKind::Core => {
+                return Err(anyhow::anyhow!("a proof can't be wrapped into a core proof"))
+            }
+            SP1ProofKind::Compressed => {
+                return Ok(SP1ProofWithPublicValues {
+                    proof: SP1Proof::Compressed(reduce_proof.proof),
+                    stdin,
+                    public_values,
+                    sp1_version: self.version().to_string(),
+                    metadata,
+                });
+            }
+            SP1ProofKind::Plonk | SP1ProofKind::Groth16 => {}
         }
 
         // Generate the shrink proof.
-        let compress_proof = self.prover.shrink(reduce_proof, opts.sp1_prover_opts)?;
+        ensure_not_cancelled()?;
+        let compress_proof = report_stage(progress, ProofStage::Shrink, || {
+            self.prover.shrink(reduce_proof, opts.sp1_prover_opts)
+        })?;
 
         // Genenerate the wrap proof.
-        let outer_proof = self.prover.wrap_bn254(compress_proof, opts.sp1_prover_opts)?;
+        ensure_not_cancelled()?;
+        let outer_proof = report_stage(progress, ProofStage::Wrap, || {
+            self.prover.wrap_bn254(compress_proof, opts.sp1_prover_opts)
+        })?;
+        ensure_not_cancelled()?;
 
         if kind == SP1ProofKind::Plonk {
             let plonk_bn254_aritfacts = if sp1_prover::build::sp1_dev_mode() {
@@ -87,15 +127,18 @@ impl Prover<DefaultProverComponents> for CpuProver {
                     &outer_proof.proof,
                 )
             } else {
-                try_install_circuit_artifacts()
+                install_circuit_artifacts_with(&self.install, ArtifactsType::Plonk)?
             };
-            let proof = self.prover.wrap_plonk_bn254(outer_proof, &plonk_bn254_aritfacts);
+            let proof = report_stage(progress, ProofStage::Plonk, || {
+                self.prover.wrap_plonk_bn254(outer_proof, &plonk_bn254_aritfacts)
+            });
 
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Plonk(proof),
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                metadata,
             });
         } else if kind == SP1ProofKind::Groth16 {
             let groth16_bn254_artifacts = if sp1_prover::build::sp1_dev_mode() {
@@ -104,20 +147,167 @@ impl Prover<DefaultProverComponents> for CpuProver {
                     &outer_proof.proof,
                 )
             } else {
-                try_install_circuit_artifacts()
+                install_circuit_artifacts_with(&self.install, ArtifactsType::Groth16)?
             };
 
-            let proof = self.prover.wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts);
+            let proof = report_stage(progress, ProofStage::Groth16, || {
+                self.prover.wrap_groth16_bn254(outer_proof, &groth16_bn254_artifacts)
+            });
             return Ok(SP1ProofWithPublicValues {
                 proof: SP1Proof::Groth16(proof),
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
+                metadata,
             });
         }
 
         unreachable!()
     }
+
+    /// Runs the stages of proof generation up to a proof of the given kind, resuming from and
+    /// persisting to the checkpoint if one is set.
+    fn prove_stages<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        mut opts: ProofOpts,
+        mut context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        opts.sp1_prover_opts = opts.fitted_prover_opts()?;
+        let recorder = StageRecorder::default();
+        context.progress = Some(recorder.callback(context.progress.take()));
+        let cancellation_token = context.cancellation_token.clone();
+        let progress = context.progress.clone();
+        let checkpoint = opts
+            .checkpoint_dir
+            .as_ref()
+            .map(|dir| ProofCheckpoint::new(dir, &pk.vk, &stdin, &opts.sp1_prover_opts));
+
+        let mut proof = match checkpoint.as_ref().and_then(|checkpoint| checkpoint.resume(kind)) {
+            Some(proof) => {
+                let resumed = SP1ProofKind::from(&proof.proof);
+                tracing::info!("resuming from the {:?} proof of the checkpoint", resumed);
+                proof
+            }
+            None => {
+                // Generate the core proof.
+                context.shard_proofs_dir =
+                    checkpoint.as_ref().map(ProofCheckpoint::shard_proofs_dir);
+                let proof = self
+                    .prover
+                    .prove_core(pk, &stdin, opts.sp1_prover_opts, context)
+                    .map_err(core_prover_error)?;
+                let metadata = proof_metadata(self.id(), proof.cycles, proof.proof.0.len());
+                let proof = recorder.finish(SP1ProofWithPublicValues {
+                    proof: SP1Proof::Core(proof.proof.0),
+                    stdin,
+                    public_values: proof.public_values,
+                    sp1_version: self.version().to_string(),
+                    metadata: Some(metadata),
+                });
+                if let Some(checkpoint) = &checkpoint {
+                    store_checkpoint(checkpoint, &proof);
+                }
+                proof
+            }
+        };
+
+        if kind != SP1ProofKind::Core {
+            // Persist the compressed proof before wrapping it, as compressing is the longest stage.
+            if let (Some(checkpoint), SP1Proof::Core(_)) = (&checkpoint, &proof.proof) {
+                if kind != SP1ProofKind::Compressed {
+                    proof = self.reduce(
+                        &pk.vk,
+                        proof,
+                        SP1ProofKind::Compressed,
+                        opts.clone(),
+                        cancellation_token.as_ref(),
+                        progress.as_ref(),
+                    )?;
+                    proof = recorder.finish(proof);
+                    store_checkpoint(checkpoint, &proof);
+                }
+            }
+            if SP1ProofKind::from(&proof.proof) != kind {
+                proof = self.reduce(
+                    &pk.vk,
+                    proof,
+                    kind,
+                    opts,
+                    cancellation_token.as_ref(),
+                    progress.as_ref(),
+                )?;
+            }
+        }
+
+        if let Some(checkpoint) = &checkpoint {
+            if let Err(err) = checkpoint.clear() {
+                tracing::warn!(
+                    "failed to remove the checkpoint {}: {}",
+                    checkpoint.dir().display(),
+                    err
+                );
+            }
+        }
+        Ok(recorder.finish(proof))
+    }
+}
+
+impl Prover<DefaultProverComponents> for CpuProver {
+    fn id(&self) -> ProverType {
+        ProverType::Cpu
+    }
+
+    fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey) {
+        self.prover.setup(elf)
+    }
+
+    fn sp1_prover(&self) -> &SP1Prover<DefaultProverComponents> {
+        &self.prover
+    }
+
+    fn install_config(&self) -> InstallConfig {
+        self.install.clone()
+    }
+
+    fn prove<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        opts: ProofOpts,
+        mut context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        let deadline = opts.timeout.map(|timeout| Deadline::start(timeout, &mut context));
+        let result = self.prove_stages(pk, stdin, opts, context, kind);
+        match deadline {
+            Some(deadline) => deadline.finish(result),
+            None => result,
+        }
+    }
+
+    fn wrap(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
+        kind: SP1ProofKind,
+        mut opts: ProofOpts,
+    ) -> Result<SP1ProofWithPublicValues> {
+        opts.sp1_prover_opts = opts.fitted_prover_opts()?;
+        let recorder = StageRecorder::default();
+        let progress = recorder.callback(None);
+        let proof = self.reduce(vk, proof, kind, opts, None, Some(&progress))?;
+        Ok(recorder.finish(proof))
+    }
+}
+
+/// Persists `proof` to `checkpoint`, only warning on failure since the proof can go on without it.
+fn store_checkpoint(checkpoint: &ProofCheckpoint, proof: &SP1ProofWithPublicValues) {
+    if let Err(err) = checkpoint.store(proof) {
+        tracing::warn!("failed to checkpoint the proof in {}: {}", checkpoint.dir().display(), err);
+    }
 }
 
 impl Default for CpuProver {
@@ -1,10 +1,14 @@
+use std::path::PathBuf;
+
 use anyhow::Result;
+use sp1_core_executor::{report_stage, ProofStage};
 use sp1_cuda::SP1CudaProver;
 use sp1_prover::{components::DefaultProverComponents, SP1Prover, SP1Stdin};
 
 use super::ProverType;
 use crate::{
-    provers::{try_install_circuit_artifacts, ProofOpts},
+    install::{install_circuit_artifacts_with, ArtifactsType, InstallConfig},
+    provers::{ensure_not_cancelled, proof_metadata, GpuOpts, ProofOpts, StageRecorder},
     Prover, SP1Context, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey,
     SP1VerifyingKey,
 };
@@ -13,6 +17,7 @@ use crate::{
 pub struct CudaProver {
     prover: SP1Prover<DefaultProverComponents>,
     cuda_prover: SP1CudaProver,
+    install: InstallConfig,
 }
 
 impl CudaProver {
@@ -20,7 +25,31 @@ impl CudaProver {
     pub fn new() -> Self {
         let prover = SP1Prover::new();
         let cuda_prover = SP1CudaProver::new();
-        Self { prover, cuda_prover }
+        Self { prover, cuda_prover, install: InstallConfig::from_env() }
+    }
+
+    /// Creates a new [CudaProver] proving on the GPU selected by `opts`.
+    pub fn with_gpu(opts: GpuOpts) -> Self {
+        let prover = SP1Prover::new();
+        let cuda_prover = SP1CudaProver::with_device(opts.device_id, opts.memory_limit);
+        Self { prover, cuda_prover, install: InstallConfig::from_env() }
+    }
+
+    /// Whether the GPU selected by `opts` is present.
+    pub fn gpu_available(opts: GpuOpts) -> bool {
+        SP1CudaProver::device_available(opts.device_id)
+    }
+
+    /// Sets the root directory under which the circuit artifacts are installed and looked up.
+    pub fn with_circuits_dir(mut self, circuits_dir: impl Into<PathBuf>) -> Self {
+        self.install.circuits_dir = circuits_dir.into();
+        self
+    }
+
+    /// Sets where the circuit artifacts are installed, looked up and downloaded from.
+    pub fn with_install_config(mut self, install: InstallConfig) -> Self {
+        self.install = install;
+        self
     }
 }
 
@@ -37,46 +66,70 @@ impl Prover<DefaultProverComponents> for CudaProver {
         &self.prover
     }
 
+    fn install_config(&self) -> InstallConfig {
+        self.install.clone()
+    }
+
     fn prove<'a>(
         &'a self,
         pk: &SP1ProvingKey,
         stdin: SP1Stdin,
         _opts: ProofOpts,
-        _context: SP1Context<'a>,
+        context: SP1Context<'a>,
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
         tracing::warn!("opts and context are ignored for the cuda prover");
 
+        // The proof can't be interrupted on the GPU, but it's still checked between stages.
+        let ensure_not_cancelled = || ensure_not_cancelled(context.cancellation_token.as_ref());
+        ensure_not_cancelled()?;
+        let recorder = StageRecorder::default();
+        let progress = recorder.callback(context.progress.clone());
+        let progress = Some(&progress);
+
         // Generate the core proof.
-        let proof = self.cuda_prover.prove_core(pk, &stdin)?;
+        let proof =
+            report_stage(progress, ProofStage::Core, || self.cuda_prover.prove_core(pk, &stdin))?;
+        let metadata = Some(proof_metadata(self.id(), proof.cycles, proof.proof.0.len()));
         if kind == SP1ProofKind::Core {
-            return Ok(SP1ProofWithPublicValues {
+            return Ok(recorder.finish(SP1ProofWithPublicValues {
                 proof: SP1Proof::Core(proof.proof.0),
                 stdin: proof.stdin,
                 public_values: proof.public_values,
                 sp1_version: self.version().to_string(),
-            });
+                metadata,
+            }));
         }
 
         let deferred_proofs = stdin.proofs.iter().map(|p| p.0.clone()).collect();
         let public_values = proof.public_values.clone();
 
         // Generate the compressed proof.
-        let reduce_proof = self.cuda_prover.compress(&pk.vk, proof, deferred_proofs)?;
+        ensure_not_cancelled()?;
+        let reduce_proof = report_stage(progress, ProofStage::Compress, || {
+            self.cuda_prover.compress(&pk.vk, proof, deferred_proofs)
+        })?;
         if kind == SP1ProofKind::Compressed {
-            return Ok(SP1ProofWithPublicValues {
+            return Ok(recorder.finish(SP1ProofWithPublicValues {
                 proof: SP1Proof::Compressed(reduce_proof.proof),
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
-            });
+                metadata,
+            }));
         }
 
         // Generate the shrink proof.
-        let compress_proof = self.cuda_prover.shrink(reduce_proof)?;
+        ensure_not_cancelled()?;
+        let compress_proof =
+            report_stage(progress, ProofStage::Shrink, || self.cuda_prover.shrink(reduce_proof))?;
 
         // Genenerate the wrap proof.
-        let outer_proof = self.cuda_prover.wrap_bn254(compress_proof)?;
+        ensure_not_cancelled()?;
+        let outer_proof = report_stage(progress, ProofStage::Wrap, || {
+            self.cuda_prover.wrap_bn254(compress_proof)
+        })?;
+        ensure_not_cancelled()?;
 
         let plonk_bn254_aritfacts = if sp1_prover::build::sp1_dev_mode() {
             sp1_prover::build::try_build_plonk_bn254_artifacts_dev(
@@ -84,16 +137,19 @@ impl Prover<DefaultProverComponents> for CudaProver {
                 &outer_proof.proof,
             )
         } else {
-            try_install_circuit_artifacts()
+            install_circuit_artifacts_with(&self.install, ArtifactsType::Plonk)?
         };
-        let proof = self.prover.wrap_plonk_bn254(outer_proof, &plonk_bn254_aritfacts);
+        let proof = report_stage(progress, ProofStage::Plonk, || {
+            self.prover.wrap_plonk_bn254(outer_proof, &plonk_bn254_aritfacts)
+        });
         if kind == SP1ProofKind::Plonk {
-            return Ok(SP1ProofWithPublicValues {
+            return Ok(recorder.finish(SP1ProofWithPublicValues {
                 proof: SP1Proof::Plonk(proof),
                 stdin,
                 public_values,
                 sp1_version: self.version().to_string(),
-            });
+                metadata,
+            }));
         }
 
         unreachable!()
@@ -1,12 +1,18 @@
 #![allow(unused_variables)]
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    time::{Duration, Instant},
+};
+
 use hashbrown::HashMap;
-use sp1_core_executor::SP1Context;
-use sp1_core_machine::io::SP1Stdin;
+use sp1_core_executor::{ProofStage, SP1Context};
+use sp1_core_machine::io::{SP1PublicValues, SP1Stdin};
 use sp1_stark::{ShardCommitment, ShardOpenedValues, ShardProof};
 
 use crate::{
-    Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey, SP1VerificationError,
-    SP1VerifyingKey,
+    ProofMetadata, Prover, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues, SP1ProvingKey,
+    SP1VerificationError, SP1VerifyingKey,
 };
 use anyhow::Result;
 use p3_baby_bear::BabyBear;
@@ -17,19 +23,187 @@ use sp1_prover::{
     verify::{verify_groth16_bn254_public_inputs, verify_plonk_bn254_public_inputs},
     Groth16Bn254Proof, HashableKey, PlonkBn254Proof, SP1Prover,
 };
+use thiserror::Error;
 
-use super::{ProofOpts, ProverType};
+use super::{proof_metadata, ProofOpts, ProverType};
 
 /// An implementation of [crate::ProverClient] that can generate mock proofs.
 pub struct MockProver {
     pub(crate) prover: SP1Prover,
+    behavior: MockBehavior,
+}
+
+/// A failure the [MockProver] injects into the proofs it generates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MockFailure {
+    /// Fail with [MockProverError::Failed] carrying the given message.
+    Error(String),
+    /// Wait for the given duration, then fail with [MockProverError::Timeout].
+    Timeout(Duration),
+    /// Succeed, but return public values that differ from the ones the program committed to.
+    ///
+    /// Mock Plonk and Groth16 proofs then fail verification. Mock core and compressed proofs
+    /// aren't checked against their public values, so they still verify.
+    InvalidPublicValues,
+}
+
+/// How the [MockProver] behaves, to exercise the error handling of applications without a real
+/// prover.
+///
+/// The default behavior generates every proof successfully and without delay.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MockBehavior {
+    /// The failure injected into every proof, if any.
+    pub failure: Option<MockFailure>,
+    /// The bounds of the random latency added to every proof, if any.
+    pub latency: Option<(Duration, Duration)>,
+}
+
+impl MockBehavior {
+    /// Fails every proof with the given message.
+    pub fn fail_with(mut self, message: impl Into<String>) -> Self {
+        self.failure = Some(MockFailure::Error(message.into()));
+        self
+    }
+
+    /// Fails every proof with a timeout once `after` has elapsed.
+    pub fn timeout_after(mut self, after: Duration) -> Self {
+        self.failure = Some(MockFailure::Timeout(after));
+        self
+    }
+
+    /// Returns invalid public values with every proof.
+    pub fn invalid_public_values(mut self) -> Self {
+        self.failure = Some(MockFailure::InvalidPublicValues);
+        self
+    }
+
+    /// Delays every proof by a duration drawn uniformly from `min..=max`.
+    pub fn latency(mut self, min: Duration, max: Duration) -> Self {
+        self.latency = Some((min, max.max(min)));
+        self
+    }
+
+    /// Draws the latency of a single proof.
+    fn sample_latency(&self) -> Option<Duration> {
+        self.latency.map(|(min, max)| {
+            let mut hasher = RandomState::new().build_hasher();
+            hasher.write_u64(0);
+            let fraction = (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64;
+            min + (max - min).mul_f64(fraction)
+        })
+    }
+}
+
+/// An error injected by the [MockProver] through [MockBehavior].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum MockProverError {
+    #[error("mock proof failed: {0}")]
+    Failed(String),
+    #[error("mock proof timed out after {0:?}")]
+    Timeout(Duration),
 }
 
 impl MockProver {
     /// Creates a new [MockProver].
     pub fn new() -> Self {
+        Self::with_behavior(MockBehavior::default())
+    }
+
+    /// Creates a new [MockProver] that injects latency and failures as set in `behavior`.
+    pub fn with_behavior(behavior: MockBehavior) -> Self {
         let prover = SP1Prover::new();
-        Self { prover }
+        Self { prover, behavior }
+    }
+
+    /// Generates a mock proof of the given kind, without injecting any failure.
+    fn mock_proof<'a>(
+        &'a self,
+        pk: &SP1ProvingKey,
+        stdin: SP1Stdin,
+        context: SP1Context<'a>,
+        kind: SP1ProofKind,
+    ) -> Result<SP1ProofWithPublicValues> {
+        let start = Instant::now();
+        let (public_values, report) = self.prover.execute(&pk.elf, &stdin, context)?;
+        let mut metadata = proof_metadata(self.id(), report.total_instruction_count(), 0);
+        metadata.stages.push((ProofStage::Execute, start.elapsed()));
+        Ok(self.mock_proof_with_public_values(&pk.vk, stdin, public_values, kind, Some(metadata)))
+    }
+
+    /// Generates a mock proof of the given kind committing to the given public values.
+    fn mock_proof_with_public_values(
+        &self,
+        vk: &SP1VerifyingKey,
+        stdin: SP1Stdin,
+        public_values: SP1PublicValues,
+        kind: SP1ProofKind,
+        metadata: Option<ProofMetadata>,
+    ) -> SP1ProofWithPublicValues {
+        match kind {
+            SP1ProofKind::Core => SP1ProofWithPublicValues {
+                proof: SP1Proof::Core(vec![]),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+                metadata,
+            },
+            SP1ProofKind::Compressed => SP1ProofWithPublicValues {
+                proof: SP1Proof::Compressed(ShardProof {
+                    commitment: ShardCommitment {
+                        main_commit: [BabyBear::zero(); 8].into(),
+                        permutation_commit: [BabyBear::zero(); 8].into(),
+                        quotient_commit: [BabyBear::zero(); 8].into(),
+                    },
+                    opened_values: ShardOpenedValues { chips: vec![] },
+                    opening_proof: TwoAdicFriPcsProof {
+                        fri_proof: FriProof {
+                            commit_phase_commits: vec![],
+                            query_proofs: vec![],
+                            final_poly: Default::default(),
+                            pow_witness: BabyBear::zero(),
+                        },
+                        query_openings: vec![],
+                    },
+                    chip_ordering: HashMap::new(),
+                    public_values: vec![],
+                }),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+                metadata,
+            },
+            SP1ProofKind::Plonk => SP1ProofWithPublicValues {
+                proof: SP1Proof::Plonk(PlonkBn254Proof {
+                    public_inputs: [
+                        vk.hash_bn254().as_canonical_biguint().to_string(),
+                        public_values.hash().to_string(),
+                    ],
+                    encoded_proof: "".to_string(),
+                    raw_proof: "".to_string(),
+                    plonk_vkey_hash: [0; 32],
+                }),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+                metadata,
+            },
+            SP1ProofKind::Groth16 => SP1ProofWithPublicValues {
+                proof: SP1Proof::Groth16(Groth16Bn254Proof {
+                    public_inputs: [
+                        vk.hash_bn254().as_canonical_biguint().to_string(),
+                        public_values.hash().to_string(),
+                    ],
+                    encoded_proof: "".to_string(),
+                    raw_proof: "".to_string(),
+                    groth16_vkey_hash: [0; 32],
+                }),
+                stdin,
+                public_values,
+                sp1_version: self.version().to_string(),
+                metadata,
+            },
+        }
     }
 }
 
@@ -54,80 +228,53 @@ impl Prover<DefaultProverComponents> for MockProver {
         context: SP1Context<'a>,
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues> {
-        match kind {
-            SP1ProofKind::Core => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Core(vec![]),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
-            }
-            SP1ProofKind::Compressed => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Compressed(ShardProof {
-                        commitment: ShardCommitment {
-                            main_commit: [BabyBear::zero(); 8].into(),
-                            permutation_commit: [BabyBear::zero(); 8].into(),
-                            quotient_commit: [BabyBear::zero(); 8].into(),
-                        },
-                        opened_values: ShardOpenedValues { chips: vec![] },
-                        opening_proof: TwoAdicFriPcsProof {
-                            fri_proof: FriProof {
-                                commit_phase_commits: vec![],
-                                query_proofs: vec![],
-                                final_poly: Default::default(),
-                                pow_witness: BabyBear::zero(),
-                            },
-                            query_openings: vec![],
-                        },
-                        chip_ordering: HashMap::new(),
-                        public_values: vec![],
-                    }),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
+        if let Some(latency) = self.behavior.sample_latency() {
+            std::thread::sleep(latency);
+        }
+        match &self.behavior.failure {
+            None => self.mock_proof(pk, stdin, context, kind),
+            Some(MockFailure::Error(message)) => {
+                Err(MockProverError::Failed(message.clone()).into())
             }
-            SP1ProofKind::Plonk => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Plonk(PlonkBn254Proof {
-                        public_inputs: [
-                            pk.vk.hash_bn254().as_canonical_biguint().to_string(),
-                            public_values.hash().to_string(),
-                        ],
-                        encoded_proof: "".to_string(),
-                        raw_proof: "".to_string(),
-                        plonk_vkey_hash: [0; 32],
-                    }),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
+            Some(MockFailure::Timeout(after)) => {
+                std::thread::sleep(*after);
+                Err(MockProverError::Timeout(*after).into())
             }
-            SP1ProofKind::Groth16 => {
-                let (public_values, _) = self.prover.execute(&pk.elf, &stdin, context)?;
-                Ok(SP1ProofWithPublicValues {
-                    proof: SP1Proof::Groth16(Groth16Bn254Proof {
-                        public_inputs: [
-                            pk.vk.hash_bn254().as_canonical_biguint().to_string(),
-                            public_values.hash().to_string(),
-                        ],
-                        encoded_proof: "".to_string(),
-                        raw_proof: "".to_string(),
-                        groth16_vkey_hash: [0; 32],
-                    }),
-                    stdin,
-                    public_values,
-                    sp1_version: self.version().to_string(),
-                })
+            Some(MockFailure::InvalidPublicValues) => {
+                let mut proof = self.mock_proof(pk, stdin, context, kind)?;
+                let mut bytes = proof.public_values.to_vec();
+                match bytes.first_mut() {
+                    Some(byte) => *byte ^= 0xff,
+                    None => bytes.push(0xff),
+                }
+                proof.public_values = SP1PublicValues::from(&bytes);
+                Ok(proof)
             }
         }
     }
 
+    fn wrap(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
+        kind: SP1ProofKind,
+        opts: ProofOpts,
+    ) -> Result<SP1ProofWithPublicValues> {
+        if let SP1Proof::Plonk(_) | SP1Proof::Groth16(_) = proof.proof {
+            return Err(anyhow::anyhow!("only core and compressed proofs can be wrapped"));
+        }
+        if kind == SP1ProofKind::Core {
+            return Err(anyhow::anyhow!("a proof can't be wrapped into a core proof"));
+        }
+        Ok(self.mock_proof_with_public_values(
+            vk,
+            proof.stdin,
+            proof.public_values,
+            kind,
+            proof.metadata,
+        ))
+    }
+
     fn verify(
         &self,
         bundle: &SP1ProofWithPublicValues,
@@ -6,22 +6,36 @@ mod mock;
 pub use cpu::CpuProver;
 #[cfg(feature = "cuda")]
 pub use cuda::CudaProver;
-pub use mock::MockProver;
+pub use mock::{MockBehavior, MockFailure, MockProver, MockProverError};
 
 use anyhow::Result;
-use sp1_core_executor::SP1Context;
-use sp1_core_machine::{io::SP1Stdin, SP1_CIRCUIT_VERSION};
+use sp1_core_executor::{
+    CancellationToken, ExecutionError, ProgressCallback, ProofEvent, ProofStage, SP1Context,
+};
+use sp1_core_machine::{io::SP1Stdin, utils::SP1CoreProverError, SP1_CIRCUIT_VERSION};
 use sp1_prover::{
     components::SP1ProverComponents, CoreSC, InnerSC, SP1CoreProofData, SP1Prover, SP1ProvingKey,
     SP1ReduceProof, SP1VerifyingKey,
 };
 use sp1_stark::{MachineVerificationError, SP1ProverOpts};
-use std::time::Duration;
+use std::{
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
 use strum_macros::EnumString;
 use thiserror::Error;
 
 use crate::{
-    install::try_install_circuit_artifacts, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues,
+    install::{
+        install_circuit_artifacts_with, is_circuit_version, ArtifactsType, InstallConfig,
+        InstallError,
+    },
+    memory::{MemoryBudget, MemoryBudgetError},
+    ProofMetadata, SP1Proof, SP1ProofKind, SP1ProofWithPublicValues,
 };
 
 /// The type of prover.
@@ -31,6 +45,45 @@ pub enum ProverType {
     Cuda,
     Mock,
     Network,
+    Distributed,
+    Hybrid,
+}
+
+/// The prover a [crate::ProverClient] uses under the hood.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum ProverMode {
+    /// Proves locally with [CpuProver], or `CudaProver` if the `cuda` feature is enabled.
+    #[default]
+    Local,
+    /// Generates mock proofs with [MockProver], for testing and development.
+    Mock,
+    /// Outsources proof generation to the prover network.
+    Network,
+}
+
+impl ProverMode {
+    /// Reads the mode from the `SP1_PROVER` environment variable, defaulting to
+    /// [ProverMode::Local] when it is unset.
+    pub fn from_env() -> Self {
+        match std::env::var("SP1_PROVER") {
+            Ok(mode) => mode.parse().unwrap_or_else(|_| {
+                panic!(
+                    "invalid value for SP1_PROVER enviroment variable: expected 'local', 'mock', or 'network'"
+                )
+            }),
+            Err(_) => ProverMode::default(),
+        }
+    }
+}
+
+/// The GPU a [crate::ProverClient] proves on, see [crate::ProverClientBuilder::gpu].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GpuOpts {
+    /// The index of the CUDA device to prove on, as listed by `nvidia-smi`.
+    pub device_id: usize,
+    /// The maximum GPU memory the prover may use, in bytes. Unlimited if `None`.
+    pub memory_limit: Option<u64>,
 }
 
 /// Options to configure proof generation.
@@ -40,6 +93,181 @@ pub struct ProofOpts {
     pub sp1_prover_opts: SP1ProverOpts,
     /// Optional timeout duration for proof generation.
     pub timeout: Option<Duration>,
+    /// The directory the progress of the proof is persisted to, see [crate::checkpoint].
+    pub checkpoint_dir: Option<PathBuf>,
+    /// The memory budget [Self::sp1_prover_opts] are fitted to by local provers, see
+    /// [MemoryBudget].
+    pub memory_budget: Option<MemoryBudget>,
+}
+
+impl ProofOpts {
+    /// The options of the SP1 prover, fitted to the memory budget if one is set.
+    pub(crate) fn fitted_prover_opts(&self) -> Result<SP1ProverOpts, MemoryBudgetError> {
+        match &self.memory_budget {
+            Some(budget) => budget.fit(self.sp1_prover_opts),
+            None => Ok(self.sp1_prover_opts),
+        }
+    }
+}
+
+/// Fails with [ExecutionError::Cancelled] if the proof was cancelled through `token`. Checked
+/// between the stages of proof generation.
+pub(crate) fn ensure_not_cancelled(token: Option<&CancellationToken>) -> Result<()> {
+    if token.is_some_and(CancellationToken::is_cancelled) {
+        return Err(ExecutionError::Cancelled.into());
+    }
+    Ok(())
+}
+
+/// The error returned when a local proof isn't done within its [ProofOpts::timeout].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("proving timed out after {timeout:?}, having completed the stages {completed:?}")]
+pub struct ProofTimedOut {
+    /// The timeout that elapsed.
+    pub timeout: Duration,
+    /// The stages of proof generation that completed before the timeout, in order.
+    pub completed: Vec<ProofStage>,
+}
+
+/// Cancels a local proof once its timeout elapses.
+///
+/// Execution stops at the next shard boundary and the other stages are checked for cancellation
+/// before they start, so the proof is abandoned cleanly, without leaving a half-written
+/// checkpoint behind, rather than at the exact deadline.
+pub(crate) struct Deadline {
+    timeout: Duration,
+    token: CancellationToken,
+    completed: Arc<Mutex<Vec<ProofStage>>>,
+    expired: Arc<AtomicBool>,
+    done: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl Deadline {
+    /// How often the cancellation token of the caller is polled while waiting for the deadline.
+    const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Starts the timeout of the proof run with `context`, cancelling it through its token.
+    ///
+    /// The token of the caller, if any, keeps cancelling the proof: it is polled and forwarded
+    /// rather than cancelled on timeout, so that the caller's other work isn't cancelled too.
+    pub(crate) fn start(timeout: Duration, context: &mut SP1Context<'_>) -> Self {
+        let token = CancellationToken::new();
+        let parent = context.cancellation_token.replace(token.clone());
+        let completed = Arc::new(Mutex::new(Vec::new()));
+        let progress = context.progress.take();
+        let stages = completed.clone();
+        context.progress = Some(Arc::new(move |event: &ProofEvent| {
+            if let ProofEvent::StageFinished { stage, .. } = event {
+                stages.lock().unwrap().push(*stage);
+            }
+            if let Some(progress) = &progress {
+                progress(event);
+            }
+        }));
+
+        let expired = Arc::new(AtomicBool::new(false));
+        let done = Arc::new((Mutex::new(false), Condvar::new()));
+        let (watchdog_token, watchdog_expired, watchdog_done) =
+            (token.clone(), expired.clone(), done.clone());
+        let deadline = Instant::now() + timeout;
+        std::thread::spawn(move || {
+            let (lock, condvar) = &*watchdog_done;
+            let mut done = lock.lock().unwrap();
+            while !*done {
+                let now = Instant::now();
+                if now >= deadline {
+                    watchdog_expired.store(true, Ordering::Relaxed);
+                    watchdog_token.cancel();
+                    return;
+                }
+                if parent.as_ref().is_some_and(CancellationToken::is_cancelled) {
+                    watchdog_token.cancel();
+                    return;
+                }
+                let wait = (deadline - now).min(Self::POLL_INTERVAL);
+                done = condvar.wait_timeout(done, wait).unwrap().0;
+            }
+        });
+        Self { timeout, token, completed, expired, done }
+    }
+
+    /// Turns the failure of a proof cancelled by the deadline into a [ProofTimedOut].
+    pub(crate) fn finish<T>(self, result: Result<T>) -> Result<T> {
+        match result {
+            Err(_) if self.expired.load(Ordering::Relaxed) && self.token.is_cancelled() => {
+                Err(ProofTimedOut {
+                    timeout: self.timeout,
+                    completed: self.completed.lock().unwrap().clone(),
+                }
+                .into())
+            }
+            result => result,
+        }
+    }
+}
+
+impl Drop for Deadline {
+    fn drop(&mut self) {
+        let (lock, condvar) = &*self.done;
+        *lock.lock().unwrap() = true;
+        condvar.notify_one();
+    }
+}
+
+/// Records the time each stage of a proof takes, for its [ProofMetadata].
+#[derive(Clone, Default)]
+pub(crate) struct StageRecorder {
+    stages: Arc<Mutex<Vec<(ProofStage, Duration)>>>,
+}
+
+impl StageRecorder {
+    /// A callback recording the stages that finish, and forwarding every event to `progress`.
+    pub(crate) fn callback<'a>(
+        &self,
+        progress: Option<ProgressCallback<'a>>,
+    ) -> ProgressCallback<'a> {
+        let stages = self.stages.clone();
+        Arc::new(move |event| {
+            if let ProofEvent::StageFinished { stage, elapsed } = event {
+                stages.lock().unwrap().push((*stage, *elapsed));
+            }
+            if let Some(progress) = &progress {
+                progress(event);
+            }
+        })
+    }
+
+    /// Appends the stages recorded so far to the metadata of `proof`, if it has any.
+    pub(crate) fn finish(&self, mut proof: SP1ProofWithPublicValues) -> SP1ProofWithPublicValues {
+        if let Some(metadata) = &mut proof.metadata {
+            metadata.stages.extend(self.stages.lock().unwrap().drain(..));
+        }
+        proof
+    }
+}
+
+/// The metadata of a proof of an execution of `cycles` cycles split into `shards` shards, before
+/// any stage is recorded.
+pub(crate) fn proof_metadata(prover: ProverType, cycles: u64, shards: usize) -> ProofMetadata {
+    ProofMetadata {
+        cycles,
+        shards,
+        stages: Vec::new(),
+        prover: format!("{:?}", prover),
+        prover_version: env!("CARGO_PKG_VERSION").to_string(),
+        network: None,
+    }
+}
+
+/// Converts an error of the core prover, unwrapping cancellations so that every cancelled proof
+/// fails with [ExecutionError::Cancelled].
+pub(crate) fn core_prover_error(err: SP1CoreProverError) -> anyhow::Error {
+    match err {
+        SP1CoreProverError::ExecutionError(ExecutionError::Cancelled) => {
+            ExecutionError::Cancelled.into()
+        }
+        err => err.into(),
+    }
 }
 
 #[derive(Error, Debug)]
@@ -54,6 +282,8 @@ pub enum SP1VerificationError {
     Plonk(anyhow::Error),
     #[error("Groth16 verification error: {0}")]
     Groth16(anyhow::Error),
+    #[error("Failed to install circuit artifacts: {0}")]
+    Install(InstallError),
 }
 
 /// An implementation of [crate::ProverClient].
@@ -66,6 +296,18 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         SP1_CIRCUIT_VERSION
     }
 
+    /// Where the circuit artifacts are installed, looked up and downloaded from.
+    fn install_config(&self) -> InstallConfig {
+        InstallConfig::from_env()
+    }
+
+    /// The [crate::NetworkProver] behind this prover, if proofs are requested from the prover
+    /// network. Lets async callers await the network requests directly.
+    #[cfg(feature = "network")]
+    fn network_prover(&self) -> Option<&crate::NetworkProver> {
+        None
+    }
+
     fn setup(&self, elf: &[u8]) -> (SP1ProvingKey, SP1VerifyingKey);
 
     /// Prove the execution of a RISCV ELF with the given inputs, according to the given proof mode.
@@ -78,6 +320,23 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
         kind: SP1ProofKind,
     ) -> Result<SP1ProofWithPublicValues>;
 
+    /// Turn an existing core or compressed proof of the program with verifying key `vk` into a
+    /// proof of the given kind, running only the remaining stages of proof generation instead of
+    /// proving the execution again.
+    fn wrap(
+        &self,
+        vk: &SP1VerifyingKey,
+        proof: SP1ProofWithPublicValues,
+        kind: SP1ProofKind,
+        opts: ProofOpts,
+    ) -> Result<SP1ProofWithPublicValues> {
+        let _ = (vk, proof, kind, opts);
+        Err(anyhow::anyhow!(
+            "wrapping existing proofs is unsupported by the {:?} prover",
+            self.id()
+        ))
+    }
+
     /// Verify that an SP1 proof is valid given its vkey and metadata.
     /// For Plonk proofs, verifies that the public inputs of the PlonkBn254 proof match
     /// the hash of the VK and the committed public values of the SP1ProofWithPublicValues.
@@ -98,32 +357,111 @@ pub trait Prover<C: SP1ProverComponents>: Send + Sync {
                 .sp1_prover()
                 .verify_compressed(&SP1ReduceProof { proof: proof.clone() }, vkey)
                 .map_err(SP1VerificationError::Recursion),
-            SP1Proof::Plonk(proof) => self
-                .sp1_prover()
-                .verify_plonk_bn254(
-                    proof,
-                    vkey,
-                    &bundle.public_values,
-                    &if sp1_prover::build::sp1_dev_mode() {
-                        sp1_prover::build::plonk_bn254_artifacts_dev_dir()
-                    } else {
-                        try_install_circuit_artifacts()
-                    },
-                )
-                .map_err(SP1VerificationError::Plonk),
-            SP1Proof::Groth16(proof) => self
-                .sp1_prover()
-                .verify_groth16_bn254(
-                    proof,
-                    vkey,
-                    &bundle.public_values,
-                    &if sp1_prover::build::sp1_dev_mode() {
-                        sp1_prover::build::groth16_bn254_artifacts_dev_dir()
-                    } else {
-                        try_install_circuit_artifacts()
-                    },
-                )
-                .map_err(SP1VerificationError::Groth16),
+            SP1Proof::Plonk(proof) => {
+                let artifacts_dir = if sp1_prover::build::sp1_dev_mode() {
+                    sp1_prover::build::plonk_bn254_artifacts_dev_dir()
+                } else {
+                    install_circuit_artifacts_with(&self.install_config(), ArtifactsType::Plonk)
+                        .map_err(SP1VerificationError::Install)?
+                };
+                self.sp1_prover()
+                    .verify_plonk_bn254(proof, vkey, &bundle.public_values, &artifacts_dir)
+                    .map_err(SP1VerificationError::Plonk)
+            }
+            SP1Proof::Groth16(proof) => {
+                let artifacts_dir = if sp1_prover::build::sp1_dev_mode() {
+                    sp1_prover::build::groth16_bn254_artifacts_dev_dir()
+                } else {
+                    install_circuit_artifacts_with(&self.install_config(), ArtifactsType::Groth16)
+                        .map_err(SP1VerificationError::Install)?
+                };
+                self.sp1_prover()
+                    .verify_groth16_bn254(proof, vkey, &bundle.public_values, &artifacts_dir)
+                    .map_err(SP1VerificationError::Groth16)
+            }
+        }
+    }
+
+    /// Verify an SP1 proof generated by any version of SP1, dispatching on the version the
+    /// proof is tagged with.
+    ///
+    /// Proofs of the current version are verified with [Self::verify]. Plonk and Groth16 proofs
+    /// of previous versions are verified against the circuit artifacts of their version, which
+    /// are installed next to the current ones on first use. Core and compressed proofs can only
+    /// be verified by the version of SP1 that generated them.
+    ///
+    /// The verifying key of a program changes with the circuit version, so the proofs of a
+    /// previous version are checked against `vkey` as that version produced it, e.g. the key it
+    /// saved next to the proof, not the one [Self::setup] returns now. The artifacts of previous
+    /// versions predate the checksum manifests, so they are installed without requiring one; a
+    /// manifest that is published is still checked.
+    fn verify_any_version(
+        &self,
+        bundle: &SP1ProofWithPublicValues,
+        vkey: &SP1VerifyingKey,
+    ) -> Result<(), SP1VerificationError> {
+        if bundle.sp1_version == self.version() {
+            return self.verify(bundle, vkey);
+        }
+        // The version is read from the proof, so check it before using it in paths and URLs.
+        if !is_circuit_version(&bundle.sp1_version) {
+            return Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone()));
+        }
+        let mut install = self.install_config().with_version(&bundle.sp1_version);
+        install.allow_unverified = true;
+        match &bundle.proof {
+            SP1Proof::Plonk(proof) => {
+                let artifacts_dir = install_circuit_artifacts_with(&install, ArtifactsType::Plonk)
+                    .map_err(SP1VerificationError::Install)?;
+                self.sp1_prover()
+                    .verify_plonk_bn254(proof, vkey, &bundle.public_values, &artifacts_dir)
+                    .map_err(SP1VerificationError::Plonk)
+            }
+            SP1Proof::Groth16(proof) => {
+                let artifacts_dir =
+                    install_circuit_artifacts_with(&install, ArtifactsType::Groth16)
+                        .map_err(SP1VerificationError::Install)?;
+                self.sp1_prover()
+                    .verify_groth16_bn254(proof, vkey, &bundle.public_values, &artifacts_dir)
+                    .map_err(SP1VerificationError::Groth16)
+            }
+            SP1Proof::Core(_) | SP1Proof::Compressed(_) => {
+                Err(SP1VerificationError::VersionMismatch(bundle.sp1_version.clone()))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deadline() {
+        let mut context = SP1Context::default();
+        let deadline = Deadline::start(Duration::from_millis(50), &mut context);
+        let progress = context.progress.clone().unwrap();
+        progress(&ProofEvent::StageFinished { stage: ProofStage::Core, elapsed: Duration::ZERO });
+        std::thread::sleep(Duration::from_millis(200));
+        assert!(context.cancellation_token.as_ref().unwrap().is_cancelled());
+        let err = deadline.finish::<()>(Err(ExecutionError::Cancelled.into())).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ProofTimedOut>(),
+            Some(&ProofTimedOut {
+                timeout: Duration::from_millis(50),
+                completed: vec![ProofStage::Core]
+            })
+        );
+
+        // Cancelling through the caller's token isn't reported as a timeout.
+        let parent = CancellationToken::new();
+        let mut context =
+            SP1Context { cancellation_token: Some(parent.clone()), ..Default::default() };
+        let deadline = Deadline::start(Duration::from_secs(60), &mut context);
+        parent.cancel();
+        std::thread::sleep(Duration::from_millis(300));
+        assert!(context.cancellation_token.as_ref().unwrap().is_cancelled());
+        let err = deadline.finish::<()>(Err(ExecutionError::Cancelled.into())).unwrap_err();
+        assert!(err.downcast_ref::<ProofTimedOut>().is_none());
+    }
+}
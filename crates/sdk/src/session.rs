@@ -0,0 +1,120 @@
+//! # Proving Sessions
+//!
+//! A [ProvingSession] keeps everything needed to prove a program resident in memory across many
+//! proofs, for long-lived proving services.
+
+use std::path::PathBuf;
+
+use sp1_core_machine::io::SP1Stdin;
+use sp1_prover::{Groth16Bn254Prover, PlonkBn254Prover, SP1ProvingKey, SP1VerifyingKey};
+
+use crate::{
+    action,
+    install::{install_circuit_artifacts_with, ArtifactsType},
+    ProverClient, SP1SdkError,
+};
+
+/// A session proving a single program many times with a [ProverClient].
+///
+/// The session holds the proving key of the program, and optionally the Plonk and Groth16
+/// circuits loaded with [Self::with_artifacts], so they aren't loaded again for every proof. The
+/// recursion programs are held by the client's prover for as long as the client lives.
+///
+/// The circuits take several gigabytes of memory, so release them with [Self::close] once the
+/// session is no longer needed. They are also released when the session is dropped.
+///
+/// ### Examples
+/// ```no_run
+/// use sp1_sdk::{install::ArtifactsType, ProverClient, SP1Stdin};
+///
+/// let elf = include_bytes!("../../../examples/fibonacci/program/elf/riscv32im-succinct-zkvm-elf");
+/// let client = ProverClient::new();
+/// let session = client.session(elf).with_artifacts(ArtifactsType::Groth16).unwrap();
+/// for n in 0..10usize {
+///     let mut stdin = SP1Stdin::new();
+///     stdin.write(&n);
+///     let proof = session.prove(stdin).groth16().run().unwrap();
+///     client.verify(&proof, session.vk()).unwrap();
+/// }
+/// session.close();
+/// ```
+pub struct ProvingSession<'a> {
+    client: &'a ProverClient,
+    pk: SP1ProvingKey,
+    vk: SP1VerifyingKey,
+    resident: Vec<(ArtifactsType, PathBuf)>,
+}
+
+impl<'a> ProvingSession<'a> {
+    /// Starts a session proving `elf` with `client`, running the setup of the program once.
+    ///
+    /// Prefer using [ProverClient::session].
+    pub fn new(client: &'a ProverClient, elf: &[u8]) -> Self {
+        let (pk, vk) = client.setup(elf);
+        Self { client, pk, vk, resident: Vec::new() }
+    }
+
+    /// Installs the Plonk or Groth16 circuit artifacts if needed, and keeps the circuit in
+    /// memory until the session is closed.
+    ///
+    /// Circuits are only kept in memory by the native Gnark prover, enabled with the
+    /// `native-gnark` feature. The docker prover loads them in every proof.
+    pub fn with_artifacts(mut self, artifacts_type: ArtifactsType) -> Result<Self, SP1SdkError> {
+        if artifacts_type == ArtifactsType::All {
+            return self
+                .with_artifacts(ArtifactsType::Plonk)?
+                .with_artifacts(ArtifactsType::Groth16);
+        }
+        if self.resident.iter().any(|(resident, _)| *resident == artifacts_type) {
+            return Ok(self);
+        }
+        let build_dir = if sp1_prover::build::sp1_dev_mode() {
+            match artifacts_type {
+                ArtifactsType::Plonk => sp1_prover::build::plonk_bn254_artifacts_dev_dir(),
+                ArtifactsType::Groth16 => sp1_prover::build::groth16_bn254_artifacts_dev_dir(),
+                ArtifactsType::All => unreachable!(),
+            }
+        } else {
+            install_circuit_artifacts_with(&self.client.prover.install_config(), artifacts_type)?
+        };
+        match artifacts_type {
+            ArtifactsType::Plonk => PlonkBn254Prover::load(&build_dir),
+            ArtifactsType::Groth16 => Groth16Bn254Prover::load(&build_dir),
+            ArtifactsType::All => unreachable!(),
+        }
+        self.resident.push((artifacts_type, build_dir));
+        Ok(self)
+    }
+
+    /// The proving key of the program.
+    pub fn pk(&self) -> &SP1ProvingKey {
+        &self.pk
+    }
+
+    /// The verifying key of the program.
+    pub fn vk(&self) -> &SP1VerifyingKey {
+        &self.vk
+    }
+
+    /// Prepare to prove the execution of the program with the given input, see
+    /// [ProverClient::prove].
+    pub fn prove(&self, stdin: SP1Stdin) -> action::Prove<'_> {
+        self.client.prove(&self.pk, stdin)
+    }
+
+    /// Ends the session, releasing the proving key and the circuits loaded into memory.
+    pub fn close(self) {}
+}
+
+impl Drop for ProvingSession<'_> {
+    fn drop(&mut self) {
+        for (artifacts_type, build_dir) in self.resident.drain(..) {
+            match artifacts_type {
+                ArtifactsType::Plonk => PlonkBn254Prover::unload(&build_dir),
+                ArtifactsType::Groth16 | ArtifactsType::All => {
+                    Groth16Bn254Prover::unload(&build_dir)
+                }
+            }
+        }
+    }
+}
@@ -97,6 +97,12 @@ pub struct SplitOpts {
     pub sha_extend: usize,
     /// The threshold for sha compress events.
     pub sha_compress: usize,
+    /// The threshold for sha512 extend events.
+    pub sha512_extend: usize,
+    /// The threshold for sha512 compress events.
+    pub sha512_compress: usize,
+    /// The threshold for ripemd160 compress events.
+    pub ripemd160_compress: usize,
     /// The threshold for memory events.
     pub memory: usize,
 }
@@ -110,6 +116,9 @@ impl SplitOpts {
             keccak: deferred_shift_threshold / 24,
             sha_extend: deferred_shift_threshold / 48,
             sha_compress: deferred_shift_threshold / 80,
+            sha512_extend: deferred_shift_threshold / 64,
+            sha512_compress: deferred_shift_threshold / 80,
+            ripemd160_compress: deferred_shift_threshold / 80,
             memory: deferred_shift_threshold * 4,
         }
     }
@@ -0,0 +1,6 @@
+fn main() {
+    // The memory layout of the program is read from these at compile time, see `src/layout.rs`.
+    println!("cargo:rerun-if-env-changed=SP1_MAX_MEMORY");
+    println!("cargo:rerun-if-env-changed=SP1_STACK_TOP");
+    println!("cargo:rerun-if-env-changed=SP1_HEAP_START");
+}
@@ -0,0 +1,36 @@
+//! The memory layout of the program, set through the build options of `sp1-build`.
+//!
+//! The values are read from the `SP1_MAX_MEMORY`, `SP1_STACK_TOP` and `SP1_HEAP_START`
+//! environment variables when the program is compiled, and take their defaults when unset.
+
+/// The end of the memory of the program, past which the heap can't grow.
+///
+/// Memory addresses must be lower than the BabyBear prime, so it is at most `0x78000000`, the
+/// default. The executor must be given the same value through `SP1Context::max_memory`.
+pub const MAX_MEMORY: usize = parse(option_env!("SP1_MAX_MEMORY"), 0x7800_0000);
+
+/// The top of the stack, which grows down towards the registers.
+pub const STACK_TOP: u32 = parse(option_env!("SP1_STACK_TOP"), 0x0020_0400) as u32;
+
+/// The lowest address of the heap, which starts right after the static data of the program if
+/// that is higher.
+pub const HEAP_START: usize = parse(option_env!("SP1_HEAP_START"), 0);
+
+const _: () = assert!(MAX_MEMORY <= 0x7800_0000, "SP1_MAX_MEMORY exceeds 0x78000000");
+const _: () = assert!(HEAP_START < MAX_MEMORY, "SP1_HEAP_START is past SP1_MAX_MEMORY");
+
+/// Parses the decimal `value` of a layout variable, or returns `default` if it is unset.
+const fn parse(value: Option<&str>, default: usize) -> usize {
+    let Some(value) = value else {
+        return default;
+    };
+    let bytes = value.as_bytes();
+    let mut result = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        assert!(bytes[i].is_ascii_digit(), "the memory layout variables must be decimal");
+        result = result * 10 + (bytes[i] - b'0') as usize;
+        i += 1;
+    }
+    result
+}
@@ -1,6 +1,8 @@
 extern crate alloc;
 
 pub mod heap;
+pub mod layout;
+pub mod perf;
 pub mod syscalls;
 
 #[cfg(feature = "lib")]
@@ -22,7 +24,7 @@ pub const POSEIDON_NUM_WORDS: usize = 8;
 
 #[cfg(target_os = "zkvm")]
 mod zkvm {
-    use crate::syscalls::syscall_halt;
+    use crate::syscalls::{install_panic_hook, syscall_halt};
 
     use cfg_if::cfg_if;
     use sha2::{Digest, Sha256};
@@ -43,6 +45,7 @@ mod zkvm {
     unsafe extern "C" fn __start() {
         {
             PUBLIC_VALUES_HASHER = Some(Sha256::new());
+            install_panic_hook();
             #[cfg(feature = "verify")]
             {
                 DEFERRED_PROOFS_DIGEST = Some([BabyBear::zero(); 8]);
@@ -57,7 +60,7 @@ mod zkvm {
         syscall_halt(0);
     }
 
-    static STACK_TOP: u32 = 0x0020_0400;
+    static STACK_TOP: u32 = crate::layout::STACK_TOP;
 
     core::arch::global_asm!(include_str!("memset.s"));
     core::arch::global_asm!(include_str!("memcpy.s"));
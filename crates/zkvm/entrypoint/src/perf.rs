@@ -0,0 +1,28 @@
+//! Measurements of the cycles used by the program, without host-side cycle trackers.
+//!
+//! The counts aren't constrained by the proof, so they may only be printed or otherwise used to
+//! benchmark the program, and must not affect its public values.
+
+use crate::syscalls::syscall_cycle_count;
+
+/// Returns the low 32 bits of the number of cycles executed so far.
+#[must_use]
+pub fn cycle_count() -> u32 {
+    syscall_cycle_count()
+}
+
+/// Runs `f` and returns its result and the number of cycles it used.
+///
+/// The count includes the few cycles it takes to read the count back from the host after `f`
+/// starts being measured.
+///
+/// ```ignore
+/// let (hash, cycles) = sp1_zkvm::perf::measure(|| sha256(&input));
+/// println!("sha256 used {cycles} cycles");
+/// ```
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, u32) {
+    let start = cycle_count();
+    let result = f();
+    let cycles = cycle_count().wrapping_sub(start);
+    (result, cycles)
+}
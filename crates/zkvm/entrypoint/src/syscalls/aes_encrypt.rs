@@ -0,0 +1,54 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Encrypts the given block in place with AES-128, using the given expanded round keys.
+///
+/// The round keys are the 44 words of the AES key expansion, the bytes of each round key in the
+/// order of the block.
+///
+/// ### Safety
+///
+/// The caller must ensure that `block` and `round_keys` are valid pointers to data that is aligned
+/// along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_aes128_encrypt(block: *mut [u32; 4], round_keys: *const [u32; 44]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::AES128_ENCRYPT,
+            in("a0") block,
+            in("a1") round_keys,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Encrypts the given block in place with AES-256, using the given expanded round keys.
+///
+/// The round keys are the 60 words of the AES key expansion, the bytes of each round key in the
+/// order of the block.
+///
+/// ### Safety
+///
+/// The caller must ensure that `block` and `round_keys` are valid pointers to data that is aligned
+/// along a four byte boundary.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_aes256_encrypt(block: *mut [u32; 4], round_keys: *const [u32; 60]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::AES256_ENCRYPT,
+            in("a0") block,
+            in("a1") round_keys,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
@@ -12,8 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-// Memory addresses must be lower than BabyBear prime.
-const MAX_MEMORY: usize = 0x78000000;
+use crate::layout::{HEAP_START, MAX_MEMORY};
 
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
@@ -31,7 +30,7 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     let mut heap_pos = unsafe { HEAP_POS };
 
     if heap_pos == 0 {
-        heap_pos = unsafe { (&_end) as *const u8 as usize };
+        heap_pos = unsafe { (&_end) as *const u8 as usize }.max(HEAP_START);
     }
 
     let offset = heap_pos & (align - 1);
@@ -43,7 +42,7 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     let (heap_pos, overflowed) = heap_pos.overflowing_add(bytes);
 
     if overflowed || MAX_MEMORY < heap_pos {
-        panic!("Memory limit exceeded (0x78000000)");
+        panic!("Memory limit exceeded (0x{MAX_MEMORY:08x})");
     }
 
     unsafe { HEAP_POS = heap_pos };
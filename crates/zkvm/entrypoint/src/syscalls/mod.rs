@@ -1,4 +1,6 @@
 mod bigint;
+mod aes_encrypt;
+mod blake3_compress;
 mod bls12381;
 mod bn254;
 mod ed25519;
@@ -7,16 +9,24 @@ mod halt;
 mod io;
 mod keccak_permute;
 mod memory;
+mod poseidon2;
+mod ripemd160_compress;
 mod secp256k1;
+mod secp256r1;
 mod sha_compress;
 mod sha_extend;
+mod sha512_compress;
+mod sha512_extend;
 mod sys;
 mod uint256_mul;
+mod uint_mod;
 mod unconstrained;
 #[cfg(feature = "verify")]
 mod verify;
 
 pub use bigint::*;
+pub use aes_encrypt::*;
+pub use blake3_compress::*;
 pub use bls12381::*;
 pub use bn254::*;
 pub use ed25519::*;
@@ -25,11 +35,17 @@ pub use halt::*;
 pub use io::*;
 pub use keccak_permute::*;
 pub use memory::*;
+pub use poseidon2::*;
+pub use ripemd160_compress::*;
 pub use secp256k1::*;
+pub use secp256r1::*;
 pub use sha_compress::*;
 pub use sha_extend::*;
+pub use sha512_compress::*;
+pub use sha512_extend::*;
 pub use sys::*;
 pub use uint256_mul::*;
+pub use uint_mod::*;
 pub use unconstrained::*;
 #[cfg(feature = "verify")]
 pub use verify::*;
@@ -141,3 +157,46 @@ pub const BN254_FP2_SUB: u32 = 0x00_01_01_2A;
 
 /// Executes the `BN254_FP2_MUL` precompile.
 pub const BN254_FP2_MUL: u32 = 0x00_01_01_2B;
+
+/// Executes the `SECP256R1_ADD` precompile.
+pub const SECP256R1_ADD: u32 = 0x00_01_01_2C;
+
+/// Executes the `SECP256R1_DOUBLE` precompile.
+pub const SECP256R1_DOUBLE: u32 = 0x00_00_01_2D;
+
+/// Executes the `SECP256R1_DECOMPRESS` precompile.
+pub const SECP256R1_DECOMPRESS: u32 = 0x00_00_01_2E;
+
+/// Executes the `POSEIDON2_PERMUTE` precompile.
+pub const POSEIDON2_PERMUTE: u32 = 0x00_01_01_2F;
+
+/// Executes the `BLAKE3_COMPRESS` precompile.
+pub const BLAKE3_COMPRESS: u32 = 0x00_01_01_30;
+
+/// Executes the `SHA512_EXTEND` precompile.
+pub const SHA512_EXTEND: u32 = 0x00_40_01_31;
+
+/// Executes the `SHA512_COMPRESS` precompile.
+#[allow(clippy::mistyped_literal_suffixes)]
+pub const SHA512_COMPRESS: u32 = 0x00_01_01_32;
+
+/// Executes the `AES128_ENCRYPT` precompile.
+pub const AES128_ENCRYPT: u32 = 0x00_01_01_33;
+
+/// Executes the `AES256_ENCRYPT` precompile.
+pub const AES256_ENCRYPT: u32 = 0x00_01_01_34;
+
+/// Executes the `RIPEMD160_COMPRESS` precompile.
+pub const RIPEMD160_COMPRESS: u32 = 0x00_01_01_35;
+
+/// Executes the `UINT320_ADD` precompile.
+pub const UINT320_ADD: u32 = 0x00_01_01_36;
+
+/// Executes the `UINT320_MUL` precompile.
+pub const UINT320_MUL: u32 = 0x00_01_01_37;
+
+/// Executes the `UINT384_ADD` precompile.
+pub const UINT384_ADD: u32 = 0x00_01_01_38;
+
+/// Executes the `UINT384_MUL` precompile.
+pub const UINT384_MUL: u32 = 0x00_01_01_39;
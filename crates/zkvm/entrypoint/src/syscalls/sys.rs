@@ -1,9 +1,26 @@
-use std::sync::Mutex;
+use std::{alloc::Layout, sync::Mutex};
 
 use lazy_static::lazy_static;
 use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::syscalls::{syscall_halt, syscall_write};
+use crate::syscalls::{
+    sys_alloc_aligned, syscall_halt, syscall_hint_len, syscall_hint_read, syscall_write,
+};
+
+/// The file descriptor through which the arguments of the program are requested from the host.
+pub const FD_ARGS: u32 = 6;
+
+/// The file descriptor through which the environment variables of the program are requested from
+/// the host.
+pub const FD_GETENV: u32 = 7;
+
+/// The file descriptor through which the location and the message of a panic are reported to the
+/// host.
+pub const FD_PANIC: u32 = 8;
+
+/// The file descriptor through which the number of cycles executed so far is requested from the
+/// host.
+pub const FD_CYCLE_COUNT: u32 = 10;
 
 /// The random number generator seed for the zkVM.
 ///
@@ -13,6 +30,45 @@ const PRNG_SEED: u64 = 0x123456789abcdef0;
 lazy_static! {
     /// A lazy static to generate a global random number generator.
     static ref RNG: Mutex<StdRng> = Mutex::new(StdRng::seed_from_u64(PRNG_SEED));
+
+    /// The arguments of the program, requested from the host on first use.
+    static ref ARGS: Vec<Vec<u8>> = {
+        syscall_write(FD_ARGS, core::ptr::null(), 0);
+        let argc = u32::from_le_bytes(read_hint().try_into().unwrap());
+        (0..argc).map(|_| read_hint()).collect()
+    };
+
+    /// The names and the values of the environment variables of the program, requested from the
+    /// host on first use, so that they can be read in unconstrained blocks afterwards.
+    static ref ENV: Vec<(Vec<u8>, Vec<u8>)> = {
+        syscall_write(FD_GETENV, core::ptr::null(), 0);
+        let count = u32::from_le_bytes(read_hint().try_into().unwrap());
+        (0..count).map(|_| (read_hint(), read_hint())).collect()
+    };
+}
+
+/// Reads the next element of the hint stream into a fresh, word aligned buffer.
+fn read_hint() -> Vec<u8> {
+    let len = syscall_hint_len();
+    let capacity = (len + 3) / 4 * 4;
+    let layout = Layout::from_size_align(capacity, 4).expect("hint is too large");
+    // SAFETY: The allocator doesn't dealloc, so the buffer is fresh, and it has `capacity` bytes.
+    unsafe {
+        let ptr = std::alloc::alloc(layout);
+        syscall_hint_read(ptr, len);
+        Vec::from_raw_parts(ptr, len, capacity)
+    }
+}
+
+/// Copies `bytes` into `buf`, truncated to `words` words, and returns the length of `bytes`.
+///
+/// # Safety
+///
+/// Make sure that `buf` has at least `words` words.
+unsafe fn copy_to_words(bytes: &[u8], buf: *mut u32, words: usize) -> usize {
+    let nbytes = bytes.len().min(words * 4);
+    core::ptr::copy_nonoverlapping(bytes.as_ptr(), buf as *mut u8, nbytes);
+    bytes.len()
 }
 
 /// A lazy static to print a warning once for using the `sys_rand` system call.
@@ -35,6 +91,25 @@ pub unsafe extern "C" fn sys_rand(recv_buf: *mut u8, words: usize) {
     }
 }
 
+/// Installs a panic hook that reports the location and the message of panics to the host, so that
+/// they are returned by the executor, before running the default hook.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            *message
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.as_str()
+        } else {
+            "Box<dyn Any>"
+        };
+        let location = info.location().map(ToString::to_string).unwrap_or_default();
+        let report = format!("{location}\n{message}");
+        syscall_write(FD_PANIC, report.as_ptr(), report.len());
+        default_hook(info);
+    }));
+}
+
 #[allow(clippy::missing_safety_doc)]
 #[no_mangle]
 pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
@@ -42,21 +117,66 @@ pub unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     syscall_halt(1);
 }
 
-#[allow(unused_variables)]
+/// Returns the number of arguments of the program, see `SP1Context::args` on the host.
+#[no_mangle]
+pub extern "C" fn sys_argc() -> usize {
+    ARGS.len()
+}
+
+/// Copies the argument at `arg_index` into `out_words` and returns its length in bytes. The
+/// argument is truncated if it is longer than `out_nwords` words.
+///
+/// # Safety
+///
+/// Make sure that `out_words` has at least `out_nwords` words.
 #[no_mangle]
-pub const fn sys_getenv(
+pub unsafe extern "C" fn sys_argv(
+    out_words: *mut u32,
+    out_nwords: usize,
+    arg_index: usize,
+) -> usize {
+    copy_to_words(&ARGS[arg_index], out_words, out_nwords)
+}
+
+/// Copies the value of the environment variable named `varname` into `recv_buf` and returns its
+/// length in bytes, or `usize::MAX` if it isn't set, see `SP1Context::env` on the host. The value
+/// is truncated if it is longer than `words` words.
+///
+/// # Safety
+///
+/// Make sure that `recv_buf` has at least `words` words, and `varname` has `varname_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sys_getenv(
     recv_buf: *mut u32,
     words: usize,
     varname: *const u8,
     varname_len: usize,
 ) -> usize {
-    0
+    let varname = core::slice::from_raw_parts(varname, varname_len);
+    match ENV.iter().find(|(name, _)| name == varname) {
+        Some((_, value)) => copy_to_words(value, recv_buf, words),
+        None => usize::MAX,
+    }
 }
 
-#[allow(unused_variables)]
+/// Returns the low 32 bits of the number of cycles executed so far.
+///
+/// The count is read from the hint stream, so it isn't constrained by the proof and can't be read
+/// in an unconstrained block. Every call allocates the 4 bytes the count is read into.
+#[no_mangle]
+pub extern "C" fn syscall_cycle_count() -> u32 {
+    syscall_write(FD_CYCLE_COUNT, core::ptr::null(), 0);
+    u32::from_le_bytes(read_hint().try_into().unwrap())
+}
+
+/// Allocates `nwords` words.
+///
+/// # Safety
+///
+/// The memory returned is never freed.
 #[no_mangle]
-pub const fn sys_alloc_words(nwords: usize) -> *mut u32 {
-    core::ptr::null_mut()
+pub unsafe extern "C" fn sys_alloc_words(nwords: usize) -> *mut u32 {
+    sys_alloc_aligned(nwords * 4, 4) as *mut u32
 }
 
 #[allow(unused_unsafe)]
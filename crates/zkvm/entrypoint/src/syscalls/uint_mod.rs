@@ -0,0 +1,106 @@
+#[cfg(target_os = "zkvm")]
+use core::arch::asm;
+
+/// Uint320 modular addition operation.
+///
+/// `y` is followed by the modulus, and a zero modulus stands for `2^320`. The result is written
+/// over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary, and that `y` is followed by the modulus.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint320_addmod(x: *mut [u32; 10], y: *const [u32; 10]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT320_ADD,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Uint320 modular multiplication operation.
+///
+/// `y` is followed by the modulus, and a zero modulus stands for `2^320`. The result is written
+/// over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary, and that `y` is followed by the modulus.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint320_mulmod(x: *mut [u32; 10], y: *const [u32; 10]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT320_MUL,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Uint384 modular addition operation.
+///
+/// `y` is followed by the modulus, and a zero modulus stands for `2^384`. The result is written
+/// over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary, and that `y` is followed by the modulus.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint384_addmod(x: *mut [u32; 12], y: *const [u32; 12]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT384_ADD,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
+
+/// Uint384 modular multiplication operation.
+///
+/// `y` is followed by the modulus, and a zero modulus stands for `2^384`. The result is written
+/// over the first input.
+///
+/// ### Safety
+///
+/// The caller must ensure that `x` and `y` are valid pointers to data that is aligned along a four
+/// byte boundary, and that `y` is followed by the modulus.
+#[allow(unused_variables)]
+#[no_mangle]
+pub extern "C" fn syscall_uint384_mulmod(x: *mut [u32; 12], y: *const [u32; 12]) {
+    #[cfg(target_os = "zkvm")]
+    unsafe {
+        asm!(
+            "ecall",
+            in("t0") crate::syscalls::UINT384_MUL,
+            in("a0") x,
+            in("a1") y,
+        );
+    }
+
+    #[cfg(not(target_os = "zkvm"))]
+    unreachable!()
+}
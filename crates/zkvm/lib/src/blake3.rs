@@ -0,0 +1,268 @@
+//! The BLAKE3 compression function, with the names and the signatures of the portable
+//! implementation of the `blake3` crate, to build tree hashes and Bao verification on top of it,
+//! and a [`Hasher`] with the names of the `blake3` crate on top of it.
+//!
+//! The hasher compresses every block with the compression precompile, while the chunks and the
+//! tree of chaining values are handled here. The BLAKE3 chip isn't part of the machine until the
+//! next circuit release, so until then programs hashing with it can be executed, but not proven.
+
+use crate::syscall_blake3_compress;
+
+/// The initialization vector, used as the key of unkeyed hashes and as the first four words of the
+/// state of every compression.
+pub const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The number of bytes of a message block.
+pub const BLOCK_LEN: usize = 64;
+
+/// The number of bytes of a chunk, the leaves of the tree.
+pub const CHUNK_LEN: usize = 1024;
+
+// The domain separation flags of a compression.
+pub const CHUNK_START: u8 = 1 << 0;
+pub const CHUNK_END: u8 = 1 << 1;
+pub const PARENT: u8 = 1 << 2;
+pub const ROOT: u8 = 1 << 3;
+pub const KEYED_HASH: u8 = 1 << 4;
+pub const DERIVE_KEY_CONTEXT: u8 = 1 << 5;
+pub const DERIVE_KEY_MATERIAL: u8 = 1 << 6;
+
+/// Compresses `block` into the chaining value `cv`, and returns the 16 words of the extended
+/// output: the new chaining value followed by the words used for the XOF.
+pub fn compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+) -> [u32; 16] {
+    let mut state = [0; 16];
+    state[..8].copy_from_slice(cv);
+    state[8..12].copy_from_slice(&IV[..4]);
+    state[12] = counter as u32;
+    state[13] = (counter >> 32) as u32;
+    state[14] = block_len as u32;
+    state[15] = flags as u32;
+    unsafe {
+        syscall_blake3_compress(&mut state, block);
+    }
+    state
+}
+
+/// Compresses `block` into the chaining value `cv` in place.
+pub fn compress_in_place(
+    cv: &mut [u32; 8],
+    block: &[u8; BLOCK_LEN],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+) {
+    let output = compress(cv, &words_from_le_bytes(block), block_len, counter, flags);
+    cv.copy_from_slice(&output[..8]);
+}
+
+/// Compresses `block` into the chaining value `cv`, and returns the 64 bytes of the extended
+/// output.
+pub fn compress_xof(
+    cv: &[u32; 8],
+    block: &[u8; BLOCK_LEN],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+) -> [u8; 64] {
+    let output = compress(cv, &words_from_le_bytes(block), block_len, counter, flags);
+    let mut bytes = [0; 64];
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(output) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    bytes
+}
+
+/// The default output length in bytes.
+pub const OUT_LEN: usize = 32;
+
+/// The number of bytes of a key.
+pub const KEY_LEN: usize = 32;
+
+/// The BLAKE3 hash of `input`.
+pub fn hash(input: &[u8]) -> [u8; OUT_LEN] {
+    let mut hasher = Hasher::new();
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// The keyed BLAKE3 hash of `input`, a MAC with the 32-byte `key`.
+pub fn keyed_hash(key: &[u8; KEY_LEN], input: &[u8]) -> [u8; OUT_LEN] {
+    let mut hasher = Hasher::new_keyed(key);
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// An incremental BLAKE3 hasher, absorbing the input as it is given.
+#[derive(Clone, Debug)]
+pub struct Hasher {
+    key: [u32; 8],
+    chunk: ChunkState,
+    /// The chaining values of the complete subtrees on the left of the current chunk, the largest
+    /// first.
+    cv_stack: Vec<[u32; 8]>,
+    flags: u8,
+}
+
+impl Hasher {
+    /// Creates a hasher for the regular hash function.
+    pub fn new() -> Self {
+        Self::new_internal(IV, 0)
+    }
+
+    /// Creates a hasher for the keyed hash function.
+    pub fn new_keyed(key: &[u8; KEY_LEN]) -> Self {
+        let key =
+            core::array::from_fn(|i| u32::from_le_bytes(key[4 * i..4 * i + 4].try_into().unwrap()));
+        Self::new_internal(key, KEYED_HASH)
+    }
+
+    fn new_internal(key: [u32; 8], flags: u8) -> Self {
+        Self { key, chunk: ChunkState::new(key, 0, flags), cv_stack: Vec::new(), flags }
+    }
+
+    /// Adds `input` to the hash state.
+    pub fn update(&mut self, mut input: &[u8]) -> &mut Self {
+        while !input.is_empty() {
+            // Only close the chunk once more input comes, as the last one is the root's.
+            if self.chunk.len() == CHUNK_LEN {
+                let cv = self.chunk.output().chaining_value();
+                let total_chunks = self.chunk.counter + 1;
+                self.push_chunk_cv(cv, total_chunks);
+                self.chunk = ChunkState::new(self.key, total_chunks, self.flags);
+            }
+            let take = (CHUNK_LEN - self.chunk.len()).min(input.len());
+            self.chunk.update(&input[..take]);
+            input = &input[take..];
+        }
+        self
+    }
+
+    /// Finalizes the hash state and returns the hash of the input.
+    pub fn finalize(&self) -> [u8; OUT_LEN] {
+        let mut output = self.chunk.output();
+        for cv in self.cv_stack.iter().rev() {
+            output = parent_output(cv, &output.chaining_value(), &self.key, self.flags);
+        }
+        let words = output.compress(ROOT);
+        let mut bytes = [0; OUT_LEN];
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Pushes the chaining value of the chunk ending the first `total_chunks` chunks, merging the
+    /// subtrees it completes.
+    fn push_chunk_cv(&mut self, mut cv: [u32; 8], mut total_chunks: u64) {
+        while total_chunks & 1 == 0 {
+            let left = self.cv_stack.pop().unwrap();
+            cv = parent_output(&left, &cv, &self.key, self.flags).chaining_value();
+            total_chunks >>= 1;
+        }
+        self.cv_stack.push(cv);
+    }
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The state of the chunk being hashed, whose last block is kept until it's known to be the last
+/// one of the chunk.
+#[derive(Clone, Debug)]
+struct ChunkState {
+    cv: [u32; 8],
+    counter: u64,
+    block: [u8; BLOCK_LEN],
+    block_len: usize,
+    blocks_compressed: usize,
+    flags: u8,
+}
+
+impl ChunkState {
+    fn new(key: [u32; 8], counter: u64, flags: u8) -> Self {
+        Self { cv: key, counter, block: [0; BLOCK_LEN], block_len: 0, blocks_compressed: 0, flags }
+    }
+
+    fn len(&self) -> usize {
+        BLOCK_LEN * self.blocks_compressed + self.block_len
+    }
+
+    fn start_flag(&self) -> u8 {
+        if self.blocks_compressed == 0 {
+            CHUNK_START
+        } else {
+            0
+        }
+    }
+
+    fn update(&mut self, mut input: &[u8]) {
+        while !input.is_empty() {
+            if self.block_len == BLOCK_LEN {
+                let flags = self.flags | self.start_flag();
+                compress_in_place(&mut self.cv, &self.block, BLOCK_LEN as u8, self.counter, flags);
+                self.blocks_compressed += 1;
+                self.block = [0; BLOCK_LEN];
+                self.block_len = 0;
+            }
+            let take = (BLOCK_LEN - self.block_len).min(input.len());
+            self.block[self.block_len..self.block_len + take].copy_from_slice(&input[..take]);
+            self.block_len += take;
+            input = &input[take..];
+        }
+    }
+
+    fn output(&self) -> Output {
+        Output {
+            cv: self.cv,
+            block: words_from_le_bytes(&self.block),
+            block_len: self.block_len as u8,
+            counter: self.counter,
+            flags: self.flags | self.start_flag() | CHUNK_END,
+        }
+    }
+}
+
+/// The inputs of the last compression of a node, which gives either its chaining value or, with
+/// the [`ROOT`] flag, the output of the hash.
+struct Output {
+    cv: [u32; 8],
+    block: [u32; 16],
+    block_len: u8,
+    counter: u64,
+    flags: u8,
+}
+
+impl Output {
+    fn compress(&self, extra_flags: u8) -> [u32; 16] {
+        // The root is compressed with a zero counter, as the first block of its output.
+        let counter = if extra_flags & ROOT != 0 { 0 } else { self.counter };
+        compress(&self.cv, &self.block, self.block_len, counter, self.flags | extra_flags)
+    }
+
+    fn chaining_value(&self) -> [u32; 8] {
+        self.compress(0)[..8].try_into().unwrap()
+    }
+}
+
+/// The output of the parent node of the chaining values `left` and `right`.
+fn parent_output(left: &[u32; 8], right: &[u32; 8], key: &[u32; 8], flags: u8) -> Output {
+    let mut block = [0; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+    Output { cv: *key, block, block_len: BLOCK_LEN as u8, counter: 0, flags: PARENT | flags }
+}
+
+fn words_from_le_bytes(block: &[u8; BLOCK_LEN]) -> [u32; 16] {
+    core::array::from_fn(|i| u32::from_le_bytes(block[4 * i..4 * i + 4].try_into().unwrap()))
+}
@@ -6,6 +6,12 @@ use std::{
     io::{Result, Write},
 };
 
+/// The file descriptor for the standard output, which `println!` writes to.
+pub const FD_STDOUT: u32 = 1;
+
+/// The file descriptor for the standard error, which `eprintln!` writes to.
+pub const FD_STDERR: u32 = 2;
+
 /// The file descriptor for public values.
 pub const FD_PUBLIC_VALUES: u32 = 3;
 
@@ -15,6 +21,31 @@ pub const FD_HINT: u32 = 4;
 /// The file descriptor for the `ecreover` hook.
 pub const FD_ECRECOVER_HOOK: u32 = 5;
 
+/// The file descriptor for the `secp256k1_lift_x` hook.
+pub const FD_SECP256K1_LIFT_X_HOOK: u32 = 9;
+
+/// The file descriptor of the hook registered under `name` on the host, for use with [write].
+///
+/// The name is hashed with 32-bit FNV-1a, with the top bit set so it never collides with the
+/// fixed file descriptors above.
+///
+/// ### Examples
+/// ```ignore
+/// sp1_zkvm::io::write(sp1_zkvm::io::hook_fd("fetch-block"), &block_number.to_le_bytes());
+/// let block: Vec<u8> = sp1_zkvm::io::read_vec();
+/// ```
+pub const fn hook_fd(name: &str) -> u32 {
+    let bytes = name.as_bytes();
+    let mut hash: u32 = 0x811c_9dc5;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x0100_0193);
+        i += 1;
+    }
+    hash | (1 << 31)
+}
+
 /// A writer that writes to a file descriptor inside the zkVM.
 struct SyscallWriter {
     fd: u32,
@@ -156,6 +187,25 @@ pub fn hint_slice(buf: &[u8]) {
     my_reader.write_all(buf).unwrap();
 }
 
+/// Send `request` to the handler registered under `name` on the host, and return its response.
+///
+/// The handler runs when the request is sent, so it can compute data that depends on the
+/// execution so far. Its responses are recorded, so that re-executing the program, e.g. when
+/// proving, doesn't invoke it again.
+///
+/// ### Examples
+/// ```ignore
+/// let key = derive_key();
+/// let value = sp1_zkvm::io::request("my-app/fetch", &key);
+/// ```
+pub fn request(name: &str, request: &[u8]) -> Vec<u8> {
+    // Write in a single syscall, even if empty, so that the handler is invoked once.
+    unsafe {
+        syscall_write(hook_fd(name), request.as_ptr(), request.len());
+    }
+    read_vec()
+}
+
 /// Write the data `buf` to the file descriptor `fd`.
 ///
 /// ### Examples
@@ -3,16 +3,28 @@
 //! Documentation for these syscalls can be found in the zkVM entrypoint
 //! `sp1_zkvm::syscalls` module.
 
+pub mod aes;
+pub mod blake3;
 pub mod bls12381;
 pub mod bn254;
 pub mod ed25519;
 pub mod io;
+pub mod p256;
+pub mod poseidon2;
+pub mod ripemd160;
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
 pub mod secp256k1;
+pub mod secp256r1;
+pub mod sha512;
 pub mod unconstrained;
 pub mod utils;
 #[cfg(feature = "verify")]
 pub mod verify;
 
+#[cfg(feature = "verify")]
+pub use verify::verify_deferred;
+
 extern "C" {
     /// Halts the program with the given exit code.
     pub fn syscall_halt(exit_code: u8) -> !;
@@ -29,6 +41,12 @@ extern "C" {
     /// Executes the SHA-256 compress operation on the given word array and a given state.
     pub fn syscall_sha256_compress(w: *mut [u32; 64], state: *mut [u32; 8]);
 
+    /// Executes the SHA-512 extend operation on the given double word array.
+    pub fn syscall_sha512_extend(w: *mut [u64; 80]);
+
+    /// Executes the SHA-512 compress operation on the given double word array and a given state.
+    pub fn syscall_sha512_compress(w: *mut [u64; 80], state: *mut [u64; 8]);
+
     /// Executes an Ed25519 curve addition on the given points.
     pub fn syscall_ed_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
@@ -44,6 +62,15 @@ extern "C" {
     /// Executes an Secp256k1 curve decompression on the given point.
     pub fn syscall_secp256k1_decompress(point: &mut [u8; 64], is_odd: bool);
 
+    /// Executes a Secp256r1 curve addition on the given points.
+    pub fn syscall_secp256r1_add(p: *mut [u32; 16], q: *const [u32; 16]);
+
+    /// Executes a Secp256r1 curve doubling on the given point.
+    pub fn syscall_secp256r1_double(p: *mut [u32; 16]);
+
+    /// Executes a Secp256r1 curve decompression on the given point.
+    pub fn syscall_secp256r1_decompress(point: &mut [u8; 64], is_odd: bool);
+
     /// Executes a Bn254 curve addition on the given points.
     pub fn syscall_bn254_add(p: *mut [u32; 16], q: *const [u32; 16]);
 
@@ -59,9 +86,36 @@ extern "C" {
     /// Executes the Keccak-256 permutation on the given state.
     pub fn syscall_keccak_permute(state: *mut [u64; 25]);
 
+    /// Executes the Poseidon2 permutation over BabyBear on the given state.
+    pub fn syscall_poseidon2_permute(state: *mut [u32; 16]);
+
+    /// Executes the BLAKE3 compression function on the given state and message block.
+    pub fn syscall_blake3_compress(state: *mut [u32; 16], msg: *const [u32; 16]);
+
+    /// Encrypts the given block in place with AES-128, using the given expanded round keys.
+    pub fn syscall_aes128_encrypt(block: *mut [u32; 4], round_keys: *const [u32; 44]);
+
+    /// Encrypts the given block in place with AES-256, using the given expanded round keys.
+    pub fn syscall_aes256_encrypt(block: *mut [u32; 4], round_keys: *const [u32; 60]);
+
+    /// Executes the RIPEMD-160 compression function on the given state and message block.
+    pub fn syscall_ripemd160_compress(state: *mut [u32; 5], block: *const [u32; 16]);
+
     /// Executes an uint256 multiplication on the given inputs.
     pub fn syscall_uint256_mulmod(x: *mut [u32; 8], y: *const [u32; 8]);
 
+    /// Executes an uint320 modular addition on the given inputs, with the modulus after `y`.
+    pub fn syscall_uint320_addmod(x: *mut [u32; 10], y: *const [u32; 10]);
+
+    /// Executes an uint320 modular multiplication on the given inputs, with the modulus after `y`.
+    pub fn syscall_uint320_mulmod(x: *mut [u32; 10], y: *const [u32; 10]);
+
+    /// Executes an uint384 modular addition on the given inputs, with the modulus after `y`.
+    pub fn syscall_uint384_addmod(x: *mut [u32; 12], y: *const [u32; 12]);
+
+    /// Executes an uint384 modular multiplication on the given inputs, with the modulus after `y`.
+    pub fn syscall_uint384_mulmod(x: *mut [u32; 12], y: *const [u32; 12]);
+
     /// Enters unconstrained mode.
     pub fn syscall_enter_unconstrained() -> bool;
 
@@ -77,6 +131,9 @@ extern "C" {
     /// Reads the next element in the hint stream into the given buffer.
     pub fn syscall_hint_read(ptr: *mut u8, len: usize);
 
+    /// Returns the low 32 bits of the number of cycles executed so far.
+    pub fn syscall_cycle_count() -> u32;
+
     /// Allocates a buffer aligned to the given alignment.
     pub fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8;
 
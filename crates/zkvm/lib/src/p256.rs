@@ -0,0 +1,136 @@
+//! Verification of the ECDSA signatures over P-256 of WebAuthn and passkeys, on the Secp256r1
+//! precompiles, with the names of the `p256` crate.
+//!
+//! The point `u1 * G + u2 * Q` that the signature commits to is computed with the addition and
+//! doubling precompiles, and the scalars are multiplied modulo the group order with the uint256
+//! multiplication precompile. The Secp256r1 chips aren't part of the machine until the next
+//! circuit release, so until then programs verifying signatures can be executed, but not proven.
+
+use crate::{
+    secp256r1::{Secp256r1AffinePoint, N},
+    utils::{
+        add_assign, double_scalar_mul, lt, mulmod_uint256, sub_assign, words_from_be_bytes,
+        AffinePoint,
+    },
+};
+
+/// The modulus of the base field of P-256, as little-endian words.
+pub const FIELD_MODULUS: [u32; 8] = [
+    0xffffffff, 0xffffffff, 0xffffffff, 0x00000000, 0x00000000, 0x00000000, 0x00000001, 0xffffffff,
+];
+
+/// The order of the group of P-256, as little-endian words.
+pub const GROUP_ORDER: [u32; 8] = [
+    0xfc632551, 0xf3b9cac2, 0xa7179e84, 0xbce6faad, 0xffffffff, 0xffffffff, 0x00000000, 0xffffffff,
+];
+
+/// The coefficient `b` of the curve `y^2 = x^3 - 3x + b`, as little-endian words.
+const CURVE_B: [u32; 8] = [
+    0x27d2604b, 0x3bce3c3e, 0xcc53b0f6, 0x651d06b0, 0x769886bc, 0xb3ebbd55, 0xaa3a93e7, 0x5ac635d8,
+];
+
+/// A P-256 public key to verify ECDSA signatures with.
+#[derive(Copy, Clone)]
+pub struct VerifyingKey(Secp256r1AffinePoint);
+
+impl VerifyingKey {
+    /// Parses the uncompressed SEC1 encoding `0x04 || x || y` of a public key, the encoding of the
+    /// COSE keys of WebAuthn. Returns `None` if the point isn't on the curve.
+    ///
+    /// Unlike the `p256` crate, compressed encodings are rejected, as the decompress precompile
+    /// fails to prove on an x coordinate that isn't on the curve.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Option<Self> {
+        let [0x04, coordinates @ ..] = bytes else {
+            return None;
+        };
+        if coordinates.len() != 64 {
+            return None;
+        }
+        let x = words_from_be_bytes(coordinates[..32].try_into().unwrap());
+        let y = words_from_be_bytes(coordinates[32..].try_into().unwrap());
+        if !lt(&x, &FIELD_MODULUS) || !lt(&y, &FIELD_MODULUS) {
+            return None;
+        }
+
+        let mut y_2 = y;
+        mulmod_uint256(&mut y_2, &y, &FIELD_MODULUS);
+        let mut rhs = x;
+        mulmod_uint256(&mut rhs, &x, &FIELD_MODULUS);
+        sub_mod(&mut rhs, &[3, 0, 0, 0, 0, 0, 0, 0]);
+        mulmod_uint256(&mut rhs, &x, &FIELD_MODULUS);
+        add_mod(&mut rhs, &CURVE_B);
+        if y_2 != rhs {
+            return None;
+        }
+
+        let mut limbs = [0; N];
+        limbs[..8].copy_from_slice(&x);
+        limbs[8..].copy_from_slice(&y);
+        Some(Self(Secp256r1AffinePoint::new(limbs)))
+    }
+
+    /// Verifies the signature `r || s` of the message whose hash is `prehash`, like the
+    /// `PrehashVerifier` of the `p256` crate. High `s` values are accepted, as in the `p256`
+    /// crate.
+    pub fn verify_prehash(&self, prehash: &[u8; 32], signature: &[u8; 64]) -> bool {
+        let r = words_from_be_bytes(signature[..32].try_into().unwrap());
+        let s = words_from_be_bytes(signature[32..].try_into().unwrap());
+        for scalar in [&r, &s] {
+            if *scalar == [0; 8] || !lt(scalar, &GROUP_ORDER) {
+                return false;
+            }
+        }
+
+        let mut z = words_from_be_bytes(prehash);
+        if !lt(&z, &GROUP_ORDER) {
+            sub_assign(&mut z, &GROUP_ORDER);
+        }
+        let s_inv = invert_scalar(&s);
+        let mut u1 = z;
+        mulmod_uint256(&mut u1, &s_inv, &GROUP_ORDER);
+        let mut u2 = r;
+        mulmod_uint256(&mut u2, &s_inv, &GROUP_ORDER);
+
+        let generator = Secp256r1AffinePoint::new(Secp256r1AffinePoint::GENERATOR);
+        let Some(point) = double_scalar_mul(&u1, generator, &u2, self.0) else {
+            return false;
+        };
+        let mut x: [u32; 8] = point.0[..8].try_into().unwrap();
+        if !lt(&x, &GROUP_ORDER) {
+            sub_assign(&mut x, &GROUP_ORDER);
+        }
+        x == r
+    }
+}
+
+/// The inverse of the non-zero `scalar` modulo [`GROUP_ORDER`], as `scalar ^ (n - 2)`.
+fn invert_scalar(scalar: &[u32; 8]) -> [u32; 8] {
+    let mut exponent = GROUP_ORDER;
+    exponent[0] -= 2;
+    let mut acc = [1, 0, 0, 0, 0, 0, 0, 0];
+    for i in (0..256).rev() {
+        let square = acc;
+        mulmod_uint256(&mut acc, &square, &GROUP_ORDER);
+        if (exponent[i / 32] >> (i % 32)) & 1 == 1 {
+            mulmod_uint256(&mut acc, scalar, &GROUP_ORDER);
+        }
+    }
+    acc
+}
+
+/// Sets `x` to `x + y` modulo [`FIELD_MODULUS`], for `x` and `y` reduced modulo it.
+fn add_mod(x: &mut [u32; 8], y: &[u32; 8]) {
+    let carry = add_assign(x, y);
+    if carry || !lt(x, &FIELD_MODULUS) {
+        sub_assign(x, &FIELD_MODULUS);
+    }
+}
+
+/// Sets `x` to `x - y` modulo [`FIELD_MODULUS`], for `x` and `y` reduced modulo it.
+fn sub_mod(x: &mut [u32; 8], y: &[u32; 8]) {
+    let borrow = lt(x, y);
+    sub_assign(x, y);
+    if borrow {
+        add_assign(x, &FIELD_MODULUS);
+    }
+}
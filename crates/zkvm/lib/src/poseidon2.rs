@@ -0,0 +1,88 @@
+//! Poseidon2 hashing over BabyBear, with the permutation and the parameters the prover uses for its
+//! own hashing.
+//!
+//! The elements are `u32`s, canonical BabyBear elements. The hashes are those of the prover's
+//! padding-free sponge and the compressions those of its Merkle trees, so that digests computed in
+//! a program match the ones computed on the host with `sp1_primitives::poseidon2_hash`.
+
+use crate::syscall_poseidon2_permute;
+
+/// The number of elements of the state of the permutation.
+pub const WIDTH: usize = 16;
+
+/// The number of elements absorbed by each permutation of the sponge.
+pub const RATE: usize = 8;
+
+/// The number of elements of a digest.
+pub const DIGEST_SIZE: usize = 8;
+
+/// Permutes `state` in place.
+pub fn permute(state: &mut [u32; WIDTH]) {
+    unsafe {
+        syscall_poseidon2_permute(state);
+    }
+}
+
+/// A padding-free sponge over the Poseidon2 permutation, which overwrites the rate of its state
+/// with the absorbed elements.
+#[derive(Clone, Debug)]
+pub struct Poseidon2Sponge {
+    state: [u32; WIDTH],
+    /// The number of elements absorbed since the last permutation.
+    absorbed: usize,
+}
+
+impl Poseidon2Sponge {
+    /// Creates a sponge with an all-zero state.
+    pub const fn new() -> Self {
+        Self { state: [0; WIDTH], absorbed: 0 }
+    }
+
+    /// Absorbs `elements`, permuting the state each time the rate is filled.
+    pub fn absorb(&mut self, elements: &[u32]) {
+        for &element in elements {
+            self.state[self.absorbed] = element;
+            self.absorbed += 1;
+            if self.absorbed == RATE {
+                permute(&mut self.state);
+                self.absorbed = 0;
+            }
+        }
+    }
+
+    /// Permutes the elements absorbed since the last permutation, if any, and returns the digest.
+    pub fn squeeze(mut self) -> [u32; DIGEST_SIZE] {
+        if self.absorbed != 0 {
+            permute(&mut self.state);
+        }
+        let mut digest = [0; DIGEST_SIZE];
+        digest.copy_from_slice(&self.state[..DIGEST_SIZE]);
+        digest
+    }
+}
+
+impl Default for Poseidon2Sponge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hashes `elements` with a [`Poseidon2Sponge`].
+pub fn hash(elements: &[u32]) -> [u32; DIGEST_SIZE] {
+    let mut sponge = Poseidon2Sponge::new();
+    sponge.absorb(elements);
+    sponge.squeeze()
+}
+
+/// Compresses two digests into one, like the nodes of a Merkle tree: the digest is the start of
+/// the permutation of their concatenation.
+pub fn compress(left: &[u32; DIGEST_SIZE], right: &[u32; DIGEST_SIZE]) -> [u32; DIGEST_SIZE] {
+    let mut state = [0; WIDTH];
+    state[..DIGEST_SIZE].copy_from_slice(left);
+    state[DIGEST_SIZE..].copy_from_slice(right);
+    permute(&mut state);
+
+    let mut digest = [0; DIGEST_SIZE];
+    digest.copy_from_slice(&state[..DIGEST_SIZE]);
+    digest
+}
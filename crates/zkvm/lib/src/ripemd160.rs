@@ -0,0 +1,106 @@
+//! RIPEMD-160 hashing on the compression precompile, absorbing the input as it is given.
+//!
+//! The precompile only compresses the blocks of the input into the state, while the padding and
+//! the incomplete blocks are handled here. Paired with SHA-256, it computes the `hash160` of
+//! Bitcoin keys and scripts. With the `digest` feature, [`Ripemd160`] implements the traits of the
+//! `digest` crate, so that it replaces the hasher of the `ripemd` crate in code generic over
+//! `Digest`. The RIPEMD-160 chip isn't part of the machine until the next circuit release, so until
+//! then programs hashing with it can be executed, but not proven.
+
+use crate::syscall_ripemd160_compress;
+
+/// The number of bytes in a block.
+pub const BLOCK_LEN: usize = 64;
+
+/// The initial hash state of RIPEMD-160.
+const IV: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/// An incremental RIPEMD-160 hasher.
+#[derive(Clone, Debug)]
+pub struct Ripemd160 {
+    state: [u32; 5],
+    buffer: [u8; BLOCK_LEN],
+    buffer_len: usize,
+    /// The number of bytes absorbed.
+    len: u64,
+}
+
+impl Ripemd160 {
+    /// Creates a hasher of the empty input.
+    pub const fn new() -> Self {
+        Self { state: IV, buffer: [0; BLOCK_LEN], buffer_len: 0, len: 0 }
+    }
+
+    /// Absorbs `data`.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        if self.buffer_len > 0 {
+            let len = data.len().min(BLOCK_LEN - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + len].copy_from_slice(&data[..len]);
+            self.buffer_len += len;
+            data = &data[len..];
+            if self.buffer_len < BLOCK_LEN {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        let mut blocks = data.chunks_exact(BLOCK_LEN);
+        for block in &mut blocks {
+            self.compress(block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffer_len = remainder.len();
+    }
+
+    /// Pads the absorbed input, and returns its hash.
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_len = self.len.wrapping_mul(8);
+        self.buffer[self.buffer_len..].fill(0);
+        self.buffer[self.buffer_len] = 0x80;
+        // The length takes up the last 8 bytes of the last block.
+        if self.buffer_len >= BLOCK_LEN - 8 {
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer = [0; BLOCK_LEN];
+        }
+        self.buffer[BLOCK_LEN - 8..].copy_from_slice(&bit_len.to_le_bytes());
+        let block = self.buffer;
+        self.compress(&block);
+
+        let mut hash = [0; 20];
+        for (bytes, word) in hash.chunks_exact_mut(4).zip(self.state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        hash
+    }
+
+    fn compress(&mut self, block: &[u8; BLOCK_LEN]) {
+        let mut words = [0u32; 16];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        unsafe {
+            syscall_ripemd160_compress(&mut self.state, &words);
+        }
+    }
+}
+
+impl Default for Ripemd160 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The RIPEMD-160 hash of `data`.
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    let mut hasher = Ripemd160::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+#[cfg(feature = "digest")]
+crate::utils::impl_digest!(Ripemd160, digest::consts::U20);
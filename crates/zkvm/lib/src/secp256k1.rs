@@ -1,8 +1,17 @@
-use crate::{syscall_secp256k1_add, syscall_secp256k1_double, utils::AffinePoint};
+use crate::{
+    io::{self, FD_SECP256K1_LIFT_X_HOOK},
+    syscall_secp256k1_add, syscall_secp256k1_decompress, syscall_secp256k1_double,
+    utils::{add_assign, lt, mulmod_uint256, sub_assign, words_from_be_bytes, AffinePoint},
+};
 
 /// The number of limbs in [Secp256k1AffinePoint].
 pub const N: usize = 16;
 
+/// The modulus of the base field of Secp256k1, as little-endian words.
+pub const FIELD_MODULUS: [u32; 8] = [
+    0xfffffc2f, 0xfffffffe, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff, 0xffffffff,
+];
+
 /// An affine point on the Secp256k1 curve.
 #[derive(Copy, Clone)]
 #[repr(align(4))]
@@ -43,3 +52,74 @@ impl AffinePoint<N> for Secp256k1AffinePoint {
         }
     }
 }
+
+impl Secp256k1AffinePoint {
+    /// The point whose x coordinate is the big-endian `x` and whose y coordinate is even, as the
+    /// `lift_x` of BIP-340. Returns `None` if `x` isn't the x coordinate of a point of the curve.
+    ///
+    /// The host tells whether `x^3 + 7` is a square. If it is, the point is decompressed with the
+    /// decompress precompile. If it isn't, the host gives a square root of `-(x^3 + 7)`, which is
+    /// checked with the uint256 multiplication precompile, as -1 isn't a square.
+    pub fn lift_x(x: &[u8; 32]) -> Option<Self> {
+        let x_words = words_from_be_bytes(x);
+        if !lt(&x_words, &FIELD_MODULUS) {
+            return None;
+        }
+
+        io::write(FD_SECP256K1_LIFT_X_HOOK, x);
+        match io::read_vec().split_first() {
+            Some((1, [])) => {
+                let mut point = [0; 64];
+                point[..32].copy_from_slice(x);
+                unsafe {
+                    syscall_secp256k1_decompress(&mut point, false);
+                }
+                // The precompile returns the coordinates in big-endian.
+                point[..32].reverse();
+                point[32..].reverse();
+                Some(<Self as AffinePoint<N>>::from(&point[..32], &point[32..]))
+            }
+            Some((0, root)) if root.len() == 32 => {
+                let mut y_2 = x_words;
+                mulmod(&mut y_2, &x_words);
+                mulmod(&mut y_2, &x_words);
+                add_mod(&mut y_2, &[7, 0, 0, 0, 0, 0, 0, 0]);
+
+                let root = words_from_be_bytes(root.try_into().unwrap());
+                let mut sum = root;
+                mulmod(&mut sum, &root);
+                add_mod(&mut sum, &y_2);
+                assert!(sum == [0; 8], "the lift_x hook returned an invalid square root");
+                None
+            }
+            _ => panic!("the lift_x hook returned an invalid result"),
+        }
+    }
+
+    /// The big-endian x coordinate of the point, its x-only encoding in BIP-340.
+    pub fn x_only(&self) -> [u8; 32] {
+        let mut x = [0; 32];
+        for (bytes, word) in x.chunks_exact_mut(4).zip(self.0[..8].iter().rev()) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        x
+    }
+
+    /// Whether the y coordinate of the point is even.
+    pub fn has_even_y(&self) -> bool {
+        self.0[8] & 1 == 0
+    }
+}
+
+/// Sets `x` to `x * y` modulo [`FIELD_MODULUS`], with the uint256 multiplication precompile.
+fn mulmod(x: &mut [u32; 8], y: &[u32; 8]) {
+    mulmod_uint256(x, y, &FIELD_MODULUS);
+}
+
+/// Sets `x` to `x + y` modulo [`FIELD_MODULUS`], for `x` and `y` reduced modulo it.
+fn add_mod(x: &mut [u32; 8], y: &[u32; 8]) {
+    let carry = add_assign(x, y);
+    if carry || !lt(x, &FIELD_MODULUS) {
+        sub_assign(x, &FIELD_MODULUS);
+    }
+}
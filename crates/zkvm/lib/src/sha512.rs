@@ -0,0 +1,274 @@
+//! SHA-512, SHA-384, SHA-512/256 and SHA-512/224 hashing on the SHA-512 extend and compress
+//! precompiles, absorbing the input as it is given.
+//!
+//! The precompiles only process the blocks of the input: the message schedule of each block is
+//! extended and compressed into the state, while the padding and the incomplete blocks are handled
+//! here. With the `digest` feature, the hashers implement the traits of the `digest` crate, so that
+//! they replace the hashers of the `sha2` crate in code generic over `Digest`. The SHA-512 chips
+//! aren't part of the machine until the next circuit release, so until then programs hashing with
+//! them can be executed, but not proven.
+
+use crate::{syscall_sha512_compress, syscall_sha512_extend};
+
+/// The number of bytes in a block.
+pub const BLOCK_LEN: usize = 128;
+
+/// The initial hash state of SHA-512.
+const SHA512_IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// The initial hash state of SHA-384.
+const SHA384_IV: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+/// The initial hash state of SHA-512/256.
+const SHA512_256_IV: [u64; 8] = [
+    0x22312194fc2bf72c,
+    0x9f555fa3c84c64c2,
+    0x2393b86b6f53b151,
+    0x963877195940eabd,
+    0x96283ee2a88effe3,
+    0xbe5e1e2553863992,
+    0x2b0199fc2c85b8aa,
+    0x0eb72ddc81c52ca2,
+];
+
+/// The initial hash state of SHA-512/224.
+const SHA512_224_IV: [u64; 8] = [
+    0x8c3d37c819544da2,
+    0x73e1996689dcd4d6,
+    0x1dfab7ae32ff9c82,
+    0x679dd514582f9fcf,
+    0x0f6d2b697bd44da8,
+    0x77e36f7304c48942,
+    0x3f9d85a86a1d36c8,
+    0x1112e6ad91d692a1,
+];
+
+/// An incremental SHA-512 hasher.
+#[derive(Clone, Debug)]
+pub struct Sha512 {
+    state: [u64; 8],
+    buffer: [u8; BLOCK_LEN],
+    buffer_len: usize,
+    /// The number of bytes absorbed.
+    len: u128,
+}
+
+impl Sha512 {
+    /// Creates a hasher of the empty input.
+    pub const fn new() -> Self {
+        Self::with_iv(SHA512_IV)
+    }
+
+    const fn with_iv(state: [u64; 8]) -> Self {
+        Self { state, buffer: [0; BLOCK_LEN], buffer_len: 0, len: 0 }
+    }
+
+    /// Absorbs `data`.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u128;
+        if self.buffer_len > 0 {
+            let len = data.len().min(BLOCK_LEN - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + len].copy_from_slice(&data[..len]);
+            self.buffer_len += len;
+            data = &data[len..];
+            if self.buffer_len < BLOCK_LEN {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer_len = 0;
+        }
+
+        let mut blocks = data.chunks_exact(BLOCK_LEN);
+        for block in &mut blocks {
+            self.compress(block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffer_len = remainder.len();
+    }
+
+    /// Pads the absorbed input, and returns its hash.
+    pub fn finalize(self) -> [u8; 64] {
+        self.finalize_truncated()
+    }
+
+    /// Pads the absorbed input, and returns the first `L` bytes of the final state, the hash of
+    /// the truncated variants.
+    fn finalize_truncated<const L: usize>(mut self) -> [u8; L] {
+        let mut hash = [0; 64];
+        for (bytes, word) in hash.chunks_exact_mut(8).zip(self.finalize_state()) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        hash[..L].try_into().unwrap()
+    }
+
+    /// Pads the absorbed input, and returns the final state.
+    fn finalize_state(&mut self) -> [u64; 8] {
+        let bit_len = self.len * 8;
+        self.buffer[self.buffer_len..].fill(0);
+        self.buffer[self.buffer_len] = 0x80;
+        // The length takes up the last 16 bytes of the last block.
+        if self.buffer_len >= BLOCK_LEN - 16 {
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffer = [0; BLOCK_LEN];
+        }
+        self.buffer[BLOCK_LEN - 16..].copy_from_slice(&bit_len.to_be_bytes());
+        let block = self.buffer;
+        self.compress(&block);
+        self.state
+    }
+
+    fn compress(&mut self, block: &[u8; BLOCK_LEN]) {
+        let mut w = [0u64; 80];
+        for (w_i, bytes) in w.iter_mut().zip(block.chunks_exact(8)) {
+            *w_i = u64::from_be_bytes(bytes.try_into().unwrap());
+        }
+        unsafe {
+            syscall_sha512_extend(&mut w);
+            syscall_sha512_compress(&mut w, &mut self.state);
+        }
+    }
+}
+
+impl Default for Sha512 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An incremental SHA-384 hasher.
+#[derive(Clone, Debug)]
+pub struct Sha384(Sha512);
+
+impl Sha384 {
+    /// Creates a hasher of the empty input.
+    pub const fn new() -> Self {
+        Self(Sha512::with_iv(SHA384_IV))
+    }
+
+    /// Absorbs `data`.
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    /// Pads the absorbed input, and returns its hash.
+    pub fn finalize(self) -> [u8; 48] {
+        self.0.finalize_truncated()
+    }
+}
+
+impl Default for Sha384 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An incremental SHA-512/256 hasher.
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct Sha512_256(Sha512);
+
+impl Sha512_256 {
+    /// Creates a hasher of the empty input.
+    pub const fn new() -> Self {
+        Self(Sha512::with_iv(SHA512_256_IV))
+    }
+
+    /// Absorbs `data`.
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    /// Pads the absorbed input, and returns its hash.
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize_truncated()
+    }
+}
+
+impl Default for Sha512_256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An incremental SHA-512/224 hasher.
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct Sha512_224(Sha512);
+
+impl Sha512_224 {
+    /// Creates a hasher of the empty input.
+    pub const fn new() -> Self {
+        Self(Sha512::with_iv(SHA512_224_IV))
+    }
+
+    /// Absorbs `data`.
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    /// Pads the absorbed input, and returns its hash.
+    pub fn finalize(self) -> [u8; 28] {
+        self.0.finalize_truncated()
+    }
+}
+
+impl Default for Sha512_224 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The SHA-512 hash of `data`.
+pub fn sha512(data: &[u8]) -> [u8; 64] {
+    let mut hasher = Sha512::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The SHA-384 hash of `data`.
+pub fn sha384(data: &[u8]) -> [u8; 48] {
+    let mut hasher = Sha384::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// The SHA-512/256 hash of `data`.
+pub fn sha512_256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha512_256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+#[cfg(feature = "digest")]
+mod digest_impls {
+    use digest::consts::{U28, U32, U48, U64};
+
+    use super::{Sha384, Sha512, Sha512_224, Sha512_256};
+    use crate::utils::impl_digest;
+
+    impl_digest!(Sha512, U64);
+    impl_digest!(Sha384, U48);
+    impl_digest!(Sha512_256, U32);
+    impl_digest!(Sha512_224, U28);
+}
@@ -1,3 +1,5 @@
+use crate::syscall_uint256_mulmod;
+
 pub trait AffinePoint<const N: usize>: Clone + Sized {
     /// The generator.
     const GENERATOR: [u32; N];
@@ -130,3 +132,140 @@ pub fn bytes_to_words_le(bytes: &[u8]) -> Vec<u32> {
         .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()))
         .collect::<Vec<_>>()
 }
+
+/// The number of words of the operands of the uint256 multiplication precompile.
+pub(crate) const UINT256_WORDS: usize = 8;
+
+/// Sets `x` to `x * y % modulus` with the uint256 multiplication precompile, for `x` and `y`
+/// reduced modulo a non-zero `modulus`.
+pub(crate) fn mulmod_uint256(
+    x: &mut [u32; UINT256_WORDS],
+    y: &[u32; UINT256_WORDS],
+    modulus: &[u32; UINT256_WORDS],
+) {
+    // The precompile reads the modulus after the second operand.
+    let mut y_and_modulus = [0; 2 * UINT256_WORDS];
+    y_and_modulus[..UINT256_WORDS].copy_from_slice(y);
+    y_and_modulus[UINT256_WORDS..].copy_from_slice(modulus);
+    unsafe {
+        syscall_uint256_mulmod(x, y_and_modulus.as_ptr() as *const [u32; UINT256_WORDS]);
+    }
+}
+
+/// Whether `a < b`, for little-endian limbs of the same length.
+pub(crate) fn lt(a: &[u32], b: &[u32]) -> bool {
+    a.iter().rev().cmp(b.iter().rev()).is_lt()
+}
+
+/// Sets `a` to `a + b` on little-endian limbs, and returns the carry.
+pub(crate) fn add_assign(a: &mut [u32], b: &[u32]) -> bool {
+    let mut carry = false;
+    for (a, &b) in a.iter_mut().zip(b) {
+        let (sum, c1) = a.overflowing_add(b);
+        let (sum, c2) = sum.overflowing_add(carry as u32);
+        *a = sum;
+        carry = c1 || c2;
+    }
+    carry
+}
+
+/// Sets `a` to `a - b` on little-endian limbs, wrapping around.
+pub(crate) fn sub_assign(a: &mut [u32], b: &[u32]) {
+    let mut borrow = false;
+    for (a, &b) in a.iter_mut().zip(b) {
+        let (diff, b1) = a.overflowing_sub(b);
+        let (diff, b2) = diff.overflowing_sub(borrow as u32);
+        *a = diff;
+        borrow = b1 || b2;
+    }
+}
+
+/// The little-endian words of the big-endian 256-bit integer `bytes`.
+pub(crate) fn words_from_be_bytes(bytes: &[u8; 32]) -> [u32; 8] {
+    core::array::from_fn(|i| {
+        let offset = 28 - 4 * i;
+        u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    })
+}
+
+/// `a * p + b * q`, for the little-endian scalars `a` and `b` of the same length and points `p`
+/// and `q` of prime order, or `None` for the point at infinity.
+pub(crate) fn double_scalar_mul<P: AffinePoint<N>, const N: usize>(
+    a: &[u32],
+    mut p: P,
+    b: &[u32],
+    mut q: P,
+) -> Option<P> {
+    let bits = 32 * a.len();
+    let mut acc = None;
+    for i in 0..bits {
+        if (a[i / 32] >> (i % 32)) & 1 == 1 {
+            add_to(&mut acc, &p);
+        }
+        if (b[i / 32] >> (i % 32)) & 1 == 1 {
+            add_to(&mut acc, &q);
+        }
+        // The points have prime order, so they don't double to the point at infinity.
+        if i + 1 < bits {
+            p.double();
+            q.double();
+        }
+    }
+    acc
+}
+
+/// Adds `point` to `acc`, where `None` is the point at infinity, doubling `acc` if it's `point`,
+/// which the addition precompiles don't handle.
+fn add_to<P: AffinePoint<N>, const N: usize>(acc: &mut Option<P>, point: &P) {
+    match acc {
+        None => *acc = Some(point.clone()),
+        Some(acc_point) if acc_point.limbs_ref()[..N / 2] == point.limbs_ref()[..N / 2] => {
+            if acc_point.limbs_ref() == point.limbs_ref() {
+                acc_point.double();
+            } else {
+                *acc = None;
+            }
+        }
+        Some(acc_point) => acc_point.add_assign(point),
+    }
+}
+
+/// Implements the traits of the `digest` crate that make up `Digest` for a hasher with the given
+/// output size.
+#[cfg(feature = "digest")]
+macro_rules! impl_digest {
+    ($hasher:ty, $output_size:ty) => {
+        impl digest::HashMarker for $hasher {}
+
+        impl digest::OutputSizeUser for $hasher {
+            type OutputSize = $output_size;
+        }
+
+        impl digest::Update for $hasher {
+            fn update(&mut self, data: &[u8]) {
+                <$hasher>::update(self, data);
+            }
+        }
+
+        impl digest::FixedOutput for $hasher {
+            fn finalize_into(self, out: &mut digest::Output<Self>) {
+                out.copy_from_slice(&<$hasher>::finalize(self));
+            }
+        }
+
+        impl digest::Reset for $hasher {
+            fn reset(&mut self) {
+                *self = Self::new();
+            }
+        }
+
+        impl digest::FixedOutputReset for $hasher {
+            fn finalize_into_reset(&mut self, out: &mut digest::Output<Self>) {
+                out.copy_from_slice(&core::mem::take(self).finalize());
+            }
+        }
+    };
+}
+
+#[cfg(feature = "digest")]
+pub(crate) use impl_digest;
@@ -1,3 +1,5 @@
+use sha2::{Digest, Sha256};
+
 use crate::syscall_verify_sp1_proof;
 
 /// Verifies the next proof in the proof input stream given a verification key digest and public
@@ -9,3 +11,21 @@ pub fn verify_sp1_proof(vk_digest: &[u32; 8], pv_digest: &[u8; 32]) {
         syscall_verify_sp1_proof(vk_digest, pv_digest);
     }
 }
+
+/// Verifies the next proof written to the input with `write_deferred_proof` on the host, and
+/// returns the digest of its verification key and its public values.
+///
+/// Reads the verification key digest and the public values the host wrote alongside the proof,
+/// so that verifying a proof recursively doesn't require committing to them separately. If the
+/// proof is invalid, the function will panic.
+///
+/// ### Examples
+/// ```ignore
+/// let (vkey, public_values) = sp1_zkvm::lib::verify_deferred();
+/// ```
+pub fn verify_deferred() -> ([u32; 8], Vec<u8>) {
+    let (vk_digest, public_values): ([u32; 8], Vec<u8>) = crate::io::read();
+    let pv_digest: [u8; 32] = Sha256::digest(&public_values).into();
+    verify_sp1_proof(&vk_digest, &pv_digest);
+    (vk_digest, public_values)
+}